@@ -0,0 +1,406 @@
+//! `cupcake assess` - apply write policies to changes made outside an agent
+//!
+//! Each changed file becomes the event its harness sends before the agent
+//! writes that file, so CI can hold human pull requests to the rules agents
+//! follow. For diffs and patches the event content is the lines the change
+//! adds; for a directory it is each file's full content. Nothing is written.
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tabled::{settings::Style, Table, Tabled};
+use tracing::debug;
+
+use cupcake_core::engine::decision::{DecisionKind, DecisionOutput, FinalDecision};
+use cupcake_core::engine::{self, Engine};
+use cupcake_core::harness::types::HarnessType;
+
+use crate::{report, synthetic_event};
+
+/// Files larger than this are skipped when assessing a directory
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Directories never assessed
+const SKIPPED_DIRS: &[&str] = &[".git", ".cupcake", "node_modules", "target"];
+
+/// What to assess
+pub enum ChangeSource {
+    /// Output of `git diff <range>`
+    GitDiff(String),
+    /// A unified diff file, `-` for stdin
+    Patch(PathBuf),
+    /// Every text file below a directory
+    Dir(PathBuf),
+}
+
+impl ChangeSource {
+    pub fn describe(&self) -> String {
+        match self {
+            ChangeSource::GitDiff(range) => format!("git diff {range}"),
+            ChangeSource::Patch(path) => format!("patch {}", path.display()),
+            ChangeSource::Dir(path) => format!("directory {}", path.display()),
+        }
+    }
+
+    pub fn changes(&self) -> Result<Vec<FileChange>> {
+        match self {
+            ChangeSource::GitDiff(range) => {
+                let output = std::process::Command::new("git")
+                    .args(["diff", "--no-color", "--no-ext-diff", range])
+                    .output()
+                    .context("Failed to execute git")?;
+                if !output.status.success() {
+                    bail!(
+                        "git diff {range} failed: {}",
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    );
+                }
+                Ok(parse_unified_diff(&String::from_utf8_lossy(&output.stdout)))
+            }
+            ChangeSource::Patch(path) if path.as_os_str() == "-" => {
+                let mut patch = String::new();
+                std::io::Read::read_to_string(&mut std::io::stdin(), &mut patch)
+                    .context("Failed to read patch from stdin")?;
+                Ok(parse_unified_diff(&patch))
+            }
+            ChangeSource::Patch(path) => {
+                let patch = fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read patch {}", path.display()))?;
+                Ok(parse_unified_diff(&patch))
+            }
+            ChangeSource::Dir(dir) => {
+                let mut changes = Vec::new();
+                collect_dir(dir, &mut changes)?;
+                changes.sort_by(|a, b| a.path.cmp(&b.path));
+                Ok(changes)
+            }
+        }
+    }
+}
+
+/// A file and the content to assess
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    pub path: String,
+    pub content: String,
+}
+
+/// Decision for one file
+#[derive(Debug, Serialize)]
+struct FileAssessment {
+    path: String,
+    decision: DecisionOutput,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Summary {
+    files: usize,
+    blocked: usize,
+    asks: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct AssessReport {
+    source: String,
+    summary: Summary,
+    files: Vec<FileAssessment>,
+}
+
+#[derive(Tabled)]
+struct AssessRow {
+    #[tabled(rename = "File")]
+    path: String,
+    #[tabled(rename = "Decision")]
+    decision: String,
+    #[tabled(rename = "Reason")]
+    reason: String,
+}
+
+/// Assess every change and print the report
+///
+/// Exits non-zero when any change would be halted, denied or blocked.
+pub async fn run(
+    policy_dir: PathBuf,
+    source: ChangeSource,
+    json: bool,
+    sarif: Option<PathBuf>,
+    engine_config: engine::EngineConfig,
+) -> Result<()> {
+    let harness = engine_config.harness;
+    let changes = source.changes()?;
+
+    let engine = match Engine::new_with_config(&policy_dir, engine_config).await {
+        Ok(engine) => engine,
+        Err(e) => {
+            crate::print_engine_init_failure(&e);
+            std::process::exit(1);
+        }
+    };
+
+    let cwd = std::env::current_dir().context("Failed to get current directory")?;
+    let mut summary = Summary::default();
+    let mut files = Vec::with_capacity(changes.len());
+    for change in changes {
+        let decision = assess_change(&engine, harness, &cwd, &change).await?;
+
+        summary.files += 1;
+        if decision.is_halt() || decision.is_blocking() {
+            summary.blocked += 1;
+        } else if matches!(decision, FinalDecision::Ask { .. }) {
+            summary.asks += 1;
+        }
+        files.push(FileAssessment {
+            path: change.path,
+            decision: DecisionOutput::from(&decision),
+        });
+    }
+
+    let report = AssessReport {
+        source: source.describe(),
+        summary,
+        files,
+    };
+    if let Some(path) = &sarif {
+        let results: Vec<_> = report
+            .files
+            .iter()
+            .filter_map(|file| report::decision_result(&file.decision, Some(file.path.clone())))
+            .collect();
+        report::write_sarif(path, &results)?;
+    }
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+
+    if report.summary.blocked > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Evaluate the write event for one change
+pub async fn assess_change(
+    engine: &Engine,
+    harness: HarnessType,
+    cwd: &Path,
+    change: &FileChange,
+) -> Result<FinalDecision> {
+    let file_path = cwd.join(&change.path);
+    let event = synthetic_event::build_write_event(
+        harness,
+        &file_path.to_string_lossy(),
+        &change.content,
+        &cwd.to_string_lossy(),
+    );
+    debug!("Assessing {}", change.path);
+    engine
+        .evaluate(&event, None)
+        .await
+        .with_context(|| format!("Failed to evaluate {}", change.path))
+}
+
+fn print_report(report: &AssessReport) {
+    println!(
+        "Assessed {} file(s) from {}",
+        report.summary.files, report.source
+    );
+
+    let rows: Vec<AssessRow> = report
+        .files
+        .iter()
+        .filter(|file| file.decision.decision != DecisionKind::Allow)
+        .map(|file| AssessRow {
+            path: file.path.clone(),
+            decision: format!("{:?}", file.decision.decision),
+            reason: file.decision.reason.clone().unwrap_or_default(),
+        })
+        .collect();
+    if rows.is_empty() {
+        println!("\nNo policy objected to these changes.");
+        return;
+    }
+
+    let mut table = Table::new(rows);
+    table.with(Style::rounded());
+    println!("\n{table}");
+    println!(
+        "\n{} blocked, {} need approval",
+        report.summary.blocked, report.summary.asks
+    );
+}
+
+/// Files a unified diff leaves in place, with the lines it adds to each
+///
+/// Deleted and binary files are skipped.
+pub fn parse_unified_diff(diff: &str) -> Vec<FileChange> {
+    let mut changes: Vec<FileChange> = Vec::new();
+    let mut current: Option<FileChange> = None;
+    // Lines left in the current hunk, old and new side
+    let (mut old_left, mut new_left) = (0usize, 0usize);
+
+    for line in diff.lines() {
+        if old_left > 0 || new_left > 0 {
+            match line.as_bytes().first() {
+                Some(b'+') => {
+                    new_left = new_left.saturating_sub(1);
+                    if let Some(change) = current.as_mut() {
+                        change.content.push_str(&line[1..]);
+                        change.content.push('\n');
+                    }
+                }
+                Some(b'-') => old_left = old_left.saturating_sub(1),
+                Some(b'\\') => {}
+                _ => {
+                    old_left = old_left.saturating_sub(1);
+                    new_left = new_left.saturating_sub(1);
+                }
+            }
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            changes.extend(current.take());
+            let path = path.split('\t').next().unwrap_or(path).trim();
+            if path != "/dev/null" {
+                let path = path.strip_prefix("b/").unwrap_or(path);
+                current = Some(FileChange {
+                    path: path.to_string(),
+                    content: String::new(),
+                });
+            }
+        } else if let Some(header) = line.strip_prefix("@@ ") {
+            (old_left, new_left) = hunk_lengths(header);
+        } else if line.starts_with("diff ") {
+            changes.extend(current.take());
+        }
+    }
+    changes.extend(current);
+    changes
+}
+
+/// Line counts from a hunk header such as `-1,2 +1,3 @@`
+fn hunk_lengths(header: &str) -> (usize, usize) {
+    let length = |prefix: char| {
+        header
+            .split_whitespace()
+            .find_map(|range| range.strip_prefix(prefix))
+            .map(|range| match range.split_once(',') {
+                Some((_, count)) => count.parse().unwrap_or(0),
+                None => 1,
+            })
+            .unwrap_or(0)
+    };
+    (length('-'), length('+'))
+}
+
+fn collect_dir(dir: &Path, changes: &mut Vec<FileChange>) -> Result<()> {
+    collect_below(dir, dir, changes)
+}
+
+fn collect_below(root: &Path, dir: &Path, changes: &mut Vec<FileChange>) -> Result<()> {
+    let entries =
+        fs::read_dir(dir).with_context(|| format!("Failed to read directory {}", dir.display()))?;
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            let skipped = SKIPPED_DIRS
+                .iter()
+                .any(|name| entry.file_name() == std::ffi::OsStr::new(name));
+            if !skipped {
+                collect_below(root, &path, changes)?;
+            }
+        } else if file_type.is_file() && entry.metadata()?.len() <= MAX_FILE_BYTES {
+            // Binary files aren't text an agent would write
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            let display_path = if root == Path::new(".") {
+                relative.to_path_buf()
+            } else {
+                root.join(relative)
+            };
+            changes.push(FileChange {
+                path: display_path.to_string_lossy().replace('\\', "/"),
+                content,
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_unified_diff() {
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,2 +1,3 @@
+ fn a() {}
+-fn b() {}
++fn b() { todo!() }
++const KEY: &str = \"AKIA0000\";
+diff --git a/old.txt b/old.txt
+deleted file mode 100644
+--- a/old.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-gone
+diff --git a/new.md b/new.md
+new file mode 100644
+--- /dev/null
++++ b/new.md
+@@ -0,0 +1 @@
+++++ not a header
+diff --git a/logo.png b/logo.png
+Binary files a/logo.png and b/logo.png differ
+--- a/plain.txt
++++ b/plain.txt
+@@ -1 +1 @@
+--- removed
++kept
+";
+        let changes = parse_unified_diff(diff);
+        assert_eq!(
+            changes,
+            vec![
+                FileChange {
+                    path: "src/lib.rs".to_string(),
+                    content: "fn b() { todo!() }\nconst KEY: &str = \"AKIA0000\";\n".to_string(),
+                },
+                FileChange {
+                    path: "new.md".to_string(),
+                    content: "+++ not a header\n".to_string(),
+                },
+                FileChange {
+                    path: "plain.txt".to_string(),
+                    content: "kept\n".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_dir_skips_vcs_and_binary_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::create_dir_all(dir.path().join(".git")).unwrap();
+        fs::write(dir.path().join("src/main.rs"), "fn main() {}").unwrap();
+        fs::write(dir.path().join(".git/HEAD"), "ref: refs/heads/main").unwrap();
+        fs::write(dir.path().join("blob.bin"), [0xff, 0xfe, 0x00]).unwrap();
+
+        let changes = ChangeSource::Dir(dir.path().to_path_buf())
+            .changes()
+            .unwrap();
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].path.ends_with("src/main.rs"));
+        assert_eq!(changes[0].content, "fn main() {}");
+    }
+}
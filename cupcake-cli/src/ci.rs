@@ -0,0 +1,421 @@
+//! `cupcake ci` - policy enforcement as a CI gate
+//!
+//! Evaluates a corpus of recorded events, the changes from `cupcake assess`,
+//! or both, then writes SARIF and JUnit reports and fails the job when too
+//! many cases reach the `--fail-on` threshold.
+//!
+//! A corpus file is either a bare harness event or a case pinning the decision
+//! it must produce, which turns the corpus into a regression suite:
+//!
+//! ```json
+//! {"event": {"hook_event_name": "PreToolUse", ...}, "expect": "Deny"}
+//! ```
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tabled::{settings::Style, Table, Tabled};
+use tracing::debug;
+
+use cupcake_core::engine::decision::{DecisionKind, DecisionOutput};
+use cupcake_core::engine::{self, Engine};
+
+use crate::assess::{self, ChangeSource};
+use crate::report::{self, SarifLevel, SarifResult, TestCase};
+
+/// Least severe decision that fails a case
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FailOn {
+    /// Only halts
+    Halt,
+    /// Halts, denials and blocks
+    Deny,
+    /// Anything that stops the action, including asks
+    Ask,
+    /// Never fail on a decision; expectations still apply
+    Never,
+}
+
+impl FailOn {
+    fn fails(self, decision: DecisionKind) -> bool {
+        let threshold = match self {
+            FailOn::Halt => 3,
+            FailOn::Deny => 2,
+            FailOn::Ask => 1,
+            FailOn::Never => return false,
+        };
+        severity(decision) >= threshold
+    }
+}
+
+fn severity(decision: DecisionKind) -> u8 {
+    match decision {
+        DecisionKind::Halt => 3,
+        DecisionKind::Deny | DecisionKind::Block => 2,
+        DecisionKind::Ask => 1,
+        DecisionKind::Modify | DecisionKind::FilterOutput | DecisionKind::Allow => 0,
+    }
+}
+
+/// What to evaluate and how to judge it
+pub struct CiOptions {
+    /// Directory of event JSON files
+    pub events: Option<PathBuf>,
+    pub changes: Option<ChangeSource>,
+    pub fail_on: FailOn,
+    /// Failing cases tolerated before the job fails
+    pub max_failures: usize,
+    pub sarif: Option<PathBuf>,
+    pub junit: Option<PathBuf>,
+    pub json: bool,
+}
+
+/// A corpus file pinning the decision its event must produce
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExpectedCase {
+    event: Value,
+    expect: DecisionKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum CaseKind {
+    Event,
+    Change,
+}
+
+/// Outcome for one event or changed file
+#[derive(Debug, Serialize)]
+struct CaseResult {
+    name: String,
+    kind: CaseKind,
+    decision: DecisionOutput,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected: Option<DecisionKind>,
+    failed: bool,
+}
+
+impl CaseResult {
+    fn new(
+        name: String,
+        kind: CaseKind,
+        decision: DecisionOutput,
+        expected: Option<DecisionKind>,
+        fail_on: FailOn,
+    ) -> Self {
+        // An expectation replaces the threshold for its case
+        let failed = match expected {
+            Some(expected) => decision.decision != expected,
+            None => fail_on.fails(decision.decision),
+        };
+        Self {
+            name,
+            kind,
+            decision,
+            expected,
+            failed,
+        }
+    }
+
+    fn failure_message(&self) -> String {
+        let reason = self.decision.reason.as_deref().unwrap_or("no reason given");
+        match self.expected {
+            Some(expected) => format!(
+                "Expected {expected:?}, got {:?}: {reason}",
+                self.decision.decision
+            ),
+            None => format!("{:?}: {reason}", self.decision.decision),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Summary {
+    cases: usize,
+    failed: usize,
+    fail_on: FailOn,
+    max_failures: usize,
+    passed: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct CiReport {
+    summary: Summary,
+    cases: Vec<CaseResult>,
+}
+
+#[derive(Tabled)]
+struct FailureRow {
+    #[tabled(rename = "Case")]
+    name: String,
+    #[tabled(rename = "Failure")]
+    failure: String,
+}
+
+/// Evaluate everything, write the reports and set the exit code
+///
+/// Exits non-zero when more than `max_failures` cases fail.
+pub async fn run(
+    policy_dir: PathBuf,
+    options: CiOptions,
+    engine_config: engine::EngineConfig,
+) -> Result<()> {
+    let harness = engine_config.harness;
+    let corpus = match &options.events {
+        Some(dir) => load_corpus(dir)?,
+        None => Vec::new(),
+    };
+    let changes = match &options.changes {
+        Some(source) => source.changes()?,
+        None => Vec::new(),
+    };
+
+    let engine = match Engine::new_with_config(&policy_dir, engine_config).await {
+        Ok(engine) => engine,
+        Err(e) => {
+            crate::print_engine_init_failure(&e);
+            std::process::exit(1);
+        }
+    };
+
+    let mut cases = Vec::with_capacity(corpus.len() + changes.len());
+    for (path, event, expected) in corpus {
+        let name = path.to_string_lossy().replace('\\', "/");
+        debug!("Evaluating {name}");
+        let decision = engine
+            .evaluate(&event, None)
+            .await
+            .with_context(|| format!("Failed to evaluate {name}"))?;
+        cases.push(CaseResult::new(
+            name,
+            CaseKind::Event,
+            DecisionOutput::from(&decision),
+            expected,
+            options.fail_on,
+        ));
+    }
+
+    let cwd = std::env::current_dir().context("Failed to get current directory")?;
+    for change in changes {
+        let decision = assess::assess_change(&engine, harness, &cwd, &change).await?;
+        cases.push(CaseResult::new(
+            change.path,
+            CaseKind::Change,
+            DecisionOutput::from(&decision),
+            None,
+            options.fail_on,
+        ));
+    }
+
+    let failed = cases.iter().filter(|case| case.failed).count();
+    let report = CiReport {
+        summary: Summary {
+            cases: cases.len(),
+            failed,
+            fail_on: options.fail_on,
+            max_failures: options.max_failures,
+            passed: failed <= options.max_failures,
+        },
+        cases,
+    };
+
+    if let Some(path) = &options.sarif {
+        report::write_sarif(path, &sarif_results(&report.cases))?;
+    }
+    if let Some(path) = &options.junit {
+        fs::write(path, report::junit("cupcake", &test_cases(&report.cases)))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    if options.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+
+    if !report.summary.passed {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn print_report(report: &CiReport) {
+    let summary = &report.summary;
+    let rows: Vec<FailureRow> = report
+        .cases
+        .iter()
+        .filter(|case| case.failed)
+        .map(|case| FailureRow {
+            name: case.name.clone(),
+            failure: case.failure_message(),
+        })
+        .collect();
+    if !rows.is_empty() {
+        let mut table = Table::new(rows);
+        table.with(Style::rounded());
+        println!("{table}\n");
+    }
+    println!(
+        "{} case(s), {} failed (fail on: {:?}, max failures: {}) - {}",
+        summary.cases,
+        summary.failed,
+        summary.fail_on,
+        summary.max_failures,
+        if summary.passed { "PASSED" } else { "FAILED" }
+    );
+}
+
+/// Event files below `dir`, sorted, with their expected decisions
+fn load_corpus(dir: &Path) -> Result<Vec<(PathBuf, Value, Option<DecisionKind>)>> {
+    let mut paths = Vec::new();
+    collect_json_files(dir, &mut paths)?;
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let value: Value = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            let (event, expected) = if value.get("expect").is_some() {
+                let case: ExpectedCase = serde_json::from_value(value)
+                    .with_context(|| format!("Invalid expected case in {}", path.display()))?;
+                (case.event, Some(case.expect))
+            } else {
+                (value, None)
+            };
+            Ok((path, event, expected))
+        })
+        .collect()
+}
+
+fn collect_json_files(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<()> {
+    let entries =
+        fs::read_dir(dir).with_context(|| format!("Failed to read directory {}", dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_json_files(&path, paths)?;
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Findings for code scanning: every decision other than Allow, and every
+/// missed expectation
+fn sarif_results(cases: &[CaseResult]) -> Vec<SarifResult> {
+    cases
+        .iter()
+        .filter_map(|case| {
+            if case.expected.is_some() && case.failed {
+                Some(SarifResult {
+                    rule_id: "cupcake/unexpected-decision".to_string(),
+                    rule_description: "An event did not produce its expected decision".to_string(),
+                    level: SarifLevel::Error,
+                    message: case.failure_message(),
+                    path: Some(case.name.clone()),
+                    line: None,
+                })
+            } else {
+                report::decision_result(&case.decision, Some(case.name.clone()))
+            }
+        })
+        .collect()
+}
+
+fn test_cases(cases: &[CaseResult]) -> Vec<TestCase> {
+    cases
+        .iter()
+        .map(|case| TestCase {
+            name: case.name.clone(),
+            classname: match case.kind {
+                CaseKind::Event => "cupcake.events".to_string(),
+                CaseKind::Change => "cupcake.changes".to_string(),
+            },
+            failure: case.failed.then(|| case.failure_message()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decision(kind: DecisionKind) -> DecisionOutput {
+        DecisionOutput {
+            decision: kind,
+            reason: Some("because".to_string()),
+            ..DecisionOutput::from(&engine::decision::FinalDecision::Allow { context: vec![] })
+        }
+    }
+
+    #[test]
+    fn test_thresholds_and_expectations() {
+        assert!(FailOn::Deny.fails(DecisionKind::Block));
+        assert!(!FailOn::Deny.fails(DecisionKind::Ask));
+        assert!(FailOn::Ask.fails(DecisionKind::Halt));
+        assert!(!FailOn::Halt.fails(DecisionKind::Deny));
+        assert!(!FailOn::Never.fails(DecisionKind::Halt));
+
+        // An expected denial passes even though denials fail the threshold
+        let case = CaseResult::new(
+            "deny.json".to_string(),
+            CaseKind::Event,
+            decision(DecisionKind::Deny),
+            Some(DecisionKind::Deny),
+            FailOn::Deny,
+        );
+        assert!(!case.failed);
+
+        let case = CaseResult::new(
+            "allow.json".to_string(),
+            CaseKind::Event,
+            decision(DecisionKind::Allow),
+            Some(DecisionKind::Deny),
+            FailOn::Never,
+        );
+        assert!(case.failed);
+        assert_eq!(case.failure_message(), "Expected Deny, got Allow: because");
+
+        let results = sarif_results(&[case]);
+        assert_eq!(results[0].rule_id, "cupcake/unexpected-decision");
+    }
+
+    #[test]
+    fn test_load_corpus() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("nested")).unwrap();
+        fs::write(
+            dir.path().join("a.json"),
+            r#"{"hook_event_name": "PreToolUse"}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("nested/b.json"),
+            r#"{"event": {"hook_event_name": "Stop"}, "expect": "Allow"}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("README.md"), "not an event").unwrap();
+
+        let corpus = load_corpus(dir.path()).unwrap();
+        assert_eq!(corpus.len(), 2);
+        assert_eq!(corpus[0].2, None);
+        assert_eq!(corpus[1].1["hook_event_name"], "Stop");
+        assert_eq!(corpus[1].2, Some(DecisionKind::Allow));
+
+        fs::write(
+            dir.path().join("c.json"),
+            r#"{"event": {}, "expect": "Maybe"}"#,
+        )
+        .unwrap();
+        assert!(load_corpus(dir.path()).is_err());
+    }
+}
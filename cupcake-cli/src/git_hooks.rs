@@ -0,0 +1,122 @@
+//! Git hooks that hold manual edits to the project's policies
+//!
+//! `cupcake init --git-hooks` installs a pre-commit hook that assesses the
+//! staged changes and a pre-push hook that assesses the commits being pushed,
+//! both with `cupcake assess` and the project's own `.cupcake/` policies.
+
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// First comment line of every hook Cupcake writes; other hooks are left alone
+pub const HOOK_MARKER: &str = "# Installed by cupcake init --git-hooks";
+
+/// Outcome for one hook
+#[derive(Debug, PartialEq, Eq)]
+pub enum Installed {
+    Written(PathBuf),
+    /// A hook not written by Cupcake is already in place
+    Kept(PathBuf),
+}
+
+/// Install the pre-commit and pre-push hooks for `harness`
+///
+/// Must run from the directory containing `.cupcake/`, inside a git work tree.
+pub fn install(harness: &str) -> Result<Vec<Installed>> {
+    let hooks_dir = PathBuf::from(git(&["rev-parse", "--git-path", "hooks"])?);
+    // Hooks run from the top of the work tree, the project may be below it
+    let prefix = git(&["rev-parse", "--show-prefix"])?;
+    let policy_dir = format!("{prefix}.cupcake");
+
+    fs::create_dir_all(&hooks_dir)
+        .with_context(|| format!("Failed to create {}", hooks_dir.display()))?;
+    let hooks = [
+        ("pre-commit", pre_commit_script(harness, &policy_dir)),
+        ("pre-push", pre_push_script(harness, &policy_dir)),
+    ];
+    hooks
+        .into_iter()
+        .map(|(name, script)| write_hook(&hooks_dir.join(name), &script))
+        .collect()
+}
+
+fn write_hook(path: &Path, script: &str) -> Result<Installed> {
+    if let Ok(existing) = fs::read_to_string(path) {
+        if !existing.contains(HOOK_MARKER) {
+            return Ok(Installed::Kept(path.to_path_buf()));
+        }
+    }
+    fs::write(path, script).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+            .with_context(|| format!("Failed to make {} executable", path.display()))?;
+    }
+    Ok(Installed::Written(path.to_path_buf()))
+}
+
+/// The command a hook runs, for users merging it into an existing hook
+pub fn assess_command(harness: &str, policy_dir: &str, changes: &str) -> String {
+    format!("cupcake assess --harness {harness} --policy-dir '{policy_dir}' {changes}")
+}
+
+fn preamble() -> String {
+    format!(
+        "#!/bin/sh\n{HOOK_MARKER} - rerun it to update, delete this file to remove\n\
+         \n\
+         if ! command -v cupcake >/dev/null 2>&1; then\n\
+         \x20   echo \"cupcake: not installed - cannot check changes against the project's policies\" >&2\n\
+         \x20   exit 1\n\
+         fi\n"
+    )
+}
+
+fn pre_commit_script(harness: &str, policy_dir: &str) -> String {
+    format!(
+        "{}\n\
+         # Assess the staged changes\n\
+         git diff --cached --no-color --no-ext-diff | {}\n",
+        preamble(),
+        assess_command(harness, policy_dir, "--patch -")
+    )
+}
+
+fn pre_push_script(harness: &str, policy_dir: &str) -> String {
+    format!(
+        "{}\n\
+         # Assess the commits being pushed; new branches from where they left the remote\n\
+         zero=$(git hash-object --stdin </dev/null | tr '0-9a-f' '0')\n\
+         empty_tree=$(git hash-object -t tree /dev/null)\n\
+         status=0\n\
+         while read -r local_ref local_sha remote_ref remote_sha; do\n\
+         \x20   [ \"$local_sha\" = \"$zero\" ] && continue\n\
+         \x20   if [ \"$remote_sha\" = \"$zero\" ]; then\n\
+         \x20       base=$(git merge-base \"$local_sha\" \"refs/remotes/$1/HEAD\" 2>/dev/null || echo \"$empty_tree\")\n\
+         \x20   else\n\
+         \x20       base=$remote_sha\n\
+         \x20   fi\n\
+         \x20   {} </dev/null || status=1\n\
+         done\n\
+         exit $status\n",
+        preamble(),
+        assess_command(harness, policy_dir, "--diff \"$base..$local_sha\"")
+    )
+}
+
+fn git(args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .context("Failed to execute git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -37,6 +37,24 @@ pub struct FactoryHarness;
 /// OpenCode harness implementation
 pub struct OpenCodeHarness;
 
+/// Cline harness implementation
+///
+/// Cline runs one executable per hook from its hooks directory rather than
+/// reading hook settings, so it is installed as scripts instead of JSON.
+pub struct ClineHarness;
+
+/// Aider harness implementation
+///
+/// Aider has no hooks, so it is installed as a launcher script that starts
+/// Aider with its confirmation points routed through `cupcake eval`.
+pub struct AiderHarness;
+
+/// Goose harness implementation
+pub struct GooseHarness;
+
+/// The Aider launcher, shipped inside the binary so init works offline
+const AIDER_LAUNCHER: &str = include_str!("../../cupcake-plugins/aider/cupcake_aider.py");
+
 /// GitHub repository for downloading plugins
 const GITHUB_REPO: &str = "eqtylab/cupcake";
 
@@ -45,6 +63,36 @@ fn get_cupcake_version() -> &'static str {
     env!("CARGO_PKG_VERSION")
 }
 
+/// Absolute policy directory for global hooks
+///
+/// On Windows `canonicalize` returns a verbatim `\\?\C:\...` path, which
+/// hook runners pass through Git Bash or PowerShell and neither resolves, so
+/// the prefix is dropped and separators become `/`, which both shells and
+/// `cupcake eval` accept.
+fn absolute_policy_dir(policy_dir: &Path) -> String {
+    let abs_path = fs::canonicalize(policy_dir).unwrap_or_else(|_| policy_dir.to_path_buf());
+    normalize_hook_path(&abs_path.display().to_string(), cfg!(windows))
+}
+
+fn normalize_hook_path(path: &str, windows: bool) -> String {
+    let path = path.strip_prefix(r"\\?\").unwrap_or(path);
+    if windows {
+        path.replace('\\', "/")
+    } else {
+        path.to_string()
+    }
+}
+
+/// Quote a path for a hook command line if it contains whitespace
+/// (`C:/Users/Jane Doe/...`, `~/Library/Application Support/...`)
+fn quote_hook_arg(path: &str) -> String {
+    if path.contains(char::is_whitespace) {
+        format!("\"{path}\"")
+    } else {
+        path.to_string()
+    }
+}
+
 impl HarnessConfig for ClaudeHarness {
     fn name(&self) -> &str {
         "Claude Code"
@@ -65,9 +113,7 @@ impl HarnessConfig for ClaudeHarness {
         // Determine the policy path to use in commands
         let policy_path = if global {
             // Global config - use absolute path
-            let abs_path =
-                fs::canonicalize(policy_dir).unwrap_or_else(|_| policy_dir.to_path_buf());
-            abs_path.display().to_string()
+            quote_hook_arg(&absolute_policy_dir(policy_dir))
         } else {
             // Project config - use environment variable for portability
             "$CLAUDE_PROJECT_DIR/.cupcake".to_string()
@@ -136,9 +182,7 @@ impl HarnessConfig for CursorHarness {
         // Determine the policy path to use in commands
         let policy_path = if global {
             // Global config - use absolute path
-            let abs_path =
-                fs::canonicalize(policy_dir).unwrap_or_else(|_| policy_dir.to_path_buf());
-            abs_path.display().to_string()
+            quote_hook_arg(&absolute_policy_dir(policy_dir))
         } else {
             // Project config - use relative path from workspace root
             ".cupcake".to_string()
@@ -198,9 +242,7 @@ impl HarnessConfig for FactoryHarness {
         // Determine the policy path to use in commands
         let policy_path = if global {
             // Global config - use absolute path
-            let abs_path =
-                fs::canonicalize(policy_dir).unwrap_or_else(|_| policy_dir.to_path_buf());
-            abs_path.display().to_string()
+            quote_hook_arg(&absolute_policy_dir(policy_dir))
         } else {
             // Project config - use environment variable for portability
             "\"$FACTORY_PROJECT_DIR\"/.cupcake".to_string()
@@ -256,6 +298,73 @@ impl HarnessConfig for FactoryHarness {
     }
 }
 
+impl HarnessConfig for GooseHarness {
+    fn name(&self) -> &str {
+        "Goose"
+    }
+
+    fn settings_path(&self, global: bool) -> PathBuf {
+        // Goose keeps its configuration in ~/.config/goose on every platform
+        if global {
+            dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("~"))
+                .join(".config")
+                .join("goose")
+                .join("hooks.json")
+        } else {
+            Path::new(".goose").join("hooks.json")
+        }
+    }
+
+    fn generate_hooks(&self, policy_dir: &Path, global: bool) -> Result<Value> {
+        // Determine the policy path to use in commands
+        let policy_path = if global {
+            // Global config - use absolute path
+            quote_hook_arg(&absolute_policy_dir(policy_dir))
+        } else {
+            // Project config - Goose runs hooks from the session's working directory
+            ".cupcake".to_string()
+        };
+
+        // Matchers use Goose's tool names: <extension>__<tool>
+        Ok(json!({
+            "hooks": {
+                "PreToolUse": [{
+                    "matcher": "*",
+                    "hooks": [{
+                        "type": "command",
+                        "command": format!("cupcake eval --harness goose --policy-dir {}", policy_path)
+                    }]
+                }],
+                "PostToolUse": [{
+                    "matcher": "developer__text_editor",
+                    "hooks": [{
+                        "type": "command",
+                        "command": format!("cupcake eval --harness goose --policy-dir {}", policy_path)
+                    }]
+                }],
+                "UserPromptSubmit": [{
+                    "hooks": [{
+                        "type": "command",
+                        "command": format!("cupcake eval --harness goose --policy-dir {}", policy_path)
+                    }]
+                }],
+                "SessionStart": [{
+                    "hooks": [{
+                        "type": "command",
+                        "command": format!("cupcake eval --harness goose --policy-dir {}", policy_path)
+                    }]
+                }]
+            }
+        }))
+    }
+
+    fn merge_settings(&self, mut existing: Value, new_hooks: Value) -> Result<Value> {
+        merge_hooks(&mut existing, new_hooks)?;
+        Ok(existing)
+    }
+}
+
 impl OpenCodeHarness {
     /// Download the OpenCode plugin from GitHub releases
     ///
@@ -337,6 +446,37 @@ impl OpenCodeHarness {
         Ok(())
     }
 
+    /// Write the plugin config to .cupcake/opencode.json
+    ///
+    /// The plugin reads it from the project it runs in; an existing file is
+    /// left alone so user settings survive re-running init. Returns whether
+    /// the file was written.
+    pub fn write_plugin_config(policy_dir: &Path) -> Result<bool> {
+        let config_path = policy_dir.join("opencode.json");
+        if config_path.exists() {
+            return Ok(false);
+        }
+        fs::create_dir_all(policy_dir)?;
+        fs::write(
+            &config_path,
+            serde_json::to_string_pretty(&Self::default_plugin_config())?,
+        )
+        .with_context(|| format!("Failed to write {}", config_path.display()))?;
+        Ok(true)
+    }
+
+    /// Plugin settings written by init, matching the plugin's own defaults
+    fn default_plugin_config() -> Value {
+        json!({
+            "enabled": true,
+            "cupcakePath": "cupcake",
+            "logLevel": "warn",
+            "timeoutMs": 5000,
+            "failMode": "closed",
+            "showToasts": true
+        })
+    }
+
     /// Print manual installation instructions as fallback
     pub fn print_manual_instructions() {
         eprintln!();
@@ -466,6 +606,531 @@ fn contains_matcher(array: &[Value], matcher: &Value) -> bool {
     })
 }
 
+/// One difference between the installed hooks and what this version expects
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Drift {
+    /// Hook event the problem is on (`*` for problems spanning events)
+    pub event: String,
+    pub problem: String,
+    /// Whether `--repair` can fix it
+    pub repairable: bool,
+}
+
+/// Whether a hook command runs `cupcake eval`
+fn is_cupcake_eval(command: &str) -> bool {
+    let mut words = command.split_whitespace();
+    let program = words.next().unwrap_or_default();
+    let program = Path::new(program.trim_matches('"'))
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    program == "cupcake" && words.next() == Some("eval")
+}
+
+/// The hook commands in one event entry - Claude and Factory nest them under
+/// `hooks`, Cursor puts the command on the entry itself
+fn entry_commands(entry: &Value) -> Vec<&str> {
+    match entry.get("hooks").and_then(|h| h.as_array()) {
+        Some(hooks) => hooks
+            .iter()
+            .filter_map(|h| h.get("command").and_then(|c| c.as_str()))
+            .collect(),
+        None => entry
+            .get("command")
+            .and_then(|c| c.as_str())
+            .into_iter()
+            .collect(),
+    }
+}
+
+fn entry_matches(existing: &Value, expected: &Value) -> bool {
+    if expected.get("hooks").is_some() {
+        contains_matcher(std::slice::from_ref(existing), expected)
+    } else {
+        existing.get("command") == expected.get("command")
+    }
+}
+
+/// Compare installed settings against the hooks `init` would write
+pub fn detect_drift(installed: &Value, expected: &Value) -> Vec<Drift> {
+    let mut drift = Vec::new();
+    let empty = Vec::new();
+    let Some(expected_hooks) = expected["hooks"].as_object() else {
+        return drift;
+    };
+
+    for (event, expected_entries) in expected_hooks {
+        let installed_entries = installed["hooks"][event].as_array().unwrap_or(&empty);
+        for expected_entry in expected_entries.as_array().unwrap_or(&empty) {
+            if installed_entries
+                .iter()
+                .any(|entry| entry_matches(entry, expected_entry))
+            {
+                continue;
+            }
+
+            let expected_command = entry_commands(expected_entry).join(" ");
+            let outdated: Vec<&str> = installed_entries
+                .iter()
+                .flat_map(entry_commands)
+                .filter(|c| is_cupcake_eval(c))
+                .collect();
+            let problem = if outdated.is_empty() {
+                "not wired to Cupcake".to_string()
+            } else if outdated.contains(&expected_command.as_str()) {
+                format!(
+                    "matcher is {} (expected {})",
+                    installed_entries
+                        .iter()
+                        .find(|e| entry_commands(e).contains(&expected_command.as_str()))
+                        .and_then(|e| e.get("matcher"))
+                        .unwrap_or(&Value::Null),
+                    expected_entry.get("matcher").unwrap_or(&Value::Null)
+                )
+            } else {
+                format!(
+                    "runs `{}` (expected `{expected_command}`)",
+                    outdated.join("`, `")
+                )
+            };
+            drift.push(Drift {
+                event: event.clone(),
+                problem,
+                repairable: true,
+            });
+        }
+    }
+
+    drift.extend(binary_drift(installed));
+    drift
+}
+
+/// Check that the binary the hooks invoke exists and is this one
+fn binary_drift(installed: &Value) -> Vec<Drift> {
+    let mut programs: Vec<&str> = installed["hooks"]
+        .as_object()
+        .into_iter()
+        .flat_map(|hooks| hooks.values())
+        .filter_map(|entries| entries.as_array())
+        .flatten()
+        .flat_map(entry_commands)
+        .filter(|c| is_cupcake_eval(c))
+        .filter_map(|c| c.split_whitespace().next())
+        .collect();
+    programs.sort_unstable();
+    programs.dedup();
+
+    let current = std::env::current_exe()
+        .ok()
+        .and_then(|p| fs::canonicalize(p).ok());
+    programs
+        .into_iter()
+        .filter_map(|program| {
+            let problem = match (resolve_program(program.trim_matches('"')), &current) {
+                (None, _) => format!("`{program}` is not installed or not on PATH"),
+                (Some(path), Some(current)) if path != *current => format!(
+                    "`{program}` resolves to {}, not this cupcake ({})",
+                    path.display(),
+                    current.display()
+                ),
+                _ => return None,
+            };
+            Some(Drift {
+                event: "*".to_string(),
+                problem,
+                repairable: false,
+            })
+        })
+        .collect()
+}
+
+/// Find a program the way the shell would, returning its canonical path
+fn resolve_program(program: &str) -> Option<PathBuf> {
+    let candidates: Vec<PathBuf> = if program.contains(std::path::MAIN_SEPARATOR) {
+        vec![PathBuf::from(program)]
+    } else {
+        std::env::var_os("PATH")
+            .map(|path| {
+                std::env::split_paths(&path)
+                    .flat_map(|dir| {
+                        [
+                            dir.join(program),
+                            dir.join(format!("{program}{}", std::env::consts::EXE_SUFFIX)),
+                        ]
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    candidates
+        .into_iter()
+        .find(|p| p.is_file())
+        .and_then(|p| fs::canonicalize(p).ok())
+}
+
+/// Replace outdated Cupcake hooks with the expected ones
+///
+/// Only `cupcake eval` hooks on the expected events are touched - other hooks
+/// and settings are kept as they are.
+pub fn repair_settings(mut installed: Value, expected: Value) -> Result<Value> {
+    if let (Some(hooks), Some(expected_hooks)) = (
+        installed.get_mut("hooks").and_then(|h| h.as_object_mut()),
+        expected["hooks"].as_object(),
+    ) {
+        for (event, expected_entries) in expected_hooks {
+            let Some(entries) = hooks.get_mut(event).and_then(|e| e.as_array_mut()) else {
+                continue;
+            };
+            let expected_entries = expected_entries.as_array().cloned().unwrap_or_default();
+            let is_expected =
+                |entry: &Value| expected_entries.iter().any(|e| entry_matches(entry, e));
+
+            entries.retain_mut(|entry| {
+                if is_expected(entry) {
+                    return true;
+                }
+                match entry.get_mut("hooks").and_then(|h| h.as_array_mut()) {
+                    Some(inner) => {
+                        inner.retain(|h| {
+                            !h.get("command")
+                                .and_then(|c| c.as_str())
+                                .is_some_and(is_cupcake_eval)
+                        });
+                        !inner.is_empty()
+                    }
+                    None => !entry
+                        .get("command")
+                        .and_then(|c| c.as_str())
+                        .is_some_and(is_cupcake_eval),
+                }
+            });
+        }
+    }
+
+    merge_hooks(&mut installed, expected)?;
+    Ok(installed)
+}
+
+/// Drift report for one harness
+#[derive(Debug, serde::Serialize)]
+pub struct HarnessStatus {
+    pub harness: String,
+    pub settings_path: PathBuf,
+    pub installed: bool,
+    pub drift: Vec<Drift>,
+    pub repaired: bool,
+}
+
+/// Check (and optionally repair) the installed integration for a harness
+pub async fn harness_status(
+    harness_type: &super::HarnessType,
+    policy_dir: &Path,
+    global: bool,
+    repair: bool,
+) -> Result<HarnessStatus> {
+    use super::HarnessType;
+
+    let harness: &dyn HarnessConfig = match harness_type {
+        HarnessType::Claude => &ClaudeHarness,
+        HarnessType::Cursor => &CursorHarness,
+        HarnessType::Factory => &FactoryHarness,
+        HarnessType::Goose => &GooseHarness,
+        HarnessType::OpenCode => return opencode_status(policy_dir, global, repair).await,
+        HarnessType::Cline => return cline_status(policy_dir, global, repair),
+        HarnessType::Aider => return aider_status(policy_dir, repair),
+    };
+
+    let settings_path = harness.settings_path(global);
+    let mut status = HarnessStatus {
+        harness: harness.name().to_string(),
+        settings_path: settings_path.clone(),
+        installed: settings_path.exists(),
+        drift: Vec::new(),
+        repaired: false,
+    };
+    if !status.installed {
+        return Ok(status);
+    }
+
+    let content = fs::read_to_string(&settings_path)?;
+    let installed: Value = serde_json::from_str(&content)
+        .map_err(|e| anyhow!("Invalid JSON in {}: {}", settings_path.display(), e))?;
+    let expected = harness.generate_hooks(policy_dir, global)?;
+    status.drift = detect_drift(&installed, &expected);
+
+    if repair && status.drift.iter().any(|d| d.repairable) {
+        let repaired = repair_settings(installed, expected.clone())?;
+        fs::write(&settings_path, serde_json::to_string_pretty(&repaired)?)?;
+        status.drift = detect_drift(&repaired, &expected);
+        status.repaired = true;
+    }
+
+    Ok(status)
+}
+
+/// OpenCode is wired through a plugin file rather than hook settings
+async fn opencode_status(policy_dir: &Path, global: bool, repair: bool) -> Result<HarnessStatus> {
+    let target_dir = if global {
+        dirs::config_dir().ok_or_else(|| anyhow!("Could not determine config directory"))?
+    } else {
+        policy_dir.parent().unwrap_or(Path::new(".")).to_path_buf()
+    };
+    let plugin_dir = if global {
+        target_dir.join("opencode").join("plugin")
+    } else {
+        target_dir.join(".opencode").join("plugin")
+    };
+    let plugin_path = plugin_dir.join("cupcake.js");
+
+    let mut status = HarnessStatus {
+        harness: "OpenCode".to_string(),
+        settings_path: plugin_path.clone(),
+        installed: plugin_dir.exists(),
+        drift: Vec::new(),
+        repaired: false,
+    };
+    if !status.installed {
+        return Ok(status);
+    }
+    if !plugin_path.exists() {
+        status.drift.push(Drift {
+            event: "*".to_string(),
+            problem: "Cupcake plugin is missing".to_string(),
+            repairable: true,
+        });
+        if repair {
+            OpenCodeHarness::download_plugin(&target_dir, global).await?;
+            status.drift.clear();
+            status.repaired = true;
+        }
+    }
+
+    // A config the plugin can't parse silently falls back to defaults
+    let config_path = policy_dir.join("opencode.json");
+    if let Ok(content) = fs::read_to_string(&config_path) {
+        if serde_json::from_str::<Value>(&content).is_err() {
+            status.drift.push(Drift {
+                event: "*".to_string(),
+                problem: format!("{} is not valid JSON", config_path.display()),
+                repairable: false,
+            });
+        }
+    }
+    Ok(status)
+}
+
+impl ClineHarness {
+    /// Hooks Cupcake installs, mirroring the Claude Code defaults
+    const HOOKS: &'static [&'static str] = &[
+        "PreToolUse",
+        "PostToolUse",
+        "UserPromptSubmit",
+        "TaskStart",
+        "TaskResume",
+    ];
+
+    /// Directory Cline loads hooks from
+    ///
+    /// Project hooks live in `.clinerules/hooks/`, global hooks in
+    /// `~/Documents/Cline/Rules/Hooks/`.
+    pub fn hooks_dir(policy_dir: &Path, global: bool) -> Result<PathBuf> {
+        if global {
+            Ok(dirs::document_dir()
+                .or_else(|| dirs::home_dir().map(|home| home.join("Documents")))
+                .ok_or_else(|| anyhow!("Could not determine documents directory"))?
+                .join("Cline")
+                .join("Rules")
+                .join("Hooks"))
+        } else {
+            Ok(policy_dir
+                .parent()
+                .unwrap_or(Path::new("."))
+                .join(".clinerules")
+                .join("hooks"))
+        }
+    }
+
+    /// Script for every hook - Cline passes the event on stdin
+    fn hook_script(policy_dir: &Path, global: bool) -> String {
+        let policy_path = if global {
+            // Already quoted in the script
+            absolute_policy_dir(policy_dir)
+        } else {
+            // Relative to the script so the project can move
+            "$(dirname \"$0\")/../../.cupcake".to_string()
+        };
+        format!(
+            "#!/bin/sh\n\
+             # Installed by cupcake init --harness cline\n\
+             exec cupcake eval --harness cline --policy-dir \"{policy_path}\"\n"
+        )
+    }
+
+    /// Write the hook scripts, leaving hooks that don't run Cupcake alone
+    ///
+    /// Returns the hooks that were skipped because another hook exists.
+    pub fn install_hooks(policy_dir: &Path, global: bool) -> Result<Vec<String>> {
+        let hooks_dir = Self::hooks_dir(policy_dir, global)?;
+        fs::create_dir_all(&hooks_dir)
+            .with_context(|| format!("Failed to create hooks directory: {hooks_dir:?}"))?;
+
+        let script = Self::hook_script(policy_dir, global);
+        let mut skipped = Vec::new();
+        for hook in Self::HOOKS {
+            let path = hooks_dir.join(hook);
+            if let Ok(existing) = fs::read_to_string(&path) {
+                if !runs_cupcake_eval(&existing) {
+                    skipped.push(hook.to_string());
+                    continue;
+                }
+            }
+            fs::write(&path, &script).with_context(|| format!("Failed to write hook: {path:?}"))?;
+            make_executable(&path)?;
+        }
+        Ok(skipped)
+    }
+
+    /// Problems with the installed hook scripts
+    fn drift(policy_dir: &Path, global: bool) -> Result<Vec<Drift>> {
+        let hooks_dir = Self::hooks_dir(policy_dir, global)?;
+        let mut drift = Vec::new();
+        for hook in Self::HOOKS {
+            let path = hooks_dir.join(hook);
+            let problem = match fs::read_to_string(&path) {
+                Err(_) => "Cupcake hook is missing",
+                Ok(content) if !runs_cupcake_eval(&content) => {
+                    // Someone else's hook - repair would overwrite it
+                    drift.push(Drift {
+                        event: hook.to_string(),
+                        problem: "Hook exists but does not run cupcake eval".to_string(),
+                        repairable: false,
+                    });
+                    continue;
+                }
+                Ok(_) if !is_executable(&path) => "Hook is not executable",
+                Ok(_) => continue,
+            };
+            drift.push(Drift {
+                event: hook.to_string(),
+                problem: problem.to_string(),
+                repairable: true,
+            });
+        }
+        Ok(drift)
+    }
+}
+
+/// Whether a hook script runs `cupcake eval` on one of its lines
+fn runs_cupcake_eval(script: &str) -> bool {
+    script.lines().any(|line| {
+        let line = line.trim();
+        is_cupcake_eval(line.strip_prefix("exec ").unwrap_or(line))
+    })
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+        .with_context(|| format!("Failed to make {path:?} executable"))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).is_ok_and(|m| m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    true
+}
+
+/// Cline is wired through hook scripts rather than hook settings
+fn cline_status(policy_dir: &Path, global: bool, repair: bool) -> Result<HarnessStatus> {
+    let hooks_dir = ClineHarness::hooks_dir(policy_dir, global)?;
+    let mut status = HarnessStatus {
+        harness: "Cline".to_string(),
+        settings_path: hooks_dir.clone(),
+        installed: hooks_dir.exists(),
+        drift: Vec::new(),
+        repaired: false,
+    };
+    if !status.installed {
+        return Ok(status);
+    }
+    status.drift = ClineHarness::drift(policy_dir, global)?;
+    if repair && status.drift.iter().any(|d| d.repairable) {
+        ClineHarness::install_hooks(policy_dir, global)?;
+        status.drift = ClineHarness::drift(policy_dir, global)?;
+        status.repaired = true;
+    }
+    Ok(status)
+}
+
+impl AiderHarness {
+    /// Where the launcher is installed - next to the policies it enforces
+    pub fn launcher_path(policy_dir: &Path) -> PathBuf {
+        policy_dir.join("aider").join("cupcake_aider.py")
+    }
+
+    /// Write the launcher, replacing an older copy
+    pub fn install_launcher(policy_dir: &Path) -> Result<PathBuf> {
+        let path = Self::launcher_path(policy_dir);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create launcher directory: {dir:?}"))?;
+        }
+        fs::write(&path, AIDER_LAUNCHER)
+            .with_context(|| format!("Failed to write launcher: {path:?}"))?;
+        make_executable(&path)?;
+        Ok(path)
+    }
+
+    /// Problems with the installed launcher
+    fn drift(policy_dir: &Path) -> Vec<Drift> {
+        let problem = match fs::read_to_string(Self::launcher_path(policy_dir)) {
+            Err(_) => "Cupcake launcher is missing",
+            Ok(content) if content != AIDER_LAUNCHER => {
+                "Launcher differs from the one shipped with this cupcake"
+            }
+            Ok(_) => return Vec::new(),
+        };
+        vec![Drift {
+            event: "*".to_string(),
+            problem: problem.to_string(),
+            repairable: true,
+        }]
+    }
+}
+
+/// Aider is wired through a launcher script rather than hook settings
+fn aider_status(policy_dir: &Path, repair: bool) -> Result<HarnessStatus> {
+    let launcher_path = AiderHarness::launcher_path(policy_dir);
+    let mut status = HarnessStatus {
+        harness: "Aider".to_string(),
+        settings_path: launcher_path.clone(),
+        installed: launcher_path.parent().is_some_and(Path::exists),
+        drift: Vec::new(),
+        repaired: false,
+    };
+    if !status.installed {
+        return Ok(status);
+    }
+    status.drift = AiderHarness::drift(policy_dir);
+    if repair && !status.drift.is_empty() {
+        AiderHarness::install_launcher(policy_dir)?;
+        status.drift = AiderHarness::drift(policy_dir);
+        status.repaired = true;
+    }
+    Ok(status)
+}
+
 /// Configure harness integration with error recovery
 pub async fn configure_harness(
     harness_type: super::HarnessType,
@@ -570,6 +1235,85 @@ pub async fn configure_harness(
                     harness.name());
             }
         }
+        HarnessType::Goose => {
+            let harness = GooseHarness;
+            let settings_path = harness.settings_path(global);
+
+            // Try to configure, fallback to manual instructions on error
+            if let Err(e) =
+                setup_harness_settings(&harness, &settings_path, policy_dir, global).await
+            {
+                eprintln!(
+                    "⚠️  Could not automatically configure {}: {}",
+                    harness.name(),
+                    e
+                );
+                print_goose_manual_instructions(&harness, policy_dir, global);
+                // Don't fail the entire init - just warn
+            } else {
+                println!(
+                    "✅ Configured {} integration in {}",
+                    harness.name(),
+                    settings_path.display()
+                );
+                println!("   - Added PreToolUse hook for tool permission checks");
+                println!("   - Added PostToolUse hook for file edits");
+                println!("   - Added UserPromptSubmit hook for prompt validation");
+                println!("   - Added SessionStart hook for initial context");
+                println!();
+                println!("   {} will now evaluate all tool calls and prompts against your Cupcake policies.",
+                    harness.name());
+            }
+        }
+        HarnessType::Cline => match ClineHarness::install_hooks(policy_dir, global) {
+            Ok(skipped) => {
+                let hooks_dir = ClineHarness::hooks_dir(policy_dir, global)?;
+                println!("✅ Configured Cline integration in {}", hooks_dir.display());
+                println!("   - Added PreToolUse hook for tool approval");
+                println!("   - Added PostToolUse hook for tool results");
+                println!("   - Added UserPromptSubmit hook for prompt validation");
+                println!("   - Added TaskStart/TaskResume hooks for initial context");
+                for hook in &skipped {
+                    eprintln!(
+                        "⚠️  Left existing {hook} hook unchanged - add 'cupcake eval --harness cline' to it to enforce policies there"
+                    );
+                }
+                println!();
+                println!("   Enable hooks in Cline's settings if they are off.");
+            }
+            Err(e) => {
+                eprintln!("⚠️  Could not automatically configure Cline: {e}");
+                eprintln!();
+                eprintln!("   Create an executable script for each hook in .clinerules/hooks/");
+                eprintln!("   (PreToolUse, PostToolUse, UserPromptSubmit, TaskStart) containing:");
+                eprintln!();
+                eprintln!("      #!/bin/sh");
+                eprintln!("      exec cupcake eval --harness cline --policy-dir .cupcake");
+                // Don't fail the entire init - just warn
+            }
+        },
+        HarnessType::Aider => {
+            match AiderHarness::install_launcher(policy_dir) {
+                Ok(path) => {
+                    println!("✅ Configured Aider integration");
+                    println!("   - Launcher installed to: {}", path.display());
+                    println!("   - Shell commands, file writes and /web fetches are evaluated before they run");
+                    println!("   - Prompts and session start are evaluated too");
+                    println!();
+                    println!("   Start Aider through the launcher to enforce policies:");
+                    println!("      python3 {} [aider arguments]", path.display());
+                }
+                Err(e) => {
+                    eprintln!("⚠️  Could not automatically configure Aider: {e}");
+                    eprintln!();
+                    eprintln!("   Copy cupcake-plugins/aider/cupcake_aider.py from the Cupcake repository");
+                    eprintln!("   to .cupcake/aider/ and start Aider with:");
+                    eprintln!();
+                    eprintln!("      python3 .cupcake/aider/cupcake_aider.py [aider arguments]");
+                    // Don't fail the entire init - just warn
+                }
+            }
+        }
         HarnessType::OpenCode => {
             // OpenCode uses a plugin model - download the plugin from GitHub releases
             println!("   Configuring OpenCode integration...");
@@ -583,6 +1327,20 @@ pub async fn configure_harness(
                 policy_dir.parent().unwrap_or(Path::new(".")).to_path_buf()
             };
 
+            // The plugin reads its config from the project, so only project
+            // installs get one
+            let config_written = if global {
+                false
+            } else {
+                match OpenCodeHarness::write_plugin_config(policy_dir) {
+                    Ok(written) => written,
+                    Err(e) => {
+                        eprintln!("⚠️  Could not write OpenCode plugin config: {e}");
+                        false
+                    }
+                }
+            };
+
             // Try to download the plugin from GitHub releases
             match OpenCodeHarness::download_plugin(&target_dir, global).await {
                 Ok(()) => {
@@ -594,19 +1352,19 @@ pub async fn configure_harness(
 
                     println!("✅ Configured OpenCode integration");
                     println!("   - Plugin installed to: {plugin_location}");
-                    println!();
+                    if config_written {
+                        println!("   - Plugin config written to: .cupcake/opencode.json");
+                    }
                     println!(
-                        "   OpenCode will automatically load the Cupcake plugin and enforce policies."
+                        "   - Tool calls and permission prompts are evaluated before they run"
+                    );
+                    println!(
+                        "   - Tool output, prompts and session lifecycle events are evaluated too"
                     );
                     println!();
                     println!(
-                        "   Optional: Create .cupcake/opencode.json to customize plugin behavior:"
+                        "   OpenCode will automatically load the Cupcake plugin and enforce policies."
                     );
-                    println!("   {{");
-                    println!("     \"enabled\": true,");
-                    println!("     \"logLevel\": \"info\",");
-                    println!("     \"failMode\": \"closed\"");
-                    println!("   }}");
                 }
                 Err(e) => {
                     eprintln!("⚠️  Could not automatically download OpenCode plugin: {e}");
@@ -689,6 +1447,36 @@ fn print_manual_instructions(harness: &dyn HarnessConfig, policy_dir: &Path, glo
     eprintln!();
 }
 
+/// Print manual configuration instructions for Goose
+fn print_goose_manual_instructions(harness: &GooseHarness, policy_dir: &Path, global: bool) {
+    let policy_path = if global {
+        policy_dir.display().to_string()
+    } else {
+        ".cupcake".to_string()
+    };
+
+    eprintln!();
+    eprintln!(
+        "   To manually configure, add this to your {}:",
+        harness.settings_path(global).display()
+    );
+    eprintln!();
+    eprintln!("   {{");
+    eprintln!("     \"hooks\": {{");
+    eprintln!("       \"PreToolUse\": [{{");
+    eprintln!("         \"matcher\": \"*\",");
+    eprintln!("         \"hooks\": [{{");
+    eprintln!("           \"type\": \"command\",");
+    eprintln!(
+        "           \"command\": \"cupcake eval --harness goose --policy-dir {policy_path}\""
+    );
+    eprintln!("         }}]");
+    eprintln!("       }}]");
+    eprintln!("     }}");
+    eprintln!("   }}");
+    eprintln!();
+}
+
 /// Print manual configuration instructions for Cursor
 fn print_cursor_manual_instructions(policy_dir: &Path, global: bool) {
     let policy_path = if global {
@@ -733,6 +1521,23 @@ fn print_cursor_manual_instructions(policy_dir: &Path, global: bool) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_global_hook_paths_survive_windows_shells() {
+        assert_eq!(
+            normalize_hook_path(r"\\?\C:\Users\dev\.cupcake", true),
+            "C:/Users/dev/.cupcake"
+        );
+        assert_eq!(
+            normalize_hook_path("/home/dev/.cupcake", false),
+            "/home/dev/.cupcake"
+        );
+        assert_eq!(
+            quote_hook_arg("C:/Users/Jane Doe/.cupcake"),
+            "\"C:/Users/Jane Doe/.cupcake\""
+        );
+        assert_eq!(quote_hook_arg("/home/dev/.cupcake"), "/home/dev/.cupcake");
+    }
+
     #[test]
     fn test_merge_empty_settings() {
         let mut existing = json!({});
@@ -820,4 +1625,165 @@ mod tests {
         // Should not duplicate
         assert_eq!(existing["hooks"]["PreToolUse"].as_array().unwrap().len(), 1);
     }
+
+    #[test]
+    fn test_detect_and_repair_drift() {
+        let expected = ClaudeHarness
+            .generate_hooks(Path::new(".cupcake"), false)
+            .unwrap();
+        let installed = json!({
+            "model": "opus",
+            "hooks": {
+                "PreToolUse": [{
+                    "matcher": "*",
+                    "hooks": [
+                        {"type": "command", "command": "cupcake eval --harness claude --policy-dir .cupcake"},
+                        {"type": "command", "command": "echo audit"}
+                    ]
+                }],
+                "PostToolUse": [{
+                    "matcher": "Write",
+                    "hooks": [{"type": "command", "command": "cupcake eval --harness claude --policy-dir $CLAUDE_PROJECT_DIR/.cupcake"}]
+                }],
+                "SessionStart": expected["hooks"]["SessionStart"].clone()
+            }
+        });
+
+        let drift: Vec<Drift> = detect_drift(&installed, &expected)
+            .into_iter()
+            .filter(|d| d.repairable)
+            .collect();
+        let events: Vec<&str> = drift.iter().map(|d| d.event.as_str()).collect();
+        assert_eq!(
+            events,
+            vec!["PostToolUse", "PreToolUse", "UserPromptSubmit"]
+        );
+        assert!(drift[0].problem.starts_with("matcher is \"Write\""));
+        assert!(drift[1].problem.contains("--policy-dir .cupcake"));
+        assert_eq!(drift[2].problem, "not wired to Cupcake");
+
+        let repaired = repair_settings(installed, expected.clone()).unwrap();
+        assert!(detect_drift(&repaired, &expected)
+            .iter()
+            .all(|d| !d.repairable));
+        // Unrelated settings and hooks survive the repair
+        assert_eq!(repaired["model"], "opus");
+        let pre_tool_use = repaired["hooks"]["PreToolUse"].as_array().unwrap();
+        assert_eq!(pre_tool_use[0]["hooks"][0]["command"], "echo audit");
+        assert_eq!(pre_tool_use.len(), 2);
+    }
+
+    #[test]
+    fn test_cline_hooks_installed_and_checked() {
+        let temp = tempfile::tempdir().unwrap();
+        let policy_dir = temp.path().join(".cupcake");
+        let hooks_dir = ClineHarness::hooks_dir(&policy_dir, false).unwrap();
+
+        // A hook the user wrote is kept
+        fs::create_dir_all(&hooks_dir).unwrap();
+        fs::write(hooks_dir.join("TaskStart"), "#!/bin/sh\necho hi\n").unwrap();
+
+        let skipped = ClineHarness::install_hooks(&policy_dir, false).unwrap();
+        assert_eq!(skipped, vec!["TaskStart"]);
+        let script = fs::read_to_string(hooks_dir.join("PreToolUse")).unwrap();
+        assert!(script.contains("cupcake eval --harness cline"));
+        assert!(is_executable(&hooks_dir.join("PreToolUse")));
+
+        let drift = ClineHarness::drift(&policy_dir, false).unwrap();
+        assert_eq!(drift.len(), 1);
+        assert_eq!(drift[0].event, "TaskStart");
+        assert!(!drift[0].repairable);
+
+        fs::remove_file(hooks_dir.join("PostToolUse")).unwrap();
+        let status = cline_status(&policy_dir, false, true).unwrap();
+        assert!(status.repaired);
+        assert!(hooks_dir.join("PostToolUse").exists());
+        assert_eq!(status.drift.len(), 1);
+    }
+
+    #[test]
+    fn test_aider_launcher_installed_and_checked() {
+        let temp = tempfile::tempdir().unwrap();
+        let policy_dir = temp.path().join(".cupcake");
+
+        assert!(!aider_status(&policy_dir, false).unwrap().installed);
+
+        let path = AiderHarness::install_launcher(&policy_dir).unwrap();
+        assert!(is_executable(&path));
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains("\"--harness\", \"aider\""));
+        assert!(AiderHarness::drift(&policy_dir).is_empty());
+
+        fs::write(&path, "# edited\n").unwrap();
+        let status = aider_status(&policy_dir, true).unwrap();
+        assert!(status.repaired);
+        assert!(status.drift.is_empty());
+        assert_eq!(fs::read_to_string(&path).unwrap(), AIDER_LAUNCHER);
+    }
+
+    #[test]
+    fn test_opencode_plugin_config_is_written_once() {
+        let temp = tempfile::tempdir().unwrap();
+        let policy_dir = temp.path().join(".cupcake");
+
+        assert!(OpenCodeHarness::write_plugin_config(&policy_dir).unwrap());
+        let config_path = policy_dir.join("opencode.json");
+        let config: Value =
+            serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(config["failMode"], "closed");
+
+        fs::write(&config_path, r#"{"failMode": "open"}"#).unwrap();
+        assert!(!OpenCodeHarness::write_plugin_config(&policy_dir).unwrap());
+        assert!(fs::read_to_string(&config_path).unwrap().contains("open"));
+    }
+
+    #[test]
+    fn test_cursor_drift_uses_flat_entries() {
+        let expected = CursorHarness
+            .generate_hooks(Path::new(".cupcake"), false)
+            .unwrap();
+        let mut installed = expected.clone();
+        installed["hooks"]["stop"] = json!([{"command": "/old/bin/cupcake eval --harness claude"}]);
+
+        let drift: Vec<Drift> = detect_drift(&installed, &expected)
+            .into_iter()
+            .filter(|d| d.repairable)
+            .collect();
+        assert_eq!(drift.len(), 1);
+        assert_eq!(drift[0].event, "stop");
+
+        let repaired = repair_settings(installed, expected.clone()).unwrap();
+        assert_eq!(repaired["hooks"]["stop"], expected["hooks"]["stop"]);
+        assert!(is_cupcake_eval(
+            "\"/usr/local/bin/cupcake\" eval --harness cursor"
+        ));
+        assert!(!is_cupcake_eval("cupcake verify"));
+    }
+
+    #[test]
+    fn test_goose_hooks_merge_with_existing() {
+        let harness = GooseHarness;
+        let expected = harness
+            .generate_hooks(Path::new(".cupcake"), false)
+            .unwrap();
+        assert_eq!(
+            expected["hooks"]["PreToolUse"][0]["hooks"][0]["command"],
+            "cupcake eval --harness goose --policy-dir .cupcake"
+        );
+
+        let existing = json!({
+            "hooks": {
+                "PostToolUse": [{
+                    "matcher": "developer__shell",
+                    "hooks": [{"type": "command", "command": "./log-shell.sh"}]
+                }]
+            }
+        });
+        let merged = harness.merge_settings(existing, expected.clone()).unwrap();
+        assert_eq!(merged["hooks"]["PostToolUse"].as_array().unwrap().len(), 2);
+        assert!(detect_drift(&merged, &expected)
+            .iter()
+            .all(|d| !d.repairable));
+    }
 }
@@ -1,11 +1,10 @@
 //! Cupcake - A performant policy engine for coding agents
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use clap::{Parser, ValueEnum};
 use std::fs;
 use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
-use std::process::Command as ProcessCommand;
 use std::str::FromStr;
 use tabled::{
     settings::{object::Rows, Alignment, Modify, Style},
@@ -14,10 +13,18 @@ use tabled::{
 use tracing::{debug, error, info};
 use tracing_subscriber::EnvFilter;
 
+use cupcake_core::engine::decision::DecisionOutput;
 use cupcake_core::{engine, harness, telemetry::TelemetryContext, validator};
 
+mod assess;
+mod ci;
+mod git_hooks;
 mod harness_config;
 mod migrations;
+mod onboard;
+mod pack;
+mod report;
+mod synthetic_event;
 #[cfg(feature = "watchdog")]
 mod watchdog_cli;
 
@@ -128,6 +135,11 @@ struct Cli {
     #[clap(long, global = true)]
     global_config: Option<PathBuf>,
 
+    /// Team configuration directory, evaluated between global and project
+    /// (defaults to $CUPCAKE_TEAM_CONFIG)
+    #[clap(long, global = true)]
+    team_config: Option<PathBuf>,
+
     /// Maximum WASM memory allocation (e.g., "10MB", "50MB")
     #[clap(long, default_value = "10MB", global = true)]
     wasm_max_memory: MemorySize,
@@ -168,6 +180,45 @@ enum Command {
         /// Strict mode (exit non-zero on deny)
         #[clap(long)]
         strict: bool,
+
+        /// Print the harness-neutral, versioned decision object instead of the
+        /// harness response
+        #[clap(long)]
+        decision: bool,
+
+        /// Read the hook event JSON from a file instead of stdin
+        #[clap(long, conflicts_with = "event")]
+        event_file: Option<PathBuf>,
+
+        /// Build a synthetic event with this hook event name (e.g. PreToolUse)
+        #[clap(long)]
+        event: Option<String>,
+
+        /// Tool name for the synthetic event (e.g. Bash)
+        #[clap(long, requires = "event")]
+        tool: Option<String>,
+
+        /// Shell command for the synthetic event
+        #[clap(long, requires = "event")]
+        command: Option<String>,
+
+        /// File path for the synthetic event
+        #[clap(long, requires = "event")]
+        file_path: Option<String>,
+
+        /// User prompt for the synthetic event
+        #[clap(long, requires = "event")]
+        prompt: Option<String>,
+
+        /// Also write the decision as a SARIF 2.1.0 report to this file
+        #[clap(long, value_name = "FILE")]
+        sarif: Option<PathBuf>,
+
+        /// Answer with the harness's JSON response, or with exit codes (0 allow,
+        /// 2 block, reason on stderr). Defaults to the rulebook's `output_mode:`
+        /// for the harness, then JSON
+        #[clap(long, value_enum, conflicts_with = "decision")]
+        output_mode: Option<OutputMode>,
     },
 
     /// Evaluate an event using Watchdog (LLM-as-judge) directly
@@ -199,6 +250,152 @@ enum Command {
         /// Directory containing policy files
         #[clap(long, default_value = "./policies")]
         policy_dir: PathBuf,
+
+        /// Output the initialization report as JSON
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Show which policies would be evaluated for an event and tool
+    Route {
+        /// The AI coding agent harness type (REQUIRED)
+        #[clap(long, value_enum)]
+        harness: HarnessType,
+
+        /// Directory containing policy files
+        #[clap(long, default_value = "./policies")]
+        policy_dir: PathBuf,
+
+        /// Hook event name (e.g., PreToolUse)
+        #[clap(long)]
+        event: String,
+
+        /// Tool name (e.g., Bash)
+        #[clap(long)]
+        tool: Option<String>,
+
+        /// Output results as JSON
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Measure engine init, routing, signal and evaluation latency for the project
+    Bench {
+        /// The AI coding agent harness type (REQUIRED)
+        #[clap(long, value_enum)]
+        harness: HarnessType,
+
+        /// Directory containing policy files
+        #[clap(long, default_value = "./policies")]
+        policy_dir: PathBuf,
+
+        /// Read the event to benchmark from a file
+        #[clap(long, conflicts_with_all = ["event", "tool", "command"])]
+        event_file: Option<PathBuf>,
+
+        /// Hook event name (defaults to the harness's shell tool event)
+        #[clap(long)]
+        event: Option<String>,
+
+        /// Tool name for the synthetic event
+        #[clap(long)]
+        tool: Option<String>,
+
+        /// Shell command for the synthetic event
+        #[clap(long, default_value = "git status")]
+        command: String,
+
+        /// Number of evaluations to time
+        #[clap(long, default_value_t = 50)]
+        iterations: usize,
+
+        /// Number of engine initializations to time
+        #[clap(long, default_value_t = 3)]
+        init_runs: usize,
+
+        /// Output results as JSON
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Run write policies against a diff, patch or directory without an agent
+    #[clap(group(clap::ArgGroup::new("changes").required(true)))]
+    Assess {
+        /// The AI coding agent harness whose policies apply (REQUIRED)
+        #[clap(long, value_enum)]
+        harness: HarnessType,
+
+        /// Directory containing policy files
+        #[clap(long, default_value = "./policies")]
+        policy_dir: PathBuf,
+
+        /// Assess `git diff <RANGE>`, e.g. origin/main...HEAD
+        #[clap(long, value_name = "RANGE", group = "changes")]
+        diff: Option<String>,
+
+        /// Assess a unified diff file (`-` reads stdin)
+        #[clap(long, group = "changes")]
+        patch: Option<PathBuf>,
+
+        /// Assess every text file below a directory
+        #[clap(long, group = "changes")]
+        dir: Option<PathBuf>,
+
+        /// Output the report as JSON
+        #[clap(long)]
+        json: bool,
+
+        /// Also write the decisions as a SARIF 2.1.0 report to this file
+        #[clap(long, value_name = "FILE")]
+        sarif: Option<PathBuf>,
+    },
+
+    /// Gate CI on policies: evaluate an event corpus and/or changes, write reports
+    #[clap(group(clap::ArgGroup::new("inputs").required(true).multiple(true)))]
+    Ci {
+        /// The AI coding agent harness whose policies apply (REQUIRED)
+        #[clap(long, value_enum)]
+        harness: HarnessType,
+
+        /// Directory containing policy files
+        #[clap(long, default_value = "./policies")]
+        policy_dir: PathBuf,
+
+        /// Evaluate every event JSON file below a directory
+        #[clap(long, value_name = "DIR", group = "inputs")]
+        events: Option<PathBuf>,
+
+        /// Assess `git diff <RANGE>`, e.g. origin/main...HEAD
+        #[clap(long, value_name = "RANGE", group = "inputs", conflicts_with_all = ["patch", "dir"])]
+        diff: Option<String>,
+
+        /// Assess a unified diff file (`-` reads stdin)
+        #[clap(long, group = "inputs", conflicts_with = "dir")]
+        patch: Option<PathBuf>,
+
+        /// Assess every text file below a directory
+        #[clap(long, group = "inputs")]
+        dir: Option<PathBuf>,
+
+        /// Least severe decision that fails a case
+        #[clap(long, value_enum, default_value = "deny")]
+        fail_on: ci::FailOn,
+
+        /// Failing cases tolerated before the job fails
+        #[clap(long, default_value_t = 0)]
+        max_failures: usize,
+
+        /// Write a SARIF 2.1.0 report to this file
+        #[clap(long, value_name = "FILE")]
+        sarif: Option<PathBuf>,
+
+        /// Write a JUnit XML report to this file
+        #[clap(long, value_name = "FILE")]
+        junit: Option<PathBuf>,
+
+        /// Output the results as JSON
+        #[clap(long)]
+        json: bool,
     },
 
     /// Initialize a new Cupcake project
@@ -210,6 +407,16 @@ enum Command {
         /// Configure integration with an agent harness (e.g., 'claude')
         #[clap(long, value_enum)]
         harness: Option<HarnessType>,
+
+        /// Install git pre-commit and pre-push hooks that run `cupcake assess`
+        #[clap(long, conflicts_with = "global")]
+        git_hooks: bool,
+    },
+
+    /// Inspect and repair agent harness integrations
+    Harness {
+        #[clap(subcommand)]
+        command: HarnessCommand,
     },
 
     /// Validate policies for Cupcake requirements and best practices
@@ -221,6 +428,18 @@ enum Command {
         /// Output results as JSON
         #[clap(long)]
         json: bool,
+
+        /// Fix common issues in place, printing a diff of each change
+        #[clap(long)]
+        fix: bool,
+
+        /// Run the OPA compile check in strict mode (unused variables, imports, etc.)
+        #[clap(long)]
+        strict: bool,
+
+        /// Also write the issues as a SARIF 2.1.0 report to this file
+        #[clap(long, value_name = "FILE")]
+        sarif: Option<PathBuf>,
     },
 
     /// Inspect policies to show metadata and routing information
@@ -236,10 +455,107 @@ enum Command {
         /// Display results in a compact table format
         #[clap(short, long)]
         table: bool,
+
+        /// Render the routing map (events → tools → policies) as a graph,
+        /// including global and team policies
+        #[clap(long, value_enum, conflicts_with_all = ["json", "table"])]
+        graph: Option<GraphFormat>,
+
+        /// Compare routes and policies against a git revision or another policy directory
+        #[clap(long, value_name = "GIT_REF_OR_DIR", conflicts_with_all = ["table", "graph"])]
+        diff: Option<String>,
+    },
+
+    /// Convert rule files (CLAUDE.md, .cursorrules) into Cupcake policies using Claude
+    Onboard {
+        /// Rule files to convert (default: every known rule file in the project)
+        #[clap(value_name = "FILE")]
+        files: Vec<PathBuf>,
+
+        /// Harness to write policies for (default: detected from .cupcake/policies/)
+        #[clap(long, value_enum)]
+        harness: Option<HarnessType>,
+
+        /// Anthropic model used for the conversion
+        #[clap(long, default_value = onboard::DEFAULT_MODEL)]
+        model: String,
+
+        /// Convert a YAML spec of protected paths, blocked commands and required
+        /// checks without calling the API
+        #[clap(long, value_name = "SPEC", conflicts_with_all = ["files", "model"])]
+        offline: Option<PathBuf>,
+
+        /// Answer yes to every prompt; existing policies are never overwritten
+        #[clap(short, long)]
+        yes: bool,
+    },
+
+    /// Export the project's policies, rulebook, signals and watchdog prompts as a policy pack
+    Export {
+        /// Project Cupcake directory
+        #[clap(long, default_value = ".cupcake")]
+        policy_dir: PathBuf,
+
+        /// Pack file to write
+        #[clap(short, long, default_value = pack::DEFAULT_PACK_FILE)]
+        output: PathBuf,
+
+        /// Pack name (default: the project directory's name)
+        #[clap(long)]
+        name: Option<String>,
+
+        /// Pack version, e.g. 1.2.0
+        #[clap(long, default_value = "0.1.0")]
+        pack_version: String,
     },
 
-    /// Launch the interactive onboarding wizard to convert rule files into Cupcake policies
-    Onboard,
+    /// Import a policy pack exported by `cupcake export`
+    Import {
+        /// Pack file to import
+        pack: PathBuf,
+
+        /// Project Cupcake directory
+        #[clap(long, default_value = ".cupcake")]
+        policy_dir: PathBuf,
+
+        /// Import policies under cupcake.policies.<NAMESPACE> to avoid collisions
+        #[clap(long)]
+        namespace: Option<String>,
+
+        /// Only accept the pack with this digest (printed by `cupcake export`)
+        #[clap(long, value_name = "DIGEST")]
+        sha256: Option<String>,
+
+        /// Overwrite files that exist with different content
+        #[clap(long)]
+        force: bool,
+
+        /// Show what would change without writing anything
+        #[clap(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum HarnessCommand {
+    /// Compare installed hook configuration against what this version expects
+    Status {
+        /// Check one harness (default: every harness with an installed configuration)
+        #[clap(long, value_enum)]
+        harness: Option<HarnessType>,
+
+        /// Check the user-level (global) configuration instead of the project
+        #[clap(long)]
+        global: bool,
+
+        /// Rewrite outdated or missing Cupcake hooks
+        #[clap(long)]
+        repair: bool,
+
+        /// Output results as JSON
+        #[clap(long)]
+        json: bool,
+    },
 }
 
 /// Supported agent harness types for integration
@@ -254,6 +570,30 @@ enum HarnessType {
     /// OpenCode (opencode.ai)
     #[clap(name = "opencode")]
     OpenCode,
+    /// Cline (cline.bot) and compatible forks
+    Cline,
+    /// Aider (aider.chat)
+    Aider,
+    /// Goose (block.github.io/goose)
+    Goose,
+}
+
+/// How `cupcake eval` answers the harness
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum OutputMode {
+    /// The harness's JSON hook response on stdout
+    Json,
+    /// Exit 0 to allow or 2 to block, with the reason on stderr
+    ExitCode,
+}
+
+impl From<OutputMode> for cupcake_core::harness::exit_code::OutputMode {
+    fn from(mode: OutputMode) -> Self {
+        match mode {
+            OutputMode::Json => Self::Json,
+            OutputMode::ExitCode => Self::ExitCode,
+        }
+    }
 }
 
 impl From<HarnessType> for cupcake_core::harness::types::HarnessType {
@@ -263,6 +603,27 @@ impl From<HarnessType> for cupcake_core::harness::types::HarnessType {
             HarnessType::Cursor => cupcake_core::harness::types::HarnessType::Cursor,
             HarnessType::Factory => cupcake_core::harness::types::HarnessType::Factory,
             HarnessType::OpenCode => cupcake_core::harness::types::HarnessType::OpenCode,
+            HarnessType::Cline => cupcake_core::harness::types::HarnessType::Cline,
+            HarnessType::Aider => cupcake_core::harness::types::HarnessType::Aider,
+            HarnessType::Goose => cupcake_core::harness::types::HarnessType::Goose,
+        }
+    }
+}
+
+/// Routing graph output formats
+#[derive(Debug, Clone, ValueEnum)]
+enum GraphFormat {
+    /// Graphviz DOT
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+}
+
+impl From<GraphFormat> for engine::routing_graph::GraphFormat {
+    fn from(format: GraphFormat) -> Self {
+        match format {
+            GraphFormat::Dot => engine::routing_graph::GraphFormat::Dot,
+            GraphFormat::Mermaid => engine::routing_graph::GraphFormat::Mermaid,
         }
     }
 }
@@ -274,6 +635,9 @@ fn harness_dir_name(harness: &HarnessType) -> &'static str {
         HarnessType::Cursor => "cursor",
         HarnessType::Factory => "factory",
         HarnessType::OpenCode => "opencode",
+        HarnessType::Cline => "cline",
+        HarnessType::Aider => "aider",
+        HarnessType::Goose => "goose",
     }
 }
 
@@ -285,8 +649,11 @@ fn prompt_harness_selection() -> Result<HarnessType> {
     println!("  2) cursor   - Cursor (cursor.com)");
     println!("  3) factory  - Factory AI Droid (factory.ai)");
     println!("  4) opencode - OpenCode (opencode.ai)");
+    println!("  5) cline    - Cline (cline.bot) and compatible forks");
+    println!("  6) aider    - Aider (aider.chat)");
+    println!("  7) goose    - Goose (block.github.io/goose)");
     println!();
-    print!("Enter choice [1-4]: ");
+    print!("Enter choice [1-7]: ");
     io::stdout().flush()?;
 
     let mut input = String::new();
@@ -297,8 +664,11 @@ fn prompt_harness_selection() -> Result<HarnessType> {
         "2" | "cursor" => Ok(HarnessType::Cursor),
         "3" | "factory" => Ok(HarnessType::Factory),
         "4" | "opencode" => Ok(HarnessType::OpenCode),
+        "5" | "cline" => Ok(HarnessType::Cline),
+        "6" | "aider" => Ok(HarnessType::Aider),
+        "7" | "goose" => Ok(HarnessType::Goose),
         _ => Err(anyhow!(
-            "Invalid selection '{}'. Please enter 1-4 or a harness name (claude, cursor, factory, opencode)",
+            "Invalid selection '{}'. Please enter 1-7 or a harness name (claude, cursor, factory, opencode, cline, aider, goose)",
             input.trim()
         )),
     }
@@ -371,6 +741,15 @@ async fn main() -> Result<()> {
             policy_dir,
             debug,
             strict,
+            decision,
+            event_file,
+            event,
+            tool,
+            command,
+            file_path,
+            prompt,
+            sarif,
+            output_mode,
         } => {
             if debug {
                 tracing::subscriber::set_global_default(
@@ -387,14 +766,34 @@ async fn main() -> Result<()> {
             let engine_config = engine::EngineConfig {
                 harness: harness.into(), // Convert CLI HarnessType to core HarnessType
                 wasm_max_memory: Some(cli.wasm_max_memory.bytes),
+                wasm_pool_size: None,
                 opa_path: cli.opa_path.clone(),
                 global_config: cli.global_config.clone(),
+                team_config: cli.team_config.clone(),
                 debug_routing: cli.debug_routing,
             };
 
+            let source = match (event_file, event) {
+                (Some(path), _) => EventSource::File(path),
+                (None, Some(event)) => EventSource::Synthetic(synthetic_event::EventSpec {
+                    event,
+                    tool,
+                    command,
+                    file_path,
+                    prompt,
+                }),
+                (None, None) => EventSource::Stdin,
+            };
+
             eval_command(
+                source,
                 policy_dir,
-                strict,
+                EvalOutput {
+                    strict,
+                    decision,
+                    sarif,
+                    mode: output_mode.map(Into::into),
+                },
                 cli.debug_files,
                 cli.debug_dir,
                 engine_config,
@@ -404,8 +803,167 @@ async fn main() -> Result<()> {
         Command::Verify {
             harness,
             policy_dir,
-        } => verify_command(harness.into(), policy_dir).await,
-        Command::Init { global, harness } => init_command(global, harness).await,
+            json,
+        } => {
+            if json {
+                verify_json_command(harness.into(), policy_dir).await
+            } else {
+                verify_command(harness.into(), policy_dir).await
+            }
+        }
+        Command::Route {
+            harness,
+            policy_dir,
+            event,
+            tool,
+            json,
+        } => {
+            let engine_config = engine::EngineConfig {
+                harness: harness.into(),
+                wasm_max_memory: Some(cli.wasm_max_memory.bytes),
+                wasm_pool_size: None,
+                opa_path: cli.opa_path.clone(),
+                global_config: cli.global_config.clone(),
+                team_config: cli.team_config.clone(),
+                debug_routing: false,
+            };
+            route_command(policy_dir, event, tool, json, engine_config).await
+        }
+        Command::Bench {
+            harness,
+            policy_dir,
+            event_file,
+            event,
+            tool,
+            command,
+            iterations,
+            init_runs,
+            json,
+        } => {
+            let engine_config = engine::EngineConfig {
+                harness: harness.into(),
+                wasm_max_memory: Some(cli.wasm_max_memory.bytes),
+                wasm_pool_size: None,
+                opa_path: cli.opa_path.clone(),
+                global_config: cli.global_config.clone(),
+                team_config: cli.team_config.clone(),
+                debug_routing: false,
+            };
+            let source = match event_file {
+                Some(path) => EventSource::File(path),
+                None => {
+                    // Default to the event each harness fires before running a shell command
+                    let (default_event, default_tool) = match engine_config.harness {
+                        harness::types::HarnessType::Cursor => ("beforeShellExecution", None),
+                        _ => ("PreToolUse", Some("Bash")),
+                    };
+                    let tool = match &event {
+                        Some(_) => tool,
+                        None => tool.or(default_tool.map(String::from)),
+                    };
+                    EventSource::Synthetic(synthetic_event::EventSpec {
+                        event: event.unwrap_or_else(|| default_event.to_string()),
+                        tool,
+                        command: Some(command),
+                        ..Default::default()
+                    })
+                }
+            };
+            bench_command(
+                policy_dir,
+                source,
+                iterations.max(1),
+                init_runs.max(1),
+                json,
+                engine_config,
+            )
+            .await
+        }
+        Command::Assess {
+            harness,
+            policy_dir,
+            diff,
+            patch,
+            dir,
+            json,
+            sarif,
+        } => {
+            let engine_config = engine::EngineConfig {
+                harness: harness.into(),
+                wasm_max_memory: Some(cli.wasm_max_memory.bytes),
+                wasm_pool_size: None,
+                opa_path: cli.opa_path.clone(),
+                global_config: cli.global_config.clone(),
+                team_config: cli.team_config.clone(),
+                debug_routing: false,
+            };
+            let source = match (diff, patch, dir) {
+                (Some(range), _, _) => assess::ChangeSource::GitDiff(range),
+                (_, Some(path), _) => assess::ChangeSource::Patch(path),
+                (_, _, Some(path)) => assess::ChangeSource::Dir(path),
+                (None, None, None) => unreachable!("clap requires one change source"),
+            };
+            assess::run(policy_dir, source, json, sarif, engine_config).await
+        }
+        Command::Ci {
+            harness,
+            policy_dir,
+            events,
+            diff,
+            patch,
+            dir,
+            fail_on,
+            max_failures,
+            sarif,
+            junit,
+            json,
+        } => {
+            let engine_config = engine::EngineConfig {
+                harness: harness.into(),
+                wasm_max_memory: Some(cli.wasm_max_memory.bytes),
+                wasm_pool_size: None,
+                opa_path: cli.opa_path.clone(),
+                global_config: cli.global_config.clone(),
+                team_config: cli.team_config.clone(),
+                debug_routing: false,
+            };
+            let changes = match (diff, patch, dir) {
+                (Some(range), _, _) => Some(assess::ChangeSource::GitDiff(range)),
+                (_, Some(path), _) => Some(assess::ChangeSource::Patch(path)),
+                (_, _, Some(path)) => Some(assess::ChangeSource::Dir(path)),
+                (None, None, None) => None,
+            };
+            let options = ci::CiOptions {
+                events,
+                changes,
+                fail_on,
+                max_failures,
+                sarif,
+                junit,
+                json,
+            };
+            ci::run(policy_dir, options, engine_config).await
+        }
+        Command::Init {
+            global,
+            harness,
+            git_hooks,
+        } => {
+            if git_hooks {
+                init_git_hooks(harness).await
+            } else {
+                init_command(global, harness).await
+            }
+        }
+        Command::Harness {
+            command:
+                HarnessCommand::Status {
+                    harness,
+                    global,
+                    repair,
+                    json,
+                },
+        } => harness_status_command(harness, global, repair, json).await,
         #[cfg(feature = "watchdog")]
         Command::Watchdog {
             config,
@@ -413,19 +971,122 @@ async fn main() -> Result<()> {
             input,
             dry_run,
         } => watchdog_cli::run(config, model, input, dry_run).await,
-        Command::Validate { policy_dir, json } => validate_command(policy_dir, json).await,
+        Command::Validate {
+            policy_dir,
+            json,
+            fix,
+            strict,
+            sarif,
+        } => validate_command(policy_dir, json, fix, strict, sarif, cli.opa_path).await,
+        Command::Inspect {
+            policy_dir,
+            graph: Some(format),
+            ..
+        } => inspect_graph_command(
+            policy_dir,
+            format.into(),
+            cli.global_config,
+            cli.team_config,
+        ),
+        Command::Inspect {
+            policy_dir,
+            json,
+            diff: Some(base),
+            ..
+        } => inspect_diff_command(policy_dir, base, json),
         Command::Inspect {
             policy_dir,
             json,
             table,
+            ..
         } => inspect_command(policy_dir, json, table).await,
-        Command::Onboard => onboard_command().await,
+        Command::Onboard {
+            files,
+            harness,
+            model,
+            offline,
+            yes,
+        } => {
+            let cwd = std::env::current_dir().context("Failed to get current directory")?;
+            let options = onboard::OnboardOptions {
+                files,
+                harness: harness.map(Into::into),
+                model,
+                offline,
+                yes,
+            };
+            onboard::run(&cwd, options).await
+        }
+        Command::Export {
+            policy_dir,
+            output,
+            name,
+            pack_version,
+        } => export_command(policy_dir, output, name, pack_version),
+        Command::Import {
+            pack,
+            policy_dir,
+            namespace,
+            sha256,
+            force,
+            dry_run,
+        } => {
+            let options = pack::ImportOptions {
+                namespace,
+                expected_digest: sha256,
+                force,
+                dry_run,
+            };
+            import_command(pack, policy_dir, options)
+        }
     }
 }
 
+/// Where `eval` reads the hook event from
+enum EventSource {
+    Stdin,
+    File(PathBuf),
+    Synthetic(synthetic_event::EventSpec),
+}
+
+impl EventSource {
+    /// Read the raw event JSON
+    fn read(self, harness: harness::types::HarnessType) -> Result<String> {
+        match self {
+            EventSource::Stdin => {
+                let mut buffer = String::new();
+                io::stdin()
+                    .read_to_string(&mut buffer)
+                    .context("Failed to read hook event from stdin")?;
+                Ok(buffer)
+            }
+            EventSource::File(path) => fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read event file: {path:?}")),
+            EventSource::Synthetic(spec) => {
+                let cwd = std::env::current_dir().context("Failed to get current directory")?;
+                let event = synthetic_event::build_event(harness, &spec, &cwd.to_string_lossy());
+                Ok(event.to_string())
+            }
+        }
+    }
+}
+
+/// What `cupcake eval` reports and how it exits
+struct EvalOutput {
+    /// Exit non-zero on blocking decisions
+    strict: bool,
+    /// Print the decision object instead of the harness response
+    decision: bool,
+    /// Also write the decision as SARIF to this file
+    sarif: Option<PathBuf>,
+    /// JSON or exit codes; `None` defers to the rulebook
+    mode: Option<harness::exit_code::OutputMode>,
+}
+
 async fn eval_command(
+    source: EventSource,
     policy_dir: PathBuf,
-    strict: bool,
+    output: EvalOutput,
     debug_files_enabled: bool,
     debug_dir: Option<PathBuf>,
     engine_config: engine::EngineConfig,
@@ -433,20 +1094,23 @@ async fn eval_command(
     // Get the harness type from engine_config for later use
     let harness_type = engine_config.harness;
 
-    // Read hook event from stdin and parse JSON once (reused for path resolution and evaluation)
-    let mut stdin_buffer = String::new();
-    io::stdin()
-        .read_to_string(&mut stdin_buffer)
-        .context("Failed to read hook event from stdin")?;
+    // Results about no particular file are located at the event file
+    let event_file = match &source {
+        EventSource::File(path) => Some(path.to_string_lossy().into_owned()),
+        _ => None,
+    };
+
+    // Read hook event and parse JSON once (reused for path resolution and evaluation)
+    let event_buffer = source.read(harness_type)?;
 
     let mut hook_event_json: serde_json::Value =
-        serde_json::from_str(&stdin_buffer).context("Failed to parse hook event JSON")?;
+        serde_json::from_str(&event_buffer).context("Failed to parse hook event JSON")?;
 
     // TELEMETRY: Capture raw event BEFORE any preprocessing mutations
     let raw_event_for_telemetry = hook_event_json.clone();
 
     info!("Processing harness: {:?}", harness_type);
-    debug!("Parsing hook event from stdin");
+    debug!("Parsed hook event");
 
     // Policy directory is resolved relative to the process cwd
     // All harnesses (Claude Code, Cursor, etc.) spawn hooks with cwd set to the project root
@@ -465,11 +1129,7 @@ async fn eval_command(
         }
         Err(e) => {
             error!("Fatal: Cupcake engine failed to initialize: {:#}", e);
-            eprintln!("\nError: Could not start the Cupcake engine.");
-            eprintln!(
-                "Please ensure the OPA CLI is installed and accessible in your system's PATH."
-            );
-            eprintln!("You can download it from: https://www.openpolicyagent.org/docs/latest/#running-opa");
+            print_engine_init_failure(&e);
             std::process::exit(1);
         }
     };
@@ -535,6 +1195,13 @@ async fn eval_command(
         None
     };
 
+    // The --decision object is always JSON
+    let exit_code_mode = !output.decision
+        && output
+            .mode
+            .unwrap_or_else(|| engine.output_mode(harness_type))
+            == harness::exit_code::OutputMode::ExitCode;
+
     // Evaluate policies for this hook event
     let decision = match engine
         .evaluate(&hook_event_json, telemetry_ctx.as_mut())
@@ -553,8 +1220,12 @@ async fn eval_command(
 
             // On error, return a safe "allow" with no modifications
             // This ensures we don't break the agent on engine failures
-            println!("{{}}");
-            if strict {
+            if exit_code_mode {
+                eprintln!("Cupcake policy evaluation failed: {e:#}");
+            } else {
+                println!("{{}}");
+            }
+            if output.strict {
                 std::process::exit(1);
             }
             return Ok(());
@@ -563,32 +1234,78 @@ async fn eval_command(
 
     // Format response based on harness type from engine config
     //
-    // NOTE: We re-parse stdin_buffer into typed event structs here. This is intentional:
+    // NOTE: We re-parse event_buffer into typed event structs here. This is intentional:
     // - Earlier we parsed to serde_json::Value for generic engine evaluation
     // - Here we need strongly-typed event structs for type-safe response formatting
     // - The format_response() methods require specific event types (ClaudeCodeEvent, CursorEvent, etc.)
     // - Attempting to convert Value -> typed struct would require the same deserialization work
-    let response = match harness_type {
-        cupcake_core::harness::types::HarnessType::ClaudeCode => {
-            let event = serde_json::from_str::<harness::events::claude_code::ClaudeCodeEvent>(
-                &stdin_buffer,
-            )?;
-            harness::ClaudeHarness::format_response(&event, &decision)?
-        }
-        cupcake_core::harness::types::HarnessType::Cursor => {
-            let event =
-                serde_json::from_str::<harness::events::cursor::CursorEvent>(&stdin_buffer)?;
-            harness::CursorHarness::format_response(&event, &decision)?
-        }
-        cupcake_core::harness::types::HarnessType::Factory => {
-            let event =
-                serde_json::from_str::<harness::events::factory::FactoryEvent>(&stdin_buffer)?;
-            harness::FactoryHarness::format_response(&event, &decision)?
-        }
-        cupcake_core::harness::types::HarnessType::OpenCode => {
-            let event =
-                serde_json::from_str::<harness::events::opencode::OpenCodeEvent>(&stdin_buffer)?;
-            harness::OpenCodeHarness::format_response(&event, &decision)?
+    let response_mode = |event_name: &str| harness::passthrough::ResponseMode {
+        permission_suggestions: engine.permission_suggestions(),
+        ..harness::passthrough::ResponseMode::resolve(
+            engine.response_passthrough(),
+            harness_type,
+            event_name,
+        )
+    };
+    let exit_code_response = if exit_code_mode {
+        let caps = harness::capabilities::HarnessCapabilities::parse(harness_type, &event_buffer)?;
+        let decision = response_mode(caps.event).apply_context(&decision);
+        Some(harness::exit_code::ExitCodeResponse::from_decision(
+            &decision, caps,
+        ))
+    } else {
+        None
+    };
+    let response = if output.decision {
+        serde_json::to_value(DecisionOutput::from(&decision))?
+    } else if let Some(exit_code_response) = &exit_code_response {
+        serde_json::to_value(exit_code_response)?
+    } else {
+        match harness_type {
+            cupcake_core::harness::types::HarnessType::ClaudeCode => {
+                let event = serde_json::from_str::<harness::events::claude_code::ClaudeCodeEvent>(
+                    &event_buffer,
+                )?;
+                let mode = response_mode(event.event_name());
+                harness::ClaudeHarness::format_response_with_mode(&event, &decision, mode)?
+            }
+            cupcake_core::harness::types::HarnessType::Cursor => {
+                let event =
+                    serde_json::from_str::<harness::events::cursor::CursorEvent>(&event_buffer)?;
+                let mode = response_mode(event.event_name());
+                harness::CursorHarness::format_response_with_mode(&event, &decision, mode)?
+            }
+            cupcake_core::harness::types::HarnessType::Factory => {
+                let event =
+                    serde_json::from_str::<harness::events::factory::FactoryEvent>(&event_buffer)?;
+                let mode = response_mode(event.event_name());
+                harness::FactoryHarness::format_response_with_mode(&event, &decision, mode)?
+            }
+            cupcake_core::harness::types::HarnessType::OpenCode => {
+                let event = serde_json::from_str::<harness::events::opencode::OpenCodeEvent>(
+                    &event_buffer,
+                )?;
+                let mode = response_mode(event.event_name());
+                harness::OpenCodeHarness::format_response_with_mode(&event, &decision, mode)?
+            }
+            cupcake_core::harness::types::HarnessType::Cline => {
+                let event =
+                    serde_json::from_str::<harness::events::cline::ClineEvent>(&event_buffer)?;
+                let mode = response_mode(event.event_name());
+                harness::ClineHarness::format_response_with_mode(&event, &decision, mode)?
+            }
+            cupcake_core::harness::types::HarnessType::Aider => {
+                let event =
+                    serde_json::from_str::<harness::events::aider::AiderEvent>(&event_buffer)?;
+                let mode = response_mode(event.event_name());
+                harness::AiderHarness::format_response_with_mode(&event, &decision, mode)?
+            }
+            cupcake_core::harness::types::HarnessType::Goose => {
+                let event =
+                    serde_json::from_str::<harness::events::goose::GooseEvent>(&event_buffer)?;
+                let mode = response_mode(event.event_name());
+                harness::GooseHarness::format_response_with_mode(&event, &decision, mode)?
+            }
         }
     };
 
@@ -600,17 +1317,261 @@ async fn eval_command(
         }
     }
 
+    if let Some(path) = &output.sarif {
+        let location = report::event_file_path(&hook_event_json).or(event_file);
+        let results: Vec<_> = report::decision_result(&DecisionOutput::from(&decision), location)
+            .into_iter()
+            .collect();
+        report::write_sarif(path, &results)?;
+    }
+
+    if let Some(response) = exit_code_response {
+        if let Some(stdout) = &response.stdout {
+            println!("{stdout}");
+        }
+        if let Some(stderr) = &response.stderr {
+            eprintln!("{stderr}");
+        }
+        std::process::exit(response.exit_code);
+    }
+
     // Output the response to stdout as JSON string
     println!("{}", serde_json::to_string(&response)?);
 
     // In strict mode, exit non-zero on blocking decisions
-    if strict && (decision.is_halt() || decision.is_blocking()) {
+    if output.strict && (decision.is_halt() || decision.is_blocking()) {
         std::process::exit(1);
     }
 
     Ok(())
 }
 
+/// One row of the `cupcake bench` report
+#[derive(Tabled)]
+struct BenchRow {
+    #[tabled(rename = "Stage")]
+    stage: &'static str,
+    #[tabled(rename = "Mean")]
+    mean: String,
+    #[tabled(rename = "p50")]
+    p50: String,
+    #[tabled(rename = "p95")]
+    p95: String,
+    #[tabled(rename = "Min")]
+    min: String,
+    #[tabled(rename = "Max")]
+    max: String,
+}
+
+impl BenchRow {
+    fn new(stage: &'static str, stats: &engine::profile::LatencyStats) -> Self {
+        Self {
+            stage,
+            mean: format_micros(stats.mean_us),
+            p50: format_micros(stats.p50_us),
+            p95: format_micros(stats.p95_us),
+            min: format_micros(stats.min_us),
+            max: format_micros(stats.max_us),
+        }
+    }
+}
+
+fn format_micros(us: u64) -> String {
+    if us >= 1000 {
+        format!("{:.2} ms", us as f64 / 1000.0)
+    } else {
+        format!("{us} µs")
+    }
+}
+
+async fn bench_command(
+    policy_dir: PathBuf,
+    source: EventSource,
+    iterations: usize,
+    init_runs: usize,
+    json: bool,
+    engine_config: engine::EngineConfig,
+) -> Result<()> {
+    use engine::profile::LatencyStats;
+    use std::time::Instant;
+
+    let event: serde_json::Value = serde_json::from_str(&source.read(engine_config.harness)?)
+        .context("Failed to parse hook event JSON")?;
+
+    let mut init_samples = Vec::with_capacity(init_runs);
+    let mut engine = None;
+    for _ in 0..init_runs {
+        let start = Instant::now();
+        let initialized = match engine::Engine::new_with_config(&policy_dir, engine_config.clone())
+            .await
+        {
+            Ok(e) => e,
+            Err(e) => {
+                error!("Fatal: Cupcake engine failed to initialize: {:#}", e);
+                eprintln!("\n❌ Error: Could not start the Cupcake engine.");
+                eprintln!(
+                        "   Please ensure the OPA CLI is installed and accessible in your system's PATH."
+                    );
+                std::process::exit(1);
+            }
+        };
+        init_samples.push(start.elapsed());
+        engine = Some(initialized);
+    }
+    let engine = engine.context("No engine was initialized")?;
+
+    // Warm up: compiles lazily loaded tiers and fetches external data
+    engine.evaluate(&event, None).await?;
+
+    let mut routing = Vec::with_capacity(iterations);
+    let mut signals = Vec::with_capacity(iterations);
+    let mut wasm = Vec::with_capacity(iterations);
+    let mut matched_policies = 0;
+    for _ in 0..iterations {
+        let timings = engine.profile_stages(&event).await?;
+        routing.push(timings.routing);
+        signals.push(timings.signals);
+        wasm.push(timings.wasm);
+        matched_policies = timings.matched_policies;
+    }
+
+    let mut end_to_end = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        engine.evaluate(&event, None).await?;
+        end_to_end.push(start.elapsed());
+    }
+
+    let stages = [
+        ("Engine init", LatencyStats::from_samples(&init_samples)),
+        ("Routing", LatencyStats::from_samples(&routing)),
+        ("Signals", LatencyStats::from_samples(&signals)),
+        ("WASM evaluation", LatencyStats::from_samples(&wasm)),
+        (
+            "End-to-end evaluate",
+            LatencyStats::from_samples(&end_to_end),
+        ),
+    ];
+
+    if json {
+        let output = serde_json::json!({
+            "event": event,
+            "matched_policies": matched_policies,
+            "stages": stages
+                .iter()
+                .map(|(stage, stats)| serde_json::json!({"stage": stage, "stats": stats}))
+                .collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    let event_name = event
+        .get("hookEventName")
+        .or_else(|| event.get("hook_event_name"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("?");
+    println!(
+        "Benchmarked {event_name} ({matched_policies} project policies matched, {iterations} iterations)\n"
+    );
+
+    let rows: Vec<BenchRow> = stages
+        .iter()
+        .map(|(stage, stats)| BenchRow::new(stage, stats))
+        .collect();
+    let table = Table::new(&rows)
+        .with(Style::rounded())
+        .with(Modify::new(Rows::first()).with(Alignment::center()))
+        .to_string();
+    println!("{table}");
+
+    // Each hook runs in a fresh process, so it pays for init as well as evaluation
+    let hook_us = stages[0].1.mean_us + stages[4].1.mean_us;
+    println!(
+        "\nEstimated hook latency (init + evaluate): {}",
+        format_micros(hook_us)
+    );
+    Ok(())
+}
+
+async fn route_command(
+    policy_dir: PathBuf,
+    event: String,
+    tool: Option<String>,
+    json: bool,
+    engine_config: engine::EngineConfig,
+) -> Result<()> {
+    let engine = match engine::Engine::new_with_config(&policy_dir, engine_config).await {
+        Ok(e) => e,
+        Err(e) => {
+            error!("Fatal: Cupcake engine failed to initialize: {:#}", e);
+            eprintln!("\n❌ Error: Could not start the Cupcake engine.");
+            eprintln!(
+                "   Please ensure the OPA CLI is installed and accessible in your system's PATH."
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let routes = engine.explain_route(&event, tool.as_deref());
+
+    if json {
+        let output = serde_json::json!({
+            "event": event,
+            "tool": tool,
+            "tiers": routes,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    let target = match &tool {
+        Some(tool) => format!("{event} / {tool}"),
+        None => event.clone(),
+    };
+    println!("Routing for {target}");
+    if let Some(route) = routes.first() {
+        println!("Keys consulted: {}", route.keys_consulted.join(", "));
+    }
+
+    for route in &routes {
+        let heading = match route.tier {
+            "global" => "Global",
+            "team" => "Team",
+            _ => "Project",
+        };
+        println!("\n=== {heading} Policies ===");
+        if route.policies.is_empty() {
+            println!("  (none)");
+        }
+        for policy in &route.policies {
+            println!("  - {} ({})", policy.package_name, policy.file_path);
+        }
+    }
+
+    if routes.iter().all(|r| r.policies.is_empty()) {
+        println!(
+            "\nNo policies match. Check the policy's required_events and required_tools metadata."
+        );
+    }
+
+    Ok(())
+}
+
+/// Explain an engine initialization failure on stderr
+///
+/// Compilation errors already name the policy file and line; the OPA install
+/// hint is only shown when OPA itself couldn't be run.
+fn print_engine_init_failure(e: &anyhow::Error) {
+    eprintln!("\nError: Could not start the Cupcake engine: {e:#}");
+    if format!("{e:#}").contains("Failed to execute OPA command") {
+        eprintln!("Please ensure the OPA CLI is installed and accessible in your system's PATH.");
+        eprintln!(
+            "You can download it from: https://www.openpolicyagent.org/docs/latest/#running-opa"
+        );
+    }
+}
+
 async fn verify_command(
     harness: cupcake_core::harness::types::HarnessType,
     policy_dir: PathBuf,
@@ -626,6 +1587,9 @@ async fn verify_command(
     match GlobalPaths::discover()? {
         Some(global_paths) if global_paths.is_initialized() => {
             println!("✅ Global config found at: {:?}", global_paths.root);
+            for layer in global_paths.layers.iter().skip(1) {
+                println!("   Layered over: {layer:?}");
+            }
 
             // Count global policies
             if let Ok(entries) = fs::read_dir(&global_paths.policies) {
@@ -656,60 +1620,274 @@ async fn verify_command(
         }
     }
 
-    // Initialize the engine - MUST succeed or we exit
-    println!("\n=== Project Configuration ===");
-    let engine = match engine::Engine::new(&policy_dir, harness).await {
-        Ok(e) => {
-            println!("✅ Engine initialized successfully");
-            e
-        }
-        Err(e) => {
-            error!("Fatal: Cupcake engine failed to initialize: {:#}", e);
-            eprintln!("\n❌ Error: Could not start the Cupcake engine.");
-            eprintln!(
-                "   Please ensure the OPA CLI is installed and accessible in your system's PATH."
-            );
-            eprintln!("   You can download it from: https://www.openpolicyagent.org/docs/latest/#running-opa");
-            std::process::exit(1);
+    // Initialize the engine - MUST succeed or we exit
+    println!("\n=== Project Configuration ===");
+    // Global and team policies normally compile in the background; verify
+    // compiles them here so broken ones fail it
+    let initialized = match engine::Engine::new(&policy_dir, harness).await {
+        Ok(e) => e.compile_deferred_tiers().await.map(|()| e),
+        Err(e) => Err(e),
+    };
+    let engine = match initialized {
+        Ok(e) => {
+            println!("✅ Engine initialized successfully");
+            e
+        }
+        Err(e) => {
+            error!("Fatal: Cupcake engine failed to initialize: {:#}", e);
+            print_engine_init_failure(&e);
+            std::process::exit(1);
+        }
+    };
+
+    // Display routing maps
+    println!("\n=== Project Routing Map ===");
+    for (key, policies) in engine.routing_map() {
+        println!("  {} -> {} policies", key, policies.len());
+        for policy in policies {
+            println!("    - {}", policy.package_name);
+        }
+    }
+
+    // Display global routing map if it exists
+    if !engine.global_routing_map().is_empty() {
+        println!("\n=== Global Routing Map ===");
+        for (key, policies) in engine.global_routing_map() {
+            println!("  {} -> {} policies", key, policies.len());
+            for policy in policies {
+                println!("    - {}", policy.package_name);
+            }
+        }
+    }
+
+    // Display team routing map if it exists
+    if !engine.team_routing_map().is_empty() {
+        println!("\n=== Team Routing Map ===");
+        for (key, policies) in engine.team_routing_map() {
+            println!("  {} -> {} policies", key, policies.len());
+            for policy in policies {
+                println!("    - {}", policy.package_name);
+            }
+        }
+    }
+
+    // Check WASM modules
+    println!("\n=== WASM Compilation ===");
+    if let Some(wasm) = engine.wasm_module() {
+        println!("  Project WASM: {} bytes ✅", wasm.len());
+    } else {
+        // This should never happen now - engine initialization would have failed
+        println!("  Project WASM: MISSING ❌");
+    }
+
+    if let Some(global_wasm) = engine
+        .global_wasm_module()
+        .await
+        .context("Failed to compile global policies")?
+    {
+        println!("  Global WASM:  {} bytes ✅", global_wasm.len());
+    } else {
+        println!("  Global WASM:  Not compiled (no global policies or only system policies)");
+    }
+
+    if let Some(team_wasm) = engine
+        .team_wasm_module()
+        .await
+        .context("Failed to compile team policies")?
+    {
+        println!("  Team WASM:    {} bytes ✅", team_wasm.len());
+    }
+
+    println!("\n✅ Verification complete!");
+    Ok(())
+}
+
+/// `cupcake verify --json`: machine-readable verification for CI gates and
+/// fleet monitoring
+///
+/// Covers global config presence and layers, policy counts per tier, routing
+/// maps, WASM sizes, watchdog status and the engine's initialization report.
+/// Global and team policies are compiled here as well, like the human-readable
+/// verify. Exits with status 1 when discovery, initialization or compilation fails.
+async fn verify_json_command(
+    harness: cupcake_core::harness::types::HarnessType,
+    policy_dir: PathBuf,
+) -> Result<()> {
+    use cupcake_core::engine::global_config::GlobalPaths;
+
+    let result = async {
+        let global = GlobalPaths::discover()?;
+        let engine = engine::Engine::new(&policy_dir, harness).await?;
+        let global_wasm = engine
+            .global_wasm_module()
+            .await
+            .context("Failed to compile global policies")?
+            .map(<[u8]>::len);
+        let team_wasm = engine
+            .team_wasm_module()
+            .await
+            .context("Failed to compile team policies")?
+            .map(<[u8]>::len);
+
+        let global_json = match &global {
+            Some(global) => {
+                let mut layers = Vec::new();
+                for layer in global.layer_paths() {
+                    let mut files = Vec::new();
+                    let harness_dir = layer.policies.join(harness.policy_dir());
+                    if harness_dir.is_dir() {
+                        find_rego_files(&harness_dir, &mut files)?;
+                    }
+                    layers.push(serde_json::json!({
+                        "root": layer.root,
+                        "initialized": layer.is_initialized(),
+                        "policy_files": files.len(),
+                    }));
+                }
+                serde_json::json!({"found": true, "root": global.root, "layers": layers})
+            }
+            None => serde_json::json!({"found": false, "root": null, "layers": []}),
+        };
+
+        let report = engine.init_report();
+        let policy_counts: serde_json::Map<String, serde_json::Value> = report
+            .tiers
+            .iter()
+            .map(|tier| (tier.tier.to_string(), tier.policies_loaded.len().into()))
+            .collect();
+
+        let routing = |map: &std::collections::HashMap<String, Vec<engine::PolicyUnit>>| {
+            map.iter()
+                .map(|(key, policies)| {
+                    let mut packages: Vec<String> =
+                        policies.iter().map(|p| p.package_name.clone()).collect();
+                    packages.sort();
+                    (key.clone(), packages)
+                })
+                .collect::<std::collections::BTreeMap<_, _>>()
+        };
+
+        anyhow::Ok(serde_json::json!({
+            "ok": true,
+            "harness": harness.as_str(),
+            "global": global_json,
+            "policies": policy_counts,
+            "routing": {
+                "global": routing(engine.global_routing_map()),
+                "team": routing(engine.team_routing_map()),
+                "project": routing(engine.routing_map()),
+            },
+            "wasm": {
+                "global": global_wasm,
+                "team": team_wasm,
+                "project": engine.wasm_module().map(<[u8]>::len),
+            },
+            "watchdog": {
+                "configured": engine.watchdog_configured(),
+                "active": engine.watchdog().is_some(),
+                "backend": engine.watchdog().map(|w| w.backend_name()),
+            },
+            "init": report,
+        }))
+    }
+    .await;
+
+    let output = match &result {
+        Ok(output) => output.clone(),
+        Err(e) => serde_json::json!({
+            "ok": false,
+            "harness": harness.as_str(),
+            "error": format!("{e:#}"),
+        }),
+    };
+    println!("{}", serde_json::to_string_pretty(&output)?);
+
+    if result.is_err() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+async fn harness_status_command(
+    harness: Option<HarnessType>,
+    global: bool,
+    repair: bool,
+    json: bool,
+) -> Result<()> {
+    let policy_dir = if global {
+        use cupcake_core::engine::global_config::GlobalPaths;
+        match GlobalPaths::discover()? {
+            Some(paths) => paths.root,
+            None => bail!("No global configuration found. Run 'cupcake init --global' first."),
         }
+    } else {
+        PathBuf::from(".cupcake")
     };
 
-    // Display routing maps
-    println!("\n=== Project Routing Map ===");
-    for (key, policies) in engine.routing_map() {
-        println!("  {} -> {} policies", key, policies.len());
-        for policy in policies {
-            println!("    - {}", policy.package_name);
-        }
-    }
+    let explicit = harness.is_some();
+    let harnesses = match harness {
+        Some(harness) => vec![harness],
+        None => vec![
+            HarnessType::Claude,
+            HarnessType::Cursor,
+            HarnessType::Factory,
+            HarnessType::OpenCode,
+            HarnessType::Cline,
+            HarnessType::Aider,
+            HarnessType::Goose,
+        ],
+    };
 
-    // Display global routing map if it exists
-    if !engine.global_routing_map().is_empty() {
-        println!("\n=== Global Routing Map ===");
-        for (key, policies) in engine.global_routing_map() {
-            println!("  {} -> {} policies", key, policies.len());
-            for policy in policies {
-                println!("    - {}", policy.package_name);
-            }
+    let mut statuses = Vec::new();
+    for harness in &harnesses {
+        let status = harness_config::harness_status(harness, &policy_dir, global, repair).await?;
+        if status.installed || explicit {
+            statuses.push(status);
         }
     }
+    let drifted = statuses.iter().any(|s| !s.installed || !s.drift.is_empty());
 
-    // Check WASM modules
-    println!("\n=== WASM Compilation ===");
-    if let Some(wasm) = engine.wasm_module() {
-        println!("  Project WASM: {} bytes ✅", wasm.len());
+    if json {
+        println!("{}", serde_json::to_string_pretty(&statuses)?);
+    } else if statuses.is_empty() {
+        println!(
+            "No harness integrations found. Run 'cupcake init --harness <harness>' to set one up."
+        );
     } else {
-        // This should never happen now - engine initialization would have failed
-        println!("  Project WASM: MISSING ❌");
+        for status in &statuses {
+            let state = if !status.installed {
+                "not installed"
+            } else if !status.drift.is_empty() {
+                "drift"
+            } else if status.repaired {
+                "repaired"
+            } else {
+                "ok"
+            };
+            println!(
+                "{}: {} ({})",
+                status.harness,
+                state,
+                status.settings_path.display()
+            );
+            for drift in &status.drift {
+                let note = if drift.repairable {
+                    ""
+                } else {
+                    " [not repairable]"
+                };
+                println!("  - {}: {}{}", drift.event, drift.problem, note);
+            }
+        }
+        if drifted && !repair && statuses.iter().flat_map(|s| &s.drift).any(|d| d.repairable) {
+            println!();
+            println!("Run 'cupcake harness status --repair' to fix the Cupcake hooks.");
+        }
     }
 
-    if let Some(global_wasm) = engine.global_wasm_module() {
-        println!("  Global WASM:  {} bytes ✅", global_wasm.len());
-    } else {
-        println!("  Global WASM:  Not compiled (no global policies or only system policies)");
+    if drifted {
+        std::process::exit(1);
     }
-
-    println!("\n✅ Verification complete!");
     Ok(())
 }
 
@@ -736,6 +1914,55 @@ async fn init_command(global: bool, harness: Option<HarnessType>) -> Result<()>
     }
 }
 
+/// Install git hooks that assess manual changes, initializing the project if needed
+async fn init_git_hooks(harness: Option<HarnessType>) -> Result<()> {
+    let harness = match harness {
+        Some(h) => h,
+        None => {
+            // Use the project's harness when there is exactly one
+            let initialized: Vec<HarnessType> = HarnessType::value_variants()
+                .iter()
+                .filter(|h| {
+                    Path::new(".cupcake/policies")
+                        .join(harness_dir_name(h))
+                        .exists()
+                })
+                .cloned()
+                .collect();
+            match initialized.as_slice() {
+                [] if !Path::new(".cupcake").exists() => prompt_harness_selection()?,
+                [only] => only.clone(),
+                _ => bail!("Use --harness to choose whose policies the git hooks apply"),
+            }
+        }
+    };
+    if !Path::new(".cupcake").exists() {
+        init_project_config(harness.clone()).await?;
+        println!();
+    }
+
+    let harness_name = harness_dir_name(&harness);
+    for hook in git_hooks::install(harness_name)? {
+        match hook {
+            git_hooks::Installed::Written(path) => {
+                println!("✅ Installed {}", path.display());
+            }
+            git_hooks::Installed::Kept(path) => {
+                println!(
+                    "⚠️  Kept existing {} - it was not installed by Cupcake",
+                    path.display()
+                );
+                println!(
+                    "   Add this to it: {}",
+                    git_hooks::assess_command(harness_name, ".cupcake", "...")
+                );
+            }
+        }
+    }
+    println!("   Commits and pushes are now checked against .cupcake/policies/{harness_name}/");
+    Ok(())
+}
+
 async fn init_global_config(harness: Option<HarnessType>) -> Result<()> {
     use cupcake_core::engine::global_config::GlobalPaths;
 
@@ -768,12 +1995,7 @@ async fn init_global_config(harness: Option<HarnessType>) -> Result<()> {
                     .join("cupcake")
             };
 
-            GlobalPaths {
-                root: config_dir.clone(),
-                policies: config_dir.join("policies"),
-                rulebook: config_dir.join("rulebook.yml"),
-                signals: config_dir.join("signals"),
-            }
+            GlobalPaths::at(config_dir)
         }
     };
 
@@ -810,6 +2032,7 @@ denials := collect_verbs("deny")
 blocks := collect_verbs("block")
 asks := collect_verbs("ask")
 modifications := collect_verbs("modify")
+output_filters := collect_verbs("filter_output")
 add_context := collect_verbs("add_context")
 
 # Main evaluation entrypoint
@@ -819,6 +2042,7 @@ evaluate := {
     "blocks": blocks,
     "asks": asks,
     "modifications": modifications,
+    "output_filters": output_filters,
     "add_context": add_context
 }
 
@@ -864,6 +2088,7 @@ denials := collect_verbs("deny")
 blocks := collect_verbs("block")
 asks := collect_verbs("ask")
 modifications := collect_verbs("modify")
+output_filters := collect_verbs("filter_output")
 add_context := collect_verbs("add_context")
 
 # Main evaluation entrypoint
@@ -873,6 +2098,7 @@ evaluate := {
     "blocks": blocks,
     "asks": asks,
     "modifications": modifications,
+    "output_filters": output_filters,
     "add_context": add_context
 }
 
@@ -918,6 +2144,7 @@ denials := collect_verbs("deny")
 blocks := collect_verbs("block")
 asks := collect_verbs("ask")
 modifications := collect_verbs("modify")
+output_filters := collect_verbs("filter_output")
 add_context := collect_verbs("add_context")
 
 # Main evaluation entrypoint
@@ -927,6 +2154,7 @@ evaluate := {
     "blocks": blocks,
     "asks": asks,
     "modifications": modifications,
+    "output_filters": output_filters,
     "add_context": add_context
 }
 
@@ -972,6 +2200,7 @@ denials := collect_verbs("deny")
 blocks := collect_verbs("block")
 asks := collect_verbs("ask")
 modifications := collect_verbs("modify")
+output_filters := collect_verbs("filter_output")
 add_context := collect_verbs("add_context")
 
 # Main evaluation entrypoint
@@ -981,6 +2210,7 @@ evaluate := {
     "blocks": blocks,
     "asks": asks,
     "modifications": modifications,
+    "output_filters": output_filters,
     "add_context": add_context
 }
 
@@ -1145,6 +2375,31 @@ import rego.v1
         fs::write(opencode_builtins_dir.join(filename), content)?;
     }
 
+    // Deploy Cline, Aider and Goose global builtin policies - Claude Code's, as their
+    // events are mapped onto Claude Code's tool names and fields
+    let mapped_global_builtins = vec![
+        (
+            "system_protection.rego",
+            CLAUDE_GLOBAL_SYSTEM_PROTECTION_POLICY,
+        ),
+        (
+            "sensitive_data_protection.rego",
+            CLAUDE_GLOBAL_SENSITIVE_DATA_POLICY,
+        ),
+        (
+            "cupcake_exec_protection.rego",
+            CLAUDE_GLOBAL_CUPCAKE_EXEC_POLICY,
+        ),
+    ];
+
+    for harness_dir in ["cline", "aider", "goose"] {
+        let builtins_dir = global_paths.policies.join(harness_dir).join("builtins");
+        fs::create_dir_all(&builtins_dir)?;
+        for (filename, content) in &mapped_global_builtins {
+            fs::write(builtins_dir.join(filename), content)?;
+        }
+    }
+
     println!("✅ Initialized global Cupcake configuration");
     println!("   Location: {:?}", global_paths.root);
     println!("   Policies: {:?}", global_paths.policies);
@@ -1236,9 +2491,37 @@ async fn init_project_config(harness: HarnessType) -> Result<()> {
             info!(".cupcake directory permissions set to 0o700 (owner-only access)");
         }
 
-        #[cfg(not(unix))]
+        // Windows equivalent of 0o700: drop inherited ACEs and grant full control
+        // to the current user and SYSTEM only. icacls ships with every Windows
+        // install; if it fails (e.g. no USERNAME in a service context) warn rather
+        // than abort init.
+        #[cfg(windows)]
         {
-            eprintln!("Warning: .cupcake directory permissions should be restricted manually on non-Unix systems");
+            let user = match (std::env::var("USERDOMAIN"), std::env::var("USERNAME")) {
+                (Ok(domain), Ok(name)) => Some(format!("{domain}\\{name}")),
+                (Err(_), Ok(name)) => Some(name),
+                _ => None,
+            };
+            let restricted = user.is_some_and(|user| {
+                std::process::Command::new("icacls")
+                    .arg(cupcake_dir)
+                    .args(["/inheritance:r", "/grant:r"])
+                    .arg(format!("{user}:(OI)(CI)F"))
+                    .arg("*S-1-5-18:(OI)(CI)F")
+                    .stdout(std::process::Stdio::null())
+                    .status()
+                    .is_ok_and(|status| status.success())
+            });
+            if restricted {
+                info!(".cupcake directory ACL restricted to the current user and SYSTEM");
+            } else {
+                eprintln!("Warning: failed to restrict .cupcake directory ACL; restrict it manually with icacls");
+            }
+        }
+
+        #[cfg(not(any(unix, windows)))]
+        {
+            eprintln!("Warning: .cupcake directory permissions should be restricted manually on this platform");
         }
 
         // Write the base rulebook configuration
@@ -1287,7 +2570,8 @@ async fn init_project_config(harness: HarnessType) -> Result<()> {
 /// Deploy builtin policies for a specific harness
 fn deploy_harness_builtins(harness: &HarnessType, harness_name: &str) -> Result<()> {
     let builtins_to_deploy: Vec<(&str, &str)> = match harness {
-        HarnessType::Claude => vec![
+        // Cline, Aider and Goose events are mapped onto Claude Code's tool names and fields
+        HarnessType::Claude | HarnessType::Cline | HarnessType::Aider | HarnessType::Goose => vec![
             (
                 "claude_code_always_inject_on_prompt.rego",
                 CLAUDE_ALWAYS_INJECT_POLICY,
@@ -1298,15 +2582,14 @@ fn deploy_harness_builtins(harness: &HarnessType, harness_name: &str) -> Result<
                 "rulebook_security_guardrails.rego",
                 CLAUDE_RULEBOOK_SECURITY_POLICY,
             ),
-            ("protected_paths.rego", CLAUDE_PROTECTED_PATHS_POLICY),
-            (
-                "git_block_no_verify.rego",
-                CLAUDE_GIT_BLOCK_NO_VERIFY_POLICY,
-            ),
             (
                 "claude_code_enforce_full_file_read.rego",
                 CLAUDE_ENFORCE_FULL_FILE_READ_POLICY,
             ),
+            (
+                "claude_code_watchdog_stop_review.rego",
+                CLAUDE_WATCHDOG_STOP_REVIEW_POLICY,
+            ),
         ],
         HarnessType::Cursor => vec![
             ("git_pre_check.rego", CURSOR_GIT_PRE_CHECK_POLICY),
@@ -1315,11 +2598,6 @@ fn deploy_harness_builtins(harness: &HarnessType, harness_name: &str) -> Result<
                 "rulebook_security_guardrails.rego",
                 CURSOR_RULEBOOK_SECURITY_POLICY,
             ),
-            ("protected_paths.rego", CURSOR_PROTECTED_PATHS_POLICY),
-            (
-                "git_block_no_verify.rego",
-                CURSOR_GIT_BLOCK_NO_VERIFY_POLICY,
-            ),
             // Note: enforce_full_file_read intentionally NOT included - incompatible with Cursor
         ],
         HarnessType::Factory => vec![
@@ -1333,11 +2611,6 @@ fn deploy_harness_builtins(harness: &HarnessType, harness_name: &str) -> Result<
                 "rulebook_security_guardrails.rego",
                 FACTORY_RULEBOOK_SECURITY_POLICY,
             ),
-            ("protected_paths.rego", FACTORY_PROTECTED_PATHS_POLICY),
-            (
-                "git_block_no_verify.rego",
-                FACTORY_GIT_BLOCK_NO_VERIFY_POLICY,
-            ),
             (
                 "factory_enforce_full_file_read.rego",
                 FACTORY_ENFORCE_FULL_FILE_READ_POLICY,
@@ -1354,11 +2627,6 @@ fn deploy_harness_builtins(harness: &HarnessType, harness_name: &str) -> Result<
                 "rulebook_security_guardrails.rego",
                 OPENCODE_RULEBOOK_SECURITY_POLICY,
             ),
-            ("protected_paths.rego", OPENCODE_PROTECTED_PATHS_POLICY),
-            (
-                "git_block_no_verify.rego",
-                OPENCODE_GIT_BLOCK_NO_VERIFY_POLICY,
-            ),
             (
                 "opencode_enforce_full_file_read.rego",
                 OPENCODE_ENFORCE_FULL_FILE_READ_POLICY,
@@ -1372,10 +2640,31 @@ fn deploy_harness_builtins(harness: &HarnessType, harness_name: &str) -> Result<
             .with_context(|| format!("Failed to create builtin: {filename}"))?;
     }
 
+    // Builtins written once as templates, rendered for the harnesses they support
+    let harness_type = cupcake_core::harness::types::HarnessType::from(harness.clone());
+    for (filename, source) in BUILTIN_TEMPLATES {
+        let supported = engine::template::supported_harnesses(source).unwrap_or_default();
+        if !supported.contains(&harness_type) {
+            continue;
+        }
+        let content = engine::template::render(source, harness_type)
+            .with_context(|| format!("Failed to render builtin template: {filename}"))?;
+        let path = format!(".cupcake/policies/{harness_name}/builtins/{filename}");
+        fs::write(&path, content)
+            .with_context(|| format!("Failed to create builtin: {filename}"))?;
+    }
+
     Ok(())
 }
 
-async fn validate_command(policy_dir: PathBuf, json: bool) -> Result<()> {
+async fn validate_command(
+    policy_dir: PathBuf,
+    json: bool,
+    fix: bool,
+    strict: bool,
+    sarif: Option<PathBuf>,
+    opa_path: Option<PathBuf>,
+) -> Result<()> {
     info!("Validating policies in directory: {:?}", policy_dir);
 
     if !policy_dir.exists() {
@@ -1405,15 +2694,46 @@ async fn validate_command(policy_dir: PathBuf, json: bool) -> Result<()> {
         }
     }
 
+    // Apply fixes before validating so results reflect the fixed files
+    let mut fixed_files = Vec::new();
+    if fix {
+        let fixer = validator::fixes::PolicyFixer::new();
+        for policy in &mut policies {
+            if let Some(fixed) = fixer.fix_policy(policy) {
+                if !json {
+                    print_fix_diff(&policy.path, &policy.content, &fixed.content);
+                }
+                policy.update_content(fixed.content)?;
+                policy
+                    .write_to_file()
+                    .with_context(|| format!("Failed to write fixed policy {:?}", policy.path))?;
+                fixed_files.push((policy.path.clone(), fixed.applied));
+            }
+        }
+        if !json {
+            println!("Fixed {} file(s)\n", fixed_files.len());
+        }
+    }
+
     // Create validator
     let validator = validator::PolicyValidator::new();
 
     // Validate policies
-    let result = validator.validate_policies(&policies);
+    let mut result = validator.validate_policies(&policies);
+
+    // Compile check catches errors that would otherwise surface at first eval
+    match validator::opa_check::check_policies(&policies, opa_path, strict) {
+        Ok(issues) => result.add_issues(issues),
+        Err(e) => eprintln!("Warning: Skipping OPA compile check: {e:#}"),
+    }
+
+    if let Some(path) = &sarif {
+        report::write_sarif(path, &validation_sarif_results(&validator, &result))?;
+    }
 
     // Output results
     if json {
-        let json_output = serde_json::json!({
+        let mut json_output = serde_json::json!({
             "total_files": policies.len(),
             "total_errors": result.total_errors,
             "total_warnings": result.total_warnings,
@@ -1433,6 +2753,12 @@ async fn validate_command(policy_dir: PathBuf, json: bool) -> Result<()> {
                 })
             }).collect::<Vec<_>>()
         });
+        if fix {
+            json_output["fixed"] = serde_json::json!(fixed_files
+                .iter()
+                .map(|(path, applied)| serde_json::json!({ "path": path, "fixes": applied }))
+                .collect::<Vec<_>>());
+        }
         println!("{}", serde_json::to_string_pretty(&json_output)?);
     } else {
         // Human-readable output
@@ -1447,6 +2773,34 @@ async fn validate_command(policy_dir: PathBuf, json: bool) -> Result<()> {
     Ok(())
 }
 
+/// Lint issues as SARIF results, one per issue
+fn validation_sarif_results(
+    validator: &validator::PolicyValidator,
+    result: &validator::ValidationResult,
+) -> Vec<report::SarifResult> {
+    result
+        .policies
+        .iter()
+        .flat_map(|policy| {
+            policy.issues.iter().map(|issue| report::SarifResult {
+                rule_id: issue.rule_id.to_string(),
+                rule_description: validator
+                    .describe_rule(issue.rule_id)
+                    .unwrap_or(issue.rule_id)
+                    .to_string(),
+                level: match issue.severity {
+                    validator::Severity::Error => report::SarifLevel::Error,
+                    validator::Severity::Warning => report::SarifLevel::Warning,
+                    validator::Severity::Info => report::SarifLevel::Note,
+                },
+                message: issue.message.clone(),
+                path: Some(policy.path.to_string_lossy().into_owned()),
+                line: issue.line,
+            })
+        })
+        .collect()
+}
+
 fn find_rego_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
     for entry in std::fs::read_dir(dir)? {
         let entry = entry?;
@@ -1466,6 +2820,15 @@ fn find_rego_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
     Ok(())
 }
 
+fn print_fix_diff(path: &Path, original: &str, fixed: &str) {
+    let path = path.display().to_string();
+    let diff = similar::TextDiff::from_lines(original, fixed);
+    print!(
+        "{}",
+        diff.unified_diff().context_radius(3).header(&path, &path)
+    );
+}
+
 fn print_validation_results(result: &validator::ValidationResult) {
     use validator::Severity;
 
@@ -1539,6 +2902,171 @@ struct PolicyTableRow {
     policy_type: String,
 }
 
+/// Package name and routing directive of a policy, skipping system entrypoints
+fn routed_policy(content: &str) -> Option<engine::routing_graph::RoutedPolicy> {
+    let package_name = engine::metadata::extract_package_name(content).ok()?;
+    // System entrypoints aggregate decisions rather than handle events
+    if package_name.starts_with("cupcake.system") {
+        return None;
+    }
+    let routing = engine::metadata::parse_metadata(content)
+        .ok()
+        .flatten()
+        .and_then(|m| m.custom.routing);
+    Some(engine::routing_graph::RoutedPolicy {
+        package_name,
+        routing,
+    })
+}
+
+/// Load the routing of every policy under a directory
+fn load_routed_policies(dir: &Path) -> Result<Vec<engine::routing_graph::RoutedPolicy>> {
+    let mut policy_files = Vec::new();
+    find_rego_files(dir, &mut policy_files)?;
+    policy_files.sort();
+
+    let mut policies = Vec::new();
+    for path in policy_files {
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read policy file: {path:?}"))?;
+        policies.extend(routed_policy(&content));
+    }
+
+    Ok(policies)
+}
+
+/// Load the routing of every policy under a directory as of a git revision
+fn load_routed_policies_at_rev(
+    rev: &str,
+    dir: &Path,
+) -> Result<Vec<engine::routing_graph::RoutedPolicy>> {
+    let git = |args: &[&std::ffi::OsStr]| -> Result<String> {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .output()
+            .context("Failed to execute git")?;
+        if !output.status.success() {
+            bail!(
+                "git failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+
+    // Paths are listed relative to the current directory
+    let listing = git(&[
+        "ls-tree".as_ref(),
+        "-r".as_ref(),
+        "--name-only".as_ref(),
+        rev.as_ref(),
+        "--".as_ref(),
+        dir.as_os_str(),
+    ])
+    .with_context(|| format!("Failed to list policies at revision '{rev}'"))?;
+
+    let mut policies = Vec::new();
+    for path in listing.lines().filter(|p| p.ends_with(".rego")) {
+        let object = format!("{rev}:./{path}");
+        let content = git(&["show".as_ref(), object.as_ref()])
+            .with_context(|| format!("Failed to read {path} at revision '{rev}'"))?;
+        policies.extend(routed_policy(&content));
+    }
+
+    Ok(policies)
+}
+
+fn inspect_diff_command(policy_dir: PathBuf, base: String, json: bool) -> Result<()> {
+    if !policy_dir.exists() {
+        eprintln!("Error: Policy directory does not exist: {policy_dir:?}");
+        std::process::exit(1);
+    }
+
+    // A directory is compared directly; anything else is treated as a git revision
+    let base_policies = if Path::new(&base).is_dir() {
+        load_routed_policies(Path::new(&base))?
+    } else {
+        load_routed_policies_at_rev(&base, &policy_dir)?
+    };
+    let current_policies = load_routed_policies(&policy_dir)?;
+    let diff = engine::routing_diff::RoutingDiff::between(&base_policies, &current_policies);
+
+    if json {
+        let output = serde_json::json!({
+            "base": base,
+            "diff": diff,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if diff.is_empty() {
+        println!("No routing changes compared to {base}");
+        return Ok(());
+    }
+
+    println!("Routing changes compared to {base}:\n");
+    if !diff.added_policies.is_empty() || !diff.removed_policies.is_empty() {
+        println!("Policies:");
+        for package in &diff.added_policies {
+            println!("  + {package}");
+        }
+        for package in &diff.removed_policies {
+            println!("  - {package}");
+        }
+        println!();
+    }
+    if !diff.added_routes.is_empty() || !diff.removed_routes.is_empty() {
+        println!("Routes:");
+        for route in &diff.added_routes {
+            println!("  + {} → {}", route.key, route.package);
+        }
+        for route in &diff.removed_routes {
+            println!("  - {} → {}", route.key, route.package);
+        }
+    }
+
+    Ok(())
+}
+
+fn inspect_graph_command(
+    policy_dir: PathBuf,
+    format: engine::routing_graph::GraphFormat,
+    global_config: Option<PathBuf>,
+    team_config: Option<PathBuf>,
+) -> Result<()> {
+    use cupcake_core::engine::global_config::GlobalPaths;
+    use cupcake_core::engine::team_config::TeamPaths;
+
+    if !policy_dir.exists() {
+        eprintln!("Error: Policy directory does not exist: {policy_dir:?}");
+        std::process::exit(1);
+    }
+
+    // Layers in evaluation order: global, team, project
+    let mut graph = engine::routing_graph::RoutingGraph::new();
+    if let Some(global) = GlobalPaths::discover_with_override(global_config)? {
+        let mut routed = Vec::new();
+        for layer in global.layer_paths() {
+            if layer.policies.exists() {
+                routed.extend(load_routed_policies(&layer.policies)?);
+            }
+        }
+        if !routed.is_empty() {
+            graph.add_layer("global", routed);
+        }
+    }
+    if let Some(team) = TeamPaths::discover_with_override(team_config)? {
+        if team.policies.exists() {
+            graph.add_layer("team", load_routed_policies(&team.policies)?);
+        }
+    }
+    graph.add_layer("project", load_routed_policies(&policy_dir)?);
+
+    print!("{}", graph.render(format));
+    Ok(())
+}
+
 async fn inspect_command(policy_dir: PathBuf, json: bool, table: bool) -> Result<()> {
     info!("Inspecting policies in directory: {:?}", policy_dir);
 
@@ -1600,6 +3128,7 @@ async fn inspect_command(policy_dir: PathBuf, json: bool, table: bool) -> Result
                 "required_events": routing.map(|r| r.required_events.clone()).unwrap_or_default(),
                 "required_tools": routing.map(|r| r.required_tools.clone()).unwrap_or_default(),
                 "required_signals": routing.map(|r| r.required_signals.clone()).unwrap_or_default(),
+                "required_watchdog": routing.is_some_and(|r| r.required_watchdog),
             },
             "metadata": {
                 "title": metadata.title,
@@ -1767,65 +3296,104 @@ async fn inspect_command(policy_dir: PathBuf, json: bool, table: bool) -> Result
     Ok(())
 }
 
-async fn onboard_command() -> Result<()> {
-    // Display warning about what cupcake onboard will do
-    println!("┌─────────────────────────────────────────────────────────────────┐");
-    println!("│                    Cupcake Onboard Wizard                       │");
-    println!("├─────────────────────────────────────────────────────────────────┤");
-    println!("│                                                                 │");
-    println!("│  Converts rule files → Cupcake policies using Claude.           │");
-    println!("│                                                                 │");
-    println!("│  API Key: Uses ANTHROPIC_API_KEY from environment.              │");
-    println!("│           Claude Code users: already set.                       │");
-    println!("│                                                                 │");
-    println!("│  Cost: ~5% of Anthropic's weekly token limit.                   │");
-    println!("│                                                                 │");
-    println!("└─────────────────────────────────────────────────────────────────┘");
+fn export_command(
+    policy_dir: PathBuf,
+    output: PathBuf,
+    name: Option<String>,
+    version: String,
+) -> Result<()> {
+    let name = match name {
+        Some(name) => name,
+        None => std::env::current_dir()?
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("cupcake")
+            .to_string(),
+    };
+    let pack = pack::export(&policy_dir, name, version)?;
+    let json = serde_json::to_string_pretty(&pack)?;
+    fs::write(&output, json + "\n")
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+
+    println!(
+        "Exported {} {} ({} files, harnesses: {}) to {}",
+        pack.name,
+        pack.version,
+        pack.files.len(),
+        if pack.harnesses.is_empty() {
+            "none".to_string()
+        } else {
+            pack.harnesses.join(", ")
+        },
+        output.display()
+    );
+    println!("Digest: {}", pack.digest);
     println!();
+    println!("Import it elsewhere with:");
+    println!(
+        "  cupcake import {} --sha256 {}",
+        output.display(),
+        pack.digest
+    );
+    Ok(())
+}
 
-    // Prompt for confirmation
-    print!("Continue? [Y/n] ");
-    io::stdout().flush()?;
-
-    let mut input = String::new();
-    io::stdin().read_line(&mut input)?;
-    let input = input.trim().to_lowercase();
-
-    // Accept empty (just Enter), 'y', or 'yes'
-    if !input.is_empty() && input != "y" && input != "yes" {
-        println!("Aborted.");
-        return Ok(());
+fn import_command(
+    pack_path: PathBuf,
+    policy_dir: PathBuf,
+    options: pack::ImportOptions,
+) -> Result<()> {
+    if !policy_dir.is_dir() {
+        bail!(
+            "{} doesn't exist. Run `cupcake init` first",
+            policy_dir.display()
+        );
     }
+    let pack = pack::Pack::load(&pack_path)?;
+    let plan = pack::import(&pack, &policy_dir, &options)?;
 
-    println!();
-    println!("Launching onboard wizard...");
-    println!();
-
-    // Try to find and run cupcake-onboard
-    // First, try the local development path (for unpublished package)
-    let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
-    let local_cli_path = workspace_root.join("cupcake-onboard/dist/cli.js");
-
-    let status = if local_cli_path.exists() {
-        // Use local development version
-        info!("Using local cupcake-onboard from: {:?}", local_cli_path);
-        ProcessCommand::new("node")
-            .arg(&local_cli_path)
-            .status()
-            .context("Failed to run cupcake-onboard")?
-    } else {
-        // Fall back to npx for published version
-        info!("Using npx to run @eqtylab/cupcake-onboard");
-        ProcessCommand::new("npx")
-            .args(["@eqtylab/cupcake-onboard"])
-            .status()
-            .context("Failed to run cupcake-onboard via npx. Is Node.js installed?")?
-    };
-
-    if !status.success() {
-        return Err(anyhow!("cupcake-onboard exited with error"));
+    println!(
+        "{} {} {} (exported by Cupcake {}, digest {})",
+        if options.dry_run {
+            "Would import"
+        } else {
+            "Imported"
+        },
+        pack.name,
+        pack.version,
+        pack.cupcake_version,
+        pack.digest
+    );
+    for (path, action) in &plan {
+        let label = match action {
+            pack::Action::Create => "create",
+            pack::Action::Update => "update",
+            pack::Action::Unchanged => "unchanged",
+            pack::Action::Conflict => "CONFLICT",
+        };
+        println!("  {label:<9} {}", path.display());
     }
 
+    for harness in &pack.harnesses {
+        if !policy_dir
+            .join("policies")
+            .join(harness)
+            .join("builtins")
+            .is_dir()
+        {
+            println!();
+            println!(
+                "Note: {harness} isn't initialized here - run `cupcake init --harness {harness}`"
+            );
+        }
+    }
+    if !options.dry_run {
+        println!();
+        println!(
+            "Check the result with `cupcake verify --harness <HARNESS> --policy-dir {}`",
+            policy_dir.display()
+        );
+    }
     Ok(())
 }
 
@@ -1855,6 +3423,7 @@ evaluate := decision_set if {
         "blocks": collect_verbs("block"),
         "asks": collect_verbs("ask"),
         "modifications": collect_verbs("modify"),
+        "output_filters": collect_verbs("filter_output"),
         "add_context": collect_verbs("add_context")
     }
 }
@@ -1887,6 +3456,70 @@ default collect_verbs(_) := []
 const EXAMPLE_POLICY_TEMPLATE: &str = include_str!("../../fixtures/example.rego");
 const RULEBOOK_TEMPLATE: &str = include_str!("../../fixtures/init/base-config.yml");
 
+// Builtin policy templates, shared by every harness they list
+// Note: mcp_allowlist is not rendered for Cursor (MCP events omit the server name) or
+// OpenCode (MCP tool names can't be split by server)
+// Note: license_header is not rendered for Cursor - afterFileEdit can't return context
+// Note: binary_file_guard is not rendered for Cursor - Cursor has no pre-edit hook
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "protected_paths.rego",
+        include_str!("../../fixtures/builtins/protected_paths.rego"),
+    ),
+    (
+        "dependency_guard.rego",
+        include_str!("../../fixtures/builtins/dependency_guard.rego"),
+    ),
+    (
+        "mass_deletion_guard.rego",
+        include_str!("../../fixtures/builtins/mass_deletion_guard.rego"),
+    ),
+    (
+        "prompt_injection_guard.rego",
+        include_str!("../../fixtures/builtins/prompt_injection_guard.rego"),
+    ),
+    (
+        "env_exfiltration_guard.rego",
+        include_str!("../../fixtures/builtins/env_exfiltration_guard.rego"),
+    ),
+    (
+        "git_block_no_verify.rego",
+        include_str!("../../fixtures/builtins/git_block_no_verify.rego"),
+    ),
+    (
+        "container_guard.rego",
+        include_str!("../../fixtures/builtins/container_guard.rego"),
+    ),
+    (
+        "terraform_plan_gate.rego",
+        include_str!("../../fixtures/builtins/terraform_plan_gate.rego"),
+    ),
+    (
+        "change_freeze.rego",
+        include_str!("../../fixtures/builtins/change_freeze.rego"),
+    ),
+    (
+        "rate_limit.rego",
+        include_str!("../../fixtures/builtins/rate_limit.rego"),
+    ),
+    (
+        "license_header.rego",
+        include_str!("../../fixtures/builtins/license_header.rego"),
+    ),
+    (
+        "binary_file_guard.rego",
+        include_str!("../../fixtures/builtins/binary_file_guard.rego"),
+    ),
+    (
+        "obfuscation_guard.rego",
+        include_str!("../../fixtures/builtins/obfuscation_guard.rego"),
+    ),
+    (
+        "mcp_allowlist.rego",
+        include_str!("../../fixtures/builtins/mcp_allowlist.rego"),
+    ),
+];
+
 // Claude Code builtin policies
 const CLAUDE_ALWAYS_INJECT_POLICY: &str =
     include_str!("../../fixtures/claude/builtins/claude_code_always_inject_on_prompt.rego");
@@ -1896,12 +3529,10 @@ const CLAUDE_POST_EDIT_CHECK_POLICY: &str =
     include_str!("../../fixtures/claude/builtins/post_edit_check.rego");
 const CLAUDE_RULEBOOK_SECURITY_POLICY: &str =
     include_str!("../../fixtures/claude/builtins/rulebook_security_guardrails.rego");
-const CLAUDE_PROTECTED_PATHS_POLICY: &str =
-    include_str!("../../fixtures/claude/builtins/protected_paths.rego");
-const CLAUDE_GIT_BLOCK_NO_VERIFY_POLICY: &str =
-    include_str!("../../fixtures/claude/builtins/git_block_no_verify.rego");
 const CLAUDE_ENFORCE_FULL_FILE_READ_POLICY: &str =
     include_str!("../../fixtures/claude/builtins/claude_code_enforce_full_file_read.rego");
+const CLAUDE_WATCHDOG_STOP_REVIEW_POLICY: &str =
+    include_str!("../../fixtures/claude/builtins/claude_code_watchdog_stop_review.rego");
 
 // Cursor builtin policies (only compatible ones)
 // Note: Cursor doesn't have always_inject_on_prompt (Claude Code only)
@@ -1911,11 +3542,7 @@ const CURSOR_POST_EDIT_CHECK_POLICY: &str =
     include_str!("../../fixtures/cursor/builtins/post_edit_check.rego");
 const CURSOR_RULEBOOK_SECURITY_POLICY: &str =
     include_str!("../../fixtures/cursor/builtins/rulebook_security_guardrails.rego");
-const CURSOR_PROTECTED_PATHS_POLICY: &str =
-    include_str!("../../fixtures/cursor/builtins/protected_paths.rego");
-const CURSOR_GIT_BLOCK_NO_VERIFY_POLICY: &str =
-    include_str!("../../fixtures/cursor/builtins/git_block_no_verify.rego");
-// Note: enforce_full_file_read is NOT available for Cursor (incompatible)
+// Note: enforce_full_file_read, license_header and binary_file_guard are NOT available for Cursor (incompatible)
 
 // Factory AI builtin policies (same as Claude Code - full feature parity)
 const FACTORY_ALWAYS_INJECT_POLICY: &str =
@@ -1926,10 +3553,6 @@ const FACTORY_POST_EDIT_CHECK_POLICY: &str =
     include_str!("../../fixtures/factory/builtins/post_edit_check.rego");
 const FACTORY_RULEBOOK_SECURITY_POLICY: &str =
     include_str!("../../fixtures/factory/builtins/rulebook_security_guardrails.rego");
-const FACTORY_PROTECTED_PATHS_POLICY: &str =
-    include_str!("../../fixtures/factory/builtins/protected_paths.rego");
-const FACTORY_GIT_BLOCK_NO_VERIFY_POLICY: &str =
-    include_str!("../../fixtures/factory/builtins/git_block_no_verify.rego");
 const FACTORY_ENFORCE_FULL_FILE_READ_POLICY: &str =
     include_str!("../../fixtures/factory/builtins/factory_enforce_full_file_read.rego");
 
@@ -1968,10 +3591,6 @@ const OPENCODE_POST_EDIT_CHECK_POLICY: &str =
     include_str!("../../fixtures/opencode/builtins/post_edit_check.rego");
 const OPENCODE_RULEBOOK_SECURITY_POLICY: &str =
     include_str!("../../fixtures/opencode/builtins/rulebook_security_guardrails.rego");
-const OPENCODE_PROTECTED_PATHS_POLICY: &str =
-    include_str!("../../fixtures/opencode/builtins/protected_paths.rego");
-const OPENCODE_GIT_BLOCK_NO_VERIFY_POLICY: &str =
-    include_str!("../../fixtures/opencode/builtins/git_block_no_verify.rego");
 const OPENCODE_ENFORCE_FULL_FILE_READ_POLICY: &str =
     include_str!("../../fixtures/opencode/builtins/opencode_enforce_full_file_read.rego");
 
@@ -0,0 +1,128 @@
+//! Minimal Anthropic Messages API client for onboarding
+//!
+//! One request, one text answer - no streaming or tool use. The key comes from
+//! `ANTHROPIC_API_KEY`; `ANTHROPIC_BASE_URL` points the client at a proxy or
+//! gateway instead of api.anthropic.com.
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const DEFAULT_BASE_URL: &str = "https://api.anthropic.com";
+const API_VERSION: &str = "2023-06-01";
+
+/// Generating a full policy set takes a while
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+pub struct AnthropicClient {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: String,
+    model: String,
+}
+
+#[derive(Serialize)]
+struct MessagesRequest<'a> {
+    model: &'a str,
+    max_tokens: u32,
+    system: &'a str,
+    messages: [Message<'a>; 1],
+}
+
+#[derive(Serialize)]
+struct Message<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Deserialize)]
+struct MessagesResponse {
+    content: Vec<ContentBlock>,
+    #[serde(default)]
+    stop_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ContentBlock {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    text: String,
+}
+
+impl AnthropicClient {
+    /// Client for `model`, configured from the environment
+    pub fn from_env(model: &str) -> Result<Self> {
+        let api_key = std::env::var("ANTHROPIC_API_KEY")
+            .ok()
+            .filter(|key| !key.trim().is_empty())
+            .ok_or_else(|| {
+                anyhow!("ANTHROPIC_API_KEY is not set. Export your Anthropic API key and retry")
+            })?;
+        let base_url = std::env::var("ANTHROPIC_BASE_URL")
+            .unwrap_or_else(|_| DEFAULT_BASE_URL.to_string())
+            .trim_end_matches('/')
+            .to_string();
+
+        let client = reqwest::Client::builder()
+            .user_agent(format!("cupcake/{}", env!("CARGO_PKG_VERSION")))
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        Ok(Self {
+            client,
+            base_url,
+            api_key,
+            model: model.to_string(),
+        })
+    }
+
+    /// The text of the model's answer to `prompt`
+    pub async fn complete(&self, system: &str, prompt: &str, max_tokens: u32) -> Result<String> {
+        let request = MessagesRequest {
+            model: &self.model,
+            max_tokens,
+            system,
+            messages: [Message {
+                role: "user",
+                content: prompt,
+            }],
+        };
+
+        let response = self
+            .client
+            .post(format!("{}/v1/messages", self.base_url))
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", API_VERSION)
+            .header("content-type", "application/json")
+            .body(serde_json::to_vec(&request)?)
+            .send()
+            .await
+            .context("Failed to reach the Anthropic API")?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .context("Failed to read the Anthropic API response")?;
+        if !status.is_success() {
+            return Err(anyhow!("Anthropic API error {status}: {body}"));
+        }
+
+        let response: MessagesResponse =
+            serde_json::from_str(&body).context("Unexpected Anthropic API response")?;
+        if response.stop_reason.as_deref() == Some("max_tokens") {
+            return Err(anyhow!(
+                "The model's answer was cut off at {max_tokens} tokens - try fewer rule files"
+            ));
+        }
+
+        Ok(response
+            .content
+            .into_iter()
+            .filter(|block| block.kind == "text")
+            .map(|block| block.text)
+            .collect())
+    }
+}
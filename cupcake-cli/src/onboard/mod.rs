@@ -0,0 +1,525 @@
+//! `cupcake onboard` - turn a project's rule files into Cupcake policies
+//!
+//! Finds the rule files agents are already asked to follow (see
+//! [`sources`]) and asks Claude to sort their rules: those that can be checked
+//! mechanically become Rego policies, guidance and existing lint or pre-commit
+//! checks become rulebook entries (see [`rulebook`]). Each policy is validated
+//! and written to `.cupcake/policies/<harness>/` if the user accepts it; the
+//! rulebook entries go to a fragment next to rulebook.yml for the user to
+//! merge. Nothing else is changed.
+//!
+//! Without API access, `--offline` converts a structured spec instead (see
+//! [`offline`]).
+
+mod anthropic;
+mod offline;
+mod rulebook;
+mod sources;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use cupcake_core::harness::types::HarnessType;
+use cupcake_core::validator::{PolicyContent, PolicyValidator, Severity};
+
+use anthropic::AnthropicClient;
+use rulebook::RulebookEntries;
+use sources::{RuleFile, RULE_FILES};
+
+/// Model used unless `--model` says otherwise
+pub const DEFAULT_MODEL: &str = "claude-sonnet-4-5";
+
+/// Room for a full set of policies in one answer
+const MAX_TOKENS: u32 = 16_000;
+
+pub struct OnboardOptions {
+    /// Rule files to convert instead of the discovered ones
+    pub files: Vec<PathBuf>,
+    /// Harness to write policies for (detected from `.cupcake/policies/` if unset)
+    pub harness: Option<HarnessType>,
+    pub model: String,
+    /// Convert this spec without calling the API
+    pub offline: Option<PathBuf>,
+    /// Accept every prompt: all files, all valid policies, no overwrites
+    pub yes: bool,
+}
+
+/// The model's answer
+#[derive(Debug, Deserialize)]
+struct Conversion {
+    #[serde(default)]
+    policies: Vec<GeneratedPolicy>,
+    #[serde(flatten)]
+    rulebook: RulebookEntries,
+    /// Rules that were left out and why
+    #[serde(default)]
+    skipped: Vec<SkippedRule>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeneratedPolicy {
+    name: String,
+    #[serde(default)]
+    description: String,
+    rego: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SkippedRule {
+    rule: String,
+    reason: String,
+}
+
+pub async fn run(project_dir: &Path, options: OnboardOptions) -> Result<()> {
+    if let Some(spec) = &options.offline {
+        let (cupcake_dir, harness) = locate(project_dir, options.harness)?;
+        let spec_path = project_dir.join(spec);
+        let conversion = offline::Spec::load(&spec_path)?.convert(harness)?;
+        println!(
+            "Converting {} for {}...",
+            spec.display(),
+            harness.display_name()
+        );
+        return apply(&cupcake_dir, harness, conversion, options.yes);
+    }
+
+    println!("┌─────────────────────────────────────────────────────────────────┐");
+    println!("│                    Cupcake Onboard Wizard                       │");
+    println!("├─────────────────────────────────────────────────────────────────┤");
+    println!("│                                                                 │");
+    println!("│  Converts rule files → Cupcake policies using Claude.           │");
+    println!("│                                                                 │");
+    println!("│  API Key: Uses ANTHROPIC_API_KEY from environment.              │");
+    println!("│           Claude Code users: already set.                       │");
+    println!("│                                                                 │");
+    println!("│  Cost: one request; large rule files use more tokens.           │");
+    println!("│                                                                 │");
+    println!("└─────────────────────────────────────────────────────────────────┘");
+    println!();
+
+    if !options.yes && !confirm("Continue?", true)? {
+        println!("Aborted.");
+        return Ok(());
+    }
+
+    let (cupcake_dir, harness) = locate(project_dir, options.harness)?;
+
+    let rule_files = if options.files.is_empty() {
+        sources::discover(project_dir)
+    } else {
+        options
+            .files
+            .iter()
+            .map(|path| sources::read(&project_dir.join(path), path))
+            .collect::<Result<Vec<_>>>()?
+    };
+    if rule_files.is_empty() {
+        bail!(
+            "No rule files found (looked for {} and .cursor/rules/*.mdc). Name them explicitly: `cupcake onboard <FILE>...`",
+            RULE_FILES.join(", ")
+        );
+    }
+
+    println!();
+    println!("Rule files:");
+    let mut selected = Vec::new();
+    for file in rule_files {
+        let question = format!(
+            "  Convert {} ({}, {} lines)?",
+            file.path.display(),
+            file.format.describe(),
+            file.content.lines().count()
+        );
+        if options.yes {
+            println!("{question} yes");
+            selected.push(file);
+        } else if confirm(&question, true)? {
+            selected.push(file);
+        }
+    }
+    if selected.is_empty() {
+        println!("No files selected.");
+        return Ok(());
+    }
+
+    let client = AnthropicClient::from_env(&options.model)?;
+    println!();
+    println!(
+        "Converting {} file(s) with {} for {}...",
+        selected.len(),
+        options.model,
+        harness.display_name()
+    );
+    let answer = client
+        .complete(&system_prompt(harness), &user_prompt(&selected), MAX_TOKENS)
+        .await?;
+    let conversion = parse_conversion(&answer)?;
+    apply(&cupcake_dir, harness, conversion, options.yes)
+}
+
+/// The project's `.cupcake` directory and the harness to write policies for
+fn locate(project_dir: &Path, harness: Option<HarnessType>) -> Result<(PathBuf, HarnessType)> {
+    let cupcake_dir = project_dir.join(".cupcake");
+    if !cupcake_dir.is_dir() {
+        bail!(
+            "No .cupcake directory in {}. Run `cupcake init` first",
+            project_dir.display()
+        );
+    }
+    let harness = match harness {
+        Some(harness) => harness,
+        None => detect_harness(&cupcake_dir)?,
+    };
+    Ok((cupcake_dir, harness))
+}
+
+/// Write the converted policies and rulebook entries, then summarize
+fn apply(
+    cupcake_dir: &Path,
+    harness: HarnessType,
+    conversion: Conversion,
+    yes: bool,
+) -> Result<()> {
+    let policy_dir = cupcake_dir.join("policies").join(harness.policy_dir());
+    fs::create_dir_all(&policy_dir)
+        .with_context(|| format!("Failed to create {}", policy_dir.display()))?;
+    let written = write_policies(&policy_dir, &conversion.policies, yes)?;
+    let fragment = write_rulebook_fragment(cupcake_dir, &conversion.rulebook, harness, yes)?;
+
+    let mut skipped = conversion.skipped;
+    if !rulebook::supports_context(harness) {
+        skipped.extend(conversion.rulebook.context.iter().map(|rule| SkippedRule {
+            rule: rule.clone(),
+            reason: format!("{} can't inject context", harness.display_name()),
+        }));
+    }
+    if !skipped.is_empty() {
+        println!();
+        println!("Not converted:");
+        for skipped in &skipped {
+            println!("  - {} ({})", skipped.rule, skipped.reason);
+        }
+    }
+
+    println!();
+    println!(
+        "Wrote {written} of {} policies to {}",
+        conversion.policies.len(),
+        policy_dir.display()
+    );
+    if let Some(path) = &fragment {
+        println!(
+            "Wrote rulebook entries to {} - merge them into rulebook.yml",
+            path.display()
+        );
+    }
+    if written > 0 || fragment.is_some() {
+        println!("Review them, then check with:");
+        println!("  cupcake validate");
+        println!("  cupcake verify --harness {harness} --policy-dir .cupcake");
+    }
+    Ok(())
+}
+
+/// The harness whose policy directory exists, asking if there are several
+fn detect_harness(cupcake_dir: &Path) -> Result<HarnessType> {
+    let mut harnesses: Vec<HarnessType> = fs::read_dir(cupcake_dir.join("policies"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().is_dir())
+                .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    harnesses.sort_by_key(|harness| harness.as_str());
+
+    match harnesses.as_slice() {
+        [] => bail!("No harness policy directory in .cupcake/policies. Pass --harness"),
+        [harness] => Ok(*harness),
+        _ => {
+            let names: Vec<&str> = harnesses.iter().map(|h| h.as_str()).collect();
+            let answer = ask(&format!(
+                "Write policies for which harness? [{}]",
+                names.join("/")
+            ))?;
+            answer.parse().map_err(|e: String| anyhow!(e))
+        }
+    }
+}
+
+/// Validate each generated policy and write the accepted ones; returns how
+/// many were written
+fn write_policies(policy_dir: &Path, policies: &[GeneratedPolicy], yes: bool) -> Result<usize> {
+    let validator = PolicyValidator::new();
+    let mut written = 0;
+
+    for policy in policies {
+        println!();
+        if !is_policy_name(&policy.name) {
+            println!("✗ Skipping '{}': not a valid policy name", policy.name);
+            continue;
+        }
+        let path = policy_dir.join(format!("{}.rego", policy.name));
+        let content = PolicyContent::from_content(path.clone(), ensure_newline(&policy.rego))
+            .with_context(|| format!("Failed to parse generated policy '{}'", policy.name))?;
+        let expected_package = format!("cupcake.policies.{}", policy.name);
+        let result = validator.validate_policy(&content);
+
+        println!("{} - {}", policy.name, policy.description);
+        for issue in &result.issues {
+            let label = match issue.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Info => "info",
+            };
+            println!("    {label}: {} [{}]", issue.message, issue.rule_id);
+        }
+        if content.package_name.as_deref() != Some(expected_package.as_str()) {
+            println!("✗ Skipping: package must be {expected_package}");
+            continue;
+        }
+        if result.error_count > 0 {
+            println!("✗ Skipping: {} validation error(s)", result.error_count);
+            continue;
+        }
+
+        if path.exists() && (yes || !confirm(&format!("  Overwrite {}?", path.display()), false)?) {
+            println!("  Kept existing {}", path.display());
+            continue;
+        }
+        if !yes && !confirm(&format!("  Write {}?", path.display()), true)? {
+            continue;
+        }
+        fs::write(&path, &content.content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        println!("✓ Wrote {}", path.display());
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// Write the rulebook entries as a fragment next to rulebook.yml; returns its
+/// path if one was written
+fn write_rulebook_fragment(
+    cupcake_dir: &Path,
+    entries: &RulebookEntries,
+    harness: HarnessType,
+    yes: bool,
+) -> Result<Option<PathBuf>> {
+    let Some(fragment) = entries.render(harness)? else {
+        return Ok(None);
+    };
+    println!();
+    println!("Rulebook entries:");
+    for line in fragment.lines().filter(|line| !line.starts_with('#')) {
+        println!("    {line}");
+    }
+
+    let path = cupcake_dir.join(rulebook::FRAGMENT_FILE);
+    if path.exists() && (yes || !confirm(&format!("  Overwrite {}?", path.display()), false)?) {
+        println!("  Kept existing {}", path.display());
+        return Ok(None);
+    }
+    if !yes && !confirm(&format!("  Write {}?", path.display()), true)? {
+        return Ok(None);
+    }
+    fs::write(&path, fragment).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(Some(path))
+}
+
+/// Lowercase snake_case, usable as both file name and package segment
+fn is_policy_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn ensure_newline(text: &str) -> String {
+    format!("{}\n", text.trim_end())
+}
+
+/// The model's JSON answer, tolerating a Markdown code fence around it
+fn parse_conversion(answer: &str) -> Result<Conversion> {
+    let trimmed = answer.trim();
+    let json = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.trim_end().strip_suffix("```"))
+        .unwrap_or(trimmed);
+    serde_json::from_str(json.trim()).with_context(|| {
+        format!(
+            "The model's answer was not the expected JSON:\n{}",
+            answer.chars().take(500).collect::<String>()
+        )
+    })
+}
+
+fn user_prompt(files: &[RuleFile]) -> String {
+    let mut prompt = String::from("Convert the rules in these files.\n");
+    for file in files {
+        prompt.push_str(&format!(
+            "\n=== {} ({}) ===\n{}\n",
+            file.path.display(),
+            file.format.describe(),
+            file.content.trim_end()
+        ));
+    }
+    prompt
+}
+
+fn system_prompt(harness: HarnessType) -> String {
+    let context = if rulebook::supports_context(harness) {
+        "- context: guidance the agent should keep in mind but that can't be checked from an event (conventions, preferences, architecture notes). One short sentence per rule."
+    } else {
+        "- context: leave empty; this harness can't inject context. List such guidance under skipped."
+    };
+    format!(
+        r#"You convert the rules a project gives its AI coding agent into Cupcake configuration. Cupcake evaluates each agent event (tool calls, prompts, stops) against Rego policies and returns a decision.
+
+Target harness: {harness_name}. {vocabulary}
+
+The files are agent instructions (CLAUDE.md, AGENTS.md, Cursor and Copilot rules) or tool configuration (ESLint, pre-commit). Sort every rule into exactly one of:
+- policies: rules that can be enforced mechanically from the event - commands, file paths, tool names, prompt text.
+{context}
+- protected_paths: files or directories (globs allowed) the agent may read but must not modify. Use these instead of a policy.
+- git_pre_check: commands that must pass before commits, pushes and merges. A pre-commit configuration becomes `pre-commit run --all-files`.
+- post_edit_check: a command to run after files with an extension are edited, e.g. ESLint on ts, tsx, js and jsx files. The command runs in the project root.
+- skipped: anything else, with a short reason.
+
+Each policy is one Rego v1 file in exactly this shape:
+
+# METADATA
+# scope: package
+# title: <Short title>
+# custom:
+#   routing:
+#     required_events: ["PreToolUse"]
+#     required_tools: ["Bash"]
+package cupcake.policies.<name>
+
+import rego.v1
+
+deny contains decision if {{
+    input.tool_name == "Bash"
+    contains(input.tool_input.command, "git push --force")
+    decision := {{
+        "rule_id": "<NAME-001>",
+        "reason": "<message shown to the agent>",
+        "severity": "HIGH",
+    }}
+}}
+
+Rules:
+- <name> is lowercase snake_case and matches the package name.
+- required_events lists every event the policy checks; required_tools is optional.
+- Decision verbs: halt, deny, block, ask, add_context. Use deny for actions that must not happen, ask when a human should confirm, block on PostToolUse or Stop, and add_context (a set of strings) for reminders injected into the prompt.
+- Every decision object has rule_id, reason and severity (LOW, MEDIUM, HIGH or CRITICAL). rule_ids are unique across policies.
+- Use `contains`, `startswith`, `regex.match` and `in` - no external data.
+
+Respond with ONLY a JSON object, no Markdown:
+{{"policies": [{{"name": "...", "description": "...", "rego": "..."}}], "context": ["..."], "protected_paths": ["..."], "git_pre_check": [{{"command": "...", "message": "..."}}], "post_edit_check": [{{"extension": "ts", "command": "...", "message": "..."}}], "skipped": [{{"rule": "...", "reason": "..."}}]}}"#,
+        harness_name = harness.display_name(),
+        vocabulary = vocabulary(harness),
+    )
+}
+
+/// Event and tool names the harness's policies match on
+fn vocabulary(harness: HarnessType) -> &'static str {
+    match harness {
+        HarnessType::Cursor => {
+            "Events: beforeShellExecution (input.command), beforeMCPExecution (input.tool_name), beforeReadFile and afterFileEdit (input.file_path), beforeSubmitPrompt (input.prompt). Cursor has no tool_name for shell or file events."
+        }
+        HarnessType::Goose => {
+            "Events: PreToolUse and PostToolUse (input.tool_name, input.tool_input), UserPromptSubmit (input.prompt). Tools are named <extension>__<tool>, e.g. developer__shell with input.tool_input.command."
+        }
+        _ => {
+            "Events: PreToolUse and PostToolUse (input.tool_name, input.tool_input), UserPromptSubmit (input.prompt), Stop. Tools: Bash (tool_input.command), Read, Write, Edit and MultiEdit (tool_input.file_path)."
+        }
+    }
+}
+
+/// Ask a yes/no question; an empty answer takes the default
+fn confirm(question: &str, default: bool) -> Result<bool> {
+    let hint = if default { "[Y/n]" } else { "[y/N]" };
+    let answer = ask(&format!("{question} {hint}"))?.to_lowercase();
+    Ok(match answer.as_str() {
+        "" => default,
+        "y" | "yes" => true,
+        _ => false,
+    })
+}
+
+fn ask(question: &str) -> Result<String> {
+    print!("{question} ");
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(answer.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_conversion_accepts_fenced_json() {
+        let answer = "```json\n{\"policies\": [{\"name\": \"no_force_push\", \"rego\": \"package x\"}]}\n```";
+        let conversion = parse_conversion(answer).unwrap();
+        assert_eq!(conversion.policies[0].name, "no_force_push");
+        assert!(conversion.skipped.is_empty());
+        assert!(conversion.rulebook.context.is_empty());
+
+        let answer = r#"{"policies": [], "context": ["Use pnpm"], "skipped": [{"rule": "Be nice", "reason": "judgment"}]}"#;
+        let conversion = parse_conversion(answer).unwrap();
+        assert_eq!(conversion.rulebook.context, vec!["Use pnpm"]);
+        assert_eq!(conversion.skipped[0].rule, "Be nice");
+
+        assert!(parse_conversion("Here are your policies!").is_err());
+    }
+
+    #[test]
+    fn test_write_policies_validates_before_writing() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let rego = |package: &str| {
+            format!(
+                "# METADATA\n# scope: package\n# custom:\n#   routing:\n#     required_events: [\"PreToolUse\"]\n#     required_tools: [\"Bash\"]\npackage {package}\n\nimport rego.v1\n\ndeny contains decision if {{\n    input.tool_name == \"Bash\"\n    contains(input.tool_input.command, \"git push --force\")\n    decision := {{\"rule_id\": \"PUSH-001\", \"reason\": \"No force pushes\", \"severity\": \"HIGH\"}}\n}}"
+            )
+        };
+        let policies = vec![
+            GeneratedPolicy {
+                name: "no_force_push".into(),
+                description: "Block force pushes".into(),
+                rego: rego("cupcake.policies.no_force_push"),
+            },
+            GeneratedPolicy {
+                name: "wrong_package".into(),
+                description: String::new(),
+                rego: rego("cupcake.policies.other"),
+            },
+            GeneratedPolicy {
+                name: "../escape".into(),
+                description: String::new(),
+                rego: rego("cupcake.policies.escape"),
+            },
+        ];
+
+        let written = write_policies(temp.path(), &policies, true).unwrap();
+        assert_eq!(written, 1);
+        let content = fs::read_to_string(temp.path().join("no_force_push.rego")).unwrap();
+        assert!(content.ends_with("}\n"));
+        assert!(!temp.path().join("wrong_package.rego").exists());
+
+        // --yes never overwrites
+        fs::write(temp.path().join("no_force_push.rego"), "kept").unwrap();
+        assert_eq!(write_policies(temp.path(), &policies, true).unwrap(), 0);
+        assert_eq!(
+            fs::read_to_string(temp.path().join("no_force_push.rego")).unwrap(),
+            "kept"
+        );
+    }
+}
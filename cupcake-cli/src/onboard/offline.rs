@@ -0,0 +1,258 @@
+//! Offline onboarding from a structured spec
+//!
+//! For environments that can't reach the Anthropic API, `cupcake onboard
+//! --offline <SPEC>` converts a YAML file deterministically - the same spec
+//! always produces the same policy and rulebook entries:
+//!
+//! ```yaml
+//! protected_paths:
+//!   - migrations/
+//!   - .env
+//! blocked_commands:
+//!   - git push --force
+//!   - command: terraform destroy
+//!     reason: Destroy only through the release pipeline
+//! required_checks:
+//!   - command: cargo test
+//!     message: Tests must pass before committing
+//!   - command: npx eslint --quiet
+//!     extensions: [ts, tsx]
+//! context:
+//!   - Use pnpm, not npm
+//! ```
+//!
+//! Blocked commands become one `onboard_blocked_commands` policy; everything
+//! else becomes rulebook entries. A required check with `extensions` runs after
+//! edits to those files, one without runs before git operations.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+
+use cupcake_core::engine::builtins::CheckConfig;
+use cupcake_core::engine::template;
+use cupcake_core::harness::types::HarnessType;
+
+use super::rulebook::{ExtensionCheck, RulebookEntries};
+use super::{Conversion, GeneratedPolicy};
+
+/// Name (and package segment) of the generated policy
+const BLOCKED_COMMANDS_POLICY: &str = "onboard_blocked_commands";
+
+/// Policy template for blocked commands; `__BLOCKED_COMMANDS__` is replaced
+/// with the list after rendering
+const BLOCKED_COMMANDS_TEMPLATE: &str = r#"# cupcake:template
+# METADATA
+# scope: package
+# title: Blocked Commands
+# custom:
+#   severity: HIGH
+#   id: ONBOARD-BLOCKED-COMMAND
+#   routing:
+#     required_events: ["{{shell_event}}"]
+#     required_tools: ["Bash"] # cupcake:unless cursor
+package cupcake.policies.onboard_blocked_commands
+
+import rego.v1
+
+# Generated by `cupcake onboard --offline`. Edit the spec and re-run instead
+# of changing this list by hand.
+blocked_commands := __BLOCKED_COMMANDS__
+
+deny contains decision if {
+	input.hook_event_name == "{{shell_event}}"
+	input.tool_name == "Bash" # cupcake:unless cursor
+
+	# Compare lowercased with whitespace collapsed, so spacing can't dodge a match
+	command := regex.replace(lower({{shell_command}}), `\s+`, " ")
+	some blocked in blocked_commands
+	contains(command, blocked.command)
+
+	decision := {
+		"rule_id": "ONBOARD-BLOCKED-COMMAND",
+		"reason": blocked.reason,
+		"severity": "HIGH",
+	}
+}
+"#;
+
+/// The offline onboarding input
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Spec {
+    /// Paths (globs allowed) the agent may read but not modify
+    #[serde(default)]
+    pub protected_paths: Vec<String>,
+    /// Shell commands the agent may not run
+    #[serde(default)]
+    pub blocked_commands: Vec<BlockedCommand>,
+    /// Checks that must pass
+    #[serde(default)]
+    pub required_checks: Vec<RequiredCheck>,
+    /// Guidance injected as context on every prompt
+    #[serde(default)]
+    pub context: Vec<String>,
+}
+
+/// A command, matched anywhere in the shell command line
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum BlockedCommand {
+    Command(String),
+    Detailed {
+        command: String,
+        #[serde(default)]
+        reason: Option<String>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RequiredCheck {
+    pub command: String,
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Run after edits to files with these extensions instead of before git
+    /// operations
+    #[serde(default)]
+    pub extensions: Vec<String>,
+}
+
+impl Spec {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_yaml_ng::from_str(&content)
+            .with_context(|| format!("Failed to parse onboarding spec {}", path.display()))
+    }
+
+    /// The policies and rulebook entries for `harness`
+    pub fn convert(&self, harness: HarnessType) -> Result<Conversion> {
+        let mut policies = Vec::new();
+        if !self.blocked_commands.is_empty() {
+            policies.push(self.blocked_commands_policy(harness)?);
+        }
+
+        let mut entries = RulebookEntries {
+            context: self.context.clone(),
+            protected_paths: self.protected_paths.clone(),
+            ..Default::default()
+        };
+        for check in &self.required_checks {
+            let message = check
+                .message
+                .clone()
+                .unwrap_or_else(|| format!("Required check: {}", check.command));
+            if check.extensions.is_empty() {
+                entries.git_pre_check.push(CheckConfig {
+                    command: check.command.clone(),
+                    message: message.clone(),
+                });
+            }
+            for extension in &check.extensions {
+                entries.post_edit_check.push(ExtensionCheck {
+                    extension: extension.clone(),
+                    command: check.command.clone(),
+                    message: message.clone(),
+                });
+            }
+        }
+
+        Ok(Conversion {
+            policies,
+            rulebook: entries,
+            skipped: Vec::new(),
+        })
+    }
+
+    fn blocked_commands_policy(&self, harness: HarnessType) -> Result<GeneratedPolicy> {
+        let blocked: Vec<_> = self
+            .blocked_commands
+            .iter()
+            .map(|blocked| {
+                let (command, reason) = match blocked {
+                    BlockedCommand::Command(command) => (command, None),
+                    BlockedCommand::Detailed { command, reason } => (command, reason.clone()),
+                };
+                let reason = reason.unwrap_or_else(|| format!("`{command}` is blocked"));
+                let command = command
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .to_lowercase();
+                json!({"command": command, "reason": reason})
+            })
+            .collect();
+
+        let rego = template::render(BLOCKED_COMMANDS_TEMPLATE, harness)?.replace(
+            "__BLOCKED_COMMANDS__",
+            &serde_json::to_string_pretty(&blocked)?,
+        );
+        Ok(GeneratedPolicy {
+            name: BLOCKED_COMMANDS_POLICY.to_string(),
+            description: format!("Blocks {} command(s)", blocked.len()),
+            rego,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cupcake_core::validator::{PolicyContent, PolicyValidator};
+
+    const SPEC: &str = r#"
+protected_paths: [migrations/]
+blocked_commands:
+  - "git   push --FORCE"
+  - command: terraform destroy
+    reason: Destroy only through the release pipeline
+required_checks:
+  - command: cargo test
+  - command: npx eslint --quiet
+    message: ESLint
+    extensions: [ts, tsx]
+context: ["Use pnpm, not npm"]
+"#;
+
+    #[test]
+    fn test_convert_is_deterministic_and_valid() {
+        let spec: Spec = serde_yaml_ng::from_str(SPEC).unwrap();
+
+        for harness in [HarnessType::ClaudeCode, HarnessType::Cursor] {
+            let conversion = spec.convert(harness).unwrap();
+            let policy = &conversion.policies[0];
+            assert_eq!(policy.name, "onboard_blocked_commands");
+            assert!(policy.rego.contains(r#""command": "git push --force""#));
+            assert!(policy
+                .rego
+                .contains("Destroy only through the release pipeline"));
+            assert!(!policy.rego.contains("cupcake:"));
+
+            let content = PolicyContent::from_content(
+                format!("{}.rego", policy.name).into(),
+                policy.rego.clone(),
+            )
+            .unwrap();
+            let result = PolicyValidator::new().validate_policy(&content);
+            assert_eq!(result.error_count, 0, "{:?}", result.issues);
+
+            assert_eq!(policy.rego, spec.convert(harness).unwrap().policies[0].rego);
+        }
+        let cursor = spec.convert(HarnessType::Cursor).unwrap();
+        assert!(cursor.policies[0].rego.contains("beforeShellExecution"));
+
+        let entries = spec.convert(HarnessType::ClaudeCode).unwrap().rulebook;
+        assert_eq!(entries.protected_paths, vec!["migrations/"]);
+        assert_eq!(
+            entries.git_pre_check[0].message,
+            "Required check: cargo test"
+        );
+        assert_eq!(entries.post_edit_check.len(), 2);
+        assert_eq!(entries.context, vec!["Use pnpm, not npm"]);
+
+        assert!(serde_yaml_ng::from_str::<Spec>("blocked_command: [ls]").is_err());
+    }
+}
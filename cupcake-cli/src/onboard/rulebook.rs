@@ -0,0 +1,182 @@
+//! Rulebook entries produced by onboarding
+//!
+//! Not every rule is a policy. Guidance the agent should keep in mind becomes
+//! context injected on each prompt, read-only paths become `protected_paths`,
+//! and checks a project already runs (linters, pre-commit hooks) become
+//! `git_pre_check` and `post_edit_check` builtins.
+//! These are rendered as a rulebook fragment for the user to merge - the
+//! existing rulebook.yml is never rewritten.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_yaml_ng::Value;
+use std::collections::HashMap;
+
+use cupcake_core::engine::builtins::{
+    AlwaysInjectConfig, CheckConfig, ContextSource, GitPreCheckConfig, PostEditCheckConfig,
+    ProtectedPathsConfig,
+};
+use cupcake_core::harness::types::HarnessType;
+
+/// Where the fragment is written, relative to `.cupcake/`
+pub const FRAGMENT_FILE: &str = "rulebook.onboard.yml";
+
+/// Message shown when an onboarded protected path is modified
+const PROTECTED_PATHS_MESSAGE: &str = "This path is read-only and cannot be modified";
+
+/// Builtin configuration collected from the rule files
+#[derive(Debug, Default, Deserialize)]
+pub struct RulebookEntries {
+    /// Guidance injected as context on every prompt
+    #[serde(default)]
+    pub context: Vec<String>,
+    /// Files and directories (globs allowed) the agent may read but not modify
+    #[serde(default)]
+    pub protected_paths: Vec<String>,
+    /// Checks that must pass before commits, pushes and merges
+    #[serde(default)]
+    pub git_pre_check: Vec<CheckConfig>,
+    /// Checks run after a file with the given extension is edited
+    #[serde(default)]
+    pub post_edit_check: Vec<ExtensionCheck>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExtensionCheck {
+    /// File extension without the dot, e.g. `ts`
+    pub extension: String,
+    pub command: String,
+    pub message: String,
+}
+
+#[derive(Serialize)]
+struct Fragment {
+    builtins: FragmentBuiltins,
+}
+
+#[derive(Serialize)]
+struct FragmentBuiltins {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    claude_code_always_inject_on_prompt: Option<AlwaysInjectConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    protected_paths: Option<ProtectedPathsConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    git_pre_check: Option<GitPreCheckConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    post_edit_check: Option<PostEditCheckConfig>,
+}
+
+/// Whether the harness can inject context on prompts
+pub fn supports_context(harness: HarnessType) -> bool {
+    harness != HarnessType::Cursor
+}
+
+impl RulebookEntries {
+    /// The entries as a rulebook fragment, or `None` if there is nothing the
+    /// harness can use
+    pub fn render(&self, harness: HarnessType) -> Result<Option<String>> {
+        let context = if supports_context(harness) {
+            &self.context[..]
+        } else {
+            &[]
+        };
+        let builtins = FragmentBuiltins {
+            claude_code_always_inject_on_prompt: (!context.is_empty()).then(|| {
+                AlwaysInjectConfig {
+                    enabled: true,
+                    context: context.iter().cloned().map(ContextSource::String).collect(),
+                }
+            }),
+            protected_paths: (!self.protected_paths.is_empty()).then(|| ProtectedPathsConfig {
+                enabled: true,
+                message: PROTECTED_PATHS_MESSAGE.to_string(),
+                paths: self.protected_paths.clone(),
+            }),
+            git_pre_check: (!self.git_pre_check.is_empty()).then(|| GitPreCheckConfig {
+                enabled: true,
+                checks: self.git_pre_check.clone(),
+            }),
+            post_edit_check: (!self.post_edit_check.is_empty()).then(|| PostEditCheckConfig {
+                enabled: true,
+                by_extension: self
+                    .post_edit_check
+                    .iter()
+                    .map(|check| {
+                        (
+                            check.extension.trim_start_matches('.').to_string(),
+                            CheckConfig {
+                                command: check.command.clone(),
+                                message: check.message.clone(),
+                            },
+                        )
+                    })
+                    .collect::<HashMap<_, _>>(),
+            }),
+        };
+        if builtins.claude_code_always_inject_on_prompt.is_none()
+            && builtins.protected_paths.is_none()
+            && builtins.git_pre_check.is_none()
+            && builtins.post_edit_check.is_none()
+        {
+            return Ok(None);
+        }
+
+        // by_extension is a HashMap - sort it so the same entries always
+        // render the same fragment
+        let mut fragment = serde_yaml_ng::to_value(Fragment { builtins })?;
+        if let Some(Value::Mapping(by_extension)) = fragment
+            .get_mut("builtins")
+            .and_then(|builtins| builtins.get_mut("post_edit_check"))
+            .and_then(|check| check.get_mut("by_extension"))
+        {
+            let mut checks: Vec<_> = std::mem::take(by_extension).into_iter().collect();
+            checks.sort_by(|(a, _), (b, _)| a.as_str().cmp(&b.as_str()));
+            *by_extension = checks.into_iter().collect();
+        }
+        let yaml = serde_yaml_ng::to_string(&fragment)?;
+        Ok(Some(format!(
+            "# Generated by `cupcake onboard`. Review, then merge these builtins\n\
+             # into the `builtins:` section of rulebook.yml.\n{yaml}"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cupcake_core::engine::builtins::BuiltinsConfig;
+
+    #[test]
+    fn test_render_is_valid_builtins_config() {
+        let entries: RulebookEntries = serde_json::from_str(
+            r#"{
+                "context": ["Use pnpm, not npm"],
+                "protected_paths": ["migrations/"],
+                "git_pre_check": [{"command": "pre-commit run --all-files", "message": "pre-commit hooks"}],
+                "post_edit_check": [{"extension": ".ts", "command": "npx eslint --quiet", "message": "ESLint"}]
+            }"#,
+        )
+        .unwrap();
+
+        let fragment = entries.render(HarnessType::ClaudeCode).unwrap().unwrap();
+        let value: serde_yaml_ng::Value = serde_yaml_ng::from_str(&fragment).unwrap();
+        let builtins: BuiltinsConfig =
+            serde_yaml_ng::from_value(value["builtins"].clone()).unwrap();
+        builtins.validate().unwrap();
+        assert_eq!(builtins.enabled_builtins().len(), 4);
+        assert!(builtins
+            .post_edit_check
+            .unwrap()
+            .by_extension
+            .contains_key("ts"));
+
+        // Cursor can't inject context - only the checks remain
+        let fragment = entries.render(HarnessType::Cursor).unwrap().unwrap();
+        assert!(!fragment.contains("always_inject"));
+
+        assert!(RulebookEntries::default()
+            .render(HarnessType::ClaudeCode)
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,191 @@
+//! Rule files onboarding reads
+//!
+//! Agents pick up rules from several places: instruction files written for
+//! them (CLAUDE.md, AGENTS.md, Cursor and Copilot rules) and the checks a
+//! project already runs on every change (ESLint, pre-commit). Each file is
+//! tagged with its format so the conversion knows whether it is reading prose
+//! or tool configuration.
+
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Rule files larger than this are skipped - they'd crowd out the answer
+const MAX_RULE_FILE_BYTES: u64 = 256 * 1024;
+
+/// Rule files looked for in the project root
+pub const RULE_FILES: &[&str] = &[
+    "CLAUDE.md",
+    ".claude/CLAUDE.md",
+    "AGENTS.md",
+    ".cursorrules",
+    ".github/copilot-instructions.md",
+    ".eslintrc",
+    ".eslintrc.json",
+    ".eslintrc.yml",
+    ".eslintrc.yaml",
+    ".eslintrc.js",
+    ".eslintrc.cjs",
+    "eslint.config.js",
+    "eslint.config.mjs",
+    "eslint.config.cjs",
+    ".pre-commit-config.yaml",
+];
+
+/// Directory of Cursor project rules (`*.mdc`)
+const CURSOR_RULES_DIR: &str = ".cursor/rules";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleFormat {
+    /// Prose instructions for an agent: CLAUDE.md, AGENTS.md and the like
+    Instructions,
+    /// Cursor rules: `.cursorrules` or `.cursor/rules/*.mdc`
+    CursorRules,
+    /// `.github/copilot-instructions.md`
+    CopilotInstructions,
+    /// ESLint configuration, legacy or flat
+    Eslint,
+    /// `.pre-commit-config.yaml`
+    PreCommit,
+}
+
+impl RuleFormat {
+    /// Format of the file at `path`, from its name
+    pub fn of(path: &Path) -> Self {
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        match name {
+            ".cursorrules" => RuleFormat::CursorRules,
+            "copilot-instructions.md" => RuleFormat::CopilotInstructions,
+            ".pre-commit-config.yaml" | ".pre-commit-config.yml" => RuleFormat::PreCommit,
+            _ if name.starts_with(".eslintrc") || name.starts_with("eslint.config.") => {
+                RuleFormat::Eslint
+            }
+            _ if name.ends_with(".mdc") => RuleFormat::CursorRules,
+            _ => RuleFormat::Instructions,
+        }
+    }
+
+    /// How the file is described to the model
+    pub fn describe(self) -> &'static str {
+        match self {
+            RuleFormat::Instructions => "agent instructions",
+            RuleFormat::CursorRules => "Cursor rules",
+            RuleFormat::CopilotInstructions => "GitHub Copilot instructions",
+            RuleFormat::Eslint => "ESLint configuration",
+            RuleFormat::PreCommit => "pre-commit configuration",
+        }
+    }
+}
+
+/// A rule file and its contents
+pub struct RuleFile {
+    /// Path relative to the project, as shown to the user and the model
+    pub path: PathBuf,
+    pub format: RuleFormat,
+    pub content: String,
+}
+
+/// Every known rule file present in `project_dir`; unreadable ones are
+/// reported and skipped
+pub fn discover(project_dir: &Path) -> Vec<RuleFile> {
+    let mut paths: Vec<PathBuf> = RULE_FILES
+        .iter()
+        .map(PathBuf::from)
+        .filter(|name| project_dir.join(name).is_file())
+        .collect();
+
+    if let Ok(entries) = fs::read_dir(project_dir.join(CURSOR_RULES_DIR)) {
+        let mut cursor_rules: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "mdc"))
+            .filter_map(|path| path.strip_prefix(project_dir).ok().map(Path::to_path_buf))
+            .collect();
+        cursor_rules.sort();
+        paths.extend(cursor_rules);
+    }
+
+    paths
+        .into_iter()
+        .filter_map(|path| match read(&project_dir.join(&path), &path) {
+            Ok(file) => Some(file),
+            Err(e) => {
+                eprintln!("Skipping {}: {e:#}", path.display());
+                None
+            }
+        })
+        .collect()
+}
+
+/// Read `path`, shown as `display`
+pub fn read(path: &Path, display: &Path) -> Result<RuleFile> {
+    let size = fs::metadata(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?
+        .len();
+    if size > MAX_RULE_FILE_BYTES {
+        bail!(
+            "{} is {size} bytes; rule files over {MAX_RULE_FILE_BYTES} bytes are not converted",
+            display.display()
+        );
+    }
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(RuleFile {
+        path: display.to_path_buf(),
+        format: RuleFormat::of(display),
+        content,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_discover_tags_formats() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join(".github")).unwrap();
+        fs::create_dir_all(root.join(".cursor/rules")).unwrap();
+        fs::write(root.join("AGENTS.md"), "Never push to main").unwrap();
+        fs::write(root.join(".github/copilot-instructions.md"), "Use pnpm").unwrap();
+        fs::write(root.join(".eslintrc.json"), "{}").unwrap();
+        fs::write(root.join(".pre-commit-config.yaml"), "repos: []").unwrap();
+        fs::write(root.join(".cursor/rules/style.mdc"), "Prefer tabs").unwrap();
+        fs::write(root.join(".cursor/rules/notes.txt"), "ignored").unwrap();
+
+        let found: Vec<(String, RuleFormat)> = discover(root)
+            .into_iter()
+            .map(|file| (file.path.display().to_string(), file.format))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("AGENTS.md".to_string(), RuleFormat::Instructions),
+                (
+                    ".github/copilot-instructions.md".to_string(),
+                    RuleFormat::CopilotInstructions
+                ),
+                (".eslintrc.json".to_string(), RuleFormat::Eslint),
+                (".pre-commit-config.yaml".to_string(), RuleFormat::PreCommit),
+                (
+                    ".cursor/rules/style.mdc".to_string(),
+                    RuleFormat::CursorRules
+                ),
+            ]
+        );
+
+        assert_eq!(
+            RuleFormat::of(Path::new("eslint.config.mjs")),
+            RuleFormat::Eslint
+        );
+        assert_eq!(
+            RuleFormat::of(Path::new("docs/rules.md")),
+            RuleFormat::Instructions
+        );
+    }
+}
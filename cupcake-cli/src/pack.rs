@@ -0,0 +1,516 @@
+//! `cupcake export` / `cupcake import` - move a vetted policy set between repos
+//!
+//! A policy pack is one JSON file holding a project's rulebook, its own
+//! policies (`policies/<harness>/`, without the builtins `cupcake init`
+//! deploys), signal scripts and watchdog prompts, together with a manifest:
+//! the SHA-256 of every file and a digest over all of them. The digest
+//! identifies the pack - import it with `--sha256 <digest>` and nothing but the
+//! vetted version is accepted.
+//!
+//! `--namespace` moves the imported policies under
+//! `cupcake.policies.<namespace>` (and `policies/<harness>/<namespace>/`) so
+//! they can't collide with the target repository's own packages.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Identifies a policy pack file
+const PACK_FORMAT: &str = "cupcake-policy-pack";
+
+/// Pack layout version; bumped when the layout changes incompatibly
+const PACK_FORMAT_VERSION: u32 = 1;
+
+/// Default pack file name
+pub const DEFAULT_PACK_FILE: &str = "cupcake-pack.json";
+
+/// Directories below `.cupcake/` exported in full
+const PACKED_DIRS: &[&str] = &["signals", "watchdog"];
+
+const RULEBOOK_FILE: &str = "rulebook.yml";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Pack {
+    pub format: String,
+    pub format_version: u32,
+    pub name: String,
+    pub version: String,
+    /// Version of the Cupcake that exported the pack
+    pub cupcake_version: String,
+    /// Harnesses with policies in the pack
+    pub harnesses: Vec<String>,
+    /// SHA-256 over the manifest of files
+    pub digest: String,
+    pub files: Vec<PackFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackFile {
+    /// Path below `.cupcake/`, always with `/` separators
+    pub path: String,
+    pub sha256: String,
+    /// UTF-8 text, or hex when `encoding` is `hex`
+    pub content: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub executable: bool,
+}
+
+impl PackFile {
+    fn new(path: String, bytes: Vec<u8>, executable: bool) -> Self {
+        let sha256 = hex::encode(Sha256::digest(&bytes));
+        let (content, encoding) = match String::from_utf8(bytes) {
+            Ok(text) => (text, None),
+            Err(e) => (hex::encode(e.into_bytes()), Some("hex".to_string())),
+        };
+        Self {
+            path,
+            sha256,
+            content,
+            encoding,
+            executable,
+        }
+    }
+
+    fn bytes(&self) -> Result<Vec<u8>> {
+        match self.encoding.as_deref() {
+            None => Ok(self.content.as_bytes().to_vec()),
+            Some("hex") => hex::decode(&self.content)
+                .with_context(|| format!("{}: invalid hex content", self.path)),
+            Some(other) => bail!("{}: unknown encoding '{other}'", self.path),
+        }
+    }
+}
+
+/// Digest over the manifest - each file's path and hash, in path order
+fn manifest_digest(files: &[PackFile]) -> String {
+    let mut entries: Vec<(&str, &str)> = files
+        .iter()
+        .map(|f| (f.path.as_str(), f.sha256.as_str()))
+        .collect();
+    entries.sort();
+    let mut hasher = Sha256::new();
+    for (path, sha256) in entries {
+        hasher.update(path.as_bytes());
+        hasher.update([0]);
+        hasher.update(sha256.as_bytes());
+        hasher.update(b"\n");
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Collect the exportable files of a `.cupcake` directory into a pack
+pub fn export(cupcake_dir: &Path, name: String, version: String) -> Result<Pack> {
+    if !cupcake_dir.join(RULEBOOK_FILE).is_file() && !cupcake_dir.join("policies").is_dir() {
+        bail!(
+            "{} is not a Cupcake project directory (no rulebook.yml or policies/)",
+            cupcake_dir.display()
+        );
+    }
+
+    let mut files = Vec::new();
+    let mut harnesses = BTreeSet::new();
+    for entry in WalkDir::new(cupcake_dir).sort_by_file_name() {
+        let entry = entry.context("Failed to read the Cupcake directory")?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(cupcake_dir)?;
+        let parts: Vec<&str> = relative.iter().filter_map(|p| p.to_str()).collect();
+        let included = match parts.as_slice() {
+            [RULEBOOK_FILE] => true,
+            ["policies", harness, rest @ ..] if !rest.is_empty() && rest[0] != "builtins" => {
+                harnesses.insert(harness.to_string());
+                true
+            }
+            [dir, _, ..] => PACKED_DIRS.contains(dir),
+            _ => false,
+        };
+        if !included {
+            continue;
+        }
+
+        let bytes = fs::read(entry.path())
+            .with_context(|| format!("Failed to read {}", entry.path().display()))?;
+        files.push(PackFile::new(
+            parts.join("/"),
+            bytes,
+            is_executable(entry.path()),
+        ));
+    }
+
+    Ok(Pack {
+        format: PACK_FORMAT.to_string(),
+        format_version: PACK_FORMAT_VERSION,
+        name,
+        version,
+        cupcake_version: env!("CARGO_PKG_VERSION").to_string(),
+        harnesses: harnesses.into_iter().collect(),
+        digest: manifest_digest(&files),
+        files,
+    })
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).is_ok_and(|m| m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    false
+}
+
+impl Pack {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content =
+            fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let pack: Pack = serde_json::from_slice(&content)
+            .with_context(|| format!("{} is not a Cupcake policy pack", path.display()))?;
+        if pack.format != PACK_FORMAT {
+            bail!("{} is not a Cupcake policy pack", path.display());
+        }
+        if pack.format_version > PACK_FORMAT_VERSION {
+            bail!(
+                "Pack format {} needs a newer Cupcake (exported by {}, this is {})",
+                pack.format_version,
+                pack.cupcake_version,
+                env!("CARGO_PKG_VERSION")
+            );
+        }
+        Ok(pack)
+    }
+
+    /// Check every file against the manifest, and the manifest against
+    /// `expected_digest` if given
+    pub fn verify(&self, expected_digest: Option<&str>) -> Result<()> {
+        for file in &self.files {
+            let actual = hex::encode(Sha256::digest(file.bytes()?));
+            if actual != file.sha256 {
+                bail!(
+                    "{}: content doesn't match its SHA-256 in the manifest",
+                    file.path
+                );
+            }
+            check_path(&file.path)?;
+        }
+        let digest = manifest_digest(&self.files);
+        if digest != self.digest {
+            bail!("Pack digest doesn't match its manifest - the pack was modified");
+        }
+        if let Some(expected) = expected_digest {
+            if !digest.eq_ignore_ascii_case(expected.trim()) {
+                bail!("Pack digest is {digest}, expected {expected}");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Pack paths must stay inside the parts of `.cupcake/` a pack may write
+fn check_path(path: &str) -> Result<()> {
+    let p = Path::new(path);
+    if p.components().any(|c| !matches!(c, Component::Normal(_))) {
+        bail!("{path}: pack paths must be relative, without '..'");
+    }
+    let top = path.split('/').next().unwrap_or_default();
+    if path != RULEBOOK_FILE && top != "policies" && !PACKED_DIRS.contains(&top) {
+        bail!("{path}: not a location a policy pack may write");
+    }
+    if top == "policies" {
+        match path.split('/').nth(2) {
+            None => bail!("{path}: policies must be in a harness directory"),
+            Some("builtins") => {
+                bail!("{path}: builtins are deployed by `cupcake init`, not by packs")
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// What importing one file does
+#[derive(Debug, PartialEq, Eq)]
+pub enum Action {
+    Create,
+    Update,
+    Unchanged,
+    /// Exists with different content and `--force` wasn't given
+    Conflict,
+}
+
+pub struct ImportOptions {
+    pub namespace: Option<String>,
+    pub expected_digest: Option<String>,
+    pub force: bool,
+    pub dry_run: bool,
+}
+
+/// Import a pack into `cupcake_dir`, returning each target path and what
+/// happened to it
+pub fn import(
+    pack: &Pack,
+    cupcake_dir: &Path,
+    options: &ImportOptions,
+) -> Result<Vec<(PathBuf, Action)>> {
+    pack.verify(options.expected_digest.as_deref())?;
+    if let Some(namespace) = &options.namespace {
+        if !is_identifier(namespace) {
+            bail!("Namespace '{namespace}' must be a lowercase identifier, e.g. platform_team");
+        }
+    }
+
+    let packages = pack_packages(pack)?;
+    let mut plan = Vec::new();
+    for file in &pack.files {
+        let (path, bytes) = match &options.namespace {
+            Some(namespace) if file.path.starts_with("policies/") => {
+                let mut parts: Vec<&str> = file.path.split('/').collect();
+                parts.insert(2, namespace);
+                let bytes = if file.path.ends_with(".rego") {
+                    let text = String::from_utf8(file.bytes()?)
+                        .with_context(|| format!("{}: not UTF-8", file.path))?;
+                    namespace_policy(&text, &packages, namespace).into_bytes()
+                } else {
+                    file.bytes()?
+                };
+                (parts.join("/"), bytes)
+            }
+            _ => (file.path.clone(), file.bytes()?),
+        };
+
+        let target = cupcake_dir.join(&path);
+        let action = match fs::read(&target) {
+            Ok(existing) if existing == bytes => Action::Unchanged,
+            Ok(_) if options.force => Action::Update,
+            Ok(_) => Action::Conflict,
+            Err(_) => Action::Create,
+        };
+        plan.push((target, action, bytes, file.executable));
+    }
+
+    let conflicts: Vec<String> = plan
+        .iter()
+        .filter(|(_, action, ..)| *action == Action::Conflict)
+        .map(|(path, ..)| path.display().to_string())
+        .collect();
+    if !conflicts.is_empty() && !options.dry_run {
+        bail!(
+            "These files exist with different content (use --force to overwrite, or --namespace to import alongside):\n  {}",
+            conflicts.join("\n  ")
+        );
+    }
+
+    if !options.dry_run {
+        for (target, action, bytes, executable) in &plan {
+            if !matches!(action, Action::Create | Action::Update) {
+                continue;
+            }
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            fs::write(target, bytes)
+                .with_context(|| format!("Failed to write {}", target.display()))?;
+            #[cfg(unix)]
+            if *executable {
+                use std::os::unix::fs::PermissionsExt;
+                fs::set_permissions(target, fs::Permissions::from_mode(0o755))
+                    .with_context(|| format!("Failed to make {} executable", target.display()))?;
+            }
+            #[cfg(not(unix))]
+            let _ = executable;
+        }
+    }
+
+    Ok(plan
+        .into_iter()
+        .map(|(target, action, ..)| (target, action))
+        .collect())
+}
+
+/// Packages declared by the pack's policies, without the `cupcake.policies.` prefix
+fn pack_packages(pack: &Pack) -> Result<Vec<String>> {
+    let mut packages = Vec::new();
+    for file in pack.files.iter().filter(|f| f.path.ends_with(".rego")) {
+        let text = String::from_utf8(file.bytes()?).unwrap_or_default();
+        packages.extend(text.lines().filter_map(|line| {
+            line.trim()
+                .strip_prefix("package cupcake.policies.")
+                .map(|pkg| pkg.trim().to_string())
+        }));
+    }
+    // Longest first, so `a.b` wins over `a` when rewriting references
+    packages.sort_by_key(|pkg| std::cmp::Reverse(pkg.len()));
+    Ok(packages)
+}
+
+/// Move a policy's package, and its references to other packages in the pack,
+/// under `cupcake.policies.<namespace>`
+fn namespace_policy(text: &str, packages: &[String], namespace: &str) -> String {
+    const PREFIX: &str = "cupcake.policies.";
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find(PREFIX) {
+        let after = &rest[pos + PREFIX.len()..];
+        out.push_str(&rest[..pos + PREFIX.len()]);
+        let in_pack = packages.iter().any(|pkg| {
+            after.starts_with(pkg.as_str())
+                && !after[pkg.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_')
+        });
+        if in_pack {
+            out.push_str(namespace);
+            out.push('.');
+        }
+        rest = after;
+    }
+    out.push_str(rest);
+    out
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const POLICY: &str = "package cupcake.policies.git_rules\n\nimport rego.v1\n\nimport data.cupcake.policies.git_rules_helpers\nimport data.cupcake.system.commands\n";
+
+    fn project() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        fs::create_dir_all(dir.join("policies/claude/builtins")).unwrap();
+        fs::create_dir_all(dir.join("signals")).unwrap();
+        fs::create_dir_all(dir.join("system")).unwrap();
+        fs::write(dir.join("rulebook.yml"), "signals: {}\n").unwrap();
+        fs::write(dir.join("policies/claude/git_rules.rego"), POLICY).unwrap();
+        fs::write(
+            dir.join("policies/claude/helpers.rego"),
+            "package cupcake.policies.git_rules_helpers\n",
+        )
+        .unwrap();
+        fs::write(dir.join("policies/claude/builtins/x.rego"), "builtin").unwrap();
+        fs::write(dir.join("system/evaluate.rego"), "system").unwrap();
+        fs::write(dir.join("signals/branch.sh"), [0xff, 0xfe, b'\n']).unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let source = project();
+        let pack = export(source.path(), "team".into(), "1.2.0".into()).unwrap();
+        let paths: Vec<&str> = pack.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "policies/claude/git_rules.rego",
+                "policies/claude/helpers.rego",
+                "rulebook.yml",
+                "signals/branch.sh",
+            ]
+        );
+        assert_eq!(pack.harnesses, vec!["claude"]);
+        assert_eq!(pack.files[3].encoding.as_deref(), Some("hex"));
+
+        // Serialized and loaded back, it still verifies and pins
+        let json = serde_json::to_string(&pack).unwrap();
+        let pack: Pack = serde_json::from_str(&json).unwrap();
+        pack.verify(Some(&pack.digest)).unwrap();
+        assert!(pack.verify(Some("0000")).is_err());
+
+        let target = TempDir::new().unwrap();
+        let options = ImportOptions {
+            namespace: None,
+            expected_digest: None,
+            force: false,
+            dry_run: false,
+        };
+        let plan = import(&pack, target.path(), &options).unwrap();
+        assert!(plan.iter().all(|(_, action)| *action == Action::Create));
+        assert_eq!(
+            fs::read(target.path().join("signals/branch.sh")).unwrap(),
+            [0xff, 0xfe, b'\n']
+        );
+
+        // Importing again changes nothing; a local edit is a conflict
+        let plan = import(&pack, target.path(), &options).unwrap();
+        assert!(plan.iter().all(|(_, action)| *action == Action::Unchanged));
+        fs::write(target.path().join("rulebook.yml"), "local").unwrap();
+        assert!(import(&pack, target.path(), &options).is_err());
+        let forced = ImportOptions {
+            force: true,
+            ..options
+        };
+        import(&pack, target.path(), &forced).unwrap();
+        assert_eq!(
+            fs::read_to_string(target.path().join("rulebook.yml")).unwrap(),
+            "signals: {}\n"
+        );
+    }
+
+    #[test]
+    fn test_import_with_namespace() {
+        let source = project();
+        let pack = export(source.path(), "team".into(), "1.0.0".into()).unwrap();
+        let target = TempDir::new().unwrap();
+        let options = ImportOptions {
+            namespace: Some("platform".into()),
+            expected_digest: None,
+            force: false,
+            dry_run: false,
+        };
+        import(&pack, target.path(), &options).unwrap();
+
+        let policy = fs::read_to_string(
+            target
+                .path()
+                .join("policies/claude/platform/git_rules.rego"),
+        )
+        .unwrap();
+        assert!(policy.starts_with("package cupcake.policies.platform.git_rules\n"));
+        assert!(policy.contains("import data.cupcake.policies.platform.git_rules_helpers\n"));
+        // References outside the pack are left alone
+        assert!(policy.contains("import data.cupcake.system.commands\n"));
+        assert!(target.path().join("rulebook.yml").exists());
+
+        let bad = ImportOptions {
+            namespace: Some("../up".into()),
+            ..options
+        };
+        assert!(import(&pack, target.path(), &bad).is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_tampering() {
+        let source = project();
+        let mut pack = export(source.path(), "team".into(), "1.0.0".into()).unwrap();
+        pack.files[2].content = "signals: {evil: {}}\n".into();
+        assert!(pack.verify(None).is_err());
+
+        let mut pack = export(source.path(), "team".into(), "1.0.0".into()).unwrap();
+        pack.files
+            .push(PackFile::new("../outside.sh".into(), b"x".to_vec(), true));
+        pack.digest = manifest_digest(&pack.files);
+        assert!(pack.verify(None).is_err());
+
+        for path in [
+            "system/evaluate.rego",
+            "policies/claude/builtins/x.rego",
+            "policies/x.rego",
+        ] {
+            assert!(check_path(path).is_err(), "{path}");
+        }
+    }
+}
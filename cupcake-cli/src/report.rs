@@ -0,0 +1,325 @@
+//! Machine-readable reports for CI - SARIF 2.1.0 and JUnit XML
+//!
+//! Code scanning UIs (GitHub, GitLab) read SARIF; test dashboards read JUnit.
+//! Both are built from plain result lists so any command can emit them.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use cupcake_core::engine::decision::{DecisionKind, DecisionOutput};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const INFORMATION_URI: &str = "https://github.com/eqtylab/cupcake";
+
+/// SARIF result level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SarifLevel {
+    Error,
+    Warning,
+    Note,
+}
+
+impl SarifLevel {
+    fn as_str(self) -> &'static str {
+        match self {
+            SarifLevel::Error => "error",
+            SarifLevel::Warning => "warning",
+            SarifLevel::Note => "note",
+        }
+    }
+}
+
+/// One finding
+#[derive(Debug, Clone)]
+pub struct SarifResult {
+    pub rule_id: String,
+    /// Short description of the rule, listed once per rule_id
+    pub rule_description: String,
+    pub level: SarifLevel,
+    pub message: String,
+    /// File the result is about; made relative to the working directory
+    pub path: Option<String>,
+    pub line: Option<usize>,
+}
+
+/// Build a SARIF log with a single Cupcake run
+pub fn sarif(results: &[SarifResult]) -> Value {
+    let mut rules = BTreeMap::new();
+    for result in results {
+        rules
+            .entry(result.rule_id.as_str())
+            .or_insert(result.rule_description.as_str());
+    }
+    let rules: Vec<Value> = rules
+        .into_iter()
+        .map(|(id, description)| {
+            json!({
+                "id": id,
+                "shortDescription": {"text": description},
+            })
+        })
+        .collect();
+
+    let results: Vec<Value> = results
+        .iter()
+        .map(|result| {
+            let mut value = json!({
+                "ruleId": result.rule_id,
+                "level": result.level.as_str(),
+                "message": {"text": result.message},
+            });
+            if let Some(path) = &result.path {
+                let mut location = json!({
+                    "artifactLocation": {"uri": artifact_uri(path)},
+                });
+                if let Some(line) = result.line {
+                    location["region"] = json!({"startLine": line.max(1)});
+                }
+                value["locations"] = json!([{"physicalLocation": location}]);
+            }
+            value
+        })
+        .collect();
+
+    json!({
+        "version": "2.1.0",
+        "$schema": SARIF_SCHEMA,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "cupcake",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": INFORMATION_URI,
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+/// Write a SARIF log to `path`
+pub fn write_sarif(path: &Path, results: &[SarifResult]) -> Result<()> {
+    let log = serde_json::to_string_pretty(&sarif(results))?;
+    std::fs::write(path, log).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Result for a policy decision, `None` for Allow
+pub fn decision_result(decision: &DecisionOutput, path: Option<String>) -> Option<SarifResult> {
+    let level = match decision.decision {
+        DecisionKind::Allow => return None,
+        DecisionKind::Halt | DecisionKind::Deny | DecisionKind::Block => SarifLevel::Error,
+        DecisionKind::Ask => SarifLevel::Warning,
+        DecisionKind::Modify | DecisionKind::FilterOutput => SarifLevel::Note,
+    };
+    Some(SarifResult {
+        rule_id: format!("cupcake/{}", decision_slug(decision.decision)),
+        rule_description: format!("A policy returned {:?}", decision.decision),
+        level,
+        message: format!(
+            "{:?}: {}",
+            decision.decision,
+            decision.reason.as_deref().unwrap_or("no reason given")
+        ),
+        path,
+        line: None,
+    })
+}
+
+/// File a hook event acts on, if it names one
+pub fn event_file_path(event: &Value) -> Option<String> {
+    const FIELDS: &[&str] = &["file_path", "filePath", "notebook_path", "path"];
+    [event.get("tool_input"), event.get("args"), Some(event)]
+        .into_iter()
+        .flatten()
+        .find_map(|object| {
+            FIELDS
+                .iter()
+                .find_map(|field| object.get(*field).and_then(Value::as_str))
+        })
+        .map(str::to_string)
+}
+
+fn decision_slug(decision: DecisionKind) -> &'static str {
+    match decision {
+        DecisionKind::Halt => "halt",
+        DecisionKind::Deny => "deny",
+        DecisionKind::Block => "block",
+        DecisionKind::Ask => "ask",
+        DecisionKind::Modify => "modify",
+        DecisionKind::FilterOutput => "filter-output",
+        DecisionKind::Allow => "allow",
+    }
+}
+
+/// Code scanning resolves relative URIs against the repository root
+fn artifact_uri(path: &str) -> String {
+    let path = Path::new(path);
+    let relative = std::env::current_dir()
+        .ok()
+        .filter(|_| path.is_absolute())
+        .and_then(|cwd| path.strip_prefix(cwd).ok().map(Path::to_path_buf));
+    let uri = relative
+        .as_deref()
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/");
+    match uri.strip_prefix("./") {
+        Some(stripped) => stripped.to_string(),
+        None if uri.starts_with('/') => format!("file://{uri}"),
+        None => uri,
+    }
+}
+
+/// One JUnit test case
+#[derive(Debug, Clone)]
+pub struct TestCase {
+    pub name: String,
+    pub classname: String,
+    /// Failure message, if the case failed
+    pub failure: Option<String>,
+}
+
+/// Build a JUnit XML document with one test suite
+pub fn junit(suite: &str, cases: &[TestCase]) -> String {
+    let failures = cases.iter().filter(|c| c.failure.is_some()).count();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites tests=\"{}\" failures=\"{failures}\">\n",
+        cases.len()
+    ));
+    xml.push_str(&format!(
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{failures}\">\n",
+        escape_xml(suite),
+        cases.len()
+    ));
+    for case in cases {
+        let attributes = format!(
+            "name=\"{}\" classname=\"{}\"",
+            escape_xml(&case.name),
+            escape_xml(&case.classname)
+        );
+        match &case.failure {
+            Some(message) => {
+                xml.push_str(&format!("    <testcase {attributes}>\n"));
+                xml.push_str(&format!(
+                    "      <failure message=\"{}\">{}</failure>\n",
+                    escape_xml(first_line(message)),
+                    escape_xml(message)
+                ));
+                xml.push_str("    </testcase>\n");
+            }
+            None => xml.push_str(&format!("    <testcase {attributes}/>\n")),
+        }
+    }
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or_default()
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters are not allowed in XML 1.0
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sarif_lists_each_rule_once() {
+        let result = |path: &str| SarifResult {
+            rule_id: "cupcake/deny".to_string(),
+            rule_description: "A policy denied the change".to_string(),
+            level: SarifLevel::Error,
+            message: "No secrets".to_string(),
+            path: Some(path.to_string()),
+            line: None,
+        };
+        let log = sarif(&[result("a.rs"), result("b\\c.rs")]);
+
+        let run = &log["runs"][0];
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 1);
+        assert_eq!(run["results"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            run["results"][1]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "b/c.rs"
+        );
+    }
+
+    #[test]
+    fn test_decision_result_and_event_location() {
+        let deny = DecisionOutput::from(&cupcake_core::engine::decision::FinalDecision::Deny {
+            reason: "No secrets".to_string(),
+            agent_messages: vec![],
+        });
+        let result = decision_result(&deny, Some("./src/main.rs".to_string())).unwrap();
+        assert_eq!(result.rule_id, "cupcake/deny");
+        assert_eq!(result.level, SarifLevel::Error);
+        assert_eq!(result.message, "Deny: No secrets");
+        assert_eq!(
+            sarif(&[result])["runs"][0]["results"][0]["locations"][0]["physicalLocation"]
+                ["artifactLocation"]["uri"],
+            "src/main.rs"
+        );
+
+        let allow = DecisionOutput::from(&cupcake_core::engine::decision::FinalDecision::Allow {
+            context: vec![],
+        });
+        assert!(decision_result(&allow, None).is_none());
+
+        assert_eq!(
+            event_file_path(&json!({"tool_input": {"file_path": "a.rs"}})).as_deref(),
+            Some("a.rs")
+        );
+        assert_eq!(
+            event_file_path(&json!({"args": {"filePath": "b.rs"}})).as_deref(),
+            Some("b.rs")
+        );
+        assert_eq!(
+            event_file_path(&json!({"tool_input": {"command": "ls"}})),
+            None
+        );
+    }
+
+    #[test]
+    fn test_junit_escapes_and_counts_failures() {
+        let xml = junit(
+            "cupcake",
+            &[
+                TestCase {
+                    name: "src/<main>.rs".to_string(),
+                    classname: "assess".to_string(),
+                    failure: Some("Deny: \"rm -rf\" & more\nsecond line".to_string()),
+                },
+                TestCase {
+                    name: "ok.rs".to_string(),
+                    classname: "assess".to_string(),
+                    failure: None,
+                },
+            ],
+        );
+        assert!(xml.contains("<testsuites tests=\"2\" failures=\"1\">"));
+        assert!(xml.contains("name=\"src/&lt;main&gt;.rs\""));
+        assert!(xml.contains("message=\"Deny: &quot;rm -rf&quot; &amp; more\""));
+        assert!(xml.contains("<testcase name=\"ok.rs\" classname=\"assess\"/>"));
+    }
+}
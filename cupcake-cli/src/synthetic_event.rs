@@ -0,0 +1,326 @@
+//! Synthetic hook events for ad-hoc policy testing
+//!
+//! Builds a minimal event in the wire format of a harness from a few CLI
+//! flags, so `cupcake eval` can be exercised without hand-written JSON.
+
+use cupcake_core::harness::types::HarnessType;
+use serde_json::{json, Map, Value};
+
+/// Session id used for every synthetic event
+const SYNTHETIC_SESSION_ID: &str = "cupcake-synthetic";
+
+/// Event fields supplied on the command line
+#[derive(Debug, Default)]
+pub struct EventSpec {
+    pub event: String,
+    pub tool: Option<String>,
+    pub command: Option<String>,
+    pub file_path: Option<String>,
+    pub prompt: Option<String>,
+}
+
+impl EventSpec {
+    /// Tool input built from `--command` and `--file-path`
+    fn tool_input(&self) -> Map<String, Value> {
+        let mut input = Map::new();
+        if let Some(command) = &self.command {
+            input.insert("command".to_string(), json!(command));
+        }
+        if let Some(file_path) = &self.file_path {
+            input.insert("file_path".to_string(), json!(file_path));
+        }
+        input
+    }
+}
+
+/// Build a hook event for the given harness
+pub fn build_event(harness: HarnessType, spec: &EventSpec, cwd: &str) -> Value {
+    let mut event = match harness {
+        HarnessType::ClaudeCode => json!({
+            "hook_event_name": spec.event,
+            "session_id": SYNTHETIC_SESSION_ID,
+            "transcript_path": "",
+            "cwd": cwd,
+        }),
+        HarnessType::Factory => json!({
+            "hookEventName": spec.event,
+            "sessionId": SYNTHETIC_SESSION_ID,
+            "transcriptPath": "",
+            "cwd": cwd,
+            "permissionMode": "default",
+        }),
+        HarnessType::Cursor => json!({
+            "hook_event_name": spec.event,
+            "conversation_id": SYNTHETIC_SESSION_ID,
+            "generation_id": SYNTHETIC_SESSION_ID,
+            "workspace_roots": [cwd],
+        }),
+        HarnessType::OpenCode | HarnessType::Aider | HarnessType::Goose => json!({
+            "hook_event_name": spec.event,
+            "session_id": SYNTHETIC_SESSION_ID,
+            "cwd": cwd,
+        }),
+        HarnessType::Cline => json!({
+            "hookName": spec.event,
+            "taskId": SYNTHETIC_SESSION_ID,
+            "workspaceRoots": [cwd],
+        }),
+    };
+    let obj = event.as_object_mut().expect("event is a JSON object");
+
+    match harness {
+        // Cursor events carry their fields at the top level rather than in a tool input
+        HarnessType::Cursor => {
+            obj.extend(spec.tool_input());
+            if spec.command.is_some() {
+                obj.insert("cwd".to_string(), json!(cwd));
+            }
+            if let Some(tool) = &spec.tool {
+                obj.insert("tool_name".to_string(), json!(tool));
+                obj.insert("tool_input".to_string(), json!({}));
+            }
+            if let Some(prompt) = &spec.prompt {
+                obj.insert("prompt".to_string(), json!(prompt));
+                obj.insert("attachments".to_string(), json!([]));
+            }
+            if spec.file_path.is_some() && spec.command.is_none() {
+                obj.insert("content".to_string(), json!(""));
+                obj.insert("attachments".to_string(), json!([]));
+            }
+        }
+        HarnessType::OpenCode => {
+            if let Some(tool) = &spec.tool {
+                obj.insert("tool".to_string(), json!(tool.to_lowercase()));
+                obj.insert("args".to_string(), Value::Object(spec.tool_input()));
+            }
+        }
+        HarnessType::Aider => {
+            if let Some(tool) = &spec.tool {
+                let mut args = spec.tool_input();
+                if let Some(file_path) = args.remove("file_path") {
+                    args.insert("path".to_string(), file_path);
+                }
+                obj.insert("tool".to_string(), json!(aider_action_name(tool)));
+                obj.insert("args".to_string(), Value::Object(args));
+                if spec.event == "PostToolUse" {
+                    obj.insert("result".to_string(), json!({"output": ""}));
+                }
+            }
+            if let Some(prompt) = &spec.prompt {
+                obj.insert("prompt".to_string(), json!(prompt));
+            }
+        }
+        HarnessType::Goose => {
+            if let Some(tool) = &spec.tool {
+                let (goose_tool, mut arguments) = goose_tool_call(tool, spec.tool_input());
+                let extension = goose_tool.split_once("__").map_or("", |(ext, _)| ext);
+                obj.insert("extension".to_string(), json!(extension));
+                obj.insert("tool".to_string(), json!(goose_tool));
+                if let Some(file_path) = arguments.remove("file_path") {
+                    arguments.insert("path".to_string(), file_path);
+                }
+                obj.insert("arguments".to_string(), Value::Object(arguments));
+                if spec.event == "PostToolUse" {
+                    obj.insert(
+                        "result".to_string(),
+                        json!({"content": [], "is_error": false}),
+                    );
+                }
+            }
+            if let Some(prompt) = &spec.prompt {
+                obj.insert("prompt".to_string(), json!(prompt));
+            }
+        }
+        // Cline nests the event data under a key named after the hook
+        HarnessType::Cline => {
+            if let Some(tool) = &spec.tool {
+                let mut parameters = spec.tool_input();
+                if let Some(file_path) = parameters.remove("file_path") {
+                    parameters.insert("path".to_string(), file_path);
+                }
+                let mut call = json!({
+                    "toolName": cline_tool_name(tool),
+                    "parameters": parameters,
+                });
+                let key = if spec.event == "PostToolUse" {
+                    call["result"] = json!("");
+                    call["success"] = json!(true);
+                    "postToolUse"
+                } else {
+                    "preToolUse"
+                };
+                obj.insert(key.to_string(), call);
+            }
+            if let Some(prompt) = &spec.prompt {
+                obj.insert(
+                    "userPromptSubmit".to_string(),
+                    json!({"prompt": prompt, "attachments": []}),
+                );
+            }
+        }
+        HarnessType::ClaudeCode | HarnessType::Factory => {
+            if let Some(tool) = &spec.tool {
+                obj.insert("tool_name".to_string(), json!(tool));
+                obj.insert("tool_input".to_string(), Value::Object(spec.tool_input()));
+            }
+            if let Some(prompt) = &spec.prompt {
+                obj.insert("prompt".to_string(), json!(prompt));
+            }
+        }
+    }
+
+    event
+}
+
+/// Cline's name for a tool given in Claude Code naming
+fn cline_tool_name(tool: &str) -> &str {
+    match tool {
+        "Bash" => "execute_command",
+        "Read" => "read_file",
+        "Write" => "write_to_file",
+        "Edit" => "replace_in_file",
+        "Grep" => "search_files",
+        "LS" => "list_files",
+        "WebFetch" => "web_fetch",
+        other => other,
+    }
+}
+
+/// The Aider launcher's name for a tool given in Claude Code naming
+fn aider_action_name(tool: &str) -> &str {
+    match tool {
+        "Bash" => "shell_command",
+        "Write" => "write_file",
+        "Read" => "add_file",
+        "WebFetch" => "fetch_url",
+        other => other,
+    }
+}
+
+/// Goose's tool name and arguments for a tool given in Claude Code naming
+///
+/// File tools are one `developer__text_editor` tool that takes a `command`,
+/// and MCP tools drop their `mcp__` prefix.
+fn goose_tool_call(tool: &str, mut arguments: Map<String, Value>) -> (String, Map<String, Value>) {
+    let editor_command = match tool {
+        "Bash" => return ("developer__shell".to_string(), arguments),
+        "Read" => "view",
+        "Write" => "write",
+        "Edit" => "str_replace",
+        other => {
+            let name = other.strip_prefix("mcp__").unwrap_or(other);
+            return (name.to_string(), arguments);
+        }
+    };
+    arguments.insert("command".to_string(), json!(editor_command));
+    ("developer__text_editor".to_string(), arguments)
+}
+
+/// Build the event a harness sends before the agent writes `content` to a file
+///
+/// Cursor has no pre-write hook, so its event is `afterFileEdit` with a single
+/// edit that inserts the content.
+pub fn build_write_event(harness: HarnessType, file_path: &str, content: &str, cwd: &str) -> Value {
+    let (event, tool) = match harness {
+        HarnessType::Cursor => ("afterFileEdit", None),
+        _ => ("PreToolUse", Some("Write".to_string())),
+    };
+    let spec = EventSpec {
+        event: event.to_string(),
+        tool,
+        file_path: Some(file_path.to_string()),
+        ..Default::default()
+    };
+    let mut event = build_event(harness, &spec, cwd);
+
+    match harness {
+        HarnessType::Cursor => {
+            let obj = event.as_object_mut().expect("event is a JSON object");
+            obj.remove("content");
+            obj.remove("attachments");
+            obj.insert(
+                "edits".to_string(),
+                json!([{"old_string": "", "new_string": content}]),
+            );
+        }
+        HarnessType::OpenCode | HarnessType::Aider => event["args"]["content"] = json!(content),
+        HarnessType::Goose => event["arguments"]["file_text"] = json!(content),
+        HarnessType::Cline => event["preToolUse"]["parameters"]["content"] = json!(content),
+        HarnessType::ClaudeCode | HarnessType::Factory => {
+            event["tool_input"]["content"] = json!(content)
+        }
+    }
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cupcake_core::harness::events::{
+        AiderEvent, ClaudeCodeEvent, ClineEvent, CursorEvent, GooseEvent,
+    };
+
+    #[test]
+    fn test_claude_pre_tool_use() {
+        let spec = EventSpec {
+            event: "PreToolUse".to_string(),
+            tool: Some("Bash".to_string()),
+            command: Some("rm -rf /".to_string()),
+            ..Default::default()
+        };
+        let event = build_event(HarnessType::ClaudeCode, &spec, "/project");
+
+        assert_eq!(event["hook_event_name"], "PreToolUse");
+        assert_eq!(event["tool_name"], "Bash");
+        assert_eq!(event["tool_input"]["command"], "rm -rf /");
+        assert_eq!(event["cwd"], "/project");
+        // Must deserialize as a real Claude Code event
+        serde_json::from_value::<ClaudeCodeEvent>(event).unwrap();
+    }
+
+    #[test]
+    fn test_cursor_shell_execution() {
+        let spec = EventSpec {
+            event: "beforeShellExecution".to_string(),
+            command: Some("git push --force".to_string()),
+            ..Default::default()
+        };
+        let event = build_event(HarnessType::Cursor, &spec, "/project");
+
+        assert_eq!(event["command"], "git push --force");
+        assert_eq!(event["cwd"], "/project");
+        serde_json::from_value::<CursorEvent>(event).unwrap();
+    }
+
+    #[test]
+    fn test_write_events() {
+        let event = build_write_event(HarnessType::ClaudeCode, "/p/a.rs", "fn main() {}", "/p");
+        assert_eq!(event["tool_name"], "Write");
+        assert_eq!(event["tool_input"]["content"], "fn main() {}");
+        serde_json::from_value::<ClaudeCodeEvent>(event).unwrap();
+
+        let event = build_write_event(HarnessType::Cursor, "/p/a.rs", "fn main() {}", "/p");
+        assert_eq!(event["hook_event_name"], "afterFileEdit");
+        assert_eq!(event["edits"][0]["new_string"], "fn main() {}");
+        serde_json::from_value::<CursorEvent>(event).unwrap();
+
+        let event = build_write_event(HarnessType::Cline, "/p/a.rs", "fn main() {}", "/p");
+        assert_eq!(event["preToolUse"]["toolName"], "write_to_file");
+        assert_eq!(event["preToolUse"]["parameters"]["path"], "/p/a.rs");
+        assert_eq!(event["preToolUse"]["parameters"]["content"], "fn main() {}");
+        serde_json::from_value::<ClineEvent>(event).unwrap();
+
+        let event = build_write_event(HarnessType::Aider, "/p/a.rs", "fn main() {}", "/p");
+        assert_eq!(event["tool"], "write_file");
+        assert_eq!(event["args"]["path"], "/p/a.rs");
+        assert_eq!(event["args"]["content"], "fn main() {}");
+        serde_json::from_value::<AiderEvent>(event).unwrap();
+
+        let event = build_write_event(HarnessType::Goose, "/p/a.rs", "fn main() {}", "/p");
+        assert_eq!(event["tool"], "developer__text_editor");
+        assert_eq!(event["arguments"]["command"], "write");
+        assert_eq!(event["arguments"]["path"], "/p/a.rs");
+        assert_eq!(event["arguments"]["file_text"], "fn main() {}");
+        serde_json::from_value::<GooseEvent>(event).unwrap();
+    }
+}
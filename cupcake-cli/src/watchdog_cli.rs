@@ -52,10 +52,14 @@ pub async fn run(
     // - Linux: ~/.config/cupcake/watchdog/
     // - macOS: ~/Library/Application Support/cupcake/watchdog/
     // - Windows: %APPDATA%\cupcake\watchdog\
-    let global_watchdog_dir = GlobalPaths::discover()
-        .ok()
-        .flatten()
-        .map(|paths| paths.root.join("watchdog"));
+    let global_watchdog_dir = GlobalPaths::discover().ok().flatten().and_then(|paths| {
+        // First global layer that provides one, matching ProjectPaths::global_watchdog_dir
+        paths
+            .layers
+            .iter()
+            .map(|root| root.join("watchdog"))
+            .find(|dir| dir.exists())
+    });
 
     // Use from_directories_with_dry_run() to load config with proper precedence:
     // project directory -> global directory -> defaults
@@ -119,13 +119,26 @@ fn test_init_creates_all_required_files() -> Result<()> {
         "system/commands.rego",
         // Claude harness files (example + builtins)
         "policies/claude/example.rego",
+        "policies/claude/builtins/binary_file_guard.rego",
+        "policies/claude/builtins/change_freeze.rego",
         "policies/claude/builtins/claude_code_always_inject_on_prompt.rego",
         "policies/claude/builtins/claude_code_enforce_full_file_read.rego",
+        "policies/claude/builtins/claude_code_watchdog_stop_review.rego",
+        "policies/claude/builtins/container_guard.rego",
+        "policies/claude/builtins/dependency_guard.rego",
+        "policies/claude/builtins/env_exfiltration_guard.rego",
+        "policies/claude/builtins/obfuscation_guard.rego",
         "policies/claude/builtins/git_block_no_verify.rego",
         "policies/claude/builtins/git_pre_check.rego",
+        "policies/claude/builtins/license_header.rego",
+        "policies/claude/builtins/mass_deletion_guard.rego",
+        "policies/claude/builtins/mcp_allowlist.rego",
         "policies/claude/builtins/post_edit_check.rego",
+        "policies/claude/builtins/prompt_injection_guard.rego",
         "policies/claude/builtins/protected_paths.rego",
+        "policies/claude/builtins/rate_limit.rego",
         "policies/claude/builtins/rulebook_security_guardrails.rego",
+        "policies/claude/builtins/terraform_plan_gate.rego",
     ];
 
     for file_name in expected_files {
@@ -511,11 +524,11 @@ fn test_correct_number_of_files_created() -> Result<()> {
     // - 1 rulebook.yml
     // - 1 example.rego
     // - 2 files in system/ (evaluate.rego + commands.rego)
-    // - 7 Claude builtins
-    // Total: 1 + 1 + 2 + 7 = 11 files
+    // - 20 Claude builtins
+    // Total: 1 + 1 + 2 + 20 = 24 files
     assert_eq!(
-        file_count, 11,
-        "Should have exactly 11 files (1 rulebook + 1 example + 2 system + 7 builtins)"
+        file_count, 24,
+        "Should have exactly 24 files (1 rulebook + 1 example + 2 system + 20 builtins)"
     );
 
     // We should have exactly 4 directories: system, policies, policies/claude, policies/claude/builtins
@@ -551,10 +564,11 @@ fn test_init_cursor_creates_cursor_only() -> Result<()> {
         "OpenCode directory should NOT exist when initializing with Cursor"
     );
 
-    // Cursor should have 5 builtins (no always_inject_on_prompt or enforce_full_file_read)
+    // Cursor should have 14 builtins (no always_inject_on_prompt, enforce_full_file_read, license_header
+    // or binary_file_guard)
     let builtins_dir = cupcake_dir.join("policies/cursor/builtins");
     let builtin_count = fs::read_dir(&builtins_dir)?.count();
-    assert_eq!(builtin_count, 5, "Cursor should have 5 builtins");
+    assert_eq!(builtin_count, 14, "Cursor should have 14 builtins");
 
     Ok(())
 }
@@ -586,10 +600,10 @@ fn test_init_opencode_creates_opencode_only() -> Result<()> {
         "Factory directory should NOT exist when initializing with OpenCode"
     );
 
-    // OpenCode should have 7 builtins (same as Claude)
+    // OpenCode should have 18 builtins (Claude's, minus mcp_allowlist)
     let builtins_dir = cupcake_dir.join("policies/opencode/builtins");
     let builtin_count = fs::read_dir(&builtins_dir)?.count();
-    assert_eq!(builtin_count, 7, "OpenCode should have 7 builtins");
+    assert_eq!(builtin_count, 18, "OpenCode should have 18 builtins");
 
     Ok(())
 }
@@ -621,10 +635,10 @@ fn test_init_factory_creates_factory_only() -> Result<()> {
         "OpenCode directory should NOT exist when initializing with Factory"
     );
 
-    // Factory should have 7 builtins (same as Claude)
+    // Factory should have 19 builtins (Claude's, minus watchdog_stop_review)
     let builtins_dir = cupcake_dir.join("policies/factory/builtins");
     let builtin_count = fs::read_dir(&builtins_dir)?.count();
-    assert_eq!(builtin_count, 7, "Factory should have 7 builtins");
+    assert_eq!(builtin_count, 19, "Factory should have 19 builtins");
 
     Ok(())
 }
@@ -694,14 +708,14 @@ fn test_init_can_add_second_harness() -> Result<()> {
     // Verify cursor has correct builtins
     let cursor_builtins_dir = project_path.join(".cupcake/policies/cursor/builtins");
     let cursor_builtin_count = fs::read_dir(&cursor_builtins_dir)?.count();
-    assert_eq!(cursor_builtin_count, 5, "Cursor should have 5 builtins");
+    assert_eq!(cursor_builtin_count, 14, "Cursor should have 14 builtins");
 
     // Verify claude still has its builtins
     let claude_builtins_dir = project_path.join(".cupcake/policies/claude/builtins");
     let claude_builtin_count = fs::read_dir(&claude_builtins_dir)?.count();
     assert_eq!(
-        claude_builtin_count, 7,
-        "Claude should still have 7 builtins"
+        claude_builtin_count, 20,
+        "Claude should still have 20 builtins"
     );
 
     Ok(())
@@ -741,6 +755,59 @@ fn test_init_without_harness_requires_selection() -> Result<()> {
     Ok(())
 }
 
+/// Test that --git-hooks installs assess hooks without replacing foreign ones
+#[test]
+fn test_init_git_hooks() -> Result<()> {
+    let (_temp_dir, project_path) = run_init_with_harness("cursor")?;
+    let git = Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(&project_path)
+        .status()?;
+    assert!(git.success());
+    let hooks_dir = project_path.join(".git/hooks");
+    fs::create_dir_all(&hooks_dir)?;
+    fs::write(
+        hooks_dir.join("pre-push"),
+        "#!/bin/sh
+make lint
+",
+    )?;
+
+    // The harness is detected from the initialized project
+    let output = Command::new(get_cupcake_binary())
+        .args(["init", "--git-hooks"])
+        .current_dir(&project_path)
+        .output()?;
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let pre_commit = fs::read_to_string(hooks_dir.join("pre-commit"))?;
+    assert!(pre_commit.contains("# Installed by cupcake init --git-hooks"));
+    assert!(pre_commit.contains(
+        "git diff --cached --no-color --no-ext-diff | cupcake assess --harness cursor --policy-dir '.cupcake' --patch -"
+    ));
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(hooks_dir.join("pre-commit"))?
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o111, 0o111, "pre-commit should be executable");
+    }
+
+    // The user's own pre-push hook is kept
+    assert_eq!(
+        fs::read_to_string(hooks_dir.join("pre-push"))?,
+        "#!/bin/sh\nmake lint\n"
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Kept existing"));
+
+    Ok(())
+}
+
 /// Test that each harness can be loaded by the engine
 #[tokio::test]
 #[serial(home_env)]
@@ -773,6 +840,9 @@ async fn test_all_harnesses_create_valid_engine_structures() -> Result<()> {
             "opencode",
             cupcake_core::harness::types::HarnessType::OpenCode,
         ),
+        ("cline", cupcake_core::harness::types::HarnessType::Cline),
+        ("aider", cupcake_core::harness::types::HarnessType::Aider),
+        ("goose", cupcake_core::harness::types::HarnessType::Goose),
     ];
 
     let result = async {
@@ -0,0 +1,156 @@
+//! Per-stage benchmarks for Cupcake engine
+//! Breaks evaluation latency down into init, routing, signals and WASM
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use cupcake_core::engine::Engine;
+use cupcake_core::harness::types::HarnessType;
+use serde_json::json;
+use std::fs;
+use std::time::Duration;
+use tempfile::TempDir;
+
+const SYSTEM_POLICY: &str = r#"package cupcake.system
+
+import rego.v1
+
+# METADATA
+# scope: document
+# custom:
+#   entrypoint: true
+
+evaluate := {
+    "halts": collect_verbs("halt"),
+    "denials": collect_verbs("deny"),
+    "blocks": collect_verbs("block"),
+    "asks": collect_verbs("ask"),
+    "modifications": collect_verbs("modify"),
+    "add_context": collect_verbs("add_context")
+}
+
+collect_verbs(verb_name) := [decision |
+    walk(data.cupcake.policies, [path, value])
+    path[count(path) - 1] == verb_name
+    some decision in value
+]
+
+default collect_verbs(_) := []
+"#;
+
+const BRANCH_POLICY: &str = r#"# METADATA
+# scope: package
+# custom:
+#   routing:
+#     required_events: ["PreToolUse"]
+#     required_tools: ["Bash"]
+#     required_signals: ["git_branch"]
+package cupcake.policies.protected_branch
+
+import rego.v1
+
+deny contains decision if {
+    input.signals.git_branch == "main"
+    contains(input.tool_input.command, "git push")
+    decision := {
+        "reason": "Direct pushes to main are blocked",
+        "severity": "HIGH",
+        "rule_id": "BENCH-002"
+    }
+}
+"#;
+
+/// Project with one signal-backed policy in the `.cupcake/` layout
+fn create_test_project() -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    let cupcake_dir = temp_dir.path().join(".cupcake");
+    let claude_dir = cupcake_dir.join("policies/claude");
+
+    fs::create_dir_all(claude_dir.join("system")).unwrap();
+    fs::write(claude_dir.join("system/evaluate.rego"), SYSTEM_POLICY).unwrap();
+    fs::write(claude_dir.join("protected_branch.rego"), BRANCH_POLICY).unwrap();
+    fs::write(
+        cupcake_dir.join("rulebook.yml"),
+        "signals:\n  git_branch:\n    command: \"echo main\"\n    timeout_seconds: 1\nbuiltins: {}\n",
+    )
+    .unwrap();
+
+    temp_dir
+}
+
+fn push_event() -> serde_json::Value {
+    json!({
+        "hook_event_name": "PreToolUse",
+        "tool_name": "Bash",
+        "tool_input": {"command": "git push origin main"},
+        "session_id": "bench",
+        "transcript_path": "/tmp/test.txt",
+        "cwd": "/tmp"
+    })
+}
+
+fn benchmark_engine_init(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let project = create_test_project();
+
+    let mut group = c.benchmark_group("engine_init");
+    // Init shells out to OPA, so keep the sample count low
+    group.sample_size(10);
+    group.bench_function("engine_init", |b| {
+        b.iter(|| {
+            runtime
+                .block_on(Engine::new(project.path(), HarnessType::ClaudeCode))
+                .unwrap()
+        });
+    });
+    group.finish();
+}
+
+fn benchmark_stages(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let project = create_test_project();
+    let engine = runtime
+        .block_on(Engine::new(project.path(), HarnessType::ClaudeCode))
+        .unwrap();
+    let event = push_event();
+
+    c.bench_function("routing", |b| {
+        b.iter(|| engine.explain_route(black_box("PreToolUse"), black_box(Some("Bash"))));
+    });
+
+    // Signals and WASM can't run on their own, so time them from stage profiles
+    c.bench_function("signal_gathering", |b| {
+        b.iter_custom(|iters| {
+            (0..iters)
+                .map(|_| {
+                    runtime
+                        .block_on(engine.profile_stages(&event))
+                        .unwrap()
+                        .signals
+                })
+                .sum::<Duration>()
+        });
+    });
+
+    c.bench_function("wasm_evaluation", |b| {
+        b.iter_custom(|iters| {
+            (0..iters)
+                .map(|_| {
+                    runtime
+                        .block_on(engine.profile_stages(&event))
+                        .unwrap()
+                        .wasm
+                })
+                .sum::<Duration>()
+        });
+    });
+
+    c.bench_function("end_to_end_with_signals", |b| {
+        b.iter(|| {
+            runtime
+                .block_on(engine.evaluate(black_box(&event), None))
+                .unwrap()
+        });
+    });
+}
+
+criterion_group!(benches, benchmark_engine_init, benchmark_stages);
+criterion_main!(benches);
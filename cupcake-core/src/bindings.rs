@@ -5,30 +5,291 @@
 //!
 //! Key Design Principles:
 //! - Thread-safe by default (Arc<Engine>)
-//! - String-based error handling (no complex Rust types across FFI)
-//! - JSON in/out for maximum compatibility
+//! - Typed errors that serialize to JSON (see [`BindingError`])
+//! - JSON in/out for maximum compatibility; decisions use the versioned
+//!   [`DecisionOutput`] contract
 //! - Both sync and async evaluation methods
+//! - Claude Code hook output for in-process hook hosts (see
+//!   [`BindingEngine::evaluate_hook_async`])
+//! - Decision and telemetry events streamed to subscribers (see [`BindingEvent`])
+//! - Optional auto-reload when files under `.cupcake/` change (see [`BindingOptions::watch`])
 //! - Single-threaded Tokio runtime for FFI compatibility
 
-use crate::engine::Engine;
+use crate::engine::decision::{DecisionOutput, FinalDecision, OutputFilter};
+use crate::engine::executor::SignalGatheringFailed;
+use crate::engine::policy_watch::{PolicyWatcher, DEFAULT_DEBOUNCE};
+use crate::engine::trace::generate_trace_id;
+use crate::engine::wasm_runtime::MemoryLimitExceeded;
+use crate::engine::{Engine, EngineConfig, ProjectPaths, TelemetryConfig};
+use crate::harness::passthrough::ResponseMode;
+use crate::harness::types::HarnessType;
+use crate::harness::ClaudeHarness;
+use crate::telemetry::TelemetryContext;
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::sync::Arc;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Errors returned across the FFI boundary
+///
+/// Serialized as `{"kind": "InvalidInput", "message": "..."}` so bindings can
+/// raise a typed exception per kind instead of matching on message text.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "message")]
+pub enum BindingError {
+    /// The event JSON or harness name was rejected
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    /// Policies could not be loaded or compiled
+    #[error("Policy compilation failed: {0}")]
+    PolicyCompile(String),
+    /// A signal required by the routed policies could not be gathered
+    #[error("Signal failure: {0}")]
+    SignalFailure(String),
+    /// Policy evaluation failed inside the WASM runtime
+    #[error("WASM runtime error: {0}")]
+    WasmRuntime(String),
+    /// An operation ran past its deadline
+    #[error("Timed out: {0}")]
+    Timeout(String),
+    /// Any other failure inside the binding
+    #[error("Internal error: {0}")]
+    Internal(String),
+}
+
+impl BindingError {
+    /// Stable error code for the binding's exception, e.g. `INVALID_INPUT`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidInput(_) => "INVALID_INPUT",
+            Self::PolicyCompile(_) => "POLICY_COMPILE",
+            Self::SignalFailure(_) => "SIGNAL_FAILURE",
+            Self::WasmRuntime(_) => "WASM_RUNTIME",
+            Self::Timeout(_) => "TIMEOUT",
+            Self::Internal(_) => "INTERNAL",
+        }
+    }
+
+    /// JSON form passed across the FFI boundary
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| {
+            serde_json::json!({"kind": "Internal", "message": self.to_string()}).to_string()
+        })
+    }
+
+    /// Classify an engine initialization failure
+    fn from_init_error(error: anyhow::Error) -> Self {
+        let message = format!("{error:#}");
+        if is_timeout(&error) {
+            Self::Timeout(message)
+        } else {
+            Self::PolicyCompile(message)
+        }
+    }
+
+    /// Classify an evaluation failure
+    fn from_evaluation_error(error: anyhow::Error) -> Self {
+        let message = format!("{error:#}");
+        if is_timeout(&error) {
+            Self::Timeout(message)
+        } else if error.downcast_ref::<SignalGatheringFailed>().is_some() {
+            Self::SignalFailure(message)
+        } else if error.downcast_ref::<MemoryLimitExceeded>().is_some()
+            || error.downcast_ref::<wasmtime::Trap>().is_some()
+        {
+            Self::WasmRuntime(message)
+        } else {
+            Self::Internal(message)
+        }
+    }
+}
+
+fn is_timeout(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .any(|cause| cause.is::<tokio::time::error::Elapsed>())
+}
+
+/// Options for creating a [`BindingEngine`]
+///
+/// Mirrors the CLI flags that make sense for embedded use. Deserializes from
+/// camelCase JSON so bindings can pass their options object through as-is.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BindingOptions {
+    /// Harness type (e.g., "claude" or "cursor"); defaults to "claude"
+    pub harness: Option<String>,
+    /// Maximum WASM memory in bytes; defaults to 10MB
+    pub wasm_max_memory: Option<usize>,
+    /// Path to the OPA binary; defaults to the bundled OPA or system PATH
+    pub opa_path: Option<PathBuf>,
+    /// Global config directory; defaults to the platform config directory
+    pub global_config: Option<PathBuf>,
+    /// Return a Deny decision instead of an error when evaluation fails
+    pub fail_closed: bool,
+    /// Write a telemetry span for each evaluation, using the rulebook's
+    /// telemetry format and destination
+    pub telemetry: bool,
+    /// Reload the engine when files under `.cupcake/` change
+    pub watch: bool,
+    /// Quiet period after a change before reloading; defaults to 500ms
+    pub watch_debounce_ms: Option<u64>,
+}
+
+impl BindingOptions {
+    /// Build the engine configuration these options describe
+    fn engine_config(&self) -> Result<EngineConfig, BindingError> {
+        let harness = self.harness.as_deref().unwrap_or("claude");
+        let harness_type: HarnessType = harness.parse().map_err(|e| {
+            BindingError::InvalidInput(format!("Invalid harness type '{harness}': {e}"))
+        })?;
+
+        let mut config = EngineConfig::new(harness_type);
+        config.wasm_max_memory = self.wasm_max_memory;
+        config.opa_path = self.opa_path.clone();
+        config.global_config = self.global_config.clone();
+        Ok(config)
+    }
+}
+
+/// Event streamed to subscribers after each evaluation
+///
+/// Serialized with a `type` tag and camelCase fields, e.g.
+/// `{"type": "decision", "traceId": "...", "decision": {...}, "durationMs": 3}`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum BindingEvent {
+    /// A decision was returned to the caller (including fail-closed denials)
+    #[serde(rename_all = "camelCase")]
+    Decision {
+        trace_id: String,
+        hook_event_name: Option<String>,
+        decision: Value,
+        duration_ms: u64,
+    },
+    /// Evaluation failed and the error was returned to the caller
+    #[serde(rename_all = "camelCase")]
+    Error {
+        trace_id: String,
+        hook_event_name: Option<String>,
+        error: BindingError,
+        duration_ms: u64,
+    },
+    /// The evaluation's telemetry span, sent after its decision or error
+    #[serde(rename_all = "camelCase")]
+    Telemetry { trace_id: String, span: Value },
+}
+
+/// Engine health, returned by [`BindingEngine::health`]
+///
+/// Serialized with camelCase fields, e.g.
+/// `{"ready": true, "version": "...", "watching": true, "lastReload": "...", "lastReloadError": null}`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BindingHealth {
+    pub ready: bool,
+    pub version: String,
+    /// Whether policy edits reload the engine automatically
+    pub watching: bool,
+    /// When the policies in use were loaded
+    pub last_reload: DateTime<Utc>,
+    /// Why the most recent reload failed, if it did; the previous policies stay in use
+    pub last_reload_error: Option<String>,
+}
+
+/// Outcome of the most recent load or reload
+#[derive(Debug, Clone)]
+struct ReloadStatus {
+    last_reload: DateTime<Utc>,
+    last_error: Option<String>,
+}
+
+/// Callback registered with [`BindingEngine::subscribe`]
+type Subscriber = Arc<dyn Fn(&BindingEvent) + Send + Sync>;
+
+/// Subscribers shared by all clones of a binding engine
+#[derive(Default)]
+struct Subscribers {
+    next_id: AtomicU64,
+    callbacks: RwLock<Vec<(u64, Subscriber)>>,
+}
+
+impl Subscribers {
+    fn add(&self, callback: Subscriber) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        self.write().push((id, callback));
+        id
+    }
+
+    fn remove(&self, id: u64) -> bool {
+        let mut callbacks = self.write();
+        let before = callbacks.len();
+        callbacks.retain(|(existing, _)| *existing != id);
+        callbacks.len() != before
+    }
+
+    fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Call every subscriber; the lock is released before any callback runs
+    fn publish(&self, event: &BindingEvent) {
+        let callbacks: Vec<Subscriber> = self.read().iter().map(|(_, cb)| cb.clone()).collect();
+        for callback in callbacks {
+            callback(event);
+        }
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, Vec<(u64, Subscriber)>> {
+        self.callbacks.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, Vec<(u64, Subscriber)>> {
+        self.callbacks.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
 
 /// FFI-friendly engine wrapper for foreign language bindings
 ///
 /// This struct is designed to be:
 /// - Cloneable (for multiple references)
 /// - Thread-safe (Arc internally)
-/// - FFI-compatible (simple methods, string errors)
+/// - FFI-compatible (simple methods, JSON-serializable errors)
 #[derive(Clone)]
 pub struct BindingEngine {
-    /// The core engine wrapped in Arc for thread safety
-    inner: Arc<Engine>,
+    /// The core engine, swapped out wholesale by `reload`
+    inner: Arc<RwLock<Arc<Engine>>>,
+
+    /// Project path and configuration the engine was created from, for `reload`
+    path: String,
+    config: EngineConfig,
 
     /// Dedicated runtime for this binding instance
     /// Uses current_thread for FFI compatibility (avoids thread-local issues)
     runtime: Arc<tokio::runtime::Runtime>,
+
+    /// Harness the engine was created for, recorded in telemetry
+    harness: HarnessType,
+
+    /// Whether evaluation errors become Deny decisions
+    fail_closed: bool,
+
+    /// Whether each evaluation writes a telemetry span
+    telemetry: bool,
+
+    /// Callbacks receiving decision and telemetry events
+    subscribers: Arc<Subscribers>,
+
+    /// Outcome of the most recent load or reload, for `health`
+    reload_status: Arc<RwLock<ReloadStatus>>,
+
+    /// Reloads the engine on policy edits, when watching
+    watcher: Option<Arc<PolicyWatcher>>,
 }
 
 impl BindingEngine {
@@ -40,29 +301,86 @@ impl BindingEngine {
     ///
     /// # Returns
     /// * `Ok(BindingEngine)` - Successfully initialized engine
-    /// * `Err(String)` - Error message suitable for FFI
-    pub fn new(path: &str, harness: &str) -> Result<Self, String> {
-        // Parse harness string
-        let harness_type: crate::harness::types::HarnessType = harness
-            .parse()
-            .map_err(|e| format!("Invalid harness type '{harness}': {e}"))?;
+    /// * `Err(BindingError)` - `InvalidInput`, `PolicyCompile`, `Timeout` or `Internal`
+    pub fn new(path: &str, harness: &str) -> Result<Self, BindingError> {
+        Self::with_options(
+            path,
+            &BindingOptions {
+                harness: Some(harness.to_string()),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Create a new binding engine with the given project path and options
+    ///
+    /// # Arguments
+    /// * `path` - Path to the project directory or .cupcake folder
+    /// * `options` - Harness, memory limit, OPA path and other overrides
+    ///
+    /// # Returns
+    /// * `Ok(BindingEngine)` - Successfully initialized engine
+    /// * `Err(BindingError)` - `InvalidInput`, `PolicyCompile`, `Timeout` or `Internal`
+    pub fn with_options(path: &str, options: &BindingOptions) -> Result<Self, BindingError> {
+        let config = options.engine_config()?;
+        let harness = config.harness;
+        let path = path.to_string();
 
         // Create a current_thread runtime for FFI compatibility
         // This avoids thread-local storage issues with multi-threaded runtime
         let runtime = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
-            .map_err(|e| format!("Failed to create Tokio runtime: {e}"))?;
+            .map_err(|e| BindingError::Internal(format!("Failed to create Tokio runtime: {e}")))?;
 
         // Initialize the core engine using the runtime
         let engine = runtime
-            .block_on(Engine::new(path, harness_type))
-            .map_err(|e| format!("Failed to initialize core engine: {e}"))?;
+            .block_on(Engine::new_with_config(&path, config.clone()))
+            .map_err(BindingError::from_init_error)?;
 
-        Ok(Self {
-            inner: Arc::new(engine),
+        let mut binding = Self {
+            inner: Arc::new(RwLock::new(Arc::new(engine))),
+            path,
+            config,
             runtime: Arc::new(runtime),
+            harness,
+            fail_closed: options.fail_closed,
+            telemetry: options.telemetry,
+            subscribers: Arc::default(),
+            reload_status: Arc::new(RwLock::new(ReloadStatus {
+                last_reload: Utc::now(),
+                last_error: None,
+            })),
+            watcher: None,
+        };
+        if options.watch {
+            let debounce = options
+                .watch_debounce_ms
+                .map_or(DEFAULT_DEBOUNCE, Duration::from_millis);
+            binding.watcher = Some(Arc::new(binding.watch(debounce)?));
+        }
+        Ok(binding)
+    }
+
+    /// Watch the project's `.cupcake/` directory and reload on changes
+    fn watch(&self, debounce: Duration) -> Result<PolicyWatcher, BindingError> {
+        let paths =
+            ProjectPaths::resolve_with_config(&self.path, self.config.global_config.clone())
+                .map_err(|e| BindingError::InvalidInput(format!("{e:#}")))?;
+
+        // The watcher thread holds a clone without the watcher, so dropping the
+        // last engine handle stops watching
+        let engine = Self {
+            watcher: None,
+            ..self.clone()
+        };
+        PolicyWatcher::start(&paths.cupcake_dir, debounce, move || {
+            info!("Policy files changed, reloading");
+            if let Err(e) = engine.reload() {
+                warn!("Reload failed, keeping the current policies: {}", e);
+            }
         })
+        .map_err(|e| BindingError::Internal(format!("{e:#}")))
     }
 
     /// Synchronous evaluation method for blocking language bindings
@@ -75,22 +393,11 @@ impl BindingEngine {
     /// * `input_json` - JSON string containing the hook event
     ///
     /// # Returns
-    /// * `Ok(String)` - JSON response with decision
-    /// * `Err(String)` - Error message
-    pub fn evaluate_sync(&self, input_json: &str) -> Result<String, String> {
-        // Parse input JSON
-        let input: Value =
-            serde_json::from_str(input_json).map_err(|e| format!("Invalid input JSON: {e}"))?;
-
+    /// * `Ok(String)` - [`DecisionOutput`] JSON
+    /// * `Err(BindingError)` - Classified evaluation error
+    pub fn evaluate_sync(&self, input_json: &str) -> Result<String, BindingError> {
         // Evaluate using the runtime (blocks until complete)
-        let decision = self
-            .runtime
-            .block_on(self.inner.evaluate(&input, None))
-            .map_err(|e| format!("Core engine evaluation failed: {e}"))?;
-
-        // Serialize the decision to JSON
-        serde_json::to_string(&decision)
-            .map_err(|e| format!("Failed to serialize final decision: {e}"))
+        self.runtime.block_on(self.evaluate_async(input_json))
     }
 
     /// Asynchronous evaluation method for async language bindings
@@ -103,23 +410,241 @@ impl BindingEngine {
     /// * `input_json` - JSON string containing the hook event
     ///
     /// # Returns
-    /// * `Ok(String)` - JSON response with decision
-    /// * `Err(String)` - Error message
-    pub async fn evaluate_async(&self, input_json: &str) -> Result<String, String> {
-        // Parse input JSON
-        let input: Value =
-            serde_json::from_str(input_json).map_err(|e| format!("Invalid input JSON: {e}"))?;
-
-        // Evaluate asynchronously
-        let decision = self
-            .inner
-            .evaluate(&input, None)
+    /// * `Ok(String)` - [`DecisionOutput`] JSON
+    /// * `Err(BindingError)` - Classified evaluation error
+    pub async fn evaluate_async(&self, input_json: &str) -> Result<String, BindingError> {
+        self.evaluate_with(input_json, |_, decision| serialize_decision(decision))
             .await
-            .map_err(|e| format!("Core engine evaluation failed: {e}"))?;
+    }
+
+    /// Synchronous variant of [`BindingEngine::evaluate_hook_async`]
+    pub fn evaluate_hook_sync(&self, input_json: &str) -> Result<String, BindingError> {
+        self.runtime.block_on(self.evaluate_hook_async(input_json))
+    }
 
-        // Serialize the decision to JSON
-        serde_json::to_string(&decision)
-            .map_err(|e| format!("Failed to serialize final decision: {e}"))
+    /// Evaluate a Claude Code hook event and answer in the hook's JSON output format
+    ///
+    /// For hosts that call hooks in-process with Claude Code's input and output
+    /// objects (e.g. the Claude Agent SDK), so they get the same response the
+    /// `claude` harness writes to stdout, including `response_passthrough` and
+    /// permission suggestions.
+    ///
+    /// # Arguments
+    /// * `input_json` - JSON string containing the Claude Code hook input
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Hook output JSON (`{}` when there is nothing to say)
+    /// * `Err(BindingError)` - `InvalidInput` if the engine isn't for the `claude`
+    ///   harness or the input isn't a Claude Code hook event; otherwise as
+    ///   [`BindingEngine::evaluate_async`]
+    pub async fn evaluate_hook_async(&self, input_json: &str) -> Result<String, BindingError> {
+        if self.harness != HarnessType::ClaudeCode {
+            return Err(BindingError::InvalidInput(format!(
+                "Hook responses require the 'claude' harness, not '{}'",
+                self.harness
+            )));
+        }
+        let event = ClaudeHarness::parse_event(input_json).map_err(|e| {
+            BindingError::InvalidInput(format!("Invalid Claude Code hook input: {e}"))
+        })?;
+
+        self.evaluate_with(input_json, |engine, decision| {
+            let mode = ResponseMode {
+                permission_suggestions: engine.permission_suggestions(),
+                ..ResponseMode::resolve(
+                    engine.response_passthrough(),
+                    HarnessType::ClaudeCode,
+                    event.event_name(),
+                )
+            };
+            let response = ClaudeHarness::format_response_with_mode(&event, decision, mode)
+                .map_err(|e| {
+                    BindingError::Internal(format!("Failed to format hook response: {e:#}"))
+                })?;
+            serde_json::to_string(&response).map_err(|e| {
+                BindingError::Internal(format!("Failed to serialize hook response: {e}"))
+            })
+        })
+        .await
+    }
+
+    /// Evaluate an event and render the decision with `format`
+    ///
+    /// Subscribers always receive the decision in its [`DecisionOutput`] form;
+    /// telemetry records the rendered response.
+    async fn evaluate_with(
+        &self,
+        input_json: &str,
+        format: impl FnOnce(&Engine, &FinalDecision) -> Result<String, BindingError>,
+    ) -> Result<String, BindingError> {
+        let input = parse_input(input_json)?;
+
+        let engine = self.engine();
+        let streaming = !self.subscribers.is_empty();
+        let mut telemetry = self.telemetry_context(&engine, &input, streaming);
+        let start = Instant::now();
+        let decision = match engine.evaluate(&input, telemetry.as_mut()).await {
+            Ok(decision) => Ok(decision),
+            Err(e) if self.fail_closed => {
+                let error = BindingError::from_evaluation_error(e);
+                warn!("Evaluation failed, denying (fail-closed): {}", error);
+                if let Some(ref mut ctx) = telemetry {
+                    ctx.add_error(error.to_string());
+                }
+                Ok(FinalDecision::Deny {
+                    reason: error.to_string(),
+                    agent_messages: vec![],
+                })
+            }
+            Err(e) => {
+                if let Some(ref mut ctx) = telemetry {
+                    ctx.add_error(format!("Policy evaluation failed: {e:#}"));
+                }
+                Err(BindingError::from_evaluation_error(e))
+            }
+        };
+        let duration = start.elapsed();
+        let result = decision
+            .as_ref()
+            .map_err(Clone::clone)
+            .and_then(|decision| format(&engine, decision));
+
+        if let Some(ref mut ctx) = telemetry {
+            let response = result
+                .as_ref()
+                .ok()
+                .and_then(|r| serde_json::from_str(r).ok());
+            if let Err(e) = ctx.finalize(response) {
+                debug!("Failed to finalize telemetry: {}", e);
+            }
+        }
+        if streaming {
+            self.publish(&input, &decision, duration, telemetry.as_ref());
+        }
+        result
+    }
+
+    /// Register a callback for decision, error and telemetry events
+    ///
+    /// The callback runs on the evaluating thread after each evaluation, before
+    /// the result is returned, and is shared by all clones of this engine.
+    ///
+    /// # Returns
+    /// A subscription id for [`BindingEngine::unsubscribe`]
+    pub fn subscribe(&self, callback: impl Fn(&BindingEvent) + Send + Sync + 'static) -> u64 {
+        self.subscribers.add(Arc::new(callback))
+    }
+
+    /// Remove a callback; returns false if the id is not subscribed
+    pub fn unsubscribe(&self, id: u64) -> bool {
+        self.subscribers.remove(id)
+    }
+
+    /// Stream the outcome of one evaluation to subscribers
+    fn publish(
+        &self,
+        input: &Value,
+        result: &Result<FinalDecision, BindingError>,
+        duration: Duration,
+        telemetry: Option<&TelemetryContext>,
+    ) {
+        let trace_id = telemetry.map_or_else(generate_trace_id, |ctx| ctx.trace_id().to_string());
+        let hook_event_name = input
+            .get("hook_event_name")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        let duration_ms = duration.as_millis() as u64;
+
+        self.subscribers.publish(&match result {
+            Ok(decision) => BindingEvent::Decision {
+                trace_id: trace_id.clone(),
+                hook_event_name,
+                decision: serde_json::to_value(DecisionOutput::from(decision))
+                    .unwrap_or(Value::Null),
+                duration_ms,
+            },
+            Err(error) => BindingEvent::Error {
+                trace_id: trace_id.clone(),
+                hook_event_name,
+                error: error.clone(),
+                duration_ms,
+            },
+        });
+
+        if let Some(ctx) = telemetry {
+            match serde_json::to_value(&ctx.span) {
+                Ok(span) => self
+                    .subscribers
+                    .publish(&BindingEvent::Telemetry { trace_id, span }),
+                Err(e) => debug!("Failed to serialize telemetry span: {}", e),
+            }
+        }
+    }
+
+    /// Reload policies, rulebooks and signals from disk
+    ///
+    /// Builds a new engine with the original path and options; evaluations already
+    /// in flight finish against the old one. On failure the current engine is kept.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Subsequent evaluations use the reloaded engine
+    /// * `Err(BindingError)` - `PolicyCompile`, `Timeout` or `Internal`
+    pub fn reload(&self) -> Result<(), BindingError> {
+        let result = self
+            .runtime
+            .block_on(Engine::new_with_config(&self.path, self.config.clone()))
+            .map_err(BindingError::from_init_error);
+
+        let mut status = self
+            .reload_status
+            .write()
+            .unwrap_or_else(|e| e.into_inner());
+        let engine = match result {
+            Ok(engine) => engine,
+            Err(error) => {
+                status.last_error = Some(error.to_string());
+                return Err(error);
+            }
+        };
+        match self.inner.write() {
+            Ok(mut guard) => *guard = Arc::new(engine),
+            Err(poisoned) => *poisoned.into_inner() = Arc::new(engine),
+        }
+        *status = ReloadStatus {
+            last_reload: Utc::now(),
+            last_error: None,
+        };
+        Ok(())
+    }
+
+    /// The current engine
+    fn engine(&self) -> Arc<Engine> {
+        match self.inner.read() {
+            Ok(guard) => guard.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Telemetry context for one evaluation, if telemetry is enabled or being streamed
+    ///
+    /// When only streaming, the span is collected for subscribers but not written.
+    fn telemetry_context(
+        &self,
+        engine: &Engine,
+        input: &Value,
+        streaming: bool,
+    ) -> Option<TelemetryContext> {
+        if !self.telemetry && !streaming {
+            return None;
+        }
+
+        let config = self.telemetry.then(|| TelemetryConfig {
+            enabled: true,
+            ..engine.telemetry_config().cloned().unwrap_or_default()
+        });
+        let mut ctx = TelemetryContext::new(input.clone(), self.harness, generate_trace_id());
+        ctx.configure(false, None, config);
+        Some(ctx)
     }
 
     /// Get engine version information
@@ -136,6 +661,58 @@ impl BindingEngine {
         // In the future, we could add more sophisticated health checks
         true
     }
+
+    /// Readiness, version and reload status, for health endpoints
+    pub fn health(&self) -> BindingHealth {
+        let status = self
+            .reload_status
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        BindingHealth {
+            ready: self.is_ready(),
+            version: self.version(),
+            watching: self.watcher.is_some(),
+            last_reload: status.last_reload,
+            last_reload_error: status.last_error,
+        }
+    }
+}
+
+fn parse_input(input_json: &str) -> Result<Value, BindingError> {
+    serde_json::from_str(input_json)
+        .map_err(|e| BindingError::InvalidInput(format!("Invalid input JSON: {e}")))
+}
+
+/// Serialize a decision in its versioned output form (see [`DecisionOutput`])
+fn serialize_decision(decision: &FinalDecision) -> Result<String, BindingError> {
+    serde_json::to_string(&DecisionOutput::from(decision))
+        .map_err(|e| BindingError::Internal(format!("Failed to serialize final decision: {e}")))
+}
+
+/// Apply a FilterOutput decision's `filter` to a tool response
+///
+/// For bindings that run tools in-process (e.g. agent framework middleware)
+/// and must rewrite the result themselves, using the same redaction and
+/// truncation as the harnesses.
+///
+/// # Arguments
+/// * `filter_json` - The decision's `filter` object
+/// * `tool_response_json` - The tool's result
+///
+/// # Returns
+/// * `Ok(String)` - The filtered tool response JSON
+/// * `Err(BindingError)` - Either argument is not valid JSON
+pub fn apply_output_filter(
+    filter_json: &str,
+    tool_response_json: &str,
+) -> Result<String, BindingError> {
+    let filter: OutputFilter = serde_json::from_str(filter_json)
+        .map_err(|e| BindingError::InvalidInput(format!("Invalid output filter JSON: {e}")))?;
+    let tool_response = serde_json::from_str(tool_response_json)
+        .map_err(|e| BindingError::InvalidInput(format!("Invalid tool response JSON: {e}")))?;
+    serde_json::to_string(&filter.apply(&tool_response))
+        .map_err(|e| BindingError::Internal(format!("Failed to serialize tool response: {e}")))
 }
 
 // Compile-time thread safety verification
@@ -171,6 +748,109 @@ mod tests {
         assert!(result.is_err()); // Expected to fail without valid project
     }
 
+    #[test]
+    fn test_invalid_harness_is_invalid_input() {
+        let result = BindingEngine::new("test_path", "not-a-harness");
+        assert!(matches!(result, Err(BindingError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_options_deserialize_from_camel_case() {
+        let options: BindingOptions = serde_json::from_str(
+            r#"{"harness": "cursor", "wasmMaxMemory": 20971520, "failClosed": true, "watch": true}"#,
+        )
+        .unwrap();
+        assert_eq!(options.harness.as_deref(), Some("cursor"));
+        assert!(options.fail_closed);
+        assert!(!options.telemetry);
+        assert!(options.watch);
+        assert_eq!(options.watch_debounce_ms, None);
+
+        let config = options.engine_config().unwrap();
+        assert_eq!(config.harness, HarnessType::Cursor);
+        assert_eq!(config.wasm_max_memory, Some(20971520));
+    }
+
+    #[test]
+    fn test_error_serializes_with_kind() {
+        let error = BindingError::SignalFailure("git_branch timed out".to_string());
+        assert_eq!(
+            error.to_json(),
+            r#"{"kind":"SignalFailure","message":"git_branch timed out"}"#
+        );
+        assert_eq!(error.code(), "SIGNAL_FAILURE");
+    }
+
+    #[test]
+    fn test_evaluation_error_classification() {
+        let signal = anyhow::anyhow!("Signal 'x' failed").context(SignalGatheringFailed);
+        assert!(matches!(
+            BindingError::from_evaluation_error(signal),
+            BindingError::SignalFailure(_)
+        ));
+
+        let memory: anyhow::Error = MemoryLimitExceeded {
+            entrypoint: "cupcake.system.evaluate".to_string(),
+            limit_bytes: 1048576,
+            policies: Vec::new(),
+        }
+        .into();
+        assert!(matches!(
+            BindingError::from_evaluation_error(memory),
+            BindingError::WasmRuntime(_)
+        ));
+    }
+
+    #[test]
+    fn test_subscribers_receive_events_until_removed() {
+        let subscribers = Subscribers::default();
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let id = subscribers.add(Arc::new(move |event: &BindingEvent| {
+            sink.lock()
+                .unwrap()
+                .push(serde_json::to_value(event).unwrap());
+        }));
+
+        let event = BindingEvent::Decision {
+            trace_id: "abc".to_string(),
+            hook_event_name: Some("PreToolUse".to_string()),
+            decision: serde_json::json!({"schema_version": 1, "decision": "Allow"}),
+            duration_ms: 4,
+        };
+        subscribers.publish(&event);
+        assert!(subscribers.remove(id));
+        assert!(!subscribers.remove(id));
+        assert!(subscribers.is_empty());
+        subscribers.publish(&event);
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0]["type"], "decision");
+        assert_eq!(received[0]["traceId"], "abc");
+        assert_eq!(received[0]["hookEventName"], "PreToolUse");
+        assert_eq!(received[0]["durationMs"], 4);
+    }
+
+    #[test]
+    fn test_apply_output_filter() {
+        let filtered = apply_output_filter(
+            r#"{"updated_response": null, "redact": ["sk-[a-z0-9]+"], "max_length": null}"#,
+            r#"{"stdout": "key=sk-abc123", "exit_code": 0}"#,
+        )
+        .unwrap();
+        let filtered: Value = serde_json::from_str(&filtered).unwrap();
+        assert_eq!(
+            filtered,
+            serde_json::json!({"stdout": "key=[REDACTED]", "exit_code": 0})
+        );
+
+        assert!(matches!(
+            apply_output_filter("not json", "{}"),
+            Err(BindingError::InvalidInput(_))
+        ));
+    }
+
     #[test]
     fn test_version() {
         // Version should always work even without initialized engine
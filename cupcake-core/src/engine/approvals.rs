@@ -0,0 +1,273 @@
+//! Human-in-the-loop approvals - escalate `ask` decisions to an external approver
+//!
+//! When `approvals.command` is configured in the rulebook, an Ask decision is not
+//! returned to the agent user directly. Instead the approver command is run with
+//! the decision and event on stdin, and the engine waits (bounded by
+//! `timeout_seconds`) for its verdict:
+//!
+//! - exit code `0` approves - the decision becomes Allow
+//! - any other exit code rejects - the decision becomes Deny
+//! - a timeout or spawn failure applies `on_timeout` (default: keep the Ask)
+//!
+//! The first line of the approver's stdout, if any, is used as its message.
+//! The command can be anything: a script posting a Slack interactive message and
+//! polling for the answer, a web approval service client, or a prompt on another
+//! terminal.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use super::command::{CommandExecutor, CommandSpec, CommandTimedOut};
+use super::decision::FinalDecision;
+use super::rulebook::DEFAULT_MAX_OUTPUT_BYTES;
+use super::sandbox::{ResourceLimits, SandboxConfig};
+
+/// What to do with an Ask when the approver does not answer in time
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ApprovalFallback {
+    /// Fall back to asking the agent user as if no approver were configured
+    #[default]
+    Ask,
+    /// Treat silence as rejection
+    Deny,
+    /// Treat silence as approval
+    Allow,
+}
+
+/// Approval workflow configuration (`approvals:` in rulebook.yml)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalsConfig {
+    /// Approver command - a shell string, argv array or pipeline, like a signal
+    #[serde(default)]
+    pub command: Option<CommandSpec>,
+
+    /// How long to wait for a verdict (default 120)
+    #[serde(default = "default_approval_timeout")]
+    pub timeout_seconds: u64,
+
+    /// Decision applied when the approver times out or cannot be started
+    #[serde(default)]
+    pub on_timeout: ApprovalFallback,
+
+    /// Environment, working directory and network restrictions (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<SandboxConfig>,
+
+    /// CPU, memory and file size limits, enforced on Unix (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<ResourceLimits>,
+}
+
+impl Default for ApprovalsConfig {
+    fn default() -> Self {
+        Self {
+            command: None,
+            timeout_seconds: default_approval_timeout(),
+            on_timeout: ApprovalFallback::default(),
+            sandbox: None,
+            limits: None,
+        }
+    }
+}
+
+fn default_approval_timeout() -> u64 {
+    120
+}
+
+impl ApprovalsConfig {
+    /// Whether Ask decisions should be escalated
+    pub fn is_enabled(&self) -> bool {
+        self.command.as_ref().is_some_and(|c| c.validate().is_ok())
+    }
+}
+
+/// Outcome reported by the approver
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Verdict {
+    Approved(Option<String>),
+    Rejected(Option<String>),
+    TimedOut,
+}
+
+/// Escalate an Ask decision to the configured approver
+///
+/// Non-Ask decisions, and all decisions when no approver is configured, are
+/// returned unchanged.
+pub async fn escalate(
+    config: &ApprovalsConfig,
+    decision: FinalDecision,
+    event: &Value,
+    working_dir: &Path,
+) -> FinalDecision {
+    let Some(command) = config.command.as_ref().filter(|_| config.is_enabled()) else {
+        return decision;
+    };
+    let FinalDecision::Ask {
+        reason,
+        agent_messages,
+    } = decision
+    else {
+        return decision;
+    };
+
+    let request = json!({
+        "decision": "ask",
+        "reason": reason,
+        "agent_messages": agent_messages,
+        "event": event,
+    });
+
+    info!("Escalating Ask decision to approver: {}", command);
+    let verdict = match run_approver(config, command, &request, working_dir).await {
+        Ok(verdict) => verdict,
+        Err(e) if e.is::<CommandTimedOut>() => {
+            warn!("Approver did not answer within {}s", config.timeout_seconds);
+            Verdict::TimedOut
+        }
+        Err(e) => {
+            warn!("Approver failed to run: {:#}", e);
+            Verdict::TimedOut
+        }
+    };
+    debug!("Approver verdict: {:?}", verdict);
+
+    match verdict {
+        Verdict::Approved(_) => FinalDecision::Allow { context: vec![] },
+        Verdict::Rejected(message) => FinalDecision::Deny {
+            reason: format!("Rejected by approver: {}", message.unwrap_or(reason)),
+            agent_messages,
+        },
+        Verdict::TimedOut => match config.on_timeout {
+            ApprovalFallback::Ask => FinalDecision::Ask {
+                reason,
+                agent_messages,
+            },
+            ApprovalFallback::Deny => FinalDecision::Deny {
+                reason: format!("Approval timed out: {reason}"),
+                agent_messages,
+            },
+            ApprovalFallback::Allow => FinalDecision::Allow { context: vec![] },
+        },
+    }
+}
+
+/// Run the approver command and interpret its exit status
+///
+/// `{{event.field}}` placeholders in argv commands resolve against the event.
+async fn run_approver(
+    config: &ApprovalsConfig,
+    command: &CommandSpec,
+    request: &Value,
+    working_dir: &Path,
+) -> Result<Verdict> {
+    let executor = CommandExecutor {
+        timeout: Duration::from_secs(config.timeout_seconds),
+        max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+        working_dir: Some(working_dir),
+        sandbox: config.sandbox.as_ref(),
+        limits: config.limits.as_ref(),
+    };
+    let payload = serde_json::to_vec(request)?;
+    let output = executor
+        .run(command, &payload, &request["event"])
+        .await
+        .context("Approver command failed")?;
+
+    let message = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty());
+
+    Ok(if output.status.success() {
+        Verdict::Approved(message)
+    } else {
+        Verdict::Rejected(message)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ask() -> FinalDecision {
+        FinalDecision::Ask {
+            reason: "Confirm deploy".to_string(),
+            agent_messages: vec![],
+        }
+    }
+
+    fn approver(command: &str, on_timeout: ApprovalFallback) -> ApprovalsConfig {
+        ApprovalsConfig {
+            command: Some(command.into()),
+            timeout_seconds: 1,
+            on_timeout,
+            sandbox: None,
+            limits: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_disabled_leaves_ask_untouched() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let decision = escalate(&ApprovalsConfig::default(), ask(), &json!({}), dir.path()).await;
+        assert!(decision.is_ask());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_verdicts() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let event = json!({"tool_name": "Bash"});
+
+        let approved = escalate(
+            &approver("cat > /dev/null; exit 0", ApprovalFallback::Ask),
+            ask(),
+            &event,
+            dir.path(),
+        )
+        .await;
+        assert!(matches!(approved, FinalDecision::Allow { .. }));
+
+        let rejected = escalate(
+            &approver(
+                "cat > /dev/null; echo 'not during freeze'; exit 1",
+                ApprovalFallback::Ask,
+            ),
+            ask(),
+            &event,
+            dir.path(),
+        )
+        .await;
+        match rejected {
+            FinalDecision::Deny { reason, .. } => {
+                assert_eq!(reason, "Rejected by approver: not during freeze")
+            }
+            other => panic!("Expected Deny, got {other:?}"),
+        }
+
+        let timed_out = escalate(
+            &approver("sleep 5", ApprovalFallback::Deny),
+            ask(),
+            &event,
+            dir.path(),
+        )
+        .await;
+        assert!(timed_out.is_blocking());
+
+        // Non-Ask decisions never reach the approver
+        let allow = escalate(
+            &approver("exit 1", ApprovalFallback::Ask),
+            FinalDecision::Allow { context: vec![] },
+            &event,
+            dir.path(),
+        )
+        .await;
+        assert!(matches!(allow, FinalDecision::Allow { .. }));
+    }
+}
@@ -4,11 +4,13 @@
 //! abstractions that simplify common security patterns without writing Rego.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 use tracing::{debug, info};
 
-use super::rulebook::SignalConfig;
+use super::command::CommandSpec;
+use super::rulebook::{SignalConfig, DEFAULT_MAX_OUTPUT_BYTES};
+use crate::preprocessing::obfuscation_detector::IndicatorKind;
 
 /// Configuration for all builtin abstractions
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -37,6 +39,54 @@ pub struct BuiltinsConfig {
     #[serde(default)]
     pub git_block_no_verify: Option<GitBlockNoVerifyConfig>,
 
+    /// Dependency guard configuration (package allow/deny lists)
+    #[serde(default)]
+    pub dependency_guard: Option<DependencyGuardConfig>,
+
+    /// Container guard configuration (docker/podman/kubectl safety)
+    #[serde(default)]
+    pub container_guard: Option<ContainerGuardConfig>,
+
+    /// Terraform plan gate configuration (plan before apply)
+    #[serde(default)]
+    pub terraform_plan_gate: Option<TerraformPlanGateConfig>,
+
+    /// Change freeze configuration (time windows and freeze periods)
+    #[serde(default)]
+    pub change_freeze: Option<ChangeFreezeConfig>,
+
+    /// Rate limit configuration (per-session tool/command budgets)
+    #[serde(default)]
+    pub rate_limit: Option<RateLimitConfig>,
+
+    /// Mass deletion guard configuration (oversized deletions in one operation)
+    #[serde(default)]
+    pub mass_deletion_guard: Option<MassDeletionGuardConfig>,
+
+    /// License header configuration (required headers for new files)
+    #[serde(default)]
+    pub license_header: Option<LicenseHeaderConfig>,
+
+    /// Binary file guard configuration (binary blobs and oversized writes)
+    #[serde(default)]
+    pub binary_file_guard: Option<BinaryFileGuardConfig>,
+
+    /// Prompt injection guard configuration (prompts and fetched content)
+    #[serde(default)]
+    pub prompt_injection_guard: Option<PromptInjectionGuardConfig>,
+
+    /// Environment exfiltration guard configuration (secret env vars and .env files)
+    #[serde(default)]
+    pub env_exfiltration_guard: Option<EnvExfiltrationGuardConfig>,
+
+    /// Obfuscation guard configuration (commands built from variables, aliases or eval)
+    #[serde(default)]
+    pub obfuscation_guard: Option<ObfuscationGuardConfig>,
+
+    /// MCP allowlist configuration (which MCP servers and tools may be called)
+    #[serde(default)]
+    pub mcp_allowlist: Option<McpAllowlistConfig>,
+
     // Global-only builtins (for machine-wide security)
     /// System protection configuration - prevents modification of OS paths
     #[serde(default)]
@@ -53,6 +103,11 @@ pub struct BuiltinsConfig {
     /// Enforce full file read - prevents partial reads of small files (Claude Code only)
     #[serde(default, alias = "enforce_full_file_read")]
     pub claude_code_enforce_full_file_read: Option<EnforceFullFileReadConfig>,
+
+    /// Watchdog stop review - keeps the agent working when the watchdog judges
+    /// the task unfinished (Claude Code only, needs the watchdog enabled)
+    #[serde(default, alias = "watchdog_stop_review")]
+    pub claude_code_watchdog_stop_review: Option<WatchdogStopReviewConfig>,
 }
 
 /// Configuration for always_inject_on_prompt builtin
@@ -188,6 +243,511 @@ fn default_git_block_no_verify_message() -> String {
     "Git operations with --no-verify are not permitted. Commit hooks must run.".to_string()
 }
 
+/// Decision emitted by builtins that support a configurable response
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BuiltinAction {
+    /// Block the action outright
+    #[default]
+    Deny,
+    /// Ask the user to confirm
+    Ask,
+}
+
+/// Response for builtins that report findings to the agent rather than gate a tool
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackAction {
+    /// Inject a warning into the agent's context
+    #[default]
+    AddContext,
+    /// Block with feedback the agent must address
+    Block,
+}
+
+/// Configuration for dependency_guard builtin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyGuardConfig {
+    /// Whether this builtin is enabled (defaults to true)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Package names that may never be added (supports `*` wildcards)
+    #[serde(default)]
+    pub deny: Vec<String>,
+
+    /// If non-empty, only these packages may be installed from the shell
+    #[serde(default)]
+    pub allow: Vec<String>,
+
+    /// Whether violations are denied or escalated to the user
+    #[serde(default)]
+    pub action: BuiltinAction,
+
+    /// Custom message for blocked installs
+    #[serde(default = "default_dependency_guard_message")]
+    pub message: String,
+}
+
+fn default_dependency_guard_message() -> String {
+    "Dependency blocked by project policy".to_string()
+}
+
+/// Configuration for container_guard builtin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerGuardConfig {
+    /// Whether this builtin is enabled (defaults to true)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Host paths to protect from bind mounts (beyond the defaults)
+    #[serde(default)]
+    pub additional_mounts: Vec<String>,
+
+    /// Whether matches are denied or escalated to the user
+    #[serde(default)]
+    pub action: BuiltinAction,
+
+    /// Custom message for blocked operations
+    #[serde(default = "default_container_guard_message")]
+    pub message: String,
+}
+
+fn default_container_guard_message() -> String {
+    "Container operation blocked by project policy".to_string()
+}
+
+/// Configuration for terraform_plan_gate builtin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerraformPlanGateConfig {
+    /// Whether this builtin is enabled (defaults to true)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Saved plan file written by `terraform plan -out=<plan_file>`
+    #[serde(default = "default_plan_file")]
+    pub plan_file: String,
+
+    /// A plan older than this no longer counts as reviewed
+    #[serde(default = "default_max_plan_age_minutes")]
+    pub max_plan_age_minutes: u64,
+
+    /// Permit `terraform apply -auto-approve`
+    #[serde(default)]
+    pub allow_auto_approve: bool,
+
+    /// Permit `terraform destroy`, `apply -destroy` and state removal
+    #[serde(default)]
+    pub allow_destroy: bool,
+
+    /// Custom message for blocked operations
+    #[serde(default = "default_terraform_plan_gate_message")]
+    pub message: String,
+}
+
+fn default_plan_file() -> String {
+    "tfplan".to_string()
+}
+
+fn default_max_plan_age_minutes() -> u64 {
+    60
+}
+
+fn default_terraform_plan_gate_message() -> String {
+    "Terraform change blocked".to_string()
+}
+
+impl TerraformPlanGateConfig {
+    /// Shell command reporting whether the saved plan is recent and newer
+    /// than every .tf/.tfvars file in the working directory
+    fn status_command(&self) -> String {
+        let plan = format!("'{}'", self.plan_file.replace('\'', "'\\''"));
+        format!(
+            "if [ ! -f {plan} ]; then echo '{{\"plan_exists\":false,\"fresh\":false}}'; \
+             elif [ -n \"$(find {plan} -mmin +{age})\" ] || \
+             [ -n \"$(find . -maxdepth 1 \\( -name '*.tf' -o -name '*.tfvars' \\) -newer {plan} | head -n 1)\" ]; \
+             then echo '{{\"plan_exists\":true,\"fresh\":false}}'; \
+             else echo '{{\"plan_exists\":true,\"fresh\":true}}'; fi",
+            age = self.max_plan_age_minutes,
+        )
+    }
+}
+
+/// Configuration for change_freeze builtin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeFreezeConfig {
+    /// Whether this builtin is enabled (defaults to true)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Commands subject to the freeze (matched as whole words, e.g. "git push")
+    #[serde(default = "default_freeze_operations")]
+    pub operations: Vec<String>,
+
+    /// Times when changes are allowed; outside them operations are blocked
+    #[serde(default)]
+    pub windows: Vec<ChangeWindow>,
+
+    /// Declared freeze periods
+    #[serde(default)]
+    pub freezes: Vec<FreezePeriod>,
+
+    /// Change-management endpoint returning `{"frozen": bool, "reason": "..."}`
+    #[serde(default)]
+    pub status_url: Option<String>,
+
+    /// Whether matches are denied or escalated to the user
+    #[serde(default)]
+    pub action: BuiltinAction,
+
+    /// Custom message for blocked operations
+    #[serde(default = "default_change_freeze_message")]
+    pub message: String,
+}
+
+/// A recurring window when changes are allowed (local time)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeWindow {
+    /// Weekdays the window applies to (e.g. ["mon", "tue"]); empty means every day
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<String>,
+
+    /// Start time, "HH:MM"
+    pub start: String,
+
+    /// End time (exclusive), "HH:MM"
+    pub end: String,
+}
+
+/// A period during which guarded operations are frozen
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FreezePeriod {
+    /// Start date or datetime, e.g. "2026-12-20" or "2026-12-20T18:00"
+    pub start: String,
+
+    /// End date or datetime (inclusive)
+    pub end: String,
+
+    /// Shown to the agent when the freeze applies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+fn default_freeze_operations() -> Vec<String> {
+    [
+        "git push",
+        "kubectl apply",
+        "helm install",
+        "helm upgrade",
+        "terraform apply",
+        "npm publish",
+        "cargo publish",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+fn default_change_freeze_message() -> String {
+    "Change blocked by change freeze policy".to_string()
+}
+
+/// Parse "HH:MM" into minutes since midnight
+fn parse_clock(clock: &str) -> Option<u32> {
+    let (hours, minutes) = clock.split_once(':')?;
+    let (hours, minutes): (u32, u32) = (hours.parse().ok()?, minutes.parse().ok()?);
+    // "24:00" is accepted so a window can run to the end of the day
+    (hours < 24 && minutes < 60 || hours == 24 && minutes == 0).then_some(hours * 60 + minutes)
+}
+
+/// Configuration for rate_limit builtin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    /// Whether this builtin is enabled (defaults to true)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Limits checked on every tool invocation
+    #[serde(default)]
+    pub limits: Vec<RateLimitRule>,
+
+    /// Whether exceeding a limit is denied or escalated to the user
+    #[serde(default)]
+    pub action: BuiltinAction,
+
+    /// Custom message for rate-limited operations
+    #[serde(default = "default_rate_limit_message")]
+    pub message: String,
+}
+
+/// A single budget, e.g. at most 3 `git push` per session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimitRule {
+    /// Identifier shown in the denial reason
+    pub name: String,
+
+    /// Tool names this limit applies to; empty means any tool
+    #[serde(default)]
+    pub tools: Vec<String>,
+
+    /// Only count shell commands containing this phrase (e.g. "git push")
+    #[serde(default)]
+    pub command: Option<String>,
+
+    /// Invocations allowed per window
+    pub max: u32,
+
+    /// Window the count is kept over
+    #[serde(default)]
+    pub per: RateWindow,
+}
+
+/// Window a rate limit is counted over
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RateWindow {
+    /// The whole agent session
+    #[default]
+    Session,
+    /// A sliding one-minute window
+    Minute,
+    /// A sliding one-hour window
+    Hour,
+}
+
+fn default_rate_limit_message() -> String {
+    "Rate limit exceeded".to_string()
+}
+
+/// Configuration for mass_deletion_guard builtin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MassDeletionGuardConfig {
+    /// Whether this builtin is enabled (defaults to true)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Net lines a single Edit, MultiEdit or Write may remove
+    #[serde(default = "default_max_deleted_lines")]
+    pub max_deleted_lines: usize,
+
+    /// Files a single shell command may remove or revert
+    #[serde(default = "default_max_deleted_files")]
+    pub max_files: usize,
+
+    /// Whether violations are denied or escalated to the user
+    #[serde(default)]
+    pub action: BuiltinAction,
+
+    /// Custom message for blocked operations
+    #[serde(default = "default_mass_deletion_guard_message")]
+    pub message: String,
+}
+
+fn default_max_deleted_lines() -> usize {
+    200
+}
+
+fn default_max_deleted_files() -> usize {
+    20
+}
+
+fn default_mass_deletion_guard_message() -> String {
+    "Large deletion blocked".to_string()
+}
+
+/// Configuration for license_header builtin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseHeaderConfig {
+    /// Whether this builtin is enabled (defaults to true)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Required header text by file extension (e.g. "rs" -> "// SPDX-License-Identifier: MIT")
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+
+    /// Whether a missing header is reported as context or blocks the agent
+    #[serde(default)]
+    pub action: FeedbackAction,
+
+    /// Custom message prefixed to the instruction
+    #[serde(default = "default_license_header_message")]
+    pub message: String,
+}
+
+fn default_license_header_message() -> String {
+    "License header required".to_string()
+}
+
+/// Configuration for binary_file_guard builtin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinaryFileGuardConfig {
+    /// Whether this builtin is enabled (defaults to true)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Largest content a single Write may contain
+    #[serde(default = "default_max_file_bytes")]
+    pub max_file_bytes: u64,
+
+    /// File extensions (without dot) that may never be written or edited
+    #[serde(default = "default_binary_extensions")]
+    pub binary_extensions: Vec<String>,
+
+    /// Also protect paths marked `binary` or `-diff` in .gitattributes
+    #[serde(default = "default_respect_gitattributes")]
+    pub respect_gitattributes: bool,
+
+    /// Whether violations are denied or escalated to the user
+    #[serde(default)]
+    pub action: BuiltinAction,
+
+    /// Custom message for blocked writes
+    #[serde(default = "default_binary_file_guard_message")]
+    pub message: String,
+}
+
+fn default_max_file_bytes() -> u64 {
+    1024 * 1024
+}
+
+fn default_binary_extensions() -> Vec<String> {
+    [
+        "exe", "dll", "so", "dylib", "o", "a", "lib", "bin", "class", "jar", "pyc", "wasm", "zip",
+        "gz", "tgz", "bz2", "xz", "7z", "tar",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+fn default_respect_gitattributes() -> bool {
+    true
+}
+
+fn default_binary_file_guard_message() -> String {
+    "Binary or oversized file write blocked".to_string()
+}
+
+/// Configuration for prompt_injection_guard builtin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptInjectionGuardConfig {
+    /// Whether this builtin is enabled (defaults to true)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Phrases to flag in addition to the built-in markers (case-insensitive)
+    #[serde(default)]
+    pub additional_markers: Vec<String>,
+
+    /// Whether findings are added as a warning or block the prompt/result
+    #[serde(default)]
+    pub action: FeedbackAction,
+
+    /// Custom message describing the finding
+    #[serde(default = "default_prompt_injection_guard_message")]
+    pub message: String,
+}
+
+fn default_prompt_injection_guard_message() -> String {
+    "Possible prompt injection detected".to_string()
+}
+
+/// Configuration for env_exfiltration_guard builtin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvExfiltrationGuardConfig {
+    /// Whether this builtin is enabled (defaults to true)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Variable names treated as secrets (case-insensitive, `*` wildcards)
+    #[serde(default = "default_sensitive_env_patterns")]
+    pub sensitive_patterns: Vec<String>,
+
+    /// Variables that may be printed even though they match a pattern
+    #[serde(default)]
+    pub allow_vars: Vec<String>,
+
+    /// Whether violations are denied or escalated to the user
+    #[serde(default)]
+    pub action: BuiltinAction,
+
+    /// Custom message for blocked operations
+    #[serde(default = "default_env_exfiltration_guard_message")]
+    pub message: String,
+}
+
+fn default_sensitive_env_patterns() -> Vec<String> {
+    [
+        "*SECRET*",
+        "*TOKEN*",
+        "*PASSWORD*",
+        "*API_KEY*",
+        "*PRIVATE_KEY*",
+        "AWS_*",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+fn default_env_exfiltration_guard_message() -> String {
+    "Environment variable exposure blocked".to_string()
+}
+
+/// Configuration for obfuscation_guard builtin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObfuscationGuardConfig {
+    /// Whether this builtin is enabled (defaults to true)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Indicator kinds to let through (e.g. "alias_definition")
+    #[serde(default)]
+    pub ignore_indicators: Vec<String>,
+
+    /// Whether flagged commands are escalated to the user (default) or denied
+    #[serde(default = "default_obfuscation_guard_action")]
+    pub action: BuiltinAction,
+
+    /// Custom message for flagged commands
+    #[serde(default = "default_obfuscation_guard_message")]
+    pub message: String,
+}
+
+fn default_obfuscation_guard_action() -> BuiltinAction {
+    BuiltinAction::Ask
+}
+
+fn default_obfuscation_guard_message() -> String {
+    "Obfuscated command requires confirmation".to_string()
+}
+
+/// Configuration for mcp_allowlist builtin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpAllowlistConfig {
+    /// Whether this builtin is enabled (defaults to true)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Allowed MCP servers, each with the tools that may be called on it
+    /// (supports `*` wildcards; an empty list allows every tool)
+    #[serde(default)]
+    pub servers: BTreeMap<String, Vec<String>>,
+
+    /// Whether unlisted calls are denied or escalated to the user
+    #[serde(default)]
+    pub action: BuiltinAction,
+
+    /// Custom message for blocked MCP calls
+    #[serde(default = "default_mcp_allowlist_message")]
+    pub message: String,
+}
+
+fn default_mcp_allowlist_message() -> String {
+    "MCP tool is not on the allowlist".to_string()
+}
+
 // Global builtin configurations
 
 /// Configuration for system protection builtin (global only)
@@ -274,6 +834,30 @@ fn default_enforce_full_read_message() -> String {
     "Please read the entire file first (files under 2000 lines must be read completely)".to_string()
 }
 
+/// Configuration for watchdog_stop_review builtin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchdogStopReviewConfig {
+    /// Whether this builtin is enabled (defaults to true)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Lowest watchdog confidence that blocks the stop
+    #[serde(default = "default_stop_review_min_confidence")]
+    pub min_confidence: f64,
+
+    /// Message shown before the watchdog's reasoning and missing items
+    #[serde(default = "default_stop_review_message")]
+    pub message: String,
+}
+
+fn default_stop_review_min_confidence() -> f64 {
+    0.7
+}
+
+fn default_stop_review_message() -> String {
+    "The task doesn't look finished yet.".to_string()
+}
+
 impl BuiltinsConfig {
     /// Validate configuration and return errors if invalid
     pub fn validate(&self) -> Result<(), Vec<String>> {
@@ -371,6 +955,217 @@ impl BuiltinsConfig {
 
         // Validate git_block_no_verify (no specific validation needed - it's simple)
 
+        // Validate dependency_guard
+        if let Some(config) = &self.dependency_guard {
+            if config.enabled && config.deny.is_empty() && config.allow.is_empty() {
+                errors.push(
+                    "dependency_guard: enabled but neither deny nor allow configured".to_string(),
+                );
+            }
+
+            for (list, patterns) in [("deny", &config.deny), ("allow", &config.allow)] {
+                for (idx, pattern) in patterns.iter().enumerate() {
+                    if pattern.trim().is_empty() {
+                        errors.push(format!(
+                            "dependency_guard.{list}[{idx}]: package name cannot be empty"
+                        ));
+                    }
+                }
+            }
+        }
+
+        // Validate container_guard
+        if let Some(config) = &self.container_guard {
+            for (idx, path) in config.additional_mounts.iter().enumerate() {
+                if path.trim().is_empty() {
+                    errors.push(format!(
+                        "container_guard.additional_mounts[{idx}]: path cannot be empty"
+                    ));
+                }
+            }
+        }
+
+        // Validate terraform_plan_gate
+        if let Some(config) = &self.terraform_plan_gate {
+            if config.enabled && config.plan_file.trim().is_empty() {
+                errors.push("terraform_plan_gate: plan_file cannot be empty".to_string());
+            }
+        }
+
+        // Validate change_freeze
+        if let Some(config) = &self.change_freeze {
+            if config.enabled
+                && config.windows.is_empty()
+                && config.freezes.is_empty()
+                && config.status_url.is_none()
+            {
+                errors.push(
+                    "change_freeze: enabled but no windows, freezes or status_url configured"
+                        .to_string(),
+                );
+            }
+
+            const DAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+            for (idx, window) in config.windows.iter().enumerate() {
+                match (parse_clock(&window.start), parse_clock(&window.end)) {
+                    (Some(start), Some(end)) if start < end => {}
+                    (Some(_), Some(_)) => errors.push(format!(
+                        "change_freeze.windows[{idx}]: start must be before end (split overnight windows in two)"
+                    )),
+                    _ => errors.push(format!(
+                        "change_freeze.windows[{idx}]: times must be in HH:MM format"
+                    )),
+                }
+                for day in &window.days {
+                    let day = day.to_lowercase();
+                    if !DAYS.iter().any(|d| day.starts_with(d)) {
+                        errors.push(format!("change_freeze.windows[{idx}]: unknown day '{day}'"));
+                    }
+                }
+            }
+
+            for (idx, freeze) in config.freezes.iter().enumerate() {
+                if freeze.start.trim().is_empty() || freeze.end.trim().is_empty() {
+                    errors.push(format!(
+                        "change_freeze.freezes[{idx}]: start and end are required"
+                    ));
+                } else if freeze.start > freeze.end {
+                    errors.push(format!(
+                        "change_freeze.freezes[{idx}]: start must not be after end"
+                    ));
+                }
+            }
+        }
+
+        // Validate rate_limit
+        if let Some(config) = &self.rate_limit {
+            if config.enabled && config.limits.is_empty() {
+                errors.push("rate_limit: enabled but no limits configured".to_string());
+            }
+
+            let mut names = std::collections::HashSet::new();
+            for (idx, rule) in config.limits.iter().enumerate() {
+                if rule.name.trim().is_empty() {
+                    errors.push(format!("rate_limit.limits[{idx}]: name cannot be empty"));
+                } else if !names.insert(rule.name.as_str()) {
+                    errors.push(format!(
+                        "rate_limit.limits[{idx}]: duplicate name '{}'",
+                        rule.name
+                    ));
+                }
+                if rule.command.as_deref().is_some_and(|c| c.trim().is_empty()) {
+                    errors.push(format!("rate_limit.limits[{idx}]: command cannot be empty"));
+                }
+            }
+        }
+
+        // Validate mass_deletion_guard
+        if let Some(config) = &self.mass_deletion_guard {
+            if config.enabled && config.max_deleted_lines == 0 {
+                errors.push("mass_deletion_guard: max_deleted_lines cannot be 0".to_string());
+            }
+            if config.enabled && config.max_files == 0 {
+                errors.push("mass_deletion_guard: max_files cannot be 0".to_string());
+            }
+        }
+
+        // Validate license_header
+        if let Some(config) = &self.license_header {
+            if config.enabled && config.headers.is_empty() {
+                errors.push("license_header: enabled but no headers configured".to_string());
+            }
+
+            for (ext, header) in &config.headers {
+                if header.trim().is_empty() {
+                    errors.push(format!(
+                        "license_header.headers.{ext}: header cannot be empty"
+                    ));
+                }
+                if ext.contains('.') {
+                    errors.push(format!(
+                        "license_header.headers.{ext}: extension should not include dot (use 'rs' not '.rs')"
+                    ));
+                }
+            }
+        }
+
+        // Validate binary_file_guard
+        if let Some(config) = &self.binary_file_guard {
+            if config.enabled && config.max_file_bytes == 0 {
+                errors.push("binary_file_guard: max_file_bytes cannot be 0".to_string());
+            }
+
+            for ext in &config.binary_extensions {
+                if ext.contains('.') {
+                    errors.push(format!(
+                        "binary_file_guard.binary_extensions: '{ext}' should not include dot (use 'exe' not '.exe')"
+                    ));
+                }
+            }
+        }
+
+        // Validate prompt_injection_guard
+        if let Some(config) = &self.prompt_injection_guard {
+            if config
+                .additional_markers
+                .iter()
+                .any(|m| m.trim().is_empty())
+            {
+                errors.push(
+                    "prompt_injection_guard.additional_markers: markers cannot be empty"
+                        .to_string(),
+                );
+            }
+        }
+
+        // Validate env_exfiltration_guard
+        if let Some(config) = &self.env_exfiltration_guard {
+            if config.enabled && config.sensitive_patterns.is_empty() {
+                errors.push(
+                    "env_exfiltration_guard: enabled but no sensitive_patterns configured"
+                        .to_string(),
+                );
+            }
+            if config
+                .sensitive_patterns
+                .iter()
+                .any(|p| p.trim().is_empty())
+            {
+                errors.push(
+                    "env_exfiltration_guard.sensitive_patterns: patterns cannot be empty"
+                        .to_string(),
+                );
+            }
+        }
+
+        // Validate obfuscation_guard
+        if let Some(config) = &self.obfuscation_guard {
+            for kind in &config.ignore_indicators {
+                if !IndicatorKind::ALL.contains(&kind.as_str()) {
+                    errors.push(format!(
+                        "obfuscation_guard.ignore_indicators: unknown indicator '{kind}' (expected one of: {})",
+                        IndicatorKind::ALL.join(", ")
+                    ));
+                }
+            }
+        }
+
+        // Validate mcp_allowlist
+        if let Some(config) = &self.mcp_allowlist {
+            for (server, tools) in &config.servers {
+                if server.is_empty() || server.contains("__") {
+                    errors.push(format!(
+                        "mcp_allowlist.servers: invalid server name '{server}'"
+                    ));
+                }
+                if tools.iter().any(|t| t.is_empty()) {
+                    errors.push(format!(
+                        "mcp_allowlist.servers.{server}: tool names cannot be empty"
+                    ));
+                }
+            }
+        }
+
         // Validate claude_code_enforce_full_file_read
         if let Some(config) = &self.claude_code_enforce_full_file_read {
             if config.enabled && config.max_lines == 0 {
@@ -379,6 +1174,16 @@ impl BuiltinsConfig {
             }
         }
 
+        // Validate claude_code_watchdog_stop_review
+        if let Some(config) = &self.claude_code_watchdog_stop_review {
+            if config.enabled && !(0.0..=1.0).contains(&config.min_confidence) {
+                errors.push(
+                    "claude_code_watchdog_stop_review: min_confidence must be between 0 and 1"
+                        .to_string(),
+                );
+            }
+        }
+
         if errors.is_empty() {
             Ok(())
         } else {
@@ -399,6 +1204,24 @@ impl BuiltinsConfig {
                 .is_some_and(|c| c.enabled)
             || self.protected_paths.as_ref().is_some_and(|c| c.enabled)
             || self.git_block_no_verify.as_ref().is_some_and(|c| c.enabled)
+            || self.dependency_guard.as_ref().is_some_and(|c| c.enabled)
+            || self.container_guard.as_ref().is_some_and(|c| c.enabled)
+            || self.terraform_plan_gate.as_ref().is_some_and(|c| c.enabled)
+            || self.change_freeze.as_ref().is_some_and(|c| c.enabled)
+            || self.rate_limit.as_ref().is_some_and(|c| c.enabled)
+            || self.mass_deletion_guard.as_ref().is_some_and(|c| c.enabled)
+            || self.license_header.as_ref().is_some_and(|c| c.enabled)
+            || self.binary_file_guard.as_ref().is_some_and(|c| c.enabled)
+            || self
+                .prompt_injection_guard
+                .as_ref()
+                .is_some_and(|c| c.enabled)
+            || self
+                .env_exfiltration_guard
+                .as_ref()
+                .is_some_and(|c| c.enabled)
+            || self.obfuscation_guard.as_ref().is_some_and(|c| c.enabled)
+            || self.mcp_allowlist.as_ref().is_some_and(|c| c.enabled)
             || self.system_protection.as_ref().is_some_and(|c| c.enabled)
             || self
                 .sensitive_data_protection
@@ -412,6 +1235,10 @@ impl BuiltinsConfig {
                 .claude_code_enforce_full_file_read
                 .as_ref()
                 .is_some_and(|c| c.enabled)
+            || self
+                .claude_code_watchdog_stop_review
+                .as_ref()
+                .is_some_and(|c| c.enabled)
     }
 
     /// Get list of enabled builtin names
@@ -444,6 +1271,50 @@ impl BuiltinsConfig {
         if self.git_block_no_verify.as_ref().is_some_and(|c| c.enabled) {
             enabled.push("git_block_no_verify".to_string());
         }
+        if self.dependency_guard.as_ref().is_some_and(|c| c.enabled) {
+            enabled.push("dependency_guard".to_string());
+        }
+        if self.container_guard.as_ref().is_some_and(|c| c.enabled) {
+            enabled.push("container_guard".to_string());
+        }
+        if self.terraform_plan_gate.as_ref().is_some_and(|c| c.enabled) {
+            enabled.push("terraform_plan_gate".to_string());
+        }
+        if self.change_freeze.as_ref().is_some_and(|c| c.enabled) {
+            enabled.push("change_freeze".to_string());
+        }
+        if self.rate_limit.as_ref().is_some_and(|c| c.enabled) {
+            enabled.push("rate_limit".to_string());
+        }
+        if self.mass_deletion_guard.as_ref().is_some_and(|c| c.enabled) {
+            enabled.push("mass_deletion_guard".to_string());
+        }
+        if self.license_header.as_ref().is_some_and(|c| c.enabled) {
+            enabled.push("license_header".to_string());
+        }
+        if self.binary_file_guard.as_ref().is_some_and(|c| c.enabled) {
+            enabled.push("binary_file_guard".to_string());
+        }
+        if self
+            .prompt_injection_guard
+            .as_ref()
+            .is_some_and(|c| c.enabled)
+        {
+            enabled.push("prompt_injection_guard".to_string());
+        }
+        if self
+            .env_exfiltration_guard
+            .as_ref()
+            .is_some_and(|c| c.enabled)
+        {
+            enabled.push("env_exfiltration_guard".to_string());
+        }
+        if self.obfuscation_guard.as_ref().is_some_and(|c| c.enabled) {
+            enabled.push("obfuscation_guard".to_string());
+        }
+        if self.mcp_allowlist.as_ref().is_some_and(|c| c.enabled) {
+            enabled.push("mcp_allowlist".to_string());
+        }
         if self.system_protection.as_ref().is_some_and(|c| c.enabled) {
             enabled.push("system_protection".to_string());
         }
@@ -468,6 +1339,13 @@ impl BuiltinsConfig {
         {
             enabled.push("claude_code_enforce_full_file_read".to_string());
         }
+        if self
+            .claude_code_watchdog_stop_review
+            .as_ref()
+            .is_some_and(|c| c.enabled)
+        {
+            enabled.push("claude_code_watchdog_stop_review".to_string());
+        }
 
         enabled
     }
@@ -501,8 +1379,11 @@ impl BuiltinsConfig {
                     signals.insert(
                         signal_name,
                         SignalConfig {
-                            command: check.command.clone(),
+                            command: check.command.clone().into(),
                             timeout_seconds: 30, // Reasonable timeout for tests/linting
+                            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+                            sandbox: None,
+                            limits: None,
                         },
                     );
                 }
@@ -517,26 +1398,101 @@ impl BuiltinsConfig {
                     signals.insert(
                         signal_name,
                         SignalConfig {
-                            command: check.command.clone(),
+                            command: check.command.clone().into(),
                             timeout_seconds: 10, // Quick feedback for edit checks
+                            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+                            sandbox: None,
+                            limits: None,
                         },
                     );
                 }
             }
         }
 
-        // rulebook_security_guardrails: No signals needed - static config injected directly
-
-        // protected_paths: No signals needed - static config injected directly
-
-        // Generate signals for git_block_no_verify (if needed)
-        if let Some(config) = &self.git_block_no_verify {
-            if config.enabled {
-                // No signals needed for this simple builtin - it just blocks patterns
-                // But we could add a signal for the message if desired
+        // rulebook_security_guardrails: No signals needed - static config injected directly
+
+        // protected_paths: No signals needed - static config injected directly
+
+        // Generate signals for git_block_no_verify (if needed)
+        if let Some(config) = &self.git_block_no_verify {
+            if config.enabled {
+                // No signals needed for this simple builtin - it just blocks patterns
+                // But we could add a signal for the message if desired
+            }
+        }
+
+        // dependency_guard: No signals needed - static config injected directly
+
+        // container_guard: No signals needed - static config injected directly
+
+        // Generate the plan status signal for terraform_plan_gate
+        if let Some(config) = &self.terraform_plan_gate {
+            if config.enabled {
+                signals.insert(
+                    "__builtin_terraform_plan_gate_status".to_string(),
+                    SignalConfig {
+                        command: config.status_command().into(),
+                        timeout_seconds: 5,
+                        max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+                        sandbox: None,
+                        limits: None,
+                    },
+                );
+            }
+        }
+
+        // Generate the change-management status signal for change_freeze
+        if let Some(config) = &self.change_freeze {
+            if let Some(url) = config.status_url.as_ref().filter(|_| config.enabled) {
+                signals.insert(
+                    "__builtin_change_freeze_status".to_string(),
+                    SignalConfig {
+                        // argv, so the URL needs no quoting
+                        command: CommandSpec::Argv(
+                            ["curl", "-fsS", "--max-time", "5", url.as_str()]
+                                .map(String::from)
+                                .to_vec(),
+                        ),
+                        timeout_seconds: 10,
+                        max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+                        sandbox: None,
+                        limits: None,
+                    },
+                );
+            }
+        }
+
+        // rate_limit: No signals needed - usage is recorded by the engine
+
+        // mass_deletion_guard: No signals needed - sizes come from the tool input
+
+        // license_header: No signals needed - the written content is in the tool input
+
+        // Generate the .gitattributes pattern signal for binary_file_guard
+        if let Some(config) = &self.binary_file_guard {
+            if config.enabled && config.respect_gitattributes {
+                signals.insert(
+                    "__builtin_binary_file_guard_gitattributes".to_string(),
+                    SignalConfig {
+                        // Prints the pattern of every line marking files binary or -diff
+                        command: "[ -f .gitattributes ] && awk '$1 !~ /^#/ && /[ \\t](binary|-diff)([ \\t]|$)/ { print $1 }' .gitattributes || true".into(),
+                        timeout_seconds: 5,
+                        max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+                        sandbox: None,
+                        limits: None,
+                    },
+                );
             }
         }
 
+        // prompt_injection_guard: No signals needed - prompts and results are in the input
+
+        // env_exfiltration_guard: No signals needed - .env values are never read
+
+        // obfuscation_guard: No signals needed - indicators come from preprocessing
+
+        // mcp_allowlist: No signals needed - server and tool come from preprocessing
+
         // system_protection: No signals needed - static config injected directly
 
         // sensitive_data_protection: No signals needed - static config injected directly
@@ -545,6 +1501,8 @@ impl BuiltinsConfig {
 
         // enforce_full_file_read: No signals needed - static config injected directly
 
+        // watchdog_stop_review: No signals needed - the verdict is routed via required_watchdog
+
         if !signals.is_empty() {
             info!("Generated {} signals for enabled builtins", signals.len());
             for name in signals.keys() {
@@ -578,11 +1536,14 @@ impl BuiltinsConfig {
         // Add rulebook_security_guardrails config if enabled
         if let Some(config) = &self.rulebook_security_guardrails {
             if config.enabled {
+                // The module cache key must stay out of the agent's reach
+                let mut protected_paths = config.protected_paths.clone();
+                protected_paths.push(super::module_cache::PROTECTED_PATH.to_string());
                 configs.insert(
                     "rulebook_security_guardrails".to_string(),
                     json!({
                         "message": config.message,
-                        "protected_paths": config.protected_paths,
+                        "protected_paths": protected_paths,
                     }),
                 );
             }
@@ -601,6 +1562,19 @@ impl BuiltinsConfig {
             }
         }
 
+        // Add claude_code_watchdog_stop_review config if enabled
+        if let Some(config) = &self.claude_code_watchdog_stop_review {
+            if config.enabled {
+                configs.insert(
+                    "claude_code_watchdog_stop_review".to_string(),
+                    json!({
+                        "message": config.message,
+                        "min_confidence": config.min_confidence,
+                    }),
+                );
+            }
+        }
+
         // Add git_block_no_verify config if enabled
         if let Some(config) = &self.git_block_no_verify {
             if config.enabled {
@@ -614,6 +1588,185 @@ impl BuiltinsConfig {
             }
         }
 
+        // Add dependency_guard config if enabled
+        if let Some(config) = &self.dependency_guard {
+            if config.enabled {
+                configs.insert(
+                    "dependency_guard".to_string(),
+                    json!({
+                        "message": config.message,
+                        "action": config.action,
+                        "deny": config.deny,
+                        "allow": config.allow,
+                    }),
+                );
+            }
+        }
+
+        // Add container_guard config if enabled
+        if let Some(config) = &self.container_guard {
+            if config.enabled {
+                configs.insert(
+                    "container_guard".to_string(),
+                    json!({
+                        "message": config.message,
+                        "action": config.action,
+                        "additional_mounts": config.additional_mounts,
+                    }),
+                );
+            }
+        }
+
+        // Add terraform_plan_gate config if enabled
+        if let Some(config) = &self.terraform_plan_gate {
+            if config.enabled {
+                configs.insert(
+                    "terraform_plan_gate".to_string(),
+                    json!({
+                        "message": config.message,
+                        "plan_file": config.plan_file,
+                        "allow_auto_approve": config.allow_auto_approve,
+                        "allow_destroy": config.allow_destroy,
+                    }),
+                );
+            }
+        }
+
+        // Add change_freeze config if enabled
+        if let Some(config) = &self.change_freeze {
+            if config.enabled {
+                configs.insert(
+                    "change_freeze".to_string(),
+                    json!({
+                        "message": config.message,
+                        "action": config.action,
+                        "operations": config.operations,
+                        "windows": config.windows,
+                        "freezes": config.freezes,
+                    }),
+                );
+            }
+        }
+
+        // Add rate_limit config if enabled (usage is added per evaluation)
+        if let Some(config) = &self.rate_limit {
+            if config.enabled {
+                configs.insert(
+                    "rate_limit".to_string(),
+                    json!({
+                        "message": config.message,
+                        "action": config.action,
+                    }),
+                );
+            }
+        }
+
+        // Add mass_deletion_guard config if enabled
+        if let Some(config) = &self.mass_deletion_guard {
+            if config.enabled {
+                configs.insert(
+                    "mass_deletion_guard".to_string(),
+                    json!({
+                        "message": config.message,
+                        "action": config.action,
+                        "max_deleted_lines": config.max_deleted_lines,
+                        "max_files": config.max_files,
+                    }),
+                );
+            }
+        }
+
+        // Add license_header config if enabled (extensions matched case-insensitively)
+        if let Some(config) = &self.license_header {
+            if config.enabled {
+                let headers: HashMap<String, &String> = config
+                    .headers
+                    .iter()
+                    .map(|(ext, header)| (ext.to_lowercase(), header))
+                    .collect();
+                configs.insert(
+                    "license_header".to_string(),
+                    json!({
+                        "message": config.message,
+                        "action": config.action,
+                        "headers": headers,
+                    }),
+                );
+            }
+        }
+
+        // Add binary_file_guard config if enabled
+        if let Some(config) = &self.binary_file_guard {
+            if config.enabled {
+                configs.insert(
+                    "binary_file_guard".to_string(),
+                    json!({
+                        "message": config.message,
+                        "action": config.action,
+                        "max_file_bytes": config.max_file_bytes,
+                        "binary_extensions": config.binary_extensions,
+                    }),
+                );
+            }
+        }
+
+        // Add prompt_injection_guard config if enabled
+        if let Some(config) = &self.prompt_injection_guard {
+            if config.enabled {
+                configs.insert(
+                    "prompt_injection_guard".to_string(),
+                    json!({
+                        "message": config.message,
+                        "action": config.action,
+                        "additional_markers": config.additional_markers,
+                    }),
+                );
+            }
+        }
+
+        // Add env_exfiltration_guard config if enabled
+        if let Some(config) = &self.env_exfiltration_guard {
+            if config.enabled {
+                configs.insert(
+                    "env_exfiltration_guard".to_string(),
+                    json!({
+                        "message": config.message,
+                        "action": config.action,
+                        "sensitive_patterns": config.sensitive_patterns,
+                        "allow_vars": config.allow_vars,
+                    }),
+                );
+            }
+        }
+
+        // Add obfuscation_guard config if enabled
+        if let Some(config) = &self.obfuscation_guard {
+            if config.enabled {
+                configs.insert(
+                    "obfuscation_guard".to_string(),
+                    json!({
+                        "message": config.message,
+                        "action": config.action,
+                        "ignore_indicators": config.ignore_indicators,
+                    }),
+                );
+            }
+        }
+
+        // Add mcp_allowlist config if enabled
+        if let Some(config) = &self.mcp_allowlist {
+            if config.enabled {
+                configs.insert(
+                    "mcp_allowlist".to_string(),
+                    json!({
+                        "message": config.message,
+                        "action": config.action,
+                        "servers": config.servers,
+                    }),
+                );
+            }
+        }
+
         // Add system_protection config if enabled
         if let Some(config) = &self.system_protection {
             if config.enabled {
@@ -700,6 +1853,28 @@ impl BuiltinsConfig {
         // Return the signal name for this extension
         Some(format!("__builtin_post_edit_{extension}"))
     }
+
+    /// Get the signals referenced by `{{signal.name}}` placeholders in static
+    /// always_inject_on_prompt contexts, so they're gathered before evaluation
+    pub fn get_prompt_template_signals(&self) -> Vec<String> {
+        let Some(config) = self
+            .claude_code_always_inject_on_prompt
+            .as_ref()
+            .filter(|c| c.enabled)
+        else {
+            return Vec::new();
+        };
+
+        config
+            .context
+            .iter()
+            .filter_map(|source| match source {
+                ContextSource::String(s) => Some(s),
+                ContextSource::Dynamic { .. } => None,
+            })
+            .flat_map(|s| crate::engine::synthesis::templating::referenced_signals(s))
+            .collect()
+    }
 }
 
 /// Convert a ContextSource to a SignalConfig (only for dynamic sources)
@@ -713,13 +1888,19 @@ fn context_source_to_signal(source: &ContextSource) -> Option<SignalConfig> {
         ContextSource::Dynamic { file, command } => {
             if let Some(cmd) = command {
                 Some(SignalConfig {
-                    command: cmd.clone(),
+                    command: cmd.clone().into(),
                     timeout_seconds: 5,
+                    max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+                    sandbox: None,
+                    limits: None,
                 })
             } else {
                 file.as_ref().map(|path| SignalConfig {
-                    command: format!("cat '{}'", path.replace('\'', "\\'")),
+                    command: CommandSpec::Argv(vec!["cat".to_string(), path.clone()]),
                     timeout_seconds: 2,
+                    max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+                    sandbox: None,
+                    limits: None,
                 })
             }
         }
@@ -785,6 +1966,21 @@ git_block_no_verify:
         );
     }
 
+    #[test]
+    fn test_prompt_template_signals() {
+        let yaml = r#"
+claude_code_always_inject_on_prompt:
+  enabled: true
+  context:
+    - "Current branch: {{signal.git_branch}}"
+    - "Prompt event: {{event.hook_event_name}}"
+    - file: "CONTEXT.md"
+"#;
+
+        let config: BuiltinsConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert_eq!(config.get_prompt_template_signals(), vec!["git_branch"]);
+    }
+
     #[test]
     fn test_default_enabled() {
         // Test that builtins default to DISABLED when field is omitted
@@ -911,4 +2107,322 @@ git_pre_check:
         assert_eq!(signals.len(), 1);
         assert!(signals.contains_key("__builtin_git_check_0"));
     }
+
+    #[test]
+    fn test_dependency_guard_config() {
+        let yaml = r#"
+dependency_guard:
+  enabled: true
+  deny: ["event-stream", "@evil/*"]
+  action: ask
+"#;
+        let config: BuiltinsConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+        assert!(config
+            .enabled_builtins()
+            .contains(&"dependency_guard".to_string()));
+
+        let json = config.to_json_configs();
+        let guard = &json["dependency_guard"];
+        assert_eq!(guard["action"], "ask");
+        assert_eq!(guard["deny"][1], "@evil/*");
+        assert_eq!(guard["message"], "Dependency blocked by project policy");
+
+        // Enabled with neither list is a configuration error
+        let empty: BuiltinsConfig =
+            serde_yaml_ng::from_str("dependency_guard:\n  enabled: true\n").unwrap();
+        assert!(empty.validate().is_err());
+    }
+
+    #[test]
+    fn test_container_guard_config() {
+        let yaml = r#"
+container_guard:
+  enabled: true
+  additional_mounts: ["/srv/secrets"]
+"#;
+        let config: BuiltinsConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+
+        let json = config.to_json_configs();
+        let guard = &json["container_guard"];
+        assert_eq!(guard["action"], "deny");
+        assert_eq!(guard["additional_mounts"][0], "/srv/secrets");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_terraform_plan_gate_status_signal() {
+        let config: BuiltinsConfig =
+            serde_yaml_ng::from_str("terraform_plan_gate:\n  enabled: true\n").unwrap();
+        let signals = config.generate_signals();
+        let status = &signals["__builtin_terraform_plan_gate_status"];
+
+        let run = |dir: &Path| {
+            let output = std::process::Command::new("sh")
+                .arg("-c")
+                .arg(status.command.to_string())
+                .current_dir(dir)
+                .output()
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+        };
+
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("main.tf"), "").unwrap();
+        assert_eq!(run(dir.path())["plan_exists"], false);
+
+        // A plan written after the last config change is fresh
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        std::fs::write(dir.path().join("tfplan"), "").unwrap();
+        assert_eq!(run(dir.path())["fresh"], true);
+
+        // Editing configuration afterwards invalidates it
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        std::fs::write(dir.path().join("main.tf"), "# changed").unwrap();
+        assert_eq!(run(dir.path())["fresh"], false);
+    }
+
+    #[test]
+    fn test_change_freeze_config() {
+        let yaml = r#"
+change_freeze:
+  enabled: true
+  windows:
+    - days: ["Mon", "tue"]
+      start: "09:00"
+      end: "17:30"
+  freezes:
+    - start: "2026-12-20"
+      end: "2027-01-03"
+  status_url: https://change.example.com/freeze
+"#;
+        let config: BuiltinsConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+        assert!(config
+            .generate_signals()
+            .contains_key("__builtin_change_freeze_status"));
+
+        let json = config.to_json_configs();
+        let freeze = &json["change_freeze"];
+        assert_eq!(freeze["operations"][0], "git push");
+        assert_eq!(freeze["windows"][0]["end"], "17:30");
+        // Unset optional fields are omitted rather than serialized as null
+        assert!(freeze["freezes"][0].get("reason").is_none());
+
+        let bad: BuiltinsConfig = serde_yaml_ng::from_str(
+            "change_freeze:\n  windows:\n    - days: [funday]\n      start: \"18:00\"\n      end: \"9am\"\n",
+        )
+        .unwrap();
+        let errors = bad.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.contains("HH:MM")));
+        assert!(errors.iter().any(|e| e.contains("unknown day")));
+    }
+
+    #[test]
+    fn test_mass_deletion_guard_config() {
+        let yaml = r#"
+mass_deletion_guard:
+  enabled: true
+  max_files: 5
+  action: ask
+"#;
+        let config: BuiltinsConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+        assert!(config
+            .enabled_builtins()
+            .contains(&"mass_deletion_guard".to_string()));
+
+        let json = config.to_json_configs();
+        let guard = &json["mass_deletion_guard"];
+        assert_eq!(guard["max_deleted_lines"], 200);
+        assert_eq!(guard["max_files"], 5);
+        assert_eq!(guard["action"], "ask");
+
+        let bad: BuiltinsConfig = serde_yaml_ng::from_str(
+            "mass_deletion_guard:\n  enabled: true\n  max_deleted_lines: 0\n",
+        )
+        .unwrap();
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_license_header_config() {
+        let yaml = r#"
+license_header:
+  enabled: true
+  action: block
+  headers:
+    RS: "// SPDX-License-Identifier: Apache-2.0"
+    ".ts": "// Copyright Example Corp"
+"#;
+        let config: BuiltinsConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("should not include dot"));
+
+        let json = config.to_json_configs();
+        let header = &json["license_header"];
+        assert_eq!(header["action"], "block");
+        assert_eq!(
+            header["headers"]["rs"],
+            "// SPDX-License-Identifier: Apache-2.0"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_binary_file_guard_gitattributes_signal() {
+        let config: BuiltinsConfig =
+            serde_yaml_ng::from_str("binary_file_guard:\n  enabled: true\n").unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.to_json_configs()["binary_file_guard"]["max_file_bytes"],
+            1024 * 1024
+        );
+
+        let signals = config.generate_signals();
+        let signal = &signals["__builtin_binary_file_guard_gitattributes"];
+
+        let dir = tempfile::tempdir().unwrap();
+        let run = || {
+            let output = std::process::Command::new("sh")
+                .arg("-c")
+                .arg(signal.command.to_string())
+                .current_dir(dir.path())
+                .output()
+                .unwrap();
+            String::from_utf8(output.stdout).unwrap()
+        };
+
+        // No .gitattributes is not an error
+        assert_eq!(run(), "");
+
+        std::fs::write(
+            dir.path().join(".gitattributes"),
+            "# assets\n*.png binary\n*.rs text eol=lf\nvendor/blob.dat\t-diff -merge\n",
+        )
+        .unwrap();
+        assert_eq!(run(), "*.png\nvendor/blob.dat\n");
+    }
+
+    #[test]
+    fn test_prompt_injection_guard_config() {
+        let yaml = r#"
+prompt_injection_guard:
+  enabled: true
+  additional_markers: ["reveal your system prompt"]
+"#;
+        let config: BuiltinsConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+
+        let json = config.to_json_configs();
+        let guard = &json["prompt_injection_guard"];
+        assert_eq!(guard["action"], "add_context");
+        assert_eq!(guard["additional_markers"][0], "reveal your system prompt");
+
+        let bad: BuiltinsConfig = serde_yaml_ng::from_str(
+            "prompt_injection_guard:\n  enabled: true\n  additional_markers: [\" \"]\n",
+        )
+        .unwrap();
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_watchdog_stop_review_config() {
+        let config: BuiltinsConfig = serde_yaml_ng::from_str(
+            "watchdog_stop_review:\n  enabled: true\n  min_confidence: 0.9\n",
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.enabled_builtins(),
+            vec!["claude_code_watchdog_stop_review"]
+        );
+
+        let json = config.to_json_configs();
+        let review = &json["claude_code_watchdog_stop_review"];
+        assert_eq!(review["min_confidence"], 0.9);
+        assert_eq!(review["message"], "The task doesn't look finished yet.");
+
+        let bad: BuiltinsConfig = serde_yaml_ng::from_str(
+            "watchdog_stop_review:\n  enabled: true\n  min_confidence: 70\n",
+        )
+        .unwrap();
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_env_exfiltration_guard_config() {
+        let yaml = r#"
+env_exfiltration_guard:
+  enabled: true
+  allow_vars: ["GITHUB_TOKEN_URL"]
+  action: ask
+"#;
+        let config: BuiltinsConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+
+        let json = config.to_json_configs();
+        let guard = &json["env_exfiltration_guard"];
+        assert_eq!(guard["sensitive_patterns"][0], "*SECRET*");
+        assert_eq!(guard["allow_vars"][0], "GITHUB_TOKEN_URL");
+        assert_eq!(guard["action"], "ask");
+
+        let bad: BuiltinsConfig = serde_yaml_ng::from_str(
+            "env_exfiltration_guard:\n  enabled: true\n  sensitive_patterns: []\n",
+        )
+        .unwrap();
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_obfuscation_guard_config() {
+        let yaml = r#"
+obfuscation_guard:
+  enabled: true
+  ignore_indicators: ["alias_definition"]
+"#;
+        let config: BuiltinsConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+        assert!(config
+            .enabled_builtins()
+            .contains(&"obfuscation_guard".to_string()));
+
+        let json = config.to_json_configs();
+        let guard = &json["obfuscation_guard"];
+        assert_eq!(guard["action"], "ask");
+        assert_eq!(guard["ignore_indicators"][0], "alias_definition");
+
+        let bad: BuiltinsConfig = serde_yaml_ng::from_str(
+            "obfuscation_guard:\n  enabled: true\n  ignore_indicators: [\"unknown\"]\n",
+        )
+        .unwrap();
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_mcp_allowlist_config() {
+        let yaml = r#"
+mcp_allowlist:
+  enabled: true
+  servers:
+    github: ["*"]
+    filesystem: ["read_file", "list_*"]
+"#;
+        let config: BuiltinsConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+        assert!(config
+            .enabled_builtins()
+            .contains(&"mcp_allowlist".to_string()));
+
+        let json = config.to_json_configs();
+        let allowlist = &json["mcp_allowlist"];
+        assert_eq!(allowlist["action"], "deny");
+        assert_eq!(allowlist["servers"]["filesystem"][1], "list_*");
+
+        let bad: BuiltinsConfig =
+            serde_yaml_ng::from_str("mcp_allowlist:\n  servers:\n    \"a__b\": []\n").unwrap();
+        assert!(bad.validate().is_err());
+    }
 }
@@ -0,0 +1,633 @@
+//! CommandExecutor - runs signal and approver commands
+//!
+//! A command in the rulebook takes one of three forms:
+//!
+//! ```yaml
+//! signals:
+//!   # Shell string - run with `sh -c` (Git Bash or PowerShell on Windows)
+//!   git_branch:
+//!     command: "git rev-parse --abbrev-ref HEAD"
+//!   # Argv array - run directly, no shell
+//!   lint_file:
+//!     command: ["npx", "eslint", "--format", "json", "{{event.tool_input.file_path}}"]
+//!   # Pipeline - stages connected by pipes, optional file redirects
+//!   todo_count:
+//!     command:
+//!       pipeline:
+//!         - ["git", "grep", "-n", "TODO"]
+//!         - ["wc", "-l"]
+//! ```
+//!
+//! Argv and pipeline commands never pass through a shell. `{{event.field}}`
+//! placeholders are resolved against the hook event (see
+//! [`super::synthesis::templating`]) and each one expands to exactly one
+//! argument, so values an agent controls - file paths, commands - can't inject
+//! shell syntax. Shell strings are kept for compatibility and get no
+//! placeholder substitution.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Stdio};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::process::{Child, Command};
+
+use super::sandbox::{ResourceLimits, SandboxConfig};
+use super::synthesis::templating;
+
+/// A command to run, as written in the rulebook
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum CommandSpec {
+    /// Shell command line
+    Shell(String),
+    /// Program and arguments, run without a shell
+    Argv(Vec<String>),
+    /// Programs connected by pipes, run without a shell
+    Pipeline(PipelineSpec),
+}
+
+/// Stages of a pipeline and its redirects
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineSpec {
+    /// Argv of each stage; each stage's stdout feeds the next one's stdin
+    pub pipeline: Vec<Vec<String>>,
+
+    /// Read the first stage's stdin from this file instead of the event (`<`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stdin_file: Option<String>,
+
+    /// Write the last stage's stdout to this file instead of capturing it (`>`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stdout_file: Option<String>,
+
+    /// Append to `stdout_file` rather than truncating it (`>>`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub append: bool,
+}
+
+impl From<String> for CommandSpec {
+    fn from(command: String) -> Self {
+        Self::Shell(command)
+    }
+}
+
+impl From<&str> for CommandSpec {
+    fn from(command: &str) -> Self {
+        Self::Shell(command.to_string())
+    }
+}
+
+impl std::fmt::Display for CommandSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Shell(command) => f.write_str(command),
+            Self::Argv(argv) => f.write_str(&display_argv(argv)),
+            Self::Pipeline(spec) => {
+                let stages: Vec<String> = spec.pipeline.iter().map(|a| display_argv(a)).collect();
+                f.write_str(&stages.join(" | "))?;
+                if let Some(file) = &spec.stdin_file {
+                    write!(f, " < {file}")?;
+                }
+                if let Some(file) = &spec.stdout_file {
+                    write!(f, " {} {file}", if spec.append { ">>" } else { ">" })?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Argv for logs and telemetry - arguments with spaces are quoted
+fn display_argv(argv: &[String]) -> String {
+    argv.iter()
+        .map(|arg| {
+            if arg.is_empty() || arg.contains(char::is_whitespace) {
+                format!("'{arg}'")
+            } else {
+                arg.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl CommandSpec {
+    /// Problems with the command's shape, for rulebook validation
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            Self::Shell(command) if command.trim().is_empty() => bail!("command is empty"),
+            Self::Argv(argv) if argv.first().is_none_or(|p| p.trim().is_empty()) => {
+                bail!("command argv has no program")
+            }
+            Self::Pipeline(spec) => {
+                if spec.pipeline.is_empty() {
+                    bail!("pipeline has no stages");
+                }
+                if let Some(idx) = spec
+                    .pipeline
+                    .iter()
+                    .position(|argv| argv.first().is_none_or(|p| p.trim().is_empty()))
+                {
+                    bail!("pipeline stage {} has no program", idx + 1);
+                }
+                if spec.append && spec.stdout_file.is_none() {
+                    bail!("append requires stdout_file");
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The command did not finish within its timeout and was killed
+#[derive(Debug, Clone, Copy)]
+pub struct CommandTimedOut(pub Duration);
+
+impl std::fmt::Display for CommandTimedOut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Command timed out after {}s", self.0.as_secs())
+    }
+}
+
+impl std::error::Error for CommandTimedOut {}
+
+impl CommandTimedOut {
+    /// Structured result for telemetry in place of the command's output
+    pub fn to_json(&self) -> Value {
+        serde_json::json!({
+            "timed_out": true,
+            "timeout_ms": self.0.as_millis() as u64,
+        })
+    }
+}
+
+/// What a finished command produced
+#[derive(Debug)]
+pub struct CommandOutput {
+    /// Exit status of the command (the last stage of a pipeline)
+    pub status: ExitStatus,
+    /// Captured stdout, at most `max_output_bytes`
+    pub stdout: Vec<u8>,
+    /// Bytes written to stdout in total, including any discarded
+    pub stdout_total: usize,
+    /// Captured stderr (of the last stage), at most `max_output_bytes`
+    pub stderr: Vec<u8>,
+}
+
+impl CommandOutput {
+    /// Whether stdout exceeded the limit and was cut short
+    pub fn truncated(&self) -> bool {
+        self.stdout_total > self.stdout.len()
+    }
+}
+
+/// Runs commands with a timeout and capped output
+///
+/// Every process is killed if the run times out or is dropped.
+#[derive(Debug, Clone)]
+pub struct CommandExecutor<'a> {
+    /// Kill the command after this long
+    pub timeout: Duration,
+    /// Bytes kept from each of stdout and stderr; the rest is drained and discarded
+    pub max_output_bytes: usize,
+    /// Working directory (default: the current one)
+    pub working_dir: Option<&'a Path>,
+    /// Environment, directory and network restrictions (default: none)
+    pub sandbox: Option<&'a SandboxConfig>,
+    /// CPU, memory and file limits (default: none)
+    pub limits: Option<&'a ResourceLimits>,
+}
+
+impl CommandExecutor<'_> {
+    /// Run a command, writing `stdin` to it and resolving placeholders against `event`
+    ///
+    /// Fails with [`CommandTimedOut`] if the command outlives the timeout.
+    pub async fn run(
+        &self,
+        spec: &CommandSpec,
+        stdin: &[u8],
+        event: &Value,
+    ) -> Result<CommandOutput> {
+        spec.validate()?;
+        if let Some(sandbox) = self.sandbox {
+            sandbox.validate()?;
+        }
+        let mut stages = self.spawn(spec, event)?;
+
+        let result =
+            tokio::time::timeout(self.timeout, self.collect(spec, &mut stages, stdin)).await;
+        match result {
+            Ok(output) => output,
+            Err(_) => {
+                kill_process_groups(&stages);
+                // Dropping the children kills any the group kill missed (kill_on_drop)
+                Err(CommandTimedOut(self.timeout).into())
+            }
+        }
+    }
+
+    fn command(&self, program: &str, args: &[String]) -> Result<Command> {
+        let mut command = match self.sandbox {
+            Some(sandbox) => sandbox.command(program, args)?,
+            None => {
+                let mut command = Command::new(program);
+                command.args(args);
+                command
+            }
+        };
+        command.kill_on_drop(true);
+        if let Some(limits) = self.limits {
+            limits.apply(&mut command);
+        }
+        // Own process group, so a timeout also kills whatever the command started
+        #[cfg(unix)]
+        command.process_group(0);
+        if let Some(dir) = self.dir() {
+            command.current_dir(dir);
+        }
+        Ok(command)
+    }
+
+    /// Where the command runs - the sandbox's directory within the working directory
+    fn dir(&self) -> Option<PathBuf> {
+        match self.sandbox {
+            Some(sandbox) => sandbox.working_dir(self.working_dir),
+            None => self.working_dir.map(Path::to_path_buf),
+        }
+    }
+
+    /// Spawn every process of the command, wired together
+    fn spawn(&self, spec: &CommandSpec, event: &Value) -> Result<Vec<Child>> {
+        let pipeline = match spec {
+            CommandSpec::Shell(line) => {
+                // sh on Unix; Git Bash or PowerShell on Windows (see shell_invocation)
+                let (program, args) = super::config::shell_invocation(line);
+                let child = self
+                    .command(program, &args)?
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped())
+                    .spawn()
+                    .with_context(|| format!("Failed to spawn `{program}`"))?;
+                return Ok(vec![child]);
+            }
+            CommandSpec::Argv(argv) => PipelineSpec {
+                pipeline: vec![argv.clone()],
+                stdin_file: None,
+                stdout_file: None,
+                append: false,
+            },
+            CommandSpec::Pipeline(spec) => spec.clone(),
+        };
+
+        let last = pipeline.pipeline.len() - 1;
+        let mut children: Vec<Child> = Vec::with_capacity(pipeline.pipeline.len());
+        for (idx, argv) in pipeline.pipeline.iter().enumerate() {
+            let argv: Vec<String> = argv
+                .iter()
+                .map(|arg| templating::render(arg, event))
+                .collect();
+            let mut command = self.command(&argv[0], &argv[1..])?;
+
+            let stdin = match children.last_mut() {
+                Some(previous) => previous
+                    .stdout
+                    .take()
+                    .context("Pipeline stage stdout not captured")?
+                    .try_into()
+                    .context("Failed to connect pipeline stages")?,
+                None => match &pipeline.stdin_file {
+                    Some(file) => Stdio::from(
+                        self.open(file, |o| o.read(true))
+                            .with_context(|| format!("Failed to open stdin_file '{file}'"))?,
+                    ),
+                    None => Stdio::piped(),
+                },
+            };
+            let stdout = match (&pipeline.stdout_file, idx == last) {
+                (Some(file), true) => Stdio::from(
+                    self.open(file, |o| {
+                        o.create(true).write(true);
+                        if pipeline.append {
+                            o.append(true)
+                        } else {
+                            o.truncate(true)
+                        }
+                    })
+                    .with_context(|| format!("Failed to open stdout_file '{file}'"))?,
+                ),
+                _ => Stdio::piped(),
+            };
+            let stderr = if idx == last {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            };
+
+            let child = command
+                .stdin(stdin)
+                .stdout(stdout)
+                .stderr(stderr)
+                .spawn()
+                .with_context(|| format!("Failed to spawn `{}`", argv[0]))?;
+            children.push(child);
+        }
+        Ok(children)
+    }
+
+    /// Open a redirect target, relative to the directory the command runs in
+    fn open(
+        &self,
+        file: &str,
+        options: impl FnOnce(&mut std::fs::OpenOptions) -> &mut std::fs::OpenOptions,
+    ) -> std::io::Result<std::fs::File> {
+        let path = match self.dir() {
+            Some(dir) => dir.join(file),
+            None => PathBuf::from(file),
+        };
+        options(&mut std::fs::OpenOptions::new()).open(path)
+    }
+
+    /// Feed stdin, capture the last stage's output and wait for every stage
+    async fn collect(
+        &self,
+        spec: &CommandSpec,
+        stages: &mut [Child],
+        stdin: &[u8],
+    ) -> Result<CommandOutput> {
+        let reads_event = !matches!(spec, CommandSpec::Pipeline(p) if p.stdin_file.is_some());
+        if let Some(mut pipe) = stages[0].stdin.take().filter(|_| reads_event) {
+            // Commands that don't read stdin close it early - not an error
+            let _ = pipe.write_all(stdin).await;
+            let _ = pipe.flush().await;
+        }
+
+        let (last, earlier) = stages.split_last_mut().expect("at least one stage");
+        let stdout_pipe = last.stdout.take();
+        let stderr_pipe = last.stderr.take();
+        let limit = self.max_output_bytes;
+
+        let ((stdout, stdout_total), (stderr, _), status) = tokio::try_join!(
+            read_capped(stdout_pipe, limit),
+            read_capped(stderr_pipe, limit),
+            last.wait(),
+        )
+        .context("Failed to wait for command output")?;
+        for stage in earlier {
+            stage
+                .wait()
+                .await
+                .context("Failed to wait for pipeline stage")?;
+        }
+
+        Ok(CommandOutput {
+            status,
+            stdout,
+            stdout_total,
+            stderr,
+        })
+    }
+}
+
+/// Kill every process the stages started, not just the stages themselves
+#[cfg(unix)]
+fn kill_process_groups(stages: &[Child]) {
+    for pid in stages.iter().filter_map(Child::id) {
+        // SAFETY: kill has no memory-safety preconditions; each stage leads
+        // its own process group, so -pid addresses only that group
+        unsafe {
+            libc::kill(-(pid as libc::pid_t), libc::SIGKILL);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_process_groups(_stages: &[Child]) {}
+
+/// Read up to `limit` bytes from a pipe, then drain the remainder
+///
+/// Returns the captured bytes and the total number of bytes the process wrote.
+async fn read_capped<R>(reader: Option<R>, limit: usize) -> std::io::Result<(Vec<u8>, usize)>
+where
+    R: AsyncRead + Unpin,
+{
+    let Some(mut reader) = reader else {
+        return Ok((Vec::new(), 0));
+    };
+    let mut captured = Vec::new();
+    (&mut reader)
+        .take(limit as u64)
+        .read_to_end(&mut captured)
+        .await?;
+    let rest = tokio::io::copy(&mut reader, &mut tokio::io::sink()).await?;
+    let total = captured.len() + rest as usize;
+    Ok((captured, total))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn executor(dir: Option<&Path>) -> CommandExecutor<'_> {
+        CommandExecutor {
+            timeout: Duration::from_secs(5),
+            max_output_bytes: 1024,
+            working_dir: dir,
+            sandbox: None,
+            limits: None,
+        }
+    }
+
+    fn spec(yaml: &str) -> CommandSpec {
+        serde_yaml_ng::from_str(yaml).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_argv_placeholders_are_single_arguments() {
+        let event = json!({"tool_input": {"file_path": "a.txt; touch pwned"}});
+        let output = executor(None)
+            .run(
+                &spec(r#"["printf", "%s|", "{{event.tool_input.file_path}}", "x"]"#),
+                b"",
+                &event,
+            )
+            .await
+            .unwrap();
+
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"a.txt; touch pwned|x|");
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_and_redirects() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("in.txt"), "b\na\nb\n").unwrap();
+        let exec = executor(Some(dir.path()));
+
+        let output = exec
+            .run(
+                &spec("pipeline: [[sort], [uniq]]\nstdin_file: in.txt"),
+                b"",
+                &Value::Null,
+            )
+            .await
+            .unwrap();
+        assert_eq!(output.stdout, b"a\nb\n");
+
+        // Event JSON goes to the first stage's stdin; output can go to a file
+        let pipeline =
+            spec("pipeline: [[cat], [tr, a-z, A-Z]]\nstdout_file: out.txt\nappend: true");
+        exec.run(&pipeline, b"one\n", &Value::Null).await.unwrap();
+        exec.run(&pipeline, b"two\n", &Value::Null).await.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("out.txt")).unwrap(),
+            "ONE\nTWO\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shell_string_timeout_and_truncation() {
+        let exec = executor(None);
+        let output = exec
+            .run(&"head -c 4096 /dev/zero".into(), b"", &Value::Null)
+            .await
+            .unwrap();
+        assert!(output.truncated());
+        assert_eq!((output.stdout.len(), output.stdout_total), (1024, 4096));
+
+        let slow = CommandExecutor {
+            timeout: Duration::from_millis(200),
+            ..exec
+        };
+        let err = slow
+            .run(&spec(r#"["sleep", "5"]"#), b"", &Value::Null)
+            .await
+            .unwrap_err();
+        assert!(err.is::<CommandTimedOut>());
+    }
+
+    #[tokio::test]
+    async fn test_sandbox_env_and_working_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        let sandbox: SandboxConfig =
+            serde_yaml_ng::from_str("env: [CUPCAKE_SANDBOX_TEST]\nworking_dir: sub").unwrap();
+        let exec = CommandExecutor {
+            sandbox: Some(&sandbox),
+            ..executor(Some(dir.path()))
+        };
+
+        std::env::set_var("CUPCAKE_SANDBOX_TEST", "kept");
+        std::env::set_var("CUPCAKE_SANDBOX_SECRET", "scrubbed");
+        let output = exec
+            .run(
+                &"printf '%s:%s:%s' \"$CUPCAKE_SANDBOX_TEST\" \"$CUPCAKE_SANDBOX_SECRET\" \"$(basename \"$PWD\")\""
+                    .into(),
+                b"",
+                &Value::Null,
+            )
+            .await
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "kept::sub");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_sandbox_network_namespace() {
+        let offline = SandboxConfig {
+            network: false,
+            ..SandboxConfig::default()
+        };
+        let exec = CommandExecutor {
+            sandbox: Some(&offline),
+            ..executor(None)
+        };
+        let netns = spec(r#"["readlink", "/proc/self/ns/net"]"#);
+
+        match exec.run(&netns, b"", &Value::Null).await {
+            Ok(output) => {
+                let ours = std::fs::read_link("/proc/self/ns/net").unwrap();
+                assert!(output.status.success());
+                assert_ne!(
+                    String::from_utf8_lossy(&output.stdout).trim(),
+                    ours.to_string_lossy()
+                );
+            }
+            // Kernels without unprivileged user namespaces refuse to run it
+            Err(e) => assert!(format!("{e:#}").contains("Failed to spawn"), "{e:#}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_limits_kill_runaways() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let limits = ResourceLimits {
+            cpu_seconds: Some(1),
+            ..ResourceLimits::default()
+        };
+        let exec = CommandExecutor {
+            limits: Some(&limits),
+            ..executor(None)
+        };
+        let output = exec
+            .run(&"while :; do :; done".into(), b"", &Value::Null)
+            .await
+            .unwrap();
+        assert!(matches!(
+            output.status.signal(),
+            Some(libc::SIGXCPU | libc::SIGKILL)
+        ));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_timeout_kills_the_process_group() {
+        let dir = tempfile::tempdir().unwrap();
+        let exec = CommandExecutor {
+            timeout: Duration::from_millis(300),
+            ..executor(Some(dir.path()))
+        };
+        let err = exec
+            .run(
+                &"sleep 30 & echo $! > child.pid; wait".into(),
+                b"",
+                &Value::Null,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<CommandTimedOut>().unwrap().to_json(),
+            json!({"timed_out": true, "timeout_ms": 300})
+        );
+
+        // The backgrounded sleep went down with the shell - gone, or a zombie
+        // where nothing reaps orphans (containers)
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let pid = std::fs::read_to_string(dir.path().join("child.pid")).unwrap();
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid.trim()));
+        assert!(stat.map_or(true, |stat| stat.contains(") Z ")));
+    }
+
+    #[test]
+    fn test_spec_forms_and_validation() {
+        assert_eq!(
+            spec("\"git status\""),
+            CommandSpec::Shell("git status".into())
+        );
+        assert_eq!(
+            spec("pipeline: [[git, log], [head, '-n', '1']]").to_string(),
+            "git log | head -n 1"
+        );
+        assert!(spec("[]").validate().is_err());
+        assert!(spec("pipeline: [[ls], []]").validate().is_err());
+        assert!(spec("pipeline: [[ls]]\nappend: true").validate().is_err());
+        assert!(serde_yaml_ng::from_str::<CommandSpec>("pipeline: [[ls]]\nstdout: x").is_err());
+    }
+}
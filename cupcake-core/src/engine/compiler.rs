@@ -3,13 +3,27 @@
 //! Uses `cupcake.system.evaluate` as the single aggregation entrypoint.
 
 use anyhow::{bail, Context, Result};
+use std::fmt;
 use std::path::{Path, PathBuf};
-use std::process::Command;
 use tempfile::TempDir;
-use tracing::{debug, error, info};
+use tokio::process::Command;
+use tracing::{debug, error, info, warn};
 
+use super::init_report::SkippedPolicy;
+use super::namespaces::Tier;
 use super::PolicyUnit;
 
+/// A policy file copied into the compilation directory, for mapping OPA
+/// diagnostics back to the file the user edits
+struct CompiledSource {
+    /// Location inside the compilation directory
+    temp: PathBuf,
+    /// Original file
+    source: PathBuf,
+    /// Whether OPA saw a template rendering rather than the file itself
+    rendered: bool,
+}
+
 /// Find the OPA binary with optional CLI override
 ///
 /// # Resolution Order
@@ -108,6 +122,104 @@ pub fn find_opa_binary(cli_override: Option<PathBuf>) -> Result<PathBuf> {
     })
 }
 
+/// File names OPA loads as data documents
+const DATA_DOCUMENT_NAMES: &[&str] = &["data.json", "data.yaml", "data.yml"];
+
+/// Result of an OPA build - the WASM module plus the bundle's data document
+pub struct CompiledBundle {
+    pub wasm: Vec<u8>,
+    /// Merged `data.{json,yaml}` documents, `None` when the policies ship no data
+    pub data: Option<serde_json::Value>,
+}
+
+/// Compile a tier's policies, excluding the ones OPA rejects
+///
+/// Each failed build drops the policy files its diagnostics point at and
+/// retries with the rest. Gives up - returning the compile error - when the
+/// failure can't be pinned on a policy, or when a system entrypoint is broken,
+/// since nothing can be evaluated without it. Returns the bundle together with
+/// the excluded policies and why.
+pub async fn compile_bundle_excluding_broken(
+    policies: &[PolicyUnit],
+    namespace: &str,
+    opa_path_override: Option<PathBuf>,
+    cupcake_dir: Option<&Path>,
+) -> Result<(CompiledBundle, Vec<SkippedPolicy>)> {
+    let mut remaining: Vec<PolicyUnit> = policies.to_vec();
+    let mut excluded: Vec<SkippedPolicy> = Vec::new();
+
+    loop {
+        let error = match compile_bundle_with_namespace(
+            &remaining,
+            namespace,
+            opa_path_override.clone(),
+            cupcake_dir,
+        )
+        .await
+        {
+            Ok(bundle) => return Ok((bundle, excluded)),
+            Err(error) => error,
+        };
+        let Some(compile_error) = error.downcast_ref::<CompileError>() else {
+            return Err(error);
+        };
+
+        let broken: Vec<&PolicyUnit> = remaining
+            .iter()
+            .filter(|policy| {
+                compile_error
+                    .diagnostics
+                    .iter()
+                    .any(|d| d.file == policy.path)
+            })
+            .collect();
+        // Excluding every rule would leave nothing to evaluate
+        let rules_survive = remaining.iter().any(|p| {
+            !is_system_package(&p.package_name) && !broken.iter().any(|b| b.path == p.path)
+        });
+        if broken.is_empty()
+            || broken.iter().any(|p| is_system_package(&p.package_name))
+            || !rules_survive
+        {
+            return Err(error);
+        }
+
+        for policy in &broken {
+            let reason = compile_error
+                .diagnostics
+                .iter()
+                .filter(|d| d.file == policy.path)
+                .map(|d| format!("line {}: {}: {}", d.line, d.code, d.message))
+                .collect::<Vec<_>>()
+                .join("; ");
+            warn!(
+                "Excluding {} policy {} ({}) - it failed to compile: {}",
+                compile_error.tier,
+                policy.package_name,
+                policy.path.display(),
+                reason
+            );
+            excluded.push(SkippedPolicy {
+                path: policy.path.clone(),
+                reason: format!("failed to compile: {reason}"),
+            });
+        }
+        let broken_paths: Vec<PathBuf> = broken.iter().map(|p| p.path.clone()).collect();
+        remaining.retain(|policy| !broken_paths.contains(&policy.path));
+    }
+}
+
+/// System packages are entrypoints and helpers, not rules
+fn is_system_package(package: &str) -> bool {
+    [
+        "cupcake.system",
+        "cupcake.global.system",
+        "cupcake.team.system",
+    ]
+    .iter()
+    .any(|prefix| package.starts_with(prefix))
+}
+
 /// Compile all policies into a single unified WASM module using OPA
 pub async fn compile_policies(
     policies: &[PolicyUnit],
@@ -118,17 +230,37 @@ pub async fn compile_policies(
 
 /// Compile policies with a specific namespace for the entrypoint
 ///
+/// Returns only the WASM module; use [`compile_bundle_with_namespace`] when the
+/// policies' data documents are needed as well.
+pub async fn compile_policies_with_namespace(
+    policies: &[PolicyUnit],
+    namespace: &str,
+    opa_path_override: Option<PathBuf>,
+    cupcake_dir: Option<&Path>,
+) -> Result<Vec<u8>> {
+    compile_bundle_with_namespace(policies, namespace, opa_path_override, cupcake_dir)
+        .await
+        .map(|bundle| bundle.wasm)
+}
+
+/// Compile policies and their data documents with a specific namespace for the entrypoint
+///
+/// Any `data.json` / `data.yaml` found under the policies root is copied into
+/// the build, so OPA validates and merges it using its usual directory-to-path
+/// mapping (`policies/data.yaml` is the root of `data`, `policies/claude/data.json`
+/// lands under `data.claude`).
+///
 /// # Arguments
 /// * `policies` - List of policy units to compile
 /// * `namespace` - Namespace for the entrypoint (e.g., "cupcake.system")
 /// * `opa_path_override` - Optional path to OPA binary
 /// * `cupcake_dir` - Optional path to .cupcake directory for locating helpers at root level
-pub async fn compile_policies_with_namespace(
+pub async fn compile_bundle_with_namespace(
     policies: &[PolicyUnit],
     namespace: &str,
     opa_path_override: Option<PathBuf>,
     cupcake_dir: Option<&Path>,
-) -> Result<Vec<u8>> {
+) -> Result<CompiledBundle> {
     if policies.is_empty() {
         bail!("No policies to compile");
     }
@@ -149,20 +281,11 @@ pub async fn compile_policies_with_namespace(
     // Write all policies to the temp directory, preserving directory structure
     debug!("Copying {} policies to temp dir", policies.len());
 
-    // Find the common policies directory root
-    let policies_root = if !policies.is_empty() {
-        // Assume all policies are under a common "policies" directory
-        let first_policy_path = &policies[0].path;
-        let mut current = first_policy_path.parent();
-        while let Some(parent) = current {
-            if parent.file_name() == Some(std::ffi::OsStr::new("policies")) {
-                break;
-            }
-            current = parent.parent();
-        }
-        current.unwrap_or_else(|| first_policy_path.parent().unwrap())
-    } else {
-        bail!("No policies to determine root from");
+    // Find the policies directory root of the first policy; layered global
+    // configs spread policies over several roots, resolved per policy below
+    let policies_root = match policies.first() {
+        Some(policy) => policies_root_of(&policy.path),
+        None => bail!("No policies to determine root from"),
     };
 
     debug!("Policies root: {:?}", policies_root);
@@ -183,6 +306,8 @@ pub async fn compile_policies_with_namespace(
         policies_root.join("helpers")
     };
 
+    let mut sources: Vec<CompiledSource> = Vec::new();
+
     if helpers_src.exists() && helpers_src.is_dir() {
         debug!("Copying helpers directory: {:?}", helpers_src);
         let helpers_dest = temp_path.join("helpers");
@@ -197,6 +322,11 @@ pub async fn compile_policies_with_namespace(
                 let dest_path = helpers_dest.join(file_name);
                 tokio::fs::copy(&path, &dest_path).await?;
                 debug!("Copied helper: {:?} -> {:?}", path, dest_path);
+                sources.push(CompiledSource {
+                    temp: dest_path,
+                    source: path,
+                    rendered: false,
+                });
             }
         }
     }
@@ -205,7 +335,7 @@ pub async fn compile_policies_with_namespace(
         // Get the relative path from the policies root
         let relative_path = policy
             .path
-            .strip_prefix(policies_root)
+            .strip_prefix(policies_root_of(&policy.path))
             .unwrap_or_else(|_| policy.path.file_name().unwrap().as_ref());
 
         let dest_path = temp_path.join(relative_path);
@@ -215,10 +345,13 @@ pub async fn compile_policies_with_namespace(
             tokio::fs::create_dir_all(parent).await?;
         }
 
-        // Read the policy content
-        let mut content = tokio::fs::read_to_string(&policy.path)
-            .await
-            .context(format!("Failed to read policy {:?}", policy.path))?;
+        // Read the policy content, or the harness rendering of a template
+        let mut content = match &policy.rendered {
+            Some(rendered) => rendered.clone(),
+            None => tokio::fs::read_to_string(&policy.path)
+                .await
+                .context(format!("Failed to read policy {:?}", policy.path))?,
+        };
 
         // If this is for global namespace and the package needs transformation
         if namespace.starts_with("cupcake.global") && !content.contains("package cupcake.global") {
@@ -235,6 +368,16 @@ pub async fn compile_policies_with_namespace(
             );
         }
 
+        // Team configs reuse the project layout verbatim, so relocate the whole
+        // tree (packages and data references) under cupcake.team
+        if namespace.starts_with("cupcake.team") && !content.contains("package cupcake.team") {
+            content = to_team_namespace(&content);
+            debug!(
+                "Transformed policy {} to team namespace",
+                policy.package_name
+            );
+        }
+
         // Write the (possibly transformed) content to temp dir
         tokio::fs::write(&dest_path, &content)
             .await
@@ -250,6 +393,38 @@ pub async fn compile_policies_with_namespace(
             "Wrote policy {} to temp: {:?}",
             policy.package_name, dest_path
         );
+        sources.push(CompiledSource {
+            temp: dest_path,
+            source: policy.path.clone(),
+            rendered: policy.rendered.is_some(),
+        });
+    }
+
+    // Copy data documents so OPA merges them into the bundle
+    // (with layered roots, the first root to provide a path wins)
+    let mut data_roots: Vec<&Path> = Vec::new();
+    for policy in policies.iter() {
+        let root = policies_root_of(&policy.path);
+        if !data_roots.contains(&root) {
+            data_roots.push(root);
+        }
+    }
+    for root in data_roots {
+        for data_file in find_data_documents(root)? {
+            let relative_path = data_file.strip_prefix(root)?;
+            let dest_path = temp_path.join(relative_path);
+            if dest_path.exists() {
+                debug!("Skipping shadowed data document: {:?}", data_file);
+                continue;
+            }
+            if let Some(parent) = dest_path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::copy(&data_file, &dest_path)
+                .await
+                .context(format!("Failed to copy data document {data_file:?}"))?;
+            debug!("Copied data document: {:?} -> {:?}", data_file, dest_path);
+        }
     }
 
     // Build the OPA command for Hybrid Model
@@ -257,7 +432,9 @@ pub async fn compile_policies_with_namespace(
     let opa_path = find_opa_binary(opa_path_override)?;
     debug!("Using OPA binary: {:?}", opa_path);
     let mut opa_cmd = Command::new(&opa_path);
+    // Tiers compiled in the background must not outlive an engine that exits first
     opa_cmd
+        .kill_on_drop(true)
         .arg("build")
         .arg("-t")
         .arg("wasm") // Target WASM
@@ -313,6 +490,7 @@ pub async fn compile_policies_with_namespace(
     // Execute the command - this MUST work or we fail
     let output = opa_cmd
         .output()
+        .await
         .context("Failed to execute OPA command. Is the OPA CLI installed and in your PATH?")?;
 
     if !output.status.success() {
@@ -338,6 +516,16 @@ pub async fn compile_policies_with_namespace(
             }
         }
 
+        // Point at the policy files (and tier) OPA complained about
+        let diagnostics = parse_diagnostics(&format!("{stderr}\n{stdout}"), &sources);
+        if !diagnostics.is_empty() {
+            return Err(CompileError {
+                tier: Tier::of_package(namespace),
+                diagnostics,
+            }
+            .into());
+        }
+
         let error_msg = if !stderr.is_empty() {
             format!("stderr: {stderr}")
         } else if !stdout.is_empty() {
@@ -350,8 +538,8 @@ pub async fn compile_policies_with_namespace(
 
     info!("OPA compilation successful");
 
-    // Extract the WASM module from the bundle
-    let wasm_bytes = extract_wasm_from_bundle(&bundle_path)
+    // Extract the WASM module and data document from the bundle
+    let (wasm_bytes, data) = extract_wasm_from_bundle(&bundle_path)
         .await
         .context("Failed to extract WASM from bundle")?;
 
@@ -364,11 +552,185 @@ pub async fn compile_policies_with_namespace(
     tokio::fs::write(&debug_wasm_path, &wasm_bytes).await?;
     debug!("Saved WASM to {:?} for debugging", debug_wasm_path);
 
-    Ok(wasm_bytes)
+    Ok(CompiledBundle {
+        wasm: wasm_bytes,
+        data,
+    })
+}
+
+/// One OPA diagnostic, pointing at the original policy file where possible
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    /// Original policy file, or the path OPA reported if it isn't one of ours
+    pub file: PathBuf,
+    pub line: usize,
+    /// OPA error code, e.g. `rego_parse_error`
+    pub code: String,
+    pub message: String,
+    /// Whether `line` refers to a template rendering rather than the file itself
+    pub rendered: bool,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}: {}",
+            self.file.display(),
+            self.line,
+            self.code,
+            self.message
+        )?;
+        if self.rendered {
+            write!(f, " (line of the template as rendered for this harness)")?;
+        }
+        Ok(())
+    }
+}
+
+/// OPA rejected a tier's policies; the diagnostics name the files at fault
+#[derive(Debug)]
+pub struct CompileError {
+    pub tier: Tier,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OPA compilation of {} policies failed:", self.tier)?;
+        for diagnostic in &self.diagnostics {
+            write!(f, "\n  {diagnostic}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for CompileError {}
+
+/// Parse OPA's `<file>:<line>: <code>: <message>` diagnostics, naming the
+/// original policy files instead of their copies in the compilation directory
+///
+/// Empty when the output has none (e.g. OPA itself failed to run).
+fn parse_diagnostics(output: &str, sources: &[CompiledSource]) -> Vec<Diagnostic> {
+    static DIAGNOSTIC: once_cell::sync::Lazy<regex::Regex> = once_cell::sync::Lazy::new(|| {
+        regex::Regex::new(r"(?m)(\S+\.rego):(\d+): (rego_[a-z_]+): (.+)$").unwrap()
+    });
+
+    DIAGNOSTIC
+        .captures_iter(output)
+        .map(|caps| {
+            let reported = Path::new(caps[1].trim_start_matches("file://"));
+            let source = sources.iter().find(|s| same_location(reported, &s.temp));
+            Diagnostic {
+                file: source.map_or_else(|| reported.to_path_buf(), |s| s.source.clone()),
+                line: caps[2].parse().unwrap_or(0),
+                code: caps[3].to_string(),
+                message: caps[4].trim().to_string(),
+                rendered: source.is_some_and(|s| s.rendered),
+            }
+        })
+        .collect()
+}
+
+/// Whether a path OPA reported is the given file in the compilation directory.
+/// OPA may report temp paths with symlinks resolved (macOS `/private/var`) or
+/// with Windows separators flipped, so compare by trailing components too.
+fn same_location(reported: &Path, temp: &Path) -> bool {
+    if reported == temp {
+        return true;
+    }
+    let normalize = |path: &Path| path.to_string_lossy().replace('\\', "/");
+    let (reported, temp) = (normalize(reported), normalize(temp));
+    reported.ends_with(&temp) || temp.ends_with(&reported)
 }
 
-/// Extract the policy.wasm file from the OPA bundle
-async fn extract_wasm_from_bundle(bundle_path: &Path) -> Result<Vec<u8>> {
+/// The `policies` directory a policy file lives under, or its parent directory
+fn policies_root_of(policy_path: &Path) -> &Path {
+    let mut current = policy_path.parent();
+    while let Some(parent) = current {
+        if parent.file_name() == Some(std::ffi::OsStr::new("policies")) {
+            return parent;
+        }
+        current = parent.parent();
+    }
+    policy_path.parent().unwrap_or(policy_path)
+}
+
+/// Recursively find data documents under the policies root
+fn find_data_documents(policies_root: &Path) -> Result<Vec<PathBuf>> {
+    let mut found = Vec::new();
+    if !policies_root.is_dir() {
+        return Ok(found);
+    }
+    let mut pending = vec![policies_root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir).context("Failed to read policies directory")? {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| DATA_DOCUMENT_NAMES.contains(&n))
+            {
+                found.push(path);
+            }
+        }
+    }
+    found.sort();
+    Ok(found)
+}
+
+/// Rewrite a project-layout policy into the team namespace
+///
+/// `cupcake.policies.*` becomes `cupcake.team.policies.*` and `cupcake.system.*`
+/// becomes `cupcake.team.system.*`, for both package declarations and `data.`
+/// references, so the stock system entrypoint and helpers work unchanged.
+/// Only whole references are rewritten - `cupcake.policies_extra`, or the same
+/// text inside another identifier, a string or a comment, is left alone.
+pub fn to_team_namespace(content: &str) -> String {
+    const PREFIX: &str = "cupcake.";
+    let mut out = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(pos) = rest.find(PREFIX) {
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + PREFIX.len()..];
+        let relocated = ["policies", "system"].iter().any(|root| {
+            after.starts_with(root) && !after[root.len()..].starts_with(is_identifier_char)
+        });
+        let at_reference = is_package_declaration(&out) || is_data_reference(&out);
+        out.push_str(PREFIX);
+        if relocated && at_reference {
+            out.push_str("team.");
+        }
+        rest = after;
+    }
+    out.push_str(rest);
+    out
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Whether `preceding` ends a `package` keyword at the start of a line
+fn is_package_declaration(preceding: &str) -> bool {
+    let line = preceding.rsplit('\n').next().unwrap_or_default();
+    let keyword = line.trim_end_matches([' ', '\t']);
+    keyword.len() < line.len() && keyword.trim_start() == "package"
+}
+
+/// Whether `preceding` ends with a `data.` reference root
+fn is_data_reference(preceding: &str) -> bool {
+    preceding
+        .strip_suffix("data.")
+        .is_some_and(|before| !before.ends_with(|c: char| is_identifier_char(c) || c == '.'))
+}
+
+/// Extract the policy.wasm file and the data document from the OPA bundle
+async fn extract_wasm_from_bundle(
+    bundle_path: &Path,
+) -> Result<(Vec<u8>, Option<serde_json::Value>)> {
     // OPA creates a tar.gz bundle, we need to extract policy.wasm from it
 
     let bundle_bytes = tokio::fs::read(bundle_path)
@@ -391,6 +753,7 @@ async fn extract_wasm_from_bundle(bundle_path: &Path) -> Result<Vec<u8>> {
         .arg("-C")
         .arg(extract_path)
         .output()
+        .await
         .context("Failed to extract tar bundle")?;
 
     if !output.status.success() {
@@ -406,5 +769,99 @@ async fn extract_wasm_from_bundle(bundle_path: &Path) -> Result<Vec<u8>> {
         bail!("No policy.wasm found in OPA bundle");
     }
 
-    Ok(tokio::fs::read(wasm_path).await?)
+    let wasm = tokio::fs::read(wasm_path).await?;
+
+    // OPA writes an empty data.json when the policies ship no data documents
+    let data_path = extract_path.join("data.json");
+    let data = if data_path.exists() {
+        let raw = tokio::fs::read(&data_path).await?;
+        let value: serde_json::Value =
+            serde_json::from_slice(&raw).context("Failed to parse bundle data.json")?;
+        Some(value).filter(|v| v.as_object().is_none_or(|o| !o.is_empty()))
+    } else {
+        None
+    };
+
+    Ok((wasm, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostics_name_original_policy_files() {
+        let sources = vec![
+            CompiledSource {
+                temp: PathBuf::from("/tmp/.tmpAbc/claude/git.rego"),
+                source: PathBuf::from("/repo/.cupcake/policies/claude/git.rego"),
+                rendered: false,
+            },
+            CompiledSource {
+                temp: PathBuf::from("/tmp/.tmpAbc/claude/builtins/rate_limit.rego"),
+                source: PathBuf::from("/repo/.cupcake/policies/claude/builtins/rate_limit.rego"),
+                rendered: true,
+            },
+        ];
+        let output = "error: 2 errors occurred:\n\
+            /tmp/.tmpAbc/claude/git.rego:12: rego_unsafe_var_error: var cmd is unsafe\n\
+            /private/tmp/.tmpAbc/claude/builtins/rate_limit.rego:4: rego_parse_error: unexpected } token\n";
+
+        let error = CompileError {
+            tier: Tier::Project,
+            diagnostics: parse_diagnostics(output, &sources),
+        };
+        assert_eq!(
+            error.to_string(),
+            "OPA compilation of project policies failed:\n  \
+             /repo/.cupcake/policies/claude/git.rego:12: rego_unsafe_var_error: var cmd is unsafe\n  \
+             /repo/.cupcake/policies/claude/builtins/rate_limit.rego:4: rego_parse_error: unexpected } token \
+             (line of the template as rendered for this harness)"
+        );
+
+        // A single error is reported inline after the summary
+        let output =
+            "error: 1 error occurred: /tmp/other/x.rego:3: rego_type_error: conflicting rules";
+        let diagnostics = parse_diagnostics(output, &sources);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].file, PathBuf::from("/tmp/other/x.rego"));
+        assert_eq!(diagnostics[0].line, 3);
+        assert!(!diagnostics[0].rendered);
+
+        assert!(parse_diagnostics("exec format error", &sources).is_empty());
+    }
+
+    #[test]
+    fn test_team_namespace_rewrites_only_whole_references() {
+        let policy = "package cupcake.policies.git_rules\n\
+            \n\
+            import data.cupcake.system.commands\n\
+            import data.cupcake.policies.helpers\n\
+            \n\
+            # see cupcake.policies.builtins for the stock rules\n\
+            all := data.cupcake.policies\n\
+            first := data.cupcake.policies[name]\n\
+            other := data.cupcake.policies_archive.rules\n\
+            mine := mydata.cupcake.policies.x\n\
+            msg := \"moved from cupcake.system.commands\"\n";
+
+        assert_eq!(
+            to_team_namespace(policy),
+            "package cupcake.team.policies.git_rules\n\
+             \n\
+             import data.cupcake.team.system.commands\n\
+             import data.cupcake.team.policies.helpers\n\
+             \n\
+             # see cupcake.policies.builtins for the stock rules\n\
+             all := data.cupcake.team.policies\n\
+             first := data.cupcake.team.policies[name]\n\
+             other := data.cupcake.policies_archive.rules\n\
+             mine := mydata.cupcake.policies.x\n\
+             msg := \"moved from cupcake.system.commands\"\n"
+        );
+        assert_eq!(
+            to_team_namespace("  package   cupcake.system\n"),
+            "  package   cupcake.team.system\n"
+        );
+    }
 }
@@ -8,7 +8,7 @@ use once_cell::sync::Lazy;
 use std::path::{Path, PathBuf};
 use tracing::{debug, info};
 
-use super::global_config;
+use super::{global_config, team_config};
 
 /// Detects the appropriate shell command for the current platform
 ///
@@ -43,6 +43,55 @@ fn find_shell_command() -> &'static str {
 /// Cached shell command determined at first use
 pub static SHELL_COMMAND: Lazy<&'static str> = Lazy::new(find_shell_command);
 
+/// Program and arguments that run a rulebook command string (signals, approvers)
+///
+/// Unix runs the command with `sh -c`. Windows behaves differently in two ways:
+/// - A bare `.ps1` script runs with `powershell.exe -File`, so its path is
+///   never re-parsed as PowerShell code
+/// - Everything else runs with Git Bash, so rulebooks written for Unix keep
+///   working; a `.sh` script given as a drive path (`C:\hooks\check.sh`) is
+///   rewritten to the `/c/hooks/check.sh` form Git Bash expects
+pub fn shell_invocation(command: &str) -> (&'static str, Vec<String>) {
+    shell_invocation_for(command, cfg!(windows), *SHELL_COMMAND)
+}
+
+fn shell_invocation_for(
+    command: &str,
+    windows: bool,
+    shell: &'static str,
+) -> (&'static str, Vec<String>) {
+    let trimmed = command.trim();
+    if !windows {
+        return (shell, vec!["-c".to_string(), command.to_string()]);
+    }
+
+    if trimmed.to_ascii_lowercase().ends_with(".ps1") && !trimmed.contains(char::is_whitespace) {
+        let args = [
+            "-NoProfile",
+            "-NonInteractive",
+            "-ExecutionPolicy",
+            "Bypass",
+            "-File",
+        ];
+        let mut args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        args.push(trimmed.to_string());
+        return ("powershell.exe", args);
+    }
+
+    let mut chars = trimmed.chars();
+    let command = match (chars.next(), chars.next()) {
+        (Some(drive), Some(':')) if trimmed.ends_with(".sh") && drive.is_ascii_alphabetic() => {
+            format!(
+                "/{}{}",
+                drive.to_ascii_lowercase(),
+                trimmed[2..].replace('\\', "/")
+            )
+        }
+        _ => command.to_string(),
+    };
+    (shell, vec!["-c".to_string(), command])
+}
+
 /// Project path resolution following .cupcake/ convention with optional global config
 #[derive(Debug, Clone)]
 pub struct ProjectPaths {
@@ -66,6 +115,18 @@ pub struct ProjectPaths {
     pub global_signals: Option<PathBuf>,
     /// Global rulebook file
     pub global_rulebook: Option<PathBuf>,
+    /// Every global config root, highest precedence first (empty without global config)
+    pub global_layers: Vec<PathBuf>,
+
+    // Team configuration paths (optional - opt-in via CLI flag or environment)
+    /// Team config root directory
+    pub team_root: Option<PathBuf>,
+    /// Team policies directory
+    pub team_policies: Option<PathBuf>,
+    /// Team signals directory
+    pub team_signals: Option<PathBuf>,
+    /// Team rulebook file
+    pub team_rulebook: Option<PathBuf>,
 }
 
 impl ProjectPaths {
@@ -76,6 +137,27 @@ impl ProjectPaths {
         Self::resolve_with_config(input_path, None)
     }
 
+    /// Resolve project paths with optional global and team config overrides
+    /// Used by Engine::new_with_config() to apply CLI --global-config and --team-config flags
+    pub fn resolve_with_tiers(
+        input_path: impl AsRef<Path>,
+        global_config_override: Option<PathBuf>,
+        team_config_override: Option<PathBuf>,
+    ) -> Result<Self> {
+        let mut paths = Self::resolve_with_config(input_path, global_config_override)?;
+
+        // Team config is opt-in, so a broken team path is a hard error
+        if let Some(team) = team_config::TeamPaths::discover_with_override(team_config_override)? {
+            info!("Team configuration discovered at {:?}", team.root);
+            paths.team_root = Some(team.root);
+            paths.team_policies = Some(team.policies);
+            paths.team_signals = Some(team.signals);
+            paths.team_rulebook = Some(team.rulebook);
+        }
+
+        Ok(paths)
+    }
+
     /// Resolve project paths with optional global config override
     /// Used by Engine::new_with_config() to apply CLI --global-config flag
     pub fn resolve_with_config(
@@ -111,18 +193,19 @@ impl ProjectPaths {
                 });
 
         // Extract global paths if config exists
-        let (global_root, global_policies, global_signals, global_rulebook) =
+        let (global_root, global_policies, global_signals, global_rulebook, global_layers) =
             if let Some(global) = global_config {
-                info!("Global configuration discovered at {:?}", global.root);
+                info!("Global configuration discovered at {:?}", global.layers);
                 (
                     Some(global.root),
                     Some(global.policies),
                     Some(global.signals),
                     Some(global.rulebook),
+                    global.layers,
                 )
             } else {
                 debug!("No global configuration found - using project config only");
-                (None, None, None, None)
+                (None, None, None, None, Vec::new())
             };
 
         Ok(ProjectPaths {
@@ -135,6 +218,11 @@ impl ProjectPaths {
             global_policies,
             global_signals,
             global_rulebook,
+            global_layers,
+            team_root: None,
+            team_policies: None,
+            team_signals: None,
+            team_rulebook: None,
         })
     }
 
@@ -152,16 +240,69 @@ impl ProjectPaths {
 
     /// Get global watchdog directory path (~/.config/cupcake/watchdog/)
     ///
-    /// Returns Some if the directory exists, None otherwise.
+    /// Returns the first global layer's watchdog directory that exists, None otherwise.
     pub fn global_watchdog_dir(&self) -> Option<PathBuf> {
-        self.global_root.as_ref().and_then(|root| {
-            let watchdog_dir = root.join("watchdog");
-            if watchdog_dir.exists() {
-                Some(watchdog_dir)
-            } else {
-                None
+        self.global_layers
+            .iter()
+            .map(|root| root.join("watchdog"))
+            .find(|watchdog_dir| watchdog_dir.exists())
+    }
+
+    /// Find the nearest nested `.cupcake/` directory for a target path (monorepo support)
+    ///
+    /// Walks up from `target` towards the project root and returns the first
+    /// `.cupcake/` directory that contains a `rulebook.yml`. The root `.cupcake/`
+    /// itself is never returned, and targets outside the project yield None.
+    /// Relative targets are resolved against the project root.
+    pub fn nested_cupcake_dir_for(&self, target: &Path) -> Option<PathBuf> {
+        // Compare canonical paths so relative roots and symlinked temp dirs line up
+        let root = self
+            .root
+            .canonicalize()
+            .unwrap_or_else(|_| self.root.clone());
+        let target = if target.is_absolute() {
+            target.to_path_buf()
+        } else {
+            root.join(target)
+        };
+        let target = target
+            .canonicalize()
+            .or_else(|e| {
+                // New files don't exist yet - canonicalize the parent instead
+                match (target.parent(), target.file_name()) {
+                    (Some(parent), Some(name)) => parent.canonicalize().map(|p| p.join(name)),
+                    _ => Err(e),
+                }
+            })
+            .unwrap_or(target);
+
+        if !target.starts_with(&root) {
+            return None;
+        }
+
+        // Start at the target itself if it is a directory, otherwise its parent
+        let mut current = if target.is_dir() {
+            Some(target.as_path())
+        } else {
+            target.parent()
+        };
+
+        while let Some(dir) = current {
+            if dir == root || !dir.starts_with(&root) {
+                break;
             }
-        })
+            let candidate = dir.join(".cupcake");
+            if candidate.join("rulebook.yml").is_file() {
+                debug!(
+                    "Nested .cupcake directory found for {:?}: {:?}",
+                    target, candidate
+                );
+                return Some(candidate);
+            }
+            current = dir.parent();
+        }
+
+        None
     }
 }
 
@@ -177,6 +318,10 @@ pub struct EngineConfig {
     /// If None, uses default 10MB with 1MB-100MB enforcement
     pub wasm_max_memory: Option<usize>,
 
+    /// Override the number of idle WASM instances kept per runtime
+    /// If None, uses CUPCAKE_WASM_POOL_SIZE when set, otherwise the number of CPUs
+    pub wasm_pool_size: Option<usize>,
+
     /// Override OPA binary path
     /// If None, uses bundled OPA or system PATH
     pub opa_path: Option<PathBuf>,
@@ -185,6 +330,10 @@ pub struct EngineConfig {
     /// If None, uses platform-specific default (~/.config/cupcake or ~/Library/Application Support/cupcake)
     pub global_config: Option<PathBuf>,
 
+    /// Override team config directory
+    /// If None, uses CUPCAKE_TEAM_CONFIG when set, otherwise no team tier
+    pub team_config: Option<PathBuf>,
+
     /// Enable routing diagnostics debug output
     /// If true, writes routing maps to .cupcake/debug/routing/
     pub debug_routing: bool,
@@ -196,9 +345,48 @@ impl EngineConfig {
         Self {
             harness,
             wasm_max_memory: None,
+            wasm_pool_size: None,
             opa_path: None,
             global_config: None,
+            team_config: None,
             debug_routing: false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unix_runs_commands_with_sh() {
+        let (program, args) = shell_invocation_for("git branch --show-current", false, "sh");
+        assert_eq!(program, "sh");
+        assert_eq!(args, ["-c", "git branch --show-current"]);
+    }
+
+    #[test]
+    fn test_windows_runs_powershell_scripts_with_file() {
+        let (program, args) = shell_invocation_for(r"C:\signals\branch.PS1", true, "bash.exe");
+        assert_eq!(program, "powershell.exe");
+        assert_eq!(
+            args.last().map(String::as_str),
+            Some(r"C:\signals\branch.PS1")
+        );
+        assert!(args.contains(&"-File".to_string()));
+
+        // Anything with arguments is a shell command line, not a script path
+        let (program, _) = shell_invocation_for("pwsh -File check.ps1", true, "bash.exe");
+        assert_eq!(program, "bash.exe");
+    }
+
+    #[test]
+    fn test_windows_rewrites_drive_paths_for_git_bash() {
+        let (program, args) = shell_invocation_for(r"C:\Users\dev\check.sh", true, "bash.exe");
+        assert_eq!(program, "bash.exe");
+        assert_eq!(args, ["-c", "/c/Users/dev/check.sh"]);
+
+        let (_, args) = shell_invocation_for("echo hi", true, "bash.exe");
+        assert_eq!(args, ["-c", "echo hi"]);
+    }
+}
@@ -1,7 +1,9 @@
 //! Decision types - [`DecisionSet`] from WASM and [`FinalDecision`] after synthesis.
 
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tracing::warn;
 
 /// A single decision object from a policy rule
 /// This is the standard format returned by decision verb rules
@@ -21,6 +23,48 @@ pub struct DecisionObject {
     /// Currently used by Cursor harness to populate `agentMessage` field
     #[serde(default)]
     pub agent_context: Option<String>,
+
+    /// Optional link to documentation for the rule (http or https)
+    #[serde(default)]
+    pub docs_url: Option<String>,
+
+    /// Optional guidance on how to proceed without violating the rule
+    #[serde(default)]
+    pub remediation: Option<String>,
+}
+
+impl DecisionObject {
+    /// Remediation and docs lines to show with the reason
+    ///
+    /// Empty values are left out, as are docs URLs that aren't http(s), with a warning.
+    pub fn guidance(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(remediation) = self.remediation.as_deref().map(str::trim) {
+            if !remediation.is_empty() {
+                lines.push(format!("Remediation: {remediation}"));
+            }
+        }
+        if let Some(url) = self.docs_url.as_deref().map(str::trim) {
+            if is_docs_url(url) {
+                lines.push(format!("Docs: {url}"));
+            } else if !url.is_empty() {
+                warn!(
+                    "Ignoring docs_url '{}' of rule {}: expected an http(s) URL",
+                    url, self.rule_id
+                );
+            }
+        }
+        lines
+    }
+}
+
+/// Whether a `docs_url` value is an http(s) URL
+pub fn is_docs_url(url: &str) -> bool {
+    ["https://", "http://"].iter().any(|scheme| {
+        url.len() > scheme.len()
+            && url[..scheme.len()].eq_ignore_ascii_case(scheme)
+            && !url.contains(char::is_whitespace)
+    })
 }
 
 /// A modification decision that transforms tool input before execution
@@ -51,6 +95,130 @@ pub struct ModificationObject {
     pub agent_context: Option<String>,
 }
 
+/// An output filter decision that rewrites `tool_response` before the agent sees it
+/// This is used by the `filter_output` verb on PostToolUse to redact or truncate output
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OutputFilterObject {
+    /// Human-readable explanation of the filter
+    pub reason: String,
+
+    /// Severity level (HIGH, MEDIUM, LOW)
+    #[serde(default = "default_severity")]
+    pub severity: String,
+
+    /// Unique identifier for the rule that generated this filter
+    pub rule_id: String,
+
+    /// Priority for conflict resolution when multiple policies replace the same
+    /// field (1-100, higher wins). Default is 50 (medium priority)
+    #[serde(default = "default_priority")]
+    pub priority: u8,
+
+    /// Replacement for the tool response, deep-merged over the original
+    #[serde(default)]
+    pub updated_response: Option<Value>,
+
+    /// Regex patterns whose matches are replaced with `[REDACTED]` in every string
+    #[serde(default)]
+    pub redact: Vec<String>,
+
+    /// Truncate every string to this many characters
+    #[serde(default)]
+    pub max_length: Option<usize>,
+
+    /// Optional agent-specific context
+    #[serde(default)]
+    pub agent_context: Option<String>,
+}
+
+/// The merged effect of all `filter_output` decisions, applied to a tool response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct OutputFilter {
+    /// Replacement for the tool response, deep-merged over the original
+    pub updated_response: Option<Value>,
+
+    /// Regex patterns to redact
+    pub redact: Vec<String>,
+
+    /// Maximum length of any string
+    pub max_length: Option<usize>,
+}
+
+impl OutputFilter {
+    /// Apply the filter to a tool response
+    ///
+    /// Replacements are merged first, then redactions and truncation are applied
+    /// to every string in the result. Invalid patterns are skipped with a warning.
+    pub fn apply(&self, tool_response: &Value) -> Value {
+        let mut output = match &self.updated_response {
+            Some(updated) => merge_over(tool_response.clone(), updated.clone()),
+            None => tool_response.clone(),
+        };
+
+        let patterns: Vec<Regex> = self
+            .redact
+            .iter()
+            .filter_map(|pattern| match Regex::new(pattern) {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    warn!(
+                        "Skipping invalid filter_output redact pattern '{}': {}",
+                        pattern, e
+                    );
+                    None
+                }
+            })
+            .collect();
+
+        filter_strings(&mut output, &|text: &str| {
+            let mut text = text.to_string();
+            for pattern in &patterns {
+                text = pattern.replace_all(&text, "[REDACTED]").into_owned();
+            }
+            match self.max_length {
+                Some(max) if text.chars().count() > max => {
+                    let omitted = text.chars().count() - max;
+                    let kept: String = text.chars().take(max).collect();
+                    format!("{kept}... [truncated {omitted} characters]")
+                }
+                _ => text,
+            }
+        });
+        output
+    }
+}
+
+/// Merge `updated` over `original`; objects merge key by key, anything else replaces
+fn merge_over(original: Value, updated: Value) -> Value {
+    match (original, updated) {
+        (Value::Object(mut original), Value::Object(updated)) => {
+            for (key, value) in updated {
+                let merged = match original.remove(&key) {
+                    Some(existing) => merge_over(existing, value),
+                    None => value,
+                };
+                original.insert(key, merged);
+            }
+            Value::Object(original)
+        }
+        (_, updated) => updated,
+    }
+}
+
+/// Rewrite every string in a JSON value
+fn filter_strings(value: &mut Value, filter: &dyn Fn(&str) -> String) {
+    match value {
+        Value::String(text) => *text = filter(text),
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| filter_strings(item, filter)),
+        Value::Object(fields) => fields
+            .values_mut()
+            .for_each(|field| filter_strings(field, filter)),
+        _ => {}
+    }
+}
+
 fn default_severity() -> String {
     "MEDIUM".to_string()
 }
@@ -84,6 +252,11 @@ pub struct DecisionSet {
     #[serde(default)]
     pub modifications: Vec<ModificationObject>,
 
+    /// Output filter decisions (low priority)
+    /// Allows policies to redact or replace tool output after execution
+    #[serde(default)]
+    pub output_filters: Vec<OutputFilterObject>,
+
     /// Context injection decisions (informational)
     #[serde(default)]
     pub add_context: Vec<String>,
@@ -129,6 +302,14 @@ pub enum FinalDecision {
         agent_messages: Vec<String>,
     },
 
+    /// Filter tool output and allow - low priority
+    /// The agent sees the tool response with the filter applied
+    FilterOutput {
+        reason: String,
+        filter: OutputFilter,
+        agent_messages: Vec<String>,
+    },
+
     /// Allow with optional context - default
     Allow { context: Vec<String> },
 }
@@ -162,6 +343,11 @@ impl FinalDecision {
         matches!(self, FinalDecision::Modify { .. })
     }
 
+    /// Check if this decision filters tool output
+    pub fn is_filter_output(&self) -> bool {
+        matches!(self, FinalDecision::FilterOutput { .. })
+    }
+
     /// Get the primary reason for this decision
     pub fn reason(&self) -> Option<&str> {
         match self {
@@ -170,6 +356,7 @@ impl FinalDecision {
             FinalDecision::Block { reason, .. } => Some(reason),
             FinalDecision::Ask { reason, .. } => Some(reason),
             FinalDecision::Modify { reason, .. } => Some(reason),
+            FinalDecision::FilterOutput { reason, .. } => Some(reason),
             FinalDecision::Allow { .. } => None,
         }
     }
@@ -182,6 +369,7 @@ impl FinalDecision {
             FinalDecision::Block { agent_messages, .. } => Some(agent_messages),
             FinalDecision::Ask { agent_messages, .. } => Some(agent_messages),
             FinalDecision::Modify { agent_messages, .. } => Some(agent_messages),
+            FinalDecision::FilterOutput { agent_messages, .. } => Some(agent_messages),
             FinalDecision::Allow { .. } => None,
         }
     }
@@ -193,6 +381,130 @@ impl FinalDecision {
             _ => None,
         }
     }
+
+    /// Get the output filter if this is a FilterOutput decision
+    pub fn output_filter(&self) -> Option<&OutputFilter> {
+        match self {
+            FinalDecision::FilterOutput { filter, .. } => Some(filter),
+            _ => None,
+        }
+    }
+}
+
+/// Version of the [`DecisionOutput`] contract, emitted as `schema_version`
+///
+/// Bumped when a field is removed or changes meaning; adding a field doesn't bump it.
+pub const DECISION_SCHEMA_VERSION: u32 = 1;
+
+/// JSON schema for [`DecisionOutput`]
+pub const DECISION_SCHEMA: &str = include_str!("../../schema/decision.schema.json");
+
+/// Kind of a [`FinalDecision`], serialized as its variant name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DecisionKind {
+    Halt,
+    Deny,
+    Block,
+    Ask,
+    Modify,
+    FilterOutput,
+    Allow,
+}
+
+/// Harness-neutral, versioned form of a [`FinalDecision`]
+///
+/// This is the stable output contract of the bindings and `cupcake eval --decision`,
+/// described by [`DECISION_SCHEMA`]. `FinalDecision`'s own serde form follows the
+/// enum layout and may change with it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecisionOutput {
+    pub schema_version: u32,
+
+    pub decision: DecisionKind,
+
+    /// Why the decision was made; absent for Allow
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
+    /// Messages intended for the agent rather than the user
+    #[serde(default)]
+    pub agent_messages: Vec<String>,
+
+    /// Context injected by `add_context` rules; only set for Allow
+    #[serde(default)]
+    pub context: Vec<String>,
+
+    /// Replacement tool input, for Modify
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_input: Option<Value>,
+
+    /// Filter applied to the tool response, for FilterOutput
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<OutputFilter>,
+}
+
+impl From<&FinalDecision> for DecisionOutput {
+    fn from(decision: &FinalDecision) -> Self {
+        let kind = match decision {
+            FinalDecision::Halt { .. } => DecisionKind::Halt,
+            FinalDecision::Deny { .. } => DecisionKind::Deny,
+            FinalDecision::Block { .. } => DecisionKind::Block,
+            FinalDecision::Ask { .. } => DecisionKind::Ask,
+            FinalDecision::Modify { .. } => DecisionKind::Modify,
+            FinalDecision::FilterOutput { .. } => DecisionKind::FilterOutput,
+            FinalDecision::Allow { .. } => DecisionKind::Allow,
+        };
+        Self {
+            schema_version: DECISION_SCHEMA_VERSION,
+            decision: kind,
+            reason: decision.reason().map(str::to_string),
+            agent_messages: decision.agent_messages().cloned().unwrap_or_default(),
+            context: match decision {
+                FinalDecision::Allow { context } => context.clone(),
+                _ => Vec::new(),
+            },
+            updated_input: decision.updated_input().cloned(),
+            filter: decision.output_filter().cloned(),
+        }
+    }
+}
+
+impl From<DecisionOutput> for FinalDecision {
+    fn from(output: DecisionOutput) -> Self {
+        let reason = output.reason.unwrap_or_default();
+        let agent_messages = output.agent_messages;
+        match output.decision {
+            DecisionKind::Halt => FinalDecision::Halt {
+                reason,
+                agent_messages,
+            },
+            DecisionKind::Deny => FinalDecision::Deny {
+                reason,
+                agent_messages,
+            },
+            DecisionKind::Block => FinalDecision::Block {
+                reason,
+                agent_messages,
+            },
+            DecisionKind::Ask => FinalDecision::Ask {
+                reason,
+                agent_messages,
+            },
+            DecisionKind::Modify => FinalDecision::Modify {
+                reason,
+                updated_input: output.updated_input.unwrap_or(Value::Null),
+                agent_messages,
+            },
+            DecisionKind::FilterOutput => FinalDecision::FilterOutput {
+                reason,
+                filter: output.filter.unwrap_or_default(),
+                agent_messages,
+            },
+            DecisionKind::Allow => FinalDecision::Allow {
+                context: output.context,
+            },
+        }
+    }
 }
 
 impl DecisionSet {
@@ -221,6 +533,11 @@ impl DecisionSet {
         !self.modifications.is_empty()
     }
 
+    /// Check if any output filter decisions are present
+    pub fn has_output_filters(&self) -> bool {
+        !self.output_filters.is_empty()
+    }
+
     /// Check if the decision set is completely empty (no decisions)
     pub fn is_empty(&self) -> bool {
         self.halts.is_empty()
@@ -228,6 +545,7 @@ impl DecisionSet {
             && self.blocks.is_empty()
             && self.asks.is_empty()
             && self.modifications.is_empty()
+            && self.output_filters.is_empty()
             && self.add_context.is_empty()
     }
 
@@ -248,6 +566,7 @@ impl DecisionSet {
             + self.blocks.len()
             + self.asks.len()
             + self.modifications.len()
+            + self.output_filters.len()
     }
 }
 
@@ -269,6 +588,8 @@ mod tests {
             severity: "HIGH".to_string(),
             rule_id: "TEST-001".to_string(),
             agent_context: None,
+            docs_url: None,
+            remediation: None,
         });
 
         assert!(!decision_set.is_empty());
@@ -321,4 +642,152 @@ mod tests {
         assert_eq!(allow.reason(), None);
         assert_eq!(allow.agent_messages(), None);
     }
+
+    #[test]
+    fn test_output_filter_apply() {
+        use serde_json::json;
+
+        let filter = OutputFilter {
+            updated_response: Some(json!({"stderr": ""})),
+            redact: vec![r"sk-[A-Za-z0-9]+".to_string(), "(".to_string()],
+            max_length: Some(25),
+        };
+        let response = json!({
+            "stdout": "OPENAI_API_KEY=sk-abc123\nHOME=/home/user\nPATH=/usr/bin",
+            "stderr": "warning",
+            "exit_code": 0,
+        });
+
+        let filtered = filter.apply(&response);
+        assert_eq!(
+            filtered["stdout"],
+            "OPENAI_API_KEY=[REDACTED]... [truncated 30 characters]"
+        );
+        assert_eq!(filtered["stderr"], "");
+        assert_eq!(filtered["exit_code"], 0);
+    }
+
+    #[test]
+    fn test_decision_output_shape() {
+        use serde_json::json;
+
+        let deny = FinalDecision::Deny {
+            reason: "No rm -rf".to_string(),
+            agent_messages: vec!["Use trash instead".to_string()],
+        };
+        assert_eq!(
+            serde_json::to_value(DecisionOutput::from(&deny)).unwrap(),
+            json!({
+                "schema_version": 1,
+                "decision": "Deny",
+                "reason": "No rm -rf",
+                "agent_messages": ["Use trash instead"],
+                "context": [],
+            })
+        );
+
+        let allow = FinalDecision::Allow {
+            context: vec!["On branch main".to_string()],
+        };
+        assert_eq!(
+            serde_json::to_value(DecisionOutput::from(&allow)).unwrap(),
+            json!({
+                "schema_version": 1,
+                "decision": "Allow",
+                "agent_messages": [],
+                "context": ["On branch main"],
+            })
+        );
+    }
+
+    #[test]
+    fn test_decision_output_round_trips() {
+        use serde_json::json;
+
+        let decisions = vec![
+            FinalDecision::Halt {
+                reason: "stop".to_string(),
+                agent_messages: vec![],
+            },
+            FinalDecision::Block {
+                reason: "blocked".to_string(),
+                agent_messages: vec!["why".to_string()],
+            },
+            FinalDecision::Ask {
+                reason: "sure?".to_string(),
+                agent_messages: vec![],
+            },
+            FinalDecision::Modify {
+                reason: "sanitized".to_string(),
+                updated_input: json!({"command": "ls"}),
+                agent_messages: vec![],
+            },
+            FinalDecision::FilterOutput {
+                reason: "redacted".to_string(),
+                filter: OutputFilter {
+                    updated_response: None,
+                    redact: vec!["sk-.*".to_string()],
+                    max_length: Some(100),
+                },
+                agent_messages: vec![],
+            },
+            FinalDecision::Allow { context: vec![] },
+        ];
+        for decision in decisions {
+            let json = serde_json::to_string(&DecisionOutput::from(&decision)).unwrap();
+            let output: DecisionOutput = serde_json::from_str(&json).unwrap();
+            assert_eq!(FinalDecision::from(output), decision);
+        }
+    }
+
+    #[test]
+    fn test_decision_schema_matches_output() {
+        use serde_json::json;
+
+        let schema: Value = serde_json::from_str(DECISION_SCHEMA).unwrap();
+        assert_eq!(
+            schema["properties"]["schema_version"]["const"],
+            DECISION_SCHEMA_VERSION
+        );
+        assert_eq!(
+            schema["properties"]["decision"]["enum"],
+            json!([
+                "Halt",
+                "Deny",
+                "Block",
+                "Ask",
+                "Modify",
+                "FilterOutput",
+                "Allow"
+            ])
+        );
+
+        // Every field the output can carry is described by the schema
+        let modify = DecisionOutput::from(&FinalDecision::Modify {
+            reason: "r".to_string(),
+            updated_input: json!({}),
+            agent_messages: vec![],
+        });
+        let filter = DecisionOutput::from(&FinalDecision::FilterOutput {
+            reason: "r".to_string(),
+            filter: OutputFilter::default(),
+            agent_messages: vec![],
+        });
+        for output in [modify, filter] {
+            let value = serde_json::to_value(&output).unwrap();
+            for field in value.as_object().unwrap().keys() {
+                assert!(
+                    schema["properties"].get(field).is_some(),
+                    "{field} missing from schema"
+                );
+            }
+            if let Some(filter) = value.get("filter") {
+                for field in filter.as_object().unwrap().keys() {
+                    assert!(schema["properties"]["filter"]["properties"]
+                        .get(field)
+                        .is_some());
+                }
+            }
+        }
+    }
 }
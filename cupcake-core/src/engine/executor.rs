@@ -14,12 +14,25 @@ use std::path::Path;
 use std::time::Instant;
 use tracing::{debug, error, info, trace, warn};
 
+use super::command::CommandTimedOut;
 use super::metadata::PolicyUnit;
+use super::native_signals;
 use super::rulebook::Rulebook;
 use crate::debug::SignalTelemetry;
 use crate::telemetry::span::SignalExecution;
 use crate::watchdog::Watchdog;
 
+/// Context attached to signal gathering errors, so callers can tell them
+/// apart from routing and WASM failures
+#[derive(Debug, Clone, Copy)]
+pub struct SignalGatheringFailed;
+
+impl std::fmt::Display for SignalGatheringFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Failed to gather signals")
+    }
+}
+
 /// Executor handles all OS/IO interactions for policy evaluation.
 ///
 /// This is an ephemeral struct created for each evaluation. It holds references
@@ -44,7 +57,7 @@ impl<'a> Executor<'a> {
     /// 2. Auto-adds signals for builtin policies
     /// 3. Injects builtin configuration
     /// 4. Executes signals
-    /// 5. Runs watchdog evaluation if enabled
+    /// 5. Runs watchdog evaluation if enabled and the event or a policy asks for it
     #[tracing::instrument(
         skip(self, input, matched_policies, signal_telemetry),
         fields(
@@ -104,6 +117,19 @@ impl<'a> Executor<'a> {
                                 required_signals.insert(signal_name.clone());
                             }
                         }
+
+                        // Signals referenced by injected context placeholders
+                        if builtin_name.ends_with("always_inject_on_prompt") {
+                            for signal_name in rulebook.builtins.get_prompt_template_signals() {
+                                if rulebook.signals.contains_key(&signal_name) {
+                                    debug!(
+                                        "Auto-adding signal '{}' referenced by builtin '{}'",
+                                        signal_name, builtin_name
+                                    );
+                                    required_signals.insert(signal_name);
+                                }
+                            }
+                        }
                     }
                 }
             }
@@ -138,7 +164,8 @@ impl<'a> Executor<'a> {
             }
         }
 
-        // Check if watchdog should run
+        // Policies can ask for a watchdog verdict on any event. Pre-action events
+        // also get the legacy implicit evaluation at `input.signals.watchdog`
         let is_pre_action_event = input
             .get("hook_event_name")
             .and_then(|v| v.as_str())
@@ -149,22 +176,32 @@ impl<'a> Executor<'a> {
                 )
             })
             .unwrap_or(false);
-        let watchdog_should_run = self.watchdog.is_some() && is_pre_action_event;
+        let watchdog_requested = matched_policies
+            .iter()
+            .any(|policy| policy.routing.required_watchdog);
+        if watchdog_requested && self.watchdog.is_none() {
+            warn!("A policy requires a watchdog verdict but the watchdog is not enabled");
+        }
+        let watchdog_should_run =
+            self.watchdog.is_some() && (is_pre_action_event || watchdog_requested);
 
         if required_signals.is_empty() && !watchdog_should_run {
             debug!("No signals required and watchdog not enabled - returning with builtin config");
             return Ok(enriched_input);
         }
 
-        let signal_names: Vec<String> = required_signals.into_iter().collect();
+        // Native providers (builtin.*) are computed in-process, not via the rulebook
+        let (native_names, signal_names): (Vec<String>, Vec<String>) = required_signals
+            .into_iter()
+            .partition(|name| native_signals::is_native_signal(name));
         info!(
             "Gathering {} signals: {:?}",
-            signal_names.len(),
+            signal_names.len() + native_names.len(),
             signal_names
         );
 
         // Execute signals if we have a rulebook
-        let signal_data = if let Some(rulebook) = self.rulebook {
+        let mut signal_data = if let Some(rulebook) = self.rulebook {
             self.execute_signals(
                 &signal_names,
                 rulebook,
@@ -181,46 +218,58 @@ impl<'a> Executor<'a> {
             HashMap::new()
         };
 
+        self.collect_native_signals(
+            &native_names,
+            input,
+            &mut signal_data,
+            signal_telemetry.as_deref_mut(),
+        )
+        .await;
+
         // Merge signal data into enriched input
         let mut signal_count = signal_data.len();
         if let Some(input_obj) = enriched_input.as_object_mut() {
             let mut signals_obj = serde_json::to_value(signal_data)?;
 
             // Execute Watchdog if enabled
-            if let Some(watchdog) = self.watchdog {
-                if is_pre_action_event {
-                    debug!(
-                        "Executing Watchdog evaluation for {:?} event",
-                        input.get("hook_event_name")
-                    );
-                    let watchdog_start = Instant::now();
-                    let watchdog_input = Watchdog::input_from_event(input);
-                    let watchdog_output = watchdog.evaluate(watchdog_input).await;
-                    let watchdog_duration = watchdog_start.elapsed();
-                    debug!(
-                        "Watchdog result: allow={}, confidence={}",
-                        watchdog_output.allow, watchdog_output.confidence
-                    );
+            if let Some(watchdog) = self.watchdog.filter(|_| watchdog_should_run) {
+                debug!(
+                    "Executing Watchdog evaluation for {:?} event",
+                    input.get("hook_event_name")
+                );
+                let watchdog_start = Instant::now();
+                let watchdog_input = watchdog.input_with_context(input, self.working_dir);
+                let watchdog_output = watchdog.evaluate(watchdog_input).await;
+                let watchdog_duration = watchdog_start.elapsed();
+                debug!(
+                    "Watchdog result: allow={}, confidence={}",
+                    watchdog_output.allow, watchdog_output.confidence
+                );
 
-                    // Capture watchdog in telemetry
-                    if let Some(ref mut telemetry) = signal_telemetry {
-                        telemetry.signals.push(SignalExecution {
-                            name: "watchdog".to_string(),
-                            command: format!("LLM evaluation via {}", watchdog.backend_name()),
-                            result: serde_json::to_value(&watchdog_output).unwrap_or_default(),
-                            duration_ms: Some(watchdog_duration.as_millis() as u64),
-                            exit_code: None,
-                        });
-                    }
+                // Capture watchdog in telemetry
+                if let Some(ref mut telemetry) = signal_telemetry {
+                    telemetry.signals.push(SignalExecution {
+                        name: "watchdog".to_string(),
+                        command: format!("LLM evaluation via {}", watchdog.backend_name()),
+                        result: serde_json::to_value(&watchdog_output).unwrap_or_default(),
+                        duration_ms: Some(watchdog_duration.as_millis() as u64),
+                        exit_code: None,
+                    });
+                }
 
+                let verdict = serde_json::to_value(&watchdog_output)?;
+                if is_pre_action_event {
                     if let Some(signals_map) = signals_obj.as_object_mut() {
-                        signals_map.insert(
-                            "watchdog".to_string(),
-                            serde_json::to_value(&watchdog_output)?,
-                        );
+                        signals_map.insert("watchdog".to_string(), verdict.clone());
                         signal_count += 1;
                     }
                 }
+                if watchdog_requested {
+                    input_obj.insert(
+                        "watchdog".to_string(),
+                        serde_json::json!({ "verdict": verdict }),
+                    );
+                }
             }
 
             input_obj.insert("signals".to_string(), signals_obj);
@@ -283,8 +332,13 @@ impl<'a> Executor<'a> {
 
                     let signal_execution = SignalExecution {
                         name: name.clone(),
-                        command: signal.command.clone(),
-                        result: result.as_ref().unwrap_or(&Value::Null).clone(),
+                        command: signal.command.to_string(),
+                        result: match &result {
+                            Ok(value) => value.clone(),
+                            Err(e) => e
+                                .downcast_ref::<CommandTimedOut>()
+                                .map_or(Value::Null, CommandTimedOut::to_json),
+                        },
                         duration_ms: Some(signal_duration.as_millis() as u64),
                         exit_code: None,
                     };
@@ -310,7 +364,7 @@ impl<'a> Executor<'a> {
                     }
                 }
                 Err(e) => {
-                    error!("Signal '{}' failed: {}", name, e);
+                    error!("Signal '{}' failed: {:#}", name, e);
 
                     if let (Some(ref mut telemetry), Some(execution)) =
                         (&mut signal_telemetry, signal_execution)
@@ -324,6 +378,36 @@ impl<'a> Executor<'a> {
         Ok(signal_data)
     }
 
+    /// Compute requested native signal providers and store them under `builtin`.
+    async fn collect_native_signals(
+        &self,
+        native_names: &[String],
+        input: &Value,
+        signal_data: &mut HashMap<String, Value>,
+        signal_telemetry: Option<&mut SignalTelemetry>,
+    ) {
+        let providers = native_signals::requested_providers(native_names);
+        if providers.is_empty() {
+            return;
+        }
+
+        debug!("Collecting native signals: {:?}", providers);
+        let native_start = Instant::now();
+        let value = native_signals::collect(&providers, self.working_dir, input).await;
+
+        if let Some(telemetry) = signal_telemetry {
+            telemetry.signals.push(SignalExecution {
+                name: native_signals::NATIVE_SIGNAL_KEY.to_string(),
+                command: format!("native: {}", providers.join(", ")),
+                result: value.clone(),
+                duration_ms: Some(native_start.elapsed().as_millis() as u64),
+                exit_code: None,
+            });
+        }
+
+        signal_data.insert(native_signals::NATIVE_SIGNAL_KEY.to_string(), value);
+    }
+
     /// Gather signals for global policy evaluation.
     ///
     /// This uses the global rulebook for signal definitions and builtin configs.
@@ -333,11 +417,6 @@ impl<'a> Executor<'a> {
         matched_policies: &[PolicyUnit],
         signal_telemetry: Option<&mut SignalTelemetry>,
     ) -> Result<Value> {
-        let Some(rulebook) = self.global_rulebook else {
-            debug!("No global rulebook - returning input unchanged");
-            return Ok(input.clone());
-        };
-
         // Collect required signals from matched policies
         let mut required_signals = std::collections::HashSet::new();
         for policy in matched_policies {
@@ -346,24 +425,44 @@ impl<'a> Executor<'a> {
             }
         }
 
-        // Auto-add signals for global builtin policies
+        // Native providers don't need a rulebook
+        let native_names: Vec<String> = required_signals
+            .iter()
+            .filter(|name| native_signals::is_native_signal(name))
+            .cloned()
+            .collect();
+        required_signals.retain(|name| !native_signals::is_native_signal(name));
+
+        let Some(rulebook) = self.global_rulebook else {
+            debug!("No global rulebook - returning input with native signals only");
+            let mut enriched_input = input.clone();
+            if !native_names.is_empty() {
+                let mut signal_data = HashMap::new();
+                self.collect_native_signals(
+                    &native_names,
+                    input,
+                    &mut signal_data,
+                    signal_telemetry,
+                )
+                .await;
+                if let Some(obj) = enriched_input.as_object_mut() {
+                    obj.insert("signals".to_string(), serde_json::json!(signal_data));
+                }
+            }
+            return Ok(enriched_input);
+        };
+
+        // Auto-add signals for global (or team) builtin policies
         for policy in matched_policies {
-            let is_global_builtin = policy
-                .package_name
-                .starts_with("cupcake.global.policies.builtins.");
-            let is_project_builtin = policy
-                .package_name
-                .starts_with("cupcake.policies.builtins.");
-
-            if is_global_builtin || is_project_builtin {
-                let prefix = if is_global_builtin {
-                    "cupcake.global.policies.builtins."
-                } else {
-                    "cupcake.policies.builtins."
-                };
-
-                let builtin_name = policy.package_name.strip_prefix(prefix).unwrap_or("");
+            let builtin_name = [
+                "cupcake.global.policies.builtins.",
+                "cupcake.team.policies.builtins.",
+                "cupcake.policies.builtins.",
+            ]
+            .iter()
+            .find_map(|prefix| policy.package_name.strip_prefix(prefix));
 
+            if let Some(builtin_name) = builtin_name {
                 // Add all signals that match this builtin's pattern
                 let signal_prefix = format!("__builtin_{builtin_name}_");
                 for signal_name in rulebook.signals.keys() {
@@ -389,6 +488,19 @@ impl<'a> Executor<'a> {
                         required_signals.insert(signal_name.clone());
                     }
                 }
+
+                // Signals referenced by injected context placeholders
+                if builtin_name.ends_with("always_inject_on_prompt") {
+                    for signal_name in rulebook.builtins.get_prompt_template_signals() {
+                        if rulebook.signals.contains_key(&signal_name) {
+                            debug!(
+                                "Auto-adding signal '{}' referenced by global builtin '{}'",
+                                signal_name, builtin_name
+                            );
+                            required_signals.insert(signal_name);
+                        }
+                    }
+                }
             }
         }
 
@@ -407,23 +519,35 @@ impl<'a> Executor<'a> {
             }
         }
 
-        if required_signals.is_empty() {
+        if required_signals.is_empty() && native_names.is_empty() {
             debug!("No global signals required - returning with builtin config");
             return Ok(enriched_input);
         }
 
         let signal_names: Vec<String> = required_signals.into_iter().collect();
-        info!("Gathering {} global signals", signal_names.len());
+        info!(
+            "Gathering {} global signals",
+            signal_names.len() + native_names.len()
+        );
 
         // Execute signals using global rulebook
-        let signal_data = self
-            .execute_signals(&signal_names, rulebook, input, signal_telemetry)
+        let mut signal_telemetry = signal_telemetry;
+        let mut signal_data = self
+            .execute_signals(
+                &signal_names,
+                rulebook,
+                input,
+                signal_telemetry.as_deref_mut(),
+            )
             .await
             .unwrap_or_else(|e| {
                 warn!("Global signal execution failed: {}", e);
                 HashMap::new()
             });
 
+        self.collect_native_signals(&native_names, input, &mut signal_data, signal_telemetry)
+            .await;
+
         // Merge signal data into enriched input
         if let Some(obj) = enriched_input.as_object_mut() {
             obj.insert("signals".to_string(), serde_json::json!(signal_data));
@@ -0,0 +1,332 @@
+//! External policy data - documents fetched from an HTTP endpoint or file
+//!
+//! Configured in rulebook.yml and exposed to project policies as
+//! `data.external.<name>`:
+//!
+//! ```yaml
+//! external_data:
+//!   malicious_packages:
+//!     url: https://security.example.com/npm-denylist.json
+//!     refresh_seconds: 3600
+//!   frozen_services:
+//!     file: data/frozen.yaml
+//! ```
+//!
+//! Each source is re-fetched at most every `refresh_seconds`. Fetched documents
+//! are cached under `.cupcake/cache/external/` so short-lived CLI invocations
+//! share the refresh interval. A failed refresh keeps serving the last good
+//! copy and marks it stale. Refresh metadata is exposed as
+//! `data.external._meta.<name>`.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+/// Key under `data.external` holding per-source refresh metadata
+pub const EXTERNAL_META_KEY: &str = "_meta";
+
+/// Configuration for one external data source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalSourceConfig {
+    /// HTTP(S) endpoint returning JSON or YAML
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// Local file (JSON or YAML), relative to the .cupcake directory
+    #[serde(default)]
+    pub file: Option<PathBuf>,
+
+    /// Minimum seconds between refreshes (default 3600)
+    #[serde(default = "default_refresh_seconds")]
+    pub refresh_seconds: u64,
+
+    /// Fetch timeout in seconds (default 10)
+    #[serde(default = "default_fetch_timeout")]
+    pub timeout_seconds: u64,
+}
+
+fn default_refresh_seconds() -> u64 {
+    3600
+}
+
+fn default_fetch_timeout() -> u64 {
+    10
+}
+
+impl ExternalSourceConfig {
+    fn describe(&self) -> String {
+        match (&self.url, &self.file) {
+            (Some(url), _) => url.clone(),
+            (None, Some(file)) => file.display().to_string(),
+            (None, None) => "<unset>".to_string(),
+        }
+    }
+}
+
+/// Last good copy of a source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedDocument {
+    fetched_at: i64,
+    data: Value,
+}
+
+/// Refresh state for one source, persisted so failed refreshes are not
+/// retried by every short-lived process
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SourceState {
+    cached: Option<CachedDocument>,
+    last_attempt: Option<i64>,
+    error: Option<String>,
+    #[serde(skip)]
+    loaded: bool,
+}
+
+/// Refreshes external sources and builds the `data.external` document
+pub struct ExternalData {
+    sources: BTreeMap<String, ExternalSourceConfig>,
+    cupcake_dir: PathBuf,
+    cache_dir: PathBuf,
+    state: Mutex<BTreeMap<String, SourceState>>,
+}
+
+impl ExternalData {
+    /// Create a manager for the configured sources, or `None` if there are none
+    pub fn new(
+        sources: &HashMap<String, ExternalSourceConfig>,
+        cupcake_dir: &Path,
+    ) -> Result<Option<Self>> {
+        if sources.is_empty() {
+            return Ok(None);
+        }
+        for (name, source) in sources {
+            if name == EXTERNAL_META_KEY {
+                bail!("External data source name '{EXTERNAL_META_KEY}' is reserved");
+            }
+            if source.url.is_some() == source.file.is_some() {
+                bail!("External data source '{name}' must set exactly one of 'url' or 'file'");
+            }
+        }
+        Ok(Some(Self {
+            sources: sources.clone().into_iter().collect(),
+            cupcake_dir: cupcake_dir.to_path_buf(),
+            cache_dir: cupcake_dir.join("cache").join("external"),
+            state: Mutex::new(BTreeMap::new()),
+        }))
+    }
+
+    /// Refresh any sources that are due
+    ///
+    /// Returns the rebuilt `data.external` document when anything was loaded or
+    /// re-fetched since the previous call, `None` when nothing changed.
+    pub async fn refresh(&self) -> Option<Value> {
+        let mut state = self.state.lock().await;
+        let now = chrono::Utc::now().timestamp();
+        let mut changed = false;
+
+        for (name, source) in &self.sources {
+            let entry = state.entry(name.clone()).or_default();
+
+            if !entry.loaded {
+                *entry = self.read_cache(name).await.unwrap_or_default();
+                entry.loaded = true;
+                changed = true;
+            }
+
+            let last_refresh = entry
+                .cached
+                .as_ref()
+                .map(|c| c.fetched_at)
+                .max(entry.last_attempt);
+            let due = last_refresh.is_none_or(|t| now - t >= source.refresh_seconds as i64);
+            if !due {
+                continue;
+            }
+
+            debug!("Refreshing external data source '{}'", name);
+            entry.last_attempt = Some(now);
+            changed = true;
+            match self.fetch(source).await {
+                Ok(data) => {
+                    info!("Refreshed external data source '{}'", name);
+                    entry.cached = Some(CachedDocument {
+                        fetched_at: now,
+                        data,
+                    });
+                    entry.error = None;
+                }
+                Err(e) => {
+                    warn!("Failed to refresh external data source '{}': {:#}", name, e);
+                    entry.error = Some(format!("{e:#}"));
+                }
+            }
+            self.write_cache(name, entry).await;
+        }
+
+        changed.then(|| self.build_document(&state, now))
+    }
+
+    fn build_document(&self, state: &BTreeMap<String, SourceState>, now: i64) -> Value {
+        let mut external = Map::new();
+        let mut meta = Map::new();
+
+        for (name, source) in &self.sources {
+            let Some(entry) = state.get(name) else {
+                continue;
+            };
+            let fetched_at = entry.cached.as_ref().map(|c| c.fetched_at);
+            if let Some(cached) = &entry.cached {
+                external.insert(name.clone(), cached.data.clone());
+            }
+            meta.insert(
+                name.clone(),
+                json!({
+                    "source": source.describe(),
+                    "last_refresh": fetched_at
+                        .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+                        .map(|t| t.to_rfc3339()),
+                    "last_refresh_unix": fetched_at,
+                    "refresh_seconds": source.refresh_seconds,
+                    "stale": fetched_at
+                        .is_none_or(|t| now - t >= source.refresh_seconds as i64),
+                    "error": entry.error,
+                }),
+            );
+        }
+
+        external.insert(EXTERNAL_META_KEY.to_string(), Value::Object(meta));
+        Value::Object(external)
+    }
+
+    async fn fetch(&self, source: &ExternalSourceConfig) -> Result<Value> {
+        if let Some(file) = &source.file {
+            let path = self.cupcake_dir.join(file);
+            let raw = tokio::fs::read(&path)
+                .await
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            return parse_document(&raw);
+        }
+        let url = source.url.as_deref().context("No url or file configured")?;
+        fetch_url(url, source.timeout_seconds).await
+    }
+
+    fn cache_path(&self, name: &str) -> PathBuf {
+        self.cache_dir.join(format!("{name}.json"))
+    }
+
+    async fn read_cache(&self, name: &str) -> Option<SourceState> {
+        let raw = tokio::fs::read(self.cache_path(name)).await.ok()?;
+        serde_json::from_slice(&raw).ok()
+    }
+
+    async fn write_cache(&self, name: &str, state: &SourceState) {
+        let result = async {
+            tokio::fs::create_dir_all(&self.cache_dir).await?;
+            tokio::fs::write(self.cache_path(name), serde_json::to_vec(state)?).await?;
+            anyhow::Ok(())
+        }
+        .await;
+        if let Err(e) = result {
+            debug!("Could not cache external data source '{}': {}", name, e);
+        }
+    }
+}
+
+/// Parse a fetched document as JSON, falling back to YAML
+fn parse_document(raw: &[u8]) -> Result<Value> {
+    serde_json::from_slice(raw).or_else(|_| {
+        serde_yaml_ng::from_slice(raw).context("External data is neither valid JSON nor YAML")
+    })
+}
+
+#[cfg(feature = "external-data")]
+async fn fetch_url(url: &str, timeout_seconds: u64) -> Result<Value> {
+    let response = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(timeout_seconds))
+        .build()?
+        .get(url)
+        .send()
+        .await
+        .with_context(|| format!("Request to {url} failed"))?
+        .error_for_status()?;
+    parse_document(&response.bytes().await?)
+}
+
+#[cfg(not(feature = "external-data"))]
+async fn fetch_url(url: &str, _timeout_seconds: u64) -> Result<Value> {
+    bail!("Cannot fetch {url}: cupcake was built without the 'external-data' feature")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_source(file: &str, refresh_seconds: u64) -> ExternalSourceConfig {
+        ExternalSourceConfig {
+            url: None,
+            file: Some(PathBuf::from(file)),
+            refresh_seconds,
+            timeout_seconds: 1,
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_sources() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let both = ExternalSourceConfig {
+            url: Some("https://example.com".to_string()),
+            ..file_source("a.json", 60)
+        };
+        let sources = HashMap::from([("both".to_string(), both)]);
+        assert!(ExternalData::new(&sources, dir.path()).is_err());
+
+        let sources = HashMap::from([(EXTERNAL_META_KEY.to_string(), file_source("a.json", 60))]);
+        assert!(ExternalData::new(&sources, dir.path()).is_err());
+
+        assert!(ExternalData::new(&HashMap::new(), dir.path())
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_file_source_refresh_and_stale_fallback() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("frozen.yaml"), "services:\n  - billing\n").unwrap();
+
+        let sources = HashMap::from([("frozen".to_string(), file_source("frozen.yaml", 0))]);
+        let external = ExternalData::new(&sources, dir.path()).unwrap().unwrap();
+
+        let doc = external.refresh().await.unwrap();
+        assert_eq!(doc["frozen"]["services"][0], json!("billing"));
+        assert_eq!(doc["_meta"]["frozen"]["error"], Value::Null);
+        assert!(dir.path().join("cache/external/frozen.json").exists());
+
+        // A failed refresh keeps the last good copy and reports the error
+        std::fs::remove_file(dir.path().join("frozen.yaml")).unwrap();
+        let doc = external.refresh().await.unwrap();
+        assert_eq!(doc["frozen"]["services"][0], json!("billing"));
+        assert_eq!(doc["_meta"]["frozen"]["stale"], json!(true));
+        assert!(doc["_meta"]["frozen"]["error"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_respects_interval_and_disk_cache() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("list.json"), r#"["a"]"#).unwrap();
+        let sources = HashMap::from([("list".to_string(), file_source("list.json", 3600))]);
+
+        let first = ExternalData::new(&sources, dir.path()).unwrap().unwrap();
+        assert!(first.refresh().await.is_some());
+        assert!(first.refresh().await.is_none());
+
+        // A new process starts from the disk cache instead of re-fetching
+        std::fs::write(dir.path().join("list.json"), r#"["b"]"#).unwrap();
+        let second = ExternalData::new(&sources, dir.path()).unwrap().unwrap();
+        let doc = second.refresh().await.unwrap();
+        assert_eq!(doc["list"], json!(["a"]));
+        assert_eq!(doc["_meta"]["list"]["stale"], json!(false));
+    }
+}
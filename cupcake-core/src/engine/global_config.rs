@@ -2,15 +2,24 @@
 //!
 //! Provides cross-platform discovery and management of machine-wide Cupcake configurations.
 //! Global policies take absolute precedence over project-specific policies.
+//!
+//! The global tier can be layered from several roots - a system-wide baseline
+//! shipped with the machine image, then per-user customization. Roots are
+//! listed in precedence order, like `PATH`: all of them are loaded, and where
+//! two define the same policy file or rulebook entry, the earlier root wins.
+//! A user layer can add policies but can't replace or weaken the baseline.
 
 use anyhow::{Context, Result};
 use std::path::PathBuf;
-use tracing::{debug, info, trace};
+use tracing::{debug, info, trace, warn};
+
+/// Environment variable listing global config roots, separated like `PATH`
+pub const CONFIG_PATH_ENV: &str = "CUPCAKE_CONFIG_PATH";
 
 /// Global configuration paths for system-wide policies
 #[derive(Debug, Clone)]
 pub struct GlobalPaths {
-    /// Root directory for global config
+    /// Root directory for global config (the highest-precedence layer)
     pub root: PathBuf,
     /// Global policies directory
     pub policies: PathBuf,
@@ -18,6 +27,8 @@ pub struct GlobalPaths {
     pub rulebook: PathBuf,
     /// Global signals directory
     pub signals: PathBuf,
+    /// Every global config root, highest precedence first (includes `root`)
+    pub layers: Vec<PathBuf>,
 }
 
 impl GlobalPaths {
@@ -25,8 +36,9 @@ impl GlobalPaths {
     ///
     /// Resolution order:
     /// 1. CLI override (if provided)
-    /// 2. Platform-specific user config directory
-    /// 3. None if config directory doesn't exist (graceful absence)
+    /// 2. `CUPCAKE_CONFIG_PATH` roots
+    /// 3. System-wide directory, then the platform-specific user config directory
+    /// 4. None if no config directory exists (graceful absence)
     pub fn discover() -> Result<Option<Self>> {
         Self::discover_with_override(None)
     }
@@ -34,9 +46,11 @@ impl GlobalPaths {
     /// Discover global configuration with optional CLI override
     ///
     /// Resolution order:
-    /// 1. CLI override parameter (if provided)
-    /// 2. Platform-specific user config directory
-    /// 3. None if config directory doesn't exist (graceful absence)
+    /// 1. CLI override parameter (if provided) - the only layer
+    /// 2. `CUPCAKE_CONFIG_PATH` roots, in the order listed
+    /// 3. System-wide directory (`/etc/cupcake`, `%PROGRAMDATA%\cupcake`),
+    ///    then the platform-specific user config directory
+    /// 4. None if no config directory exists (graceful absence)
     pub fn discover_with_override(cli_override: Option<PathBuf>) -> Result<Option<Self>> {
         trace!("Discovering global configuration paths");
 
@@ -82,27 +96,83 @@ impl GlobalPaths {
             return Ok(Some(Self::from_root(canonical_path)?));
         }
 
-        // Use platform-specific config directory
-        let config_dir = Self::get_platform_config_dir()?;
-        let cupcake_global_dir = config_dir.join("cupcake");
+        let candidates = match std::env::var_os(CONFIG_PATH_ENV) {
+            Some(value) if !value.is_empty() => Self::parse_search_path(&value)?,
+            // System-wide baseline first, then the user's own configuration
+            _ => Self::get_system_config_dir()
+                .into_iter()
+                .chain(std::iter::once(
+                    Self::get_platform_config_dir()?.join("cupcake"),
+                ))
+                .collect(),
+        };
+
+        Self::from_candidates(candidates)
+    }
 
-        // Check if global config exists - graceful absence
-        if !cupcake_global_dir.exists() {
-            debug!("No global configuration found at {:?}", cupcake_global_dir);
-            return Ok(None);
+    /// Parse a `CUPCAKE_CONFIG_PATH` value into its roots
+    fn parse_search_path(value: &std::ffi::OsStr) -> Result<Vec<PathBuf>> {
+        let roots: Vec<PathBuf> = std::env::split_paths(value).collect();
+        if let Some(relative) = roots.iter().find(|root| !root.is_absolute()) {
+            return Err(anyhow::anyhow!(
+                "Global config paths in {} must be absolute (got: {})",
+                CONFIG_PATH_ENV,
+                relative.display()
+            ));
+        }
+        debug!(
+            "Using global config roots from {}: {:?}",
+            CONFIG_PATH_ENV, roots
+        );
+        Ok(roots)
+    }
+
+    /// Keep the candidate roots that exist, in order, as layers
+    fn from_candidates(candidates: Vec<PathBuf>) -> Result<Option<Self>> {
+        let mut layers: Vec<PathBuf> = Vec::new();
+        for candidate in candidates {
+            // Check if global config exists - graceful absence
+            if !candidate.is_dir() {
+                debug!("No global configuration found at {:?}", candidate);
+                continue;
+            }
+            let resolved = candidate.canonicalize().with_context(|| {
+                format!(
+                    "Failed to resolve global config path: {}",
+                    candidate.display()
+                )
+            })?;
+            if layers.contains(&resolved) {
+                warn!("Global config root {:?} is listed twice", resolved);
+                continue;
+            }
+            info!("Found global configuration at {:?}", resolved);
+            layers.push(resolved);
         }
 
-        info!("Found global configuration at {:?}", cupcake_global_dir);
-        Ok(Some(Self::from_root(cupcake_global_dir)?))
+        if layers.is_empty() {
+            return Ok(None);
+        }
+        Self::from_layers(layers).map(Some)
     }
 
     /// Create GlobalPaths from a root directory
     fn from_root(root: PathBuf) -> Result<Self> {
-        // Verify root exists
-        if !root.exists() {
+        Self::from_layers(vec![root])
+    }
+
+    /// Create GlobalPaths from roots in precedence order
+    fn from_layers(layers: Vec<PathBuf>) -> Result<Self> {
+        let root = layers
+            .first()
+            .cloned()
+            .context("At least one global config root is required")?;
+
+        // Verify every root exists
+        if let Some(missing) = layers.iter().find(|layer| !layer.exists()) {
             return Err(anyhow::anyhow!(
                 "Global config root does not exist: {:?}",
-                root
+                missing
             ));
         }
 
@@ -111,9 +181,44 @@ impl GlobalPaths {
             rulebook: root.join("rulebook.yml"),
             signals: root.join("signals"),
             root,
+            layers,
         })
     }
 
+    /// Create GlobalPaths for a directory that doesn't exist yet, e.g. before `init --global`
+    pub fn at(root: PathBuf) -> Self {
+        GlobalPaths {
+            policies: root.join("policies"),
+            rulebook: root.join("rulebook.yml"),
+            signals: root.join("signals"),
+            layers: vec![root.clone()],
+            root,
+        }
+    }
+
+    /// Every layer's GlobalPaths, highest precedence first
+    pub fn layer_paths(&self) -> Vec<GlobalPaths> {
+        self.layers.iter().cloned().map(Self::at).collect()
+    }
+
+    /// The system-wide config directory, shared by every user of the machine
+    fn get_system_config_dir() -> Option<PathBuf> {
+        #[cfg(unix)]
+        {
+            Some(PathBuf::from("/etc/cupcake"))
+        }
+
+        #[cfg(windows)]
+        {
+            std::env::var_os("PROGRAMDATA").map(|dir| PathBuf::from(dir).join("cupcake"))
+        }
+
+        #[cfg(not(any(unix, windows)))]
+        {
+            None
+        }
+    }
+
     /// Get the platform-specific config directory
     fn get_platform_config_dir() -> Result<PathBuf> {
         // Use the directories crate for cross-platform support
@@ -213,6 +318,7 @@ builtins: {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::Path;
     use tempfile::TempDir;
 
     #[test]
@@ -269,6 +375,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_search_path_layers_in_order() {
+        let system = TempDir::new().unwrap();
+        let user = TempDir::new().unwrap();
+        let value = std::env::join_paths([
+            system.path(),
+            Path::new("/nonexistent/cupcake"),
+            user.path(),
+            system.path(),
+        ])
+        .unwrap();
+
+        let roots = GlobalPaths::parse_search_path(&value).unwrap();
+        assert_eq!(roots.len(), 4);
+
+        // Missing roots are skipped, repeated roots kept once, order preserved
+        let global_paths = GlobalPaths::from_candidates(roots).unwrap().unwrap();
+        let system_root = system.path().canonicalize().unwrap();
+        assert_eq!(global_paths.root, system_root);
+        assert_eq!(global_paths.policies, system_root.join("policies"));
+        assert_eq!(
+            global_paths.layers,
+            vec![system_root, user.path().canonicalize().unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_search_path_rejects_relative_roots() {
+        let value = std::env::join_paths([Path::new("/etc/cupcake"), Path::new("team")]).unwrap();
+        let err = GlobalPaths::parse_search_path(&value).unwrap_err();
+        assert!(err.to_string().contains(CONFIG_PATH_ENV));
+        assert!(err.to_string().contains("must be absolute"));
+    }
+
+    #[test]
+    fn test_search_path_without_existing_roots() {
+        let roots = vec![
+            PathBuf::from("/nonexistent/a"),
+            PathBuf::from("/nonexistent/b"),
+        ];
+        assert!(GlobalPaths::from_candidates(roots).unwrap().is_none());
+    }
+
     #[test]
     fn test_discover_graceful_absence() {
         // Discovery should return None when no global config exists
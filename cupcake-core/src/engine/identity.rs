@@ -0,0 +1,256 @@
+//! Identity - who is driving the agent, and which roles they hold
+//!
+//! The user is read from the first source that provides one, then mapped to
+//! roles by the `identity:` rulebook section:
+//!
+//! ```yaml
+//! identity:
+//!   sources: [harness, env, os]   # default order
+//!   env: CUPCAKE_USER             # variable read by the env source
+//!   roles:
+//!     lead: [alice, bob@example.com]
+//!     intern: [carol]
+//!   default_roles: [developer]    # roles of users listed nowhere
+//! ```
+//!
+//! - `harness` - a user the harness reports with the event (Cursor's `user_email`)
+//! - `env` - the configured environment variable
+//! - `os` - the login name (`USER`, or `USERNAME` on Windows)
+//!
+//! The identity is exposed to policies as `input.identity` and recorded in
+//! telemetry; `severity_mapping` rules can be limited to roles. Every tier
+//! resolves it with its own rulebook. Only the harness source is authenticated -
+//! the others are controlled by whoever runs the agent.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Environment variable read by the `env` source unless `identity.env` says otherwise
+pub const USER_ENV: &str = "CUPCAKE_USER";
+
+/// Where a user name comes from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IdentitySource {
+    Harness,
+    Env,
+    Os,
+}
+
+/// The `identity:` rulebook section
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdentityConfig {
+    /// Sources tried in order
+    #[serde(default = "default_sources")]
+    pub sources: Vec<IdentitySource>,
+
+    /// Environment variable read by the `env` source
+    #[serde(default = "default_env")]
+    pub env: String,
+
+    /// Role name -> users holding it (case-insensitive)
+    #[serde(default)]
+    pub roles: BTreeMap<String, Vec<String>>,
+
+    /// Roles of users that hold no role in `roles`
+    #[serde(default)]
+    pub default_roles: Vec<String>,
+}
+
+impl Default for IdentityConfig {
+    fn default() -> Self {
+        Self {
+            sources: default_sources(),
+            env: default_env(),
+            roles: BTreeMap::new(),
+            default_roles: Vec::new(),
+        }
+    }
+}
+
+fn default_sources() -> Vec<IdentitySource> {
+    vec![
+        IdentitySource::Harness,
+        IdentitySource::Env,
+        IdentitySource::Os,
+    ]
+}
+
+fn default_env() -> String {
+    USER_ENV.to_string()
+}
+
+/// The resolved identity, as seen by policies in `input.identity`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Identity {
+    /// User name, if any source provided one
+    pub user: Option<String>,
+
+    /// Source the user came from
+    pub source: Option<IdentitySource>,
+
+    /// Roles held by the user, sorted
+    pub roles: Vec<String>,
+}
+
+impl Identity {
+    pub fn has_any_role(&self, roles: &[String]) -> bool {
+        roles
+            .iter()
+            .any(|role| self.roles.iter().any(|r| r.eq_ignore_ascii_case(role)))
+    }
+}
+
+/// Check the section for mistakes the deserializer can't catch
+pub fn validate(config: &IdentityConfig) -> Vec<String> {
+    let mut errors = Vec::new();
+    if config.sources.is_empty() {
+        errors.push("identity.sources: at least one source is required".to_string());
+    }
+    if config.sources.contains(&IdentitySource::Env) && config.env.trim().is_empty() {
+        errors.push("identity.env: variable name must not be empty".to_string());
+    }
+    for (role, users) in &config.roles {
+        if role.trim().is_empty() {
+            errors.push("identity.roles: role names must not be empty".to_string());
+        }
+        if users.iter().any(|user| user.trim().is_empty()) {
+            errors.push(format!(
+                "identity.roles.{role}: user names must not be empty"
+            ));
+        }
+    }
+    errors
+}
+
+/// Resolve the identity behind an event
+pub fn resolve(config: &IdentityConfig, input: &Value) -> Identity {
+    let found = config.sources.iter().find_map(|source| {
+        let user = match source {
+            IdentitySource::Harness => harness_user(input),
+            IdentitySource::Env => std::env::var(&config.env).ok(),
+            IdentitySource::Os => std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .ok(),
+        };
+        user.map(|user| user.trim().to_string())
+            .filter(|user| !user.is_empty())
+            .map(|user| (user, *source))
+    });
+
+    let Some((user, source)) = found else {
+        return Identity {
+            roles: config.default_roles.clone(),
+            ..Default::default()
+        };
+    };
+    let mut roles: Vec<String> = config
+        .roles
+        .iter()
+        .filter(|(_, users)| users.iter().any(|u| u.eq_ignore_ascii_case(&user)))
+        .map(|(role, _)| role.clone())
+        .collect();
+    if roles.is_empty() {
+        roles = config.default_roles.clone();
+    }
+    Identity {
+        user: Some(user),
+        source: Some(source),
+        roles,
+    }
+}
+
+/// Expose the identity to policies as `input.identity`
+pub fn attach(input: &mut Value, identity: &Identity) {
+    if let Some(object) = input.as_object_mut() {
+        object.insert(
+            "identity".to_string(),
+            serde_json::to_value(identity).unwrap_or(Value::Null),
+        );
+    }
+}
+
+fn harness_user(input: &Value) -> Option<String> {
+    ["user_email", "user"]
+        .into_iter()
+        .find_map(|field| input.get(field).and_then(Value::as_str))
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config(yaml: &str) -> IdentityConfig {
+        serde_yaml_ng::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_harness_user_maps_to_roles() {
+        let config = config(
+            r#"
+roles:
+  lead: [Alice@example.com]
+  reviewer: [alice@example.com, bob]
+default_roles: [developer]
+"#,
+        );
+        let identity = resolve(&config, &json!({"user_email": "alice@example.com"}));
+        assert_eq!(identity.user.as_deref(), Some("alice@example.com"));
+        assert_eq!(identity.source, Some(IdentitySource::Harness));
+        assert_eq!(identity.roles, vec!["lead", "reviewer"]);
+        assert!(identity.has_any_role(&["LEAD".to_string()]));
+
+        let identity = resolve(&config, &json!({"user_email": "dave@example.com"}));
+        assert_eq!(identity.roles, vec!["developer"]);
+    }
+
+    #[test]
+    fn test_sources_are_tried_in_order() {
+        let var = "CUPCAKE_TEST_IDENTITY_USER";
+        std::env::set_var(var, "carol");
+        let config = config(&format!(
+            "sources: [env, harness]\nenv: {var}\nroles:\n  intern: [carol]\n"
+        ));
+        let identity = resolve(&config, &json!({"user_email": "alice@example.com"}));
+        std::env::remove_var(var);
+
+        assert_eq!(identity.user.as_deref(), Some("carol"));
+        assert_eq!(identity.source, Some(IdentitySource::Env));
+        assert_eq!(identity.roles, vec!["intern"]);
+
+        // Nothing found: no user, default roles only
+        let identity = resolve(
+            &IdentityConfig {
+                sources: vec![IdentitySource::Harness],
+                default_roles: vec!["guest".to_string()],
+                ..Default::default()
+            },
+            &json!({}),
+        );
+        assert_eq!(identity.user, None);
+        assert_eq!(identity.roles, vec!["guest"]);
+    }
+
+    #[test]
+    fn test_attach_and_validate() {
+        let mut input = json!({"tool_name": "Bash"});
+        attach(
+            &mut input,
+            &Identity {
+                user: Some("bob".to_string()),
+                source: Some(IdentitySource::Os),
+                roles: vec!["lead".to_string()],
+            },
+        );
+        assert_eq!(
+            input["identity"],
+            json!({"user": "bob", "source": "os", "roles": ["lead"]})
+        );
+
+        let errors = validate(&config("sources: []\nroles:\n  lead: ['']\n"));
+        assert_eq!(errors.len(), 2);
+    }
+}
@@ -0,0 +1,104 @@
+//! Engine initialization report
+//!
+//! Records what the engine found and did while loading each tier - policies
+//! discovered and loaded, files skipped and why, compilation time, WASM size
+//! and discovered signals - so automation can assert on a healthy start
+//! instead of scraping logs. Retrieved with `Engine::init_report()` and
+//! printed by `cupcake verify --json`.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::namespaces::Tier;
+
+/// How the engine initialized, tier by tier
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct InitReport {
+    /// Tiers in evaluation order (global, team, project); absent tiers are omitted
+    pub tiers: Vec<TierReport>,
+
+    /// Wall-clock time for the whole initialization, in milliseconds
+    pub total_ms: u64,
+}
+
+/// Initialization of one configuration tier
+#[derive(Debug, Clone, Serialize)]
+pub struct TierReport {
+    pub tier: Tier,
+
+    /// Policy files found by scanning (after builtin filtering)
+    pub policies_discovered: usize,
+
+    /// Packages of the policies that were loaded
+    pub policies_loaded: Vec<String>,
+
+    /// Policy files that were found but not loaded
+    pub skipped: Vec<SkippedPolicy>,
+
+    /// Signals configured or discovered for this tier, sorted by name
+    pub signals: Vec<String>,
+
+    /// Time spent loading the tier (rulebook, scanning, parsing), in milliseconds
+    pub load_ms: u64,
+
+    /// Time spent compiling the tier's policies to WASM, in milliseconds.
+    /// `None` when nothing was compiled at startup - global and team policies
+    /// are compiled on the first matching event.
+    pub compile_ms: Option<u64>,
+
+    /// Size of the compiled WASM module, when compiled at startup
+    pub wasm_bytes: Option<usize>,
+}
+
+/// A policy file that was discovered but not loaded
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedPolicy {
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+impl TierReport {
+    pub fn new(tier: Tier) -> Self {
+        Self {
+            tier,
+            policies_discovered: 0,
+            policies_loaded: Vec::new(),
+            skipped: Vec::new(),
+            signals: Vec::new(),
+            load_ms: 0,
+            compile_ms: None,
+            wasm_bytes: None,
+        }
+    }
+
+    pub fn skip(&mut self, path: &Path, reason: impl Into<String>) {
+        self.skipped.push(SkippedPolicy {
+            path: path.to_path_buf(),
+            reason: reason.into(),
+        });
+    }
+}
+
+impl InitReport {
+    /// The report for a tier, if that tier was initialized
+    pub fn tier(&self, tier: Tier) -> Option<&TierReport> {
+        self.tiers.iter().find(|report| report.tier == tier)
+    }
+
+    /// The report for a tier, added if it isn't there yet
+    pub(crate) fn tier_mut(&mut self, tier: Tier) -> &mut TierReport {
+        match self.tiers.iter().position(|report| report.tier == tier) {
+            Some(index) => &mut self.tiers[index],
+            None => {
+                self.tiers.push(TierReport::new(tier));
+                self.tiers.last_mut().expect("just pushed")
+            }
+        }
+    }
+}
+
+/// Milliseconds in a duration, for report fields
+pub(crate) fn millis(duration: Duration) -> u64 {
+    duration.as_millis().try_into().unwrap_or(u64::MAX)
+}
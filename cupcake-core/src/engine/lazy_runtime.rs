@@ -0,0 +1,192 @@
+//! Lazily compiled WASM runtimes for the global and team tiers
+//!
+//! Machine-wide policies often never route for a given project, so compiling
+//! them during `Engine::new` only delays project-only evaluations. Their WASM
+//! is compiled in a background task started at init - so a broken policy is
+//! logged straight away - and the first event matching one of their policies
+//! waits for it. `Engine::compile_deferred_tiers` compiles them up front for
+//! commands like `cupcake verify` that must fail on broken policies.
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+use tokio::task::AbortHandle;
+use tracing::{info, warn};
+
+use super::compiler;
+use super::config::EngineConfig;
+use super::init_report::SkippedPolicy;
+use super::metadata::PolicyUnit;
+use super::module_cache::ModuleCache;
+use super::rulebook::OnPolicyError;
+use super::wasm_runtime::WasmRuntime;
+
+/// A compiled tier: the WASM bytes and the runtime built from them
+pub struct CompiledTier {
+    pub module: Vec<u8>,
+    pub runtime: WasmRuntime,
+    /// Policies left out because they failed to compile
+    pub excluded: Vec<SkippedPolicy>,
+}
+
+/// WASM for a policy tier, compiled on first use
+pub struct LazyRuntime {
+    /// Aggregation entrypoint namespace (e.g. "cupcake.global.system")
+    namespace: &'static str,
+    /// Tier root used to resolve shared helpers
+    helpers_root: Option<PathBuf>,
+    /// Whether policies that fail to compile are excluded or fail the tier
+    on_policy_error: OnPolicyError,
+    /// Shared with the background compilation started by `compile_in_background`
+    compiled: Arc<OnceCell<CompiledTier>>,
+    /// The background compilation, stopped when the runtime is dropped
+    background: Option<AbortHandle>,
+}
+
+impl LazyRuntime {
+    pub fn new(
+        namespace: &'static str,
+        helpers_root: Option<PathBuf>,
+        on_policy_error: OnPolicyError,
+    ) -> Self {
+        Self {
+            namespace,
+            helpers_root,
+            on_policy_error,
+            compiled: Arc::new(OnceCell::new()),
+            background: None,
+        }
+    }
+
+    /// Policies excluded from the compiled tier (empty until it is compiled)
+    pub fn excluded(&self) -> &[SkippedPolicy] {
+        self.compiled
+            .get()
+            .map(|tier| tier.excluded.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Start compiling the tier in a background task
+    ///
+    /// A failure is logged and not cached, so the first matching event
+    /// retries and reports it. Does nothing outside a Tokio runtime.
+    pub fn compile_in_background(
+        &mut self,
+        policies: Vec<PolicyUnit>,
+        config: EngineConfig,
+        cache: ModuleCache,
+    ) {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let compiled = Arc::clone(&self.compiled);
+        let namespace = self.namespace;
+        let helpers_root = self.helpers_root.clone();
+        let on_policy_error = self.on_policy_error;
+        let task = handle.spawn(async move {
+            let result = compiled
+                .get_or_try_init(|| {
+                    compile(
+                        namespace,
+                        helpers_root.as_deref(),
+                        on_policy_error,
+                        &policies,
+                        &config,
+                        &cache,
+                    )
+                })
+                .await;
+            if let Err(e) = result {
+                warn!("Background compilation of {} failed: {:#}", namespace, e);
+            }
+        });
+        self.background = Some(task.abort_handle());
+    }
+
+    /// Get the compiled tier, compiling the policies on the first call
+    ///
+    /// Concurrent callers, including a background compilation, share a single
+    /// compilation. A failed compilation is not cached, so the next call retries.
+    pub async fn get(
+        &self,
+        policies: &[PolicyUnit],
+        config: &EngineConfig,
+        cache: &ModuleCache,
+    ) -> Result<&CompiledTier> {
+        self.compiled
+            .get_or_try_init(|| {
+                compile(
+                    self.namespace,
+                    self.helpers_root.as_deref(),
+                    self.on_policy_error,
+                    policies,
+                    config,
+                    cache,
+                )
+            })
+            .await
+    }
+}
+
+impl Drop for LazyRuntime {
+    fn drop(&mut self) {
+        // Also kills the OPA process if it's still running
+        if let Some(task) = &self.background {
+            task.abort();
+        }
+    }
+}
+
+/// Compile a tier's policies and build its runtime
+async fn compile(
+    namespace: &'static str,
+    helpers_root: Option<&Path>,
+    on_policy_error: OnPolicyError,
+    policies: &[PolicyUnit],
+    config: &EngineConfig,
+    cache: &ModuleCache,
+) -> Result<CompiledTier> {
+    info!(
+        "Compiling {} WASM module ({} policies)",
+        namespace,
+        policies.len()
+    );
+    let (bundle, excluded) = match on_policy_error {
+        OnPolicyError::Fail => (
+            compiler::compile_bundle_with_namespace(
+                policies,
+                namespace,
+                config.opa_path.clone(),
+                helpers_root,
+            )
+            .await?,
+            Vec::new(),
+        ),
+        OnPolicyError::Skip => {
+            compiler::compile_bundle_excluding_broken(
+                policies,
+                namespace,
+                config.opa_path.clone(),
+                helpers_root,
+            )
+            .await?
+        }
+    };
+    info!(
+        "Successfully compiled {} WASM module ({} bytes)",
+        namespace,
+        bundle.wasm.len()
+    );
+
+    let runtime =
+        WasmRuntime::new_with_cache(&bundle.wasm, namespace, config.wasm_max_memory, Some(cache))?
+            .with_data(bundle.data)
+            .with_pool_size(config.wasm_pool_size);
+
+    Ok(CompiledTier {
+        module: bundle.wasm,
+        runtime,
+        excluded,
+    })
+}
@@ -0,0 +1,223 @@
+//! Message catalog - replace user-facing reasons by rule_id before synthesis
+//!
+//! Lets an organization reword or translate the messages of any policy,
+//! builtins included, without editing its Rego:
+//!
+//! ```yaml
+//! messages:
+//!   locale: de                  # CUPCAKE_LOCALE takes precedence
+//!   catalog:
+//!     de:
+//!       GIT-001: "Pre-commit-Hooks dürfen nicht übersprungen werden"
+//!   overrides:
+//!     GIT-001: "Hooks are mandatory - see https://wiki.example.com/hooks ({{reason}})"
+//! ```
+//!
+//! A halt, deny, block or ask decision takes its message from the catalog of
+//! the active locale, then from `overrides`, and otherwise keeps the policy's
+//! reason. Messages may use `{{reason}}` for the original reason, plus the
+//! `{{signal.name}}` and `{{event.field}}` placeholders of `add_context`.
+//! Every tier applies only the messages from its own rulebook.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use tracing::debug;
+
+use super::decision::{DecisionObject, DecisionSet};
+use super::synthesis::templating;
+
+/// Environment variable selecting the catalog locale, overriding `messages.locale`
+pub const LOCALE_ENV: &str = "CUPCAKE_LOCALE";
+
+/// The `messages:` rulebook section
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MessageConfig {
+    /// Locale whose catalog applies, e.g. `de` or `pt-BR`
+    #[serde(default)]
+    pub locale: Option<String>,
+
+    /// Messages keyed by locale, then rule_id
+    #[serde(default)]
+    pub catalog: HashMap<String, HashMap<String, String>>,
+
+    /// Messages keyed by rule_id, used in every locale without a catalog entry
+    #[serde(default)]
+    pub overrides: HashMap<String, String>,
+}
+
+impl MessageConfig {
+    fn is_empty(&self) -> bool {
+        self.catalog.is_empty() && self.overrides.is_empty()
+    }
+
+    /// The active locale's catalog; `de-AT` falls back to `de`
+    fn active_catalog(&self) -> Option<&HashMap<String, String>> {
+        let locale = std::env::var(LOCALE_ENV)
+            .ok()
+            .filter(|locale| !locale.trim().is_empty())
+            .or_else(|| self.locale.clone())?;
+        let locale = locale.trim();
+        let language = locale.split(['-', '_']).next().unwrap_or(locale);
+
+        let find = |candidate: &str| {
+            self.catalog
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(candidate))
+                .map(|(_, messages)| messages)
+        };
+        find(locale).or_else(|| find(language))
+    }
+}
+
+/// Check the section for mistakes the deserializer can't catch
+pub fn validate(config: &MessageConfig) -> Vec<String> {
+    let mut errors = Vec::new();
+    for (locale, messages) in &config.catalog {
+        if locale.trim().is_empty() {
+            errors.push("messages.catalog: locale names must not be empty".to_string());
+        }
+        for (rule_id, message) in messages {
+            if message.trim().is_empty() {
+                errors.push(format!(
+                    "messages.catalog.{locale}.{rule_id}: message is empty"
+                ));
+            }
+        }
+    }
+    for (rule_id, message) in &config.overrides {
+        if message.trim().is_empty() {
+            errors.push(format!("messages.overrides.{rule_id}: message is empty"));
+        }
+    }
+    errors
+}
+
+/// Replace the reasons of halt, deny, block and ask decisions in place
+///
+/// `input` is the enriched input the policies were evaluated with, for placeholders.
+pub fn apply(config: &MessageConfig, decision_set: &mut DecisionSet, input: &Value) {
+    if config.is_empty() {
+        return;
+    }
+    let catalog = config.active_catalog();
+
+    for decision in decision_set
+        .halts
+        .iter_mut()
+        .chain(decision_set.denials.iter_mut())
+        .chain(decision_set.blocks.iter_mut())
+        .chain(decision_set.asks.iter_mut())
+    {
+        let message = catalog
+            .and_then(|messages| messages.get(&decision.rule_id))
+            .or_else(|| config.overrides.get(&decision.rule_id));
+        if let Some(message) = message {
+            debug!("Message catalog: replacing reason of {}", decision.rule_id);
+            decision.reason = render(message, decision, input);
+        }
+    }
+}
+
+fn render(message: &str, decision: &DecisionObject, input: &Value) -> String {
+    templating::render(message, input).replace("{{reason}}", &decision.reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn decision(rule_id: &str, reason: &str) -> DecisionObject {
+        DecisionObject {
+            reason: reason.to_string(),
+            severity: "HIGH".to_string(),
+            rule_id: rule_id.to_string(),
+            agent_context: None,
+            docs_url: None,
+            remediation: None,
+        }
+    }
+
+    fn config(yaml: &str) -> MessageConfig {
+        serde_yaml_ng::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_catalog_then_overrides_then_original() {
+        let config = config(
+            r#"
+locale: de-AT
+catalog:
+  de:
+    GIT-001: "Hooks dürfen nicht übersprungen werden"
+overrides:
+  GIT-001: "Hooks are mandatory"
+  RM-001: "Blocked {{event.tool_input.command}}: {{reason}}"
+"#,
+        );
+        let mut decision_set = DecisionSet {
+            denials: vec![
+                decision("GIT-001", "Cannot bypass pre-commit hooks"),
+                decision("RM-001", "No recursive deletes"),
+            ],
+            asks: vec![decision("PUSH-001", "Push to remote?")],
+            ..Default::default()
+        };
+        let input = json!({"tool_input": {"command": "rm -rf /"}});
+
+        apply(&config, &mut decision_set, &input);
+
+        assert_eq!(
+            decision_set.denials[0].reason,
+            "Hooks dürfen nicht übersprungen werden"
+        );
+        assert_eq!(
+            decision_set.denials[1].reason,
+            "Blocked rm -rf /: No recursive deletes"
+        );
+        assert_eq!(decision_set.asks[0].reason, "Push to remote?");
+    }
+
+    #[test]
+    fn test_unknown_locale_uses_overrides() {
+        let config = config(
+            r#"
+locale: fr
+catalog:
+  de:
+    GIT-001: "Nein"
+overrides:
+  GIT-001: "Hooks are mandatory"
+"#,
+        );
+        let mut decision_set = DecisionSet {
+            halts: vec![decision("GIT-001", "original")],
+            ..Default::default()
+        };
+
+        apply(&config, &mut decision_set, &json!({}));
+        assert_eq!(decision_set.halts[0].reason, "Hooks are mandatory");
+    }
+
+    #[test]
+    fn test_validate_rejects_empty_messages() {
+        let config = config(
+            r#"
+catalog:
+  de:
+    GIT-001: ""
+overrides:
+  RM-001: "  "
+"#,
+        );
+        let errors = validate(&config);
+        assert_eq!(errors.len(), 2);
+        assert!(errors
+            .iter()
+            .any(|e| e.contains("messages.catalog.de.GIT-001")));
+        assert!(errors
+            .iter()
+            .any(|e| e.contains("messages.overrides.RM-001")));
+    }
+}
@@ -22,6 +22,10 @@ pub struct RoutingDirective {
     /// External signals required by this policy
     #[serde(default)]
     pub required_signals: Vec<String>,
+
+    /// Whether this policy reads a watchdog verdict (`input.watchdog.verdict`)
+    #[serde(default)]
+    pub required_watchdog: bool,
 }
 
 /// Complete metadata block structure
@@ -135,6 +139,9 @@ pub fn validate_routing_directive(directive: &RoutingDirective, package_name: &s
         if !directive.required_signals.is_empty() {
             return Err(anyhow!("System policy '{}' should not specify required_signals - system policies work with pre-aggregated data", package_name));
         }
+        if directive.required_watchdog {
+            return Err(anyhow!("System policy '{}' should not specify required_watchdog - system policies work with pre-aggregated data", package_name));
+        }
         debug!(
             "System policy {} has valid empty routing directive",
             package_name
@@ -162,6 +169,7 @@ pub fn validate_routing_directive(directive: &RoutingDirective, package_name: &s
         "Notification",
         "PreCompact",
         "SessionStart",
+        "SessionEnd",
         // Cursor events
         "beforeShellExecution",
         "beforeMCPExecution",
@@ -218,6 +226,9 @@ pub struct PolicyUnit {
 
     /// Complete metadata for this policy (optional)
     pub metadata: Option<PolicyMetadata>,
+
+    /// Source rendered for the engine's harness, when the file is a template
+    pub rendered: Option<String>,
 }
 
 #[cfg(test)]
@@ -294,6 +305,7 @@ deny if {
             required_events: vec!["PreToolUse".to_string()],
             required_tools: vec!["Bash".to_string()],
             required_signals: vec![],
+            required_watchdog: false,
         };
         assert!(validate_routing_directive(&valid_regular, "cupcake.policies.bash_guard").is_ok());
 
@@ -302,6 +314,7 @@ deny if {
             required_events: vec![], // Empty events but has tools - should fail for regular policies
             required_tools: vec!["Bash".to_string()],
             required_signals: vec![],
+            required_watchdog: false,
         };
         assert!(
             validate_routing_directive(&invalid_regular, "cupcake.policies.bash_guard").is_err()
@@ -312,6 +325,7 @@ deny if {
             required_events: vec![],
             required_tools: vec![],
             required_signals: vec![],
+            required_watchdog: false,
         };
         assert!(validate_routing_directive(&valid_system, "cupcake.system.evaluate").is_ok());
 
@@ -320,6 +334,7 @@ deny if {
             required_events: vec!["PreToolUse".to_string()],
             required_tools: vec![],
             required_signals: vec![],
+            required_watchdog: false,
         };
         assert!(validate_routing_directive(&invalid_system, "cupcake.system.evaluate").is_err());
 
@@ -328,6 +343,7 @@ deny if {
             required_events: vec![],
             required_tools: vec![],
             required_signals: vec!["global_setting".to_string()],
+            required_watchdog: false,
         };
         assert!(
             validate_routing_directive(&valid_empty_regular, "cupcake.policies.global_rules")
@@ -6,7 +6,8 @@
 use anyhow::{Context, Result};
 use serde_json::Value;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use tracing::{debug, error, info, instrument, trace, warn};
 
@@ -14,27 +15,47 @@ use crate::debug::SignalTelemetry;
 use crate::telemetry::TelemetryContext;
 
 // Core engine modules - discovery and compilation
+pub mod command;
 pub mod compiler;
 pub mod config;
 pub mod executor;
+pub mod init_report;
 pub mod metadata;
+pub mod namespaces;
 pub mod scanner;
+pub mod template;
 
 // Routing system
 pub mod routing;
 pub mod routing_debug;
+pub mod routing_diff;
+pub mod routing_graph;
 
 // Policy evaluation
 pub mod decision;
+pub mod lazy_runtime;
+pub mod module_cache;
 pub mod synthesis;
 pub mod wasm_runtime;
 
 // Configuration and extensions
+pub mod approvals;
 pub mod builtins;
+pub mod external_data;
 pub mod global_config;
+pub mod identity;
+pub mod messages;
+pub mod native_signals;
+pub mod policy_watch;
+pub mod rate_limits;
 pub mod rulebook;
+pub mod sandbox;
+pub mod severity_mapping;
+pub mod team_config;
+pub mod waivers;
 
 // Diagnostics and debugging
+pub mod profile;
 pub mod trace;
 
 // Re-export types for public API
@@ -65,15 +86,19 @@ pub struct Engine {
     /// Optional rulebook for signals
     rulebook: Option<rulebook::Rulebook>,
 
+    /// Exceptions to project rules from .cupcake/waivers.yml
+    waivers: waivers::Waivers,
+
+    /// Nested rulebooks (monorepo subpackages), merged over the root rulebook.
+    /// Keyed by nested `.cupcake/` directory and loaded lazily on first use.
+    nested_rulebooks: Mutex<HashMap<PathBuf, Arc<rulebook::Rulebook>>>,
+
     // Global configuration support (optional - may not exist)
     /// Global policies routing map
     global_routing_map: HashMap<String, Vec<PolicyUnit>>,
 
-    /// Global WASM module (compiled separately)
-    global_wasm_module: Option<Vec<u8>>,
-
-    /// Global WASM runtime instance
-    global_wasm_runtime: Option<wasm_runtime::WasmRuntime>,
+    /// Global WASM runtime, compiled on the first matching event
+    global_wasm: Option<lazy_runtime::LazyRuntime>,
 
     /// List of global policies
     global_policies: Vec<PolicyUnit>,
@@ -81,8 +106,39 @@ pub struct Engine {
     /// Optional global rulebook
     global_rulebook: Option<rulebook::Rulebook>,
 
+    /// Exceptions to global rules, from every global layer
+    global_waivers: waivers::Waivers,
+
+    // Team configuration support (optional - evaluated between global and project)
+    /// Team policies routing map
+    team_routing_map: HashMap<String, Vec<PolicyUnit>>,
+
+    /// Team WASM runtime, compiled on the first matching event
+    team_wasm: Option<lazy_runtime::LazyRuntime>,
+
+    /// List of team policies
+    team_policies: Vec<PolicyUnit>,
+
+    /// Optional team rulebook
+    team_rulebook: Option<rulebook::Rulebook>,
+
+    /// Exceptions to team rules
+    team_waivers: waivers::Waivers,
+
     /// Watchdog LLM-as-judge instance (optional)
     watchdog: Option<crate::watchdog::Watchdog>,
+
+    /// External data sources exposed to project policies as `data.external`
+    external_data: Option<external_data::ExternalData>,
+
+    /// Per-session invocation counters for the rate_limit builtin
+    rate_limiter: rate_limits::RateLimiter,
+
+    /// Precompiled WASM modules persisted between runs
+    module_cache: module_cache::ModuleCache,
+
+    /// What initialization found and did, tier by tier
+    init_report: init_report::InitReport,
 }
 
 impl Engine {
@@ -103,7 +159,11 @@ impl Engine {
         project_path: impl AsRef<Path>,
         config: EngineConfig,
     ) -> Result<Self> {
-        let paths = ProjectPaths::resolve_with_config(project_path, config.global_config.clone())?;
+        let paths = ProjectPaths::resolve_with_tiers(
+            project_path,
+            config.global_config.clone(),
+            config.team_config.clone(),
+        )?;
 
         info!("Initializing Cupcake Engine");
         info!("Project root: {:?}", paths.root);
@@ -115,17 +175,25 @@ impl Engine {
                 config.wasm_max_memory.unwrap()
             );
         }
+        if config.wasm_pool_size.is_some() {
+            info!("WASM pool size override: {:?}", config.wasm_pool_size);
+        }
         if config.opa_path.is_some() {
             info!("OPA path override: {:?}", config.opa_path);
         }
         if config.global_config.is_some() {
             info!("Global config override: {:?}", config.global_config);
         }
+        if config.team_config.is_some() {
+            info!("Team config override: {:?}", config.team_config);
+        }
         if config.debug_routing {
             info!("Routing debug output enabled");
         }
 
         // Create engine instance with both project and global support
+        let rate_limiter = rate_limits::RateLimiter::new(&paths.cupcake_dir);
+        let module_cache = module_cache::ModuleCache::new(&paths.root);
         let mut engine = Self {
             paths,
             config,
@@ -134,18 +202,32 @@ impl Engine {
             wasm_runtime: None,
             policies: Vec::new(),
             rulebook: None,
+            waivers: waivers::Waivers::default(),
+            nested_rulebooks: Mutex::new(HashMap::new()),
             // Initialize global fields (will be populated if global config exists)
             global_routing_map: HashMap::new(),
-            global_wasm_module: None,
-            global_wasm_runtime: None,
+            global_wasm: None,
             global_policies: Vec::new(),
             global_rulebook: None,
+            global_waivers: waivers::Waivers::default(),
+            // Initialize team fields (will be populated if team config exists)
+            team_routing_map: HashMap::new(),
+            team_wasm: None,
+            team_policies: Vec::new(),
+            team_rulebook: None,
+            team_waivers: waivers::Waivers::default(),
             // Watchdog initialized later from rulebook config
             watchdog: None,
+            external_data: None,
+            rate_limiter,
+            module_cache,
+            init_report: init_report::InitReport::default(),
         };
 
         // Initialize the engine (scan, parse, compile)
+        let started = Instant::now();
         engine.initialize().await?;
+        engine.init_report.total_ms = init_report::millis(started.elapsed());
 
         Ok(engine)
     }
@@ -160,12 +242,21 @@ impl Engine {
             self.initialize_global().await?;
         }
 
+        // Step 0A2: Initialize team configuration (between global and project)
+        if self.paths.team_root.is_some() {
+            info!("Team configuration detected - initializing team policies");
+            self.initialize_team().await?;
+        }
+
         // Step 0B: Load project rulebook to get builtin configuration
+        let started = Instant::now();
         self.rulebook = Some(
             rulebook::Rulebook::load_with_conventions(&self.paths.rulebook, &self.paths.signals)
                 .await?,
         );
         info!("Project rulebook loaded with convention-based discovery");
+        self.waivers =
+            waivers::Waivers::load(&[self.paths.cupcake_dir.join(waivers::WAIVERS_FILE)])?;
 
         // Step 0C: Initialize Watchdog if enabled in rulebook
         // Watchdog uses directory-based configuration from .cupcake/watchdog/
@@ -204,6 +295,12 @@ impl Engine {
             }
         }
 
+        // Step 0D: Register external data sources (fetched lazily on first evaluation)
+        if let Some(ref rulebook) = self.rulebook {
+            self.external_data =
+                external_data::ExternalData::new(&rulebook.external_data, &self.paths.cupcake_dir)?;
+        }
+
         // Get list of enabled builtins for filtering
         let enabled_builtins = self
             .rulebook
@@ -221,6 +318,9 @@ impl Engine {
             crate::harness::types::HarnessType::Cursor => "cursor",
             crate::harness::types::HarnessType::Factory => "factory",
             crate::harness::types::HarnessType::OpenCode => "opencode",
+            crate::harness::types::HarnessType::Cline => "cline",
+            crate::harness::types::HarnessType::Aider => "aider",
+            crate::harness::types::HarnessType::Goose => "goose",
         };
         let harness_policies_dir = self.paths.policies.join(harness_subdir);
         info!(
@@ -252,6 +352,10 @@ impl Engine {
             Vec::new()
         };
         info!("Found {} system policy files", system_files.len());
+        let signals = Self::signal_names(self.rulebook.as_ref());
+        let report = self.init_report.tier_mut(namespaces::Tier::Project);
+        report.policies_discovered = policy_files.len() + system_files.len();
+        report.signals = signals;
 
         // Step 2: Parse selectors and build policy units from harness policies
         for path in policy_files {
@@ -263,9 +367,18 @@ impl Engine {
                     );
                     self.policies.push(unit);
                 }
+                Err(e)
+                    if Self::on_policy_error(self.rulebook.as_ref())
+                        == rulebook::OnPolicyError::Fail =>
+                {
+                    return Err(e.context(format!("Failed to parse policy at {path:?}")));
+                }
                 Err(e) => {
                     // Fail loudly but don't crash - log and skip bad policies
                     error!("Failed to parse policy at {:?}: {}", path, e);
+                    self.init_report
+                        .tier_mut(namespaces::Tier::Project)
+                        .skip(&path, format!("{e:#}"));
                 }
             }
         }
@@ -280,12 +393,26 @@ impl Engine {
                     );
                     self.policies.push(unit);
                 }
+                Err(e)
+                    if Self::on_policy_error(self.rulebook.as_ref())
+                        == rulebook::OnPolicyError::Fail =>
+                {
+                    return Err(e.context(format!("Failed to parse system policy at {path:?}")));
+                }
                 Err(e) => {
                     error!("Failed to parse system policy at {:?}: {}", path, e);
+                    self.init_report
+                        .tier_mut(namespaces::Tier::Project)
+                        .skip(&path, format!("{e:#}"));
                 }
             }
         }
 
+        Self::check_package_conflicts(namespaces::Tier::Project, &self.policies)?;
+        let report = self.init_report.tier_mut(namespaces::Tier::Project);
+        report.policies_loaded = Self::package_names(&self.policies);
+        report.load_ms = init_report::millis(started.elapsed());
+
         if self.policies.is_empty() {
             warn!("No valid policies found in directory");
             return Ok(());
@@ -322,25 +449,57 @@ impl Engine {
 
         // Step 4: Compile unified WASM module with OPA path from CLI
         // Pass cupcake_dir for helpers resolution at root level
-        let wasm_bytes = compiler::compile_policies_with_namespace(
-            &self.policies,
-            "cupcake.system",
-            self.config.opa_path.clone(),
-            Some(&self.paths.cupcake_dir),
-        )
-        .await?;
+        let compile_started = Instant::now();
+        let bundle = match Self::on_policy_error(self.rulebook.as_ref()) {
+            rulebook::OnPolicyError::Fail => {
+                compiler::compile_bundle_with_namespace(
+                    &self.policies,
+                    "cupcake.system",
+                    self.config.opa_path.clone(),
+                    Some(&self.paths.cupcake_dir),
+                )
+                .await?
+            }
+            rulebook::OnPolicyError::Skip => {
+                let (bundle, excluded) = compiler::compile_bundle_excluding_broken(
+                    &self.policies,
+                    "cupcake.system",
+                    self.config.opa_path.clone(),
+                    Some(&self.paths.cupcake_dir),
+                )
+                .await?;
+                if !excluded.is_empty() {
+                    self.policies
+                        .retain(|p| !excluded.iter().any(|e| e.path == p.path));
+                    self.build_routing_map();
+                    let report = self.init_report.tier_mut(namespaces::Tier::Project);
+                    report.policies_loaded = Self::package_names(&self.policies);
+                    report.skipped.extend(excluded);
+                }
+                bundle
+            }
+        };
+        let wasm_bytes = bundle.wasm;
         info!(
             "Successfully compiled unified WASM module ({} bytes)",
             wasm_bytes.len()
         );
         self.wasm_module = Some(wasm_bytes.clone());
+        let report = self.init_report.tier_mut(namespaces::Tier::Project);
+        report.compile_ms = Some(init_report::millis(compile_started.elapsed()));
+        report.wasm_bytes = Some(wasm_bytes.len());
 
         // Step 5: Initialize WASM runtime with memory config from CLI
-        self.wasm_runtime = Some(wasm_runtime::WasmRuntime::new_with_config(
-            &wasm_bytes,
-            "cupcake.system",
-            self.config.wasm_max_memory,
-        )?);
+        self.wasm_runtime = Some(
+            wasm_runtime::WasmRuntime::new_with_cache(
+                &wasm_bytes,
+                "cupcake.system",
+                self.config.wasm_max_memory,
+                Some(&self.module_cache),
+            )?
+            .with_data(bundle.data)
+            .with_pool_size(self.config.wasm_pool_size),
+        );
         info!("WASM runtime initialized");
 
         // Step 6: Dump routing diagnostics if debug mode enabled via CLI flag
@@ -361,28 +520,36 @@ impl Engine {
         info!("Initializing global configuration...");
 
         // Verify we have global paths
-        let global_policies_path = self
-            .paths
-            .global_policies
-            .as_ref()
-            .context("Global policies path not set")?;
-        let global_rulebook_path = self
-            .paths
-            .global_rulebook
-            .as_ref()
-            .context("Global rulebook path not set")?;
+        let started = Instant::now();
+        let layers = self.paths.global_layers.clone();
+        if layers.is_empty() {
+            anyhow::bail!("Global config layers not set");
+        }
 
-        // Load global rulebook
-        if global_rulebook_path.exists() {
+        // Load global rulebooks - layers are listed highest precedence first,
+        // layered loading expects the lowest first
+        let rulebook_paths: Vec<PathBuf> = layers
+            .iter()
+            .rev()
+            .map(|root| root.join("rulebook.yml"))
+            .collect();
+        if rulebook_paths.iter().any(|path| path.exists()) {
+            let signals_dirs: Vec<PathBuf> = layers
+                .iter()
+                .rev()
+                .map(|root| root.join("signals"))
+                .collect();
             self.global_rulebook = Some(
-                rulebook::Rulebook::load_with_conventions(
-                    global_rulebook_path,
-                    self.paths.global_signals.as_ref().unwrap(),
-                )
-                .await?,
+                rulebook::Rulebook::load_layered_with_conventions(&rulebook_paths, &signals_dirs)
+                    .await?,
             );
-            info!("Global rulebook loaded");
+            info!("Global rulebook loaded from {} layer(s)", layers.len());
         }
+        let waiver_files: Vec<PathBuf> = layers
+            .iter()
+            .map(|root| root.join(waivers::WAIVERS_FILE))
+            .collect();
+        self.global_waivers = waivers::Waivers::load(&waiver_files)?;
 
         // Get global enabled builtins
         let global_enabled_builtins = self
@@ -397,11 +564,21 @@ impl Engine {
             crate::harness::types::HarnessType::Cursor => "cursor",
             crate::harness::types::HarnessType::Factory => "factory",
             crate::harness::types::HarnessType::OpenCode => "opencode",
+            crate::harness::types::HarnessType::Cline => "cline",
+            crate::harness::types::HarnessType::Aider => "aider",
+            crate::harness::types::HarnessType::Goose => "goose",
         };
-        let harness_global_policies_dir = global_policies_path.join(harness_subdir);
 
-        // Scan for global policies in harness-specific directory
-        if harness_global_policies_dir.exists() {
+        // Scan for global policies in each layer's harness-specific directory.
+        // A policy file already provided by a higher-precedence layer shadows
+        // the same file further down.
+        let mut seen_files: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
+        for layer in &layers {
+            let layer_policies_dir = layer.join("policies");
+            let harness_global_policies_dir = layer_policies_dir.join(harness_subdir);
+            if !harness_global_policies_dir.exists() {
+                continue;
+            }
             info!(
                 "Scanning global harness-specific policies for: {:?} at {:?}",
                 self.config.harness, harness_global_policies_dir
@@ -416,9 +593,26 @@ impl Engine {
                 global_policy_files.len(),
                 harness_subdir
             );
+            self.init_report
+                .tier_mut(namespaces::Tier::Global)
+                .policies_discovered += global_policy_files.len();
 
             // Parse global policies
             for path in global_policy_files {
+                let relative = path
+                    .strip_prefix(&layer_policies_dir)
+                    .unwrap_or(&path)
+                    .to_path_buf();
+                if !seen_files.insert(relative) {
+                    info!(
+                        "Skipping global policy {:?} - shadowed by a higher-precedence layer",
+                        path
+                    );
+                    self.init_report
+                        .tier_mut(namespaces::Tier::Global)
+                        .skip(&path, "shadowed by a higher-precedence global layer");
+                    continue;
+                }
                 match self.parse_policy(&path).await {
                     Ok(mut unit) => {
                         // Transform package name to global namespace
@@ -435,70 +629,267 @@ impl Engine {
                         );
                         self.global_policies.push(unit);
                     }
+                    Err(e)
+                        if Self::on_policy_error(self.global_rulebook.as_ref())
+                            == rulebook::OnPolicyError::Fail =>
+                    {
+                        return Err(e.context(format!("Failed to parse global policy at {path:?}")));
+                    }
                     Err(e) => {
                         error!("Failed to parse global policy at {:?}: {}", path, e);
+                        self.init_report
+                            .tier_mut(namespaces::Tier::Global)
+                            .skip(&path, format!("{e:#}"));
                     }
                 }
             }
+        }
 
-            if !self.global_policies.is_empty() {
-                // Check if we have non-system policies (OPA panics with only system policies)
+        Self::check_package_conflicts(namespaces::Tier::Global, &self.global_policies)?;
+        let signals = Self::signal_names(self.global_rulebook.as_ref());
+        let report = self.init_report.tier_mut(namespaces::Tier::Global);
+        report.policies_loaded = Self::package_names(&self.global_policies);
+        report.signals = signals;
+        report.load_ms = init_report::millis(started.elapsed());
+
+        if !self.global_policies.is_empty() {
+            // Check if we have non-system policies (OPA panics with only system policies)
+            info!(
+                "Global policies found: {:?}",
+                self.global_policies
+                    .iter()
+                    .map(|p| &p.package_name)
+                    .collect::<Vec<_>>()
+            );
+            let non_system_count = self
+                .global_policies
+                .iter()
+                .filter(|p| !p.package_name.ends_with(".system"))
+                .count();
+
+            if non_system_count > 0 {
+                // Build global routing map
+                self.build_global_routing_map();
                 info!(
-                    "Global policies found: {:?}",
-                    self.global_policies
-                        .iter()
-                        .map(|p| &p.package_name)
-                        .collect::<Vec<_>>()
+                    "Built global routing map with {} entries",
+                    self.global_routing_map.len()
                 );
-                let non_system_count = self
-                    .global_policies
-                    .iter()
-                    .filter(|p| !p.package_name.ends_with(".system"))
-                    .count();
 
-                if non_system_count > 0 {
-                    // Build global routing map
-                    self.build_global_routing_map();
-                    info!(
-                        "Built global routing map with {} entries",
-                        self.global_routing_map.len()
-                    );
+                info!(
+                    "Found {} global policies ({} non-system) - compiling global WASM in the background",
+                    self.global_policies.len(),
+                    non_system_count
+                );
 
-                    info!(
-                        "Found {} global policies ({} non-system) - compiling global WASM module",
-                        self.global_policies.len(),
-                        non_system_count
-                    );
+                // Global policies are compiled off the init path; global_root resolves helpers
+                let mut global_wasm = lazy_runtime::LazyRuntime::new(
+                    "cupcake.global.system",
+                    self.paths.global_root.clone(),
+                    Self::on_policy_error(self.global_rulebook.as_ref()),
+                );
+                global_wasm.compile_in_background(
+                    self.global_policies.clone(),
+                    self.config.clone(),
+                    self.module_cache.clone(),
+                );
+                self.global_wasm = Some(global_wasm);
+            } else {
+                info!("Only system policies found in global config - skipping global WASM compilation");
+            }
+        }
+
+        info!("Global configuration initialization complete");
+        Ok(())
+    }
+
+    /// Initialize team configuration (policies, rulebook, WASM)
+    ///
+    /// The team directory uses the project `.cupcake/` layout. Policies are
+    /// relocated into the `cupcake.team.*` namespace at compile time.
+    async fn initialize_team(&mut self) -> Result<()> {
+        info!("Initializing team configuration...");
+        let started = Instant::now();
+
+        let team_root = self
+            .paths
+            .team_root
+            .clone()
+            .context("Team root path not set")?;
+        let team_policies_path = self
+            .paths
+            .team_policies
+            .clone()
+            .context("Team policies path not set")?;
+
+        // Load team rulebook
+        let team_rulebook_path = self
+            .paths
+            .team_rulebook
+            .clone()
+            .context("Team rulebook path not set")?;
+        if team_rulebook_path.exists() {
+            self.team_rulebook = Some(
+                rulebook::Rulebook::load_with_conventions(
+                    &team_rulebook_path,
+                    self.paths.team_signals.as_ref().unwrap(),
+                )
+                .await?,
+            );
+            info!("Team rulebook loaded");
+        }
+        self.team_waivers = waivers::Waivers::load(&[team_root.join(waivers::WAIVERS_FILE)])?;
 
-                    // Compile global policies to WASM with OPA path from CLI
-                    // Pass global_root for helpers resolution at root level
-                    let global_wasm_bytes = compiler::compile_policies_with_namespace(
-                        &self.global_policies,
-                        "cupcake.global.system",
-                        self.config.opa_path.clone(),
-                        self.paths.global_root.as_deref(),
-                    )
-                    .await?;
+        let team_enabled_builtins = self
+            .team_rulebook
+            .as_ref()
+            .map(|t| t.builtins.enabled_builtins())
+            .unwrap_or_default();
+
+        let harness_team_policies_dir = team_policies_path.join(self.config.harness.policy_dir());
+
+        let mut policy_files = if harness_team_policies_dir.exists() {
+            scanner::scan_policies_with_filter(&harness_team_policies_dir, &team_enabled_builtins)
+                .await?
+        } else {
+            debug!(
+                "No team policies directory at {:?}",
+                harness_team_policies_dir
+            );
+            Vec::new()
+        };
+
+        let team_system_dir = team_root.join("system");
+        if team_system_dir.is_dir() {
+            policy_files.extend(scanner::scan_policies(&team_system_dir).await?);
+        }
+
+        self.init_report
+            .tier_mut(namespaces::Tier::Team)
+            .policies_discovered = policy_files.len();
+        for path in policy_files {
+            match self.parse_policy(&path).await {
+                Ok(mut unit) => {
+                    if !unit.package_name.starts_with("cupcake.team.") {
+                        unit.package_name = unit
+                            .package_name
+                            .replace("cupcake.policies", "cupcake.team.policies")
+                            .replace("cupcake.system", "cupcake.team.system");
+                    }
                     info!(
-                        "Successfully compiled global WASM module ({} bytes)",
-                        global_wasm_bytes.len()
+                        "Successfully parsed team policy: {} from {:?}",
+                        unit.package_name, path
                     );
-                    self.global_wasm_module = Some(global_wasm_bytes.clone());
-
-                    // Initialize global WASM runtime with global namespace and memory config
-                    self.global_wasm_runtime = Some(wasm_runtime::WasmRuntime::new_with_config(
-                        &global_wasm_bytes,
-                        "cupcake.global.system",
-                        self.config.wasm_max_memory,
-                    )?);
-                    info!("Global WASM runtime initialized with namespace: cupcake.global.system");
-                } else {
-                    info!("Only system policies found in global config - skipping global WASM compilation");
+                    self.team_policies.push(unit);
+                }
+                Err(e)
+                    if Self::on_policy_error(self.team_rulebook.as_ref())
+                        == rulebook::OnPolicyError::Fail =>
+                {
+                    return Err(e.context(format!("Failed to parse team policy at {path:?}")));
+                }
+                Err(e) => {
+                    error!("Failed to parse team policy at {:?}: {}", path, e);
+                    self.init_report
+                        .tier_mut(namespaces::Tier::Team)
+                        .skip(&path, format!("{e:#}"));
                 }
             }
         }
 
-        info!("Global configuration initialization complete");
+        Self::check_package_conflicts(namespaces::Tier::Team, &self.team_policies)?;
+        let signals = Self::signal_names(self.team_rulebook.as_ref());
+        let report = self.init_report.tier_mut(namespaces::Tier::Team);
+        report.policies_loaded = Self::package_names(&self.team_policies);
+        report.signals = signals;
+        report.load_ms = init_report::millis(started.elapsed());
+
+        let non_system_count = self
+            .team_policies
+            .iter()
+            .filter(|p| !p.package_name.starts_with("cupcake.team.system"))
+            .count();
+
+        if non_system_count == 0 {
+            info!("No team policies found (only system policies) - skipping team WASM compilation");
+            return Ok(());
+        }
+
+        Self::build_routing_map_generic(&self.team_policies, &mut self.team_routing_map, "team");
+
+        info!("Compiling team WASM in the background");
+        let mut team_wasm = lazy_runtime::LazyRuntime::new(
+            "cupcake.team.system",
+            Some(team_root),
+            Self::on_policy_error(self.team_rulebook.as_ref()),
+        );
+        team_wasm.compile_in_background(
+            self.team_policies.clone(),
+            self.config.clone(),
+            self.module_cache.clone(),
+        );
+        self.team_wasm = Some(team_wasm);
+
+        Ok(())
+    }
+
+    /// A tier's `on_policy_error` setting (`skip` without a rulebook)
+    fn on_policy_error(rulebook: Option<&rulebook::Rulebook>) -> rulebook::OnPolicyError {
+        rulebook.map(|r| r.on_policy_error).unwrap_or_default()
+    }
+
+    /// Policies excluded because they failed to parse or compile, one line
+    /// each, across every tier loaded so far
+    pub fn policy_load_errors(&self) -> Vec<String> {
+        let lazy_tiers = [
+            (namespaces::Tier::Global, &self.global_wasm),
+            (namespaces::Tier::Team, &self.team_wasm),
+        ];
+        let lazy = lazy_tiers.into_iter().flat_map(|(tier, runtime)| {
+            runtime
+                .iter()
+                .flat_map(|runtime| runtime.excluded())
+                .map(move |skipped| (tier, skipped))
+        });
+        self.init_report
+            .tiers
+            .iter()
+            .flat_map(|report| report.skipped.iter().map(|s| (report.tier, s)))
+            .filter(|(_, skipped)| !skipped.reason.starts_with("shadowed"))
+            .chain(lazy)
+            .map(|(tier, skipped)| {
+                format!(
+                    "{tier} policy {} excluded: {}",
+                    skipped.path.display(),
+                    skipped.reason
+                )
+            })
+            .collect()
+    }
+
+    /// Package names of loaded policies, for the init report
+    fn package_names(policies: &[PolicyUnit]) -> Vec<String> {
+        policies.iter().map(|p| p.package_name.clone()).collect()
+    }
+
+    /// Sorted signal names of a tier's rulebook, for the init report
+    fn signal_names(rulebook: Option<&rulebook::Rulebook>) -> Vec<String> {
+        let mut names: Vec<String> = rulebook
+            .map(|r| r.signals.keys().cloned().collect())
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+
+    /// Fail loading a tier whose policies would collide in OPA's package namespace
+    fn check_package_conflicts(tier: namespaces::Tier, policies: &[PolicyUnit]) -> Result<()> {
+        let conflicts = namespaces::package_conflicts(tier, policies);
+        if !conflicts.is_empty() {
+            anyhow::bail!(
+                "Policy package conflicts in {} configuration:\n{}",
+                tier,
+                conflicts.join("\n")
+            );
+        }
         Ok(())
     }
 
@@ -517,13 +908,28 @@ impl Engine {
             .await
             .context("Failed to read policy file")?;
 
+        // Templates are rendered before anything reads the metadata, so routing
+        // sees this harness's event names
+        let rendered = if template::is_template(&content) {
+            let rendered = template::render(&content, self.config.harness)
+                .with_context(|| format!("Failed to render policy template {path:?}"))?;
+            debug!(
+                "Rendered policy template {:?} for {}",
+                path, self.config.harness
+            );
+            Some(rendered)
+        } else {
+            None
+        };
+        let content = rendered.as_deref().unwrap_or(&content);
+
         // Extract package name
         let package_name =
-            metadata::extract_package_name(&content).context("Failed to extract package name")?;
+            metadata::extract_package_name(content).context("Failed to extract package name")?;
 
         // Parse OPA metadata
         let policy_metadata =
-            metadata::parse_metadata(&content).context("Failed to parse OPA metadata")?;
+            metadata::parse_metadata(content).context("Failed to parse OPA metadata")?;
 
         // Extract routing directive - system policies don't need routing
         let routing = if let Some(ref meta) = policy_metadata {
@@ -536,6 +942,7 @@ impl Engine {
                 routing_directive.clone()
             } else if package_name.starts_with("cupcake.system")
                 || package_name.starts_with("cupcake.global.system")
+                || package_name.starts_with("cupcake.team.system")
             {
                 // System packages don't need routing - they're entrypoints or helpers
                 // This covers cupcake.system, cupcake.system.commands, cupcake.global.system, etc.
@@ -575,6 +982,7 @@ impl Engine {
             package_name,
             routing,
             metadata: policy_metadata,
+            rendered,
         })
     }
 
@@ -698,14 +1106,76 @@ impl Engine {
         self.wasm_module.as_deref()
     }
 
+    /// What initialization found and did: policies loaded and skipped per
+    /// tier, timings, WASM size and signals
+    pub fn init_report(&self) -> &init_report::InitReport {
+        &self.init_report
+    }
+
+    /// Whether the project rulebook enables the watchdog
+    pub fn watchdog_configured(&self) -> bool {
+        self.rulebook
+            .as_ref()
+            .is_some_and(|rulebook| rulebook.watchdog.enabled)
+    }
+
+    /// The watchdog, when it is enabled and its backend initialized
+    pub fn watchdog(&self) -> Option<&crate::watchdog::Watchdog> {
+        self.watchdog.as_ref()
+    }
+
     /// Get the global routing map (for verification/testing)
     pub fn global_routing_map(&self) -> &HashMap<String, Vec<PolicyUnit>> {
         &self.global_routing_map
     }
 
     /// Get the compiled global WASM module (for verification/testing)
-    pub fn global_wasm_module(&self) -> Option<&[u8]> {
-        self.global_wasm_module.as_deref()
+    ///
+    /// Compiles the global policies if no event has needed them yet.
+    pub async fn global_wasm_module(&self) -> Result<Option<&[u8]>> {
+        match &self.global_wasm {
+            Some(tier) => Ok(Some(
+                &tier
+                    .get(&self.global_policies, &self.config, &self.module_cache)
+                    .await?
+                    .module,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Compile the global and team tiers now instead of on first use
+    ///
+    /// `Engine::new` leaves them to a background task, so this is how callers
+    /// that must reject broken policies (e.g. `cupcake verify`) surface errors.
+    pub async fn compile_deferred_tiers(&self) -> Result<()> {
+        self.global_wasm_module()
+            .await
+            .context("Failed to compile global policies")?;
+        self.team_wasm_module()
+            .await
+            .context("Failed to compile team policies")?;
+        Ok(())
+    }
+
+    /// Get the team routing map (for verification/testing)
+    pub fn team_routing_map(&self) -> &HashMap<String, Vec<PolicyUnit>> {
+        &self.team_routing_map
+    }
+
+    /// Get the compiled team WASM module (for verification/testing)
+    ///
+    /// Compiles the team policies if no event has needed them yet.
+    pub async fn team_wasm_module(&self) -> Result<Option<&[u8]>> {
+        match &self.team_wasm {
+            Some(tier) => Ok(Some(
+                &tier
+                    .get(&self.team_policies, &self.config, &self.module_cache)
+                    .await?
+                    .module,
+            )),
+            None => Ok(None),
+        }
     }
 
     /// Get the telemetry configuration from the rulebook
@@ -713,6 +1183,94 @@ impl Engine {
         self.rulebook.as_ref().map(|rb| &rb.telemetry)
     }
 
+    /// Get the permission suggestion settings from the rulebook
+    pub fn permission_suggestions(
+        &self,
+    ) -> Option<crate::harness::permission_suggestions::SettingsDestination> {
+        self.rulebook
+            .as_ref()
+            .and_then(|rb| rb.permission_suggestions.destination())
+    }
+
+    /// Get the response passthrough rules from the rulebook
+    pub fn response_passthrough(&self) -> &[crate::harness::passthrough::PassthroughRule] {
+        self.rulebook
+            .as_ref()
+            .map(|rb| rb.response_passthrough.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Get how the rulebook says to answer a harness (JSON unless configured)
+    pub fn output_mode(
+        &self,
+        harness: crate::harness::types::HarnessType,
+    ) -> crate::harness::exit_code::OutputMode {
+        self.rulebook
+            .as_ref()
+            .and_then(|rb| rb.output_mode.get(&harness).copied())
+            .unwrap_or_default()
+    }
+
+    /// Resolve the rulebook override for the file or directory an event acts on
+    ///
+    /// Returns None when no nested `.cupcake/rulebook.yml` sits between the
+    /// target and the project root. Otherwise returns the root rulebook with the
+    /// nearest nested one merged on top (nested wins). Nested signal directories
+    /// take precedence over root ones. Policies, watchdog and telemetry are
+    /// always taken from the root configuration - so a nested rulebook can
+    /// reconfigure builtins the root enables, but a builtin enabled only in the
+    /// nested rulebook has no compiled policy and is reported with a warning.
+    async fn nested_rulebook_for(&self, input: &Value) -> Result<Option<Arc<rulebook::Rulebook>>> {
+        let Some(target) = extract_target_path(input) else {
+            return Ok(None);
+        };
+        let Some(nested_dir) = self.paths.nested_cupcake_dir_for(&target) else {
+            return Ok(None);
+        };
+
+        if let Some(cached) = self
+            .nested_rulebooks
+            .lock()
+            .expect("nested rulebook cache poisoned")
+            .get(&nested_dir)
+        {
+            return Ok(Some(Arc::clone(cached)));
+        }
+
+        info!("Loading nested rulebook from {:?}", nested_dir);
+        let merged = rulebook::Rulebook::load_layered_with_conventions(
+            &[self.paths.rulebook.clone(), nested_dir.join("rulebook.yml")],
+            &[self.paths.signals.clone(), nested_dir.join("signals")],
+        )
+        .await
+        .with_context(|| format!("Failed to load nested rulebook in {}", nested_dir.display()))?;
+
+        let root_builtins = self
+            .rulebook
+            .as_ref()
+            .map(|rb| rb.builtins.enabled_builtins())
+            .unwrap_or_default();
+        for builtin in merged.builtins.enabled_builtins() {
+            if !root_builtins.contains(&builtin) {
+                warn!(
+                    "Builtin '{}' is enabled in {} but not in the root rulebook - \
+                     builtin policies are only compiled from the root configuration, \
+                     so it will not run. Enable it in {} instead.",
+                    builtin,
+                    nested_dir.join("rulebook.yml").display(),
+                    self.paths.rulebook.display()
+                );
+            }
+        }
+
+        let merged = Arc::new(merged);
+        self.nested_rulebooks
+            .lock()
+            .expect("nested rulebook cache poisoned")
+            .insert(nested_dir, Arc::clone(&merged));
+        Ok(Some(merged))
+    }
+
     /// Find policies that match the given event criteria
     #[instrument(
         name = "route_event",
@@ -725,28 +1283,9 @@ impl Engine {
     )]
     pub fn route_event(&self, event_name: &str, tool_name: Option<&str>) -> Vec<&PolicyUnit> {
         let start = Instant::now();
-        let key = routing::create_event_key(event_name, tool_name);
-
-        // First try the specific key
-        let mut result: Vec<&PolicyUnit> = self
-            .routing_map
-            .get(&key)
-            .map(|policies| policies.iter().collect())
-            .unwrap_or_default();
-
-        // ALSO check for event-only policies when there's a tool
-        // This handles the case where ONLY wildcard policies exist (nothing to duplicate into)
-        if tool_name.is_some() {
-            let wildcard_key = event_name.to_string();
-            if let Some(wildcard_policies) = self.routing_map.get(&wildcard_key) {
-                // Only add if not already present (avoid duplicates from build-time merging)
-                for policy in wildcard_policies {
-                    if !result.iter().any(|p| p.package_name == policy.package_name) {
-                        result.push(policy);
-                    }
-                }
-            }
-        }
+        // Specific key, tool/MCP wildcards and event-only policies. Lookups are
+        // needed even with build-time merging, for tools no policy names exactly
+        let result = Self::route_in_map(&self.routing_map, event_name, tool_name);
 
         // Record matched policies
         let current_span = tracing::Span::current();
@@ -843,22 +1382,35 @@ impl Engine {
 
         info!("Evaluating event: {} tool: {:?}", event_name, tool_name);
 
+        // Policies excluded at load time leave a gap in governance - make it
+        // visible in every telemetry record, not just the startup log
+        if let Some(ref mut ctx) = telemetry {
+            for load_error in self.policy_load_errors() {
+                ctx.add_error(load_error);
+            }
+        }
+
+        // Monorepo support: a nested .cupcake/rulebook.yml closest to the target
+        // of this event is merged over the root rulebook
+        let nested_rulebook = self.nested_rulebook_for(&safe_input).await?;
+
         // Create Executor early - used for both global and project evaluation
         // The Executor handles all OS/IO interactions (signals)
         let exec = executor::Executor {
-            rulebook: self.rulebook.as_ref(),
+            rulebook: nested_rulebook.as_deref().or(self.rulebook.as_ref()),
             global_rulebook: self.global_rulebook.as_ref(),
             watchdog: self.watchdog.as_ref(),
             working_dir: &self.paths.root,
         };
 
         // PHASE 1: Evaluate global policies first (if they exist)
-        if self.global_wasm_runtime.is_some() {
+        if self.global_wasm.is_some() {
             debug!("Phase 1: Evaluating global policies");
             let capture_telemetry = telemetry.is_some();
-            let (global_decision, global_decision_set, global_signal_executions) = self
+            let global = self
                 .evaluate_global(&safe_input, event_name, tool_name, &exec, capture_telemetry)
                 .await?;
+            let global_decision = global.decision;
 
             // Record global evaluation in telemetry
             if let Some(ref mut ctx) = telemetry {
@@ -868,12 +1420,17 @@ impl Engine {
                     &self.global_routing_map.keys().cloned().collect::<Vec<_>>(),
                 );
                 // Record signal executions from global evaluation
-                for signal in global_signal_executions {
+                for signal in global.signal_executions {
                     phase.record_signal(signal);
                 }
                 phase
                     .evaluation_mut()
-                    .record_wasm_result(&global_decision_set);
+                    .record_wasm_result(&global.decision_set);
+                phase.evaluation_mut().record_identity(&global.identity);
+                phase.evaluation_mut().record_waivers(&global.waived);
+                if let Some(usage) = global.wasm_memory {
+                    phase.evaluation_mut().record_wasm_memory(usage);
+                }
                 phase
                     .evaluation_mut()
                     .record_final_decision(&global_decision);
@@ -939,6 +1496,65 @@ impl Engine {
             }
         }
 
+        // PHASE 1B: Evaluate team policies (between global and project)
+        if self.team_wasm.is_some() {
+            debug!("Phase 1B: Evaluating team policies");
+            let capture_telemetry = telemetry.is_some();
+            let team = self
+                .evaluate_team(&safe_input, event_name, tool_name, capture_telemetry)
+                .await?;
+            let team_decision = team.decision;
+
+            if let Some(ref mut ctx) = telemetry {
+                let phase = ctx.start_phase("team");
+                phase.evaluation_mut().record_routing(
+                    true,
+                    &self.team_routing_map.keys().cloned().collect::<Vec<_>>(),
+                );
+                for signal in team.signal_executions {
+                    phase.record_signal(signal);
+                }
+                phase
+                    .evaluation_mut()
+                    .record_wasm_result(&team.decision_set);
+                phase.evaluation_mut().record_identity(&team.identity);
+                phase.evaluation_mut().record_waivers(&team.waived);
+                if let Some(usage) = team.wasm_memory {
+                    phase.evaluation_mut().record_wasm_memory(usage);
+                }
+                phase.evaluation_mut().record_final_decision(&team_decision);
+            }
+
+            // Early termination on team blocking decisions (same rules as global)
+            if team_decision.is_halt() || team_decision.is_blocking() {
+                let reason = team_decision.reason().unwrap_or_default().to_string();
+                info!("Team policy {:?} - immediate termination", team_decision);
+                if let Some(ref mut ctx) = telemetry {
+                    if let Some(phase) = ctx.current_phase_mut() {
+                        phase
+                            .evaluation_mut()
+                            .record_exit(format!("Team termination: {reason}"));
+                        phase.finalize();
+                    }
+                }
+                let label = match &team_decision {
+                    decision::FinalDecision::Halt { .. } => "TeamHalt",
+                    decision::FinalDecision::Deny { .. } => "TeamDeny",
+                    _ => "TeamBlock",
+                };
+                current_span.record("final_decision", label);
+                current_span.record("duration_ms", eval_start.elapsed().as_millis());
+                return Ok(team_decision);
+            }
+
+            debug!("Team policies did not halt/deny/block - proceeding to project evaluation");
+            if let Some(ref mut ctx) = telemetry {
+                if let Some(phase) = ctx.current_phase_mut() {
+                    phase.finalize();
+                }
+            }
+        }
+
         // PHASE 2: Evaluate project policies
         debug!("Phase 2: Evaluating project policies");
 
@@ -947,6 +1563,16 @@ impl Engine {
             ctx.start_phase("project");
         }
 
+        // Resolve who is behind the event and expose it as input.identity
+        let identity = tier_identity(exec.rulebook, &safe_input);
+        let mut project_input = safe_input.clone();
+        identity::attach(&mut project_input, &identity);
+        if let Some(ref mut ctx) = telemetry {
+            if let Some(phase) = ctx.current_phase_mut() {
+                phase.evaluation_mut().record_identity(&identity);
+            }
+        }
+
         // Step 1: Route - find relevant policies (collect owned PolicyUnits)
         let matched_policies: Vec<PolicyUnit> = self
             .route_event(event_name, tool_name)
@@ -993,16 +1619,22 @@ impl Engine {
 
         // Step 2: Gather signals using the Executor (created earlier for global evaluation)
         // Gather signals - collect telemetry if enabled
-        let (enriched_input, signal_executions) = if telemetry.is_some() {
+        let (mut enriched_input, signal_executions) = if telemetry.is_some() {
             let mut signal_telemetry = SignalTelemetry::new();
             let result = exec
-                .gather_signals(&safe_input, &matched_policies, Some(&mut signal_telemetry))
-                .await?;
+                .gather_signals(
+                    &project_input,
+                    &matched_policies,
+                    Some(&mut signal_telemetry),
+                )
+                .await
+                .context(executor::SignalGatheringFailed)?;
             (result, signal_telemetry.signals)
         } else {
             let result = exec
-                .gather_signals(&safe_input, &matched_policies, None)
-                .await?;
+                .gather_signals(&project_input, &matched_policies, None)
+                .await
+                .context(executor::SignalGatheringFailed)?;
             (result, Vec::new())
         };
 
@@ -1015,19 +1647,83 @@ impl Engine {
             }
         }
 
+        // Step 2b: Count this invocation against rate limits and expose the usage
+        let rate_limit = exec
+            .rulebook
+            .and_then(|r| r.builtins.rate_limit.as_ref())
+            .filter(|c| c.enabled);
+        if let Some(config) = rate_limit {
+            if policy_names
+                .iter()
+                .any(|name| name == "cupcake.policies.builtins.rate_limit")
+            {
+                let usage = self.rate_limiter.record(config, &safe_input).await;
+                if let Some(builtin) = enriched_input
+                    .pointer_mut("/builtin_config/rate_limit")
+                    .and_then(|v| v.as_object_mut())
+                {
+                    builtin.insert("usage".to_string(), usage);
+                }
+            }
+        }
+
         // Step 3: Evaluate using single aggregation entrypoint with enriched input
         debug!("About to evaluate decision set with enriched input");
-        let decision_set = self.evaluate_decision_set(&enriched_input).await?;
+        let (mut decision_set, wasm_memory) = self
+            .evaluate_decision_set(&enriched_input)
+            .await
+            .map_err(|e| wasm_runtime::attribute_policies(e, &policy_names))?;
 
         // Record WASM results in telemetry
         if let Some(ref mut ctx) = telemetry {
             if let Some(phase) = ctx.current_phase_mut() {
                 phase.evaluation_mut().record_wasm_result(&decision_set);
+                phase.evaluation_mut().record_wasm_memory(wasm_memory);
             }
         }
 
+        // Step 3b: Remap verbs by severity for this harness, apply waivers, then
+        // reword messages
+        if let Some(rulebook) = exec.rulebook {
+            severity_mapping::apply(
+                &rulebook.severity_mapping,
+                self.config.harness,
+                &identity,
+                &mut decision_set,
+            );
+        }
+        let waived = self.waivers.apply(
+            &mut decision_set,
+            extract_target_path(&safe_input).as_deref(),
+            &self.paths.root,
+            identity.user.as_deref(),
+        );
+        if let Some(ref mut ctx) = telemetry {
+            if let Some(phase) = ctx.current_phase_mut() {
+                phase.evaluation_mut().record_waivers(&waived);
+            }
+        }
+        if let Some(rulebook) = exec.rulebook {
+            messages::apply(&rulebook.messages, &mut decision_set, &enriched_input);
+        }
+
         // Step 4: Apply Intelligence Layer synthesis
-        let final_decision = synthesis::SynthesisEngine::synthesize(&decision_set)?;
+        let final_decision =
+            synthesis::SynthesisEngine::synthesize_with_input(&decision_set, &enriched_input)?;
+
+        // Step 5: Escalate Ask to an external approver when one is configured
+        let final_decision = match exec.rulebook {
+            Some(rulebook) => {
+                approvals::escalate(
+                    &rulebook.approvals,
+                    final_decision,
+                    &safe_input,
+                    &self.paths.root,
+                )
+                .await
+            }
+            None => final_decision,
+        };
 
         info!("Synthesized final decision: {:?}", final_decision);
 
@@ -1058,8 +1754,7 @@ impl Engine {
 
     /// Evaluate global policies
     ///
-    /// Returns (decision, decision_set, signal_executions) where signal_executions
-    /// is populated only when capture_telemetry is true.
+    /// Signal executions are captured only when capture_telemetry is true.
     async fn evaluate_global(
         &self,
         input: &Value,
@@ -1067,11 +1762,12 @@ impl Engine {
         tool_name: Option<&str>,
         exec: &executor::Executor<'_>,
         capture_telemetry: bool,
-    ) -> Result<(
-        decision::FinalDecision,
-        decision::DecisionSet,
-        Vec<crate::telemetry::span::SignalExecution>,
-    )> {
+    ) -> Result<TierOutcome> {
+        let identity = tier_identity(self.global_rulebook.as_ref(), input);
+        let mut input = input.clone();
+        identity::attach(&mut input, &identity);
+        let input = &input;
+
         // Route through global policies
         let global_matched: Vec<PolicyUnit> = self
             .route_global_event(event_name, tool_name)
@@ -1081,11 +1777,10 @@ impl Engine {
 
         if global_matched.is_empty() {
             debug!("No global policies matched for this event");
-            return Ok((
-                decision::FinalDecision::Allow { context: vec![] },
-                decision::DecisionSet::default(),
-                Vec::new(),
-            ));
+            return Ok(TierOutcome {
+                identity,
+                ..Default::default()
+            });
         }
 
         info!("Found {} matching global policies", global_matched.len());
@@ -1095,68 +1790,227 @@ impl Engine {
             let mut signal_telemetry = SignalTelemetry::new();
             let result = exec
                 .gather_global_signals(input, &global_matched, Some(&mut signal_telemetry))
-                .await?;
+                .await
+                .context(executor::SignalGatheringFailed)?;
             (result, signal_telemetry.signals)
         } else {
             let result = exec
                 .gather_global_signals(input, &global_matched, None)
-                .await?;
+                .await
+                .context(executor::SignalGatheringFailed)?;
             (result, Vec::new())
         };
 
-        // Evaluate using global WASM runtime
-        let global_runtime = self
-            .global_wasm_runtime
+        // Evaluate using global WASM runtime, compiling it on the first match
+        let global_runtime = &self
+            .global_wasm
             .as_ref()
-            .context("Global WASM runtime not initialized")?;
-
-        let global_decision_set = global_runtime.query_decision_set(&enriched_input)?;
+            .context("Global WASM runtime not initialized")?
+            .get(&self.global_policies, &self.config, &self.module_cache)
+            .await?
+            .runtime;
+
+        let (mut global_decision_set, wasm_memory) = global_runtime
+            .query_decision_set_with_usage(&enriched_input)
+            .map_err(|e| wasm_runtime::attribute_policies(e, &package_names(&global_matched)))?;
+        if let Some(rulebook) = &self.global_rulebook {
+            severity_mapping::apply(
+                &rulebook.severity_mapping,
+                self.config.harness,
+                &identity,
+                &mut global_decision_set,
+            );
+        }
+        let waived = self.global_waivers.apply(
+            &mut global_decision_set,
+            extract_target_path(input).as_deref(),
+            &self.paths.root,
+            identity.user.as_deref(),
+        );
+        if let Some(rulebook) = &self.global_rulebook {
+            messages::apply(
+                &rulebook.messages,
+                &mut global_decision_set,
+                &enriched_input,
+            );
+        }
         debug!(
             "Global DecisionSet: {} total decisions",
             global_decision_set.decision_count()
         );
 
         // Synthesize global decision
-        let global_decision = synthesis::SynthesisEngine::synthesize(&global_decision_set)?;
+        let global_decision = synthesis::SynthesisEngine::synthesize_with_input(
+            &global_decision_set,
+            &enriched_input,
+        )?;
         info!("Global policy decision: {:?}", global_decision);
 
-        Ok((global_decision, global_decision_set, signal_executions))
+        Ok(TierOutcome {
+            decision: global_decision,
+            decision_set: global_decision_set,
+            identity,
+            waived,
+            signal_executions,
+            wasm_memory: Some(wasm_memory),
+        })
     }
 
-    /// Route event through global policies
-    fn route_global_event(&self, event_name: &str, tool_name: Option<&str>) -> Vec<&PolicyUnit> {
-        let key = routing::create_event_key(event_name, tool_name);
+    /// Evaluate team policies against the input
+    ///
+    /// Signals and builtin configs come from the team rulebook only.
+    async fn evaluate_team(
+        &self,
+        input: &Value,
+        event_name: &str,
+        tool_name: Option<&str>,
+        capture_telemetry: bool,
+    ) -> Result<TierOutcome> {
+        let identity = tier_identity(self.team_rulebook.as_ref(), input);
+        let mut input = input.clone();
+        identity::attach(&mut input, &identity);
+        let input = &input;
+
+        let team_matched: Vec<PolicyUnit> =
+            Self::route_in_map(&self.team_routing_map, event_name, tool_name)
+                .into_iter()
+                .cloned()
+                .collect();
+
+        if team_matched.is_empty() {
+            debug!("No team policies matched for this event");
+            return Ok(TierOutcome {
+                identity,
+                ..Default::default()
+            });
+        }
 
-        // First try the specific key
-        let mut result: Vec<&PolicyUnit> = self
-            .global_routing_map
-            .get(&key)
-            .map(|policies| policies.iter().collect())
-            .unwrap_or_default();
+        info!("Found {} matching team policies", team_matched.len());
 
-        // If we have a tool name, also check for wildcards
+        // The team tier reuses the global signal path with the team rulebook in its place
+        let team_exec = executor::Executor {
+            rulebook: None,
+            global_rulebook: self.team_rulebook.as_ref(),
+            watchdog: None,
+            working_dir: &self.paths.root,
+        };
+
+        let (enriched_input, signal_executions) = if capture_telemetry {
+            let mut signal_telemetry = SignalTelemetry::new();
+            let result = team_exec
+                .gather_global_signals(input, &team_matched, Some(&mut signal_telemetry))
+                .await
+                .context(executor::SignalGatheringFailed)?;
+            (result, signal_telemetry.signals)
+        } else {
+            let result = team_exec
+                .gather_global_signals(input, &team_matched, None)
+                .await
+                .context(executor::SignalGatheringFailed)?;
+            (result, Vec::new())
+        };
+
+        let team_runtime = &self
+            .team_wasm
+            .as_ref()
+            .context("Team WASM runtime not initialized")?
+            .get(&self.team_policies, &self.config, &self.module_cache)
+            .await?
+            .runtime;
+
+        let (mut team_decision_set, wasm_memory) = team_runtime
+            .query_decision_set_with_usage(&enriched_input)
+            .map_err(|e| wasm_runtime::attribute_policies(e, &package_names(&team_matched)))?;
+        if let Some(rulebook) = &self.team_rulebook {
+            severity_mapping::apply(
+                &rulebook.severity_mapping,
+                self.config.harness,
+                &identity,
+                &mut team_decision_set,
+            );
+        }
+        let waived = self.team_waivers.apply(
+            &mut team_decision_set,
+            extract_target_path(input).as_deref(),
+            &self.paths.root,
+            identity.user.as_deref(),
+        );
+        if let Some(rulebook) = &self.team_rulebook {
+            messages::apply(&rulebook.messages, &mut team_decision_set, &enriched_input);
+        }
+        debug!(
+            "Team DecisionSet: {} total decisions",
+            team_decision_set.decision_count()
+        );
+
+        let team_decision =
+            synthesis::SynthesisEngine::synthesize_with_input(&team_decision_set, &enriched_input)?;
+        info!("Team policy decision: {:?}", team_decision);
+
+        Ok(TierOutcome {
+            decision: team_decision,
+            decision_set: team_decision_set,
+            identity,
+            waived,
+            signal_executions,
+            wasm_memory: Some(wasm_memory),
+        })
+    }
+
+    /// Route event through a tier routing map
+    ///
+    /// Collects the exact key, tool wildcards (`Event:*`, MCP server and MCP
+    /// prefixes) and event-only policies, without duplicates.
+    fn route_in_map<'a>(
+        routing_map: &'a HashMap<String, Vec<PolicyUnit>>,
+        event_name: &str,
+        tool_name: Option<&str>,
+    ) -> Vec<&'a PolicyUnit> {
+        let mut keys = routing::lookup_keys(event_name, tool_name);
         if tool_name.is_some() {
-            let wildcard_key = event_name.to_string();
-            if let Some(wildcard_policies) = self.global_routing_map.get(&wildcard_key) {
-                result.extend(wildcard_policies.iter());
+            keys.push(event_name.to_string());
+        }
+
+        let mut result: Vec<&PolicyUnit> = Vec::new();
+        for policies in keys.iter().filter_map(|key| routing_map.get(key)) {
+            for policy in policies {
+                if !result.iter().any(|p| p.package_name == policy.package_name) {
+                    result.push(policy);
+                }
             }
         }
 
+        result
+    }
+
+    /// Route event through global policies
+    fn route_global_event(&self, event_name: &str, tool_name: Option<&str>) -> Vec<&PolicyUnit> {
+        let result = Self::route_in_map(&self.global_routing_map, event_name, tool_name);
         debug!("Routed to {} global policies", result.len());
         result
     }
 
     /// Evaluate using the Hybrid Model single aggregation entrypoint
-    async fn evaluate_decision_set(&self, input: &Value) -> Result<decision::DecisionSet> {
+    async fn evaluate_decision_set(
+        &self,
+        input: &Value,
+    ) -> Result<(decision::DecisionSet, wasm_runtime::MemoryUsage)> {
         let runtime = self
             .wasm_runtime
             .as_ref()
             .context("WASM runtime not initialized")?;
 
+        // Swap in refreshed external data before querying
+        if let Some(ref external) = self.external_data {
+            if let Some(document) = external.refresh().await {
+                runtime.set_external_data(document);
+            }
+        }
+
         debug!("Evaluating using single cupcake.system.evaluate entrypoint");
 
         // Query the single aggregation entrypoint
-        let decision_set = runtime.query_decision_set(input)?;
+        let (decision_set, usage) = runtime.query_decision_set_with_usage(input)?;
 
         debug!(
             "Raw DecisionSet from WASM: {} total decisions",
@@ -1167,6 +2021,68 @@ impl Engine {
             synthesis::SynthesisEngine::summarize_decision_set(&decision_set)
         );
 
-        Ok(decision_set)
+        Ok((decision_set, usage))
+    }
+}
+
+/// Result of evaluating the global or team policy tier
+struct TierOutcome {
+    decision: decision::FinalDecision,
+    decision_set: decision::DecisionSet,
+    /// Identity resolved with the tier's rulebook
+    identity: identity::Identity,
+    /// Waivers applied to the decision set
+    waived: Vec<waivers::WaiverUse>,
+    /// Populated only when telemetry is being captured
+    signal_executions: Vec<crate::telemetry::span::SignalExecution>,
+    /// None when no policy in the tier matched and WASM was not evaluated
+    wasm_memory: Option<wasm_runtime::MemoryUsage>,
+}
+
+impl Default for TierOutcome {
+    /// Implicit allow, used when no policy in the tier matched
+    fn default() -> Self {
+        Self {
+            decision: decision::FinalDecision::Allow { context: vec![] },
+            decision_set: decision::DecisionSet::default(),
+            identity: identity::Identity::default(),
+            waived: Vec::new(),
+            signal_executions: Vec::new(),
+            wasm_memory: None,
+        }
+    }
+}
+
+/// Resolve the identity behind an event with a tier's rulebook, or the defaults
+fn tier_identity(rulebook: Option<&rulebook::Rulebook>, input: &Value) -> identity::Identity {
+    match rulebook {
+        Some(rulebook) => identity::resolve(&rulebook.identity, input),
+        None => identity::resolve(&identity::IdentityConfig::default(), input),
     }
 }
+
+/// Package names of routed policies, used to attribute evaluation errors
+fn package_names(policies: &[PolicyUnit]) -> Vec<String> {
+    policies.iter().map(|p| p.package_name.clone()).collect()
+}
+
+/// Extract the path an event acts on, used to locate nested `.cupcake/` directories
+///
+/// Prefers explicit file targets (after symlink resolution) and falls back to the
+/// working directory reported by the harness.
+fn extract_target_path(input: &Value) -> Option<PathBuf> {
+    let tool_input = input.get("tool_input");
+    [
+        input.get("resolved_file_path"),
+        tool_input.and_then(|t| t.get("file_path")),
+        tool_input.and_then(|t| t.get("notebook_path")),
+        tool_input.and_then(|t| t.get("path")),
+        input.get("file_path"),
+        input.get("cwd"),
+    ]
+    .into_iter()
+    .flatten()
+    .find_map(|v| v.as_str())
+    .filter(|s| !s.is_empty())
+    .map(PathBuf::from)
+}
@@ -0,0 +1,358 @@
+//! Precompiled module cache - persists Wasmtime machine code between runs
+//!
+//! JIT-compiling the policy WASM dominates engine start for short-lived hook
+//! invocations. Compiled modules are serialized and loaded directly on later
+//! starts, keyed by the WASM bytes and the Wasmtime configuration.
+//!
+//! Loading precompiled code skips Wasmtime's validation - a planted entry runs
+//! as native code. The agent runs as the same user as Cupcake, so neither the
+//! location nor the permissions of the cache can be trusted on their own:
+//!
+//! - Every entry carries an HMAC-SHA256 tag made with a random per-user key,
+//!   and is only deserialized when the tag verifies. An entry written without
+//!   the key is recompiled and replaced.
+//! - The cache, key included, lives in the per-user cache directory (e.g.
+//!   `~/.cache/cupcake/wasm-cache/` on Linux) rather than the project, and is
+//!   always on the `rulebook_security_guardrails` protected list, so agent tools
+//!   can neither read the key nor write entries.
+
+use anyhow::{anyhow, bail, Context, Result};
+use directories::ProjectDirs;
+use sha2::{Digest, Sha256};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+use wasmtime::{Engine, Module};
+
+/// Directory under the per-user cache directory holding the key and entries
+const CACHE_DIR: &str = "wasm-cache";
+
+/// How the cache directory appears in any path or command that touches it;
+/// rulebook_security_guardrails always protects it
+pub const PROTECTED_PATH: &str = "cupcake/wasm-cache/";
+
+/// File extension for serialized modules
+const CACHE_EXTENSION: &str = "cwasm";
+
+/// Per-user HMAC key, in the cache directory root
+const KEY_FILE: &str = "key";
+
+/// Length of the key and of the tag prefixed to each entry
+const TAG_LEN: usize = 32;
+
+/// On-disk cache of compiled Wasmtime modules
+#[derive(Clone)]
+pub struct ModuleCache {
+    /// Entries for this project; the key lives in the parent directory.
+    /// `None` when the platform has no per-user cache directory
+    dir: Option<PathBuf>,
+}
+
+impl ModuleCache {
+    /// The cache for the project at `project_root`
+    pub fn new(project_root: &Path) -> Self {
+        let root = project_root
+            .canonicalize()
+            .unwrap_or_else(|_| project_root.to_path_buf());
+        let project_hash = hex::encode(&Sha256::digest(root.to_string_lossy().as_bytes())[..8]);
+        let dir = ProjectDirs::from("", "", "cupcake")
+            .map(|dirs| dirs.cache_dir().join(CACHE_DIR).join(project_hash));
+        if dir.is_none() {
+            debug!("No per-user cache directory - compiled WASM modules won't be cached");
+        }
+        Self { dir }
+    }
+
+    /// Load the module from the cache, compiling and caching it on a miss
+    ///
+    /// Cache failures are never fatal; the module is compiled in memory instead.
+    pub fn load(&self, engine: &Engine, wasm_bytes: &[u8], namespace: &str) -> Result<Module> {
+        let Some(dir) = &self.dir else {
+            return Module::from_binary(engine, wasm_bytes).context("Failed to load WASM module");
+        };
+        let path = Self::entry_path(dir, engine, wasm_bytes, namespace);
+
+        if path.exists() && is_private(dir) {
+            match Self::read_entry(dir, engine, &path) {
+                Ok(module) => {
+                    debug!("Loaded precompiled WASM module from {:?}", path);
+                    return Ok(module);
+                }
+                Err(e) => warn!("Ignoring unusable module cache entry {:?}: {:#}", path, e),
+            }
+        }
+
+        let module =
+            Module::from_binary(engine, wasm_bytes).context("Failed to load WASM module")?;
+        if let Err(e) = Self::store(dir, &module, &path, namespace) {
+            warn!("Failed to cache compiled WASM module: {:#}", e);
+        }
+        Ok(module)
+    }
+
+    /// Deserialize an entry after checking its tag
+    fn read_entry(dir: &Path, engine: &Engine, path: &Path) -> Result<Module> {
+        let key = read_key(dir)?;
+        let bytes = std::fs::read(path)?;
+        if bytes.len() < TAG_LEN {
+            bail!("entry is too short to carry a tag");
+        }
+        let (tag, serialized) = bytes.split_at(TAG_LEN);
+        if !verify_tag(&key, serialized, tag) {
+            bail!("entry was not written with this user's cache key");
+        }
+
+        // SAFETY: the bytes carry a valid tag, so they were produced by
+        // `Module::serialize` in `store` for this engine configuration (part of
+        // the file name); Wasmtime still rejects data from incompatible versions.
+        unsafe { Module::deserialize(engine, serialized) }
+    }
+
+    /// Serialize the module, replacing older entries for the same namespace
+    fn store(dir: &Path, module: &Module, path: &Path, namespace: &str) -> Result<()> {
+        create_private_dir(dir)?;
+        if !is_private(dir) {
+            bail!("{:?} is accessible to other users", dir);
+        }
+        let key = load_or_create_key(dir)?;
+
+        // Write to a temp file and rename, so concurrent hooks never read a partial file
+        let serialized = module.serialize()?;
+        let mut tmp = tempfile::NamedTempFile::new_in(dir)?;
+        std::io::Write::write_all(&mut tmp, &tag(&key, &serialized))?;
+        std::io::Write::write_all(&mut tmp, &serialized)?;
+        tmp.persist(path)?;
+        debug!("Cached compiled WASM module at {:?}", path);
+
+        let prefix = format!("{namespace}-");
+        for entry in std::fs::read_dir(dir)?.flatten() {
+            let stale = entry.path();
+            let is_stale = stale != path
+                && stale.extension().is_some_and(|ext| ext == CACHE_EXTENSION)
+                && entry.file_name().to_string_lossy().starts_with(&prefix);
+            if is_stale {
+                let _ = std::fs::remove_file(&stale);
+            }
+        }
+        Ok(())
+    }
+
+    /// `<namespace>-<wasm sha256>-<engine config hash>.cwasm`
+    fn entry_path(dir: &Path, engine: &Engine, wasm_bytes: &[u8], namespace: &str) -> PathBuf {
+        let wasm_hash = hex::encode(Sha256::digest(wasm_bytes));
+        let mut hasher = DefaultHasher::new();
+        engine.precompile_compatibility_hash().hash(&mut hasher);
+
+        dir.join(format!(
+            "{namespace}-{wasm_hash}-{:016x}.{CACHE_EXTENSION}",
+            hasher.finish()
+        ))
+    }
+}
+
+fn key_path(dir: &Path) -> PathBuf {
+    dir.parent().unwrap_or(dir).join(KEY_FILE)
+}
+
+fn read_key(dir: &Path) -> Result<[u8; TAG_LEN]> {
+    let path = key_path(dir);
+    let bytes =
+        std::fs::read(&path).with_context(|| format!("Failed to read cache key {:?}", path))?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow!("Cache key {:?} is malformed", path))
+}
+
+/// Read the per-user key, generating it on first use
+fn load_or_create_key(dir: &Path) -> Result<[u8; TAG_LEN]> {
+    let path = key_path(dir);
+    if path.exists() {
+        return read_key(dir);
+    }
+
+    let mut key = [0u8; TAG_LEN];
+    getrandom::fill(&mut key).map_err(|e| anyhow!("Failed to generate cache key: {e}"))?;
+    // Temp files are created readable by the owner only
+    let mut tmp = tempfile::NamedTempFile::new_in(path.parent().unwrap_or(dir))?;
+    std::io::Write::write_all(&mut tmp, &key)?;
+    match tmp.persist_noclobber(&path) {
+        Ok(_) => Ok(key),
+        // Another hook created it first - use theirs
+        Err(e) if e.error.kind() == std::io::ErrorKind::AlreadyExists => read_key(dir),
+        Err(e) => Err(e.error).with_context(|| format!("Failed to write cache key {:?}", path)),
+    }
+}
+
+/// HMAC-SHA256 of `data`
+fn tag(key: &[u8; TAG_LEN], data: &[u8]) -> [u8; TAG_LEN] {
+    const BLOCK_LEN: usize = 64;
+    let mut inner_pad = [0x36u8; BLOCK_LEN];
+    let mut outer_pad = [0x5cu8; BLOCK_LEN];
+    for (i, byte) in key.iter().enumerate() {
+        inner_pad[i] ^= byte;
+        outer_pad[i] ^= byte;
+    }
+    let inner = Sha256::new()
+        .chain_update(inner_pad)
+        .chain_update(data)
+        .finalize();
+    Sha256::new()
+        .chain_update(outer_pad)
+        .chain_update(inner)
+        .finalize()
+        .into()
+}
+
+/// Compare tags in constant time
+fn verify_tag(key: &[u8; TAG_LEN], data: &[u8], expected: &[u8]) -> bool {
+    let actual = tag(key, data);
+    expected.len() == TAG_LEN
+        && actual
+            .iter()
+            .zip(expected)
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Create `dir` and any missing parents, readable only by the current user
+fn create_private_dir(dir: &Path) -> Result<()> {
+    let mut builder = std::fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder
+        .create(dir)
+        .with_context(|| format!("Failed to create {:?}", dir))
+}
+
+/// Whether `dir` is owned by the current user and closed to everyone else
+#[cfg(unix)]
+fn is_private(dir: &Path) -> bool {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+    // SAFETY: geteuid has no preconditions and cannot fail
+    let uid = unsafe { libc::geteuid() };
+    std::fs::symlink_metadata(dir).is_ok_and(|metadata| {
+        metadata.is_dir() && metadata.uid() == uid && metadata.permissions().mode() & 0o077 == 0
+    })
+}
+
+/// Per-user cache directories are private by default on other platforms
+#[cfg(not(unix))]
+fn is_private(dir: &Path) -> bool {
+    dir.is_dir()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Smallest valid module: `(module)`
+    const EMPTY_MODULE: &[u8] = b"\0asm\x01\0\0\0";
+
+    #[test]
+    fn test_module_is_cached_and_reloaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ModuleCache {
+            dir: Some(dir.path().join("wasm")),
+        };
+        let engine = Engine::default();
+
+        cache.load(&engine, EMPTY_MODULE, "cupcake.system").unwrap();
+        let entries: Vec<_> = std::fs::read_dir(dir.path().join("wasm"))
+            .unwrap()
+            .flatten()
+            .collect();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0]
+            .file_name()
+            .to_string_lossy()
+            .starts_with("cupcake.system-"));
+
+        // A second load deserializes the entry rather than recompiling
+        cache.load(&engine, EMPTY_MODULE, "cupcake.system").unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_entry_falls_back_to_compilation() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_dir = dir.path().join("wasm");
+        let cache = ModuleCache {
+            dir: Some(cache_dir.clone()),
+        };
+        let engine = Engine::default();
+
+        let path = ModuleCache::entry_path(&cache_dir, &engine, EMPTY_MODULE, "cupcake.system");
+        create_private_dir(&cache_dir).unwrap();
+        std::fs::write(&path, b"not a module").unwrap();
+
+        cache.load(&engine, EMPTY_MODULE, "cupcake.system").unwrap();
+        // The corrupt entry was replaced by a valid one
+        assert_ne!(std::fs::read(&path).unwrap(), b"not a module");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_shared_directory_is_not_trusted() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache_dir = dir.path().join("wasm");
+        let cache = ModuleCache {
+            dir: Some(cache_dir.clone()),
+        };
+        let engine = Engine::default();
+
+        // An entry planted in a directory other users can write to is never loaded
+        let path = ModuleCache::entry_path(&cache_dir, &engine, EMPTY_MODULE, "cupcake.system");
+        create_private_dir(&cache_dir).unwrap();
+        std::fs::set_permissions(&cache_dir, std::fs::Permissions::from_mode(0o777)).unwrap();
+        std::fs::write(&path, b"planted").unwrap();
+
+        cache.load(&engine, EMPTY_MODULE, "cupcake.system").unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"planted");
+    }
+
+    #[test]
+    fn test_unauthenticated_entry_in_private_directory_is_rejected() {
+        /// `(module (func (export "planted")))`
+        const PLANTED_MODULE: &[u8] = b"\0asm\x01\0\0\0\x01\x04\x01\x60\x00\x00\x03\x02\x01\x00\x07\x0b\x01\x07planted\x00\x00\x0a\x04\x01\x02\x00\x0b";
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache_dir = dir.path().join("wasm");
+        let cache = ModuleCache {
+            dir: Some(cache_dir.clone()),
+        };
+        let engine = Engine::default();
+        // Create the key, as an earlier run would have
+        cache.load(&engine, EMPTY_MODULE, "cupcake.system").unwrap();
+
+        // A genuine serialized module, planted by someone without the key -
+        // once untagged and once with a forged tag
+        let planted = Module::new(&engine, PLANTED_MODULE)
+            .unwrap()
+            .serialize()
+            .unwrap();
+        let path = ModuleCache::entry_path(&cache_dir, &engine, EMPTY_MODULE, "cupcake.system");
+        for forged in [planted.clone(), [vec![0; TAG_LEN], planted].concat()] {
+            std::fs::write(&path, &forged).unwrap();
+            assert!(is_private(&cache_dir));
+
+            let module = cache.load(&engine, EMPTY_MODULE, "cupcake.system").unwrap();
+            assert_eq!(module.exports().count(), 0, "planted module was loaded");
+            assert_ne!(std::fs::read(&path).unwrap(), forged);
+        }
+    }
+
+    #[test]
+    fn test_cache_is_outside_the_project() {
+        let project = tempfile::tempdir().unwrap();
+        let cache = ModuleCache::new(project.path());
+        if let Some(dir) = &cache.dir {
+            assert!(!dir.starts_with(project.path()));
+        }
+    }
+}
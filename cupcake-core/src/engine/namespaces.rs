@@ -0,0 +1,163 @@
+//! Package namespace conflict detection
+//!
+//! Every tier compiles into its own namespace: project policies use
+//! `cupcake.policies.*` and `cupcake.system`, global policies are relocated to
+//! `cupcake.global.*`, team policies to `cupcake.team.*`. OPA merges all files
+//! that declare the same package, so two policy files sharing a package would
+//! silently combine their rules - or one would override the other's - and a
+//! project policy declaring a `cupcake.global.*` package would reach into the
+//! global tier. Both are reported when the engine loads a tier.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+
+use super::metadata::PolicyUnit;
+
+/// Tier a set of policies was loaded into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tier {
+    Global,
+    Team,
+    Project,
+}
+
+impl Tier {
+    /// The tier a package (or compilation namespace) belongs to
+    pub fn of_package(package: &str) -> Tier {
+        if package.starts_with("cupcake.global.") {
+            Tier::Global
+        } else if package.starts_with("cupcake.team.") {
+            Tier::Team
+        } else {
+            Tier::Project
+        }
+    }
+
+    /// Package prefix reserved for this tier, if it has one
+    fn reserved_prefix(self) -> Option<&'static str> {
+        match self {
+            Tier::Global => Some("cupcake.global."),
+            Tier::Team => Some("cupcake.team."),
+            Tier::Project => None,
+        }
+    }
+}
+
+impl fmt::Display for Tier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tier::Global => write!(f, "global"),
+            Tier::Team => write!(f, "team"),
+            Tier::Project => write!(f, "project"),
+        }
+    }
+}
+
+/// Find package conflicts among the (namespace-transformed) policies of one tier
+///
+/// Returns one message per conflict; an empty list means the tier is clean.
+pub fn package_conflicts(tier: Tier, policies: &[PolicyUnit]) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    // A package inside another tier's namespace would be compiled into that tier
+    for policy in policies {
+        let owner = [Tier::Global, Tier::Team]
+            .into_iter()
+            .filter(|other| *other != tier)
+            .find(|other| {
+                other
+                    .reserved_prefix()
+                    .is_some_and(|prefix| policy.package_name.starts_with(prefix))
+            });
+        if let Some(owner) = owner {
+            errors.push(format!(
+                "{} policy {} declares package '{}', which is reserved for the {} tier - move it to a {} package",
+                tier,
+                policy.path.display(),
+                policy.package_name,
+                owner,
+                match tier.reserved_prefix() {
+                    Some(prefix) => format!("{prefix}policies.*"),
+                    None => "cupcake.policies.*".to_string(),
+                }
+            ));
+        }
+    }
+
+    // Two files declaring the same package would be merged by OPA
+    let mut declared: HashMap<&str, &PolicyUnit> = HashMap::new();
+    for policy in policies {
+        if let Some(first) = declared.get(policy.package_name.as_str()) {
+            errors.push(format!(
+                "{} package '{}' is declared by both {} and {} - rename one of the packages",
+                tier,
+                policy.package_name,
+                first.path.display(),
+                policy.path.display()
+            ));
+        } else {
+            declared.insert(&policy.package_name, policy);
+        }
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::metadata::RoutingDirective;
+    use std::path::PathBuf;
+
+    fn unit(path: &str, package_name: &str) -> PolicyUnit {
+        PolicyUnit {
+            path: PathBuf::from(path),
+            package_name: package_name.to_string(),
+            routing: RoutingDirective::default(),
+            metadata: None,
+            rendered: None,
+        }
+    }
+
+    #[test]
+    fn test_duplicate_package_names_both_files() {
+        let policies = vec![
+            unit("/p/claude/git.rego", "cupcake.policies.git"),
+            unit("/p/claude/system/evaluate.rego", "cupcake.system"),
+            unit("/p/claude/vcs/git.rego", "cupcake.policies.git"),
+        ];
+
+        let errors = package_conflicts(Tier::Project, &policies);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("'cupcake.policies.git'"));
+        assert!(errors[0].contains("/p/claude/git.rego"));
+        assert!(errors[0].contains("/p/claude/vcs/git.rego"));
+    }
+
+    #[test]
+    fn test_package_in_another_tiers_namespace() {
+        let project = vec![unit(
+            "/p/claude/sneaky.rego",
+            "cupcake.global.policies.sneaky",
+        )];
+        let errors = package_conflicts(Tier::Project, &project);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("reserved for the global tier"));
+        assert!(errors[0].contains("cupcake.policies.*"));
+
+        let global = vec![unit("/g/claude/team.rego", "cupcake.team.policies.audit")];
+        let errors = package_conflicts(Tier::Global, &global);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("reserved for the team tier"));
+        assert!(errors[0].contains("cupcake.global.policies.*"));
+
+        // A tier's own namespace is fine
+        let global = vec![unit(
+            "/g/claude/audit.rego",
+            "cupcake.global.policies.audit",
+        )];
+        assert!(package_conflicts(Tier::Global, &global).is_empty());
+    }
+}
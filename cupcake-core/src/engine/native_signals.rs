@@ -0,0 +1,389 @@
+//! Native signal providers - zero-configuration signals implemented in Rust
+//!
+//! Policies request them like any other signal, using a `builtin.` prefix:
+//!
+//! ```yaml
+//! # METADATA
+//! # custom:
+//! #   routing:
+//! #     required_events: ["PreToolUse"]
+//! #     required_signals: ["builtin.git", "builtin.time"]
+//! ```
+//!
+//! Results are injected as `input.signals.builtin.<provider>`. Requesting plain
+//! `builtin` collects every provider. No rulebook entry or shell script is needed.
+//!
+//! `builtin.transcript` summarizes the session transcript referenced by the
+//! event's `transcript_path` (Claude Code and Factory AI), which lets PreCompact
+//! and SessionStart policies see what is about to be, or just was, compacted.
+
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::time::Duration;
+use tokio::process::Command;
+use tracing::debug;
+
+/// Prefix that marks a required signal as a native provider
+pub const NATIVE_SIGNAL_PREFIX: &str = "builtin.";
+
+/// Key under `input.signals` where native signals are injected
+pub const NATIVE_SIGNAL_KEY: &str = "builtin";
+
+/// Available native providers
+pub const NATIVE_PROVIDERS: &[&str] = &["git", "env", "os", "time", "transcript"];
+
+/// Longest prompt or summary text kept in the transcript signal
+const TRANSCRIPT_TEXT_LIMIT: usize = 2000;
+
+/// Timeout for each git invocation
+const GIT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Well-known CI environment variables and the provider they identify
+const CI_PROVIDERS: &[(&str, &str)] = &[
+    ("GITHUB_ACTIONS", "github_actions"),
+    ("GITLAB_CI", "gitlab"),
+    ("CIRCLECI", "circleci"),
+    ("BUILDKITE", "buildkite"),
+    ("JENKINS_URL", "jenkins"),
+    ("TRAVIS", "travis"),
+    ("TF_BUILD", "azure_pipelines"),
+    ("BITBUCKET_BUILD_NUMBER", "bitbucket"),
+    ("TEAMCITY_VERSION", "teamcity"),
+];
+
+/// Check whether a required signal name refers to a native provider
+pub fn is_native_signal(name: &str) -> bool {
+    name == NATIVE_SIGNAL_KEY || name.starts_with(NATIVE_SIGNAL_PREFIX)
+}
+
+/// Expand requested native signal names into a sorted, de-duplicated provider list
+///
+/// Unknown providers are dropped with a debug log so a typo never fails evaluation.
+pub fn requested_providers<'a>(names: impl IntoIterator<Item = &'a String>) -> Vec<&'static str> {
+    let mut providers: Vec<&'static str> = Vec::new();
+    for name in names {
+        if name == NATIVE_SIGNAL_KEY {
+            providers.extend_from_slice(NATIVE_PROVIDERS);
+            continue;
+        }
+        let Some(requested) = name.strip_prefix(NATIVE_SIGNAL_PREFIX) else {
+            continue;
+        };
+        match NATIVE_PROVIDERS.iter().find(|p| **p == requested) {
+            Some(provider) => providers.push(provider),
+            None => debug!("Unknown native signal provider '{}' - ignoring", name),
+        }
+    }
+    providers.sort_unstable();
+    providers.dedup();
+    providers
+}
+
+/// Collect the given native providers, returning the `input.signals.builtin` object
+pub async fn collect(providers: &[&str], working_dir: &Path, input: &Value) -> Value {
+    let mut result = Map::new();
+    for provider in providers {
+        let value = match *provider {
+            "git" => git_signal(working_dir).await,
+            "env" => env_signal(),
+            "os" => os_signal(),
+            "time" => time_signal(),
+            "transcript" => transcript_signal(input, working_dir).await,
+            _ => continue,
+        };
+        result.insert(provider.to_string(), value);
+    }
+    Value::Object(result)
+}
+
+/// Current branch, dirty status and staged files
+async fn git_signal(working_dir: &Path) -> Value {
+    let (branch, status, staged) = tokio::join!(
+        run_git(working_dir, &["rev-parse", "--abbrev-ref", "HEAD"]),
+        run_git(working_dir, &["status", "--porcelain"]),
+        run_git(working_dir, &["diff", "--cached", "--name-only"]),
+    );
+
+    let Some(branch) = branch else {
+        return json!({ "available": false });
+    };
+
+    let staged_files: Vec<&str> = staged
+        .as_deref()
+        .unwrap_or_default()
+        .lines()
+        .filter(|l| !l.is_empty())
+        .collect();
+
+    json!({
+        "available": true,
+        "branch": branch.trim(),
+        "dirty": status.as_deref().is_some_and(|s| !s.trim().is_empty()),
+        "staged_files": staged_files,
+    })
+}
+
+/// Run a git command, returning stdout on success
+async fn run_git(working_dir: &Path, args: &[&str]) -> Option<String> {
+    let output = tokio::time::timeout(
+        GIT_TIMEOUT,
+        Command::new("git")
+            .args(args)
+            .current_dir(working_dir)
+            .stdin(std::process::Stdio::null())
+            .output(),
+    )
+    .await
+    .ok()?
+    .ok()?;
+
+    if !output.status.success() {
+        debug!("git {:?} failed with {:?}", args, output.status.code());
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// CI detection
+fn env_signal() -> Value {
+    let provider = CI_PROVIDERS
+        .iter()
+        .find(|(var, _)| std::env::var_os(var).is_some())
+        .map(|(_, name)| *name);
+
+    let ci_flag = std::env::var("CI")
+        .map(|v| !v.is_empty() && v != "0" && !v.eq_ignore_ascii_case("false"))
+        .unwrap_or(false);
+
+    json!({
+        "ci": ci_flag || provider.is_some(),
+        "ci_provider": provider,
+    })
+}
+
+/// Operating system and host information
+fn os_signal() -> Value {
+    json!({
+        "os": std::env::consts::OS,
+        "family": std::env::consts::FAMILY,
+        "arch": std::env::consts::ARCH,
+        "hostname": hostname(),
+    })
+}
+
+fn hostname() -> Option<String> {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok())
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty())
+}
+
+/// Local wall-clock time
+fn time_signal() -> Value {
+    use chrono::{Datelike, Local, Timelike};
+
+    let now = Local::now();
+    json!({
+        "local": now.to_rfc3339(),
+        "utc": now.with_timezone(&chrono::Utc).to_rfc3339(),
+        "unix": now.timestamp(),
+        "hour": now.hour(),
+        "minute": now.minute(),
+        "weekday": now.weekday().to_string(),
+        "utc_offset_seconds": now.offset().local_minus_utc(),
+    })
+}
+
+/// Summary of the session transcript (JSONL) at `input.transcript_path`
+///
+/// Counts messages and tool uses, lists files touched by tools, and keeps the
+/// last user prompt and the last compaction summary.
+async fn transcript_signal(input: &Value, working_dir: &Path) -> Value {
+    let Some(path) = input.get("transcript_path").and_then(|p| p.as_str()) else {
+        return json!({ "available": false });
+    };
+    let path = working_dir.join(path);
+    let Ok(content) = tokio::fs::read_to_string(&path).await else {
+        debug!("Transcript {:?} could not be read", path);
+        return json!({ "available": false, "path": path });
+    };
+
+    let mut entries = 0usize;
+    let mut user_messages = 0usize;
+    let mut assistant_messages = 0usize;
+    let mut compactions = 0usize;
+    let mut tool_uses: BTreeMap<String, usize> = BTreeMap::new();
+    let mut files_touched: BTreeSet<String> = BTreeSet::new();
+    let mut last_user_prompt: Option<String> = None;
+    let mut last_summary: Option<String> = None;
+
+    for entry in content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+    {
+        entries += 1;
+        let content = entry.pointer("/message/content");
+
+        match entry.get("type").and_then(|t| t.as_str()) {
+            Some("summary") => {
+                compactions += 1;
+                last_summary = entry
+                    .get("summary")
+                    .and_then(|s| s.as_str())
+                    .map(truncate_text);
+            }
+            Some("user") if entry.get("isCompactSummary") == Some(&Value::Bool(true)) => {
+                compactions += 1;
+                last_summary = content.and_then(message_text).map(|t| truncate_text(&t));
+            }
+            Some("user") => {
+                // Tool results are recorded as user messages; only count real prompts
+                if let Some(text) = content.and_then(message_text) {
+                    user_messages += 1;
+                    last_user_prompt = Some(truncate_text(&text));
+                }
+            }
+            Some("assistant") => {
+                assistant_messages += 1;
+                for block in content.and_then(|c| c.as_array()).into_iter().flatten() {
+                    if block.get("type").and_then(|t| t.as_str()) != Some("tool_use") {
+                        continue;
+                    }
+                    if let Some(name) = block.get("name").and_then(|n| n.as_str()) {
+                        *tool_uses.entry(name.to_string()).or_default() += 1;
+                    }
+                    let file = block
+                        .pointer("/input/file_path")
+                        .or_else(|| block.pointer("/input/notebook_path"))
+                        .and_then(|f| f.as_str());
+                    if let Some(file) = file {
+                        files_touched.insert(file.to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    json!({
+        "available": true,
+        "path": path,
+        "size_bytes": content.len(),
+        "estimated_tokens": content.len() / 4,
+        "entries": entries,
+        "user_messages": user_messages,
+        "assistant_messages": assistant_messages,
+        "tool_uses": tool_uses,
+        "files_touched": files_touched,
+        "compactions": compactions,
+        "last_user_prompt": last_user_prompt,
+        "last_summary": last_summary,
+    })
+}
+
+/// Text of a message, ignoring tool results - `None` if there is no text
+fn message_text(content: &Value) -> Option<String> {
+    let text = match content {
+        Value::String(s) => s.clone(),
+        Value::Array(blocks) => blocks
+            .iter()
+            .filter(|b| b.get("type").and_then(|t| t.as_str()) == Some("text"))
+            .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => return None,
+    };
+    (!text.trim().is_empty()).then_some(text)
+}
+
+fn truncate_text(text: &str) -> String {
+    match text.char_indices().nth(TRANSCRIPT_TEXT_LIMIT) {
+        Some((idx, _)) => format!("{}...", &text[..idx]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_native_signal() {
+        assert!(is_native_signal("builtin"));
+        assert!(is_native_signal("builtin.git"));
+        assert!(!is_native_signal("git_branch"));
+        assert!(!is_native_signal("__builtin_post_edit_rs"));
+    }
+
+    #[test]
+    fn test_requested_providers() {
+        let names = vec![
+            "builtin.time".to_string(),
+            "builtin.git".to_string(),
+            "builtin.time".to_string(),
+            "builtin.nope".to_string(),
+            "custom_signal".to_string(),
+        ];
+        assert_eq!(requested_providers(&names), vec!["git", "time"]);
+
+        let all = vec!["builtin".to_string()];
+        assert_eq!(
+            requested_providers(&all),
+            vec!["env", "git", "os", "time", "transcript"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_collect_outside_git_repo() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let value = collect(&["git", "os", "time"], temp_dir.path(), &json!({})).await;
+
+        assert_eq!(value["git"]["available"], json!(false));
+        assert_eq!(value["os"]["os"], json!(std::env::consts::OS));
+        assert!(value["time"]["hour"].as_u64().unwrap() < 24);
+        assert!(value.get("env").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_transcript_summary() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let lines = [
+            json!({"type": "user", "message": {"role": "user", "content": "Fix the login bug"}}),
+            json!({"type": "assistant", "message": {"role": "assistant", "content": [
+                {"type": "text", "text": "Reading the handler"},
+                {"type": "tool_use", "name": "Read", "input": {"file_path": "src/login.rs"}},
+                {"type": "tool_use", "name": "Edit", "input": {"file_path": "src/login.rs"}}
+            ]}}),
+            json!({"type": "user", "message": {"role": "user", "content": [
+                {"type": "tool_result", "tool_use_id": "t1", "content": "ok"}
+            ]}}),
+            json!({"type": "summary", "summary": "Fixed login handler"}),
+        ];
+        let transcript: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        std::fs::write(
+            temp_dir.path().join("transcript.jsonl"),
+            transcript.join("\n") + "\nnot json\n",
+        )
+        .unwrap();
+
+        let input = json!({"transcript_path": "transcript.jsonl"});
+        let value = collect(&["transcript"], temp_dir.path(), &input).await;
+        let transcript = &value["transcript"];
+
+        assert_eq!(transcript["available"], json!(true));
+        assert_eq!(transcript["entries"], json!(4));
+        assert_eq!(transcript["user_messages"], json!(1));
+        assert_eq!(transcript["assistant_messages"], json!(1));
+        assert_eq!(transcript["tool_uses"], json!({"Edit": 1, "Read": 1}));
+        assert_eq!(transcript["files_touched"], json!(["src/login.rs"]));
+        assert_eq!(transcript["compactions"], json!(1));
+        assert_eq!(transcript["last_user_prompt"], json!("Fix the login bug"));
+        assert_eq!(transcript["last_summary"], json!("Fixed login handler"));
+
+        let missing = collect(&["transcript"], temp_dir.path(), &json!({})).await;
+        assert_eq!(missing["transcript"]["available"], json!(false));
+    }
+}
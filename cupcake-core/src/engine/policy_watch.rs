@@ -0,0 +1,168 @@
+//! Watch a `.cupcake/` directory for policy edits
+//!
+//! Long-running hosts - the language bindings - keep one engine for many
+//! evaluations, so a policy edit only takes effect once the engine is rebuilt.
+//! `PolicyWatcher` watches the directory recursively and calls back once a
+//! burst of changes has settled. The directories the engine itself writes to
+//! (`cache/`, `debug/`, `telemetry/`) are ignored, so neither a reload nor an
+//! evaluation triggers another reload.
+
+use anyhow::{Context, Result};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// How long the directory must be quiet before a change is reported
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Subdirectories of `.cupcake/` written at runtime rather than by authors
+const RUNTIME_DIRS: &[&str] = &["cache", "debug", "telemetry"];
+
+/// Watches a configuration directory until dropped
+pub struct PolicyWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+impl PolicyWatcher {
+    /// Start watching `dir`, calling `on_change` once per settled burst of edits
+    ///
+    /// The callback runs on a dedicated thread; edits made while it runs are
+    /// reported again once it returns. Dropping the watcher stops the thread.
+    pub fn start(
+        dir: &Path,
+        debounce: Duration,
+        on_change: impl Fn() + Send + 'static,
+    ) -> Result<Self> {
+        // Some backends report canonical paths, so compare against the canonical root
+        let root = dir
+            .canonicalize()
+            .with_context(|| format!("Cannot watch {}", dir.display()))?;
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            let _ = tx.send(event);
+        })
+        .context("Failed to create file watcher")?;
+        watcher
+            .watch(&root, RecursiveMode::Recursive)
+            .with_context(|| format!("Cannot watch {}", root.display()))?;
+
+        thread::Builder::new()
+            .name("cupcake-policy-watch".to_string())
+            .spawn(move || debounce_changes(rx, root, debounce, on_change))
+            .context("Failed to start policy watcher thread")?;
+
+        Ok(Self { _watcher: watcher })
+    }
+}
+
+/// Wait for a relevant change, then for `debounce` of quiet, then report it
+///
+/// Returns when the watcher is dropped and the channel closes.
+fn debounce_changes(
+    rx: Receiver<notify::Result<Event>>,
+    root: PathBuf,
+    debounce: Duration,
+    on_change: impl Fn(),
+) {
+    while let Ok(event) = rx.recv() {
+        if !is_policy_change(&event, &root) {
+            continue;
+        }
+        loop {
+            match rx.recv_timeout(debounce) {
+                Ok(_) => continue,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+        debug!("Change detected under {}", root.display());
+        on_change();
+    }
+}
+
+/// Whether an event created, modified or removed anything outside the runtime dirs
+fn is_policy_change(event: &notify::Result<Event>, root: &Path) -> bool {
+    match event {
+        Ok(event) => {
+            matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            ) && event
+                .paths
+                .iter()
+                .any(|path| !is_runtime_output(path, root))
+        }
+        Err(e) => {
+            warn!("Policy watcher error: {}", e);
+            false
+        }
+    }
+}
+
+fn is_runtime_output(path: &Path, root: &Path) -> bool {
+    path.strip_prefix(root)
+        .ok()
+        .and_then(|relative| relative.components().next())
+        .is_some_and(|first| RUNTIME_DIRS.iter().any(|dir| first.as_os_str() == *dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Instant;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_runtime_output_is_ignored() {
+        let root = Path::new("/p/.cupcake");
+        assert!(is_runtime_output(
+            Path::new("/p/.cupcake/cache/wasm/x"),
+            root
+        ));
+        assert!(is_runtime_output(Path::new("/p/.cupcake/telemetry"), root));
+        assert!(!is_runtime_output(
+            Path::new("/p/.cupcake/policies/claude/a.rego"),
+            root
+        ));
+        assert!(!is_runtime_output(
+            Path::new("/p/.cupcake/rulebook.yml"),
+            root
+        ));
+    }
+
+    #[test]
+    fn test_burst_of_edits_is_reported_once() {
+        let dir = TempDir::new().unwrap();
+        let policies = dir.path().join("policies");
+        std::fs::create_dir(&policies).unwrap();
+
+        let changes = Arc::new(AtomicUsize::new(0));
+        let counter = changes.clone();
+        let _watcher = PolicyWatcher::start(dir.path(), Duration::from_millis(200), move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+
+        for i in 0..3 {
+            std::fs::write(policies.join(format!("p{i}.rego")), "package x").unwrap();
+        }
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while changes.load(Ordering::SeqCst) == 0 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(20));
+        }
+        thread::sleep(Duration::from_millis(400));
+        assert_eq!(changes.load(Ordering::SeqCst), 1);
+
+        // Runtime output doesn't count as a change
+        std::fs::create_dir_all(dir.path().join("cache/wasm")).unwrap();
+        std::fs::write(dir.path().join("cache/wasm/module"), "wasm").unwrap();
+        thread::sleep(Duration::from_millis(600));
+        assert_eq!(changes.load(Ordering::SeqCst), 1);
+    }
+}
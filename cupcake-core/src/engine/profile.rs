@@ -0,0 +1,136 @@
+//! Evaluation profiling - times the stages of evaluating an event
+//!
+//! Used by `cupcake bench` and the criterion benchmarks to show where hook
+//! latency goes: routing, signal gathering or WASM evaluation.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::time::{Duration, Instant};
+
+use super::wasm_runtime::MemoryUsage;
+use super::{executor, Engine, PolicyUnit};
+
+/// Time spent in each stage of evaluating one event against project policies
+#[derive(Debug, Clone, Default)]
+pub struct StageTimings {
+    pub routing: Duration,
+    pub signals: Duration,
+    pub wasm: Duration,
+    /// Number of project policies the event routed to
+    pub matched_policies: usize,
+    /// Memory used by the WASM evaluation
+    pub wasm_memory: MemoryUsage,
+}
+
+impl Engine {
+    /// Run the project evaluation stages for an event, timing each one
+    ///
+    /// Mirrors the project phase of `evaluate`, but skips telemetry, rate limit
+    /// accounting and approval escalation so profiling has no side effects.
+    /// Stages after routing are skipped when no policy matches.
+    pub async fn profile_stages(&self, input: &Value) -> Result<StageTimings> {
+        let mut safe_input = input.clone();
+        crate::preprocessing::preprocess_input(
+            &mut safe_input,
+            &crate::preprocessing::PreprocessConfig::default(),
+            self.config.harness,
+        );
+
+        let event_name = safe_input
+            .get("hookEventName")
+            .or_else(|| safe_input.get("hook_event_name"))
+            .and_then(|v| v.as_str())
+            .context("Missing hookEventName/hook_event_name in input")?;
+        let tool_name = safe_input.get("tool_name").and_then(|v| v.as_str());
+
+        let mut timings = StageTimings::default();
+
+        let start = Instant::now();
+        let matched: Vec<PolicyUnit> = self
+            .route_event(event_name, tool_name)
+            .into_iter()
+            .cloned()
+            .collect();
+        timings.routing = start.elapsed();
+        timings.matched_policies = matched.len();
+
+        if matched.is_empty() {
+            return Ok(timings);
+        }
+
+        let exec = executor::Executor {
+            rulebook: self.rulebook.as_ref(),
+            global_rulebook: self.global_rulebook.as_ref(),
+            watchdog: self.watchdog.as_ref(),
+            working_dir: &self.paths.root,
+        };
+        let start = Instant::now();
+        let enriched_input = exec.gather_signals(&safe_input, &matched, None).await?;
+        timings.signals = start.elapsed();
+
+        let start = Instant::now();
+        let (_, wasm_memory) = self.evaluate_decision_set(&enriched_input).await?;
+        timings.wasm = start.elapsed();
+        timings.wasm_memory = wasm_memory;
+
+        Ok(timings)
+    }
+}
+
+/// Summary statistics over latency samples
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct LatencyStats {
+    pub samples: usize,
+    pub min_us: u64,
+    pub mean_us: u64,
+    pub p50_us: u64,
+    pub p95_us: u64,
+    pub max_us: u64,
+}
+
+impl LatencyStats {
+    /// Compute statistics from samples in any order
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        let mut micros: Vec<u64> = samples.iter().map(|d| d.as_micros() as u64).collect();
+        micros.sort_unstable();
+        // Nearest-rank percentile
+        let percentile = |p: usize| micros[(micros.len() * p).div_ceil(100).max(1) - 1];
+
+        Self {
+            samples: micros.len(),
+            min_us: micros[0],
+            mean_us: micros.iter().sum::<u64>() / micros.len() as u64,
+            p50_us: percentile(50),
+            p95_us: percentile(95),
+            max_us: micros[micros.len() - 1],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_stats() {
+        let samples: Vec<Duration> = (1..=100).rev().map(Duration::from_micros).collect();
+        let stats = LatencyStats::from_samples(&samples);
+
+        assert_eq!(stats.samples, 100);
+        assert_eq!(stats.min_us, 1);
+        assert_eq!(stats.max_us, 100);
+        assert_eq!(stats.mean_us, 50);
+        assert_eq!(stats.p50_us, 50);
+        assert_eq!(stats.p95_us, 95);
+    }
+
+    #[test]
+    fn test_latency_stats_empty() {
+        assert_eq!(LatencyStats::from_samples(&[]).samples, 0);
+    }
+}
@@ -0,0 +1,273 @@
+//! Rate limiting - per-session invocation counters for the rate_limit builtin
+//!
+//! Every hook invocation is a separate process, so counts are kept in a small
+//! session state file under `.cupcake/cache/rate_limits/<session_id>.json`.
+//! Before project policies are evaluated, the engine records the current event
+//! against each matching limit and injects the resulting counts as
+//! `input.builtin_config.rate_limit.usage`:
+//!
+//! ```json
+//! { "git_push": { "count": 4, "max": 3, "per": "session", "limit": "3 per session" } }
+//! ```
+//!
+//! Attempts are counted, not successes - a blocked invocation still uses up
+//! budget, so an agent cannot retry its way past a limit.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+use super::builtins::{RateLimitConfig, RateLimitRule, RateWindow};
+
+/// Session used when the harness does not provide a session_id
+const DEFAULT_SESSION: &str = "default";
+
+/// Invocation timestamps for one session, keyed by limit name
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SessionUsage {
+    invocations: BTreeMap<String, Vec<i64>>,
+}
+
+/// Records invocations and reports usage against configured limits
+pub struct RateLimiter {
+    state_dir: PathBuf,
+    // Serializes read-modify-write cycles within this process
+    lock: Mutex<()>,
+}
+
+impl RateLimiter {
+    pub fn new(cupcake_dir: &Path) -> Self {
+        Self {
+            state_dir: cupcake_dir.join("cache").join("rate_limits"),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Record this event against every matching limit and return the usage
+    /// object for the policy. Returns an empty object for non-tool events.
+    pub async fn record(&self, config: &RateLimitConfig, input: &Value) -> Value {
+        let matching: Vec<&RateLimitRule> = config
+            .limits
+            .iter()
+            .filter(|rule| rule_matches(rule, input))
+            .collect();
+        if matching.is_empty() {
+            return Value::Object(Map::new());
+        }
+
+        let _guard = self.lock.lock().await;
+        let path = self.state_path(input);
+        let mut usage: SessionUsage = match tokio::fs::read(&path).await {
+            Ok(raw) => serde_json::from_slice(&raw).unwrap_or_default(),
+            Err(_) => SessionUsage::default(),
+        };
+
+        let now = chrono::Utc::now().timestamp();
+        let mut report = Map::new();
+        for rule in matching {
+            let timestamps = usage.invocations.entry(rule.name.clone()).or_default();
+            if let Some(window) = window_seconds(rule.per) {
+                timestamps.retain(|t| now - t < window);
+            }
+            timestamps.push(now);
+            debug!(
+                "Rate limit '{}': {} of {} per {:?}",
+                rule.name,
+                timestamps.len(),
+                rule.max,
+                rule.per
+            );
+            report.insert(
+                rule.name.clone(),
+                json!({
+                    "count": timestamps.len(),
+                    "max": rule.max,
+                    "per": rule.per,
+                    "limit": format!("{} per {}", rule.max, window_name(rule.per)),
+                }),
+            );
+        }
+
+        if let Err(e) = self.save(&path, &usage).await {
+            warn!("Failed to persist rate limit state: {}", e);
+        }
+        Value::Object(report)
+    }
+
+    fn state_path(&self, input: &Value) -> PathBuf {
+        let session: String = input
+            .get("session_id")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+            .collect();
+        let session = if session.is_empty() {
+            DEFAULT_SESSION
+        } else {
+            &session
+        };
+        self.state_dir.join(format!("{session}.json"))
+    }
+
+    async fn save(&self, path: &Path, usage: &SessionUsage) -> Result<()> {
+        tokio::fs::create_dir_all(&self.state_dir).await?;
+        tokio::fs::write(path, serde_json::to_vec(usage)?).await?;
+        Ok(())
+    }
+}
+
+fn window_seconds(per: RateWindow) -> Option<i64> {
+    match per {
+        RateWindow::Session => None,
+        RateWindow::Minute => Some(60),
+        RateWindow::Hour => Some(3600),
+    }
+}
+
+fn window_name(per: RateWindow) -> &'static str {
+    match per {
+        RateWindow::Session => "session",
+        RateWindow::Minute => "minute",
+        RateWindow::Hour => "hour",
+    }
+}
+
+/// Whether an event counts against a limit
+///
+/// Cursor's shell events carry no tool name and are treated as `Bash`, so the
+/// same limits work across harnesses.
+fn rule_matches(rule: &RateLimitRule, input: &Value) -> bool {
+    let event = input
+        .get("hook_event_name")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default();
+    let tool = match event {
+        "PreToolUse" => input.get("tool_name").and_then(|v| v.as_str()),
+        "beforeShellExecution" => Some("Bash"),
+        "beforeMCPExecution" => input.get("tool_name").and_then(|v| v.as_str()),
+        _ => return false,
+    };
+
+    if !rule.tools.is_empty() && !tool.is_some_and(|t| rule.tools.iter().any(|r| r == t)) {
+        return false;
+    }
+
+    match &rule.command {
+        None => true,
+        Some(phrase) => input
+            .get("tool_input")
+            .and_then(|t| t.get("command"))
+            .or_else(|| input.get("command"))
+            .and_then(|v| v.as_str())
+            .is_some_and(|command| contains_phrase(command, phrase)),
+    }
+}
+
+/// Word-aligned, case-insensitive phrase match ("git push" matches
+/// "cd repo && git  push origin" but not "git pushd")
+fn contains_phrase(command: &str, phrase: &str) -> bool {
+    let words = |s: &str| -> Vec<String> { s.split_whitespace().map(str::to_lowercase).collect() };
+    let command = words(command);
+    let phrase = words(phrase);
+    !phrase.is_empty()
+        && command
+            .windows(phrase.len())
+            .any(|w| w == phrase.as_slice())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limit(
+        name: &str,
+        tools: &[&str],
+        command: Option<&str>,
+        max: u32,
+        per: RateWindow,
+    ) -> RateLimitRule {
+        RateLimitRule {
+            name: name.to_string(),
+            tools: tools.iter().map(|t| t.to_string()).collect(),
+            command: command.map(String::from),
+            max,
+            per,
+        }
+    }
+
+    #[test]
+    fn test_rule_matching() {
+        let push = limit("git_push", &[], Some("git push"), 3, RateWindow::Session);
+        let shell = limit("shell", &["Bash"], None, 20, RateWindow::Minute);
+
+        let bash = json!({
+            "hook_event_name": "PreToolUse",
+            "tool_name": "Bash",
+            "tool_input": {"command": "cd repo && git  push origin main"}
+        });
+        assert!(rule_matches(&push, &bash));
+        assert!(rule_matches(&shell, &bash));
+
+        let pushd = json!({
+            "hook_event_name": "PreToolUse",
+            "tool_name": "Bash",
+            "tool_input": {"command": "git pushd"}
+        });
+        assert!(!rule_matches(&push, &pushd));
+
+        let cursor = json!({"hook_event_name": "beforeShellExecution", "command": "git push"});
+        assert!(rule_matches(&push, &cursor));
+        assert!(rule_matches(&shell, &cursor));
+
+        let prompt = json!({"hook_event_name": "UserPromptSubmit", "prompt": "git push"});
+        assert!(!rule_matches(&push, &prompt));
+    }
+
+    #[tokio::test]
+    async fn test_counts_persist_per_session() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config = RateLimitConfig {
+            enabled: true,
+            limits: vec![limit(
+                "git_push",
+                &[],
+                Some("git push"),
+                2,
+                RateWindow::Session,
+            )],
+            action: Default::default(),
+            message: String::new(),
+        };
+        let event = |session: &str| {
+            json!({
+                "hook_event_name": "PreToolUse",
+                "tool_name": "Bash",
+                "session_id": session,
+                "tool_input": {"command": "git push"}
+            })
+        };
+
+        RateLimiter::new(dir.path())
+            .record(&config, &event("a"))
+            .await;
+        RateLimiter::new(dir.path())
+            .record(&config, &event("a"))
+            .await;
+        // A fresh limiter (new hook process) sees the persisted count
+        let usage = RateLimiter::new(dir.path())
+            .record(&config, &event("a"))
+            .await;
+        assert_eq!(usage["git_push"]["count"], 3);
+        assert_eq!(usage["git_push"]["limit"], "2 per session");
+
+        let other = RateLimiter::new(dir.path())
+            .record(&config, &event("b"))
+            .await;
+        assert_eq!(other["git_push"]["count"], 1);
+    }
+}
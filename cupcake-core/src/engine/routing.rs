@@ -2,6 +2,10 @@
 //!
 //! Uses metadata-driven routing where policies declare their requirements
 //! (events, tools) and the engine builds an index for instant lookup.
+//!
+//! MCP tools (`mcp__<server>__<tool>`) can also be routed by prefix:
+//! `required_tools: ["mcp__github__*"]` matches every tool on the `github`
+//! server and `["mcp__*"]` matches every MCP tool.
 
 use super::RoutingDirective;
 
@@ -40,6 +44,37 @@ pub fn create_event_key(event_name: &str, tool_name: Option<&str>) -> String {
     }
 }
 
+/// Prefix of MCP tool names as reported by Claude Code and Factory AI
+pub const MCP_TOOL_PREFIX: &str = "mcp__";
+
+/// Split an MCP tool name (`mcp__<server>__<tool>`) into server and tool
+pub fn parse_mcp_tool_name(tool_name: &str) -> Option<(&str, &str)> {
+    let (server, tool) = tool_name.strip_prefix(MCP_TOOL_PREFIX)?.split_once("__")?;
+    if server.is_empty() || tool.is_empty() {
+        return None;
+    }
+    Some((server, tool))
+}
+
+/// Routing keys to look up for an event, most specific first
+///
+/// Besides the exact key, tool events also match `Event:*` wildcards, and MCP
+/// tools match their server (`Event:mcp__server__*`) and `Event:mcp__*`. The
+/// event-only key is handled by the callers.
+pub fn lookup_keys(event_name: &str, tool_name: Option<&str>) -> Vec<String> {
+    let mut keys = vec![create_event_key(event_name, tool_name)];
+    if let Some(tool) = tool_name {
+        if let Some((server, _)) = parse_mcp_tool_name(tool) {
+            keys.push(format!("{event_name}:{MCP_TOOL_PREFIX}{server}__*"));
+        }
+        if tool.starts_with(MCP_TOOL_PREFIX) {
+            keys.push(format!("{event_name}:{MCP_TOOL_PREFIX}*"));
+        }
+        keys.push(format!("{event_name}:*"));
+    }
+    keys
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -50,6 +85,7 @@ mod tests {
             required_events: vec!["UserPromptSubmit".to_string()],
             required_tools: vec![],
             required_signals: vec![],
+            required_watchdog: false,
         };
 
         let keys = create_routing_key_from_metadata(&directive);
@@ -62,6 +98,7 @@ mod tests {
             required_events: vec!["PreToolUse".to_string()],
             required_tools: vec!["Bash".to_string()],
             required_signals: vec![],
+            required_watchdog: false,
         };
 
         let keys = create_routing_key_from_metadata(&directive);
@@ -74,6 +111,7 @@ mod tests {
             required_events: vec!["PreToolUse".to_string()],
             required_tools: vec!["*".to_string()],
             required_signals: vec![],
+            required_watchdog: false,
         };
 
         let keys = create_routing_key_from_metadata(&directive);
@@ -86,6 +124,7 @@ mod tests {
             required_events: vec!["PreToolUse".to_string()],
             required_tools: vec!["Bash".to_string(), "Shell".to_string(), "Exec".to_string()],
             required_signals: vec![],
+            required_watchdog: false,
         };
 
         let keys = create_routing_key_from_metadata(&directive);
@@ -101,6 +140,7 @@ mod tests {
             required_events: vec!["PreToolUse".to_string(), "PostToolUse".to_string()],
             required_tools: vec!["Bash".to_string()],
             required_signals: vec![],
+            required_watchdog: false,
         };
 
         let keys = create_routing_key_from_metadata(&directive);
@@ -108,4 +148,36 @@ mod tests {
         assert!(keys.contains(&"PreToolUse:Bash".to_string()));
         assert!(keys.contains(&"PostToolUse:Bash".to_string()));
     }
+
+    #[test]
+    fn test_parse_mcp_tool_name() {
+        assert_eq!(
+            parse_mcp_tool_name("mcp__github__create_issue"),
+            Some(("github", "create_issue"))
+        );
+        assert_eq!(
+            parse_mcp_tool_name("mcp__my_server__read__all"),
+            Some(("my_server", "read__all"))
+        );
+        assert_eq!(parse_mcp_tool_name("mcp__github"), None);
+        assert_eq!(parse_mcp_tool_name("Bash"), None);
+    }
+
+    #[test]
+    fn test_lookup_keys_for_mcp_tool() {
+        assert_eq!(
+            lookup_keys("PreToolUse", Some("mcp__github__create_issue")),
+            vec![
+                "PreToolUse:mcp__github__create_issue",
+                "PreToolUse:mcp__github__*",
+                "PreToolUse:mcp__*",
+                "PreToolUse:*",
+            ]
+        );
+        assert_eq!(
+            lookup_keys("PreToolUse", Some("Bash")),
+            vec!["PreToolUse:Bash", "PreToolUse:*"]
+        );
+        assert_eq!(lookup_keys("Stop", None), vec!["Stop"]);
+    }
 }
@@ -24,6 +24,7 @@ pub struct SimplifiedPolicyInfo {
     pub required_events: Vec<String>,
     pub required_tools: Vec<String>,
     pub required_signals: Vec<String>,
+    pub required_watchdog: bool,
 }
 
 impl From<&PolicyUnit> for SimplifiedPolicyInfo {
@@ -34,6 +35,7 @@ impl From<&PolicyUnit> for SimplifiedPolicyInfo {
             required_events: policy.routing.required_events.clone(),
             required_tools: policy.routing.required_tools.clone(),
             required_signals: policy.routing.required_signals.clone(),
+            required_watchdog: policy.routing.required_watchdog,
         }
     }
 }
@@ -54,6 +56,17 @@ pub struct RoutingMapSection {
     pub routing_entries: HashMap<String, Vec<SimplifiedPolicyInfo>>,
 }
 
+/// Policies one tier routes an event to
+#[derive(Debug, Serialize)]
+pub struct TierRoute {
+    /// Tier name: "global", "team" or "project"
+    pub tier: &'static str,
+    /// Routing keys looked up, in order
+    pub keys_consulted: Vec<String>,
+    /// Policies matched by those keys
+    pub policies: Vec<SimplifiedPolicyInfo>,
+}
+
 /// Routing statistics for analysis
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RoutingStatistics {
@@ -90,6 +103,40 @@ impl Engine {
         Ok(())
     }
 
+    /// Explain which policies each tier routes an event to
+    ///
+    /// Uses the same lookups as evaluation, so the result shows exactly which
+    /// policies would be evaluated for the event/tool pair. Tiers are returned
+    /// in evaluation order: global, team, project.
+    pub fn explain_route(&self, event_name: &str, tool_name: Option<&str>) -> Vec<TierRoute> {
+        let mut keys_consulted = super::routing::lookup_keys(event_name, tool_name);
+        if tool_name.is_some() {
+            // Event-only policies act as wildcards for tool events
+            keys_consulted.push(event_name.to_string());
+        }
+
+        let tiers = [
+            ("global", self.route_global_event(event_name, tool_name)),
+            (
+                "team",
+                Self::route_in_map(&self.team_routing_map, event_name, tool_name),
+            ),
+            ("project", self.route_event(event_name, tool_name)),
+        ];
+
+        tiers
+            .into_iter()
+            .map(|(tier, policies)| TierRoute {
+                tier,
+                keys_consulted: keys_consulted.clone(),
+                policies: policies
+                    .into_iter()
+                    .map(SimplifiedPolicyInfo::from)
+                    .collect(),
+            })
+            .collect()
+    }
+
     /// Write JSON dump for programmatic analysis
     fn write_json_dump(&self, debug_dir: &Path, timestamp: &impl std::fmt::Display) -> Result<()> {
         let json_file = debug_dir.join(format!("routing_map_{timestamp}.json"));
@@ -246,6 +293,10 @@ impl Engine {
                         policy.routing.required_signals.join(", ")
                     ));
                 }
+
+                if policy.routing.required_watchdog {
+                    output.push_str("       Watchdog: required\n");
+                }
             }
             output.push('\n');
         }
@@ -0,0 +1,145 @@
+//! Routing diff - compares the routing surface of two policy sets
+//!
+//! Reports which policies and which routes (event/tool → policy) were added
+//! or removed between a base revision and the current policies, so reviewers
+//! can see how a change affects which policies fire.
+
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+use super::routing::create_routing_key_from_metadata;
+use super::routing_graph::RoutedPolicy;
+
+/// A single route: a routing key and the policy it reaches
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Route {
+    /// Routing key (`Event`, `Event:Tool` or `Event:*`)
+    pub key: String,
+    pub package: String,
+}
+
+/// Differences between a base and a current set of policies
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct RoutingDiff {
+    pub added_policies: Vec<String>,
+    pub removed_policies: Vec<String>,
+    pub added_routes: Vec<Route>,
+    pub removed_routes: Vec<Route>,
+}
+
+impl RoutingDiff {
+    /// Compare base policies against current ones
+    pub fn between(base: &[RoutedPolicy], current: &[RoutedPolicy]) -> Self {
+        let base_packages = packages(base);
+        let current_packages = packages(current);
+        let base_routes = routes(base);
+        let current_routes = routes(current);
+
+        Self {
+            added_policies: current_packages
+                .difference(&base_packages)
+                .cloned()
+                .collect(),
+            removed_policies: base_packages
+                .difference(&current_packages)
+                .cloned()
+                .collect(),
+            added_routes: current_routes.difference(&base_routes).cloned().collect(),
+            removed_routes: base_routes.difference(&current_routes).cloned().collect(),
+        }
+    }
+
+    /// True when nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.added_policies.is_empty()
+            && self.removed_policies.is_empty()
+            && self.added_routes.is_empty()
+            && self.removed_routes.is_empty()
+    }
+}
+
+fn packages(policies: &[RoutedPolicy]) -> BTreeSet<String> {
+    policies.iter().map(|p| p.package_name.clone()).collect()
+}
+
+/// Routes of every policy; variants sharing a package contribute to the same set
+fn routes(policies: &[RoutedPolicy]) -> BTreeSet<Route> {
+    policies
+        .iter()
+        .filter_map(|p| p.routing.as_ref().map(|r| (p, r)))
+        .flat_map(|(policy, routing)| {
+            create_routing_key_from_metadata(routing)
+                .into_iter()
+                .map(|key| Route {
+                    key,
+                    package: policy.package_name.clone(),
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::metadata::RoutingDirective;
+
+    fn routed(package: &str, events: &[&str], tools: &[&str]) -> RoutedPolicy {
+        RoutedPolicy {
+            package_name: package.to_string(),
+            routing: Some(RoutingDirective {
+                required_events: events.iter().map(|s| s.to_string()).collect(),
+                required_tools: tools.iter().map(|s| s.to_string()).collect(),
+                required_signals: vec![],
+                required_watchdog: false,
+            }),
+        }
+    }
+
+    fn route(key: &str, package: &str) -> Route {
+        Route {
+            key: key.to_string(),
+            package: package.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_routing_diff() {
+        let base = vec![
+            routed(
+                "cupcake.policies.guard",
+                &["PreToolUse"],
+                &["Bash", "Write"],
+            ),
+            routed("cupcake.policies.old", &["Stop"], &[]),
+        ];
+        let current = vec![
+            routed("cupcake.policies.guard", &["PreToolUse"], &["Bash", "Edit"]),
+            routed("cupcake.policies.new", &["UserPromptSubmit"], &[]),
+        ];
+
+        let diff = RoutingDiff::between(&base, &current);
+
+        assert_eq!(diff.added_policies, vec!["cupcake.policies.new"]);
+        assert_eq!(diff.removed_policies, vec!["cupcake.policies.old"]);
+        assert_eq!(
+            diff.added_routes,
+            vec![
+                route("PreToolUse:Edit", "cupcake.policies.guard"),
+                route("UserPromptSubmit", "cupcake.policies.new"),
+            ]
+        );
+        assert_eq!(
+            diff.removed_routes,
+            vec![
+                route("PreToolUse:Write", "cupcake.policies.guard"),
+                route("Stop", "cupcake.policies.old"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_identical_policies_have_empty_diff() {
+        let policies = vec![routed("cupcake.policies.guard", &["PreToolUse"], &["*"])];
+        assert!(RoutingDiff::between(&policies, &policies).is_empty());
+    }
+}
@@ -0,0 +1,349 @@
+//! Routing graph rendering - shows which policies fire for which hooks
+//!
+//! Renders the routing map as a Graphviz DOT or Mermaid flowchart with
+//! events → tools → policies edges. Policies are grouped by configuration
+//! layer (global, team, project) so it's clear where each one comes from.
+//!
+//! Works from parsed metadata alone, so it needs neither OPA nor a built engine.
+
+use std::collections::BTreeSet;
+
+use super::metadata::RoutingDirective;
+use super::routing::create_routing_key_from_metadata;
+
+/// Output format for the routing graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+}
+
+/// A policy and its routing directive
+#[derive(Debug, Clone)]
+pub struct RoutedPolicy {
+    pub package_name: String,
+    /// `None` for policies without routing metadata
+    pub routing: Option<RoutingDirective>,
+}
+
+/// Routing graph across configuration layers
+#[derive(Debug, Default)]
+pub struct RoutingGraph {
+    layers: Vec<(String, Vec<RoutedPolicy>)>,
+}
+
+/// Edges collected from the routing keys of every layer
+#[derive(Default)]
+struct Edges {
+    events: BTreeSet<String>,
+    /// (event, tool) pairs; tool nodes are per event
+    tools: BTreeSet<(String, String)>,
+    /// (from node, to node, wildcard)
+    links: BTreeSet<(String, String, bool)>,
+}
+
+impl RoutingGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a layer of policies, in evaluation order
+    pub fn add_layer(&mut self, name: &str, policies: Vec<RoutedPolicy>) {
+        self.layers.push((name.to_string(), policies));
+    }
+
+    /// Render the graph in the given format
+    pub fn render(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => self.to_dot(),
+            GraphFormat::Mermaid => self.to_mermaid(),
+        }
+    }
+
+    fn edges(&self) -> Edges {
+        let mut edges = Edges::default();
+
+        for (layer, policies) in &self.layers {
+            for policy in policies {
+                let Some(routing) = &policy.routing else {
+                    continue;
+                };
+                let policy_node = policy_id(layer, &policy.package_name);
+
+                for key in create_routing_key_from_metadata(routing) {
+                    match key.split_once(':') {
+                        Some((event, "*")) => {
+                            edges.events.insert(event.to_string());
+                            edges
+                                .links
+                                .insert((event_id(event), policy_node.clone(), true));
+                        }
+                        Some((event, tool)) => {
+                            edges.events.insert(event.to_string());
+                            edges.tools.insert((event.to_string(), tool.to_string()));
+                            edges
+                                .links
+                                .insert((event_id(event), tool_id(event, tool), false));
+                            edges
+                                .links
+                                .insert((tool_id(event, tool), policy_node.clone(), false));
+                        }
+                        None => {
+                            edges.events.insert(key.clone());
+                            edges
+                                .links
+                                .insert((event_id(&key), policy_node.clone(), false));
+                        }
+                    }
+                }
+            }
+        }
+
+        edges
+    }
+
+    /// Generate Graphviz DOT format
+    fn to_dot(&self) -> String {
+        let edges = self.edges();
+        let mut dot = String::new();
+
+        dot.push_str("digraph RoutingMap {\n");
+        dot.push_str("  rankdir=LR;\n");
+        dot.push_str("  node [shape=box, style=rounded];\n\n");
+
+        dot.push_str("  subgraph cluster_events {\n");
+        dot.push_str("    label=\"Events\";\n");
+        dot.push_str("    node [shape=ellipse, style=filled, fillcolor=lightyellow];\n");
+        for event in &edges.events {
+            dot.push_str(&format!("    {} [label=\"{event}\"];\n", event_id(event)));
+        }
+        dot.push_str("  }\n\n");
+
+        if !edges.tools.is_empty() {
+            dot.push_str("  subgraph cluster_tools {\n");
+            dot.push_str("    label=\"Tools\";\n");
+            dot.push_str("    node [shape=diamond, style=filled, fillcolor=lightgreen];\n");
+            for (event, tool) in &edges.tools {
+                dot.push_str(&format!(
+                    "    {} [label=\"{tool}\"];\n",
+                    tool_id(event, tool)
+                ));
+            }
+            dot.push_str("  }\n\n");
+        }
+
+        for (layer, policies) in &self.layers {
+            if policies.is_empty() {
+                continue;
+            }
+            dot.push_str(&format!("  subgraph cluster_{} {{\n", sanitize(layer)));
+            dot.push_str(&format!("    label=\"{} policies\";\n", capitalize(layer)));
+            dot.push_str("    node [shape=box, style=filled, fillcolor=lightblue];\n");
+            for package in packages(policies) {
+                dot.push_str(&format!(
+                    "    {} [label=\"{}\"];\n",
+                    policy_id(layer, package),
+                    short_name(package)
+                ));
+            }
+            dot.push_str("  }\n\n");
+        }
+
+        for (from, to, wildcard) in &edges.links {
+            if *wildcard {
+                dot.push_str(&format!("  {from} -> {to} [label=\"*\", style=dashed];\n"));
+            } else {
+                dot.push_str(&format!("  {from} -> {to};\n"));
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Generate a Mermaid flowchart
+    fn to_mermaid(&self) -> String {
+        let edges = self.edges();
+        let mut mermaid = String::new();
+
+        mermaid.push_str("flowchart LR\n");
+
+        mermaid.push_str("  subgraph events [Events]\n");
+        for event in &edges.events {
+            mermaid.push_str(&format!("    {}([\"{event}\"])\n", event_id(event)));
+        }
+        mermaid.push_str("  end\n");
+
+        if !edges.tools.is_empty() {
+            mermaid.push_str("  subgraph tools [Tools]\n");
+            for (event, tool) in &edges.tools {
+                mermaid.push_str(&format!("    {}{{\"{tool}\"}}\n", tool_id(event, tool)));
+            }
+            mermaid.push_str("  end\n");
+        }
+
+        for (layer, policies) in &self.layers {
+            if policies.is_empty() {
+                continue;
+            }
+            mermaid.push_str(&format!(
+                "  subgraph {} [{} policies]\n",
+                sanitize(layer),
+                capitalize(layer)
+            ));
+            for package in packages(policies) {
+                mermaid.push_str(&format!(
+                    "    {}[\"{}\"]\n",
+                    policy_id(layer, package),
+                    short_name(package)
+                ));
+            }
+            mermaid.push_str("  end\n");
+        }
+
+        for (from, to, wildcard) in &edges.links {
+            if *wildcard {
+                mermaid.push_str(&format!("  {from} -.->|*| {to}\n"));
+            } else {
+                mermaid.push_str(&format!("  {from} --> {to}\n"));
+            }
+        }
+
+        mermaid
+    }
+}
+
+/// Replace characters that aren't valid in DOT/Mermaid identifiers
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn event_id(event: &str) -> String {
+    format!("event_{}", sanitize(event))
+}
+
+fn tool_id(event: &str, tool: &str) -> String {
+    format!("tool_{}_{}", sanitize(event), sanitize(tool))
+}
+
+fn policy_id(layer: &str, package: &str) -> String {
+    format!("policy_{}_{}", sanitize(layer), sanitize(package))
+}
+
+fn short_name(package: &str) -> &str {
+    package
+        .trim_start_matches("cupcake.global.policies.")
+        .trim_start_matches("cupcake.policies.")
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Unique package names in a layer
+///
+/// Per-harness variants of a policy often share a package; each package is
+/// drawn once per layer, with edges from every variant's routing.
+fn packages(policies: &[RoutedPolicy]) -> BTreeSet<&str> {
+    policies.iter().map(|p| p.package_name.as_str()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn routed(package: &str, events: &[&str], tools: &[&str]) -> RoutedPolicy {
+        RoutedPolicy {
+            package_name: package.to_string(),
+            routing: Some(RoutingDirective {
+                required_events: events.iter().map(|s| s.to_string()).collect(),
+                required_tools: tools.iter().map(|s| s.to_string()).collect(),
+                required_signals: vec![],
+                required_watchdog: false,
+            }),
+        }
+    }
+
+    fn graph() -> RoutingGraph {
+        let mut graph = RoutingGraph::new();
+        graph.add_layer(
+            "global",
+            vec![routed(
+                "cupcake.global.policies.audit",
+                &["PreToolUse"],
+                &["*"],
+            )],
+        );
+        graph.add_layer(
+            "project",
+            vec![
+                routed("cupcake.policies.bash_guard", &["PreToolUse"], &["Bash"]),
+                routed("cupcake.policies.prompt", &["UserPromptSubmit"], &[]),
+                RoutedPolicy {
+                    package_name: "cupcake.policies.unrouted".to_string(),
+                    routing: None,
+                },
+            ],
+        );
+        graph
+    }
+
+    #[test]
+    fn test_dot_graph() {
+        let dot = graph().render(GraphFormat::Dot);
+
+        assert!(dot.starts_with("digraph RoutingMap {"));
+        assert!(dot.contains("event_PreToolUse [label=\"PreToolUse\"];"));
+        assert!(dot.contains("tool_PreToolUse_Bash [label=\"Bash\"];"));
+        assert!(dot.contains("subgraph cluster_global {"));
+        assert!(dot.contains("label=\"Project policies\";"));
+        assert!(dot.contains("event_PreToolUse -> tool_PreToolUse_Bash;"));
+        assert!(dot.contains("tool_PreToolUse_Bash -> policy_project_cupcake_policies_bash_guard;"));
+        assert!(dot.contains(
+            "event_PreToolUse -> policy_global_cupcake_global_policies_audit [label=\"*\", style=dashed];"
+        ));
+        assert!(dot.contains("event_UserPromptSubmit -> policy_project_cupcake_policies_prompt;"));
+        // Unrouted policies are drawn without edges
+        assert!(dot.contains("policy_project_cupcake_policies_unrouted [label=\"unrouted\"];"));
+        assert!(!dot.contains("-> policy_project_cupcake_policies_unrouted"));
+    }
+
+    #[test]
+    fn test_mermaid_graph() {
+        let mermaid = graph().render(GraphFormat::Mermaid);
+
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("event_PreToolUse([\"PreToolUse\"])"));
+        assert!(mermaid.contains("tool_PreToolUse_Bash{\"Bash\"}"));
+        assert!(mermaid.contains("subgraph global [Global policies]"));
+        assert!(mermaid.contains("policy_global_cupcake_global_policies_audit[\"audit\"]"));
+        assert!(mermaid.contains("event_PreToolUse --> tool_PreToolUse_Bash"));
+        assert!(mermaid
+            .contains("event_PreToolUse -.->|*| policy_global_cupcake_global_policies_audit"));
+    }
+
+    #[test]
+    fn test_shared_package_drawn_once() {
+        let mut graph = RoutingGraph::new();
+        graph.add_layer(
+            "project",
+            vec![
+                routed("cupcake.policies.guard", &["PreToolUse"], &["Bash"]),
+                routed("cupcake.policies.guard", &["beforeShellExecution"], &[]),
+            ],
+        );
+        let dot = graph.render(GraphFormat::Dot);
+
+        assert_eq!(dot.matches("[label=\"guard\"]").count(), 1);
+        assert!(
+            dot.contains("event_beforeShellExecution -> policy_project_cupcake_policies_guard;")
+        );
+        assert!(!dot.contains("tool_beforeShellExecution_Bash"));
+    }
+}
@@ -8,7 +8,18 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use tracing::{debug, info};
 
+use super::approvals::ApprovalsConfig;
 use super::builtins::BuiltinsConfig;
+use super::command::{CommandExecutor, CommandSpec};
+use super::external_data::ExternalSourceConfig;
+use super::identity::{self, IdentityConfig};
+use super::messages::{self, MessageConfig};
+use super::sandbox::{ResourceLimits, SandboxConfig};
+use super::severity_mapping::{self, SeverityRule};
+use crate::harness::exit_code::OutputMode;
+use crate::harness::passthrough::{self, PassthroughRule};
+use crate::harness::permission_suggestions::PermissionSuggestionsConfig;
+use crate::harness::types::HarnessType;
 use crate::watchdog::{WatchdogConfig, WatchdogConfigInput};
 
 /// Telemetry output format
@@ -20,6 +31,17 @@ pub enum TelemetryFormat {
     Text,
 }
 
+/// What to do with a policy that fails to parse or compile
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OnPolicyError {
+    /// Exclude the broken policy with a warning and load the rest
+    #[default]
+    Skip,
+    /// Refuse to load the tier
+    Fail,
+}
+
 /// Telemetry configuration for SOC/SIEM integration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct TelemetryConfig {
@@ -38,18 +60,41 @@ pub struct TelemetryConfig {
 /// Signal configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignalConfig {
-    /// Command to execute for this signal
-    pub command: String,
+    /// Command to execute for this signal - a shell string, argv array or
+    /// pipeline (see [`super::command`])
+    pub command: CommandSpec,
 
     /// Timeout in seconds (optional, default 5)
     #[serde(default = "default_timeout")]
     pub timeout_seconds: u64,
+
+    /// Maximum bytes of stdout kept from this signal (optional, default 1MB)
+    ///
+    /// Anything beyond the limit is discarded and the signal value becomes a
+    /// truncation marker object instead of the parsed output.
+    #[serde(default = "default_max_output_bytes")]
+    pub max_output_bytes: usize,
+
+    /// Environment, working directory and network restrictions (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<SandboxConfig>,
+
+    /// CPU, memory and file size limits, enforced on Unix (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<ResourceLimits>,
 }
 
 fn default_timeout() -> u64 {
     5
 }
 
+/// Default per-signal output cap - well under the default 10MB WASM memory limit
+pub const DEFAULT_MAX_OUTPUT_BYTES: usize = 1024 * 1024;
+
+fn default_max_output_bytes() -> usize {
+    DEFAULT_MAX_OUTPUT_BYTES
+}
+
 /// The rulebook configuration structure
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Rulebook {
@@ -80,6 +125,43 @@ pub struct Rulebook {
     /// Telemetry configuration for SOC/SIEM integration
     #[serde(default)]
     pub telemetry: TelemetryConfig,
+
+    /// Human-in-the-loop approver for Ask decisions
+    #[serde(default)]
+    pub approvals: ApprovalsConfig,
+
+    /// External data sources, exposed to policies as `data.external.<name>`
+    #[serde(default)]
+    pub external_data: HashMap<String, ExternalSourceConfig>,
+
+    /// Resolve the user behind each event and the roles they hold
+    #[serde(default)]
+    pub identity: IdentityConfig,
+
+    /// Remap decision verbs by severity, per harness, environment or role
+    #[serde(default)]
+    pub severity_mapping: Vec<SeverityRule>,
+
+    /// Reword or translate block and ask messages by rule_id
+    #[serde(default)]
+    pub messages: MessageConfig,
+
+    /// Whether allow and add_context results override or defer to the harness
+    #[serde(default)]
+    pub response_passthrough: Vec<PassthroughRule>,
+
+    /// Suggest Claude Code permission rules for asked and denied tool calls
+    #[serde(default)]
+    pub permission_suggestions: PermissionSuggestionsConfig,
+
+    /// Answer a harness with exit codes instead of JSON (`exit-code`), per harness
+    #[serde(default)]
+    pub output_mode: HashMap<HarnessType, OutputMode>,
+
+    /// Exclude policies that fail to parse or compile (`skip`), or refuse to
+    /// load the tier (`fail`)
+    #[serde(default)]
+    pub on_policy_error: OnPolicyError,
 }
 
 /// Custom deserializer for watchdog config that handles both `true` and full object
@@ -91,40 +173,165 @@ where
     Ok(input.into())
 }
 
+/// Deep-merge `overlay` into `base` - mappings merge recursively, everything else is replaced
+fn merge_yaml(base: &mut serde_yaml_ng::Value, overlay: serde_yaml_ng::Value) {
+    match (base, overlay) {
+        (serde_yaml_ng::Value::Mapping(base_map), serde_yaml_ng::Value::Mapping(overlay_map)) => {
+            for (key, value) in overlay_map {
+                match base_map.get_mut(&key) {
+                    Some(existing) => merge_yaml(existing, value),
+                    None => {
+                        base_map.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
 impl Rulebook {
     /// Load rulebook from a YAML file, enhanced with convention-based discovery
     pub async fn load(path: impl AsRef<Path>) -> Result<Self> {
-        let path = path.as_ref();
-        info!("Loading rulebook from: {:?}", path);
+        Self::load_layered(&[path.as_ref().to_path_buf()]).await
+    }
 
-        let content = tokio::fs::read_to_string(path)
-            .await
-            .context("Failed to read rulebook file")?;
+    /// Load several rulebook files and merge them in order (later files win)
+    ///
+    /// Uses the same merge rules as `include:` - used to layer a nested
+    /// `.cupcake/rulebook.yml` over the project root rulebook.
+    pub async fn load_layered(paths: &[PathBuf]) -> Result<Self> {
+        let mut merged = serde_yaml_ng::Value::Mapping(Default::default());
+        for path in paths {
+            info!("Loading rulebook from: {:?}", path);
+            let mut stack = Vec::new();
+            let layer = Self::load_yaml_with_includes(path, &mut stack).await?;
+            merge_yaml(&mut merged, layer);
+        }
 
         let rulebook: Rulebook =
-            serde_yaml_ng::from_str(&content).context("Failed to parse rulebook YAML")?;
+            serde_yaml_ng::from_value(merged).context("Failed to parse rulebook YAML")?;
 
         debug!("Loaded {} explicit signals", rulebook.signals.len());
 
         Ok(rulebook)
     }
 
+    /// Read a rulebook file and resolve its `include:` list into a single YAML document
+    ///
+    /// Includes are resolved relative to the directory of the file that lists them
+    /// and are merged in order, so later includes override earlier ones. The
+    /// including file is applied last and always wins. Mappings are merged key by
+    /// key; any other value (scalars, sequences) is replaced wholesale.
+    fn load_yaml_with_includes<'a>(
+        path: &'a Path,
+        stack: &'a mut Vec<PathBuf>,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<serde_yaml_ng::Value>> + 'a>>
+    {
+        Box::pin(async move {
+            let canonical = path
+                .canonicalize()
+                .with_context(|| format!("Failed to read rulebook file: {}", path.display()))?;
+
+            if stack.contains(&canonical) {
+                anyhow::bail!(
+                    "Circular rulebook include detected: {} -> {}",
+                    stack
+                        .iter()
+                        .map(|p| p.display().to_string())
+                        .collect::<Vec<_>>()
+                        .join(" -> "),
+                    canonical.display()
+                );
+            }
+
+            let content = tokio::fs::read_to_string(&canonical)
+                .await
+                .with_context(|| format!("Failed to read rulebook file: {}", path.display()))?;
+
+            let mut value: serde_yaml_ng::Value = serde_yaml_ng::from_str(&content)
+                .with_context(|| format!("Failed to parse rulebook YAML: {}", path.display()))?;
+
+            // An empty file parses as null - treat it as an empty mapping
+            if value.is_null() {
+                value = serde_yaml_ng::Value::Mapping(Default::default());
+            }
+
+            let includes = match value.as_mapping_mut() {
+                Some(map) => map.remove("include"),
+                None => None,
+            };
+
+            let includes: Vec<String> = match includes {
+                None => return Ok(value),
+                Some(v) => serde_yaml_ng::from_value(v).with_context(|| {
+                    format!(
+                        "Invalid 'include' in {}: expected a list of file paths",
+                        path.display()
+                    )
+                })?,
+            };
+
+            let base_dir = canonical
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default();
+
+            stack.push(canonical);
+            let mut merged = serde_yaml_ng::Value::Mapping(Default::default());
+            for include in &includes {
+                let include_path = base_dir.join(include);
+                debug!("Including rulebook: {}", include_path.display());
+                let included = Self::load_yaml_with_includes(&include_path, stack).await?;
+                merge_yaml(&mut merged, included);
+            }
+            stack.pop();
+
+            merge_yaml(&mut merged, value);
+            Ok(merged)
+        })
+    }
+
     /// Create a rulebook with convention-based signal discovery
     /// Discovers scripts in signals/ directory automatically
     pub async fn load_with_conventions(
         rulebook_path: impl AsRef<Path>,
         signals_dir: impl AsRef<Path>,
     ) -> Result<Self> {
-        let mut rulebook = if rulebook_path.as_ref().exists() {
-            Self::load(rulebook_path).await?
+        Self::load_layered_with_conventions(
+            &[rulebook_path.as_ref().to_path_buf()],
+            &[signals_dir.as_ref().to_path_buf()],
+        )
+        .await
+    }
+
+    /// Layered variant of `load_with_conventions`
+    ///
+    /// Rulebooks and signal directories are both given lowest precedence first.
+    /// Missing rulebook files and signal directories are skipped.
+    pub async fn load_layered_with_conventions(
+        rulebook_paths: &[PathBuf],
+        signals_dirs: &[PathBuf],
+    ) -> Result<Self> {
+        let existing: Vec<PathBuf> = rulebook_paths
+            .iter()
+            .filter(|p| p.exists())
+            .cloned()
+            .collect();
+
+        let mut rulebook = if !existing.is_empty() {
+            Self::load_layered(&existing).await?
         } else {
             info!("No rulebook.yml found, using pure convention-based approach");
             Self::default()
         };
 
-        // Discover signals from directory (if exists)
-        if signals_dir.as_ref().exists() {
-            Self::discover_signals(&mut rulebook, signals_dir).await?;
+        // Discover signals from directories (if they exist). Discovery never
+        // overrides an existing entry, so walk from highest precedence down.
+        for signals_dir in signals_dirs.iter().rev() {
+            if signals_dir.exists() {
+                Self::discover_signals(&mut rulebook, signals_dir).await?;
+            }
         }
 
         // Generate signals for enabled builtins
@@ -171,6 +378,26 @@ impl Rulebook {
             bail!("Builtin configuration errors:\n{}", errors.join("\n"));
         }
 
+        let errors = identity::validate(&rulebook.identity);
+        if !errors.is_empty() {
+            anyhow::bail!("Identity errors:\n{}", errors.join("\n"));
+        }
+
+        let errors = severity_mapping::validate(&rulebook.severity_mapping);
+        if !errors.is_empty() {
+            anyhow::bail!("Severity mapping errors:\n{}", errors.join("\n"));
+        }
+
+        let errors = messages::validate(&rulebook.messages);
+        if !errors.is_empty() {
+            anyhow::bail!("Message catalog errors:\n{}", errors.join("\n"));
+        }
+
+        let errors = passthrough::validate(&rulebook.response_passthrough);
+        if !errors.is_empty() {
+            anyhow::bail!("Response passthrough errors:\n{}", errors.join("\n"));
+        }
+
         Ok(rulebook)
     }
 
@@ -202,9 +429,20 @@ impl Rulebook {
 
                 // Don't override explicit rulebook signals
                 if !rulebook.signals.contains_key(signal_name) {
+                    // Run scripts directly so odd file names can't inject shell
+                    // syntax; Windows needs the shell to pick an interpreter
+                    let script = path.to_string_lossy().to_string();
+                    let command = if cfg!(windows) {
+                        CommandSpec::Shell(script)
+                    } else {
+                        CommandSpec::Argv(vec![script])
+                    };
                     let signal_config = SignalConfig {
-                        command: path.to_string_lossy().to_string(),
+                        command,
                         timeout_seconds: default_timeout(),
+                        max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+                        sandbox: None,
+                        limits: None,
                     };
 
                     rulebook
@@ -242,79 +480,76 @@ impl Rulebook {
 
         debug!("Executing signal '{}': {}", signal_name, signal.command);
 
-        use tokio::io::AsyncWriteExt;
-        use tokio::process::Command;
-
-        // Use platform-appropriate shell (bash on Windows, sh on Unix)
-        let shell = *super::SHELL_COMMAND;
-
-        // On Windows, convert .sh file paths to Git Bash format
-        // C:\Users\foo\script.sh -> /c/Users/foo/script.sh
-        #[cfg(windows)]
-        let command_arg = if signal.command.ends_with(".sh")
-            && signal.command.len() >= 3
-            && signal.command.chars().nth(1) == Some(':')
-        {
-            let drive = signal.command.chars().next().unwrap().to_lowercase();
-            let path_part = &signal.command[2..].replace('\\', "/");
-            format!("/{}{}", drive, path_part)
-        } else {
-            signal.command.clone()
-        };
-
-        #[cfg(not(windows))]
-        let command_arg = &signal.command;
-
-        // Spawn the command with stdin piped
-        let mut child = Command::new(shell)
-            .arg("-c")
-            .arg(command_arg)
-            .stdin(std::process::Stdio::piped())
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped())
-            .spawn()
-            .context("Failed to spawn signal command")?;
-
         // Always write event data to stdin (signals that don't need it will ignore it)
-        if let Some(mut stdin) = child.stdin.take() {
-            let event_json = serde_json::to_string(event_data)?;
-            debug!(
-                "Writing {} bytes of event data to signal stdin",
-                event_json.len()
-            );
-            let _ = stdin.write_all(event_json.as_bytes()).await; // Ignore write errors - signal may not read stdin
-            let _ = stdin.flush().await;
-            // Drop stdin to close it
-        }
+        let event_json = serde_json::to_string(event_data)?;
+        debug!(
+            "Writing {} bytes of event data to signal stdin",
+            event_json.len()
+        );
 
-        // Wait for the command with timeout
-        let output = tokio::time::timeout(
-            std::time::Duration::from_secs(signal.timeout_seconds),
-            child.wait_with_output(),
-        )
-        .await
-        .context("Signal execution timed out")?
-        .context("Failed to wait for signal output")?;
+        // Keep at most max_output_bytes of each stream in memory
+        let limit = signal.max_output_bytes;
+        let executor = CommandExecutor {
+            timeout: std::time::Duration::from_secs(signal.timeout_seconds),
+            max_output_bytes: limit,
+            working_dir: None,
+            sandbox: signal.sandbox.as_ref(),
+            limits: signal.limits.as_ref(),
+        };
+        let output = executor
+            .run(&signal.command, event_json.as_bytes(), event_data)
+            .await
+            .context("Signal execution failed")?;
+
+        let status = output.status;
+        let truncated = output.truncated();
+        let (stdout_bytes, stdout_total, stderr_bytes) =
+            (output.stdout, output.stdout_total, output.stderr);
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stdout = String::from_utf8_lossy(&stdout_bytes);
         let trimmed_output = stdout.trim();
 
         // Always include exit code information for validation signals
         // This allows policies to check if validation failed
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let exit_code = output.status.code().unwrap_or(-1);
+        if !status.success() {
+            let stderr = String::from_utf8_lossy(&stderr_bytes);
+            let exit_code = status.code().unwrap_or(-1);
             debug!(
                 "Signal '{}' failed with exit code {}",
                 signal_name, exit_code
             );
 
             // Return structured error info for failed commands
-            return Ok(serde_json::json!({
+            let mut failure = serde_json::json!({
                 "exit_code": exit_code,
                 "output": trimmed_output,
                 "error": stderr.trim(),
                 "success": false
+            });
+            if truncated {
+                failure["truncated"] = serde_json::Value::Bool(true);
+            }
+            // Killed by a signal, e.g. SIGXCPU/SIGKILL from a CPU limit
+            #[cfg(unix)]
+            if let Some(signal) = std::os::unix::process::ExitStatusExt::signal(&status) {
+                failure["signal"] = signal.into();
+            }
+            return Ok(failure);
+        }
+
+        // Truncated output can't be trusted as JSON - hand policies an explicit marker
+        if truncated {
+            tracing::warn!(
+                "Signal '{}' produced {} bytes, truncated to max_output_bytes={}",
+                signal_name,
+                stdout_total,
+                limit
+            );
+            return Ok(serde_json::json!({
+                "truncated": true,
+                "output": trimmed_output,
+                "original_bytes": stdout_total,
+                "max_output_bytes": limit
             }));
         }
 
@@ -0,0 +1,297 @@
+//! Sandboxing for signal and approver processes
+//!
+//! A `sandbox:` block on a signal or the approver limits what a compromised
+//! script can reach:
+//!
+//! ```yaml
+//! signals:
+//!   lint:
+//!     command: ["npx", "eslint", "--format", "json", "."]
+//!     sandbox:
+//!       env: [HOME, NODE_OPTIONS]   # everything else is cleared (PATH is kept)
+//!       working_dir: packages/web   # relative to the project, can't leave it
+//!       network: false
+//! ```
+//!
+//! `network: false` is enforced per platform and fails closed where it can't be:
+//!
+//! - Linux: the process gets new user and network namespaces with no
+//!   interfaces. Needs unprivileged user namespaces (enabled on most distros).
+//! - macOS: the process runs under `sandbox-exec` with network access denied.
+//! - Elsewhere (Windows): the command is not run.
+//!
+//! A `limits:` block caps the resources a process may use, enforced by the
+//! kernel with rlimits on Unix (ignored with a warning elsewhere):
+//!
+//! ```yaml
+//!     limits:
+//!       cpu_seconds: 10     # CPU time, then SIGXCPU/SIGKILL
+//!       memory_mb: 512      # virtual address space
+//!       file_size_mb: 50    # largest file the process may write
+//!       open_files: 256
+//! ```
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+use tokio::process::Command;
+
+/// Variables kept even with an `env` allowlist - programs can't be found, or
+/// on Windows started, without them
+const ALWAYS_KEPT: &[&str] = &["PATH", "SYSTEMROOT"];
+
+/// Isolation for one command (`sandbox:` on a signal or the approver)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct SandboxConfig {
+    /// Environment variables passed to the command; the rest are cleared.
+    /// Unset inherits the whole environment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<Vec<String>>,
+
+    /// Directory to run in, relative to the project root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<PathBuf>,
+
+    /// Whether the command may use the network (default true)
+    #[serde(default = "default_network")]
+    pub network: bool,
+}
+
+fn default_network() -> bool {
+    true
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            env: None,
+            working_dir: None,
+            network: default_network(),
+        }
+    }
+}
+
+impl SandboxConfig {
+    /// Problems with the sandbox settings
+    pub fn validate(&self) -> Result<()> {
+        if let Some(dir) = &self.working_dir {
+            if dir
+                .components()
+                .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+            {
+                bail!(
+                    "sandbox working_dir '{}' must be a relative path inside the project",
+                    dir.display()
+                );
+            }
+        }
+        if let Some(name) = self
+            .env
+            .iter()
+            .flatten()
+            .find(|name| name.is_empty() || name.contains('='))
+        {
+            bail!("sandbox env entry '{name}' is not a variable name");
+        }
+        Ok(())
+    }
+
+    /// The directory to run in, given the project root (`None` = current directory)
+    pub fn working_dir(&self, root: Option<&Path>) -> Option<PathBuf> {
+        match (&self.working_dir, root) {
+            (Some(dir), Some(root)) => Some(root.join(dir)),
+            (Some(dir), None) => Some(dir.clone()),
+            (None, root) => root.map(Path::to_path_buf),
+        }
+    }
+
+    /// A process for `program` with this sandbox's network and environment
+    /// restrictions applied
+    pub fn command(&self, program: &str, args: &[String]) -> Result<Command> {
+        let mut command = if self.network {
+            let mut command = Command::new(program);
+            command.args(args);
+            command
+        } else {
+            offline_command(program, args)?
+        };
+
+        if let Some(allowed) = &self.env {
+            command.env_clear();
+            for name in ALWAYS_KEPT
+                .iter()
+                .copied()
+                .chain(allowed.iter().map(String::as_str))
+            {
+                if let Some(value) = std::env::var_os(name) {
+                    command.env(name, value);
+                }
+            }
+        }
+        Ok(command)
+    }
+}
+
+/// Kernel-enforced resource caps for one command (`limits:` on a signal or the approver)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ResourceLimits {
+    /// CPU time in seconds (RLIMIT_CPU)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_seconds: Option<u64>,
+
+    /// Virtual memory in megabytes (RLIMIT_AS). Runtimes that reserve large
+    /// address ranges up front (Node, Go, Java) need generous values
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_mb: Option<u64>,
+
+    /// Largest file the process may write, in megabytes (RLIMIT_FSIZE)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_size_mb: Option<u64>,
+
+    /// Open file descriptors (RLIMIT_NOFILE)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_files: Option<u64>,
+}
+
+impl ResourceLimits {
+    /// Set the limits in the process before it starts
+    #[cfg(unix)]
+    pub fn apply(&self, command: &mut Command) {
+        let limits = *self;
+        // SAFETY: the closure only calls setrlimit, which is async-signal-safe
+        unsafe {
+            command.pre_exec(move || limits.set());
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub fn apply(&self, _command: &mut Command) {
+        if *self != Self::default() {
+            tracing::warn!("Resource limits are only enforced on Unix; running without them");
+        }
+    }
+
+    #[cfg(unix)]
+    fn set(&self) -> std::io::Result<()> {
+        const MB: u64 = 1024 * 1024;
+        macro_rules! limit {
+            ($resource:expr, $value:expr) => {
+                if let Some(value) = $value {
+                    let mut rlimit = libc::rlimit {
+                        rlim_cur: 0,
+                        rlim_max: 0,
+                    };
+                    // SAFETY: rlimit is a valid struct for both calls. The hard
+                    // limit can only be lowered, so values above it are capped
+                    unsafe {
+                        if libc::getrlimit($resource, &mut rlimit) != 0 {
+                            return Err(std::io::Error::last_os_error());
+                        }
+                        let value = (value as libc::rlim_t).min(rlimit.rlim_max);
+                        rlimit = libc::rlimit {
+                            rlim_cur: value,
+                            rlim_max: value,
+                        };
+                        if libc::setrlimit($resource, &rlimit) != 0 {
+                            return Err(std::io::Error::last_os_error());
+                        }
+                    }
+                }
+            };
+        }
+        limit!(libc::RLIMIT_CPU, self.cpu_seconds);
+        limit!(
+            libc::RLIMIT_AS,
+            self.memory_mb.map(|mb| mb.saturating_mul(MB))
+        );
+        limit!(
+            libc::RLIMIT_FSIZE,
+            self.file_size_mb.map(|mb| mb.saturating_mul(MB))
+        );
+        limit!(libc::RLIMIT_NOFILE, self.open_files);
+        Ok(())
+    }
+}
+
+/// Run in fresh user and network namespaces - only a down loopback interface
+#[cfg(target_os = "linux")]
+fn offline_command(program: &str, args: &[String]) -> Result<Command> {
+    // Map our own ids into the namespace so file ownership looks unchanged.
+    // Formatted before fork - only async-signal-safe calls run in pre_exec
+    // SAFETY: getuid/getgid can't fail
+    let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+    let uid_map = format!("{uid} {uid} 1");
+    let gid_map = format!("{gid} {gid} 1");
+
+    let mut command = Command::new(program);
+    command.args(args);
+    // SAFETY: the closure only makes raw syscalls, no allocation or locking
+    unsafe {
+        command.pre_exec(move || {
+            if libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNET) != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            write_proc(c"/proc/self/setgroups", b"deny")?;
+            write_proc(c"/proc/self/uid_map", uid_map.as_bytes())?;
+            write_proc(c"/proc/self/gid_map", gid_map.as_bytes())
+        });
+    }
+    Ok(command)
+}
+
+#[cfg(target_os = "linux")]
+fn write_proc(path: &std::ffi::CStr, contents: &[u8]) -> std::io::Result<()> {
+    // SAFETY: path is NUL-terminated and contents outlives the write
+    unsafe {
+        let fd = libc::open(path.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        let written = libc::write(fd, contents.as_ptr().cast(), contents.len());
+        libc::close(fd);
+        if written < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// Run under a Seatbelt profile that denies network access
+#[cfg(target_os = "macos")]
+fn offline_command(program: &str, args: &[String]) -> Result<Command> {
+    let mut command = Command::new("/usr/bin/sandbox-exec");
+    command
+        .args(["-p", "(version 1)(allow default)(deny network*)", program])
+        .args(args);
+    Ok(command)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn offline_command(_program: &str, _args: &[String]) -> Result<Command> {
+    bail!("sandbox `network: false` is not supported on this platform")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sandbox_from_yaml_and_validation() {
+        let sandbox: SandboxConfig =
+            serde_yaml_ng::from_str("env: [HOME]\nworking_dir: packages/api").unwrap();
+        assert!(sandbox.network);
+        assert!(sandbox.validate().is_ok());
+        assert_eq!(
+            sandbox.working_dir(Some(Path::new("/repo"))),
+            Some(PathBuf::from("/repo/packages/api"))
+        );
+
+        for yaml in ["working_dir: ../other", "working_dir: /etc", "env: ['A=B']"] {
+            let sandbox: SandboxConfig = serde_yaml_ng::from_str(yaml).unwrap();
+            assert!(sandbox.validate().is_err(), "{yaml}");
+        }
+        assert!(serde_yaml_ng::from_str::<SandboxConfig>("net: false").is_err());
+    }
+}
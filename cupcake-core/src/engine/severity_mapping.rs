@@ -0,0 +1,304 @@
+//! Severity mapping - remap decision verbs by severity before synthesis
+//!
+//! Lets one policy set be enforced at different strengths depending on where it
+//! runs. Each rule in `severity_mapping:` moves decisions of the given severities
+//! from one verb to another, optionally only for some harnesses, only when an
+//! environment variable is set, or only for users holding a role:
+//!
+//! ```yaml
+//! severity_mapping:
+//!   # Locally, low-severity denials only ask for confirmation
+//!   - severity: [LOW]
+//!     from: deny
+//!     to: ask
+//!   # In CI nobody can answer a prompt - critical asks become denials
+//!   - severity: [CRITICAL, HIGH]
+//!     from: ask
+//!     to: deny
+//!     when_env: CI
+//!   # Interns get strict mode
+//!   - severity: [MEDIUM]
+//!     from: ask
+//!     to: deny
+//!     roles: [intern]
+//! ```
+//!
+//! Rules are applied in order, each to the result of the previous one, and
+//! every tier applies only the rules from its own rulebook.
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use super::decision::{DecisionObject, DecisionSet};
+use super::identity::Identity;
+use crate::harness::types::HarnessType;
+
+/// Decision verbs a severity rule can move between
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MappedVerb {
+    Halt,
+    Deny,
+    Block,
+    Ask,
+    /// Drops the decision - only valid as a target
+    Allow,
+}
+
+/// One remapping rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeverityRule {
+    /// Severities this rule applies to (case-insensitive)
+    #[serde(deserialize_with = "one_or_many")]
+    pub severity: Vec<String>,
+
+    /// Verb the decision was emitted with
+    pub from: MappedVerb,
+
+    /// Verb the decision is enforced as
+    pub to: MappedVerb,
+
+    /// Harnesses this rule applies to (default: all)
+    #[serde(default)]
+    pub harnesses: Vec<HarnessType>,
+
+    /// Only apply when this environment variable is set, non-empty and not
+    /// `0`/`false`
+    #[serde(default)]
+    pub when_env: Option<String>,
+
+    /// Only apply to users holding one of these roles (default: everyone)
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+impl SeverityRule {
+    fn is_active(&self, harness: HarnessType, identity: &Identity) -> bool {
+        if !self.harnesses.is_empty() && !self.harnesses.contains(&harness) {
+            return false;
+        }
+        if !self.roles.is_empty() && !identity.has_any_role(&self.roles) {
+            return false;
+        }
+        match &self.when_env {
+            Some(var) => std::env::var(var).is_ok_and(|value| {
+                let value = value.trim();
+                !value.is_empty() && value != "0" && !value.eq_ignore_ascii_case("false")
+            }),
+            None => true,
+        }
+    }
+
+    fn matches(&self, decision: &DecisionObject) -> bool {
+        self.severity
+            .iter()
+            .any(|s| s.eq_ignore_ascii_case(&decision.severity))
+    }
+}
+
+fn one_or_many<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(s) => vec![s],
+        OneOrMany::Many(v) => v,
+    })
+}
+
+/// Check rules for mistakes the deserializer can't catch
+pub fn validate(rules: &[SeverityRule]) -> Vec<String> {
+    let mut errors = Vec::new();
+    for (idx, rule) in rules.iter().enumerate() {
+        if rule.from == MappedVerb::Allow {
+            errors.push(format!(
+                "severity_mapping[{idx}]: 'from: allow' is not supported - allow decisions carry no severity"
+            ));
+        }
+        if rule.severity.is_empty() {
+            errors.push(format!(
+                "severity_mapping[{idx}]: at least one severity is required"
+            ));
+        }
+    }
+    errors
+}
+
+/// Apply the rules active for `harness` and `identity` to a DecisionSet in place
+pub fn apply(
+    rules: &[SeverityRule],
+    harness: HarnessType,
+    identity: &Identity,
+    decision_set: &mut DecisionSet,
+) {
+    for rule in rules.iter().filter(|r| r.is_active(harness, identity)) {
+        let Some(source) = verb_list(decision_set, rule.from) else {
+            continue;
+        };
+        let (moved, kept): (Vec<_>, Vec<_>) = std::mem::take(source)
+            .into_iter()
+            .partition(|d| rule.matches(d));
+        *source = kept;
+
+        if moved.is_empty() {
+            continue;
+        }
+        for decision in &moved {
+            debug!(
+                "Severity mapping: {} ({}) {:?} -> {:?}",
+                decision.rule_id, decision.severity, rule.from, rule.to
+            );
+        }
+        if let Some(target) = verb_list(decision_set, rule.to) {
+            target.extend(moved);
+        }
+    }
+}
+
+fn verb_list(decision_set: &mut DecisionSet, verb: MappedVerb) -> Option<&mut Vec<DecisionObject>> {
+    match verb {
+        MappedVerb::Halt => Some(&mut decision_set.halts),
+        MappedVerb::Deny => Some(&mut decision_set.denials),
+        MappedVerb::Block => Some(&mut decision_set.blocks),
+        MappedVerb::Ask => Some(&mut decision_set.asks),
+        MappedVerb::Allow => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decision(rule_id: &str, severity: &str) -> DecisionObject {
+        DecisionObject {
+            reason: format!("{rule_id} fired"),
+            severity: severity.to_string(),
+            rule_id: rule_id.to_string(),
+            agent_context: None,
+            docs_url: None,
+            remediation: None,
+        }
+    }
+
+    #[test]
+    fn test_remaps_matching_severities_only() {
+        let rules: Vec<SeverityRule> = serde_yaml_ng::from_str(
+            r#"
+- severity: low
+  from: deny
+  to: ask
+- severity: [CRITICAL]
+  from: ask
+  to: deny
+  harnesses: [cursor]
+"#,
+        )
+        .unwrap();
+        assert!(validate(&rules).is_empty());
+
+        let mut decision_set = DecisionSet {
+            denials: vec![decision("LOW-1", "LOW"), decision("HIGH-1", "HIGH")],
+            asks: vec![decision("CRIT-1", "CRITICAL")],
+            ..Default::default()
+        };
+        apply(
+            &rules,
+            HarnessType::ClaudeCode,
+            &Identity::default(),
+            &mut decision_set,
+        );
+
+        let ids =
+            |list: &[DecisionObject]| list.iter().map(|d| d.rule_id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&decision_set.denials), vec!["HIGH-1"]);
+        // The CRITICAL rule is scoped to Cursor, so the ask stays an ask
+        assert_eq!(ids(&decision_set.asks), vec!["CRIT-1", "LOW-1"]);
+
+        apply(
+            &rules,
+            HarnessType::Cursor,
+            &Identity::default(),
+            &mut decision_set,
+        );
+        assert_eq!(ids(&decision_set.denials), vec!["HIGH-1", "CRIT-1"]);
+        assert_eq!(ids(&decision_set.asks), vec!["LOW-1"]);
+    }
+
+    #[test]
+    fn test_allow_target_drops_and_env_gate() {
+        let rules: Vec<SeverityRule> = serde_yaml_ng::from_str(
+            r#"
+- severity: INFO
+  from: ask
+  to: allow
+- severity: LOW
+  from: ask
+  to: deny
+  when_env: CUPCAKE_TEST_SEVERITY_MAPPING_UNSET
+- severity: LOW
+  from: allow
+  to: deny
+"#,
+        )
+        .unwrap();
+        assert_eq!(validate(&rules).len(), 1);
+
+        let mut decision_set = DecisionSet {
+            asks: vec![decision("INFO-1", "INFO"), decision("LOW-1", "LOW")],
+            ..Default::default()
+        };
+        apply(
+            &rules,
+            HarnessType::ClaudeCode,
+            &Identity::default(),
+            &mut decision_set,
+        );
+
+        assert_eq!(decision_set.asks.len(), 1);
+        assert_eq!(decision_set.asks[0].rule_id, "LOW-1");
+        assert!(decision_set.denials.is_empty());
+    }
+
+    #[test]
+    fn test_roles_gate() {
+        let rules: Vec<SeverityRule> = serde_yaml_ng::from_str(
+            r#"
+- severity: MEDIUM
+  from: ask
+  to: deny
+  roles: [intern]
+"#,
+        )
+        .unwrap();
+        let intern = Identity {
+            user: Some("carol".to_string()),
+            roles: vec!["intern".to_string()],
+            ..Default::default()
+        };
+        let set = || DecisionSet {
+            asks: vec![decision("MED-1", "MEDIUM")],
+            ..Default::default()
+        };
+
+        let mut decision_set = set();
+        apply(
+            &rules,
+            HarnessType::ClaudeCode,
+            &Identity::default(),
+            &mut decision_set,
+        );
+        assert_eq!(decision_set.asks.len(), 1);
+
+        let mut decision_set = set();
+        apply(&rules, HarnessType::ClaudeCode, &intern, &mut decision_set);
+        assert!(decision_set.asks.is_empty());
+        assert_eq!(decision_set.denials[0].rule_id, "MED-1");
+    }
+}
@@ -0,0 +1,57 @@
+//! Merge logic for output filter decisions.
+//!
+//! Combines multiple `filter_output` decisions into a single [`OutputFilter`].
+
+use super::super::decision::{OutputFilter, OutputFilterObject};
+use super::merge_input_updates::deep_merge;
+
+/// Merge multiple output filters with priority-based conflict resolution.
+///
+/// Algorithm:
+/// 1. Sort filters by priority (descending - highest first)
+/// 2. Deep merge `updated_response` objects (highest priority wins conflicts)
+/// 3. Union all redaction patterns
+/// 4. Use the smallest `max_length`
+/// 5. Aggregate reasons from all filters
+pub fn merge_output_filters(filters: &[OutputFilterObject]) -> (String, OutputFilter, Vec<String>) {
+    let mut sorted: Vec<&OutputFilterObject> = filters.iter().collect();
+    sorted.sort_by(|a, b| b.priority.cmp(&a.priority));
+
+    let mut merged = OutputFilter::default();
+    for filter in &sorted {
+        if let Some(updated) = &filter.updated_response {
+            merged.updated_response = Some(match merged.updated_response.take() {
+                Some(base) => deep_merge(base, updated.clone()),
+                None => updated.clone(),
+            });
+        }
+        for pattern in &filter.redact {
+            if !merged.redact.contains(pattern) {
+                merged.redact.push(pattern.clone());
+            }
+        }
+        merged.max_length = match (merged.max_length, filter.max_length) {
+            (Some(current), Some(max)) => Some(current.min(max)),
+            (current, max) => current.or(max),
+        };
+    }
+
+    let reason = match sorted.as_slice() {
+        [] => "No output filters".to_string(),
+        [only] => only.reason.clone(),
+        _ => {
+            let reasons: Vec<String> = sorted
+                .iter()
+                .map(|f| format!("[{}] {}", f.rule_id, f.reason))
+                .collect();
+            format!("Multiple output filters applied: {}", reasons.join("; "))
+        }
+    };
+
+    let agent_messages = filters
+        .iter()
+        .filter_map(|f| f.agent_context.clone())
+        .collect();
+
+    (reason, merged, agent_messages)
+}
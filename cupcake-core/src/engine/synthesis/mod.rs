@@ -1,10 +1,14 @@
 //! Decision Synthesis Layer - Transforms [`DecisionSet`] into [`FinalDecision`].
 //!
-//! Applies strict priority: Halt > Deny/Block > Ask > Modify > Allow.
+//! Applies strict priority: Halt > Deny/Block > Ask > Modify > FilterOutput > Allow.
+//! Placeholders in `add_context` strings are resolved by [`templating`].
 
 mod merge_input_updates;
+mod merge_output_filters;
+pub mod templating;
 
 use anyhow::Result;
+use serde_json::Value;
 use std::time::Instant;
 use tracing::{debug, info, instrument, trace};
 
@@ -12,7 +16,7 @@ use super::decision::{DecisionObject, DecisionSet, FinalDecision};
 
 /// The Decision Synthesis Engine.
 ///
-/// Implements strict priority: Halt > Deny/Block > Ask > Modify > Allow.
+/// Implements strict priority: Halt > Deny/Block > Ask > Modify > FilterOutput > Allow.
 pub struct SynthesisEngine;
 
 impl SynthesisEngine {
@@ -40,12 +44,13 @@ impl SynthesisEngine {
             decision_set.decision_count()
         );
 
-        debug!("Synthesis input - Halts: {}, Denials: {}, Blocks: {}, Asks: {}, Modifications: {}, Context Items: {}",
+        debug!("Synthesis input - Halts: {}, Denials: {}, Blocks: {}, Asks: {}, Modifications: {}, Output Filters: {}, Context Items: {}",
             decision_set.halts.len(),
             decision_set.denials.len(),
             decision_set.blocks.len(),
             decision_set.asks.len(),
             decision_set.modifications.len(),
+            decision_set.output_filters.len(),
             decision_set.add_context.len());
 
         // Apply strict priority hierarchy
@@ -138,7 +143,26 @@ impl SynthesisEngine {
             );
         }
 
-        // Priority 5: Allow (Default - with optional context)
+        // Priority 5: FilterOutput (Low - rewrite tool output and allow)
+        if decision_set.has_output_filters() {
+            let (reason, filter, agent_messages) =
+                merge_output_filters::merge_output_filters(&decision_set.output_filters);
+            debug!(
+                "Synthesized FILTER_OUTPUT decision: {} (merged from {} filters)",
+                reason,
+                decision_set.output_filters.len()
+            );
+            return record_and_return(
+                "FilterOutput",
+                FinalDecision::FilterOutput {
+                    reason,
+                    filter,
+                    agent_messages,
+                },
+            );
+        }
+
+        // Priority 6: Allow (Default - with optional context)
         let context = decision_set.add_context.clone();
         if !context.is_empty() {
             debug!(
@@ -152,6 +176,29 @@ impl SynthesisEngine {
         record_and_return("Allow", FinalDecision::Allow { context })
     }
 
+    /// Synthesize a DecisionSet, resolving context placeholders against `input`
+    ///
+    /// `input` is the enriched input the policies were evaluated with, so
+    /// `{{signal.name}}` and `{{event.field}}` see the gathered signals and event.
+    pub fn synthesize_with_input(
+        decision_set: &DecisionSet,
+        input: &Value,
+    ) -> Result<FinalDecision> {
+        if !decision_set.add_context.iter().any(|c| c.contains("{{")) {
+            return Self::synthesize(decision_set);
+        }
+
+        let rendered = DecisionSet {
+            add_context: decision_set
+                .add_context
+                .iter()
+                .map(|context| templating::render(context, input))
+                .collect(),
+            ..decision_set.clone()
+        };
+        Self::synthesize(&rendered)
+    }
+
     /// Collect agent-specific messages from decisions
     ///
     /// Extracts all agent_context fields from DecisionObjects.
@@ -167,13 +214,14 @@ impl SynthesisEngine {
     ///
     /// This handles the case where multiple policies of the same priority
     /// fire simultaneously, providing a coherent explanation to the user.
+    /// Remediation and docs links of the reported decisions follow the reason.
     fn aggregate_reasons(decisions: &[DecisionObject]) -> String {
         if decisions.is_empty() {
             return "Policy evaluation completed".to_string();
         }
 
         if decisions.len() == 1 {
-            return decisions[0].reason.clone();
+            return Self::with_guidance(decisions[0].reason.clone(), &[&decisions[0]]);
         }
 
         // Multiple decisions - group by severity and create a structured message
@@ -240,9 +288,38 @@ impl SynthesisEngine {
         }
 
         if parts.is_empty() {
-            format!("Multiple policies triggered ({})", decisions.len())
+            return format!("Multiple policies triggered ({})", decisions.len());
+        }
+
+        // Only the most severe group is described, so only its guidance is shown
+        let reported = [high_decisions, medium_decisions, low_decisions]
+            .into_iter()
+            .find(|group| !group.is_empty())
+            .unwrap_or_default();
+        Self::with_guidance(parts.join(" "), &reported)
+    }
+
+    /// Append the remediation and docs lines of `decisions` to a reason
+    ///
+    /// With several decisions, each line is prefixed with its rule_id.
+    fn with_guidance(reason: String, decisions: &[&DecisionObject]) -> String {
+        let lines: Vec<String> = decisions
+            .iter()
+            .flat_map(|decision| {
+                decision.guidance().into_iter().map(|line| {
+                    if decisions.len() == 1 {
+                        line
+                    } else {
+                        format!("[{}] {}", decision.rule_id, line)
+                    }
+                })
+            })
+            .collect();
+
+        if lines.is_empty() {
+            reason
         } else {
-            parts.join(" ")
+            format!("{reason}\n{}", lines.join("\n"))
         }
     }
 
@@ -268,6 +345,12 @@ impl SynthesisEngine {
                 decision_set.modifications.len()
             ));
         }
+        if !decision_set.output_filters.is_empty() {
+            summary_parts.push(format!(
+                "{} output filter(s)",
+                decision_set.output_filters.len()
+            ));
+        }
         if !decision_set.add_context.is_empty() {
             summary_parts.push(format!(
                 "{} context item(s)",
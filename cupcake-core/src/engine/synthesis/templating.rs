@@ -0,0 +1,106 @@
+//! Placeholder interpolation for context injection.
+//!
+//! `add_context` strings may contain `{{signal.name}}` and `{{event.field}}`
+//! placeholders, resolved against the enriched input the policies were evaluated
+//! with. Dotted paths reach into nested values (`{{signal.sprint.goal}}`,
+//! `{{event.tool_input.command}}`) and numeric segments index arrays.
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde_json::Value;
+use tracing::warn;
+
+static PLACEHOLDER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{\{\s*(signal|event)\.([A-Za-z0-9_\-]+(?:\.[A-Za-z0-9_\-]+)*)\s*\}\}").unwrap()
+});
+
+/// Resolve placeholders in a context string
+///
+/// Strings are inserted as-is and other values as compact JSON. Placeholders
+/// that don't resolve are left in place, with a warning.
+pub fn render(template: &str, input: &Value) -> String {
+    if !template.contains("{{") {
+        return template.to_string();
+    }
+
+    PLACEHOLDER
+        .replace_all(template, |caps: &Captures| {
+            let root = match &caps[1] {
+                "signal" => input.get("signals"),
+                _ => Some(input),
+            };
+            match root.and_then(|root| lookup(root, &caps[2])) {
+                Some(Value::String(text)) => text.clone(),
+                Some(value) => value.to_string(),
+                None => {
+                    warn!("Context placeholder '{}' did not resolve", &caps[0]);
+                    caps[0].to_string()
+                }
+            }
+        })
+        .into_owned()
+}
+
+/// Names of the signals referenced by `{{signal.name}}` placeholders
+pub fn referenced_signals(template: &str) -> Vec<String> {
+    PLACEHOLDER
+        .captures_iter(template)
+        .filter(|caps| &caps[1] == "signal")
+        .filter_map(|caps| caps[2].split('.').next().map(str::to_string))
+        .collect()
+}
+
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(value, |current, segment| match current {
+            Value::Object(fields) => fields.get(segment),
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        })
+        .filter(|value| !value.is_null())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_render_signals_and_event_fields() {
+        let input = json!({
+            "hook_event_name": "UserPromptSubmit",
+            "tool_input": {"files": ["a.rs", "b.rs"]},
+            "signals": {
+                "git_branch": "feature/login",
+                "sprint": {"goal": "Ship SSO", "points": 21}
+            }
+        });
+
+        assert_eq!(
+            render(
+                "On {{signal.git_branch}} for {{ event.hook_event_name }}",
+                &input
+            ),
+            "On feature/login for UserPromptSubmit"
+        );
+        assert_eq!(
+            render(
+                "Goal: {{signal.sprint.goal}} ({{signal.sprint.points}} pts), {{event.tool_input.files.1}}",
+                &input
+            ),
+            "Goal: Ship SSO (21 pts), b.rs"
+        );
+        assert_eq!(
+            render("Missing: {{signal.unknown}}", &input),
+            "Missing: {{signal.unknown}}"
+        );
+    }
+
+    #[test]
+    fn test_referenced_signals() {
+        assert_eq!(
+            referenced_signals("{{signal.git_branch}} {{event.cwd}} {{signal.sprint.goal}}"),
+            vec!["git_branch".to_string(), "sprint".to_string()]
+        );
+    }
+}
@@ -1,6 +1,6 @@
 //! Tests for the synthesis engine
 
-use super::super::decision::ModificationObject;
+use super::super::decision::{ModificationObject, OutputFilterObject};
 use super::*;
 
 #[test]
@@ -12,12 +12,16 @@ fn test_synthesis_priority_hierarchy() {
             severity: "CRITICAL".to_string(),
             rule_id: "HALT-001".to_string(),
             agent_context: None,
+            docs_url: None,
+            remediation: None,
         }],
         denials: vec![DecisionObject {
             reason: "Denied".to_string(),
             severity: "HIGH".to_string(),
             rule_id: "DENY-001".to_string(),
             agent_context: None,
+            docs_url: None,
+            remediation: None,
         }],
         ..Default::default()
     };
@@ -74,6 +78,8 @@ fn test_aggregate_reasons_single() {
         severity: "HIGH".to_string(),
         rule_id: "TEST-001".to_string(),
         agent_context: None,
+        docs_url: None,
+        remediation: None,
     }];
 
     let result = SynthesisEngine::aggregate_reasons(&decisions);
@@ -88,12 +94,16 @@ fn test_aggregate_reasons_multiple_high_severity() {
             severity: "HIGH".to_string(),
             rule_id: "TEST-001".to_string(),
             agent_context: None,
+            docs_url: None,
+            remediation: None,
         },
         DecisionObject {
             reason: "Second violation".to_string(),
             severity: "HIGH".to_string(),
             rule_id: "TEST-002".to_string(),
             agent_context: None,
+            docs_url: None,
+            remediation: None,
         },
     ];
 
@@ -103,6 +113,40 @@ fn test_aggregate_reasons_multiple_high_severity() {
     assert!(result.contains("[TEST-002]"));
 }
 
+#[test]
+fn test_aggregate_reasons_with_guidance() {
+    let guided = |rule_id: &str, severity: &str, docs_url: &str| DecisionObject {
+        reason: format!("{rule_id} violated"),
+        severity: severity.to_string(),
+        rule_id: rule_id.to_string(),
+        agent_context: None,
+        docs_url: Some(docs_url.to_string()),
+        remediation: Some("Use trash instead".to_string()),
+    };
+
+    let single = SynthesisEngine::aggregate_reasons(&[guided(
+        "RM-001",
+        "HIGH",
+        "https://wiki.example.com/rm",
+    )]);
+    assert_eq!(
+        single,
+        "RM-001 violated\nRemediation: Use trash instead\nDocs: https://wiki.example.com/rm"
+    );
+
+    // Only the reported (most severe) decisions contribute guidance, prefixed by rule_id;
+    // non-http(s) docs links are dropped
+    let multiple = SynthesisEngine::aggregate_reasons(&[
+        guided("RM-001", "HIGH", "https://wiki.example.com/rm"),
+        guided("RM-002", "HIGH", "javascript:alert(1)"),
+        guided("LOW-001", "LOW", "https://wiki.example.com/low"),
+    ]);
+    assert!(multiple.contains("[RM-001] Docs: https://wiki.example.com/rm"));
+    assert!(multiple.contains("[RM-002] Remediation: Use trash instead"));
+    assert!(!multiple.contains("javascript:"));
+    assert!(!multiple.contains("LOW-001"));
+}
+
 #[test]
 fn test_collect_agent_messages() {
     let decisions = vec![
@@ -111,18 +155,24 @@ fn test_collect_agent_messages() {
             severity: "HIGH".to_string(),
             rule_id: "TEST-001".to_string(),
             agent_context: Some("Technical details for agent".to_string()),
+            docs_url: None,
+            remediation: None,
         },
         DecisionObject {
             reason: "Another message".to_string(),
             severity: "HIGH".to_string(),
             rule_id: "TEST-002".to_string(),
             agent_context: None,
+            docs_url: None,
+            remediation: None,
         },
         DecisionObject {
             reason: "Third message".to_string(),
             severity: "HIGH".to_string(),
             rule_id: "TEST-003".to_string(),
             agent_context: Some("More agent context".to_string()),
+            docs_url: None,
+            remediation: None,
         },
     ];
 
@@ -140,12 +190,16 @@ fn test_decision_set_summary() {
             severity: "HIGH".to_string(),
             rule_id: "TEST-001".to_string(),
             agent_context: None,
+            docs_url: None,
+            remediation: None,
         }],
         asks: vec![DecisionObject {
             reason: "Test ask".to_string(),
             severity: "MEDIUM".to_string(),
             rule_id: "TEST-002".to_string(),
             agent_context: None,
+            docs_url: None,
+            remediation: None,
         }],
         add_context: vec!["Context message".to_string()],
         ..Default::default()
@@ -200,6 +254,8 @@ fn test_modify_priority_below_ask() {
             severity: "MEDIUM".to_string(),
             rule_id: "ASK-001".to_string(),
             agent_context: None,
+            docs_url: None,
+            remediation: None,
         }],
         modifications: vec![ModificationObject {
             reason: "Would have modified".to_string(),
@@ -310,3 +366,95 @@ fn test_modify_priority_conflict_resolution() {
         _ => panic!("Expected Modify decision"),
     }
 }
+
+fn output_filter(rule_id: &str, priority: u8) -> OutputFilterObject {
+    OutputFilterObject {
+        reason: format!("Filtered by {rule_id}"),
+        severity: "MEDIUM".to_string(),
+        rule_id: rule_id.to_string(),
+        priority,
+        updated_response: None,
+        redact: Vec::new(),
+        max_length: None,
+        agent_context: None,
+    }
+}
+
+#[test]
+fn test_filter_output_merges_filters() {
+    use serde_json::json;
+
+    let decision_set = DecisionSet {
+        output_filters: vec![
+            OutputFilterObject {
+                updated_response: Some(json!({"stderr": "low"})),
+                redact: vec!["sk-[a-z0-9]+".to_string()],
+                max_length: Some(1000),
+                ..output_filter("LOW", 10)
+            },
+            OutputFilterObject {
+                updated_response: Some(json!({"stderr": "high"})),
+                redact: vec!["ghp_[A-Za-z0-9]+".to_string()],
+                max_length: Some(500),
+                ..output_filter("HIGH", 90)
+            },
+        ],
+        add_context: vec!["Ignored when filtering".to_string()],
+        ..Default::default()
+    };
+
+    match SynthesisEngine::synthesize(&decision_set).unwrap() {
+        FinalDecision::FilterOutput { reason, filter, .. } => {
+            assert!(reason.contains("[HIGH]") && reason.contains("[LOW]"));
+            assert_eq!(filter.updated_response, Some(json!({"stderr": "high"})));
+            assert_eq!(filter.redact.len(), 2);
+            assert_eq!(filter.max_length, Some(500));
+        }
+        other => panic!("Expected FilterOutput decision, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_filter_output_priority_below_block() {
+    let decision_set = DecisionSet {
+        blocks: vec![DecisionObject {
+            reason: "Output contains a private key".to_string(),
+            severity: "HIGH".to_string(),
+            rule_id: "BLOCK-001".to_string(),
+            agent_context: None,
+            docs_url: None,
+            remediation: None,
+        }],
+        output_filters: vec![output_filter("FILTER-001", 50)],
+        ..Default::default()
+    };
+
+    let result = SynthesisEngine::synthesize(&decision_set).unwrap();
+    assert!(
+        matches!(result, FinalDecision::Block { .. }),
+        "Block should have higher priority than FilterOutput"
+    );
+}
+
+#[test]
+fn test_synthesis_interpolates_context_placeholders() {
+    let decision_set = DecisionSet {
+        add_context: vec![
+            "Branch: {{signal.git_branch}}".to_string(),
+            "Event: {{event.hook_event_name}}".to_string(),
+        ],
+        ..Default::default()
+    };
+    let input = serde_json::json!({
+        "hook_event_name": "UserPromptSubmit",
+        "signals": {"git_branch": "main"}
+    });
+
+    let result = SynthesisEngine::synthesize_with_input(&decision_set, &input).unwrap();
+    match result {
+        FinalDecision::Allow { context } => {
+            assert_eq!(context, vec!["Branch: main", "Event: UserPromptSubmit"]);
+        }
+        other => panic!("Expected Allow, got {other:?}"),
+    }
+}
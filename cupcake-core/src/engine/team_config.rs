@@ -0,0 +1,122 @@
+//! Team Configuration Discovery Module
+//!
+//! A team configuration sits between global (machine-wide) and project configuration.
+//! Platform teams ship it as a directory with the same layout as a project `.cupcake/`
+//! directory (`rulebook.yml`, `policies/<harness>/`, `system/`, `signals/`), typically
+//! checked out from a shared repository.
+//!
+//! Team policies are evaluated after global policies and before project policies.
+//! A Halt/Deny/Block from the team tier stops evaluation before project policies run.
+
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use tracing::{debug, info};
+
+/// Environment variable pointing at the team configuration directory
+pub const TEAM_CONFIG_ENV: &str = "CUPCAKE_TEAM_CONFIG";
+
+/// Team configuration paths
+#[derive(Debug, Clone)]
+pub struct TeamPaths {
+    /// Root directory for team config
+    pub root: PathBuf,
+    /// Team policies directory
+    pub policies: PathBuf,
+    /// Team rulebook file
+    pub rulebook: PathBuf,
+    /// Team signals directory
+    pub signals: PathBuf,
+}
+
+impl TeamPaths {
+    /// Discover team configuration with optional CLI override
+    ///
+    /// Resolution order:
+    /// 1. CLI override parameter (if provided)
+    /// 2. `CUPCAKE_TEAM_CONFIG` environment variable
+    /// 3. None (team tier is opt-in)
+    ///
+    /// Unlike global config, a configured-but-missing team directory is an error:
+    /// silently skipping org-wide rules would fail open.
+    pub fn discover_with_override(cli_override: Option<PathBuf>) -> Result<Option<Self>> {
+        let (configured, source) = match cli_override {
+            Some(path) => (path, "--team-config"),
+            None => match std::env::var_os(TEAM_CONFIG_ENV) {
+                Some(value) if !value.is_empty() => (PathBuf::from(value), TEAM_CONFIG_ENV),
+                _ => {
+                    debug!("No team configuration configured");
+                    return Ok(None);
+                }
+            },
+        };
+
+        if !configured.is_absolute() {
+            return Err(anyhow::anyhow!(
+                "Team config path from {} must be absolute (got: {})",
+                source,
+                configured.display()
+            ));
+        }
+
+        let root = configured.canonicalize().with_context(|| {
+            format!(
+                "Team config path from {} does not exist: {}",
+                source,
+                configured.display()
+            )
+        })?;
+
+        if !root.is_dir() {
+            return Err(anyhow::anyhow!(
+                "Team config path must be a directory: {}",
+                root.display()
+            ));
+        }
+
+        info!("Using team configuration from {}: {:?}", source, root);
+        Ok(Some(Self::from_root(root)))
+    }
+
+    /// Create TeamPaths from a root directory
+    pub fn from_root(root: PathBuf) -> Self {
+        TeamPaths {
+            policies: root.join("policies"),
+            rulebook: root.join("rulebook.yml"),
+            signals: root.join("signals"),
+            root,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_cli_override_must_be_absolute() {
+        let result = TeamPaths::discover_with_override(Some(PathBuf::from("relative/team")));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_override_must_exist() {
+        let result =
+            TeamPaths::discover_with_override(Some(PathBuf::from("/definitely/not/a/team/dir")));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_override_resolves_layout() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = TeamPaths::discover_with_override(Some(temp_dir.path().to_path_buf()))
+            .unwrap()
+            .expect("team paths");
+
+        let root = temp_dir.path().canonicalize().unwrap();
+        assert_eq!(paths.root, root);
+        assert_eq!(paths.policies, root.join("policies"));
+        assert_eq!(paths.rulebook, root.join("rulebook.yml"));
+        assert_eq!(paths.signals, root.join("signals"));
+    }
+}
@@ -0,0 +1,369 @@
+//! Policy templates - one policy source for every harness
+//!
+//! Most builtins differ between harnesses only in event names and where a
+//! field lives in the input. A template is written once with `# cupcake:`
+//! annotations and rendered into each harness's variant, both by `cupcake init`
+//! and when the engine loads a policy file that is still a template:
+//!
+//! ```rego
+//! # cupcake:template claude cursor factory opencode
+//! # METADATA
+//! # custom:
+//! #   routing:
+//! #     required_events: ["{{shell_event}}"]
+//! #     required_tools: ["Bash"] # cupcake:unless cursor
+//! package cupcake.policies.example
+//!
+//! # cupcake:map greeting cursor = "hi"; default = "hello"
+//! deny contains decision if {
+//!     input.hook_event_name == "{{shell_event}}"
+//!     contains(lower({{shell_command}}), {{greeting}})
+//!     # ...
+//! }
+//! ```
+//!
+//! Annotations:
+//!
+//! - `# cupcake:template [harness...]` marks the file as a template, optionally
+//!   limited to the listed harnesses
+//! - `# cupcake:map NAME harness[, harness] = VALUE; default = VALUE` defines a
+//!   `{{NAME}}` placeholder, overriding the standard mappings below
+//! - `# cupcake:if harness...` / `# cupcake:unless harness...`, then optionally
+//!   `# cupcake:else`, then `# cupcake:end` keep a block of lines for some
+//!   harnesses only. Blocks don't nest
+//! - The same `if`/`unless` as a trailing comment applies to that one line
+//!
+//! Annotation lines are removed from the output.
+
+use anyhow::{anyhow, bail, Result};
+use std::collections::HashMap;
+
+use crate::harness::types::HarnessType;
+
+const DIRECTIVE: &str = "# cupcake:";
+
+/// Placeholders every template can use without declaring them
+fn standard_mapping(name: &str, harness: HarnessType) -> Option<&'static str> {
+    let cursor = harness == HarnessType::Cursor;
+    match name {
+        "harness" => Some(harness.as_str()),
+        // Cursor has a dedicated shell event; the others route Bash through PreToolUse
+        "shell_event" if cursor => Some("beforeShellExecution"),
+        "shell_event" => Some("PreToolUse"),
+        "shell_command" if cursor => Some("input.command"),
+        "shell_command" => Some("input.tool_input.command"),
+        "prompt_event" if cursor => Some("beforeSubmitPrompt"),
+        "prompt_event" => Some("UserPromptSubmit"),
+        _ => None,
+    }
+}
+
+/// Whether a policy source is a template
+pub fn is_template(content: &str) -> bool {
+    content
+        .lines()
+        .any(|line| matches!(directive(line), Some(("template", _))))
+}
+
+/// Harnesses a template can be rendered for
+///
+/// Returns `None` if the content is not a template.
+pub fn supported_harnesses(content: &str) -> Option<Vec<HarnessType>> {
+    let (_, args) = content
+        .lines()
+        .filter_map(directive)
+        .find(|(keyword, _)| *keyword == "template")?;
+    let listed: Vec<HarnessType> = args
+        .split_whitespace()
+        .filter_map(|name| name.parse().ok())
+        .collect();
+    Some(if listed.is_empty() {
+        vec![
+            HarnessType::ClaudeCode,
+            HarnessType::Cursor,
+            HarnessType::Factory,
+            HarnessType::OpenCode,
+            HarnessType::Cline,
+            HarnessType::Aider,
+            HarnessType::Goose,
+        ]
+    } else {
+        listed
+    })
+}
+
+/// Render a template for one harness
+///
+/// Content without a `# cupcake:template` marker is returned unchanged.
+pub fn render(content: &str, harness: HarnessType) -> Result<String> {
+    let Some(supported) = supported_harnesses(content) else {
+        return Ok(content.to_string());
+    };
+    if !supported.contains(&harness) {
+        bail!(
+            "template does not support {} (supports: {})",
+            harness.as_str(),
+            supported
+                .iter()
+                .map(|h| h.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    let mappings = parse_mappings(content, harness)?;
+    let mut output = String::with_capacity(content.len());
+    // (keep lines, seen else) for the open block
+    let mut block: Option<(bool, bool)> = None;
+
+    for (idx, line) in content.lines().enumerate() {
+        let line_no = idx + 1;
+        let fail = |msg: String| anyhow!("line {line_no}: {msg}");
+
+        if let Some((keyword, args)) = directive(line) {
+            match (keyword, block) {
+                ("template" | "map", _) => {}
+                ("if" | "unless", None) => {
+                    block = Some((condition(keyword, args, harness).map_err(fail)?, false));
+                }
+                ("if" | "unless", Some(_)) => {
+                    return Err(fail("cupcake:if blocks can't be nested".to_string()))
+                }
+                ("else", Some((keep, false))) => block = Some((!keep, true)),
+                ("else", _) => return Err(fail("cupcake:else without cupcake:if".to_string())),
+                ("end", Some(_)) => block = None,
+                ("end", None) => return Err(fail("cupcake:end without cupcake:if".to_string())),
+                (other, _) => return Err(fail(format!("unknown annotation 'cupcake:{other}'"))),
+            }
+            continue;
+        }
+
+        if block.is_some_and(|(keep, _)| !keep) {
+            continue;
+        }
+
+        let line = match line.find(DIRECTIVE) {
+            Some(pos) => {
+                let (keyword, args) = split_directive(&line[pos + DIRECTIVE.len()..]);
+                if !matches!(keyword, "if" | "unless") {
+                    return Err(fail(format!(
+                        "only cupcake:if and cupcake:unless can trail a line, found 'cupcake:{keyword}'"
+                    )));
+                }
+                if !condition(keyword, args, harness).map_err(fail)? {
+                    continue;
+                }
+                line[..pos].trim_end()
+            }
+            None => line,
+        };
+
+        output.push_str(&substitute(line, &mappings, harness).map_err(fail)?);
+        output.push('\n');
+    }
+
+    if block.is_some() {
+        bail!("cupcake:if block is never closed with cupcake:end");
+    }
+    Ok(output)
+}
+
+/// A whole-line annotation, as (keyword, arguments)
+fn directive(line: &str) -> Option<(&str, &str)> {
+    line.trim_start()
+        .strip_prefix(DIRECTIVE)
+        .map(split_directive)
+}
+
+fn split_directive(rest: &str) -> (&str, &str) {
+    let rest = rest.trim();
+    rest.split_once(char::is_whitespace)
+        .map_or((rest, ""), |(keyword, args)| (keyword, args.trim()))
+}
+
+fn condition(keyword: &str, args: &str, harness: HarnessType) -> Result<bool, String> {
+    let mut listed = false;
+    for name in args.split_whitespace() {
+        listed |= parse_harness(name)? == harness;
+    }
+    if args.is_empty() {
+        return Err(format!("cupcake:{keyword} needs at least one harness"));
+    }
+    Ok(if keyword == "if" { listed } else { !listed })
+}
+
+fn parse_harness(name: &str) -> Result<HarnessType, String> {
+    name.trim_end_matches(',').parse()
+}
+
+/// Collect `cupcake:map` values for this harness
+fn parse_mappings(content: &str, harness: HarnessType) -> Result<HashMap<String, String>> {
+    let mut mappings = HashMap::new();
+    for (idx, line) in content.lines().enumerate() {
+        let Some(("map", args)) = directive(line) else {
+            continue;
+        };
+        let fail = |msg: &str| anyhow!("line {}: cupcake:map {}", idx + 1, msg);
+        let (name, entries) = split_directive(args);
+        if name.is_empty() || entries.is_empty() {
+            return Err(fail(
+                "needs a name and at least one 'harness = value' entry",
+            ));
+        }
+
+        let mut default = None;
+        let mut value = None;
+        for entry in entries.split(';') {
+            let (targets, entry_value) = entry
+                .split_once('=')
+                .ok_or_else(|| fail(&format!("entry '{}' has no '='", entry.trim())))?;
+            let entry_value = entry_value.trim().to_string();
+            for target in targets.split(',').map(str::trim) {
+                if target == "default" {
+                    default = Some(entry_value.clone());
+                } else if parse_harness(target).map_err(|e| fail(&e))? == harness {
+                    value = Some(entry_value.clone());
+                }
+            }
+        }
+
+        if let Some(value) = value.or(default) {
+            mappings.insert(name.to_string(), value);
+        }
+    }
+    Ok(mappings)
+}
+
+/// Replace `{{name}}` placeholders in one line
+fn substitute(
+    line: &str,
+    mappings: &HashMap<String, String>,
+    harness: HarnessType,
+) -> Result<String, String> {
+    let mut output = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find("{{") {
+        let end = rest[start..]
+            .find("}}")
+            .ok_or_else(|| "unclosed '{{' placeholder".to_string())?;
+        let name = rest[start + 2..start + end].trim();
+        let value = mappings
+            .get(name)
+            .map(String::as_str)
+            .or_else(|| standard_mapping(name, harness))
+            .ok_or_else(|| {
+                format!(
+                    "placeholder '{{{{{name}}}}}' has no value for {}",
+                    harness.as_str()
+                )
+            })?;
+        output.push_str(&rest[..start]);
+        output.push_str(value);
+        rest = &rest[start + end + 2..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    const TEMPLATE: &str = r#"# cupcake:template
+# METADATA
+# custom:
+#   routing:
+#     required_events: ["{{shell_event}}"]
+#     required_tools: ["Bash"] # cupcake:unless cursor
+package cupcake.policies.example
+
+# cupcake:map verb cursor = "curl"; default = "wget"
+deny contains decision if {
+	input.hook_event_name == "{{shell_event}}"
+	contains(lower({{shell_command}}), {{verb}})
+# cupcake:if claude factory
+	input.tool_name == "Bash"
+# cupcake:else
+	true
+# cupcake:end
+}
+"#;
+
+    #[test]
+    fn test_render_per_harness() {
+        let claude = render(TEMPLATE, HarnessType::ClaudeCode).unwrap();
+        assert_eq!(
+            claude,
+            r#"# METADATA
+# custom:
+#   routing:
+#     required_events: ["PreToolUse"]
+#     required_tools: ["Bash"]
+package cupcake.policies.example
+
+deny contains decision if {
+	input.hook_event_name == "PreToolUse"
+	contains(lower(input.tool_input.command), "wget")
+	input.tool_name == "Bash"
+}
+"#
+        );
+
+        let cursor = render(TEMPLATE, HarnessType::Cursor).unwrap();
+        assert!(cursor.contains(r#"required_events: ["beforeShellExecution"]"#));
+        assert!(!cursor.contains("required_tools"));
+        assert!(cursor.contains(r#"contains(lower(input.command), "curl")"#));
+        assert!(cursor.contains("\ttrue\n"));
+        assert!(!cursor.contains("cupcake:"));
+
+        let opencode = render(TEMPLATE, HarnessType::OpenCode).unwrap();
+        assert!(opencode.contains("\ttrue\n"));
+    }
+
+    #[test]
+    fn test_plain_policies_are_untouched() {
+        let policy = "package cupcake.policies.plain\n\n# {{not a placeholder}}\n";
+        assert!(!is_template(policy));
+        assert_eq!(render(policy, HarnessType::Cursor).unwrap(), policy);
+    }
+
+    #[test]
+    fn test_template_errors() {
+        let limited = "# cupcake:template claude factory\npackage p\n";
+        assert_eq!(
+            supported_harnesses(limited).unwrap(),
+            vec![HarnessType::ClaudeCode, HarnessType::Factory]
+        );
+        let err = render(limited, HarnessType::Cursor).unwrap_err();
+        assert!(err.to_string().contains("does not support cursor"));
+
+        let unknown = "# cupcake:template\nx := {{missing}}\n";
+        let err = render(unknown, HarnessType::ClaudeCode).unwrap_err();
+        assert!(err.to_string().starts_with("line 2:"), "{err}");
+
+        let unclosed = "# cupcake:template\n# cupcake:if cursor\nx := 1\n";
+        assert!(render(unclosed, HarnessType::Cursor).is_err());
+
+        let bad_harness = "# cupcake:template\n# cupcake:if vscode\n# cupcake:end\n";
+        assert!(render(bad_harness, HarnessType::Cursor).is_err());
+    }
+
+    #[test]
+    fn test_builtin_templates_render() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../fixtures/builtins");
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let source = std::fs::read_to_string(&path).unwrap();
+            for harness in supported_harnesses(&source).expect("builtin is not a template") {
+                let rendered = render(&source, harness)
+                    .unwrap_or_else(|e| panic!("{path:?} for {harness}: {e}"));
+                assert!(!rendered.contains("cupcake:") && !rendered.contains("{{"));
+                let metadata = crate::engine::metadata::parse_metadata(&rendered)
+                    .unwrap()
+                    .expect("rendered builtin has no metadata");
+                assert!(metadata.custom.routing.is_some(), "{path:?} for {harness}");
+            }
+        }
+    }
+}
@@ -0,0 +1,456 @@
+//! Waivers - justified, time-boxed exceptions to individual rules
+//!
+//! Instead of deleting or editing a rule, a security team can suppress it for
+//! a path, a user or a limited period in `.cupcake/waivers.yml`:
+//!
+//! ```yaml
+//! waivers:
+//!   - rule_id: BUILTIN-GIT-CHECK
+//!     justification: "Hook migration in progress (SEC-1234)"
+//!     approved_by: security@example.com
+//!     expires: 2026-12-31          # date (inclusive, UTC) or RFC 3339 timestamp
+//!     paths: ["services/legacy/**"]
+//!     users: [alice]
+//!     downgrade_to: ask            # default: allow
+//! ```
+//!
+//! A halt, deny, block or ask decision whose rule_id matches an active waiver is
+//! dropped (`allow`) or turned into an ask (`ask`). A waiver is active until it
+//! expires; `paths` and `users` narrow it further when present. Paths are globs
+//! relative to the project root matched against the file the event acts on, and
+//! users are matched against the tier's resolved identity. Every waiver used is logged and
+//! recorded in the telemetry of its tier. Each tier - global, team and project -
+//! reads `waivers.yml` from its own configuration directory and waives only its
+//! own decisions, so a project cannot waive organization policies.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing::{info, warn};
+
+use super::decision::{DecisionObject, DecisionSet};
+
+/// File name of the waivers file inside a configuration directory
+pub const WAIVERS_FILE: &str = "waivers.yml";
+
+/// What a waived decision becomes
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WaiverAction {
+    /// Drop the decision
+    #[default]
+    Allow,
+    /// Ask the user instead of blocking
+    Ask,
+}
+
+/// One waiver as written in `waivers.yml`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Waiver {
+    /// Rule whose decisions are waived
+    pub rule_id: String,
+
+    /// Why the exception exists - required
+    pub justification: String,
+
+    /// Who approved the exception
+    #[serde(default)]
+    pub approved_by: Option<String>,
+
+    /// Last day (UTC) or instant the waiver applies
+    #[serde(default)]
+    pub expires: Option<String>,
+
+    /// Globs of project-relative paths the waiver is limited to
+    #[serde(default)]
+    pub paths: Vec<String>,
+
+    /// Users the waiver is limited to
+    #[serde(default)]
+    pub users: Vec<String>,
+
+    /// What waived decisions become
+    #[serde(default)]
+    pub downgrade_to: WaiverAction,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WaiversFile {
+    #[serde(default)]
+    waivers: Vec<Waiver>,
+}
+
+/// A waiver ready to be matched
+#[derive(Debug, Clone)]
+struct ActiveWaiver {
+    waiver: Waiver,
+    expires_at: Option<DateTime<Utc>>,
+    paths: Vec<Regex>,
+}
+
+/// Record of a waiver suppressing or downgrading one decision
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WaiverUse {
+    pub rule_id: String,
+    pub severity: String,
+    /// Verb the decision was emitted with
+    pub from: String,
+    /// Verb it was enforced as
+    pub to: WaiverAction,
+    pub justification: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approved_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+}
+
+/// The waivers of one tier
+#[derive(Debug, Clone, Default)]
+pub struct Waivers {
+    waivers: Vec<ActiveWaiver>,
+}
+
+impl Waivers {
+    /// Load the waivers files of a tier; missing files contribute nothing
+    pub fn load(files: &[impl AsRef<Path>]) -> Result<Self> {
+        let mut waivers = Vec::new();
+        let mut errors = Vec::new();
+        for file in files.iter().map(AsRef::as_ref).filter(|f| f.exists()) {
+            let content = std::fs::read_to_string(file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            let parsed: WaiversFile = serde_yaml_ng::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", file.display()))?;
+
+            for (idx, waiver) in parsed.waivers.into_iter().enumerate() {
+                match compile(waiver) {
+                    Ok(active) => waivers.push(active),
+                    Err(e) => errors.push(format!("{}: waivers[{idx}]: {e}", file.display())),
+                }
+            }
+            info!("Loaded waivers from {}", file.display());
+        }
+        if !errors.is_empty() {
+            bail!("Waiver errors:\n{}", errors.join("\n"));
+        }
+
+        let now = Utc::now();
+        for active in waivers.iter().filter(|w| !w.is_current(now)) {
+            warn!(
+                "Waiver for {} expired on {} and no longer applies - remove it",
+                active.waiver.rule_id,
+                active.waiver.expires.as_deref().unwrap_or_default()
+            );
+        }
+        Ok(Self { waivers })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.waivers.is_empty()
+    }
+
+    /// Waive matching halt, deny, block and ask decisions in place
+    ///
+    /// `target` is the path the event acts on, `project_root` anchors relative
+    /// path globs and `user` comes from the identity. Returns one record per
+    /// waived decision.
+    pub fn apply(
+        &self,
+        decision_set: &mut DecisionSet,
+        target: Option<&Path>,
+        project_root: &Path,
+        user: Option<&str>,
+    ) -> Vec<WaiverUse> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        // Resolved file paths are canonical, the project root may not be
+        let canonical_root = project_root.canonicalize().ok();
+        let relative = target.map(|path| {
+            path.strip_prefix(project_root)
+                .ok()
+                .or_else(|| {
+                    canonical_root
+                        .as_ref()
+                        .and_then(|r| path.strip_prefix(r).ok())
+                })
+                .unwrap_or(path)
+        });
+        self.apply_at(decision_set, relative, user, Utc::now())
+    }
+
+    fn apply_at(
+        &self,
+        decision_set: &mut DecisionSet,
+        target: Option<&Path>,
+        user: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Vec<WaiverUse> {
+        let mut uses = Vec::new();
+        let mut asks = Vec::new();
+        for (verb, list) in [
+            ("halt", &mut decision_set.halts),
+            ("deny", &mut decision_set.denials),
+            ("block", &mut decision_set.blocks),
+            ("ask", &mut decision_set.asks),
+        ] {
+            let mut kept = Vec::with_capacity(list.len());
+            for mut decision in std::mem::take(list) {
+                let Some(active) = self
+                    .waivers
+                    .iter()
+                    .find(|w| w.matches(&decision, target, user, now))
+                else {
+                    kept.push(decision);
+                    continue;
+                };
+                let waiver = &active.waiver;
+                info!(
+                    "Waiver applied: {} {} -> {:?} ({})",
+                    decision.rule_id, verb, waiver.downgrade_to, waiver.justification
+                );
+                uses.push(WaiverUse {
+                    rule_id: decision.rule_id.clone(),
+                    severity: decision.severity.clone(),
+                    from: verb.to_string(),
+                    to: waiver.downgrade_to,
+                    justification: waiver.justification.clone(),
+                    approved_by: waiver.approved_by.clone(),
+                    expires: waiver.expires.clone(),
+                });
+                if waiver.downgrade_to == WaiverAction::Ask {
+                    if verb == "ask" {
+                        kept.push(decision);
+                    } else {
+                        decision.reason =
+                            format!("{} (waived: {})", decision.reason, waiver.justification);
+                        asks.push(decision);
+                    }
+                }
+            }
+            *list = kept;
+        }
+        decision_set.asks.extend(asks);
+        uses
+    }
+}
+
+impl ActiveWaiver {
+    fn is_current(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_none_or(|expires_at| now <= expires_at)
+    }
+
+    fn matches(
+        &self,
+        decision: &DecisionObject,
+        target: Option<&Path>,
+        user: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        let waiver = &self.waiver;
+        if waiver.rule_id != decision.rule_id || !self.is_current(now) {
+            return false;
+        }
+        if !waiver.users.is_empty()
+            && !user.is_some_and(|user| waiver.users.iter().any(|u| u == user))
+        {
+            return false;
+        }
+        if !self.paths.is_empty() {
+            let Some(target) = target.and_then(Path::to_str) else {
+                return false;
+            };
+            let target = target.replace('\\', "/");
+            return self.paths.iter().any(|glob| glob.is_match(&target));
+        }
+        true
+    }
+}
+
+fn compile(waiver: Waiver) -> Result<ActiveWaiver, String> {
+    if waiver.rule_id.trim().is_empty() {
+        return Err("rule_id must not be empty".to_string());
+    }
+    if waiver.justification.trim().is_empty() {
+        return Err(format!("{}: a justification is required", waiver.rule_id));
+    }
+    let expires_at = waiver
+        .expires
+        .as_deref()
+        .map(parse_expiry)
+        .transpose()
+        .map_err(|e| format!("{}: {e}", waiver.rule_id))?;
+    let paths = waiver
+        .paths
+        .iter()
+        .map(|pattern| glob_to_regex(pattern))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("{}: {e}", waiver.rule_id))?;
+    Ok(ActiveWaiver {
+        waiver,
+        expires_at,
+        paths,
+    })
+}
+
+/// A date applies through the end of that day (UTC)
+fn parse_expiry(value: &str) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(23, 59, 59).expect("valid time").and_utc());
+    }
+    DateTime::parse_from_rfc3339(value)
+        .map(|instant| instant.with_timezone(&Utc))
+        .map_err(|_| format!("expires '{value}' is not a YYYY-MM-DD date or RFC 3339 timestamp"))
+}
+
+/// `**` crosses directories, `*` and `?` don't; a plain path also covers
+/// everything below it
+fn glob_to_regex(pattern: &str) -> Result<Regex, String> {
+    let pattern = pattern
+        .trim()
+        .trim_start_matches("./")
+        .trim_end_matches('/');
+    if pattern.is_empty() {
+        return Err("path patterns must not be empty".to_string());
+    }
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push_str("(?:/.*)?$");
+    Regex::new(&regex).map_err(|e| format!("invalid path pattern '{pattern}': {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn decision(rule_id: &str) -> DecisionObject {
+        DecisionObject {
+            reason: format!("{rule_id} fired"),
+            severity: "HIGH".to_string(),
+            rule_id: rule_id.to_string(),
+            agent_context: None,
+            docs_url: None,
+            remediation: None,
+        }
+    }
+
+    fn waivers(yaml: &str) -> Result<Waivers> {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join(WAIVERS_FILE);
+        std::fs::write(&file, yaml).unwrap();
+        Waivers::load(&[file])
+    }
+
+    fn at(date: &str) -> DateTime<Utc> {
+        parse_expiry(date).unwrap()
+    }
+
+    #[test]
+    fn test_waiver_scoping() {
+        let waivers = waivers(
+            r#"
+waivers:
+  - rule_id: GIT-001
+    justification: "Hook migration"
+    expires: 2026-12-31
+  - rule_id: RM-001
+    justification: "Build cleanup"
+    paths: ["build/**", "scripts/*.sh"]
+    users: [alice]
+    downgrade_to: ask
+"#,
+        )
+        .unwrap();
+        let set = || DecisionSet {
+            denials: vec![decision("GIT-001"), decision("RM-001")],
+            ..Default::default()
+        };
+
+        // Before expiry, as alice, on a matching path
+        let mut decision_set = set();
+        let uses = waivers.apply_at(
+            &mut decision_set,
+            Some(Path::new("build/out/a.o")),
+            Some("alice"),
+            at("2026-12-31"),
+        );
+        assert_eq!(uses.len(), 2);
+        assert!(decision_set.denials.is_empty());
+        assert_eq!(decision_set.asks.len(), 1);
+        assert_eq!(decision_set.asks[0].rule_id, "RM-001");
+        assert!(decision_set.asks[0]
+            .reason
+            .contains("waived: Build cleanup"));
+
+        // After expiry, as someone else, on another path: nothing is waived
+        let mut decision_set = set();
+        let uses = waivers.apply_at(
+            &mut decision_set,
+            Some(Path::new("scripts/nested/x.sh")),
+            Some("bob"),
+            at("2027-01-01"),
+        );
+        assert!(uses.is_empty());
+        assert_eq!(decision_set.denials.len(), 2);
+    }
+
+    #[test]
+    fn test_glob_patterns() {
+        let glob = glob_to_regex("src/**/*.rs").unwrap();
+        assert!(glob.is_match("src/main.rs"));
+        assert!(glob.is_match("src/a/b/lib.rs"));
+        assert!(!glob.is_match("tests/main.rs"));
+
+        let dir = glob_to_regex("vendor/").unwrap();
+        assert!(dir.is_match("vendor"));
+        assert!(dir.is_match("vendor/x/y"));
+        assert!(!dir.is_match("vendored/x"));
+    }
+
+    #[test]
+    fn test_invalid_waivers_are_rejected() {
+        let error = waivers(
+            r#"
+waivers:
+  - rule_id: GIT-001
+    justification: ""
+  - rule_id: RM-001
+    justification: "ok"
+    expires: next week
+"#,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("waivers[0]: GIT-001: a justification is required"));
+        assert!(error.contains("waivers[1]: RM-001: expires 'next week'"));
+
+        assert!(
+            waivers("waivers:\n  - rule_id: X\n    justification: y\n    until: 2026-01-01\n")
+                .is_err()
+        );
+        assert!(Waivers::load(&[Path::new("/nonexistent/waivers.yml")])
+            .unwrap()
+            .is_empty());
+    }
+}
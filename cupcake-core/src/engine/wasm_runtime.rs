@@ -1,14 +1,24 @@
 //! WASM Runtime - Executes compiled Rego policies.
 //!
 //! Queries the `cupcake.system.evaluate` aggregation endpoint and returns a [`DecisionSet`].
+//!
+//! Instantiated modules are kept in a pool and reused, so concurrent evaluations
+//! each get their own instance without paying for instantiation every time.
+//!
+//! Each evaluation reports how much WASM memory it used. Growing memory past
+//! `--wasm-max-memory` fails the evaluation with a [`MemoryLimitExceeded`] error
+//! naming the entrypoint and the policies that were routed to it.
 
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::Instant;
 use tracing::{debug, instrument, trace, warn};
 use wasmtime::*;
 
 use super::decision::DecisionSet;
+use super::module_cache::ModuleCache;
 
 // --- PART 1: Production-Grade Memory Configuration ---
 // This logic is NOT deprecated. It is a required feature.
@@ -55,6 +65,138 @@ fn get_memory_config(max_memory_bytes_override: Option<usize>) -> (u32, Option<u
     (DEFAULT_INITIAL_PAGES, Some(max_pages))
 }
 
+// --- PART 2: Instance Pooling ---
+
+/// Environment variable overriding the instance pool size
+pub const WASM_POOL_SIZE_ENV: &str = "CUPCAKE_WASM_POOL_SIZE";
+
+/// Gets the number of idle instances to keep per runtime.
+///
+/// Precedence: explicit override, then `CUPCAKE_WASM_POOL_SIZE`, then the number
+/// of available CPUs. A size of 0 disables reuse.
+fn get_pool_size(pool_size_override: Option<usize>) -> usize {
+    pool_size_override
+        .or_else(|| {
+            let value = std::env::var(WASM_POOL_SIZE_ENV).ok()?;
+            match value.parse() {
+                Ok(size) => Some(size),
+                Err(_) => {
+                    warn!("Ignoring invalid {}: {:?}", WASM_POOL_SIZE_ENV, value);
+                    None
+                }
+            }
+        })
+        .unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+        })
+}
+
+// --- PART 3: Memory Accounting ---
+
+/// WASM memory used by a single evaluation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryUsage {
+    /// Heap allocated by the evaluation (input, intermediate values and result)
+    pub heap_bytes: u64,
+    /// Size of the instance's linear memory after the evaluation
+    pub memory_bytes: u64,
+    /// Configured memory limit (`--wasm-max-memory`)
+    pub max_memory_bytes: u64,
+}
+
+/// An evaluation tried to grow WASM memory past the configured limit
+#[derive(Debug, Clone, thiserror::Error)]
+#[error(
+    "WASM memory limit of {limit_bytes} bytes exceeded while evaluating {entrypoint}{}; \
+     raise --wasm-max-memory or reduce the policies' memory use",
+    format_policies(policies)
+)]
+pub struct MemoryLimitExceeded {
+    /// Entrypoint being evaluated, e.g. `cupcake.system.evaluate`
+    pub entrypoint: String,
+    pub limit_bytes: u64,
+    /// Policies routed to this evaluation, filled in by the engine
+    pub policies: Vec<String>,
+}
+
+fn format_policies(policies: &[String]) -> String {
+    if policies.is_empty() {
+        String::new()
+    } else {
+        format!(" (routed policies: {})", policies.join(", "))
+    }
+}
+
+/// Attribute a memory limit error to the policies routed to the evaluation
+///
+/// Other errors are returned unchanged.
+pub fn attribute_policies(mut error: anyhow::Error, policies: &[String]) -> anyhow::Error {
+    if let Some(exceeded) = error.downcast_mut::<MemoryLimitExceeded>() {
+        exceeded.policies = policies.to_vec();
+    }
+    error
+}
+
+/// Store data that records attempts to grow memory past its maximum
+#[derive(Default)]
+struct MemoryLimiter {
+    exceeded: bool,
+}
+
+impl ResourceLimiter for MemoryLimiter {
+    fn memory_growing(
+        &mut self,
+        _current: usize,
+        desired: usize,
+        maximum: Option<usize>,
+    ) -> Result<bool> {
+        if maximum.is_some_and(|max| desired > max) {
+            self.exceeded = true;
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    fn table_growing(
+        &mut self,
+        _current: usize,
+        _desired: usize,
+        _maximum: Option<usize>,
+    ) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+type OpaEvalFunc = TypedFunc<(i32, i32, i32, i32, i32, i32, i32), i32>;
+
+/// An instantiated policy module that can serve many evaluations
+struct PooledInstance {
+    store: Store<MemoryLimiter>,
+    memory: Memory,
+    opa_malloc: TypedFunc<i32, i32>,
+    opa_heap_ptr_get: TypedFunc<(), i32>,
+    opa_heap_ptr_set: TypedFunc<i32, ()>,
+    opa_eval: OpaEvalFunc,
+    /// Data document loaded into this instance (compared by identity)
+    data_json: Option<Arc<String>>,
+    data_ptr: i32,
+    /// Heap pointer after the data document; reset before each evaluation
+    heap_base: i32,
+}
+
+impl PooledInstance {
+    /// Whether this instance was loaded with the given data document
+    fn has_data(&self, data_json: &Option<Arc<String>>) -> bool {
+        match (&self.data_json, data_json) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
 /// WASM runtime for executing compiled Rego policies
 pub struct WasmRuntime {
     engine: Engine,
@@ -63,6 +205,14 @@ pub struct WasmRuntime {
     namespace: String,
     /// Optional max memory override from CLI (in bytes)
     max_memory_bytes: Option<usize>,
+    /// Data document from the policy bundle (`data.{json,yaml}` files)
+    base_data: Option<Value>,
+    /// Serialized data document made available to policies as `data.*`
+    data_json: RwLock<Option<Arc<String>>>,
+    /// Idle instances ready for reuse
+    pool: Mutex<Vec<PooledInstance>>,
+    /// Maximum number of idle instances kept in the pool
+    pool_size: usize,
 }
 
 impl WasmRuntime {
@@ -82,6 +232,16 @@ impl WasmRuntime {
         wasm_bytes: &[u8],
         namespace: &str,
         max_memory_bytes: Option<usize>,
+    ) -> Result<Self> {
+        Self::new_with_cache(wasm_bytes, namespace, max_memory_bytes, None)
+    }
+
+    /// Create a new runtime, loading precompiled machine code from the module cache
+    pub fn new_with_cache(
+        wasm_bytes: &[u8],
+        namespace: &str,
+        max_memory_bytes: Option<usize>,
+        cache: Option<&ModuleCache>,
     ) -> Result<Self> {
         debug!("Initializing WASM runtime");
 
@@ -91,8 +251,12 @@ impl WasmRuntime {
         config.wasm_multi_value(true);
 
         let engine = Engine::new(&config)?;
-        let module =
-            Module::from_binary(&engine, wasm_bytes).context("Failed to load WASM module")?;
+        let module = match cache {
+            Some(cache) => cache.load(&engine, wasm_bytes, namespace)?,
+            None => {
+                Module::from_binary(&engine, wasm_bytes).context("Failed to load WASM module")?
+            }
+        };
 
         debug!("WASM module loaded successfully");
 
@@ -101,12 +265,52 @@ impl WasmRuntime {
             module,
             namespace: namespace.to_string(),
             max_memory_bytes,
+            base_data: None,
+            data_json: RwLock::new(None),
+            pool: Mutex::new(Vec::new()),
+            pool_size: get_pool_size(None),
         })
     }
 
+    /// Set the maximum number of idle instances kept for reuse
+    ///
+    /// If None, uses `CUPCAKE_WASM_POOL_SIZE` or the number of available CPUs.
+    pub fn with_pool_size(mut self, pool_size: Option<usize>) -> Self {
+        self.pool_size = get_pool_size(pool_size);
+        debug!("WASM instance pool size: {}", self.pool_size);
+        self
+    }
+
+    /// Attach the bundle's data document (from `data.{json,yaml}` files)
+    pub fn with_data(mut self, data: Option<Value>) -> Self {
+        self.data_json = RwLock::new(data.as_ref().map(|d| Arc::new(d.to_string())));
+        self.base_data = data;
+        self
+    }
+
+    /// Replace `data.external` with a freshly refreshed external data document
+    pub fn set_external_data(&self, external: Value) {
+        let mut data = match &self.base_data {
+            Some(Value::Object(map)) => map.clone(),
+            _ => serde_json::Map::new(),
+        };
+        data.insert("external".to_string(), external);
+        let serialized = Arc::new(Value::Object(data).to_string());
+        match self.data_json.write() {
+            Ok(mut guard) => *guard = Some(serialized),
+            Err(poisoned) => *poisoned.into_inner() = Some(serialized),
+        }
+    }
+
     /// Query the aggregated decision set from cupcake.system.evaluate
     /// This is the single entrypoint defined in the Hybrid Model
-    /// Thread-safe: creates fresh Store per evaluation
+    /// Thread-safe: each concurrent evaluation uses its own pooled instance
+    pub fn query_decision_set(&self, input: &Value) -> Result<DecisionSet> {
+        self.query_decision_set_with_usage(input)
+            .map(|(decision_set, _)| decision_set)
+    }
+
+    /// Query the aggregated decision set, also reporting the memory the evaluation used
     #[instrument(
         name = "wasm_evaluate",
         skip(self, input),
@@ -114,10 +318,15 @@ impl WasmRuntime {
             input_size_bytes = input.to_string().len(),
             output_size_bytes = tracing::field::Empty,
             decision_count = tracing::field::Empty,
-            evaluation_time_ms = tracing::field::Empty
+            evaluation_time_ms = tracing::field::Empty,
+            heap_bytes = tracing::field::Empty,
+            memory_bytes = tracing::field::Empty
         )
     )]
-    pub fn query_decision_set(&self, input: &Value) -> Result<DecisionSet> {
+    pub fn query_decision_set_with_usage(
+        &self,
+        input: &Value,
+    ) -> Result<(DecisionSet, MemoryUsage)> {
         let start = Instant::now();
         debug!(
             "Querying DecisionSet from {}.evaluate entrypoint",
@@ -125,7 +334,7 @@ impl WasmRuntime {
         );
 
         // Use the low-level evaluate_raw function with entrypoint 0 (single entrypoint)
-        let result_json = self.evaluate_raw(input, 0)?;
+        let (result_json, usage) = self.evaluate_raw(input, 0)?;
 
         debug!("Raw WASM result JSON: {}", result_json);
 
@@ -143,21 +352,78 @@ impl WasmRuntime {
         current_span.record("output_size_bytes", result_json.len());
         current_span.record("decision_count", decision_set.decision_count());
         current_span.record("evaluation_time_ms", elapsed.as_millis());
+        current_span.record("heap_bytes", usage.heap_bytes);
+        current_span.record("memory_bytes", usage.memory_bytes);
 
         debug!("Decision set evaluation completed in {:?}", elapsed);
         trace!(
             decisions = decision_set.decision_count(),
             duration_ms = elapsed.as_millis(),
+            heap_bytes = usage.heap_bytes,
             "WASM evaluation complete"
         );
 
-        Ok(decision_set)
+        Ok((decision_set, usage))
     }
 
     /// Low-level function that interacts with the OPA WASM ABI
     /// Takes an input JSON value and returns the raw JSON string from the policy
-    fn evaluate_raw(&self, input: &Value, entrypoint_id: i32) -> Result<String> {
-        let mut store = Store::new(&self.engine, ());
+    fn evaluate_raw(&self, input: &Value, entrypoint_id: i32) -> Result<(String, MemoryUsage)> {
+        let data_json = match self.data_json.read() {
+            Ok(guard) => guard.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        };
+
+        let mut instance = match self.take_instance(&data_json) {
+            Some(instance) => instance,
+            None => self.instantiate(data_json)?,
+        };
+
+        // An instance that failed mid-evaluation may be in any state - drop it
+        let result = match self.evaluate_instance(&mut instance, input, entrypoint_id) {
+            Ok(result) => result,
+            Err(e) if instance.store.data().exceeded => {
+                debug!("WASM evaluation hit the memory limit: {:#}", e);
+                return Err(MemoryLimitExceeded {
+                    entrypoint: format!("{}.evaluate", self.namespace),
+                    limit_bytes: self.max_memory_bytes(),
+                    policies: Vec::new(),
+                }
+                .into());
+            }
+            Err(e) => return Err(e),
+        };
+        self.return_instance(instance);
+        Ok(result)
+    }
+
+    /// Take an idle instance loaded with the current data document
+    fn take_instance(&self, data_json: &Option<Arc<String>>) -> Option<PooledInstance> {
+        let mut pool = match self.pool.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        // Instances holding a stale data document are discarded
+        pool.retain(|instance| instance.has_data(data_json));
+        pool.pop()
+    }
+
+    /// Return an instance to the pool if there is room for it
+    fn return_instance(&self, instance: PooledInstance) {
+        let mut pool = match self.pool.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if pool.len() < self.pool_size {
+            pool.push(instance);
+        }
+    }
+
+    /// Instantiate the module and load the data document into its heap
+    fn instantiate(&self, data_json: Option<Arc<String>>) -> Result<PooledInstance> {
+        trace!("Instantiating WASM module for {}", self.namespace);
+        let mut store = Store::new(&self.engine, MemoryLimiter::default());
+        store.limiter(|limiter| limiter);
         let mut linker = Linker::new(&self.engine);
 
         // Use the robust, configurable memory logic with CLI override
@@ -167,34 +433,44 @@ impl WasmRuntime {
         linker.define(&mut store, "env", "memory", memory)?;
 
         // Provide the required OPA host functions
-        linker.func_wrap("env", "opa_abort", |_: Caller<'_, ()>, addr: i32| {
-            tracing::error!(addr, "OPA policy aborted execution.");
-        })?;
-        linker.func_wrap("env", "opa_println", |_: Caller<'_, ()>, _: i32| {})?;
+        linker.func_wrap(
+            "env",
+            "opa_abort",
+            |_: Caller<'_, MemoryLimiter>, addr: i32| {
+                tracing::error!(addr, "OPA policy aborted execution.");
+            },
+        )?;
+        linker.func_wrap(
+            "env",
+            "opa_println",
+            |_: Caller<'_, MemoryLimiter>, _: i32| {},
+        )?;
         linker.func_wrap(
             "env",
             "opa_builtin0",
-            |_: Caller<'_, ()>, _: i32, _: i32| -> i32 { 0 },
+            |_: Caller<'_, MemoryLimiter>, _: i32, _: i32| -> i32 { 0 },
         )?;
         linker.func_wrap(
             "env",
             "opa_builtin1",
-            |_: Caller<'_, ()>, _: i32, _: i32, _: i32| -> i32 { 0 },
+            |_: Caller<'_, MemoryLimiter>, _: i32, _: i32, _: i32| -> i32 { 0 },
         )?;
         linker.func_wrap(
             "env",
             "opa_builtin2",
-            |_: Caller<'_, ()>, _: i32, _: i32, _: i32, _: i32| -> i32 { 0 },
+            |_: Caller<'_, MemoryLimiter>, _: i32, _: i32, _: i32, _: i32| -> i32 { 0 },
         )?;
         linker.func_wrap(
             "env",
             "opa_builtin3",
-            |_: Caller<'_, ()>, _: i32, _: i32, _: i32, _: i32, _: i32| -> i32 { 0 },
+            |_: Caller<'_, MemoryLimiter>, _: i32, _: i32, _: i32, _: i32, _: i32| -> i32 { 0 },
         )?;
         linker.func_wrap(
             "env",
             "opa_builtin4",
-            |_: Caller<'_, ()>, _: i32, _: i32, _: i32, _: i32, _: i32, _: i32| -> i32 { 0 },
+            |_: Caller<'_, MemoryLimiter>, _: i32, _: i32, _: i32, _: i32, _: i32, _: i32| -> i32 {
+                0
+            },
         )?;
 
         let instance = linker.instantiate(&mut store, &self.module)?;
@@ -206,24 +482,81 @@ impl WasmRuntime {
         let opa_malloc = instance.get_typed_func::<i32, i32>(&mut store, "opa_malloc")?;
         let opa_heap_ptr_get =
             instance.get_typed_func::<(), i32>(&mut store, "opa_heap_ptr_get")?;
+        let opa_heap_ptr_set =
+            instance.get_typed_func::<i32, ()>(&mut store, "opa_heap_ptr_set")?;
         let opa_eval = instance
             .get_typed_func::<(i32, i32, i32, i32, i32, i32, i32), i32>(&mut store, "opa_eval")?;
 
+        // Load the data document, if any, into the instance's heap
+        let data_ptr = match &data_json {
+            Some(data_json) => {
+                let opa_json_parse =
+                    instance.get_typed_func::<(i32, i32), i32>(&mut store, "opa_json_parse")?;
+                let data_bytes = data_json.as_bytes();
+                let raw_ptr = opa_malloc.call(&mut store, data_bytes.len() as i32)?;
+                memory.write(&mut store, raw_ptr as usize, data_bytes)?;
+                let parsed = opa_json_parse.call(&mut store, (raw_ptr, data_bytes.len() as i32))?;
+                if parsed == 0 {
+                    anyhow::bail!("Failed to load policy data document into WASM");
+                }
+                parsed
+            }
+            None => 0,
+        };
+
+        let heap_base = opa_heap_ptr_get.call(&mut store, ())?;
+
+        Ok(PooledInstance {
+            store,
+            memory,
+            opa_malloc,
+            opa_heap_ptr_get,
+            opa_heap_ptr_set,
+            opa_eval,
+            data_json,
+            data_ptr,
+            heap_base,
+        })
+    }
+
+    /// Configured memory limit in bytes
+    fn max_memory_bytes(&self) -> u64 {
+        let (_, max_pages) = get_memory_config(self.max_memory_bytes);
+        max_pages.map_or(0, |pages| u64::from(pages) * 65536)
+    }
+
+    /// Evaluate input against an instance, reclaiming the previous evaluation's heap
+    fn evaluate_instance(
+        &self,
+        instance: &mut PooledInstance,
+        input: &Value,
+        entrypoint_id: i32,
+    ) -> Result<(String, MemoryUsage)> {
+        let store = &mut instance.store;
+        store.data_mut().exceeded = false;
+        instance
+            .opa_heap_ptr_set
+            .call(&mut *store, instance.heap_base)?;
+
         let input_json = serde_json::to_string(input)?;
         debug!("WASM input JSON: {}", input_json);
         let input_bytes = input_json.as_bytes();
 
-        let input_ptr = opa_malloc.call(&mut store, input_bytes.len() as i32)?;
-        memory.write(&mut store, input_ptr as usize, input_bytes)?;
+        let input_ptr = instance
+            .opa_malloc
+            .call(&mut *store, input_bytes.len() as i32)?;
+        instance
+            .memory
+            .write(&mut *store, input_ptr as usize, input_bytes)?;
 
-        let heap_ptr_before = opa_heap_ptr_get.call(&mut store, ())?;
+        let heap_ptr_before = instance.opa_heap_ptr_get.call(&mut *store, ())?;
 
-        let result_ptr = opa_eval.call(
-            &mut store,
+        let result_ptr = instance.opa_eval.call(
+            &mut *store,
             (
                 0,
                 entrypoint_id,
-                0,
+                instance.data_ptr,
                 input_ptr,
                 input_bytes.len() as i32,
                 heap_ptr_before,
@@ -231,7 +564,16 @@ impl WasmRuntime {
             ),
         )?;
 
-        read_string_from_memory(&memory, &mut store, result_ptr)
+        let result = read_string_from_memory(&instance.memory, store, result_ptr)?;
+
+        let heap_ptr_after = instance.opa_heap_ptr_get.call(&mut *store, ())?;
+        let usage = MemoryUsage {
+            heap_bytes: heap_ptr_after.saturating_sub(instance.heap_base).max(0) as u64,
+            memory_bytes: instance.memory.data_size(&*store) as u64,
+            max_memory_bytes: self.max_memory_bytes(),
+        };
+
+        Ok((result, usage))
     }
 
     /// Extract the decision set from the WASM result
@@ -299,7 +641,11 @@ impl WasmRuntime {
 }
 
 /// Helper function to read a null-terminated string from WASM memory
-fn read_string_from_memory(memory: &Memory, store: &mut Store<()>, ptr: i32) -> Result<String> {
+fn read_string_from_memory(
+    memory: &Memory,
+    store: &mut Store<MemoryLimiter>,
+    ptr: i32,
+) -> Result<String> {
     let mut buffer = Vec::new();
     let mut offset = ptr as usize;
     loop {
@@ -313,3 +659,42 @@ fn read_string_from_memory(memory: &Memory, store: &mut Store<()>, ptr: i32) ->
     }
     Ok(String::from_utf8(buffer)?)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_limit_error_names_routed_policies() {
+        let error: anyhow::Error = MemoryLimitExceeded {
+            entrypoint: "cupcake.system.evaluate".to_string(),
+            limit_bytes: 1048576,
+            policies: Vec::new(),
+        }
+        .into();
+        let error = attribute_policies(
+            error,
+            &[
+                "cupcake.policies.a".to_string(),
+                "cupcake.policies.b".to_string(),
+            ],
+        );
+
+        let message = error.to_string();
+        assert!(message.contains("1048576 bytes exceeded while evaluating cupcake.system.evaluate"));
+        assert!(message.contains("(routed policies: cupcake.policies.a, cupcake.policies.b)"));
+        assert!(error.downcast_ref::<MemoryLimitExceeded>().is_some());
+    }
+
+    #[test]
+    fn test_limiter_records_growth_past_maximum() {
+        let mut limiter = MemoryLimiter::default();
+        assert!(limiter.memory_growing(65536, 131072, Some(131072)).unwrap());
+        assert!(!limiter.exceeded);
+
+        assert!(!limiter
+            .memory_growing(131072, 196608, Some(131072))
+            .unwrap());
+        assert!(limiter.exceeded);
+    }
+}
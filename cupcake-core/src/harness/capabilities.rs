@@ -0,0 +1,389 @@
+//! Harness capability matrix
+//!
+//! Not every harness can express every decision on every event - Cursor can't
+//! rewrite tool input, Claude Code can't ask on UserPromptSubmit, nothing can
+//! block a SessionEnd. Each harness describes what an event supports here, and
+//! `degrade` rewrites a FinalDecision into one the event can express before the
+//! response builders see it:
+//!
+//! | Unsupported      | Becomes                                                      |
+//! | ---------------- | ------------------------------------------------------------ |
+//! | `ask`            | `deny` if the event can be blocked, otherwise `allow`        |
+//! | `modify`         | `allow`, with the reason as context                          |
+//! | `filter_output`  | `allow`, with the reason as context                          |
+//! | context on allow | dropped                                                      |
+//!
+//! Every downgrade except dropped context is logged as a warning, so a policy
+//! that can't be enforced as written is visible in the logs.
+
+use tracing::{debug, warn};
+
+use super::events::aider::AiderEvent;
+use super::events::claude_code::ClaudeCodeEvent;
+use super::events::cline::ClineEvent;
+use super::events::cursor::CursorEvent;
+use super::events::factory::FactoryEvent;
+use super::events::goose::GooseEvent;
+use super::events::opencode::OpenCodeEvent;
+use super::types::HarnessType;
+use crate::engine::decision::FinalDecision;
+
+/// What a harness can do in response to one event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HarnessCapabilities {
+    pub harness: HarnessType,
+    pub event: &'static str,
+    /// Can prevent the action the event is about. Blocking a stop keeps the
+    /// agent working instead, so stop events don't count
+    pub block: bool,
+    /// Can ask the user for confirmation
+    pub ask: bool,
+    /// Can replace the tool input before execution
+    pub modify_input: bool,
+    /// Can replace the tool output the agent sees
+    pub filter_output: bool,
+    /// Can add context for the agent on allow
+    pub context_injection: bool,
+}
+
+impl HarnessCapabilities {
+    fn new(harness: HarnessType, event: &'static str) -> Self {
+        Self {
+            harness,
+            event,
+            block: false,
+            ask: false,
+            modify_input: false,
+            filter_output: false,
+            context_injection: false,
+        }
+    }
+
+    pub fn claude_code(event: &ClaudeCodeEvent) -> Self {
+        let caps = Self::new(HarnessType::ClaudeCode, event.event_name());
+        match event {
+            ClaudeCodeEvent::PreToolUse(_) | ClaudeCodeEvent::PermissionRequest(_) => Self {
+                block: true,
+                ask: true,
+                modify_input: true,
+                ..caps
+            },
+            ClaudeCodeEvent::PostToolUse(_) => Self {
+                block: true,
+                // Only MCP output can be replaced - the builder checks the tool name
+                filter_output: true,
+                context_injection: true,
+                ..caps
+            },
+            ClaudeCodeEvent::UserPromptSubmit(_) | ClaudeCodeEvent::SessionStart(_) => Self {
+                block: true,
+                context_injection: true,
+                ..caps
+            },
+            ClaudeCodeEvent::PreCompact(_) => Self {
+                context_injection: true,
+                ..caps
+            },
+            ClaudeCodeEvent::Stop(_)
+            | ClaudeCodeEvent::SubagentStop(_)
+            | ClaudeCodeEvent::Notification(_)
+            | ClaudeCodeEvent::SessionEnd(_) => caps,
+        }
+    }
+
+    pub fn cursor(event: &CursorEvent) -> Self {
+        let caps = Self::new(HarnessType::Cursor, event.event_name());
+        match event {
+            CursorEvent::BeforeShellExecution(_) | CursorEvent::BeforeMCPExecution(_) => Self {
+                block: true,
+                ask: true,
+                ..caps
+            },
+            CursorEvent::BeforeReadFile(_) | CursorEvent::BeforeSubmitPrompt(_) => Self {
+                block: true,
+                ..caps
+            },
+            CursorEvent::Stop(_)
+            | CursorEvent::AfterShellExecution(_)
+            | CursorEvent::AfterMCPExecution(_)
+            | CursorEvent::AfterFileEdit(_)
+            | CursorEvent::AfterAgentResponse(_)
+            | CursorEvent::AfterAgentThought(_) => caps,
+        }
+    }
+
+    pub fn factory(event: &FactoryEvent) -> Self {
+        let caps = Self::new(HarnessType::Factory, event.event_name());
+        match event {
+            FactoryEvent::PreToolUse(_) => Self {
+                block: true,
+                ask: true,
+                modify_input: true,
+                ..caps
+            },
+            FactoryEvent::PostToolUse(_)
+            | FactoryEvent::UserPromptSubmit(_)
+            | FactoryEvent::SessionStart(_) => Self {
+                block: true,
+                context_injection: true,
+                ..caps
+            },
+            FactoryEvent::PreCompact(_) => Self {
+                context_injection: true,
+                ..caps
+            },
+            FactoryEvent::Stop(_)
+            | FactoryEvent::SubagentStop(_)
+            | FactoryEvent::Notification(_)
+            | FactoryEvent::SessionEnd(_) => caps,
+        }
+    }
+
+    pub fn opencode(event: &OpenCodeEvent) -> Self {
+        let caps = Self::new(HarnessType::OpenCode, event.event_name());
+        match event {
+            // The plugin answers asks through OpenCode's permission dialog
+            OpenCodeEvent::PreToolUse(_) | OpenCodeEvent::PermissionRequest(_) => Self {
+                block: true,
+                ask: true,
+                ..caps
+            },
+            // The plugin rewrites the output the agent sees
+            OpenCodeEvent::PostToolUse(_) => Self {
+                block: true,
+                filter_output: true,
+                context_injection: true,
+                ..caps
+            },
+            OpenCodeEvent::UserPromptSubmit(_) => Self {
+                block: true,
+                context_injection: true,
+                ..caps
+            },
+            OpenCodeEvent::SessionStart(_) => Self {
+                context_injection: true,
+                ..caps
+            },
+            OpenCodeEvent::Stop(_) | OpenCodeEvent::SessionEnd(_) => caps,
+        }
+    }
+
+    pub fn cline(event: &ClineEvent) -> Self {
+        let caps = Self::new(HarnessType::Cline, event.event_name());
+        match event {
+            // Cancelling after the tool ran stops the task before the model
+            // sees the result
+            ClineEvent::PreToolUse(_)
+            | ClineEvent::PostToolUse(_)
+            | ClineEvent::UserPromptSubmit(_)
+            | ClineEvent::TaskStart(_)
+            | ClineEvent::TaskResume(_) => Self {
+                block: true,
+                context_injection: true,
+                ..caps
+            },
+            ClineEvent::PreCompact(_) => Self {
+                context_injection: true,
+                ..caps
+            },
+            ClineEvent::TaskCancel(_) | ClineEvent::TaskComplete(_) => caps,
+        }
+    }
+
+    pub fn aider(event: &AiderEvent) -> Self {
+        let caps = Self::new(HarnessType::Aider, event.event_name());
+        match event {
+            // The launcher answers asks with Aider's own confirmation prompt
+            AiderEvent::PreToolUse(_) => Self {
+                block: true,
+                ask: true,
+                ..caps
+            },
+            // Context is added to the prompt or the command output
+            AiderEvent::PostToolUse(_) | AiderEvent::UserPromptSubmit(_) => Self {
+                block: true,
+                context_injection: true,
+                ..caps
+            },
+            AiderEvent::SessionStart(_) => Self {
+                context_injection: true,
+                ..caps
+            },
+            AiderEvent::SessionEnd(_) => caps,
+        }
+    }
+
+    pub fn goose(event: &GooseEvent) -> Self {
+        let caps = Self::new(HarnessType::Goose, event.event_name());
+        match event {
+            // Goose answers asks with its own permission prompt
+            GooseEvent::PreToolUse(_) => Self {
+                block: true,
+                ask: true,
+                ..caps
+            },
+            // Context is appended to the prompt or the tool result
+            GooseEvent::PostToolUse(_) | GooseEvent::UserPromptSubmit(_) => Self {
+                block: true,
+                context_injection: true,
+                ..caps
+            },
+            GooseEvent::SessionStart(_) => Self {
+                context_injection: true,
+                ..caps
+            },
+            GooseEvent::SessionEnd(_) => caps,
+        }
+    }
+
+    /// Capabilities for a raw hook event of the given harness
+    pub fn parse(harness: HarnessType, input: &str) -> anyhow::Result<Self> {
+        use super::{
+            AiderHarness, ClaudeHarness, ClineHarness, CursorHarness, FactoryHarness, GooseHarness,
+            OpenCodeHarness,
+        };
+
+        Ok(match harness {
+            HarnessType::ClaudeCode => Self::claude_code(&ClaudeHarness::parse_event(input)?),
+            HarnessType::Cursor => Self::cursor(&CursorHarness::parse_event(input)?),
+            HarnessType::Factory => Self::factory(&FactoryHarness::parse_event(input)?),
+            HarnessType::OpenCode => Self::opencode(&OpenCodeHarness::parse_event(input)?),
+            HarnessType::Cline => Self::cline(&ClineHarness::parse_event(input)?),
+            HarnessType::Aider => Self::aider(&AiderHarness::parse_event(input)?),
+            HarnessType::Goose => Self::goose(&GooseHarness::parse_event(input)?),
+        })
+    }
+
+    /// Rewrite a decision into one this event can express
+    pub fn degrade(&self, decision: &FinalDecision) -> FinalDecision {
+        let degraded = match decision {
+            FinalDecision::Ask {
+                reason,
+                agent_messages,
+            } if !self.ask => {
+                if self.block {
+                    self.warn_downgrade("ask", "deny", reason);
+                    FinalDecision::Deny {
+                        reason: reason.clone(),
+                        agent_messages: agent_messages.clone(),
+                    }
+                } else {
+                    self.warn_downgrade("ask", "allow", reason);
+                    FinalDecision::Allow {
+                        context: vec![reason.clone()],
+                    }
+                }
+            }
+            FinalDecision::Modify { reason, .. } if !self.modify_input => {
+                self.warn_downgrade("modify", "allow", reason);
+                FinalDecision::Allow {
+                    context: vec![reason.clone()],
+                }
+            }
+            FinalDecision::FilterOutput { reason, .. } if !self.filter_output => {
+                self.warn_downgrade("filter_output", "allow", reason);
+                FinalDecision::Allow {
+                    context: vec![reason.clone()],
+                }
+            }
+            other => other.clone(),
+        };
+
+        match degraded {
+            FinalDecision::Allow { context } if !context.is_empty() && !self.context_injection => {
+                debug!(
+                    "{} {} can't inject context - dropping {} item(s)",
+                    self.harness.display_name(),
+                    self.event,
+                    context.len()
+                );
+                FinalDecision::Allow { context: vec![] }
+            }
+            other => other,
+        }
+    }
+
+    fn warn_downgrade(&self, from: &str, to: &str, reason: &str) {
+        warn!(
+            "{} {} doesn't support '{}' - enforcing as '{}': {}",
+            self.harness.display_name(),
+            self.event,
+            from,
+            to,
+            reason
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ask() -> FinalDecision {
+        FinalDecision::Ask {
+            reason: "Confirm".to_string(),
+            agent_messages: vec!["details".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_ask_degrades_to_deny_when_blockable() {
+        let caps = HarnessCapabilities {
+            block: true,
+            ..HarnessCapabilities::new(HarnessType::Cursor, "beforeSubmitPrompt")
+        };
+        match caps.degrade(&ask()) {
+            FinalDecision::Deny {
+                reason,
+                agent_messages,
+            } => {
+                assert_eq!(reason, "Confirm");
+                assert_eq!(agent_messages, vec!["details"]);
+            }
+            other => panic!("Expected Deny, got {other:?}"),
+        }
+
+        // Events that can't block let the action through, dropping the reason
+        // when there is nowhere to put it
+        let caps = HarnessCapabilities::new(HarnessType::ClaudeCode, "SessionEnd");
+        assert!(matches!(
+            caps.degrade(&ask()),
+            FinalDecision::Allow { context } if context.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_supported_decisions_pass_through() {
+        let caps = HarnessCapabilities {
+            block: true,
+            ask: true,
+            ..HarnessCapabilities::new(HarnessType::ClaudeCode, "PreToolUse")
+        };
+        assert_eq!(caps.degrade(&ask()), ask());
+
+        let modify = FinalDecision::Modify {
+            reason: "Sanitized".to_string(),
+            updated_input: serde_json::json!({"command": "ls"}),
+            agent_messages: vec![],
+        };
+        assert!(matches!(
+            caps.degrade(&modify),
+            FinalDecision::Allow { context } if context.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_cursor_prompt_capabilities() {
+        let event: CursorEvent = serde_json::from_value(serde_json::json!({
+            "hook_event_name": "beforeSubmitPrompt",
+            "conversation_id": "c",
+            "generation_id": "g",
+            "prompt": "hi"
+        }))
+        .unwrap();
+        let caps = HarnessCapabilities::cursor(&event);
+        assert!(caps.block);
+        assert!(!caps.ask);
+        assert!(!caps.context_injection);
+    }
+}
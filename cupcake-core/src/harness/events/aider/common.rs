@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// Common data fields present in all Aider events
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CommonAiderData {
+    /// Identifier the launcher generates for each Aider run
+    pub session_id: String,
+
+    /// Root of the git repository Aider is working in
+    pub cwd: String,
+
+    /// Main model, e.g. "sonnet" or "gpt-4o"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    /// Installed Aider version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aider_version: Option<String>,
+}
@@ -0,0 +1,143 @@
+//! Aider events
+//!
+//! Aider has no hook system, so Cupcake ships a launcher
+//! (`cupcake-plugins/aider/cupcake_aider.py`) that starts Aider with its
+//! confirmation points wrapped. Each one sends an event to
+//! `cupcake eval --harness aider` and acts on the decision. The format is the
+//! launcher's own, modelled on OpenCode's: `hook_event_name` plus `tool` and
+//! `args` for actions.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+mod common;
+mod post_tool_use;
+mod pre_tool_use;
+mod session;
+mod user_prompt_submit;
+
+pub use common::CommonAiderData;
+pub use post_tool_use::{CommandResult, PostToolUsePayload};
+pub use pre_tool_use::PreToolUsePayload;
+pub use session::{SessionEndPayload, SessionStartPayload};
+pub use user_prompt_submit::UserPromptSubmitPayload;
+
+/// All possible Aider events
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "hook_event_name", rename_all = "PascalCase")]
+pub enum AiderEvent {
+    /// Before a shell command, file write, file add or URL fetch
+    PreToolUse(PreToolUsePayload),
+
+    /// After a shell command ran
+    PostToolUse(PostToolUsePayload),
+
+    /// User sent a message to the model
+    UserPromptSubmit(UserPromptSubmitPayload),
+
+    /// Aider started
+    SessionStart(SessionStartPayload),
+
+    /// Aider exited
+    SessionEnd(SessionEndPayload),
+}
+
+impl AiderEvent {
+    /// Get the common event data
+    pub fn common(&self) -> &CommonAiderData {
+        match self {
+            AiderEvent::PreToolUse(payload) => &payload.common,
+            AiderEvent::PostToolUse(payload) => &payload.common,
+            AiderEvent::UserPromptSubmit(payload) => &payload.common,
+            AiderEvent::SessionStart(payload) => &payload.common,
+            AiderEvent::SessionEnd(payload) => &payload.common,
+        }
+    }
+
+    /// Get the action name, for tool events
+    pub fn tool(&self) -> Option<&str> {
+        match self {
+            AiderEvent::PreToolUse(payload) => Some(&payload.tool),
+            AiderEvent::PostToolUse(payload) => Some(&payload.tool),
+            _ => None,
+        }
+    }
+
+    /// Get the action arguments, for tool events
+    pub fn args(&self) -> Option<&Value> {
+        match self {
+            AiderEvent::PreToolUse(payload) => Some(&payload.args),
+            AiderEvent::PostToolUse(payload) => Some(&payload.args),
+            _ => None,
+        }
+    }
+
+    /// Get the event name as a string
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            AiderEvent::PreToolUse(_) => "PreToolUse",
+            AiderEvent::PostToolUse(_) => "PostToolUse",
+            AiderEvent::UserPromptSubmit(_) => "UserPromptSubmit",
+            AiderEvent::SessionStart(_) => "SessionStart",
+            AiderEvent::SessionEnd(_) => "SessionEnd",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pre_tool_use_event() {
+        let json = r#"{
+            "hook_event_name": "PreToolUse",
+            "session_id": "aider-1",
+            "cwd": "/home/user/project",
+            "model": "sonnet",
+            "tool": "write_file",
+            "args": {"path": "src/main.py", "content": "print('hi')\n"}
+        }"#;
+
+        let event: AiderEvent = serde_json::from_str(json).unwrap();
+        assert_eq!(event.event_name(), "PreToolUse");
+        assert_eq!(event.tool(), Some("write_file"));
+        assert_eq!(event.args().unwrap()["path"], "src/main.py");
+        assert_eq!(event.common().model.as_deref(), Some("sonnet"));
+    }
+
+    #[test]
+    fn test_post_tool_use_and_session_events() {
+        let post: AiderEvent = serde_json::from_str(
+            r#"{
+                "hook_event_name": "PostToolUse",
+                "session_id": "aider-1",
+                "cwd": "/home/user/project",
+                "tool": "shell_command",
+                "args": {"command": "pytest"},
+                "result": {"output": "3 passed", "exit_code": 0}
+            }"#,
+        )
+        .unwrap();
+        let AiderEvent::PostToolUse(payload) = &post else {
+            panic!("expected PostToolUse");
+        };
+        assert_eq!(payload.result.output, "3 passed");
+        assert_eq!(payload.result.exit_code, Some(0));
+
+        let start: AiderEvent = serde_json::from_str(
+            r#"{
+                "hook_event_name": "SessionStart",
+                "session_id": "aider-1",
+                "cwd": "/home/user/project",
+                "files": ["README.md"]
+            }"#,
+        )
+        .unwrap();
+        assert!(start.tool().is_none());
+        let AiderEvent::SessionStart(payload) = &start else {
+            panic!("expected SessionStart");
+        };
+        assert_eq!(payload.files, vec!["README.md"]);
+    }
+}
@@ -0,0 +1,35 @@
+use super::common::CommonAiderData;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Output of a shell command Aider ran
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CommandResult {
+    /// Combined stdout and stderr, as Aider would add it to the chat
+    #[serde(default)]
+    pub output: String,
+
+    /// Exit code of the command
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+}
+
+/// PostToolUse event payload for Aider
+/// Fired after a shell command ran, before its output is offered to the chat
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PostToolUsePayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonAiderData,
+
+    /// Action name - always "shell_command" today
+    pub tool: String,
+
+    /// Action arguments
+    #[serde(default)]
+    pub args: Value,
+
+    /// What the command produced
+    #[serde(default)]
+    pub result: CommandResult,
+}
@@ -0,0 +1,20 @@
+use super::common::CommonAiderData;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// PreToolUse event payload for Aider
+/// Fired before Aider runs a shell command, writes a file, adds a file to the
+/// chat or fetches a URL - the points where Aider would ask for confirmation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PreToolUsePayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonAiderData,
+
+    /// Action name: "shell_command", "write_file", "add_file" or "fetch_url"
+    pub tool: String,
+
+    /// Action arguments, e.g. `command`, `path` and `content`, or `url`
+    #[serde(default)]
+    pub args: Value,
+}
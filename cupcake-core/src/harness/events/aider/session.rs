@@ -0,0 +1,28 @@
+use super::common::CommonAiderData;
+use serde::{Deserialize, Serialize};
+
+/// SessionStart event payload for Aider
+/// Fired once Aider has loaded the repository, before the first prompt
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionStartPayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonAiderData,
+
+    /// Files added to the chat on the command line, relative to `cwd`
+    #[serde(default)]
+    pub files: Vec<String>,
+
+    /// Edit format in use, e.g. "diff" or "whole"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edit_format: Option<String>,
+}
+
+/// SessionEnd event payload for Aider
+/// Fired when Aider exits
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionEndPayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonAiderData,
+}
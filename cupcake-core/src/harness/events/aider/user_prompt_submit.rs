@@ -0,0 +1,15 @@
+use super::common::CommonAiderData;
+use serde::{Deserialize, Serialize};
+
+/// UserPromptSubmit event payload for Aider
+/// Fired when the user sends a message to the model. Aider's own `/` commands
+/// are not sent
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UserPromptSubmitPayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonAiderData,
+
+    /// The message as typed
+    pub prompt: String,
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// Common data fields present in all Cline hook events
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommonClineData {
+    /// Unique identifier for the task (Cline's equivalent of a session)
+    pub task_id: String,
+
+    /// Workspace folders open in VS Code
+    #[serde(default)]
+    pub workspace_roots: Vec<String>,
+
+    /// Version of the extension that sent the event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cline_version: Option<String>,
+
+    /// When the hook fired
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+
+    /// Signed-in user, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+}
+
+impl CommonClineData {
+    /// Working directory of the task - the first workspace root
+    pub fn cwd(&self) -> Option<&str> {
+        self.workspace_roots.first().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_common_data_deserialization() {
+        let json = r#"{
+            "taskId": "task123",
+            "workspaceRoots": ["/home/user/project", "/home/user/lib"],
+            "clineVersion": "3.36.0"
+        }"#;
+        let data: CommonClineData = serde_json::from_str(json).unwrap();
+        assert_eq!(data.task_id, "task123");
+        assert_eq!(data.cwd(), Some("/home/user/project"));
+        assert_eq!(data.cline_version, Some("3.36.0".to_string()));
+        assert_eq!(data.user_id, None);
+    }
+}
@@ -0,0 +1,159 @@
+//! Cline hook events
+//!
+//! Cline runs an executable per hook and passes the event on stdin. Every
+//! event names itself in `hookName` and carries its data under a key named
+//! after the hook (`preToolUse`, `userPromptSubmit`, ...). Roo Code and other
+//! Cline forks that keep the hook protocol send the same events.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+mod common;
+mod post_tool_use;
+mod pre_tool_use;
+mod task;
+mod user_prompt_submit;
+
+pub use common::CommonClineData;
+pub use post_tool_use::{PostToolUsePayload, ToolOutcome};
+pub use pre_tool_use::{PreToolUsePayload, ToolCall};
+pub use task::{
+    PreCompactPayload, TaskCancelPayload, TaskCompletePayload, TaskData, TaskResumePayload,
+    TaskStartPayload,
+};
+pub use user_prompt_submit::{UserPrompt, UserPromptSubmitPayload};
+
+/// All possible Cline hook events
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "hookName")]
+pub enum ClineEvent {
+    /// Before a tool runs
+    PreToolUse(PreToolUsePayload),
+
+    /// After a tool ran
+    PostToolUse(PostToolUsePayload),
+
+    /// User sent a message
+    UserPromptSubmit(UserPromptSubmitPayload),
+
+    /// New task started
+    TaskStart(TaskStartPayload),
+
+    /// Task resumed
+    TaskResume(TaskResumePayload),
+
+    /// Task cancelled by the user
+    TaskCancel(TaskCancelPayload),
+
+    /// Task finished
+    TaskComplete(TaskCompletePayload),
+
+    /// Conversation about to be condensed
+    PreCompact(PreCompactPayload),
+}
+
+impl ClineEvent {
+    /// Get the common event data
+    pub fn common(&self) -> &CommonClineData {
+        match self {
+            ClineEvent::PreToolUse(payload) => &payload.common,
+            ClineEvent::PostToolUse(payload) => &payload.common,
+            ClineEvent::UserPromptSubmit(payload) => &payload.common,
+            ClineEvent::TaskStart(payload) => &payload.common,
+            ClineEvent::TaskResume(payload) => &payload.common,
+            ClineEvent::TaskCancel(payload) => &payload.common,
+            ClineEvent::TaskComplete(payload) => &payload.common,
+            ClineEvent::PreCompact(payload) => &payload.common,
+        }
+    }
+
+    /// Get Cline's name for the tool, for tool events
+    pub fn tool_name(&self) -> Option<&str> {
+        match self {
+            ClineEvent::PreToolUse(payload) => Some(&payload.pre_tool_use.tool_name),
+            ClineEvent::PostToolUse(payload) => Some(&payload.post_tool_use.tool_name),
+            _ => None,
+        }
+    }
+
+    /// Get the tool parameters, for tool events
+    pub fn parameters(&self) -> Option<&Value> {
+        match self {
+            ClineEvent::PreToolUse(payload) => Some(&payload.pre_tool_use.parameters),
+            ClineEvent::PostToolUse(payload) => Some(&payload.post_tool_use.parameters),
+            _ => None,
+        }
+    }
+
+    /// Get the event name as a string
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            ClineEvent::PreToolUse(_) => "PreToolUse",
+            ClineEvent::PostToolUse(_) => "PostToolUse",
+            ClineEvent::UserPromptSubmit(_) => "UserPromptSubmit",
+            ClineEvent::TaskStart(_) => "TaskStart",
+            ClineEvent::TaskResume(_) => "TaskResume",
+            ClineEvent::TaskCancel(_) => "TaskCancel",
+            ClineEvent::TaskComplete(_) => "TaskComplete",
+            ClineEvent::PreCompact(_) => "PreCompact",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pre_tool_use_event() {
+        let json = r#"{
+            "clineVersion": "3.36.0",
+            "hookName": "PreToolUse",
+            "timestamp": "1730000000000",
+            "taskId": "task123",
+            "workspaceRoots": ["/home/user/project"],
+            "preToolUse": {
+                "toolName": "execute_command",
+                "parameters": {"command": "npm test", "requires_approval": "false"}
+            }
+        }"#;
+
+        let event: ClineEvent = serde_json::from_str(json).unwrap();
+        assert_eq!(event.event_name(), "PreToolUse");
+        assert_eq!(event.tool_name(), Some("execute_command"));
+        assert_eq!(event.parameters().unwrap()["command"], "npm test");
+        assert_eq!(event.common().task_id, "task123");
+    }
+
+    #[test]
+    fn test_prompt_and_task_events() {
+        let prompt: ClineEvent = serde_json::from_str(
+            r#"{
+                "hookName": "UserPromptSubmit",
+                "taskId": "task123",
+                "workspaceRoots": [],
+                "userPromptSubmit": {"prompt": "Deploy it", "attachments": []}
+            }"#,
+        )
+        .unwrap();
+        let ClineEvent::UserPromptSubmit(payload) = &prompt else {
+            panic!("expected UserPromptSubmit");
+        };
+        assert_eq!(payload.user_prompt_submit.prompt, "Deploy it");
+        assert_eq!(prompt.common().cwd(), None);
+
+        let start: ClineEvent = serde_json::from_str(
+            r#"{
+                "hookName": "TaskStart",
+                "taskId": "task123",
+                "taskStart": {"taskMetadata": {"taskId": "task123", "initialTask": "Fix the build"}}
+            }"#,
+        )
+        .unwrap();
+        let ClineEvent::TaskStart(payload) = &start else {
+            panic!("expected TaskStart");
+        };
+        assert_eq!(payload.task_start.initial_task(), Some("Fix the build"));
+        assert!(start.tool_name().is_none());
+    }
+}
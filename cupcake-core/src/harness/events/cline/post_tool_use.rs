@@ -0,0 +1,40 @@
+use super::common::CommonClineData;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// PostToolUse event payload for Cline
+/// Fired after a tool ran, before its result goes back to the model
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PostToolUsePayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonClineData,
+
+    /// The tool call and its outcome
+    pub post_tool_use: ToolOutcome,
+}
+
+/// A completed tool call
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolOutcome {
+    /// Cline tool name
+    pub tool_name: String,
+
+    /// Tool parameters
+    #[serde(default)]
+    pub parameters: Value,
+
+    /// What the tool returned to the model
+    #[serde(default)]
+    pub result: Value,
+
+    /// Whether the tool succeeded
+    #[serde(default)]
+    pub success: bool,
+
+    /// How long the tool took
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_time_ms: Option<u64>,
+}
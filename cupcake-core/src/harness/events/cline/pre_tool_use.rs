@@ -0,0 +1,28 @@
+use super::common::CommonClineData;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// PreToolUse event payload for Cline
+/// Fired before a tool runs, in place of Cline's approval prompt
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PreToolUsePayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonClineData,
+
+    /// The tool call awaiting approval
+    pub pre_tool_use: ToolCall,
+}
+
+/// A tool call as Cline reports it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolCall {
+    /// Cline tool name, e.g. "execute_command" or "write_to_file"
+    pub tool_name: String,
+
+    /// Tool parameters, e.g. `command` or `path` and `content`
+    #[serde(default)]
+    pub parameters: Value,
+}
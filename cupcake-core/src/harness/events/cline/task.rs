@@ -0,0 +1,87 @@
+use super::common::CommonClineData;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Data Cline sends with every task lifecycle hook
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskData {
+    /// Task details - `taskId`, `ulid` and, when starting, `initialTask`
+    #[serde(default)]
+    pub task_metadata: Value,
+}
+
+impl TaskData {
+    /// The instructions the task was started with
+    pub fn initial_task(&self) -> Option<&str> {
+        self.task_metadata
+            .get("initialTask")
+            .and_then(Value::as_str)
+    }
+}
+
+/// TaskStart event payload for Cline
+/// Fired when a new task starts
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskStartPayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonClineData,
+
+    #[serde(default)]
+    pub task_start: TaskData,
+}
+
+/// TaskResume event payload for Cline
+/// Fired when an interrupted or historical task is resumed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskResumePayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonClineData,
+
+    #[serde(default)]
+    pub task_resume: TaskData,
+}
+
+/// TaskCancel event payload for Cline
+/// Fired when the user cancels a task
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskCancelPayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonClineData,
+
+    #[serde(default)]
+    pub task_cancel: TaskData,
+}
+
+/// TaskComplete event payload for Cline
+/// Fired when the agent reports the task as done
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskCompletePayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonClineData,
+
+    #[serde(default)]
+    pub task_complete: TaskData,
+}
+
+/// PreCompact event payload for Cline
+/// Fired before the conversation is condensed to fit the context window
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PreCompactPayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonClineData,
+
+    /// Compaction details as Cline reports them
+    #[serde(default)]
+    pub pre_compact: Value,
+}
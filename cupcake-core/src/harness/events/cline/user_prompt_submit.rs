@@ -0,0 +1,27 @@
+use super::common::CommonClineData;
+use serde::{Deserialize, Serialize};
+
+/// UserPromptSubmit event payload for Cline
+/// Fired when the user sends a message, before the model sees it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UserPromptSubmitPayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonClineData,
+
+    /// The message
+    pub user_prompt_submit: UserPrompt,
+}
+
+/// A user message
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UserPrompt {
+    /// Text of the message
+    pub prompt: String,
+
+    /// Files and images attached to the message
+    #[serde(default)]
+    pub attachments: Vec<String>,
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Common data fields present in all Goose events
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CommonGooseData {
+    /// Goose session name, e.g. "20251017_1"
+    pub session_id: String,
+
+    /// Working directory of the session
+    pub cwd: String,
+
+    /// Goose mode: "auto", "approve", "smart_approve" or "chat"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub goose_mode: Option<String>,
+}
@@ -0,0 +1,155 @@
+//! Goose events
+//!
+//! Goose (Block) runs the commands in its hooks file (`.goose/hooks.json`, or
+//! `~/.config/goose/hooks.json` for every project) at its tool-permission
+//! check and at the other points below, passing the event on stdin. Tools are
+//! named `<extension>__<tool>` after the extension that provides them, e.g.
+//! `developer__shell`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+mod common;
+mod post_tool_use;
+mod pre_tool_use;
+mod session;
+mod user_prompt_submit;
+
+pub use common::CommonGooseData;
+pub use post_tool_use::PostToolUsePayload;
+pub use pre_tool_use::PreToolUsePayload;
+pub use session::{SessionEndPayload, SessionStartPayload};
+pub use user_prompt_submit::UserPromptSubmitPayload;
+
+/// All possible Goose events
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "hook_event_name", rename_all = "PascalCase")]
+pub enum GooseEvent {
+    /// At the permission check for a tool call
+    PreToolUse(PreToolUsePayload),
+
+    /// After a tool call returned
+    PostToolUse(PostToolUsePayload),
+
+    /// User sent a message
+    UserPromptSubmit(UserPromptSubmitPayload),
+
+    /// Session started or resumed
+    SessionStart(SessionStartPayload),
+
+    /// Session exited
+    SessionEnd(SessionEndPayload),
+}
+
+impl GooseEvent {
+    /// Get the common event data
+    pub fn common(&self) -> &CommonGooseData {
+        match self {
+            GooseEvent::PreToolUse(payload) => &payload.common,
+            GooseEvent::PostToolUse(payload) => &payload.common,
+            GooseEvent::UserPromptSubmit(payload) => &payload.common,
+            GooseEvent::SessionStart(payload) => &payload.common,
+            GooseEvent::SessionEnd(payload) => &payload.common,
+        }
+    }
+
+    /// Get the full tool name, for tool events
+    pub fn tool(&self) -> Option<&str> {
+        match self {
+            GooseEvent::PreToolUse(payload) => Some(&payload.tool),
+            GooseEvent::PostToolUse(payload) => Some(&payload.tool),
+            _ => None,
+        }
+    }
+
+    /// Get the extension providing the tool, for tool events
+    pub fn extension(&self) -> Option<&str> {
+        match self {
+            GooseEvent::PreToolUse(payload) => Some(&payload.extension),
+            GooseEvent::PostToolUse(payload) => Some(&payload.extension),
+            _ => None,
+        }
+    }
+
+    /// Get the tool call arguments, for tool events
+    pub fn arguments(&self) -> Option<&Value> {
+        match self {
+            GooseEvent::PreToolUse(payload) => Some(&payload.arguments),
+            GooseEvent::PostToolUse(payload) => Some(&payload.arguments),
+            _ => None,
+        }
+    }
+
+    /// Get the event name as a string
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            GooseEvent::PreToolUse(_) => "PreToolUse",
+            GooseEvent::PostToolUse(_) => "PostToolUse",
+            GooseEvent::UserPromptSubmit(_) => "UserPromptSubmit",
+            GooseEvent::SessionStart(_) => "SessionStart",
+            GooseEvent::SessionEnd(_) => "SessionEnd",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pre_tool_use_event() {
+        let json = r#"{
+            "hook_event_name": "PreToolUse",
+            "session_id": "20251017_1",
+            "cwd": "/home/user/project",
+            "goose_mode": "smart_approve",
+            "extension": "developer",
+            "tool": "developer__text_editor",
+            "arguments": {"command": "write", "path": "/home/user/project/a.py", "file_text": "x = 1\n"}
+        }"#;
+
+        let event: GooseEvent = serde_json::from_str(json).unwrap();
+        assert_eq!(event.event_name(), "PreToolUse");
+        assert_eq!(event.tool(), Some("developer__text_editor"));
+        assert_eq!(event.extension(), Some("developer"));
+        assert_eq!(event.arguments().unwrap()["command"], "write");
+        assert_eq!(event.common().goose_mode.as_deref(), Some("smart_approve"));
+    }
+
+    #[test]
+    fn test_post_tool_use_and_session_events() {
+        let post: GooseEvent = serde_json::from_str(
+            r#"{
+                "hook_event_name": "PostToolUse",
+                "session_id": "20251017_1",
+                "cwd": "/home/user/project",
+                "extension": "developer",
+                "tool": "developer__shell",
+                "arguments": {"command": "cargo test"},
+                "result": {"content": [{"type": "text", "text": "ok"}], "is_error": false}
+            }"#,
+        )
+        .unwrap();
+        let GooseEvent::PostToolUse(payload) = &post else {
+            panic!("expected PostToolUse");
+        };
+        assert_eq!(payload.result["is_error"], false);
+
+        let start: GooseEvent = serde_json::from_str(
+            r#"{
+                "hook_event_name": "SessionStart",
+                "session_id": "20251017_1",
+                "cwd": "/home/user/project",
+                "source": "resume",
+                "extensions": ["developer", "github"]
+            }"#,
+        )
+        .unwrap();
+        assert!(start.tool().is_none());
+        let GooseEvent::SessionStart(payload) = &start else {
+            panic!("expected SessionStart");
+        };
+        assert_eq!(payload.source.as_deref(), Some("resume"));
+        assert_eq!(payload.extensions, vec!["developer", "github"]);
+    }
+}
@@ -0,0 +1,26 @@
+use super::common::CommonGooseData;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// PostToolUse event payload for Goose
+/// Fired after a tool call returned, before its result goes to the model
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PostToolUsePayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonGooseData,
+
+    /// Extension that provides the tool
+    pub extension: String,
+
+    /// Full tool name, `<extension>__<tool>`
+    pub tool: String,
+
+    /// Tool call arguments
+    #[serde(default)]
+    pub arguments: Value,
+
+    /// The tool result: `content` items and `is_error`
+    #[serde(default)]
+    pub result: Value,
+}
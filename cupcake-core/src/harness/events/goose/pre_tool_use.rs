@@ -0,0 +1,22 @@
+use super::common::CommonGooseData;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// PreToolUse event payload for Goose
+/// Fired when Goose checks a tool call's permission, before it runs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PreToolUsePayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonGooseData,
+
+    /// Extension that provides the tool, e.g. "developer"
+    pub extension: String,
+
+    /// Full tool name, `<extension>__<tool>`, e.g. "developer__shell"
+    pub tool: String,
+
+    /// Tool call arguments
+    #[serde(default)]
+    pub arguments: Value,
+}
@@ -0,0 +1,28 @@
+use super::common::CommonGooseData;
+use serde::{Deserialize, Serialize};
+
+/// SessionStart event payload for Goose
+/// Fired when a session starts or is resumed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionStartPayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonGooseData,
+
+    /// "startup" for a new session, "resume" for `goose session --resume`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// Extensions enabled for the session
+    #[serde(default)]
+    pub extensions: Vec<String>,
+}
+
+/// SessionEnd event payload for Goose
+/// Fired when the session exits
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionEndPayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonGooseData,
+}
@@ -0,0 +1,14 @@
+use super::common::CommonGooseData;
+use serde::{Deserialize, Serialize};
+
+/// UserPromptSubmit event payload for Goose
+/// Fired when the user sends a message, before it reaches the model
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UserPromptSubmitPayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonGooseData,
+
+    /// The message as typed
+    pub prompt: String,
+}
@@ -4,15 +4,21 @@
 //! from different AI coding agents. Currently supports Claude Code and Cursor,
 //! but designed for extensibility.
 
+pub mod aider;
 pub mod claude_code;
+pub mod cline;
 pub mod cursor;
 pub mod factory;
+pub mod goose;
 pub mod opencode;
 
 // Re-export commonly used types
+pub use aider::{AiderEvent, CommonAiderData};
 pub use claude_code::{ClaudeCodeEvent, CommonEventData, CompactTrigger, SessionSource};
+pub use cline::{ClineEvent, CommonClineData};
 pub use cursor::{CommonCursorData, CursorEvent};
 pub use factory::{CommonFactoryData, FactoryEvent, PermissionMode};
+pub use goose::{CommonGooseData, GooseEvent};
 pub use opencode::{CommonOpenCodeData, OpenCodeEvent, ToolResult};
 
 use serde::{Deserialize, Serialize};
@@ -1,12 +1,20 @@
 use serde::{Deserialize, Serialize};
 
 mod common;
+mod permission_request;
 mod post_tool_use;
 mod pre_tool_use;
+mod session_end;
+mod session_start;
+mod user_prompt_submit;
 
 pub use common::CommonOpenCodeData;
+pub use permission_request::PermissionRequestPayload;
 pub use post_tool_use::{PostToolUsePayload, ToolResult};
 pub use pre_tool_use::PreToolUsePayload;
+pub use session_end::{SessionEndPayload, StopPayload};
+pub use session_start::SessionStartPayload;
+pub use user_prompt_submit::UserPromptSubmitPayload;
 
 /// All possible OpenCode hook events
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -17,6 +25,21 @@ pub enum OpenCodeEvent {
 
     /// After tool execution (tool.execute.after)
     PostToolUse(PostToolUsePayload),
+
+    /// OpenCode is asking for permission (permission.ask)
+    PermissionRequest(PermissionRequestPayload),
+
+    /// User sent a message (chat.message)
+    UserPromptSubmit(UserPromptSubmitPayload),
+
+    /// Session created or compacted (session.created, session.compacted)
+    SessionStart(SessionStartPayload),
+
+    /// Agent finished responding (session.idle)
+    Stop(StopPayload),
+
+    /// Session deleted (session.deleted)
+    SessionEnd(SessionEndPayload),
 }
 
 impl OpenCodeEvent {
@@ -25,22 +48,34 @@ impl OpenCodeEvent {
         match self {
             OpenCodeEvent::PreToolUse(payload) => &payload.common,
             OpenCodeEvent::PostToolUse(payload) => &payload.common,
+            OpenCodeEvent::PermissionRequest(payload) => &payload.common,
+            OpenCodeEvent::UserPromptSubmit(payload) => &payload.common,
+            OpenCodeEvent::SessionStart(payload) => &payload.common,
+            OpenCodeEvent::Stop(payload) => &payload.common,
+            OpenCodeEvent::SessionEnd(payload) => &payload.common,
         }
     }
 
     /// Get the tool name for tool-related events
-    pub fn tool(&self) -> &str {
+    ///
+    /// For permission requests this is the permission type, which names the
+    /// tool for tool permissions.
+    pub fn tool(&self) -> Option<&str> {
         match self {
-            OpenCodeEvent::PreToolUse(payload) => &payload.tool,
-            OpenCodeEvent::PostToolUse(payload) => &payload.tool,
+            OpenCodeEvent::PreToolUse(payload) => Some(&payload.tool),
+            OpenCodeEvent::PostToolUse(payload) => Some(&payload.tool),
+            OpenCodeEvent::PermissionRequest(payload) => Some(&payload.permission_type),
+            _ => None,
         }
     }
 
-    /// Get the tool input/args
-    pub fn args(&self) -> &serde_json::Value {
+    /// Get the tool input/args for tool-related events
+    pub fn args(&self) -> Option<&serde_json::Value> {
         match self {
-            OpenCodeEvent::PreToolUse(payload) => &payload.args,
-            OpenCodeEvent::PostToolUse(payload) => &payload.args,
+            OpenCodeEvent::PreToolUse(payload) => Some(&payload.args),
+            OpenCodeEvent::PostToolUse(payload) => Some(&payload.args),
+            OpenCodeEvent::PermissionRequest(payload) => Some(&payload.metadata),
+            _ => None,
         }
     }
 
@@ -49,6 +84,11 @@ impl OpenCodeEvent {
         match self {
             OpenCodeEvent::PreToolUse(_) => "PreToolUse",
             OpenCodeEvent::PostToolUse(_) => "PostToolUse",
+            OpenCodeEvent::PermissionRequest(_) => "PermissionRequest",
+            OpenCodeEvent::UserPromptSubmit(_) => "UserPromptSubmit",
+            OpenCodeEvent::SessionStart(_) => "SessionStart",
+            OpenCodeEvent::Stop(_) => "Stop",
+            OpenCodeEvent::SessionEnd(_) => "SessionEnd",
         }
     }
 
@@ -62,12 +102,20 @@ impl OpenCodeEvent {
         matches!(self, OpenCodeEvent::PostToolUse(_))
     }
 
+    /// Check if this is a session lifecycle event (SessionStart, Stop, SessionEnd)
+    pub fn is_session_event(&self) -> bool {
+        matches!(
+            self,
+            OpenCodeEvent::SessionStart(_) | OpenCodeEvent::Stop(_) | OpenCodeEvent::SessionEnd(_)
+        )
+    }
+
     /// Parse tool input as specific tool type
     pub fn parse_args<T>(&self) -> Result<T, serde_json::Error>
     where
         T: for<'de> Deserialize<'de>,
     {
-        serde_json::from_value(self.args().clone())
+        serde_json::from_value(self.args().cloned().unwrap_or_default())
     }
 }
 
@@ -88,7 +136,7 @@ mod tests {
         let event: OpenCodeEvent = serde_json::from_str(json).unwrap();
         assert!(event.is_pre_tool_use());
         assert_eq!(event.event_name(), "PreToolUse");
-        assert_eq!(event.tool(), "bash");
+        assert_eq!(event.tool(), Some("bash"));
         assert_eq!(event.common().session_id, "session123");
     }
 
@@ -110,7 +158,63 @@ mod tests {
         let event: OpenCodeEvent = serde_json::from_str(json).unwrap();
         assert!(event.is_post_tool_use());
         assert_eq!(event.event_name(), "PostToolUse");
-        assert_eq!(event.tool(), "bash");
+        assert_eq!(event.tool(), Some("bash"));
+    }
+
+    #[test]
+    fn test_session_events() {
+        let json = r#"{
+            "hook_event_name": "SessionStart",
+            "session_id": "session123",
+            "cwd": "/home/user",
+            "title": "Refactor auth"
+        }"#;
+
+        let event: OpenCodeEvent = serde_json::from_str(json).unwrap();
+        assert!(event.is_session_event());
+        assert_eq!(event.event_name(), "SessionStart");
+        assert_eq!(event.tool(), None);
+
+        let json = r#"{
+            "hook_event_name": "SessionEnd",
+            "session_id": "session123",
+            "cwd": "/home/user",
+            "reason": "deleted"
+        }"#;
+
+        let event: OpenCodeEvent = serde_json::from_str(json).unwrap();
+        assert_eq!(event.event_name(), "SessionEnd");
+        assert_eq!(event.common().session_id, "session123");
+    }
+
+    #[test]
+    fn test_permission_and_prompt_events() {
+        let json = r#"{
+            "hook_event_name": "PermissionRequest",
+            "session_id": "session123",
+            "cwd": "/home/user",
+            "permission_id": "perm1",
+            "permission_type": "edit",
+            "title": "Edit src/main.rs",
+            "metadata": {"filePath": "src/main.rs"}
+        }"#;
+
+        let event: OpenCodeEvent = serde_json::from_str(json).unwrap();
+        assert_eq!(event.event_name(), "PermissionRequest");
+        assert_eq!(event.tool(), Some("edit"));
+        assert_eq!(event.args().unwrap()["filePath"], "src/main.rs");
+
+        let json = r#"{
+            "hook_event_name": "UserPromptSubmit",
+            "session_id": "session123",
+            "cwd": "/home/user",
+            "prompt": "Deploy to production"
+        }"#;
+
+        let event: OpenCodeEvent = serde_json::from_str(json).unwrap();
+        assert_eq!(event.event_name(), "UserPromptSubmit");
+        assert_eq!(event.tool(), None);
+        assert!(!event.is_session_event());
     }
 
     #[test]
@@ -0,0 +1,61 @@
+use super::common::CommonOpenCodeData;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// PermissionRequest event payload for OpenCode
+/// Fired when OpenCode is about to show its permission dialog (permission.ask)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PermissionRequestPayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonOpenCodeData,
+
+    /// OpenCode's identifier for this permission request
+    pub permission_id: String,
+
+    /// What the permission is for - a tool name ("bash", "edit", "webfetch")
+    /// or another operation such as "external_directory"
+    pub permission_type: String,
+
+    /// Title OpenCode shows in the dialog
+    pub title: String,
+
+    /// Details of the request, e.g. `command` for bash or `filePath` for edit
+    #[serde(default)]
+    pub metadata: Value,
+
+    /// Pattern(s) the permission would be remembered for, a string or a list
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<Value>,
+
+    /// Tool call that triggered the request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub call_id: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permission_request_deserialization() {
+        let json = r#"{
+            "session_id": "session123",
+            "cwd": "/home/user",
+            "message_id": "msg456",
+            "permission_id": "perm789",
+            "permission_type": "bash",
+            "title": "Run git push",
+            "metadata": {"command": "git push"},
+            "pattern": ["git push *"],
+            "call_id": "call1"
+        }"#;
+
+        let payload: PermissionRequestPayload = serde_json::from_str(json).unwrap();
+        assert_eq!(payload.common.message_id, Some("msg456".to_string()));
+        assert_eq!(payload.permission_type, "bash");
+        assert_eq!(payload.metadata["command"], "git push");
+        assert_eq!(payload.pattern, Some(serde_json::json!(["git push *"])));
+        assert_eq!(payload.call_id, Some("call1".to_string()));
+    }
+}
@@ -0,0 +1,24 @@
+use super::common::CommonOpenCodeData;
+use serde::{Deserialize, Serialize};
+
+/// Stop event payload for OpenCode
+/// Fired when the agent finishes responding and the session goes idle (session.idle)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StopPayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonOpenCodeData,
+}
+
+/// SessionEnd event payload for OpenCode
+/// Fired when a session is deleted (session.deleted)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionEndPayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonOpenCodeData,
+
+    /// Why the session ended (e.g., "deleted")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
@@ -0,0 +1,31 @@
+use super::common::CommonOpenCodeData;
+use serde::{Deserialize, Serialize};
+
+/// SessionStart event payload for OpenCode
+/// Fired when a session is created (session.created) and after it is
+/// compacted (session.compacted), so context can be injected again
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionStartPayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonOpenCodeData,
+
+    /// Session title, if OpenCode has assigned one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// Why the session (re)started: "startup" or "compact"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+impl SessionStartPayload {
+    /// Create a new SessionStartPayload
+    pub fn new(common: CommonOpenCodeData) -> Self {
+        Self {
+            common,
+            title: None,
+            source: None,
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use super::common::CommonOpenCodeData;
+use serde::{Deserialize, Serialize};
+
+/// UserPromptSubmit event payload for OpenCode
+/// Fired when the user sends a message, before the model sees it (chat.message)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UserPromptSubmitPayload {
+    /// Common event data
+    #[serde(flatten)]
+    pub common: CommonOpenCodeData,
+
+    /// Text of the message, its text parts joined by blank lines
+    pub prompt: String,
+}
+
+impl UserPromptSubmitPayload {
+    /// Create a new UserPromptSubmitPayload
+    pub fn new(common: CommonOpenCodeData, prompt: String) -> Self {
+        Self { common, prompt }
+    }
+}
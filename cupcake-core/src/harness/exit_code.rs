@@ -0,0 +1,160 @@
+//! Exit-code hook protocol
+//!
+//! Some hook systems ignore stdout and read only the exit status: 0 lets the
+//! action through and 2 blocks it, showing stderr to the agent. `cupcake eval
+//! --output-mode exit-code` answers that way, and `output_mode:` selects it per
+//! harness in the rulebook:
+//!
+//! ```yaml
+//! output_mode:
+//!   goose: exit-code
+//! ```
+//!
+//! The protocol can only allow or block, so ask, modify and filter_output are
+//! degraded through the harness's capabilities with those three switched off
+//! (see [`super::capabilities`]). Context on an allow is printed to stdout for
+//! harnesses that add it to the conversation.
+
+use serde::{Deserialize, Serialize};
+
+use super::capabilities::HarnessCapabilities;
+use crate::engine::decision::FinalDecision;
+
+/// Exit status that lets the action through
+pub const EXIT_ALLOW: i32 = 0;
+
+/// Exit status that blocks the action
+pub const EXIT_BLOCK: i32 = 2;
+
+/// How `cupcake eval` answers the harness
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputMode {
+    /// The harness's JSON response on stdout
+    #[default]
+    Json,
+    /// Exit 0 to allow or 2 to block, with the reason on stderr
+    ExitCode,
+}
+
+/// The exit status and output for one decision
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ExitCodeResponse {
+    pub exit_code: i32,
+    /// Context for the agent on allow
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stdout: Option<String>,
+    /// Why the action was blocked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stderr: Option<String>,
+}
+
+impl ExitCodeResponse {
+    /// Answer a decision for an event with the given capabilities
+    pub fn from_decision(decision: &FinalDecision, caps: HarnessCapabilities) -> Self {
+        let caps = HarnessCapabilities {
+            ask: false,
+            modify_input: false,
+            filter_output: false,
+            ..caps
+        };
+        match caps.degrade(decision) {
+            FinalDecision::Allow { context } => Self {
+                exit_code: EXIT_ALLOW,
+                stdout: (!context.is_empty()).then(|| context.join("\n")),
+                stderr: None,
+            },
+            // Halt, deny and block - ask, modify and filter_output are degraded above
+            blocking => Self {
+                exit_code: EXIT_BLOCK,
+                stdout: None,
+                stderr: blocking.reason().map(str::to_string),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::types::HarnessType;
+
+    fn pre_tool_use() -> HarnessCapabilities {
+        HarnessCapabilities::parse(
+            HarnessType::ClaudeCode,
+            r#"{"hook_event_name": "PreToolUse", "session_id": "s", "transcript_path": "/t",
+                "cwd": "/", "tool_name": "Bash", "tool_input": {"command": "ls"}}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_blocking_decisions_exit_2_with_reason() {
+        for decision in [
+            FinalDecision::Halt {
+                reason: "Stop".to_string(),
+                agent_messages: vec![],
+            },
+            FinalDecision::Deny {
+                reason: "Stop".to_string(),
+                agent_messages: vec![],
+            },
+            // Can't ask in this protocol, so a blockable event is denied
+            FinalDecision::Ask {
+                reason: "Stop".to_string(),
+                agent_messages: vec![],
+            },
+        ] {
+            assert_eq!(
+                ExitCodeResponse::from_decision(&decision, pre_tool_use()),
+                ExitCodeResponse {
+                    exit_code: EXIT_BLOCK,
+                    stdout: None,
+                    stderr: Some("Stop".to_string()),
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_allow_exits_0() {
+        let allow = FinalDecision::Allow { context: vec![] };
+        assert_eq!(
+            ExitCodeResponse::from_decision(&allow, pre_tool_use()),
+            ExitCodeResponse {
+                exit_code: EXIT_ALLOW,
+                stdout: None,
+                stderr: None,
+            }
+        );
+
+        // Context is printed only where the event can inject it
+        let caps = HarnessCapabilities::parse(
+            HarnessType::ClaudeCode,
+            r#"{"hook_event_name": "UserPromptSubmit", "session_id": "s", "transcript_path": "/t",
+                "cwd": "/", "prompt": "hi"}"#,
+        )
+        .unwrap();
+        let allow = FinalDecision::Allow {
+            context: vec!["one".to_string(), "two".to_string()],
+        };
+        assert_eq!(
+            ExitCodeResponse::from_decision(&allow, caps)
+                .stdout
+                .as_deref(),
+            Some("one\ntwo")
+        );
+        assert_eq!(
+            ExitCodeResponse::from_decision(&allow, pre_tool_use()).stdout,
+            None
+        );
+    }
+
+    #[test]
+    fn test_output_mode_from_yaml() {
+        let modes: std::collections::HashMap<HarnessType, OutputMode> =
+            serde_yaml_ng::from_str("goose: exit-code\nclaude: json").unwrap();
+        assert_eq!(modes[&HarnessType::Goose], OutputMode::ExitCode);
+        assert_eq!(modes[&HarnessType::ClaudeCode], OutputMode::Json);
+    }
+}
@@ -1,20 +1,34 @@
 // Place this code in cupcake-rewrite/src/harness/mod.rs
 
+pub mod capabilities;
 pub mod events;
+pub mod exit_code;
+pub mod passthrough;
+pub mod permission_suggestions;
 pub mod response;
 pub mod types;
 
 use crate::engine::decision::FinalDecision;
 use anyhow::Result;
+use capabilities::HarnessCapabilities;
+use events::aider::AiderEvent;
 use events::claude_code::ClaudeCodeEvent;
+use events::cline::ClineEvent;
 use events::cursor::CursorEvent;
 use events::factory::FactoryEvent;
+use events::goose::GooseEvent;
 use events::opencode::OpenCodeEvent;
+use passthrough::{AllowResponse, ResponseMode};
+use permission_suggestions::{PermissionList, SettingsDestination};
+use response::types::{
+    CupcakeResponse, HookSpecificOutput, PermissionDecision, PermissionRequestBehavior,
+    PermissionRequestDecision,
+};
 use response::{
-    ClaudeCodeResponseBuilder, CursorResponseBuilder, EngineDecision, FactoryResponseBuilder,
-    OpenCodeResponse,
+    AiderResponse, ClaudeCodeResponseBuilder, ClineResponse, CursorResponseBuilder, EngineDecision,
+    FactoryResponseBuilder, GooseResponse, OpenCodeResponse,
 };
-use serde_json::Value;
+use serde_json::{json, Value};
 
 /// The ClaudeHarness - a pure translator
 pub struct ClaudeHarness;
@@ -28,6 +42,15 @@ pub struct FactoryHarness;
 /// The OpenCodeHarness - a pure translator for OpenCode events
 pub struct OpenCodeHarness;
 
+/// The ClineHarness - a pure translator for Cline events
+pub struct ClineHarness;
+
+/// The AiderHarness - a pure translator for Aider launcher events
+pub struct AiderHarness;
+
+/// The GooseHarness - a pure translator for Goose hook events
+pub struct GooseHarness;
+
 impl ClaudeHarness {
     /// Parse the raw hook event from stdin
     pub fn parse_event(input: &str) -> Result<ClaudeCodeEvent> {
@@ -36,6 +59,18 @@ impl ClaudeHarness {
 
     /// Format the response for this specific harness
     pub fn format_response(event: &ClaudeCodeEvent, decision: &FinalDecision) -> Result<Value> {
+        Self::format_response_with_mode(event, decision, ResponseMode::default())
+    }
+
+    /// Format the response, applying `response_passthrough` settings
+    pub fn format_response_with_mode(
+        event: &ClaudeCodeEvent,
+        decision: &FinalDecision,
+        mode: ResponseMode,
+    ) -> Result<Value> {
+        let decision = &mode.apply_context(decision);
+        let decision = &HarnessCapabilities::claude_code(event).degrade(decision);
+
         // 1. Convert our new FinalDecision into the old EngineDecision format
         //    that the response builders expect.
         let engine_decision = Self::adapt_decision(decision);
@@ -44,14 +79,47 @@ impl ClaudeHarness {
         let context = Self::extract_context(decision);
 
         // 3. Use the spec-compliant response builder with extracted context
-        let cupcake_response = ClaudeCodeResponseBuilder::build_response(
+        let mut cupcake_response = ClaudeCodeResponseBuilder::build_response(
             &engine_decision,
             event,
             context,
             false, // suppress_output can be made configurable later
         );
 
-        // 3. Convert the final response to a JSON Value.
+        // 4. Allow is a passthrough by default - only answer explicitly if configured
+        match mode.allow_override(decision) {
+            Some(AllowResponse::Explicit) => {
+                cupcake_response.hook_specific_output = match event {
+                    ClaudeCodeEvent::PreToolUse(_) => Some(HookSpecificOutput::PreToolUse {
+                        permission_decision: PermissionDecision::Allow,
+                        permission_decision_reason: None,
+                        updated_input: None,
+                    }),
+                    ClaudeCodeEvent::PermissionRequest(_) => {
+                        Some(HookSpecificOutput::PermissionRequest {
+                            decision: PermissionRequestDecision {
+                                behavior: PermissionRequestBehavior::Allow,
+                                updated_input: None,
+                                message: None,
+                                interrupt: None,
+                            },
+                        })
+                    }
+                    // Other events have no permission to grant
+                    _ => cupcake_response.hook_specific_output,
+                };
+            }
+            Some(AllowResponse::Passthrough) => cupcake_response = CupcakeResponse::empty(),
+            None => {}
+        }
+
+        if let Some(destination) = mode.permission_suggestions {
+            if let Some(message) = Self::permission_suggestion(event, decision, destination) {
+                cupcake_response.system_message = Some(message);
+            }
+        }
+
+        // 5. Convert the final response to a JSON Value.
         Ok(serde_json::to_value(cupcake_response)?)
     }
 
@@ -79,6 +147,10 @@ impl ClaudeHarness {
                 reason: reason.clone(),
                 updated_input: updated_input.clone(),
             },
+            FinalDecision::FilterOutput { reason, filter, .. } => EngineDecision::FilterOutput {
+                reason: reason.clone(),
+                filter: filter.clone(),
+            },
             FinalDecision::Allow { context } => EngineDecision::Allow {
                 reason: if !context.is_empty() {
                     Some(context.join("\n"))
@@ -104,6 +176,38 @@ impl ClaudeHarness {
             _ => None,
         }
     }
+
+    /// Suggest the permission rule covering an asked or denied tool call
+    fn permission_suggestion(
+        event: &ClaudeCodeEvent,
+        decision: &FinalDecision,
+        destination: SettingsDestination,
+    ) -> Option<String> {
+        let (tool_name, tool_input) = match event {
+            ClaudeCodeEvent::PreToolUse(payload) => (&payload.tool_name, &payload.tool_input),
+            ClaudeCodeEvent::PermissionRequest(payload) => {
+                (&payload.tool_name, &payload.tool_input)
+            }
+            _ => return None,
+        };
+        let list = match decision {
+            FinalDecision::Halt { .. }
+            | FinalDecision::Deny { .. }
+            | FinalDecision::Block { .. } => PermissionList::Deny,
+            // On PermissionRequest an ask shows Claude Code's own dialog, which
+            // already offers to remember the answer
+            FinalDecision::Ask { .. } if matches!(event, ClaudeCodeEvent::PreToolUse(_)) => {
+                PermissionList::Allow
+            }
+            _ => return None,
+        };
+        let rule = permission_suggestions::permission_rule(tool_name, tool_input);
+        Some(permission_suggestions::suggestion_message(
+            &rule,
+            list,
+            destination,
+        ))
+    }
 }
 
 impl CursorHarness {
@@ -119,6 +223,23 @@ impl CursorHarness {
     /// - beforeReadFile: Only {permission: "allow"|"deny"} - minimal schema
     /// - Other events: Full permission model with messages
     pub fn format_response(event: &CursorEvent, decision: &FinalDecision) -> Result<Value> {
+        Self::format_response_with_mode(event, decision, ResponseMode::default())
+    }
+
+    /// Format the response, applying `response_passthrough` settings
+    ///
+    /// Cursor answers allow explicitly by default; passthrough returns `{}`.
+    pub fn format_response_with_mode(
+        event: &CursorEvent,
+        decision: &FinalDecision,
+        mode: ResponseMode,
+    ) -> Result<Value> {
+        let decision = &mode.apply_context(decision);
+        let decision = &HarnessCapabilities::cursor(event).degrade(decision);
+        if mode.allow_override(decision) == Some(AllowResponse::Passthrough) {
+            return Ok(json!({}));
+        }
+
         // 1. Convert FinalDecision to EngineDecision format
         let engine_decision = Self::adapt_decision(decision);
 
@@ -149,10 +270,12 @@ impl CursorHarness {
             FinalDecision::Ask { reason, .. } => EngineDecision::Ask {
                 reason: reason.clone(),
             },
-            // Cursor doesn't support updatedInput - treat Modify as Allow
-            FinalDecision::Modify { reason, .. } => EngineDecision::Allow {
-                reason: Some(reason.clone()),
-            },
+            // Cursor doesn't support updatedInput or replacing output - treat as Allow
+            FinalDecision::Modify { reason, .. } | FinalDecision::FilterOutput { reason, .. } => {
+                EngineDecision::Allow {
+                    reason: Some(reason.clone()),
+                }
+            }
             FinalDecision::Allow { context } => EngineDecision::Allow {
                 reason: if !context.is_empty() {
                     Some(context.join("\n"))
@@ -171,7 +294,8 @@ impl CursorHarness {
             | FinalDecision::Deny { agent_messages, .. }
             | FinalDecision::Block { agent_messages, .. }
             | FinalDecision::Ask { agent_messages, .. }
-            | FinalDecision::Modify { agent_messages, .. } => {
+            | FinalDecision::Modify { agent_messages, .. }
+            | FinalDecision::FilterOutput { agent_messages, .. } => {
                 if agent_messages.is_empty() {
                     None
                 } else {
@@ -195,6 +319,23 @@ impl FactoryHarness {
     /// - updatedInput for PreToolUse (allows modifying tool parameters)
     /// - permission_mode field in all events
     pub fn format_response(event: &FactoryEvent, decision: &FinalDecision) -> Result<Value> {
+        Self::format_response_with_mode(event, decision, ResponseMode::default())
+    }
+
+    /// Format the response, applying `response_passthrough` settings
+    ///
+    /// Factory AI answers allow explicitly by default; passthrough returns `{}`.
+    pub fn format_response_with_mode(
+        event: &FactoryEvent,
+        decision: &FinalDecision,
+        mode: ResponseMode,
+    ) -> Result<Value> {
+        let decision = &mode.apply_context(decision);
+        let decision = &HarnessCapabilities::factory(event).degrade(decision);
+        if mode.allow_override(decision) == Some(AllowResponse::Passthrough) {
+            return Ok(serde_json::to_value(CupcakeResponse::empty())?);
+        }
+
         // 1. Convert FinalDecision to EngineDecision format
         let engine_decision = Self::adapt_decision(decision);
 
@@ -236,6 +377,10 @@ impl FactoryHarness {
                 reason: reason.clone(),
                 updated_input: updated_input.clone(),
             },
+            FinalDecision::FilterOutput { reason, filter, .. } => EngineDecision::FilterOutput {
+                reason: reason.clone(),
+                filter: filter.clone(),
+            },
             FinalDecision::Allow { context } => EngineDecision::Allow {
                 reason: if !context.is_empty() {
                     Some(context.join("\n"))
@@ -280,7 +425,26 @@ impl OpenCodeHarness {
     /// The TypeScript plugin will interpret this and either:
     /// - Throw an error (deny/block/ask)
     /// - Return normally (allow)
-    pub fn format_response(_event: &OpenCodeEvent, decision: &FinalDecision) -> Result<Value> {
+    pub fn format_response(event: &OpenCodeEvent, decision: &FinalDecision) -> Result<Value> {
+        Self::format_response_with_mode(event, decision, ResponseMode::default())
+    }
+
+    /// Format the response, applying `response_passthrough` settings
+    ///
+    /// The plugin always needs a decision, so passthrough is signalled with a
+    /// `passthrough` flag on the allow - `permission.ask` then leaves OpenCode's
+    /// own dialog in place instead of auto-approving.
+    pub fn format_response_with_mode(
+        event: &OpenCodeEvent,
+        decision: &FinalDecision,
+        mode: ResponseMode,
+    ) -> Result<Value> {
+        let decision = &mode.apply_context(decision);
+        let decision = &HarnessCapabilities::opencode(event).degrade(decision);
+        if mode.allow_override(decision) == Some(AllowResponse::Passthrough) {
+            return Ok(OpenCodeResponse::passthrough().to_json_value());
+        }
+
         let response = match decision {
             FinalDecision::Halt { reason, .. } => OpenCodeResponse::block(reason.clone()),
             FinalDecision::Deny { reason, .. } => OpenCodeResponse::deny(reason.clone()),
@@ -289,7 +453,20 @@ impl OpenCodeHarness {
                 // OpenCode plugin will convert "ask" to deny with approval message
                 OpenCodeResponse::ask(reason.clone())
             }
-            // OpenCode doesn't support updatedInput - treat Modify as Allow with reason
+            // The plugin replaces the output of tool.execute.after
+            FinalDecision::FilterOutput { reason, filter, .. } => match event {
+                OpenCodeEvent::PostToolUse(payload) => {
+                    let filtered = filter.apply(&serde_json::to_value(&payload.result)?);
+                    let output = match filtered.get("output") {
+                        Some(Value::String(output)) => output.clone(),
+                        Some(other) => other.to_string(),
+                        None => String::new(),
+                    };
+                    OpenCodeResponse::allow_with_output(output)
+                }
+                _ => OpenCodeResponse::allow_with_context(vec![reason.clone()]),
+            },
+            // OpenCode doesn't support updatedInput - treat as Allow with reason
             FinalDecision::Modify { reason, .. } => {
                 OpenCodeResponse::allow_with_context(vec![reason.clone()])
             }
@@ -305,3 +482,141 @@ impl OpenCodeHarness {
         Ok(response.to_json_value())
     }
 }
+
+impl ClineHarness {
+    /// Parse the raw hook event from stdin (Cline format)
+    pub fn parse_event(input: &str) -> Result<ClineEvent> {
+        Ok(serde_json::from_str(input)?)
+    }
+
+    /// Format the response for Cline harness
+    ///
+    /// Cline hooks answer with:
+    /// {
+    ///   "cancel": true|false,
+    ///   "errorMessage": "...",
+    ///   "contextModification": "..."
+    /// }
+    pub fn format_response(event: &ClineEvent, decision: &FinalDecision) -> Result<Value> {
+        Self::format_response_with_mode(event, decision, ResponseMode::default())
+    }
+
+    /// Format the response, applying `response_passthrough` settings
+    ///
+    /// An allow never overrides Cline's approval settings, so passthrough and
+    /// explicit allow produce the same response.
+    pub fn format_response_with_mode(
+        event: &ClineEvent,
+        decision: &FinalDecision,
+        mode: ResponseMode,
+    ) -> Result<Value> {
+        let decision = &mode.apply_context(decision);
+        let decision = &HarnessCapabilities::cline(event).degrade(decision);
+
+        let response = match decision {
+            FinalDecision::Halt { reason, .. }
+            | FinalDecision::Deny { reason, .. }
+            | FinalDecision::Block { reason, .. }
+            | FinalDecision::Ask { reason, .. } => ClineResponse::cancel(reason.clone()),
+            FinalDecision::Modify { reason, .. } | FinalDecision::FilterOutput { reason, .. } => {
+                ClineResponse::allow_with_context(vec![reason.clone()])
+            }
+            FinalDecision::Allow { context } if context.is_empty() => ClineResponse::allow(),
+            FinalDecision::Allow { context } => ClineResponse::allow_with_context(context.clone()),
+        };
+
+        Ok(response.to_json_value())
+    }
+}
+
+impl AiderHarness {
+    /// Parse the raw event from stdin (Aider launcher format)
+    pub fn parse_event(input: &str) -> Result<AiderEvent> {
+        Ok(serde_json::from_str(input)?)
+    }
+
+    /// Format the response for Aider harness
+    ///
+    /// The launcher reads:
+    /// {
+    ///   "decision": "allow"|"deny"|"ask",
+    ///   "reason": "...",
+    ///   "context": ["..."]
+    /// }
+    pub fn format_response(event: &AiderEvent, decision: &FinalDecision) -> Result<Value> {
+        Self::format_response_with_mode(event, decision, ResponseMode::default())
+    }
+
+    /// Format the response, applying `response_passthrough` settings
+    ///
+    /// An allow never skips Aider's own confirmation prompts, so passthrough
+    /// and explicit allow produce the same response.
+    pub fn format_response_with_mode(
+        event: &AiderEvent,
+        decision: &FinalDecision,
+        mode: ResponseMode,
+    ) -> Result<Value> {
+        let decision = &mode.apply_context(decision);
+        let decision = &HarnessCapabilities::aider(event).degrade(decision);
+
+        let response = match decision {
+            FinalDecision::Halt { reason, .. }
+            | FinalDecision::Deny { reason, .. }
+            | FinalDecision::Block { reason, .. } => AiderResponse::deny(reason.clone()),
+            FinalDecision::Ask { reason, .. } => AiderResponse::ask(reason.clone()),
+            FinalDecision::Modify { reason, .. } | FinalDecision::FilterOutput { reason, .. } => {
+                AiderResponse::allow_with_context(vec![reason.clone()])
+            }
+            FinalDecision::Allow { context } if context.is_empty() => AiderResponse::allow(),
+            FinalDecision::Allow { context } => AiderResponse::allow_with_context(context.clone()),
+        };
+
+        Ok(response.to_json_value())
+    }
+}
+
+impl GooseHarness {
+    /// Parse the raw hook event from stdin
+    pub fn parse_event(input: &str) -> Result<GooseEvent> {
+        Ok(serde_json::from_str(input)?)
+    }
+
+    /// Format the response for Goose harness
+    ///
+    /// Goose reads:
+    /// {
+    ///   "decision": "allow"|"deny"|"ask",
+    ///   "reason": "...",
+    ///   "context": ["..."]
+    /// }
+    pub fn format_response(event: &GooseEvent, decision: &FinalDecision) -> Result<Value> {
+        Self::format_response_with_mode(event, decision, ResponseMode::default())
+    }
+
+    /// Format the response, applying `response_passthrough` settings
+    ///
+    /// An allow leaves Goose's mode and tool permissions in charge, so
+    /// passthrough and explicit allow produce the same response.
+    pub fn format_response_with_mode(
+        event: &GooseEvent,
+        decision: &FinalDecision,
+        mode: ResponseMode,
+    ) -> Result<Value> {
+        let decision = &mode.apply_context(decision);
+        let decision = &HarnessCapabilities::goose(event).degrade(decision);
+
+        let response = match decision {
+            FinalDecision::Halt { reason, .. }
+            | FinalDecision::Deny { reason, .. }
+            | FinalDecision::Block { reason, .. } => GooseResponse::deny(reason.clone()),
+            FinalDecision::Ask { reason, .. } => GooseResponse::ask(reason.clone()),
+            FinalDecision::Modify { reason, .. } | FinalDecision::FilterOutput { reason, .. } => {
+                GooseResponse::allow_with_context(vec![reason.clone()])
+            }
+            FinalDecision::Allow { context } if context.is_empty() => GooseResponse::allow(),
+            FinalDecision::Allow { context } => GooseResponse::allow_with_context(context.clone()),
+        };
+
+        Ok(response.to_json_value())
+    }
+}
@@ -0,0 +1,234 @@
+//! Response passthrough - whether an allow overrides the harness's own permissions
+//!
+//! By default each harness keeps its established behavior on allow: Claude Code
+//! returns `{}` so its own permission settings still apply, while Cursor,
+//! Factory AI and OpenCode answer with an explicit allow. `response_passthrough:`
+//! rules change that per harness and per event:
+//!
+//! ```yaml
+//! response_passthrough:
+//!   # Cupcake is the permission system - skip Claude Code's prompts
+//!   - harnesses: [claude]
+//!     events: [PreToolUse, PermissionRequest]
+//!     allow: explicit
+//!   # Let Cursor's own allowlist decide whenever no policy objects
+//!   - harnesses: [cursor]
+//!     allow: passthrough
+//!   # Don't inject add_context results at session start
+//!   - events: [SessionStart]
+//!     context: passthrough
+//! ```
+//!
+//! Rules are applied in order; a later rule overrides the fields it sets.
+
+use serde::{Deserialize, Serialize};
+
+use super::permission_suggestions::SettingsDestination;
+use super::types::HarnessType;
+use crate::engine::decision::FinalDecision;
+
+/// How an allow without context is answered
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AllowResponse {
+    /// Approve the action, bypassing the harness's permission prompt
+    Explicit,
+    /// Return an empty response and let the harness decide
+    Passthrough,
+}
+
+/// What happens to add_context results on an otherwise plain allow
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ContextResponse {
+    /// Inject the context where the event supports it
+    Inject,
+    /// Drop the context
+    Passthrough,
+}
+
+/// One passthrough rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassthroughRule {
+    /// Harnesses this rule applies to (default: all)
+    #[serde(default)]
+    pub harnesses: Vec<HarnessType>,
+
+    /// Events this rule applies to, by the harness's own event name (default: all)
+    #[serde(default)]
+    pub events: Vec<String>,
+
+    #[serde(default)]
+    pub allow: Option<AllowResponse>,
+
+    #[serde(default)]
+    pub context: Option<ContextResponse>,
+}
+
+impl PassthroughRule {
+    fn matches(&self, harness: HarnessType, event: &str) -> bool {
+        (self.harnesses.is_empty() || self.harnesses.contains(&harness))
+            && (self.events.is_empty() || self.events.iter().any(|e| e == event))
+    }
+}
+
+/// Check rules for mistakes the deserializer can't catch
+pub fn validate(rules: &[PassthroughRule]) -> Vec<String> {
+    rules
+        .iter()
+        .enumerate()
+        .filter(|(_, rule)| rule.allow.is_none() && rule.context.is_none())
+        .map(|(idx, _)| {
+            format!("response_passthrough[{idx}]: set at least one of 'allow' or 'context'")
+        })
+        .collect()
+}
+
+/// The response settings in effect for one event
+///
+/// `None` fields keep the harness's default behavior.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResponseMode {
+    pub allow: Option<AllowResponse>,
+    pub context: Option<ContextResponse>,
+    /// Suggest Claude Code permission rules for this settings file
+    pub permission_suggestions: Option<SettingsDestination>,
+}
+
+impl ResponseMode {
+    pub fn resolve(rules: &[PassthroughRule], harness: HarnessType, event: &str) -> Self {
+        rules
+            .iter()
+            .filter(|rule| rule.matches(harness, event))
+            .fold(Self::default(), |mode, rule| Self {
+                allow: rule.allow.or(mode.allow),
+                context: rule.context.or(mode.context),
+                ..mode
+            })
+    }
+
+    /// Drop allow context if configured to pass it through
+    pub fn apply_context(&self, decision: &FinalDecision) -> FinalDecision {
+        match decision {
+            FinalDecision::Allow { context }
+                if !context.is_empty() && self.context == Some(ContextResponse::Passthrough) =>
+            {
+                FinalDecision::Allow { context: vec![] }
+            }
+            other => other.clone(),
+        }
+    }
+
+    /// The allow override for this decision, if any
+    ///
+    /// Only plain allows are affected - an allow carrying context still has
+    /// something to say and is always answered in full.
+    pub fn allow_override(&self, decision: &FinalDecision) -> Option<AllowResponse> {
+        match decision {
+            FinalDecision::Allow { context } if context.is_empty() => self.allow,
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_later_rules_override() {
+        let rules: Vec<PassthroughRule> = serde_yaml_ng::from_str(
+            r#"
+- allow: passthrough
+- harnesses: [claude]
+  events: [PreToolUse]
+  allow: explicit
+- events: [SessionStart]
+  context: passthrough
+"#,
+        )
+        .unwrap();
+        assert!(validate(&rules).is_empty());
+
+        let mode = ResponseMode::resolve(&rules, HarnessType::ClaudeCode, "PreToolUse");
+        assert_eq!(mode.allow, Some(AllowResponse::Explicit));
+        assert_eq!(mode.context, None);
+
+        let mode = ResponseMode::resolve(&rules, HarnessType::Cursor, "beforeShellExecution");
+        assert_eq!(mode.allow, Some(AllowResponse::Passthrough));
+
+        let mode = ResponseMode::resolve(&rules, HarnessType::ClaudeCode, "SessionStart");
+        assert_eq!(mode.context, Some(ContextResponse::Passthrough));
+        let decision = FinalDecision::Allow {
+            context: vec!["ctx".to_string()],
+        };
+        let stripped = mode.apply_context(&decision);
+        assert_eq!(stripped, FinalDecision::Allow { context: vec![] });
+        assert_eq!(mode.allow_override(&decision), None);
+        assert_eq!(
+            mode.allow_override(&stripped),
+            Some(AllowResponse::Passthrough)
+        );
+    }
+
+    #[test]
+    fn test_harness_allow_overrides() {
+        use crate::harness::{ClaudeHarness, CursorHarness};
+        use serde_json::json;
+
+        let allow = FinalDecision::Allow { context: vec![] };
+        let explicit = ResponseMode {
+            allow: Some(AllowResponse::Explicit),
+            ..Default::default()
+        };
+        let passthrough = ResponseMode {
+            allow: Some(AllowResponse::Passthrough),
+            ..Default::default()
+        };
+
+        let event = ClaudeHarness::parse_event(
+            r#"{"hook_event_name": "PreToolUse", "session_id": "s", "transcript_path": "/t",
+                "cwd": "/", "tool_name": "Bash", "tool_input": {"command": "ls"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            ClaudeHarness::format_response(&event, &allow).unwrap(),
+            json!({})
+        );
+        let response = ClaudeHarness::format_response_with_mode(&event, &allow, explicit).unwrap();
+        assert_eq!(
+            response["hookSpecificOutput"]["permissionDecision"],
+            "allow"
+        );
+
+        let event = CursorHarness::parse_event(
+            r#"{"hook_event_name": "beforeShellExecution", "conversation_id": "c",
+                "generation_id": "g", "command": "ls", "cwd": "/"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            CursorHarness::format_response(&event, &allow).unwrap(),
+            json!({"permission": "allow"})
+        );
+        assert_eq!(
+            CursorHarness::format_response_with_mode(&event, &allow, passthrough).unwrap(),
+            json!({})
+        );
+
+        // Denials are never affected
+        let deny = FinalDecision::Deny {
+            reason: "no".to_string(),
+            agent_messages: vec![],
+        };
+        let response =
+            CursorHarness::format_response_with_mode(&event, &deny, passthrough).unwrap();
+        assert_eq!(response["permission"], "deny");
+    }
+
+    #[test]
+    fn test_rule_without_settings_is_invalid() {
+        let rules: Vec<PassthroughRule> =
+            serde_yaml_ng::from_str("- harnesses: [cursor]\n").unwrap();
+        assert_eq!(validate(&rules).len(), 1);
+    }
+}
@@ -0,0 +1,209 @@
+//! Claude Code permission suggestions
+//!
+//! When a policy asks about or denies a tool call, Cupcake can tell the user
+//! which Claude Code permission rule would cover the call, so a decision that
+//! keeps coming up can be moved into the harness's own settings:
+//!
+//! ```yaml
+//! permission_suggestions:
+//!   enabled: true
+//!   destination: project   # local (default) | project | user
+//! ```
+//!
+//! The suggestion is sent as a `systemMessage`, shown to the user next to the
+//! decision. The reason for the agent is unchanged.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Permission suggestion settings (`permission_suggestions:` in rulebook.yml)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct PermissionSuggestionsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Which Claude Code settings file the suggestion points at
+    #[serde(default)]
+    pub destination: SettingsDestination,
+}
+
+impl PermissionSuggestionsConfig {
+    /// The destination to suggest, if suggestions are enabled
+    pub fn destination(&self) -> Option<SettingsDestination> {
+        self.enabled.then_some(self.destination)
+    }
+}
+
+/// Claude Code settings file a rule is suggested for
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SettingsDestination {
+    /// `.claude/settings.local.json` - this checkout only
+    #[default]
+    Local,
+    /// `.claude/settings.json` - shared with the team
+    Project,
+    /// `~/.claude/settings.json` - every project
+    User,
+}
+
+impl SettingsDestination {
+    pub fn path(&self) -> &'static str {
+        match self {
+            SettingsDestination::Local => ".claude/settings.local.json",
+            SettingsDestination::Project => ".claude/settings.json",
+            SettingsDestination::User => "~/.claude/settings.json",
+        }
+    }
+}
+
+/// Which permission list the suggestion targets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionList {
+    Allow,
+    Deny,
+}
+
+/// The Claude Code permission rule matching a tool call
+///
+/// Bash calls are matched by command prefix (`Bash(npm test:*)`), file tools
+/// by path, WebFetch by domain; any other tool by name.
+pub fn permission_rule(tool_name: &str, tool_input: &Value) -> String {
+    let field = |name: &str| tool_input.get(name).and_then(|v| v.as_str());
+
+    match tool_name {
+        "Bash" => match field("command").and_then(command_prefix) {
+            Some(prefix) => format!("Bash({prefix}:*)"),
+            None => tool_name.to_string(),
+        },
+        "Read" | "Edit" | "Write" | "MultiEdit" | "NotebookEdit" => {
+            match field("file_path").or_else(|| field("notebook_path")) {
+                // Claude Code reads `/path` as relative to the settings file and
+                // `//path` as absolute
+                Some(path) if path.starts_with('/') => format!("{tool_name}(/{path})"),
+                Some(path) => format!("{tool_name}({path})"),
+                None => tool_name.to_string(),
+            }
+        }
+        "WebFetch" => match field("url").and_then(url_host) {
+            Some(host) => format!("WebFetch(domain:{host})"),
+            None => tool_name.to_string(),
+        },
+        _ => tool_name.to_string(),
+    }
+}
+
+/// The program and, when it looks like a subcommand, its first argument
+fn command_prefix(command: &str) -> Option<String> {
+    let mut words = command.split_whitespace();
+    let program = words.next()?;
+    match words.next() {
+        Some(sub)
+            if sub
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+                && !sub.starts_with('-') =>
+        {
+            Some(format!("{program} {sub}"))
+        }
+        _ => Some(program.to_string()),
+    }
+}
+
+fn url_host(url: &str) -> Option<&str> {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let host = rest.split(['/', '?', '#']).next()?;
+    let host = host.rsplit_once('@').map_or(host, |(_, host)| host);
+    let host = host.split(':').next()?;
+    (!host.is_empty()).then_some(host)
+}
+
+/// The message shown to the user
+pub fn suggestion_message(
+    rule: &str,
+    list: PermissionList,
+    destination: SettingsDestination,
+) -> String {
+    let path = destination.path();
+    match list {
+        PermissionList::Allow => format!(
+            "Cupcake: to approve calls like this in Claude Code, add \"{rule}\" to permissions.allow in {path} (Cupcake policies still apply)"
+        ),
+        PermissionList::Deny => format!(
+            "Cupcake: to have Claude Code refuse calls like this up front, add \"{rule}\" to permissions.deny in {path}"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_permission_rule() {
+        assert_eq!(
+            permission_rule("Bash", &json!({"command": "npm test -- --watch"})),
+            "Bash(npm test:*)"
+        );
+        assert_eq!(
+            permission_rule("Bash", &json!({"command": "rm -rf build"})),
+            "Bash(rm:*)"
+        );
+        assert_eq!(
+            permission_rule("Edit", &json!({"file_path": "/repo/src/main.rs"})),
+            "Edit(//repo/src/main.rs)"
+        );
+        assert_eq!(
+            permission_rule(
+                "WebFetch",
+                &json!({"url": "https://user@docs.rs:443/serde"})
+            ),
+            "WebFetch(domain:docs.rs)"
+        );
+        assert_eq!(
+            permission_rule("mcp__github__create_issue", &json!({})),
+            "mcp__github__create_issue"
+        );
+    }
+
+    #[test]
+    fn test_claude_response_carries_suggestion() {
+        use crate::engine::decision::FinalDecision;
+        use crate::harness::passthrough::ResponseMode;
+        use crate::harness::ClaudeHarness;
+
+        let event = ClaudeHarness::parse_event(
+            r#"{"hook_event_name": "PreToolUse", "session_id": "s", "transcript_path": "/t",
+                "cwd": "/", "tool_name": "Bash", "tool_input": {"command": "git push --force"}}"#,
+        )
+        .unwrap();
+        let deny = FinalDecision::Deny {
+            reason: "Force push blocked".to_string(),
+            agent_messages: vec![],
+        };
+        let mode = ResponseMode {
+            permission_suggestions: Some(SettingsDestination::Local),
+            ..Default::default()
+        };
+
+        let response = ClaudeHarness::format_response_with_mode(&event, &deny, mode).unwrap();
+        assert_eq!(
+            response["hookSpecificOutput"]["permissionDecisionReason"],
+            "Force push blocked"
+        );
+        let message = response["systemMessage"].as_str().unwrap();
+        assert!(message.contains("\"Bash(git push:*)\" to permissions.deny"));
+
+        let response = ClaudeHarness::format_response(&event, &deny).unwrap();
+        assert!(response.get("systemMessage").is_none());
+    }
+
+    #[test]
+    fn test_config_destination() {
+        let config: PermissionSuggestionsConfig =
+            serde_yaml_ng::from_str("enabled: true\ndestination: project\n").unwrap();
+        assert_eq!(config.destination(), Some(SettingsDestination::Project));
+        assert_eq!(PermissionSuggestionsConfig::default().destination(), None);
+    }
+}
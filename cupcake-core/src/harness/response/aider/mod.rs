@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+
+/// What the Aider launcher should do with the action
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AiderDecision {
+    /// Continue - Aider's own confirmation settings still apply
+    Allow,
+    /// Refuse the action and tell the model why
+    Deny,
+    /// Show Aider's confirmation prompt, even under `--yes-always`
+    Ask,
+}
+
+/// Response from Cupcake to the Aider launcher
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AiderResponse {
+    pub decision: AiderDecision,
+
+    /// Shown to the user, and to the model on deny
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
+    /// Added to the prompt or command output the model sees
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context: Vec<String>,
+}
+
+impl AiderResponse {
+    /// Let the action continue
+    pub fn allow() -> Self {
+        Self {
+            decision: AiderDecision::Allow,
+            reason: None,
+            context: Vec::new(),
+        }
+    }
+
+    /// Let the action continue and add context for the model
+    pub fn allow_with_context(context: Vec<String>) -> Self {
+        Self {
+            context,
+            ..Self::allow()
+        }
+    }
+
+    /// Refuse the action
+    pub fn deny(reason: String) -> Self {
+        Self {
+            decision: AiderDecision::Deny,
+            reason: Some(reason),
+            context: Vec::new(),
+        }
+    }
+
+    /// Have the user confirm the action
+    pub fn ask(reason: String) -> Self {
+        Self {
+            decision: AiderDecision::Ask,
+            reason: Some(reason),
+            context: Vec::new(),
+        }
+    }
+
+    /// Convert to JSON value for stdout output
+    pub fn to_json_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("AiderResponse should always serialize")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wire_format() {
+        assert_eq!(
+            AiderResponse::allow().to_json_value(),
+            serde_json::json!({"decision": "allow"})
+        );
+        assert_eq!(
+            AiderResponse::allow_with_context(vec!["Run the tests".to_string()]).to_json_value(),
+            serde_json::json!({"decision": "allow", "context": ["Run the tests"]})
+        );
+        assert_eq!(
+            AiderResponse::ask("Confirm the push".to_string()).to_json_value(),
+            serde_json::json!({"decision": "ask", "reason": "Confirm the push"})
+        );
+    }
+
+    #[test]
+    fn test_decisions_by_event() {
+        use crate::engine::decision::FinalDecision;
+        use crate::harness::AiderHarness;
+
+        let pre = AiderHarness::parse_event(
+            r#"{
+                "hook_event_name": "PreToolUse",
+                "session_id": "aider-1",
+                "cwd": "/home/user",
+                "tool": "shell_command",
+                "args": {"command": "git push"}
+            }"#,
+        )
+        .unwrap();
+        let ask = FinalDecision::Ask {
+            reason: "Confirm the push".to_string(),
+            agent_messages: vec![],
+        };
+        let response = AiderHarness::format_response(&pre, &ask).unwrap();
+        assert_eq!(response["decision"], "ask");
+
+        let halt = FinalDecision::Halt {
+            reason: "Frozen".to_string(),
+            agent_messages: vec![],
+        };
+        let response = AiderHarness::format_response(&pre, &halt).unwrap();
+        assert_eq!(response["decision"], "deny");
+        assert_eq!(response["reason"], "Frozen");
+
+        // Prompts can't be confirmed, so the question blocks the prompt
+        let prompt = AiderHarness::parse_event(
+            r#"{
+                "hook_event_name": "UserPromptSubmit",
+                "session_id": "aider-1",
+                "cwd": "/home/user",
+                "prompt": "Deploy it"
+            }"#,
+        )
+        .unwrap();
+        let response = AiderHarness::format_response(&prompt, &ask).unwrap();
+        assert_eq!(response["decision"], "deny");
+    }
+}
@@ -23,6 +23,22 @@ impl FeedbackLoopResponseBuilder {
                 response.decision = Some("block".to_string());
                 response.reason = Some(feedback.clone());
             }
+            EngineDecision::FilterOutput { reason, filter } => match hook_event {
+                // Claude Code only accepts replacement output for MCP tools
+                ClaudeCodeEvent::PostToolUse(payload) if payload.tool_name.starts_with("mcp__") => {
+                    response.hook_specific_output = Some(HookSpecificOutput::PostToolUse {
+                        additional_context: None,
+                        updated_mcp_tool_output: Some(filter.apply(&payload.tool_response)),
+                    });
+                }
+                _ => {
+                    tracing::warn!(
+                        "Claude Code cannot replace {} output - filter_output ignored: {}",
+                        hook_event.event_name(),
+                        reason
+                    );
+                }
+            },
             EngineDecision::Allow { .. }
             | EngineDecision::Ask { .. }
             | EngineDecision::Modify { .. } => {
@@ -40,6 +56,7 @@ impl FeedbackLoopResponseBuilder {
                                 response.hook_specific_output =
                                     Some(HookSpecificOutput::PostToolUse {
                                         additional_context: Some(contexts.join("\n")),
+                                        updated_mcp_tool_output: None,
                                     });
                             }
                         }
@@ -70,6 +87,7 @@ impl FeedbackLoopResponseBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::engine::decision::OutputFilter;
     use crate::harness::events::claude_code::{
         CommonEventData, PostToolUsePayload, StopPayload, SubagentStopPayload,
     };
@@ -138,7 +156,9 @@ mod tests {
 
         // Test Rust struct correctness
         match &response.hook_specific_output {
-            Some(HookSpecificOutput::PostToolUse { additional_context }) => {
+            Some(HookSpecificOutput::PostToolUse {
+                additional_context, ..
+            }) => {
                 assert_eq!(
                     additional_context,
                     &Some("File contains TODO on line 45".to_string())
@@ -159,6 +179,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_filter_output_replaces_mcp_tool_output() {
+        let decision = EngineDecision::FilterOutput {
+            reason: "Redacted API keys".to_string(),
+            filter: OutputFilter {
+                redact: vec!["sk-[a-z0-9]+".to_string()],
+                ..Default::default()
+            },
+        };
+        let mcp_event = ClaudeCodeEvent::PostToolUse(PostToolUsePayload {
+            common: create_common_data(),
+            tool_name: "mcp__vault__read_secret".to_string(),
+            tool_input: json!({"name": "openai"}),
+            tool_response: json!({"content": "key=sk-abc123"}),
+            tool_use_id: None,
+        });
+        let response = FeedbackLoopResponseBuilder::build(&decision, None, &mcp_event, false);
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(
+            json["hookSpecificOutput"]["updatedMCPToolOutput"],
+            json!({"content": "key=[REDACTED]"})
+        );
+        assert_eq!(response.decision, None);
+
+        // Built-in tool output can't be replaced, so the response is empty
+        let bash_event = ClaudeCodeEvent::PostToolUse(PostToolUsePayload {
+            common: create_common_data(),
+            tool_name: "Bash".to_string(),
+            tool_input: json!({"command": "env"}),
+            tool_response: json!({"stdout": "OPENAI_API_KEY=sk-abc123"}),
+            tool_use_id: None,
+        });
+        let response = FeedbackLoopResponseBuilder::build(&decision, None, &bash_event, false);
+        assert!(response.hook_specific_output.is_none());
+    }
+
     #[test]
     fn test_feedback_loop_with_suppress() {
         let decision = EngineDecision::Block {
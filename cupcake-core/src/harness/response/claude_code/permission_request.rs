@@ -60,6 +60,12 @@ impl PermissionRequestResponseBuilder {
                 // Ask doesn't make sense for PermissionRequest - it IS the ask dialog
                 // Return empty response to let the normal permission dialog show to user
             }
+            EngineDecision::FilterOutput { .. } => {
+                // Nothing has executed yet - filter_output only applies to PostToolUse
+                tracing::warn!(
+                    "filter_output not supported for PermissionRequest - treating as Allow"
+                );
+            }
             // Modify implies Allow with updated input
             EngineDecision::Modify { updated_input, .. } => {
                 response.hook_specific_output = Some(HookSpecificOutput::PermissionRequest {
@@ -33,6 +33,10 @@ impl PreToolUseResponseBuilder {
                     updated_input: None,
                 });
             }
+            EngineDecision::FilterOutput { .. } => {
+                // Nothing has executed yet - filter_output only applies to PostToolUse
+                tracing::warn!("filter_output not supported for PreToolUse - treating as Allow");
+            }
             // Modify implies Allow with updated input
             EngineDecision::Modify {
                 reason,
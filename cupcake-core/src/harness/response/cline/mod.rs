@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+
+/// Response from Cupcake to a Cline hook
+///
+/// Cline reads one JSON object from the hook's stdout. `cancel` stops the
+/// action the hook fired for, `errorMessage` tells the user and model why,
+/// and `contextModification` is added to the conversation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClineResponse {
+    /// Stop the tool call, prompt or task
+    pub cancel: bool,
+
+    /// Text added to the conversation for the model
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_modification: Option<String>,
+
+    /// Why the action was cancelled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+}
+
+impl ClineResponse {
+    /// Let the action continue
+    ///
+    /// Cline's own approval settings still apply, so this is also the
+    /// passthrough response.
+    pub fn allow() -> Self {
+        Self {
+            cancel: false,
+            context_modification: None,
+            error_message: None,
+        }
+    }
+
+    /// Let the action continue and add context for the model
+    pub fn allow_with_context(context: Vec<String>) -> Self {
+        Self {
+            context_modification: Some(context.join("\n")),
+            ..Self::allow()
+        }
+    }
+
+    /// Cancel the action
+    pub fn cancel(reason: String) -> Self {
+        Self {
+            cancel: true,
+            context_modification: None,
+            error_message: Some(reason),
+        }
+    }
+
+    /// Convert to JSON value for stdout output
+    pub fn to_json_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("ClineResponse should always serialize")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wire_format() {
+        assert_eq!(
+            ClineResponse::allow().to_json_value(),
+            serde_json::json!({"cancel": false})
+        );
+        assert_eq!(
+            ClineResponse::allow_with_context(vec!["a".to_string(), "b".to_string()])
+                .to_json_value(),
+            serde_json::json!({"cancel": false, "contextModification": "a\nb"})
+        );
+        assert_eq!(
+            ClineResponse::cancel("No force pushes".to_string()).to_json_value(),
+            serde_json::json!({"cancel": true, "errorMessage": "No force pushes"})
+        );
+    }
+
+    #[test]
+    fn test_decisions_map_to_cancel() {
+        use crate::engine::decision::FinalDecision;
+        use crate::harness::ClineHarness;
+
+        let event = ClineHarness::parse_event(
+            r#"{
+                "hookName": "PreToolUse",
+                "taskId": "task123",
+                "workspaceRoots": ["/home/user"],
+                "preToolUse": {"toolName": "execute_command", "parameters": {"command": "git push"}}
+            }"#,
+        )
+        .unwrap();
+
+        // Cline can't ask - the question becomes a cancel
+        let ask = FinalDecision::Ask {
+            reason: "Confirm the push".to_string(),
+            agent_messages: vec![],
+        };
+        let response = ClineHarness::format_response(&event, &ask).unwrap();
+        assert_eq!(response["cancel"], true);
+        assert_eq!(response["errorMessage"], "Confirm the push");
+
+        let allow = FinalDecision::Allow {
+            context: vec!["Pushes go through CI".to_string()],
+        };
+        let response = ClineHarness::format_response(&event, &allow).unwrap();
+        assert_eq!(response["cancel"], false);
+        assert_eq!(response["contextModification"], "Pushes go through CI");
+    }
+}
@@ -42,7 +42,7 @@ pub fn build(decision: &EngineDecision, agent_messages: Option<Vec<String>>) ->
                 "agent_message": agent_message
             })
         }
-        EngineDecision::Modify { .. } => {
+        EngineDecision::Modify { .. } | EngineDecision::FilterOutput { .. } => {
             // Cursor doesn't support updatedInput - treat Modify as Allow
             json!({ "permission": "allow" })
         }
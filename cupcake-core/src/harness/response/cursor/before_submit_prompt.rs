@@ -44,7 +44,7 @@ pub fn build(decision: &EngineDecision, _agent_messages: Option<Vec<String>>) ->
                 "user_message": reason
             })
         }
-        EngineDecision::Modify { .. } => {
+        EngineDecision::Modify { .. } | EngineDecision::FilterOutput { .. } => {
             // Cursor doesn't support updatedInput - treat Modify as Allow
             json!({ "continue": true })
         }
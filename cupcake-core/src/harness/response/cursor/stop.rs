@@ -39,7 +39,8 @@ pub fn build(decision: &EngineDecision, _agent_messages: Option<Vec<String>>) ->
         }
         EngineDecision::Allow { .. }
         | EngineDecision::Ask { .. }
-        | EngineDecision::Modify { .. } => {
+        | EngineDecision::Modify { .. }
+        | EngineDecision::FilterOutput { .. } => {
             // Allow the agent to stop normally
             json!({})
         }
@@ -59,6 +59,12 @@ impl ContextInjectionResponseBuilder {
                     additional_context: Some(reason.clone()),
                 });
             }
+            EngineDecision::FilterOutput { .. } => {
+                // filter_output is only meaningful for PostToolUse - treat as Allow
+                tracing::warn!(
+                    "filter_output not supported for context injection events - treating as Allow"
+                );
+            }
             EngineDecision::Modify { reason, .. } => {
                 // Modify is only meaningful for PreToolUse - treat as Allow with reason as context
                 tracing::warn!(
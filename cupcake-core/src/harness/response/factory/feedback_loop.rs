@@ -23,6 +23,13 @@ impl FeedbackLoopResponseBuilder {
                 response.decision = Some("block".to_string());
                 response.reason = Some(feedback.clone());
             }
+            EngineDecision::FilterOutput { reason, .. } => {
+                // Factory AI has no way to replace tool output
+                tracing::warn!(
+                    "Factory AI cannot replace tool output - filter_output ignored: {}",
+                    reason
+                );
+            }
             EngineDecision::Allow { .. }
             | EngineDecision::Ask { .. }
             | EngineDecision::Modify { .. } => {
@@ -40,6 +47,7 @@ impl FeedbackLoopResponseBuilder {
                                 response.hook_specific_output =
                                     Some(HookSpecificOutput::PostToolUse {
                                         additional_context: Some(contexts.join("\n")),
+                                        updated_mcp_tool_output: None,
                                     });
                             }
                         }
@@ -136,7 +144,9 @@ mod tests {
 
         // Test Rust struct correctness
         match &response.hook_specific_output {
-            Some(HookSpecificOutput::PostToolUse { additional_context }) => {
+            Some(HookSpecificOutput::PostToolUse {
+                additional_context, ..
+            }) => {
                 assert_eq!(
                     additional_context,
                     &Some("File contains TODO on line 45".to_string())
@@ -39,6 +39,12 @@ impl GenericResponseBuilder {
                 );
                 // Empty response (same as Allow)
             }
+            EngineDecision::FilterOutput { .. } => {
+                // filter_output is only meaningful for PostToolUse - treat as Allow
+                tracing::warn!(
+                    "filter_output not supported for generic events - treating as Allow"
+                );
+            }
         }
 
         // Apply suppress_output if requested
@@ -45,6 +45,10 @@ impl PreToolUseResponseBuilder {
                     updated_input: None, // No modifications when asking
                 });
             }
+            EngineDecision::FilterOutput { .. } => {
+                // Nothing has executed yet - filter_output only applies to PostToolUse
+                tracing::warn!("filter_output not supported for PreToolUse - treating as Allow");
+            }
             // Modify implies Allow with updated input
             EngineDecision::Modify {
                 reason,
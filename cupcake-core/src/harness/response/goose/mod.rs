@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+
+/// Goose's answer at a permission check
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GooseDecision {
+    /// Continue - Goose's own mode and tool permissions still apply
+    Allow,
+    /// Refuse the tool call and return the reason to the model
+    Deny,
+    /// Show Goose's confirmation prompt, even in auto mode
+    Ask,
+}
+
+/// Response from Cupcake to a Goose hook
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GooseResponse {
+    pub decision: GooseDecision,
+
+    /// Shown to the user, and to the model on deny
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
+    /// Added to the prompt or tool result the model sees
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context: Vec<String>,
+}
+
+impl GooseResponse {
+    /// Let the event continue
+    pub fn allow() -> Self {
+        Self {
+            decision: GooseDecision::Allow,
+            reason: None,
+            context: Vec::new(),
+        }
+    }
+
+    /// Let the event continue and add context for the model
+    pub fn allow_with_context(context: Vec<String>) -> Self {
+        Self {
+            context,
+            ..Self::allow()
+        }
+    }
+
+    /// Refuse the tool call or prompt
+    pub fn deny(reason: String) -> Self {
+        Self {
+            decision: GooseDecision::Deny,
+            reason: Some(reason),
+            context: Vec::new(),
+        }
+    }
+
+    /// Have the user confirm the tool call
+    pub fn ask(reason: String) -> Self {
+        Self {
+            decision: GooseDecision::Ask,
+            reason: Some(reason),
+            context: Vec::new(),
+        }
+    }
+
+    /// Convert to JSON value for stdout output
+    pub fn to_json_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("GooseResponse should always serialize")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wire_format() {
+        assert_eq!(
+            GooseResponse::allow().to_json_value(),
+            serde_json::json!({"decision": "allow"})
+        );
+        assert_eq!(
+            GooseResponse::allow_with_context(vec!["Run the tests".to_string()]).to_json_value(),
+            serde_json::json!({"decision": "allow", "context": ["Run the tests"]})
+        );
+        assert_eq!(
+            GooseResponse::deny("Frozen".to_string()).to_json_value(),
+            serde_json::json!({"decision": "deny", "reason": "Frozen"})
+        );
+    }
+
+    #[test]
+    fn test_decisions_by_event() {
+        use crate::engine::decision::FinalDecision;
+        use crate::harness::GooseHarness;
+
+        let pre = GooseHarness::parse_event(
+            r#"{
+                "hook_event_name": "PreToolUse",
+                "session_id": "20251017_1",
+                "cwd": "/home/user",
+                "extension": "developer",
+                "tool": "developer__shell",
+                "arguments": {"command": "git push"}
+            }"#,
+        )
+        .unwrap();
+        let ask = FinalDecision::Ask {
+            reason: "Confirm the push".to_string(),
+            agent_messages: vec![],
+        };
+        let response = GooseHarness::format_response(&pre, &ask).unwrap();
+        assert_eq!(response["decision"], "ask");
+        assert_eq!(response["reason"], "Confirm the push");
+
+        // Goose can't rewrite tool arguments, so the modification is dropped
+        let modify = FinalDecision::Modify {
+            reason: "Pinned the remote".to_string(),
+            updated_input: serde_json::json!({"command": "git push origin main"}),
+            agent_messages: vec![],
+        };
+        let response = GooseHarness::format_response(&pre, &modify).unwrap();
+        assert_eq!(response, serde_json::json!({"decision": "allow"}));
+
+        // Prompts can't be confirmed, so the question blocks the prompt
+        let prompt = GooseHarness::parse_event(
+            r#"{
+                "hook_event_name": "UserPromptSubmit",
+                "session_id": "20251017_1",
+                "cwd": "/home/user",
+                "prompt": "Deploy it"
+            }"#,
+        )
+        .unwrap();
+        let response = GooseHarness::format_response(&prompt, &ask).unwrap();
+        assert_eq!(response["decision"], "deny");
+    }
+}
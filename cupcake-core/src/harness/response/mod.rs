@@ -1,11 +1,17 @@
+pub mod aider;
 pub mod claude_code;
+pub mod cline;
 pub mod cursor;
 pub mod factory;
+pub mod goose;
 pub mod opencode;
 pub mod types;
 
+pub use aider::{AiderDecision, AiderResponse};
 pub use claude_code::ClaudeCodeResponseBuilder;
+pub use cline::ClineResponse;
 pub use cursor::CursorResponseBuilder;
 pub use factory::FactoryResponseBuilder;
+pub use goose::{GooseDecision, GooseResponse};
 pub use opencode::OpenCodeResponse;
 pub use types::{CupcakeResponse, EngineDecision, HookSpecificOutput, PermissionDecision};
@@ -17,6 +17,14 @@ pub struct OpenCodeResponse {
     /// (Phase 2 feature - may not be supported initially)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<Vec<String>>,
+
+    /// Set on allow when the harness's own permission system should decide
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passthrough: Option<bool>,
+
+    /// Replacement for the tool output the agent sees (PostToolUse only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
 }
 
 impl OpenCodeResponse {
@@ -26,6 +34,8 @@ impl OpenCodeResponse {
             decision: "allow".to_string(),
             reason: None,
             context: None,
+            passthrough: None,
+            output: None,
         }
     }
 
@@ -35,6 +45,24 @@ impl OpenCodeResponse {
             decision: "allow".to_string(),
             reason: None,
             context: Some(context),
+            passthrough: None,
+            output: None,
+        }
+    }
+
+    /// Create an "allow" response that defers to OpenCode's own permissions
+    pub fn passthrough() -> Self {
+        Self {
+            passthrough: Some(true),
+            ..Self::allow()
+        }
+    }
+
+    /// Create an "allow" response that replaces the tool output
+    pub fn allow_with_output(output: String) -> Self {
+        Self {
+            output: Some(output),
+            ..Self::allow()
         }
     }
 
@@ -44,6 +72,8 @@ impl OpenCodeResponse {
             decision: "deny".to_string(),
             reason: Some(reason),
             context: None,
+            passthrough: None,
+            output: None,
         }
     }
 
@@ -53,6 +83,8 @@ impl OpenCodeResponse {
             decision: "block".to_string(),
             reason: Some(reason),
             context: None,
+            passthrough: None,
+            output: None,
         }
     }
 
@@ -63,6 +95,8 @@ impl OpenCodeResponse {
             decision: "ask".to_string(),
             reason: Some(reason),
             context: None,
+            passthrough: None,
+            output: None,
         }
     }
 
@@ -143,6 +177,37 @@ mod tests {
         assert_eq!(value["reason"], "Test");
     }
 
+    #[test]
+    fn test_post_tool_use_filter_replaces_output() {
+        use crate::engine::decision::{FinalDecision, OutputFilter};
+        use crate::harness::OpenCodeHarness;
+
+        let event = OpenCodeHarness::parse_event(
+            r#"{
+                "hook_event_name": "PostToolUse",
+                "session_id": "session123",
+                "cwd": "/home/user",
+                "tool": "bash",
+                "args": {"command": "env"},
+                "result": {"success": true, "output": "TOKEN=sk-abc123\nHOME=/home/user"}
+            }"#,
+        )
+        .unwrap();
+        let decision = FinalDecision::FilterOutput {
+            reason: "Secrets redacted".to_string(),
+            filter: OutputFilter {
+                updated_response: None,
+                redact: vec!["sk-[a-z0-9]+".to_string()],
+                max_length: None,
+            },
+            agent_messages: vec![],
+        };
+
+        let response = OpenCodeHarness::format_response(&event, &decision).unwrap();
+        assert_eq!(response["decision"], "allow");
+        assert_eq!(response["output"], "TOKEN=[REDACTED]\nHOME=/home/user");
+    }
+
     #[test]
     fn test_optional_fields_not_serialized() {
         let response = OpenCodeResponse::allow();
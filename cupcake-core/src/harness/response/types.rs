@@ -1,3 +1,4 @@
+use crate::engine::decision::OutputFilter;
 use serde::{Deserialize, Serialize};
 
 /// The main response structure for Claude Code hooks
@@ -63,6 +64,12 @@ pub enum HookSpecificOutput {
     PostToolUse {
         #[serde(rename = "additionalContext", skip_serializing_if = "Option::is_none")]
         additional_context: Option<String>,
+        /// Replacement output for MCP tools (Claude Code only supports this for MCP tools)
+        #[serde(
+            rename = "updatedMCPToolOutput",
+            skip_serializing_if = "Option::is_none"
+        )]
+        updated_mcp_tool_output: Option<serde_json::Value>,
     },
     PreCompact {
         #[serde(rename = "customInstructions", skip_serializing_if = "Option::is_none")]
@@ -128,4 +135,8 @@ pub enum EngineDecision {
         reason: String,
         updated_input: serde_json::Value,
     },
+    FilterOutput {
+        reason: String,
+        filter: OutputFilter,
+    },
 }
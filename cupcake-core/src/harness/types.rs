@@ -26,6 +26,18 @@ pub enum HarnessType {
     /// OpenCode (opencode.ai) - Terminal-based AI coding agent
     #[serde(rename = "opencode")]
     OpenCode,
+
+    /// Cline (cline.bot) and compatible VS Code agent extensions
+    #[serde(rename = "cline")]
+    Cline,
+
+    /// Aider (aider.chat) - Terminal-based AI pair programmer
+    #[serde(rename = "aider")]
+    Aider,
+
+    /// Goose (Block) - Open-source on-machine AI agent
+    #[serde(rename = "goose")]
+    Goose,
 }
 
 impl HarnessType {
@@ -36,6 +48,9 @@ impl HarnessType {
             HarnessType::Cursor => "cursor",
             HarnessType::Factory => "factory",
             HarnessType::OpenCode => "opencode",
+            HarnessType::Cline => "cline",
+            HarnessType::Aider => "aider",
+            HarnessType::Goose => "goose",
         }
     }
 
@@ -46,6 +61,9 @@ impl HarnessType {
             HarnessType::Cursor => "Cursor",
             HarnessType::Factory => "Factory AI",
             HarnessType::OpenCode => "OpenCode",
+            HarnessType::Cline => "Cline",
+            HarnessType::Aider => "Aider",
+            HarnessType::Goose => "Goose",
         }
     }
 
@@ -56,6 +74,9 @@ impl HarnessType {
             HarnessType::Cursor => "cursor",
             HarnessType::Factory => "factory",
             HarnessType::OpenCode => "opencode",
+            HarnessType::Cline => "cline",
+            HarnessType::Aider => "aider",
+            HarnessType::Goose => "goose",
         }
     }
 }
@@ -75,8 +96,11 @@ impl std::str::FromStr for HarnessType {
             "cursor" => Ok(HarnessType::Cursor),
             "factory" | "factoryai" | "factory-ai" | "droid" => Ok(HarnessType::Factory),
             "opencode" | "open-code" => Ok(HarnessType::OpenCode),
+            "cline" | "roo" | "roo-code" | "roocode" => Ok(HarnessType::Cline),
+            "aider" => Ok(HarnessType::Aider),
+            "goose" => Ok(HarnessType::Goose),
             _ => Err(format!(
-                "Unknown harness type: '{s}'. Valid options: claude, cursor, factory, opencode"
+                "Unknown harness type: '{s}'. Valid options: claude, cursor, factory, opencode, cline, aider, goose"
             )),
         }
     }
@@ -134,6 +158,9 @@ mod tests {
         assert_eq!(HarnessType::Cursor.policy_dir(), "cursor");
         assert_eq!(HarnessType::Factory.policy_dir(), "factory");
         assert_eq!(HarnessType::OpenCode.policy_dir(), "opencode");
+        assert_eq!(HarnessType::Cline.policy_dir(), "cline");
+        assert_eq!(HarnessType::Aider.policy_dir(), "aider");
+        assert_eq!(HarnessType::Goose.policy_dir(), "goose");
     }
 
     #[test]
@@ -153,4 +180,15 @@ mod tests {
         assert_eq!(HarnessType::OpenCode.to_string(), "opencode");
         assert_eq!(HarnessType::OpenCode.display_name(), "OpenCode");
     }
+
+    #[test]
+    fn test_cline_parsing() {
+        assert_eq!("cline".parse::<HarnessType>().unwrap(), HarnessType::Cline);
+        assert_eq!(
+            "roo-code".parse::<HarnessType>().unwrap(),
+            HarnessType::Cline
+        );
+        assert_eq!(HarnessType::Cline.to_string(), "cline");
+        assert_eq!(HarnessType::Cline.display_name(), "Cline");
+    }
 }
@@ -0,0 +1,267 @@
+//! Canonical event model shared by every harness
+//!
+//! Each harness names its events and places its fields differently - Claude Code
+//! puts a shell command in `tool_input.command` on `PreToolUse`, Cursor puts it
+//! in `command` on `beforeShellExecution`. Preprocessing attaches the same
+//! event in one harness-agnostic shape as `input.canonical`, so a policy that
+//! only reads `input.canonical` works unchanged under every harness:
+//!
+//! ```json
+//! {
+//!   "harness": "cursor",
+//!   "event": "pre_tool_use",
+//!   "tool": "Bash",
+//!   "command": "git push --force",
+//!   "file_path": null,
+//!   "content": null,
+//!   "cwd": "/home/user/project",
+//!   "session": "conv-123"
+//! }
+//! ```
+//!
+//! Fields that don't apply to the event are `null`; the harness's own fields
+//! are left untouched.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::harness::types::HarnessType;
+
+/// The harness-agnostic view of one event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CanonicalEvent {
+    /// Harness that sent the event (`claude`, `cursor`, `factory`, `opencode`, `cline`)
+    pub harness: &'static str,
+
+    /// Lifecycle phase, e.g. `pre_tool_use` - see [`canonical_event_name`]
+    pub event: String,
+
+    /// Tool in Claude Code naming (`Bash`, `Read`, `Edit`, ...), or the MCP tool name
+    pub tool: Option<String>,
+
+    /// Shell command about to run or that ran
+    pub command: Option<String>,
+
+    /// File the tool reads or writes, as the harness reported it
+    pub file_path: Option<String>,
+
+    /// Text the event carries: content being written, an edit's replacement
+    /// text, a file read (Cursor), a prompt, or an agent response
+    pub content: Option<String>,
+
+    /// Working directory of the session
+    pub cwd: Option<String>,
+
+    /// Session (Cursor: conversation) identifier
+    pub session: Option<String>,
+}
+
+/// Map a harness event name to its canonical lifecycle phase
+///
+/// | Canonical            | Claude Code / Factory AI / OpenCode / Aider / Goose | Cline                     | Cursor                                                         |
+/// | -------------------- | --------------------------------------------------- | ------------------------- | -------------------------------------------------------------- |
+/// | `pre_tool_use`       | `PreToolUse`                                        | `PreToolUse`              | `beforeShellExecution`, `beforeMCPExecution`, `beforeReadFile` |
+/// | `post_tool_use`      | `PostToolUse`                                       | `PostToolUse`             | `afterShellExecution`, `afterMCPExecution`, `afterFileEdit`    |
+/// | `permission_request` | `PermissionRequest`                                 |                           |                                                                |
+/// | `user_prompt`        | `UserPromptSubmit`                                  | `UserPromptSubmit`        | `beforeSubmitPrompt`                                           |
+/// | `agent_response`     |                                                     |                           | `afterAgentResponse`                                           |
+/// | `agent_thought`      |                                                     |                           | `afterAgentThought`                                            |
+/// | `session_start`      | `SessionStart`                                      | `TaskStart`, `TaskResume` |                                                                |
+/// | `session_end`        | `SessionEnd`                                        | `TaskCancel`              |                                                                |
+/// | `stop`               | `Stop`                                              | `TaskComplete`            | `stop`                                                         |
+/// | `subagent_stop`      | `SubagentStop`                                      |                           |                                                                |
+/// | `pre_compact`        | `PreCompact`                                        | `PreCompact`              |                                                                |
+/// | `notification`       | `Notification`                                      |                           |                                                                |
+///
+/// Unknown events keep their harness name.
+pub fn canonical_event_name(event: &str) -> String {
+    match event {
+        "PreToolUse" | "beforeShellExecution" | "beforeMCPExecution" | "beforeReadFile" => {
+            "pre_tool_use"
+        }
+        "PostToolUse" | "afterShellExecution" | "afterMCPExecution" | "afterFileEdit" => {
+            "post_tool_use"
+        }
+        "PermissionRequest" => "permission_request",
+        "UserPromptSubmit" | "beforeSubmitPrompt" => "user_prompt",
+        "afterAgentResponse" => "agent_response",
+        "afterAgentThought" => "agent_thought",
+        "SessionStart" | "TaskStart" | "TaskResume" => "session_start",
+        "SessionEnd" | "TaskCancel" => "session_end",
+        "Stop" | "stop" | "TaskComplete" => "stop",
+        "SubagentStop" => "subagent_stop",
+        "PreCompact" => "pre_compact",
+        "Notification" => "notification",
+        other => other,
+    }
+    .to_string()
+}
+
+impl CanonicalEvent {
+    /// Build the canonical view of a (preprocessed) event
+    pub fn from_input(input: &Value, harness: HarnessType) -> Self {
+        let event = str_field(input, "hook_event_name").unwrap_or_default();
+        match harness {
+            HarnessType::Cursor => Self::from_cursor(input, &event),
+            _ => Self::from_tool_input(input, harness, &event),
+        }
+    }
+
+    /// Claude Code, Factory AI, OpenCode, Cline, Aider and Goose (after
+    /// their fields are mapped to `tool_name` / `tool_input`)
+    fn from_tool_input(input: &Value, harness: HarnessType, event: &str) -> Self {
+        let tool = str_field(input, "tool_name");
+        let tool_input = input.get("tool_input").unwrap_or(&Value::Null);
+        let arg = |names: &[&str]| names.iter().find_map(|name| str_field(tool_input, name));
+
+        let command = match tool.as_deref() {
+            Some("Bash") => arg(&["command"]),
+            _ => None,
+        };
+        let file_path = arg(&["file_path", "filePath", "notebook_path"]);
+        let content = match event {
+            "UserPromptSubmit" => str_field(input, "prompt"),
+            _ => arg(&["content", "new_string", "newString", "new_source"])
+                .or_else(|| joined_edits(tool_input.get("edits"))),
+        };
+
+        Self {
+            harness: harness.as_str(),
+            event: canonical_event_name(event),
+            tool,
+            command,
+            file_path,
+            content,
+            cwd: str_field(input, "cwd"),
+            session: str_field(input, "session_id"),
+        }
+    }
+
+    fn from_cursor(input: &Value, event: &str) -> Self {
+        let tool = match event {
+            "beforeShellExecution" | "afterShellExecution" => Some("Bash".to_string()),
+            "beforeReadFile" => Some("Read".to_string()),
+            "afterFileEdit" => Some("Edit".to_string()),
+            "beforeMCPExecution" | "afterMCPExecution" => str_field(input, "tool_name"),
+            _ => None,
+        };
+        let command = match event {
+            "beforeShellExecution" | "afterShellExecution" => str_field(input, "command"),
+            _ => None,
+        };
+        let content = match event {
+            "beforeReadFile" => str_field(input, "content"),
+            "afterFileEdit" => joined_edits(input.get("edits")),
+            "beforeSubmitPrompt" => str_field(input, "prompt"),
+            "afterAgentResponse" | "afterAgentThought" => str_field(input, "text"),
+            _ => None,
+        };
+        // Only shell events carry a cwd; fall back to the first workspace root
+        let cwd = str_field(input, "cwd").or_else(|| {
+            input
+                .get("workspace_roots")
+                .and_then(|roots| roots.get(0))
+                .and_then(|root| root.as_str())
+                .map(str::to_string)
+        });
+
+        Self {
+            harness: HarnessType::Cursor.as_str(),
+            event: canonical_event_name(event),
+            tool,
+            command,
+            file_path: str_field(input, "file_path"),
+            content,
+            cwd,
+            session: str_field(input, "conversation_id"),
+        }
+    }
+}
+
+fn str_field(value: &Value, name: &str) -> Option<String> {
+    value.get(name).and_then(|v| v.as_str()).map(str::to_string)
+}
+
+/// Replacement text of every edit, one per line (MultiEdit, Cursor afterFileEdit)
+fn joined_edits(edits: Option<&Value>) -> Option<String> {
+    let edits = edits?.as_array()?;
+    let texts: Vec<&str> = edits
+        .iter()
+        .filter_map(|edit| edit.get("new_string").and_then(|v| v.as_str()))
+        .collect();
+    (!texts.is_empty()).then(|| texts.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_shell_command_is_canonical_across_harnesses() {
+        let claude = json!({
+            "hook_event_name": "PreToolUse",
+            "session_id": "s1",
+            "cwd": "/repo",
+            "tool_name": "Bash",
+            "tool_input": {"command": "git push --force"}
+        });
+        let cursor = json!({
+            "hook_event_name": "beforeShellExecution",
+            "conversation_id": "s1",
+            "generation_id": "g",
+            "workspace_roots": ["/ignored"],
+            "command": "git push --force",
+            "cwd": "/repo"
+        });
+
+        let from_claude = CanonicalEvent::from_input(&claude, HarnessType::ClaudeCode);
+        let from_cursor = CanonicalEvent::from_input(&cursor, HarnessType::Cursor);
+        assert_eq!(from_claude.event, "pre_tool_use");
+        assert_eq!(from_claude.tool.as_deref(), Some("Bash"));
+        assert_eq!(from_claude.command.as_deref(), Some("git push --force"));
+        assert_eq!(
+            CanonicalEvent {
+                harness: "claude",
+                ..from_cursor
+            },
+            from_claude
+        );
+    }
+
+    #[test]
+    fn test_file_content_and_prompts() {
+        let write = json!({
+            "hook_event_name": "PreToolUse",
+            "tool_name": "Write",
+            "tool_input": {"filePath": "/repo/a.ts", "content": "export {}"}
+        });
+        let canonical = CanonicalEvent::from_input(&write, HarnessType::OpenCode);
+        assert_eq!(canonical.file_path.as_deref(), Some("/repo/a.ts"));
+        assert_eq!(canonical.content.as_deref(), Some("export {}"));
+        assert_eq!(canonical.command, None);
+
+        let edit = json!({
+            "hook_event_name": "afterFileEdit",
+            "conversation_id": "c",
+            "workspace_roots": ["/repo"],
+            "file_path": "/repo/a.rs",
+            "edits": [{"old_string": "a", "new_string": "b"}, {"old_string": "c", "new_string": "d"}]
+        });
+        let canonical = CanonicalEvent::from_input(&edit, HarnessType::Cursor);
+        assert_eq!(canonical.event, "post_tool_use");
+        assert_eq!(canonical.tool.as_deref(), Some("Edit"));
+        assert_eq!(canonical.content.as_deref(), Some("b\nd"));
+        assert_eq!(canonical.cwd.as_deref(), Some("/repo"));
+
+        let prompt = json!({
+            "hook_event_name": "UserPromptSubmit",
+            "session_id": "s",
+            "prompt": "deploy to prod"
+        });
+        let canonical = CanonicalEvent::from_input(&prompt, HarnessType::Factory);
+        assert_eq!(canonical.event, "user_prompt");
+        assert_eq!(canonical.tool, None);
+        assert_eq!(canonical.content.as_deref(), Some("deploy to prod"));
+    }
+}
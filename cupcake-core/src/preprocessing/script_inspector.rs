@@ -2,11 +2,47 @@
 //!
 //! This module implements TOB-2 defense by detecting when a shell command
 //! executes a script file and loading its content for policy evaluation.
+//!
+//! It also extracts scripts passed inline, which never touch the filesystem:
+//! heredocs and here-strings fed to an interpreter (`bash <<EOF ... EOF`) and
+//! command-string flags (`sh -c '...'`, `python -c '...'`, `node -e '...'`).
 
+use super::shell_parser::{self, SimpleCommand};
+use serde::Serialize;
 use std::fs;
 use std::path::{Path, PathBuf};
 use tracing::debug;
 
+/// Shell interpreters, which take a command string with `-c`
+const SHELL_INTERPRETERS: &[&str] = &["bash", "sh", "zsh", "fish", "ksh", "dash"];
+
+/// Commands that run their arguments as another command
+const COMMAND_WRAPPERS: &[&str] = &["sudo", "env", "exec", "command", "nohup", "time"];
+
+/// Maximum nesting of shell command strings followed (`bash -c "sh -c '...'"`)
+const MAX_INLINE_DEPTH: usize = 3;
+
+/// Where an inline script was found in the command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InlineScriptSource {
+    /// `bash <<EOF ... EOF`
+    Heredoc,
+    /// `bash <<< '...'`
+    HereString,
+    /// `sh -c '...'`, `python -c '...'`, `node -e '...'`
+    Flag,
+}
+
+/// A script passed to an interpreter inline rather than as a file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InlineScript {
+    /// Interpreter the script is passed to (e.g. `bash`, `python3`)
+    pub interpreter: String,
+    pub source: InlineScriptSource,
+    pub content: String,
+}
+
 /// Script inspector for detecting and loading script execution
 pub struct ScriptInspector;
 
@@ -141,6 +177,169 @@ impl ScriptInspector {
             debug!("Attached script content to event: {:?}", script_path);
         }
     }
+
+    /// Extract scripts passed inline to an interpreter
+    ///
+    /// Detects patterns like:
+    /// - `bash <<EOF ... EOF` - Heredoc fed to an interpreter
+    /// - `python3 <<< 'print(1)'` - Here-string fed to an interpreter
+    /// - `sh -c 'rm -rf /'` - Shell command string
+    /// - `python -c '...'`, `node -e '...'`, `perl -e '...'` - Inline code
+    ///
+    /// Shell command strings are searched again for nested inline scripts.
+    /// Returns an empty list for commands that cannot be parsed.
+    pub fn extract_inline_scripts(command: &str) -> Vec<InlineScript> {
+        let mut scripts = Vec::new();
+        collect_inline_scripts(command, 0, &mut scripts);
+        scripts
+    }
+
+    /// Attach inline scripts to the event JSON as `inline_scripts`
+    ///
+    /// Unless a script file was already attached, their combined content is
+    /// also exposed as `executed_script_content` so existing policies cover it.
+    pub fn attach_inline_scripts_to_event(event: &mut serde_json::Value, scripts: &[InlineScript]) {
+        if scripts.is_empty() {
+            return;
+        }
+        let Some(obj) = event.as_object_mut() else {
+            return;
+        };
+
+        match serde_json::to_value(scripts) {
+            Ok(value) => {
+                obj.insert("inline_scripts".to_string(), value);
+            }
+            Err(e) => {
+                debug!("Failed to serialize inline scripts: {}", e);
+                return;
+            }
+        }
+
+        if !obj.contains_key("executed_script_content") {
+            let content = scripts
+                .iter()
+                .map(|s| s.content.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+            obj.insert(
+                "executed_script_content".to_string(),
+                serde_json::Value::String(content),
+            );
+            obj.insert(
+                "script_inspection_performed".to_string(),
+                serde_json::Value::Bool(true),
+            );
+        }
+
+        debug!("Attached {} inline script(s) to event", scripts.len());
+    }
+}
+
+fn collect_inline_scripts(command: &str, depth: usize, scripts: &mut Vec<InlineScript>) {
+    let Ok(ast) = shell_parser::parse(command) else {
+        return;
+    };
+
+    for simple in ast.simple_commands() {
+        let Some((interpreter, args)) = interpreter_invocation(simple) else {
+            continue;
+        };
+
+        for redirection in &simple.redirections {
+            let script = match (redirection.op.as_str(), &redirection.heredoc_body) {
+                ("<<" | "<<-", Some(body)) => (InlineScriptSource::Heredoc, body.clone()),
+                ("<<<", _) => (InlineScriptSource::HereString, redirection.target.clone()),
+                _ => continue,
+            };
+            scripts.push(InlineScript {
+                interpreter: interpreter.to_string(),
+                source: script.0,
+                content: script.1,
+            });
+        }
+
+        if let Some(content) = inline_flag_argument(interpreter, args) {
+            if SHELL_INTERPRETERS.contains(&interpreter) && depth < MAX_INLINE_DEPTH {
+                collect_inline_scripts(content, depth + 1, scripts);
+            }
+            scripts.push(InlineScript {
+                interpreter: interpreter.to_string(),
+                source: InlineScriptSource::Flag,
+                content: content.to_string(),
+            });
+        }
+    }
+}
+
+/// Find the interpreter a command runs, skipping wrappers like `sudo` and `env`
+///
+/// Returns the interpreter's base name and the arguments that follow it
+fn interpreter_invocation(command: &SimpleCommand) -> Option<(&str, &[String])> {
+    let mut i = 0;
+    while let Some(word) = command.argv.get(i) {
+        let name = Path::new(word)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(word);
+
+        if COMMAND_WRAPPERS.contains(&name) {
+            i += 1;
+            // Skip the wrapper's own options and env's NAME=value pairs
+            while command
+                .argv
+                .get(i)
+                .is_some_and(|a| a.starts_with('-') || (name == "env" && a.contains('=')))
+            {
+                i += 1;
+            }
+            continue;
+        }
+
+        let is_interpreter = SHELL_INTERPRETERS.contains(&name) || inline_flags(name).is_some();
+        return is_interpreter.then(|| (name, &command.argv[i + 1..]));
+    }
+    None
+}
+
+/// Flags that take inline code, as (short flag letters, long flags)
+fn inline_flags(interpreter: &str) -> Option<(&'static [char], &'static [&'static str])> {
+    if SHELL_INTERPRETERS.contains(&interpreter) {
+        return Some((&['c'], &[]));
+    }
+    match interpreter {
+        "python" | "python2" | "python3" => Some((&['c'], &[])),
+        "node" => Some((&['e', 'p'], &["--eval", "--print"])),
+        "perl" => Some((&['e', 'E'], &[])),
+        "ruby" => Some((&['e'], &[])),
+        "php" => Some((&['r'], &[])),
+        _ => None,
+    }
+}
+
+/// Return the inline code passed via the interpreter's code flag
+///
+/// Accepts the flag in a cluster of short options (`bash -xc '...'`,
+/// `perl -ne '...'`). Stops at the first non-option argument, which is a
+/// script file rather than inline code.
+fn inline_flag_argument<'a>(interpreter: &str, args: &'a [String]) -> Option<&'a str> {
+    let (short, long) = inline_flags(interpreter)?;
+
+    for (i, arg) in args.iter().enumerate() {
+        if arg == "--" || !arg.starts_with('-') {
+            return None;
+        }
+        let takes_code = if arg.starts_with("--") {
+            long.contains(&arg.as_str())
+        } else {
+            arg[1..].chars().all(|c| c.is_ascii_alphanumeric())
+                && arg.chars().last().is_some_and(|c| short.contains(&c))
+        };
+        if takes_code {
+            return args.get(i + 1).map(String::as_str);
+        }
+    }
+    None
 }
 
 /// Check if a path is likely a script file based on extension or pattern
@@ -314,4 +513,84 @@ mod tests {
         );
         assert!(event["script_inspection_performed"].as_bool().unwrap());
     }
+
+    fn inline(command: &str) -> Vec<(String, InlineScriptSource, String)> {
+        ScriptInspector::extract_inline_scripts(command)
+            .into_iter()
+            .map(|s| (s.interpreter, s.source, s.content))
+            .collect()
+    }
+
+    #[test]
+    fn test_extract_heredoc_and_here_string() {
+        assert_eq!(
+            inline("bash <<'EOF'\nrm -rf /important\nEOF"),
+            vec![(
+                "bash".to_string(),
+                InlineScriptSource::Heredoc,
+                "rm -rf /important\n".to_string()
+            )]
+        );
+        assert_eq!(
+            inline("python3 <<< 'import shutil'"),
+            vec![(
+                "python3".to_string(),
+                InlineScriptSource::HereString,
+                "import shutil".to_string()
+            )]
+        );
+
+        // Heredocs fed to non-interpreters are data, not scripts
+        assert!(inline("cat <<EOF > notes.txt\nrm -rf /\nEOF").is_empty());
+    }
+
+    #[test]
+    fn test_extract_inline_flags() {
+        assert_eq!(
+            inline("python -c 'import os; os.remove(\"x\")'"),
+            vec![(
+                "python".to_string(),
+                InlineScriptSource::Flag,
+                "import os; os.remove(\"x\")".to_string()
+            )]
+        );
+        assert_eq!(inline("sudo /bin/bash -xc 'rm -rf /'")[0].2, "rm -rf /");
+        assert_eq!(inline("perl -ne 'print'")[0].2, "print");
+        assert_eq!(inline("node --eval 'process.exit(1)'")[0].0, "node");
+
+        // Script files and commands without inline code
+        assert!(inline("bash deploy.sh -c foo").is_empty());
+        assert!(inline("python script.py").is_empty());
+        assert!(inline("grep -c pattern file").is_empty());
+    }
+
+    #[test]
+    fn test_extract_nested_shell_strings() {
+        let scripts = inline("bash -c \"python3 -c 'print(1)'\"");
+        assert_eq!(
+            scripts
+                .iter()
+                .map(|(i, _, c)| (i.as_str(), c.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("python3", "print(1)"), ("bash", "python3 -c 'print(1)'")]
+        );
+    }
+
+    #[test]
+    fn test_attach_inline_scripts_to_event() {
+        let mut event = serde_json::json!({"tool_name": "Bash"});
+        let scripts = ScriptInspector::extract_inline_scripts("sh -c 'rm -rf /'");
+        ScriptInspector::attach_inline_scripts_to_event(&mut event, &scripts);
+
+        assert_eq!(event["inline_scripts"][0]["source"], "flag");
+        assert_eq!(event["inline_scripts"][0]["interpreter"], "sh");
+        assert_eq!(event["executed_script_content"], "rm -rf /");
+        assert!(event.get("executed_script_path").is_none());
+
+        // A loaded script file takes precedence in executed_script_content
+        let mut event = serde_json::json!({"executed_script_content": "from file"});
+        ScriptInspector::attach_inline_scripts_to_event(&mut event, &scripts);
+        assert_eq!(event["executed_script_content"], "from file");
+        assert!(event.get("inline_scripts").is_some());
+    }
 }
@@ -0,0 +1,1059 @@
+//! Shell command parser
+//!
+//! Parses shell command strings into a structured AST so policies can match on
+//! parsed structure (argv lists, pipelines, subshells, command substitutions,
+//! redirections) rather than substrings of the raw command.
+//!
+//! Available to policies as `input.command_ast`.
+//!
+//! The parser covers the POSIX/bash subset that appears in agent tool calls:
+//! quoting and escapes (including `$'...'`), `&&`/`||`/`;`/`&` lists, `|`/`|&`
+//! pipelines, `( ... )` subshells, `{ ...; }` groups, `$( ... )`, backticks,
+//! `<( ... )`/`>( ... )`, redirections and heredocs. Compound statements such as
+//! `if`/`while` are flattened into the commands they contain rather than
+//! modelled as control flow. Input the parser cannot tokenize (e.g. unmatched
+//! quotes) is reported as a [`ShellParseError`] so policies can fail closed.
+
+use serde::Serialize;
+use thiserror::Error;
+
+/// Keywords that may prefix a command and are skipped when parsing it
+const LEADING_KEYWORDS: &[&str] = &["if", "then", "elif", "else", "do", "while", "until", "time"];
+
+/// Keywords that close a compound statement and carry no command of their own
+const CLOSING_KEYWORDS: &[&str] = &["fi", "done", "esac"];
+
+/// Operators recognised by the tokenizer, longest first
+const OPERATORS: &[&str] = &[
+    "&>>", "<<<", "<<-", "&&", "||", ";;", "|&", "&>", "<<", "<>", ">>", ">&", ">|", "<&", ";",
+    "&", "|", "(", ")", "<", ">",
+];
+
+/// Operators that are redirections (and therefore take a target word)
+const REDIRECTIONS: &[&str] = &[
+    "&>>", "<<<", "<<-", "&>", "<<", "<>", ">>", ">&", ">|", "<&", "<", ">",
+];
+
+/// Errors produced when a command cannot be parsed
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ShellParseError {
+    #[error("unterminated {0}")]
+    Unterminated(&'static str),
+    #[error("redirection '{0}' is missing a target")]
+    MissingRedirectTarget(String),
+    #[error("unexpected token '{0}'")]
+    UnexpectedToken(String),
+}
+
+/// A sequence of pipelines joined by control operators
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct CommandList {
+    pub pipelines: Vec<Pipeline>,
+}
+
+/// One or more commands connected with `|` or `|&`
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct Pipeline {
+    pub commands: Vec<Command>,
+    /// Whether the pipeline is prefixed with `!`
+    pub negated: bool,
+    /// Control operator following this pipeline (`&&`, `||`, `;` or `&`).
+    /// Newlines are reported as `;`.
+    pub operator: Option<String>,
+}
+
+/// A single command within a pipeline
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Command {
+    Simple(SimpleCommand),
+    Subshell {
+        body: CommandList,
+        redirections: Vec<Redirection>,
+    },
+    Group {
+        body: CommandList,
+        redirections: Vec<Redirection>,
+    },
+}
+
+/// A plain command: assignments, argv and redirections
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct SimpleCommand {
+    /// Arguments with quotes removed; `argv[0]` is the program
+    pub argv: Vec<String>,
+    /// Leading `NAME=value` assignments
+    pub assignments: Vec<String>,
+    pub redirections: Vec<Redirection>,
+    /// Command and process substitutions found in any word of this command
+    pub substitutions: Vec<CommandList>,
+}
+
+/// An I/O redirection such as `2>/dev/null` or `<<EOF`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Redirection {
+    /// Explicit file descriptor (`2` in `2>&1`), if any
+    pub fd: Option<u32>,
+    pub op: String,
+    pub target: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heredoc_body: Option<String>,
+}
+
+impl CommandList {
+    /// All simple commands in this list, including those nested inside
+    /// subshells, groups and substitutions, in source order
+    pub fn simple_commands(&self) -> Vec<&SimpleCommand> {
+        let mut out = Vec::new();
+        self.collect_simple_commands(&mut out);
+        out
+    }
+
+    fn collect_simple_commands<'a>(&'a self, out: &mut Vec<&'a SimpleCommand>) {
+        for pipeline in &self.pipelines {
+            for command in &pipeline.commands {
+                match command {
+                    Command::Simple(simple) => {
+                        out.push(simple);
+                        for sub in &simple.substitutions {
+                            sub.collect_simple_commands(out);
+                        }
+                    }
+                    Command::Subshell { body, .. } | Command::Group { body, .. } => {
+                        body.collect_simple_commands(out);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl SimpleCommand {
+    /// Render this command back into a single normalized command line
+    ///
+    /// Arguments are re-quoted where needed and redirections are spaced
+    /// consistently (`cmd arg > out`), so substring-based policies see one
+    /// logical command at a time.
+    pub fn to_command_string(&self) -> String {
+        let mut parts: Vec<String> = self
+            .assignments
+            .iter()
+            .map(|assignment| match assignment.split_once('=') {
+                Some((name, value)) => format!("{name}={}", shell_words::quote(value)),
+                None => assignment.clone(),
+            })
+            .chain(
+                self.argv
+                    .iter()
+                    .map(|arg| shell_words::quote(arg).into_owned()),
+            )
+            .collect();
+        for redirection in &self.redirections {
+            let fd = redirection.fd.map(|fd| fd.to_string()).unwrap_or_default();
+            let target = shell_words::quote(&redirection.target);
+            if redirection.op.ends_with('&') || redirection.op.starts_with("<<") {
+                // Descriptor duplication (2>&1) and heredoc delimiters stay attached
+                parts.push(format!("{fd}{}{target}", redirection.op));
+            } else {
+                parts.push(format!("{fd}{} {target}", redirection.op));
+            }
+        }
+        parts.join(" ")
+    }
+}
+
+/// Parse a shell command string into a [`CommandList`]
+///
+/// # Examples
+///
+/// - `ls | grep foo` → one pipeline with two simple commands
+/// - `cd /tmp && rm -rf x` → two pipelines joined by `&&`
+/// - `echo $(whoami)` → `echo` with one substitution containing `whoami`
+pub fn parse(command: &str) -> Result<CommandList, ShellParseError> {
+    let tokens = Lexer::new(command).run()?;
+    let mut parser = Parser { tokens, pos: 0 };
+    parser.parse_list(None)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(Word),
+    Operator(&'static str),
+    Redirect {
+        fd: Option<u32>,
+        op: &'static str,
+        heredoc_body: Option<String>,
+    },
+    Newline,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+struct Word {
+    text: String,
+    substitutions: Vec<String>,
+    quoted: bool,
+}
+
+impl Word {
+    fn is_bare(&self, text: &str) -> bool {
+        !self.quoted && self.text == text
+    }
+}
+
+struct PendingHeredoc {
+    token_index: usize,
+    delimiter: String,
+    strip_tabs: bool,
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    pos: usize,
+    tokens: Vec<Token>,
+    pending_heredocs: Vec<PendingHeredoc>,
+    awaiting_delimiter: Option<(usize, bool)>,
+}
+
+impl Lexer {
+    fn new(source: &str) -> Self {
+        Self {
+            chars: source.chars().collect(),
+            pos: 0,
+            tokens: Vec::new(),
+            pending_heredocs: Vec::new(),
+            awaiting_delimiter: None,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn starts_with(&self, s: &str) -> bool {
+        s.chars()
+            .enumerate()
+            .all(|(i, c)| self.peek_at(i) == Some(c))
+    }
+
+    fn run(mut self) -> Result<Vec<Token>, ShellParseError> {
+        while let Some(c) = self.peek() {
+            match c {
+                ' ' | '\t' | '\r' => self.pos += 1,
+                '\\' if self.peek_at(1) == Some('\n') => self.pos += 2,
+                '\n' => {
+                    self.pos += 1;
+                    self.tokens.push(Token::Newline);
+                    self.read_heredoc_bodies();
+                }
+                '#' => {
+                    while self.peek().is_some_and(|c| c != '\n') {
+                        self.pos += 1;
+                    }
+                }
+                '<' | '>' if self.peek_at(1) == Some('(') => self.lex_word()?,
+                _ => {
+                    if let Some(fd) = self.lex_fd_prefix() {
+                        let op = self
+                            .match_operator()
+                            .expect("fd prefix precedes redirection");
+                        self.push_operator(op, Some(fd));
+                    } else if let Some(op) = self.match_operator() {
+                        self.push_operator(op, None);
+                    } else {
+                        self.lex_word()?;
+                    }
+                }
+            }
+        }
+
+        if self.awaiting_delimiter.is_some() {
+            return Err(ShellParseError::MissingRedirectTarget("<<".to_string()));
+        }
+        Ok(self.tokens)
+    }
+
+    /// Consume a file descriptor number directly followed by `<` or `>`
+    fn lex_fd_prefix(&mut self) -> Option<u32> {
+        let digits = self.chars[self.pos..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+        if digits == 0 {
+            return None;
+        }
+        match (self.peek_at(digits), self.peek_at(digits + 1)) {
+            (Some('<' | '>'), next) if next != Some('(') => {
+                let fd: String = self.chars[self.pos..self.pos + digits].iter().collect();
+                let fd = fd.parse().ok()?;
+                self.pos += digits;
+                Some(fd)
+            }
+            _ => None,
+        }
+    }
+
+    fn match_operator(&mut self) -> Option<&'static str> {
+        let op = OPERATORS.iter().find(|op| self.starts_with(op))?;
+        self.pos += op.chars().count();
+        Some(op)
+    }
+
+    fn push_operator(&mut self, op: &'static str, fd: Option<u32>) {
+        if REDIRECTIONS.contains(&op) {
+            self.tokens.push(Token::Redirect {
+                fd,
+                op,
+                heredoc_body: None,
+            });
+            if op == "<<" || op == "<<-" {
+                self.awaiting_delimiter = Some((self.tokens.len() - 1, op == "<<-"));
+            }
+        } else {
+            self.tokens.push(Token::Operator(op));
+        }
+    }
+
+    fn lex_word(&mut self) -> Result<(), ShellParseError> {
+        let mut word = Word::default();
+
+        while let Some(c) = self.peek() {
+            match c {
+                ' ' | '\t' | '\r' | '\n' | ';' | '&' | '|' | ')' => break,
+                '(' if word.text.ends_with('=') && !word.quoted => {
+                    // Array assignment: a=(1 2 3)
+                    let inner = self.read_balanced('(', ')')?;
+                    word.text.push('(');
+                    word.text.push_str(&inner);
+                    word.text.push(')');
+                }
+                '(' => break,
+                '<' | '>' if self.peek_at(1) == Some('(') => {
+                    // Process substitution: <(cmd) or >(cmd)
+                    self.pos += 1;
+                    let inner = self.read_balanced('(', ')')?;
+                    word.text.push(c);
+                    word.text.push('(');
+                    word.text.push_str(&inner);
+                    word.text.push(')');
+                    word.substitutions.push(inner);
+                }
+                '<' | '>' => break,
+                '\\' => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some('\n') => self.pos += 1,
+                        Some(next) => {
+                            word.text.push(next);
+                            word.quoted = true;
+                            self.pos += 1;
+                        }
+                        None => {}
+                    }
+                }
+                '\'' => {
+                    self.pos += 1;
+                    word.quoted = true;
+                    let literal = self.read_until('\'', "single quote")?;
+                    word.text.push_str(&literal);
+                }
+                '"' => {
+                    self.pos += 1;
+                    word.quoted = true;
+                    self.lex_double_quoted(&mut word)?;
+                }
+                '$' => self.lex_dollar(&mut word, false)?,
+                '`' => self.lex_backtick(&mut word)?,
+                _ => {
+                    word.text.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+
+        if let Some((token_index, strip_tabs)) = self.awaiting_delimiter.take() {
+            self.pending_heredocs.push(PendingHeredoc {
+                token_index,
+                delimiter: word.text.clone(),
+                strip_tabs,
+            });
+        }
+        self.tokens.push(Token::Word(word));
+        Ok(())
+    }
+
+    fn lex_double_quoted(&mut self, word: &mut Word) -> Result<(), ShellParseError> {
+        loop {
+            match self.peek() {
+                None => return Err(ShellParseError::Unterminated("double quote")),
+                Some('"') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                Some('\\') => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some('\n') => self.pos += 1,
+                        Some(next @ ('$' | '`' | '"' | '\\')) => {
+                            word.text.push(next);
+                            self.pos += 1;
+                        }
+                        _ => word.text.push('\\'),
+                    }
+                }
+                Some('$') => self.lex_dollar(word, true)?,
+                Some('`') => self.lex_backtick(word)?,
+                Some(c) => {
+                    word.text.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    fn lex_dollar(
+        &mut self,
+        word: &mut Word,
+        in_double_quotes: bool,
+    ) -> Result<(), ShellParseError> {
+        match self.peek_at(1) {
+            Some('(') if self.peek_at(2) == Some('(') => {
+                // Arithmetic expansion: kept verbatim
+                self.pos += 1;
+                let inner = self.read_balanced('(', ')')?;
+                word.text.push_str("$(");
+                word.text.push_str(&inner);
+                word.text.push(')');
+            }
+            Some('(') => {
+                self.pos += 1;
+                let inner = self.read_balanced('(', ')')?;
+                word.text.push_str("$(");
+                word.text.push_str(&inner);
+                word.text.push(')');
+                word.substitutions.push(inner);
+            }
+            Some('{') => {
+                self.pos += 1;
+                let inner = self.read_balanced('{', '}')?;
+                word.text.push_str("${");
+                word.text.push_str(&inner);
+                word.text.push('}');
+            }
+            Some('\'') if !in_double_quotes => {
+                self.pos += 2;
+                word.quoted = true;
+                let decoded = self.read_ansi_c()?;
+                word.text.push_str(&decoded);
+            }
+            _ => {
+                word.text.push('$');
+                self.pos += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn lex_backtick(&mut self, word: &mut Word) -> Result<(), ShellParseError> {
+        self.pos += 1;
+        let mut inner = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(ShellParseError::Unterminated("backtick")),
+                Some('`') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some('\\') if matches!(self.peek_at(1), Some('`' | '\\' | '$')) => {
+                    inner.push(self.peek_at(1).unwrap_or_default());
+                    self.pos += 2;
+                }
+                Some(c) => {
+                    inner.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+        word.text.push('`');
+        word.text.push_str(&inner);
+        word.text.push('`');
+        word.substitutions.push(inner);
+        Ok(())
+    }
+
+    /// Read up to (and consume) `end`, returning the text in between
+    fn read_until(&mut self, end: char, what: &'static str) -> Result<String, ShellParseError> {
+        let mut out = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(ShellParseError::Unterminated(what)),
+                Some(c) if c == end => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some(c) => {
+                    out.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    /// Read a balanced `open ... close` region starting at `open`, returning
+    /// the raw text between the delimiters. Quotes inside are respected.
+    fn read_balanced(&mut self, open: char, close: char) -> Result<String, ShellParseError> {
+        let what = if open == '(' { "parenthesis" } else { "brace" };
+        self.pos += 1;
+        let mut depth = 1;
+        let mut out = String::new();
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(ShellParseError::Unterminated(what));
+            };
+            self.pos += 1;
+            match c {
+                '\\' => {
+                    out.push(c);
+                    if let Some(next) = self.peek() {
+                        out.push(next);
+                        self.pos += 1;
+                    }
+                }
+                '\'' => {
+                    out.push(c);
+                    out.push_str(&self.read_until('\'', "single quote")?);
+                    out.push(c);
+                }
+                '"' => {
+                    out.push(c);
+                    loop {
+                        match self.peek() {
+                            None => return Err(ShellParseError::Unterminated("double quote")),
+                            Some('\\') => {
+                                out.push('\\');
+                                self.pos += 1;
+                                if let Some(next) = self.peek() {
+                                    out.push(next);
+                                    self.pos += 1;
+                                }
+                            }
+                            Some('"') => {
+                                out.push('"');
+                                self.pos += 1;
+                                break;
+                            }
+                            Some(other) => {
+                                out.push(other);
+                                self.pos += 1;
+                            }
+                        }
+                    }
+                }
+                _ if c == open => {
+                    depth += 1;
+                    out.push(c);
+                }
+                _ if c == close => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(out);
+                    }
+                    out.push(c);
+                }
+                _ => out.push(c),
+            }
+        }
+    }
+
+    /// Read and decode the body of an ANSI-C quoted string (`$'...'`)
+    fn read_ansi_c(&mut self) -> Result<String, ShellParseError> {
+        let mut out = String::new();
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(ShellParseError::Unterminated("ANSI-C quote"));
+            };
+            self.pos += 1;
+            match c {
+                '\'' => return Ok(out),
+                '\\' => {
+                    let Some(esc) = self.peek() else {
+                        return Err(ShellParseError::Unterminated("ANSI-C quote"));
+                    };
+                    self.pos += 1;
+                    let decoded = match esc {
+                        'n' => Some('\n'),
+                        't' => Some('\t'),
+                        'r' => Some('\r'),
+                        'a' => Some('\x07'),
+                        'b' => Some('\x08'),
+                        'e' | 'E' => Some('\x1b'),
+                        'f' => Some('\x0c'),
+                        'v' => Some('\x0b'),
+                        '\\' | '\'' | '"' | '?' => Some(esc),
+                        'x' => self.read_radix(16, 2),
+                        'u' => self.read_radix(16, 4),
+                        'U' => self.read_radix(16, 8),
+                        '0'..='7' => {
+                            self.pos -= 1;
+                            self.read_radix(8, 3)
+                        }
+                        _ => None,
+                    };
+                    match decoded {
+                        Some(ch) => out.push(ch),
+                        None => {
+                            out.push('\\');
+                            out.push(esc);
+                        }
+                    }
+                }
+                _ => out.push(c),
+            }
+        }
+    }
+
+    fn read_radix(&mut self, radix: u32, max_digits: usize) -> Option<char> {
+        let digits: String = self.chars[self.pos..]
+            .iter()
+            .take(max_digits)
+            .take_while(|c| c.is_digit(radix))
+            .collect();
+        if digits.is_empty() {
+            return None;
+        }
+        self.pos += digits.len();
+        u32::from_str_radix(&digits, radix)
+            .ok()
+            .and_then(char::from_u32)
+    }
+
+    /// Consume heredoc bodies queued on the line that just ended
+    fn read_heredoc_bodies(&mut self) {
+        for pending in std::mem::take(&mut self.pending_heredocs) {
+            let mut body = String::new();
+            while self.pos < self.chars.len() {
+                let end = self.chars[self.pos..]
+                    .iter()
+                    .position(|&c| c == '\n')
+                    .map_or(self.chars.len(), |i| self.pos + i);
+                let line: String = self.chars[self.pos..end].iter().collect();
+                self.pos = (end + 1).min(self.chars.len());
+
+                let line = if pending.strip_tabs {
+                    line.trim_start_matches('\t')
+                } else {
+                    line.as_str()
+                };
+                if line == pending.delimiter {
+                    break;
+                }
+                body.push_str(line);
+                body.push('\n');
+            }
+            if let Some(Token::Redirect { heredoc_body, .. }) =
+                self.tokens.get_mut(pending.token_index)
+            {
+                *heredoc_body = Some(body);
+            }
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn is_closing(&self, closing: &str) -> bool {
+        match self.peek() {
+            Some(Token::Operator(op)) => *op == closing,
+            Some(Token::Word(word)) => word.is_bare(closing),
+            _ => false,
+        }
+    }
+
+    fn parse_list(
+        &mut self,
+        closing: Option<&'static str>,
+    ) -> Result<CommandList, ShellParseError> {
+        let mut list = CommandList::default();
+        loop {
+            while matches!(self.peek(), Some(Token::Newline)) {
+                self.pos += 1;
+            }
+            match (self.peek(), closing) {
+                (None, Some(c)) => {
+                    return Err(ShellParseError::Unterminated(if c == ")" {
+                        "subshell"
+                    } else {
+                        "group"
+                    }))
+                }
+                (None, None) => break,
+                (_, Some(c)) if self.is_closing(c) => break,
+                _ => {}
+            }
+
+            let mut pipeline = self.parse_pipeline()?;
+            pipeline.operator = match self.peek() {
+                Some(Token::Operator(op @ ("&&" | "||" | ";" | "&"))) => {
+                    let op = op.to_string();
+                    self.pos += 1;
+                    Some(op)
+                }
+                Some(Token::Operator(";;")) | Some(Token::Newline) => {
+                    self.pos += 1;
+                    Some(";".to_string())
+                }
+                _ => None,
+            };
+            if !pipeline.commands.is_empty() {
+                list.pipelines.push(pipeline);
+            }
+        }
+        Ok(list)
+    }
+
+    fn parse_pipeline(&mut self) -> Result<Pipeline, ShellParseError> {
+        let mut pipeline = Pipeline::default();
+        if matches!(self.peek(), Some(Token::Word(w)) if w.is_bare("!")) {
+            pipeline.negated = true;
+            self.pos += 1;
+        }
+        loop {
+            if let Some(command) = self.parse_command()? {
+                pipeline.commands.push(command);
+            }
+            match self.peek() {
+                Some(Token::Operator("|" | "|&")) => self.pos += 1,
+                _ => break,
+            }
+        }
+        Ok(pipeline)
+    }
+
+    fn parse_command(&mut self) -> Result<Option<Command>, ShellParseError> {
+        while matches!(self.peek(), Some(Token::Word(w)) if !w.quoted && LEADING_KEYWORDS.contains(&w.text.as_str()))
+        {
+            self.pos += 1;
+        }
+
+        match self.peek() {
+            Some(Token::Operator("(")) => {
+                self.pos += 1;
+                let body = self.parse_list(Some(")"))?;
+                self.pos += 1;
+                let (redirections, _) = self.parse_redirections()?;
+                Ok(Some(Command::Subshell { body, redirections }))
+            }
+            Some(Token::Word(w)) if w.is_bare("{") => {
+                self.pos += 1;
+                let body = self.parse_list(Some("}"))?;
+                self.pos += 1;
+                let (redirections, _) = self.parse_redirections()?;
+                Ok(Some(Command::Group { body, redirections }))
+            }
+            Some(Token::Word(w)) if !w.quoted && CLOSING_KEYWORDS.contains(&w.text.as_str()) => {
+                self.pos += 1;
+                self.parse_redirections()?;
+                Ok(None)
+            }
+            Some(Token::Word(_)) | Some(Token::Redirect { .. }) => self
+                .parse_simple_command()
+                .map(|c| Some(Command::Simple(c))),
+            None
+            | Some(Token::Newline)
+            | Some(Token::Operator("&&" | "||" | ";" | ";;" | "&" | "|" | "|&")) => Ok(None),
+            Some(Token::Operator(op)) => Err(ShellParseError::UnexpectedToken(op.to_string())),
+        }
+    }
+
+    fn parse_simple_command(&mut self) -> Result<SimpleCommand, ShellParseError> {
+        let mut command = SimpleCommand::default();
+        loop {
+            match self.peek() {
+                Some(Token::Word(word)) => {
+                    let word = word.clone();
+                    self.pos += 1;
+                    for sub in &word.substitutions {
+                        command.substitutions.push(parse(sub)?);
+                    }
+                    if command.argv.is_empty() && is_assignment(&word.text) {
+                        command.assignments.push(word.text);
+                    } else {
+                        command.argv.push(word.text);
+                    }
+                }
+                Some(Token::Redirect { .. }) => {
+                    let (redirection, subs) = self.parse_redirection()?;
+                    command.redirections.push(redirection);
+                    command.substitutions.extend(subs);
+                }
+                _ => break,
+            }
+        }
+        Ok(command)
+    }
+
+    fn parse_redirections(
+        &mut self,
+    ) -> Result<(Vec<Redirection>, Vec<CommandList>), ShellParseError> {
+        let mut redirections = Vec::new();
+        let mut substitutions = Vec::new();
+        while matches!(self.peek(), Some(Token::Redirect { .. })) {
+            let (redirection, subs) = self.parse_redirection()?;
+            redirections.push(redirection);
+            substitutions.extend(subs);
+        }
+        Ok((redirections, substitutions))
+    }
+
+    fn parse_redirection(&mut self) -> Result<(Redirection, Vec<CommandList>), ShellParseError> {
+        let Some(Token::Redirect {
+            fd,
+            op,
+            heredoc_body,
+        }) = self.peek().cloned()
+        else {
+            unreachable!("parse_redirection called on a non-redirect token");
+        };
+        self.pos += 1;
+
+        let Some(Token::Word(target)) = self.peek().cloned() else {
+            return Err(ShellParseError::MissingRedirectTarget(op.to_string()));
+        };
+        self.pos += 1;
+
+        let substitutions = target
+            .substitutions
+            .iter()
+            .map(|sub| parse(sub))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok((
+            Redirection {
+                fd,
+                op: op.to_string(),
+                target: target.text,
+                heredoc_body,
+            },
+            substitutions,
+        ))
+    }
+}
+
+/// Whether a word is a `NAME=value` (or `NAME+=value`) assignment
+fn is_assignment(text: &str) -> bool {
+    let Some((name, _)) = text.split_once('=') else {
+        return false;
+    };
+    let name = name.strip_suffix('+').unwrap_or(name);
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argvs(list: &CommandList) -> Vec<Vec<&str>> {
+        list.simple_commands()
+            .iter()
+            .map(|c| c.argv.iter().map(String::as_str).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_simple_command_quotes() {
+        let ast = parse(r#"echo "hello world" 'single' esc\ aped"#).unwrap();
+        assert_eq!(
+            argvs(&ast),
+            vec![vec!["echo", "hello world", "single", "esc aped"]]
+        );
+    }
+
+    #[test]
+    fn test_lists_and_pipelines() {
+        let ast = parse("cd /tmp && rm -rf x || echo fail; ls | grep y").unwrap();
+        assert_eq!(ast.pipelines.len(), 4);
+        assert_eq!(ast.pipelines[0].operator.as_deref(), Some("&&"));
+        assert_eq!(ast.pipelines[1].operator.as_deref(), Some("||"));
+        assert_eq!(ast.pipelines[2].operator.as_deref(), Some(";"));
+        assert_eq!(ast.pipelines[3].operator, None);
+        assert_eq!(ast.pipelines[3].commands.len(), 2);
+    }
+
+    #[test]
+    fn test_operators_inside_quotes_are_not_split() {
+        let ast = parse(r#"echo "a && b | c; d""#).unwrap();
+        assert_eq!(argvs(&ast), vec![vec!["echo", "a && b | c; d"]]);
+    }
+
+    #[test]
+    fn test_subshell_and_group() {
+        let ast = parse("(cd /tmp; rm -rf x) && { echo ok; }").unwrap();
+        assert!(matches!(
+            ast.pipelines[0].commands[0],
+            Command::Subshell { .. }
+        ));
+        assert!(matches!(
+            ast.pipelines[1].commands[0],
+            Command::Group { .. }
+        ));
+        assert_eq!(
+            argvs(&ast),
+            vec![
+                vec!["cd", "/tmp"],
+                vec!["rm", "-rf", "x"],
+                vec!["echo", "ok"]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_command_substitution() {
+        let ast = parse("echo $(cat /etc/passwd) `whoami` \"$(id -u)\"").unwrap();
+        assert_eq!(
+            argvs(&ast),
+            vec![
+                vec!["echo", "$(cat /etc/passwd)", "`whoami`", "$(id -u)"],
+                vec!["cat", "/etc/passwd"],
+                vec!["whoami"],
+                vec!["id", "-u"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_redirections() {
+        let ast = parse("cmd > out.txt 2>&1 < in.txt &>> all.log").unwrap();
+        let Command::Simple(cmd) = &ast.pipelines[0].commands[0] else {
+            panic!("expected simple command");
+        };
+        assert_eq!(cmd.argv, vec!["cmd"]);
+        let redirs: Vec<(Option<u32>, &str, &str)> = cmd
+            .redirections
+            .iter()
+            .map(|r| (r.fd, r.op.as_str(), r.target.as_str()))
+            .collect();
+        assert_eq!(
+            redirs,
+            vec![
+                (None, ">", "out.txt"),
+                (Some(2), ">&", "1"),
+                (None, "<", "in.txt"),
+                (None, "&>>", "all.log"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_heredoc_body_captured() {
+        let ast = parse("cat <<EOF > f.txt\nline one\nline two\nEOF\necho done").unwrap();
+        let Command::Simple(cmd) = &ast.pipelines[0].commands[0] else {
+            panic!("expected simple command");
+        };
+        assert_eq!(cmd.redirections[0].target, "EOF");
+        assert_eq!(
+            cmd.redirections[0].heredoc_body.as_deref(),
+            Some("line one\nline two\n")
+        );
+        assert_eq!(argvs(&ast), vec![vec!["cat"], vec!["echo", "done"]]);
+    }
+
+    #[test]
+    fn test_assignments_and_ansi_c_quoting() {
+        let ast = parse(r"FOO=bar X=$'\x72\x6d' env").unwrap();
+        let Command::Simple(cmd) = &ast.pipelines[0].commands[0] else {
+            panic!("expected simple command");
+        };
+        assert_eq!(cmd.assignments, vec!["FOO=bar", "X=rm"]);
+        assert_eq!(cmd.argv, vec!["env"]);
+    }
+
+    #[test]
+    fn test_compound_keywords_flattened() {
+        let ast = parse("if test -f x; then rm x; fi; for f in a b; do echo $f; done").unwrap();
+        assert_eq!(
+            argvs(&ast),
+            vec![
+                vec!["test", "-f", "x"],
+                vec!["rm", "x"],
+                vec!["for", "f", "in", "a", "b"],
+                vec!["echo", "$f"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_negation_and_process_substitution() {
+        let ast = parse("! diff <(ls a) <(ls b)").unwrap();
+        assert!(ast.pipelines[0].negated);
+        assert_eq!(
+            argvs(&ast),
+            vec![
+                vec!["diff", "<(ls a)", "<(ls b)"],
+                vec!["ls", "a"],
+                vec!["ls", "b"]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("echo 'unterminated"),
+            Err(ShellParseError::Unterminated("single quote"))
+        );
+        assert_eq!(
+            parse("echo $(ls"),
+            Err(ShellParseError::Unterminated("parenthesis"))
+        );
+        assert!(matches!(
+            parse("cat >"),
+            Err(ShellParseError::MissingRedirectTarget(_))
+        ));
+        assert!(matches!(
+            parse("echo )"),
+            Err(ShellParseError::UnexpectedToken(_))
+        ));
+    }
+
+    #[test]
+    fn test_to_command_string() {
+        let ast =
+            parse("true&&FOO=1 rm  -rf \"my dir\" 2>&1 >/tmp/log; cat <<EOF\nx\nEOF").unwrap();
+        let rendered: Vec<String> = ast
+            .simple_commands()
+            .iter()
+            .map(|c| c.to_command_string())
+            .collect();
+        assert_eq!(
+            rendered,
+            vec!["true", "FOO=1 rm -rf 'my dir' 2>&1 > /tmp/log", "cat <<EOF"]
+        );
+    }
+
+    #[test]
+    fn test_ast_serialization_shape() {
+        let ast = parse("ls -la > out").unwrap();
+        let value = serde_json::to_value(&ast).unwrap();
+        assert_eq!(value["pipelines"][0]["commands"][0]["type"], "simple");
+        assert_eq!(value["pipelines"][0]["commands"][0]["argv"][1], "-la");
+        assert_eq!(
+            value["pipelines"][0]["commands"][0]["redirections"][0]["op"],
+            ">"
+        );
+    }
+}
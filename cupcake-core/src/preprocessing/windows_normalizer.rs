@@ -0,0 +1,510 @@
+//! Windows shell normalizer
+//!
+//! Agents running on Windows issue PowerShell and cmd.exe commands, which the
+//! POSIX shell parser misreads: backslashes are path separators rather than
+//! escapes, `` ` `` (PowerShell) and `^` (cmd.exe) are the escape characters,
+//! and PowerShell runs base64 UTF-16LE scripts passed via `-EncodedCommand`.
+//! This module:
+//! - Extracts scripts passed to `powershell`/`pwsh` (`-Command`,
+//!   `-EncodedCommand`) and `cmd /c`, decoding encoded commands
+//! - Splits PowerShell and cmd.exe scripts on their command separators,
+//!   stripping escape characters and canonicalizing backslash paths
+//! - Canonicalizes Windows file paths (`C:\Dir\file`, `\\?\C:\...`) to
+//!   forward slashes with a lowercase drive letter
+//!
+//! Available to policies as `input.windows_invocations`; the individual
+//! commands are also appended to `input.commands`.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::Serialize;
+
+/// Largest `-EncodedCommand` argument we will attempt to decode (bytes)
+const MAX_ENCODED_LEN: usize = 64 * 1024;
+
+/// Maximum nesting of shells invoking shells (`cmd /c powershell -c ...`)
+const MAX_DEPTH: usize = 3;
+
+/// PowerShell parameters that take a value, which must not be mistaken for
+/// the command
+const POWERSHELL_VALUE_PARAMS: &[&str] = &[
+    "executionpolicy",
+    "ep",
+    "ex",
+    "exec",
+    "windowstyle",
+    "w",
+    "inputformat",
+    "if",
+    "outputformat",
+    "of",
+    "o",
+    "workingdirectory",
+    "wd",
+    "configurationname",
+    "psconsolefile",
+    "settingsfile",
+    "version",
+    "v",
+];
+
+/// Windows command interpreter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowsShell {
+    #[serde(rename = "powershell")]
+    PowerShell,
+    Cmd,
+}
+
+impl WindowsShell {
+    /// Shell a program name refers to (`pwsh.exe` → PowerShell)
+    fn from_program(program: &str) -> Option<Self> {
+        let name = program
+            .rsplit(['\\', '/'])
+            .next()
+            .unwrap_or(program)
+            .to_ascii_lowercase();
+        match name.trim_end_matches(".exe") {
+            "powershell" | "pwsh" => Some(Self::PowerShell),
+            "cmd" => Some(Self::Cmd),
+            _ => None,
+        }
+    }
+
+    fn escape_char(self) -> char {
+        match self {
+            Self::PowerShell => '`',
+            Self::Cmd => '^',
+        }
+    }
+}
+
+/// A script passed to PowerShell or cmd.exe
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WindowsInvocation {
+    pub shell: WindowsShell,
+    /// The script text, decoded if it was passed with `-EncodedCommand`
+    pub script: String,
+    /// Whether the script was passed with `-EncodedCommand`
+    pub encoded: bool,
+    /// Individual commands in the script, normalized
+    pub commands: Vec<String>,
+}
+
+/// Normalizer for PowerShell and cmd.exe commands and Windows paths
+pub struct WindowsNormalizer;
+
+impl WindowsNormalizer {
+    /// Find scripts passed to PowerShell or cmd.exe in a command
+    ///
+    /// Scripts that themselves invoke a Windows shell are searched again, up to
+    /// a fixed depth.
+    ///
+    /// # Examples
+    ///
+    /// - `powershell -Command "Remove-Item -Recurse C:\x; echo done"`
+    /// - `pwsh -enc <base64 UTF-16LE>`
+    /// - `cmd /c "r^d /s /q .cupcake & echo done"`
+    pub fn extract_invocations(command: &str) -> Vec<WindowsInvocation> {
+        let mut invocations = Vec::new();
+        collect_invocations(command, WindowsShell::PowerShell, 0, &mut invocations);
+        invocations
+    }
+
+    /// Split a PowerShell or cmd.exe script into individual normalized commands
+    ///
+    /// PowerShell separates commands with `;`, `&&`, `||`, `|` and newlines;
+    /// cmd.exe with `&`, `&&`, `||`, `|` and newlines. Escape characters are
+    /// removed (`` Re`move-Item `` → `Remove-Item`, `r^d` → `rd`) and
+    /// backslash path separators become `/`.
+    pub fn split_commands(script: &str, shell: WindowsShell) -> Vec<String> {
+        segments(script, shell)
+            .iter()
+            .map(|tokens| render(tokens))
+            .collect()
+    }
+
+    /// Decode the script passed to `powershell -EncodedCommand`
+    ///
+    /// Returns None unless `argv` is a PowerShell invocation with a valid
+    /// base64 UTF-16LE encoded command.
+    pub fn encoded_command(argv: &[String]) -> Option<String> {
+        let shell = WindowsShell::from_program(argv.first()?)?;
+        if shell != WindowsShell::PowerShell {
+            return None;
+        }
+        match powershell_script(&argv[1..])? {
+            (script, true) => Some(script),
+            _ => None,
+        }
+    }
+
+    /// Whether a path is a Windows path (drive letter, UNC or `\\?\` prefix)
+    pub fn is_windows_path(path: &str) -> bool {
+        let bytes = path.as_bytes();
+        path.starts_with(r"\\")
+            || (bytes.len() >= 3
+                && bytes[0].is_ascii_alphabetic()
+                && bytes[1] == b':'
+                && matches!(bytes[2], b'\\' | b'/'))
+    }
+
+    /// Canonicalize a Windows path for comparison
+    ///
+    /// Strips the `\\?\` verbatim prefix, converts backslashes to `/`,
+    /// collapses repeated separators and lowercases the drive letter:
+    /// `\\?\C:\Users\Dev\\.cupcake` → `c:/Users/Dev/.cupcake`.
+    pub fn canonicalize_path(path: &str) -> String {
+        let path = if let Some(unc) = path.strip_prefix(r"\\?\UNC\") {
+            format!(r"\\{unc}")
+        } else {
+            path.strip_prefix(r"\\?\").unwrap_or(path).to_string()
+        };
+
+        let slashed = path.replace('\\', "/");
+        let (prefix, rest) = match slashed.strip_prefix("//") {
+            Some(rest) => ("//", rest),
+            None => ("", slashed.as_str()),
+        };
+        let mut canonical = prefix.to_string();
+        let mut last_was_slash = false;
+        for c in rest.chars() {
+            if c == '/' && last_was_slash {
+                continue;
+            }
+            last_was_slash = c == '/';
+            canonical.push(c);
+        }
+
+        if canonical.as_bytes().get(1) == Some(&b':') {
+            canonical[..1].make_ascii_lowercase();
+        }
+        canonical
+    }
+}
+
+fn collect_invocations(
+    script: &str,
+    shell: WindowsShell,
+    depth: usize,
+    invocations: &mut Vec<WindowsInvocation>,
+) {
+    if depth >= MAX_DEPTH {
+        return;
+    }
+
+    for tokens in segments(script, shell) {
+        // PowerShell's call operator: & powershell ...
+        let tokens = match tokens.first() {
+            Some(first) if first == "&" => &tokens[1..],
+            _ => &tokens[..],
+        };
+        let Some(inner_shell) = tokens.first().and_then(|p| WindowsShell::from_program(p)) else {
+            continue;
+        };
+
+        let found = match inner_shell {
+            WindowsShell::PowerShell => powershell_script(&tokens[1..]),
+            WindowsShell::Cmd => cmd_script(&tokens[1..]).map(|s| (s, false)),
+        };
+        let Some((inner_script, encoded)) = found else {
+            continue;
+        };
+
+        invocations.push(WindowsInvocation {
+            shell: inner_shell,
+            commands: WindowsNormalizer::split_commands(&inner_script, inner_shell),
+            script: inner_script.clone(),
+            encoded,
+        });
+        collect_invocations(&inner_script, inner_shell, depth + 1, invocations);
+    }
+}
+
+/// Script passed to PowerShell, and whether it was encoded
+///
+/// Parameters are case-insensitive and may be abbreviated (`-enc`, `-c`).
+/// A bare argument is treated as `-Command`, as PowerShell does; `-File`
+/// runs a script file rather than inline code.
+fn powershell_script(args: &[String]) -> Option<(String, bool)> {
+    let mut i = 0;
+    while let Some(arg) = args.get(i) {
+        let Some(param) = arg.strip_prefix(['-', '/']).map(str::to_ascii_lowercase) else {
+            return Some((args[i..].join(" "), false));
+        };
+
+        if param == "e"
+            || param == "ec"
+            || (param.len() >= 2 && "encodedcommand".starts_with(&param))
+        {
+            let decoded = decode_utf16_base64(args.get(i + 1)?)?;
+            return Some((decoded, true));
+        }
+        if "command".starts_with(&param) && !param.is_empty() {
+            let rest = &args[i + 1..];
+            return (!rest.is_empty()).then(|| (rest.join(" "), false));
+        }
+        if "file".starts_with(&param) && !param.is_empty() {
+            return None;
+        }
+
+        i += if POWERSHELL_VALUE_PARAMS.contains(&param.as_str()) {
+            2
+        } else {
+            1
+        };
+    }
+    None
+}
+
+/// Script passed to `cmd /c` or `cmd /k`
+fn cmd_script(args: &[String]) -> Option<String> {
+    let position = args
+        .iter()
+        .position(|a| a.eq_ignore_ascii_case("/c") || a.eq_ignore_ascii_case("/k"))?;
+    let rest = &args[position + 1..];
+    (!rest.is_empty()).then(|| rest.join(" "))
+}
+
+fn decode_utf16_base64(encoded: &str) -> Option<String> {
+    if encoded.len() > MAX_ENCODED_LEN {
+        return None;
+    }
+    let bytes = STANDARD.decode(encoded.trim()).ok()?;
+    if bytes.len() % 2 != 0 {
+        return None;
+    }
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16(&units).ok()
+}
+
+/// Split a script into commands, each a list of unquoted, unescaped tokens
+fn segments(script: &str, shell: WindowsShell) -> Vec<Vec<String>> {
+    let chars: Vec<char> = script.chars().collect();
+    let escape = shell.escape_char();
+    let mut segments = Vec::new();
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut in_token = false;
+    let mut quote: Option<char> = None;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if let Some(q) = quote {
+            if c == q {
+                // PowerShell embeds a quote by doubling it: 'it''s'
+                if shell == WindowsShell::PowerShell && chars.get(i + 1) == Some(&q) {
+                    token.push(q);
+                    i += 2;
+                    continue;
+                }
+                quote = None;
+            } else if c == escape && q == '"' && shell == WindowsShell::PowerShell {
+                if let Some(&next) = chars.get(i + 1) {
+                    token.push(next);
+                    i += 1;
+                }
+            } else {
+                token.push(c);
+            }
+            i += 1;
+            continue;
+        }
+
+        if c == escape {
+            // Escaped character, or a line continuation
+            match chars.get(i + 1) {
+                Some('\n') | None => {}
+                Some(&next) => {
+                    token.push(next);
+                    in_token = true;
+                }
+            }
+            i += 2;
+            continue;
+        }
+
+        if c == '"' || (c == '\'' && shell == WindowsShell::PowerShell) {
+            quote = Some(c);
+            in_token = true;
+        } else if let Some(len) = separator_len(&chars[i..], shell) {
+            flush_token(&mut tokens, &mut token, &mut in_token);
+            if !tokens.is_empty() {
+                segments.push(std::mem::take(&mut tokens));
+            }
+            i += len;
+            continue;
+        } else if c.is_whitespace() {
+            flush_token(&mut tokens, &mut token, &mut in_token);
+        } else {
+            token.push(c);
+            in_token = true;
+        }
+        i += 1;
+    }
+
+    flush_token(&mut tokens, &mut token, &mut in_token);
+    if !tokens.is_empty() {
+        segments.push(tokens);
+    }
+    segments
+}
+
+fn flush_token(tokens: &mut Vec<String>, token: &mut String, in_token: &mut bool) {
+    if *in_token {
+        tokens.push(std::mem::take(token));
+        *in_token = false;
+    }
+}
+
+/// Length of the command separator at the start of `chars`, if any
+fn separator_len(chars: &[char], shell: WindowsShell) -> Option<usize> {
+    match (chars[0], chars.get(1)) {
+        ('&', Some('&')) | ('|', Some('|')) => Some(2),
+        ('\n', _) | ('\r', _) | ('|', _) => Some(1),
+        (';', _) if shell == WindowsShell::PowerShell => Some(1),
+        ('&', _) if shell == WindowsShell::Cmd => Some(1),
+        _ => None,
+    }
+}
+
+/// Render tokens as a command string with backslash paths canonicalized
+fn render(tokens: &[String]) -> String {
+    let canonical: Vec<String> = tokens
+        .iter()
+        .map(|token| {
+            if token.contains('\\') {
+                WindowsNormalizer::canonicalize_path(token)
+            } else {
+                token.clone()
+            }
+        })
+        .collect();
+    shell_words::join(canonical)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encode a script the way `powershell -EncodedCommand` expects
+    fn encode(script: &str) -> String {
+        let bytes: Vec<u8> = script.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        STANDARD.encode(bytes)
+    }
+
+    #[test]
+    fn test_split_powershell_commands() {
+        assert_eq!(
+            WindowsNormalizer::split_commands(
+                "Re`move-Item -Recurse C:\\proj\\.cupcake; Write-Host 'it''s done' | Out-Null",
+                WindowsShell::PowerShell
+            ),
+            vec![
+                "Remove-Item -Recurse c:/proj/.cupcake",
+                "Write-Host 'it'\\''s done'",
+                "Out-Null"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_cmd_commands() {
+        assert_eq!(
+            WindowsNormalizer::split_commands(
+                "r^d /s /q .cupcake & del \"C:\\My Files\\x.txt\" && echo ok",
+                WindowsShell::Cmd
+            ),
+            vec!["rd /s /q .cupcake", "del 'c:/My Files/x.txt'", "echo ok"]
+        );
+    }
+
+    #[test]
+    fn test_extract_powershell_command() {
+        let invocations = WindowsNormalizer::extract_invocations(
+            "powershell.exe -NoProfile -ExecutionPolicy Bypass -Command \"Remove-Item .cupcake; echo hi\"",
+        );
+        assert_eq!(invocations.len(), 1);
+        assert_eq!(invocations[0].shell, WindowsShell::PowerShell);
+        assert!(!invocations[0].encoded);
+        assert_eq!(
+            invocations[0].commands,
+            vec!["Remove-Item .cupcake", "echo hi"]
+        );
+    }
+
+    #[test]
+    fn test_extract_encoded_command() {
+        let command = format!("pwsh -enc {}", encode("Remove-Item -Recurse .cupcake"));
+        let invocations = WindowsNormalizer::extract_invocations(&command);
+        assert_eq!(invocations[0].script, "Remove-Item -Recurse .cupcake");
+        assert!(invocations[0].encoded);
+
+        let argv: Vec<String> = command.split(' ').map(String::from).collect();
+        assert_eq!(
+            WindowsNormalizer::encoded_command(&argv).as_deref(),
+            Some("Remove-Item -Recurse .cupcake")
+        );
+        assert_eq!(
+            WindowsNormalizer::encoded_command(&["base64".to_string(), "-e".to_string()]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_extract_nested_cmd_and_powershell() {
+        let invocations = WindowsNormalizer::extract_invocations(
+            "cmd /c \"powershell -c Remove-Item C:\\x\\.cupcake\"",
+        );
+        assert_eq!(
+            invocations
+                .iter()
+                .map(|i| (i.shell, i.commands.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    WindowsShell::Cmd,
+                    vec!["powershell -c Remove-Item c:/x/.cupcake".to_string()]
+                ),
+                (
+                    WindowsShell::PowerShell,
+                    vec!["Remove-Item c:/x/.cupcake".to_string()]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_invocations() {
+        assert!(WindowsNormalizer::extract_invocations("ls -la").is_empty());
+        assert!(WindowsNormalizer::extract_invocations("powershell -File build.ps1").is_empty());
+        assert!(WindowsNormalizer::extract_invocations("cmd").is_empty());
+    }
+
+    #[test]
+    fn test_canonicalize_path() {
+        assert_eq!(
+            WindowsNormalizer::canonicalize_path(r"C:\Users\Dev\\.cupcake\rulebook.yml"),
+            "c:/Users/Dev/.cupcake/rulebook.yml"
+        );
+        assert_eq!(
+            WindowsNormalizer::canonicalize_path(r"\\?\D:\proj\src"),
+            "d:/proj/src"
+        );
+        assert_eq!(
+            WindowsNormalizer::canonicalize_path(r"\\?\UNC\server\share\x"),
+            "//server/share/x"
+        );
+        assert!(WindowsNormalizer::is_windows_path(r"C:\x"));
+        assert!(WindowsNormalizer::is_windows_path("c:/x"));
+        assert!(WindowsNormalizer::is_windows_path(r"\\server\share"));
+        assert!(!WindowsNormalizer::is_windows_path("/home/user"));
+        assert!(!WindowsNormalizer::is_windows_path("src\\main.rs"));
+    }
+}
@@ -24,6 +24,9 @@ use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
 use crate::engine::decision::{DecisionSet, FinalDecision};
+use crate::engine::identity::Identity;
+use crate::engine::waivers::WaiverUse;
+use crate::engine::wasm_runtime::MemoryUsage;
 use crate::harness::types::HarnessType;
 
 // ============================================================================
@@ -410,6 +413,18 @@ pub struct EvaluationResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub wasm_decision_set: Option<DecisionSet>,
 
+    /// WASM memory used by the evaluation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wasm_memory: Option<MemoryUsage>,
+
+    /// Identity resolved with this tier's rulebook
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity: Option<Identity>,
+
+    /// Waivers that suppressed or downgraded decisions of this tier
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub waivers: Vec<WaiverUse>,
+
     /// Final synthesized decision
     #[serde(skip_serializing_if = "Option::is_none")]
     pub final_decision: Option<FinalDecision>,
@@ -431,6 +446,21 @@ impl EvaluationResult {
         self.wasm_decision_set = Some(decision_set.clone());
     }
 
+    /// Record WASM memory used by the evaluation.
+    pub fn record_wasm_memory(&mut self, usage: MemoryUsage) {
+        self.wasm_memory = Some(usage);
+    }
+
+    /// Record the identity behind the event.
+    pub fn record_identity(&mut self, identity: &Identity) {
+        self.identity = Some(identity.clone());
+    }
+
+    /// Record the waivers applied to the decision set.
+    pub fn record_waivers(&mut self, waivers: &[WaiverUse]) {
+        self.waivers = waivers.to_vec();
+    }
+
     /// Record the final synthesized decision.
     pub fn record_final_decision(&mut self, decision: &FinalDecision) {
         self.final_decision = Some(decision.clone());
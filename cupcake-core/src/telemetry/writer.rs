@@ -194,11 +194,21 @@ impl TelemetryWriter {
                     if !ds.modifications.is_empty() {
                         out.push_str(&format!("  Modifications: {}\n", ds.modifications.len()));
                     }
+                    if !ds.output_filters.is_empty() {
+                        out.push_str(&format!("  Output Filters: {}\n", ds.output_filters.len()));
+                    }
                     if !ds.add_context.is_empty() {
                         out.push_str(&format!("  Context: {}\n", ds.add_context.len()));
                     }
                 }
 
+                if let Some(ref mem) = eval.wasm_memory {
+                    out.push_str(&format!(
+                        "WASM Memory: {} bytes heap, {} of {} bytes linear memory\n",
+                        mem.heap_bytes, mem.memory_bytes, mem.max_memory_bytes
+                    ));
+                }
+
                 if let Some(ref decision) = eval.final_decision {
                     out.push_str(&format!("Decision: {decision:?}\n"));
                 }
@@ -13,6 +13,7 @@ pub enum DecisionVerb {
     Block,
     Ask,
     Modify,
+    FilterOutput,
     AddContext,
 }
 
@@ -25,6 +26,7 @@ impl DecisionVerb {
             Self::Block,
             Self::Ask,
             Self::Modify,
+            Self::FilterOutput,
             Self::AddContext,
         ]
     }
@@ -37,6 +39,7 @@ impl DecisionVerb {
             "block" => Some(Self::Block),
             "ask" => Some(Self::Ask),
             "modify" => Some(Self::Modify),
+            "filter_output" => Some(Self::FilterOutput),
             "add_context" => Some(Self::AddContext),
             _ => None,
         }
@@ -50,6 +53,7 @@ impl DecisionVerb {
             Self::Block => "block",
             Self::Ask => "ask",
             Self::Modify => "modify",
+            Self::FilterOutput => "filter_output",
             Self::AddContext => "add_context",
         }
     }
@@ -62,6 +66,7 @@ impl DecisionVerb {
             Self::Block => "Block action (post-execution feedback)",
             Self::Ask => "Request user confirmation",
             Self::Modify => "Modify tool input before execution",
+            Self::FilterOutput => "Redact or replace tool output after execution",
             Self::AddContext => "Inject additional context",
         }
     }
@@ -77,7 +82,7 @@ impl DecisionEventMatrix {
     pub fn new() -> Self {
         let mut compatibility = HashMap::new();
 
-        // PreToolUse: Supports all decision types including Modify
+        // PreToolUse: Supports all pre-execution decision types including Modify
         compatibility.insert(
             "PreToolUse",
             vec![
@@ -105,13 +110,14 @@ impl DecisionEventMatrix {
             ],
         );
 
-        // PostToolUse: Block (feedback loop), context
+        // PostToolUse: Block (feedback loop), output filtering, context
         // NO Ask - tool already executed
         compatibility.insert(
             "PostToolUse",
             vec![
                 DecisionVerb::Halt,
                 DecisionVerb::Block,
+                DecisionVerb::FilterOutput,
                 DecisionVerb::AddContext,
             ],
         );
@@ -155,6 +161,12 @@ impl DecisionEventMatrix {
         Self { compatibility }
     }
 
+    /// Whether the matrix covers an event; events of other harnesses
+    /// (e.g. Cursor's beforeShellExecution) are not
+    pub fn knows_event(&self, event: &str) -> bool {
+        self.compatibility.contains_key(event)
+    }
+
     /// Check if a decision verb is compatible with an event
     pub fn is_compatible(&self, event: &str, verb: DecisionVerb) -> bool {
         self.compatibility
@@ -216,7 +228,7 @@ impl DecisionEventMatrix {
             ("Stop" | "SubagentStop", DecisionVerb::Ask) => {
                 format!("{event} events do not support 'ask' decisions. Use 'block' to prevent stopping.")
             }
-            ("PreCompact", DecisionVerb::Ask | DecisionVerb::Block | DecisionVerb::Deny | DecisionVerb::Halt | DecisionVerb::Modify) => {
+            ("PreCompact", DecisionVerb::Ask | DecisionVerb::Block | DecisionVerb::Deny | DecisionVerb::Halt | DecisionVerb::Modify | DecisionVerb::FilterOutput) => {
                 format!(
                     "PreCompact events only support 'add_context' for custom instructions. '{}' decisions are not supported.",
                     verb.rego_name()
@@ -227,6 +239,11 @@ impl DecisionEventMatrix {
                     "'modify' decisions are only supported for PreToolUse events. {event} events do not support tool input modification."
                 )
             }
+            (_, DecisionVerb::FilterOutput) => {
+                format!(
+                    "'filter_output' decisions are only supported for PostToolUse events. {event} events have no tool output to filter."
+                )
+            }
             _ => {
                 format!(
                     "'{}' decisions are not supported for {} events. Supported: {}",
@@ -257,8 +274,11 @@ mod tests {
     fn test_pre_tool_use_supports_all() {
         let matrix = DecisionEventMatrix::new();
 
-        // PreToolUse should support everything
-        for verb in DecisionVerb::all() {
+        // PreToolUse should support everything except post-execution output filtering
+        for verb in DecisionVerb::all()
+            .into_iter()
+            .filter(|verb| *verb != DecisionVerb::FilterOutput)
+        {
             assert!(
                 matrix.is_compatible("PreToolUse", verb),
                 "PreToolUse should support {verb:?}"
@@ -324,6 +344,19 @@ mod tests {
         assert!(!matrix.is_compatible("Stop", DecisionVerb::Modify));
     }
 
+    #[test]
+    fn test_filter_output_only_post_tool_use() {
+        let matrix = DecisionEventMatrix::new();
+
+        assert!(matrix.is_compatible("PostToolUse", DecisionVerb::FilterOutput));
+        assert!(!matrix.is_compatible("PreToolUse", DecisionVerb::FilterOutput));
+        assert!(!matrix.is_compatible("PermissionRequest", DecisionVerb::FilterOutput));
+        assert!(!matrix.is_compatible("UserPromptSubmit", DecisionVerb::FilterOutput));
+
+        let reason = matrix.incompatibility_reason("PreToolUse", DecisionVerb::FilterOutput);
+        assert!(reason.contains("only supported for PostToolUse"));
+    }
+
     #[test]
     fn test_all_events_have_entries() {
         let matrix = DecisionEventMatrix::new();
@@ -0,0 +1,223 @@
+//! Automatic fixes for validation issues
+//!
+//! Each fix remediates the issue reported by the validation rule with the
+//! same id. Fixes are pure text transformations; writing results back is
+//! left to the caller.
+
+use super::rules::{metadata_key_rank, package_metadata_block};
+use super::PolicyContent;
+
+/// Trait for automatic fixes
+pub trait PolicyFix: Send + Sync {
+    /// Apply the fix, returning the new content if anything changed
+    fn apply(&self, content: &str) -> Option<String>;
+
+    /// Id of the validation rule this fix remediates
+    fn rule_id(&self) -> &'static str;
+
+    /// Fix description
+    fn description(&self) -> &'static str;
+}
+
+/// Applies all registered fixes to policies
+pub struct PolicyFixer {
+    fixes: Vec<Box<dyn PolicyFix>>,
+}
+
+/// Outcome of fixing a single policy
+#[derive(Debug)]
+pub struct FixResult {
+    /// Content after all fixes were applied
+    pub content: String,
+    /// Rule ids of the fixes that changed the content, in order applied
+    pub applied: Vec<&'static str>,
+}
+
+impl PolicyFixer {
+    /// Create fixer with default Cupcake fixes
+    pub fn new() -> Self {
+        let fixes: Vec<Box<dyn PolicyFix>> = vec![
+            Box::new(TrailingWhitespaceFix),
+            Box::new(MetadataScopeFix),
+            Box::new(MetadataKeyOrderFix),
+            Box::new(RegoV1ImportFix),
+        ];
+
+        Self { fixes }
+    }
+
+    /// Fix a single policy, returning `None` if nothing needed fixing
+    pub fn fix_policy(&self, policy: &PolicyContent) -> Option<FixResult> {
+        let mut content = policy.content.clone();
+        let mut applied = Vec::new();
+
+        for fix in &self.fixes {
+            if let Some(fixed) = fix.apply(&content) {
+                content = fixed;
+                applied.push(fix.rule_id());
+            }
+        }
+
+        (!applied.is_empty()).then_some(FixResult { content, applied })
+    }
+}
+
+impl Default for PolicyFixer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Line endings of a policy, kept so fixes don't rewrite CRLF files as LF
+#[derive(Clone, Copy)]
+struct Layout {
+    newline: &'static str,
+    trailing_newline: bool,
+}
+
+/// Split content into lines, remembering its line endings
+fn split_lines(content: &str) -> (Vec<String>, Layout) {
+    (
+        content.lines().map(String::from).collect(),
+        Layout {
+            newline: if content.contains("\r\n") {
+                "\r\n"
+            } else {
+                "\n"
+            },
+            trailing_newline: content.ends_with('\n'),
+        },
+    )
+}
+
+fn join_lines(lines: &[String], layout: Layout) -> String {
+    let mut out = lines.join(layout.newline);
+    if layout.trailing_newline {
+        out.push_str(layout.newline);
+    }
+    out
+}
+
+/// Fix: strip trailing whitespace from every line
+pub struct TrailingWhitespaceFix;
+
+impl PolicyFix for TrailingWhitespaceFix {
+    fn apply(&self, content: &str) -> Option<String> {
+        let (lines, layout) = split_lines(content);
+        let trimmed: Vec<String> = lines.iter().map(|l| l.trim_end().to_string()).collect();
+        let fixed = join_lines(&trimmed, layout);
+        (fixed != content).then_some(fixed)
+    }
+
+    fn rule_id(&self) -> &'static str {
+        "trailing-whitespace"
+    }
+
+    fn description(&self) -> &'static str {
+        "Remove trailing whitespace"
+    }
+}
+
+/// Fix: add `scope: package` to package metadata that lacks a scope
+pub struct MetadataScopeFix;
+
+impl PolicyFix for MetadataScopeFix {
+    fn apply(&self, content: &str) -> Option<String> {
+        let (mut lines, layout) = split_lines(content);
+        let block = package_metadata_block(&lines)?;
+        if block.keys.iter().any(|k| k.name == "scope") {
+            return None;
+        }
+
+        lines.insert(block.start + 1, "# scope: package".to_string());
+        Some(join_lines(&lines, layout))
+    }
+
+    fn rule_id(&self) -> &'static str {
+        "metadata-scope"
+    }
+
+    fn description(&self) -> &'static str {
+        "Add 'scope: package' to package metadata"
+    }
+}
+
+/// Fix: reorder top-level metadata keys into the conventional order
+pub struct MetadataKeyOrderFix;
+
+impl PolicyFix for MetadataKeyOrderFix {
+    fn apply(&self, content: &str) -> Option<String> {
+        let (lines, layout) = split_lines(content);
+        let block = package_metadata_block(&lines)?;
+
+        let mut keys: Vec<_> = block.keys.iter().collect();
+        keys.sort_by_key(|k| metadata_key_rank(&k.name));
+        if keys
+            .iter()
+            .zip(&block.keys)
+            .all(|(sorted, original)| sorted.lines == original.lines)
+        {
+            return None;
+        }
+
+        // Anything between the marker and the first key stays in place
+        let first_key = block.keys.first().map_or(block.end, |k| k.lines.start);
+        let mut fixed: Vec<String> = lines[..first_key].to_vec();
+        for key in keys {
+            fixed.extend_from_slice(&lines[key.lines.clone()]);
+        }
+        fixed.extend_from_slice(&lines[block.end..]);
+
+        Some(join_lines(&fixed, layout))
+    }
+
+    fn rule_id(&self) -> &'static str {
+        "metadata-key-order"
+    }
+
+    fn description(&self) -> &'static str {
+        "Reorder metadata keys"
+    }
+}
+
+/// Fix: add `import rego.v1` after the package declaration and its imports
+pub struct RegoV1ImportFix;
+
+impl PolicyFix for RegoV1ImportFix {
+    fn apply(&self, content: &str) -> Option<String> {
+        let (mut lines, layout) = split_lines(content);
+        if lines.iter().any(|l| l.trim() == "import rego.v1") {
+            return None;
+        }
+        let package = lines
+            .iter()
+            .position(|l| l.trim_start().starts_with("package "))?;
+
+        // Keep imports together: append to an existing import group if there is one
+        match lines[package + 1..]
+            .iter()
+            .rposition(|l| l.trim_start().starts_with("import "))
+        {
+            Some(last_import) => {
+                lines.insert(package + 1 + last_import + 1, "import rego.v1".to_string());
+            }
+            None => {
+                lines.insert(package + 1, String::new());
+                lines.insert(package + 2, "import rego.v1".to_string());
+                if lines.get(package + 3).is_some_and(|l| !l.trim().is_empty()) {
+                    lines.insert(package + 3, String::new());
+                }
+            }
+        }
+
+        Some(join_lines(&lines, layout))
+    }
+
+    fn rule_id(&self) -> &'static str {
+        "rego-v1-import"
+    }
+
+    fn description(&self) -> &'static str {
+        "Add 'import rego.v1'"
+    }
+}
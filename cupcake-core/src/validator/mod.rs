@@ -8,6 +8,8 @@ use std::path::{Path, PathBuf};
 use tracing::{debug, info};
 
 pub mod decision_event_matrix;
+pub mod fixes;
+pub mod opa_check;
 pub mod rules;
 
 #[cfg(test)]
@@ -143,6 +145,10 @@ impl PolicyValidator {
             Box::new(RoutingMetadataRule),
             Box::new(IncrementalRuleGroupingRule),
             Box::new(DecisionEventCompatibilityRule),
+            Box::new(RegoV1ImportRule),
+            Box::new(MetadataScopeRule),
+            Box::new(MetadataKeyOrderRule),
+            Box::new(TrailingWhitespaceRule),
         ];
 
         Self { rules }
@@ -182,22 +188,47 @@ impl PolicyValidator {
     pub fn validate_policies(&self, policies: &[PolicyContent]) -> ValidationResult {
         info!("Validating {} policies", policies.len());
 
-        let mut results = Vec::new();
-        let mut total_errors = 0;
-        let mut total_warnings = 0;
+        let mut result = ValidationResult {
+            policies: policies.iter().map(|p| self.validate_policy(p)).collect(),
+            total_errors: 0,
+            total_warnings: 0,
+        };
 
-        for policy in policies {
-            let result = self.validate_policy(policy);
-            total_errors += result.error_count;
-            total_warnings += result.warning_count;
-            results.push(result);
+        // Project-wide checks that need every policy at once
+        result.add_issues(check_unique_rule_ids(policies));
+
+        result
+    }
+
+    /// Description of the rule behind an issue's `rule_id`
+    pub fn describe_rule(&self, rule_id: &str) -> Option<&'static str> {
+        match rule_id {
+            "unique-rule-id" => Some("Literal rule_ids must be unique across policies"),
+            "opa-check" => Some("Policy must compile with OPA"),
+            _ => self
+                .rules
+                .iter()
+                .find(|rule| rule.rule_id() == rule_id)
+                .map(|rule| rule.description()),
         }
+    }
+}
 
-        ValidationResult {
-            policies: results,
-            total_errors,
-            total_warnings,
+impl ValidationResult {
+    /// Add issues keyed by policy index, updating per-policy and total counts
+    pub fn add_issues(&mut self, issues: Vec<(usize, ValidationIssue)>) {
+        for (index, issue) in issues {
+            let result = &mut self.policies[index];
+            match issue.severity {
+                Severity::Error => result.error_count += 1,
+                Severity::Warning => result.warning_count += 1,
+                Severity::Info => {}
+            }
+            result.issues.push(issue);
         }
+
+        self.total_errors = self.policies.iter().map(|p| p.error_count).sum();
+        self.total_warnings = self.policies.iter().map(|p| p.warning_count).sum();
     }
 }
 
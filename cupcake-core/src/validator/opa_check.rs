@@ -0,0 +1,165 @@
+//! OPA compile check - runs `opa check` over policies at validate time
+//!
+//! Catches parse, compile and type errors before the first evaluation, and
+//! maps OPA's diagnostics onto the policies they belong to.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::debug;
+
+use super::{PolicyContent, Severity, ValidationIssue};
+use crate::engine::compiler::find_opa_binary;
+
+/// `opa check --format json` output
+#[derive(Debug, Deserialize)]
+struct OpaCheckOutput {
+    #[serde(default)]
+    errors: Vec<OpaError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpaError {
+    message: String,
+    #[serde(default)]
+    code: Option<String>,
+    #[serde(default)]
+    location: Option<OpaLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpaLocation {
+    file: String,
+    row: usize,
+}
+
+/// Run `opa check` over the given policies
+///
+/// In strict mode OPA also reports unused variables and imports, duplicate
+/// imports and deprecated builtins. Returns issues keyed by the index of the
+/// policy they belong to; diagnostics without a matching file are attached to
+/// the first policy.
+pub fn check_policies(
+    policies: &[PolicyContent],
+    opa_path_override: Option<PathBuf>,
+    strict: bool,
+) -> Result<Vec<(usize, ValidationIssue)>> {
+    if policies.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let opa_path = find_opa_binary(opa_path_override)?;
+    let mut opa_cmd = Command::new(&opa_path);
+    opa_cmd.arg("check").arg("--format").arg("json");
+    if strict {
+        opa_cmd.arg("--strict");
+    }
+    for policy in policies {
+        opa_cmd.arg(&policy.path);
+    }
+
+    debug!("OPA check command: {:?}", opa_cmd);
+    let output = opa_cmd
+        .output()
+        .context("Failed to execute OPA command. Is the OPA CLI installed and in your PATH?")?;
+
+    if output.status.success() {
+        return Ok(Vec::new());
+    }
+
+    // OPA writes the JSON report to stdout, but older versions used stderr
+    let report = [&output.stdout, &output.stderr]
+        .into_iter()
+        .find_map(|stream| serde_json::from_slice::<OpaCheckOutput>(stream).ok());
+    let Some(report) = report else {
+        bail!(
+            "OPA check failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    };
+
+    Ok(report
+        .errors
+        .into_iter()
+        .map(|error| to_issue(policies, error))
+        .collect())
+}
+
+fn to_issue(policies: &[PolicyContent], error: OpaError) -> (usize, ValidationIssue) {
+    let index = error
+        .location
+        .as_ref()
+        .and_then(|loc| {
+            policies
+                .iter()
+                .position(|p| same_file(&p.path, Path::new(&loc.file)))
+        })
+        .unwrap_or(0);
+
+    let message = match &error.code {
+        Some(code) => format!("{code}: {}", error.message),
+        None => error.message,
+    };
+
+    (
+        index,
+        ValidationIssue {
+            severity: Severity::Error,
+            rule_id: "opa-check",
+            message,
+            line: error.location.map(|loc| loc.row),
+        },
+    )
+}
+
+/// Compare paths as given, falling back to canonical forms
+fn same_file(a: &Path, b: &Path) -> bool {
+    a == b
+        || matches!(
+            (a.canonicalize(), b.canonicalize()),
+            (Ok(a), Ok(b)) if a == b
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(path: &str) -> PolicyContent {
+        PolicyContent::from_content(
+            PathBuf::from(path),
+            "package cupcake.policies.test".to_string(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_opa_errors_map_to_policies() {
+        let policies = vec![policy("a.rego"), policy("b.rego")];
+        let report: OpaCheckOutput = serde_json::from_str(
+            r#"{"errors": [
+                {"message": "var x is unsafe", "code": "rego_unsafe_var_error",
+                 "location": {"file": "b.rego", "row": 7, "col": 3}},
+                {"message": "something went wrong"}
+            ]}"#,
+        )
+        .unwrap();
+
+        let issues: Vec<_> = report
+            .errors
+            .into_iter()
+            .map(|e| to_issue(&policies, e))
+            .collect();
+
+        assert_eq!(issues[0].0, 1);
+        assert_eq!(issues[0].1.line, Some(7));
+        assert_eq!(issues[0].1.severity, Severity::Error);
+        assert_eq!(
+            issues[0].1.message,
+            "rego_unsafe_var_error: var x is unsafe"
+        );
+        assert_eq!(issues[1].0, 0);
+        assert_eq!(issues[1].1.line, None);
+    }
+}
@@ -2,6 +2,8 @@
 
 use super::decision_event_matrix::{DecisionEventMatrix, DecisionVerb};
 use super::{PolicyContent, Severity, ValidationIssue, ValidationRule};
+use crate::engine::decision::is_docs_url;
+use crate::harness::types::HarnessType;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::collections::HashMap;
@@ -12,6 +14,11 @@ static OBJECT_MEMBERSHIP_PATTERN: Lazy<Regex> =
 
 static DECISION_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"decision\s*:=\s*\{").unwrap());
 
+static DECISION_FIELD_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#""(reason|severity|rule_id|docs_url|remediation)"\s*:\s*(?:"((?:[^"\\]|\\.)*)")?"#)
+        .unwrap()
+});
+
 static RULE_PATTERN: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"^([a-zA-Z_][a-zA-Z0-9_]*)\s+contains\s+").unwrap());
 
@@ -140,41 +147,51 @@ impl ValidationRule for DecisionStructureRule {
     fn check(&self, policy: &PolicyContent) -> Vec<ValidationIssue> {
         let mut issues = Vec::new();
 
-        for (i, line) in policy.lines.iter().enumerate() {
-            if DECISION_PATTERN.is_match(line) {
-                // Check next few lines for required fields
-                let mut has_reason = false;
-                let mut has_rule_id = false;
-
-                // Look ahead up to 10 lines for the closing brace
-                for j in i..std::cmp::min(i + 10, policy.lines.len()) {
-                    let check_line = &policy.lines[j];
-                    if check_line.contains("\"reason\"") {
-                        has_reason = true;
-                    }
-                    if check_line.contains("\"rule_id\"") {
-                        has_rule_id = true;
-                    }
-                    if check_line.contains('}') {
-                        break;
-                    }
+        for decision in decision_literals(policy) {
+            for field in DECISION_FIELDS {
+                if !decision.fields.contains_key(*field) {
+                    issues.push(ValidationIssue {
+                        severity: Severity::Warning,
+                        rule_id: self.rule_id(),
+                        message: format!("Decision object should include '{field}' field"),
+                        line: Some(decision.line + 1),
+                    });
                 }
+            }
 
-                if !has_reason {
+            // Only literal severities can be checked; computed values are left alone
+            if let Some(Some(severity)) = decision.fields.get("severity") {
+                if !VALID_SEVERITIES.contains(&severity.to_uppercase().as_str()) {
                     issues.push(ValidationIssue {
-                        severity: Severity::Warning,
+                        severity: Severity::Error,
                         rule_id: self.rule_id(),
-                        message: "Decision object should include 'reason' field".to_string(),
-                        line: Some(i + 1),
+                        message: format!(
+                            "Invalid decision severity '{severity}'. Expected one of: {}",
+                            VALID_SEVERITIES.join(", ")
+                        ),
+                        line: Some(decision.line + 1),
                     });
                 }
+            }
 
-                if !has_rule_id {
+            // Optional guidance fields are shown to users, so literals must be usable
+            if let Some(Some(url)) = decision.fields.get("docs_url") {
+                if !is_docs_url(url) {
+                    issues.push(ValidationIssue {
+                        severity: Severity::Error,
+                        rule_id: self.rule_id(),
+                        message: format!("Invalid docs_url '{url}'. Expected an http(s) URL"),
+                        line: Some(decision.line + 1),
+                    });
+                }
+            }
+            if let Some(Some(remediation)) = decision.fields.get("remediation") {
+                if remediation.trim().is_empty() {
                     issues.push(ValidationIssue {
                         severity: Severity::Warning,
                         rule_id: self.rule_id(),
-                        message: "Decision object should include 'rule_id' field".to_string(),
-                        line: Some(i + 1),
+                        message: "Decision remediation is empty".to_string(),
+                        line: Some(decision.line + 1),
                     });
                 }
             }
@@ -184,6 +201,377 @@ impl ValidationRule for DecisionStructureRule {
     }
 }
 
+/// Fields every decision object must carry
+const DECISION_FIELDS: &[&str] = &["reason", "severity", "rule_id"];
+
+/// Severity values recognized by decision synthesis
+const VALID_SEVERITIES: &[&str] = &["LOW", "MEDIUM", "HIGH", "CRITICAL"];
+
+/// A `decision := {...}` object literal found in a policy
+pub(crate) struct DecisionLiteral {
+    /// Zero-based line where the literal starts
+    pub line: usize,
+    /// Known fields present in the literal, with their value when it is a string literal
+    pub fields: HashMap<String, Option<String>>,
+}
+
+/// Find decision object literals and the required fields they set
+///
+/// Each literal is read up to its matching closing brace, so multi-line
+/// objects with nested values are handled.
+pub(crate) fn decision_literals(policy: &PolicyContent) -> Vec<DecisionLiteral> {
+    let mut literals = Vec::new();
+
+    for (i, line) in policy.lines.iter().enumerate() {
+        let Some(start) = DECISION_PATTERN.find(line) else {
+            continue;
+        };
+
+        // Collect the object text, tracking brace depth outside of strings
+        let mut body = String::new();
+        let mut depth = 0usize;
+        let mut in_string = false;
+        let mut escaped = false;
+        'lines: for (j, text) in policy.lines[i..].iter().enumerate() {
+            let text = if j == 0 { &text[start.start()..] } else { text };
+            for c in text.chars() {
+                body.push(c);
+                if in_string {
+                    match c {
+                        _ if escaped => escaped = false,
+                        '\\' => escaped = true,
+                        '"' => in_string = false,
+                        _ => {}
+                    }
+                    continue;
+                }
+                match c {
+                    '"' => in_string = true,
+                    '{' => depth += 1,
+                    '}' => {
+                        depth = depth.saturating_sub(1);
+                        if depth == 0 {
+                            break 'lines;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            body.push('\n');
+        }
+
+        let mut fields = HashMap::new();
+        for captures in DECISION_FIELD_PATTERN.captures_iter(&body) {
+            fields
+                .entry(captures[1].to_string())
+                .or_insert_with(|| captures.get(2).map(|m| m.as_str().to_string()));
+        }
+
+        literals.push(DecisionLiteral { line: i, fields });
+    }
+
+    literals
+}
+
+/// Check that literal rule_ids are unique across policies
+///
+/// The same rule_id may appear in several rules of one package, and in the
+/// per-harness variants of a policy, so only different packages within the
+/// same harness directory are compared. Returns issues keyed by the index of
+/// the policy they belong to.
+pub fn check_unique_rule_ids(policies: &[PolicyContent]) -> Vec<(usize, ValidationIssue)> {
+    // (harness, rule_id) -> where it was first defined
+    let mut seen: HashMap<(Option<&str>, String), FirstDefinition> = HashMap::new();
+    let mut issues = Vec::new();
+
+    for (index, policy) in policies.iter().enumerate() {
+        let harness = harness_of(policy);
+        let package = policy.package_name.as_deref();
+
+        for decision in decision_literals(policy) {
+            let Some(Some(rule_id)) = decision.fields.get("rule_id") else {
+                continue;
+            };
+            let key = (harness, rule_id.clone());
+            match seen.get(&key) {
+                Some(first) if first.package != package => {
+                    issues.push((
+                        index,
+                        ValidationIssue {
+                            severity: Severity::Error,
+                            rule_id: "unique-rule-id",
+                            message: format!(
+                                "rule_id '{rule_id}' is already used by {} (line {})",
+                                policies[first.index].path.display(),
+                                first.line + 1
+                            ),
+                            line: Some(decision.line + 1),
+                        },
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    seen.insert(
+                        key,
+                        FirstDefinition {
+                            package,
+                            index,
+                            line: decision.line,
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    issues
+}
+
+/// Where a rule_id was first defined
+struct FirstDefinition<'a> {
+    package: Option<&'a str>,
+    index: usize,
+    line: usize,
+}
+
+/// Harness directory a policy lives under, if any
+fn harness_of(policy: &PolicyContent) -> Option<&str> {
+    policy
+        .path
+        .components()
+        .filter_map(|c| c.as_os_str().to_str())
+        .find(|name| {
+            [
+                HarnessType::ClaudeCode,
+                HarnessType::Cursor,
+                HarnessType::Factory,
+                HarnessType::OpenCode,
+                HarnessType::Cline,
+                HarnessType::Aider,
+                HarnessType::Goose,
+            ]
+            .iter()
+            .any(|h| h.policy_dir() == *name)
+        })
+}
+
+/// Rule: Policies should import rego.v1
+pub struct RegoV1ImportRule;
+
+impl ValidationRule for RegoV1ImportRule {
+    fn rule_id(&self) -> &'static str {
+        "rego-v1-import"
+    }
+
+    fn description(&self) -> &'static str {
+        "Policies should import rego.v1"
+    }
+
+    fn check(&self, policy: &PolicyContent) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        let Some(package_line) = package_line(policy) else {
+            return issues;
+        };
+
+        if !policy.lines.iter().any(|l| l.trim() == "import rego.v1") {
+            issues.push(ValidationIssue {
+                severity: Severity::Warning,
+                rule_id: self.rule_id(),
+                message: "Policy should include 'import rego.v1'".to_string(),
+                line: Some(package_line + 1),
+            });
+        }
+
+        issues
+    }
+}
+
+/// Rule: Package metadata should declare its scope
+pub struct MetadataScopeRule;
+
+impl ValidationRule for MetadataScopeRule {
+    fn rule_id(&self) -> &'static str {
+        "metadata-scope"
+    }
+
+    fn description(&self) -> &'static str {
+        "Metadata before the package declaration should declare scope: package"
+    }
+
+    fn check(&self, policy: &PolicyContent) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        if let Some(block) = package_metadata_block(&policy.lines) {
+            if !block.keys.iter().any(|k| k.name == "scope") {
+                issues.push(ValidationIssue {
+                    severity: Severity::Warning,
+                    rule_id: self.rule_id(),
+                    message: "Package metadata should declare 'scope: package'".to_string(),
+                    line: Some(block.start + 1),
+                });
+            }
+        }
+
+        issues
+    }
+}
+
+/// Rule: Metadata keys should follow the conventional order
+pub struct MetadataKeyOrderRule;
+
+impl ValidationRule for MetadataKeyOrderRule {
+    fn rule_id(&self) -> &'static str {
+        "metadata-key-order"
+    }
+
+    fn description(&self) -> &'static str {
+        "Metadata keys should be ordered scope, title, description, ..., custom"
+    }
+
+    fn check(&self, policy: &PolicyContent) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        if let Some(block) = package_metadata_block(&policy.lines) {
+            let ranks: Vec<usize> = block
+                .keys
+                .iter()
+                .map(|k| metadata_key_rank(&k.name))
+                .collect();
+            if ranks.windows(2).any(|w| w[0] > w[1]) {
+                issues.push(ValidationIssue {
+                    severity: Severity::Info,
+                    rule_id: self.rule_id(),
+                    message: format!(
+                        "Metadata keys should be ordered: {}",
+                        METADATA_KEY_ORDER.join(", ")
+                    ),
+                    line: Some(block.start + 1),
+                });
+            }
+        }
+
+        issues
+    }
+}
+
+/// Rule: Lines should not end with whitespace
+pub struct TrailingWhitespaceRule;
+
+impl ValidationRule for TrailingWhitespaceRule {
+    fn rule_id(&self) -> &'static str {
+        "trailing-whitespace"
+    }
+
+    fn description(&self) -> &'static str {
+        "Lines should not end with whitespace"
+    }
+
+    fn check(&self, policy: &PolicyContent) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        let lines: Vec<usize> = policy
+            .lines
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| l.len() != l.trim_end().len())
+            .map(|(i, _)| i)
+            .collect();
+
+        if let Some(first) = lines.first() {
+            issues.push(ValidationIssue {
+                severity: Severity::Info,
+                rule_id: self.rule_id(),
+                message: format!("{} line(s) have trailing whitespace", lines.len()),
+                line: Some(first + 1),
+            });
+        }
+
+        issues
+    }
+}
+
+/// Conventional order of top-level metadata keys (OPA annotation order)
+pub(crate) const METADATA_KEY_ORDER: &[&str] = &[
+    "scope",
+    "title",
+    "description",
+    "related_resources",
+    "authors",
+    "organizations",
+    "schemas",
+    "entrypoint",
+    "custom",
+];
+
+/// Sort rank of a metadata key; unknown keys go just before `custom`
+pub(crate) fn metadata_key_rank(name: &str) -> usize {
+    METADATA_KEY_ORDER
+        .iter()
+        .position(|k| *k == name)
+        .unwrap_or(METADATA_KEY_ORDER.len() - 1)
+}
+
+/// A top-level key in a METADATA block
+pub(crate) struct MetadataKey {
+    pub name: String,
+    /// Lines belonging to the key, including indented continuation lines
+    pub lines: std::ops::Range<usize>,
+}
+
+/// The METADATA block that precedes the package declaration
+pub(crate) struct MetadataBlock {
+    /// Line of the `# METADATA` marker
+    pub start: usize,
+    /// First line after the block
+    pub end: usize,
+    pub keys: Vec<MetadataKey>,
+}
+
+/// Locate the METADATA block before the package declaration, if any
+pub(crate) fn package_metadata_block(lines: &[String]) -> Option<MetadataBlock> {
+    let start = lines
+        .iter()
+        .take_while(|l| !l.trim_start().starts_with("package "))
+        .position(|l| l.trim() == "# METADATA")?;
+
+    let mut keys: Vec<MetadataKey> = Vec::new();
+    let mut end = start + 1;
+    while end < lines.len() {
+        let Some(comment) = lines[end].trim().strip_prefix('#') else {
+            break;
+        };
+        let yaml = comment.strip_prefix(' ').unwrap_or(comment);
+        let is_key = !yaml.starts_with([' ', '-', '#'])
+            && yaml.split_once(':').is_some_and(|(k, _)| {
+                !k.is_empty() && k.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            });
+        if is_key {
+            let name = yaml
+                .split_once(':')
+                .map(|(k, _)| k.to_string())
+                .unwrap_or_default();
+            keys.push(MetadataKey {
+                name,
+                lines: end..end + 1,
+            });
+        } else if let Some(key) = keys.last_mut() {
+            key.lines.end = end + 1;
+        }
+        end += 1;
+    }
+
+    Some(MetadataBlock { start, end, keys })
+}
+
+/// Line of the package declaration, if any
+fn package_line(policy: &PolicyContent) -> Option<usize> {
+    policy
+        .lines
+        .iter()
+        .position(|l| l.trim_start().starts_with("package "))
+}
+
 /// Rule: Routing metadata should be present
 pub struct RoutingMetadataRule;
 
@@ -358,8 +746,14 @@ impl ValidationRule for DecisionEventCompatibilityRule {
             }
         }
 
-        // Check each verb against each required event
-        for event in &routing.required_events {
+        // Check each verb against each required event. Events outside the
+        // matrix belong to other harnesses, whose capabilities are enforced at
+        // evaluation time instead
+        for event in routing
+            .required_events
+            .iter()
+            .filter(|event| matrix.knows_event(event))
+        {
             for (verb, line_numbers) in &found_verbs {
                 let line = line_numbers.first().copied();
 
@@ -1,5 +1,7 @@
 //! Tests for the policy validator
 
+#[cfg(test)]
+use super::fixes::{self, PolicyFix};
 #[cfg(test)]
 use super::rules::*;
 #[cfg(test)]
@@ -292,6 +294,32 @@ block contains decision if {
     );
 }
 
+#[test]
+fn test_other_harness_events_not_checked() {
+    // Cursor's events aren't in the matrix - a deny there is not an error
+    let content = r#"# METADATA
+# scope: package
+# custom:
+#   routing:
+#     required_events: ["beforeShellExecution"]
+package cupcake.policies.test
+
+import rego.v1
+
+deny contains decision if {
+    contains(input.command, "rm -rf")
+    decision := {
+        "reason": "Test deny",
+        "severity": "HIGH",
+        "rule_id": "TEST-001"
+    }
+}"#;
+
+    let policy = create_test_policy(content);
+    let issues = DecisionEventCompatibilityRule.check(&policy);
+    assert!(issues.is_empty(), "Unexpected issues: {issues:?}");
+}
+
 #[test]
 fn test_block_posttooluse_no_warning() {
     // Using 'block' for PostToolUse should NOT emit a warning (correct usage)
@@ -389,3 +417,246 @@ deny contains decision if {
         "Message should mention incompatibility"
     );
 }
+
+#[test]
+fn test_decision_structure_invalid_severity() {
+    let content = r#"package cupcake.policies.test
+
+import rego.v1
+
+deny contains decision if {
+true
+decision := {
+    "rule_id": "TEST-001",
+    "reason": "test",
+    "severity": "URGENT"
+}
+}
+
+ask contains decision if {
+true
+decision := {"rule_id": "TEST-002", "reason": "test", "severity": "medium"}
+}"#;
+
+    let policy = create_test_policy(content);
+    let rule = DecisionStructureRule;
+    let issues = rule.check(&policy);
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].severity, Severity::Error);
+    assert!(issues[0].message.contains("URGENT"));
+    assert_eq!(issues[0].line, Some(7));
+}
+
+#[test]
+fn test_decision_structure_multiline_object() {
+    // Fields beyond the first lines of a large object are still found
+    let content = r#"package cupcake.policies.test
+
+import rego.v1
+
+modify contains decision if {
+true
+decision := {
+    "rule_id": "TEST-001",
+    "updated_input": {
+        "command": "echo '{ not a brace }'",
+        "a": 1,
+        "b": 2,
+        "c": 3,
+        "d": 4,
+        "e": 5
+    },
+    "priority": 80,
+    "reason": concat(" ", ["Sanitized", input.tool_name])
+}
+}"#;
+
+    let policy = create_test_policy(content);
+    let rule = DecisionStructureRule;
+    let issues = rule.check(&policy);
+
+    assert_eq!(issues.len(), 1);
+    assert!(issues[0].message.contains("severity"));
+}
+
+#[test]
+fn test_decision_structure_guidance_fields() {
+    let content = r#"package cupcake.policies.test
+
+import rego.v1
+
+deny contains decision if {
+decision := {"rule_id": "T-1", "reason": "r", "severity": "HIGH", "docs_url": "https://wiki.example.com/rm", "remediation": "Use trash"}
+}
+
+deny contains decision if {
+decision := {"rule_id": "T-2", "reason": "r", "severity": "HIGH", "docs_url": "wiki/rm", "remediation": " "}
+}"#;
+
+    let policy = create_test_policy(content);
+    let issues = DecisionStructureRule.check(&policy);
+
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].severity, Severity::Error);
+    assert!(issues[0].message.contains("'wiki/rm'"));
+    assert_eq!(issues[0].line, Some(10));
+    assert_eq!(issues[1].severity, Severity::Warning);
+    assert!(issues[1].message.contains("remediation"));
+}
+
+#[test]
+fn test_duplicate_rule_ids_across_policies() {
+    let first = PolicyContent::from_content(
+        PathBuf::from(".cupcake/policies/claude/first.rego"),
+        r#"package cupcake.policies.first
+
+deny contains decision if {
+decision := {"rule_id": "DUP-001", "reason": "a", "severity": "HIGH"}
+}
+
+deny contains decision if {
+decision := {"rule_id": "DUP-001", "reason": "b", "severity": "HIGH"}
+}"#
+        .to_string(),
+    )
+    .unwrap();
+    let second = PolicyContent::from_content(
+        PathBuf::from(".cupcake/policies/claude/second.rego"),
+        r#"package cupcake.policies.second
+
+deny contains decision if {
+decision := {"rule_id": "DUP-001", "reason": "c", "severity": "HIGH"}
+}"#
+        .to_string(),
+    )
+    .unwrap();
+    // Another harness's variant of the policy may reuse the rule_id
+    let cursor = PolicyContent::from_content(
+        PathBuf::from(".cupcake/policies/cursor/second.rego"),
+        r#"package cupcake.policies.cursor_second
+
+deny contains decision if {
+decision := {"rule_id": "DUP-001", "reason": "d", "severity": "HIGH"}
+}"#
+        .to_string(),
+    )
+    .unwrap();
+
+    let validator = PolicyValidator::new();
+    let result = validator.validate_policies(&[first, second, cursor]);
+
+    let duplicates: Vec<_> = result
+        .policies
+        .iter()
+        .flat_map(|p| p.issues.iter().map(move |i| (&p.path, i)))
+        .filter(|(_, i)| i.rule_id == "unique-rule-id")
+        .collect();
+    assert_eq!(duplicates.len(), 1);
+    assert!(duplicates[0].0.ends_with("claude/second.rego"));
+    assert_eq!(duplicates[0].1.severity, Severity::Error);
+    assert!(duplicates[0].1.message.contains("DUP-001"));
+    assert!(result.total_errors >= 1);
+}
+
+#[test]
+fn test_rego_v1_import_and_scope_rules() {
+    let content = r#"# METADATA
+# title: Test Policy
+package cupcake.policies.test
+
+deny contains decision if {
+decision := {"reason": "test", "rule_id": "TEST-001", "severity": "HIGH"}
+}"#;
+
+    let policy = create_test_policy(content);
+    assert_eq!(RegoV1ImportRule.check(&policy).len(), 1);
+    let scope_issues = MetadataScopeRule.check(&policy);
+    assert_eq!(scope_issues.len(), 1);
+    assert_eq!(scope_issues[0].line, Some(1));
+}
+
+#[test]
+fn test_metadata_key_order_and_whitespace_rules() {
+    let content = "# METADATA\n# custom:\n#   routing:\n#     required_events: [\"PreToolUse\"]\n# scope: package\npackage cupcake.policies.test  \n\nimport rego.v1\t\n";
+
+    let policy = create_test_policy(content);
+    let order = MetadataKeyOrderRule.check(&policy);
+    assert_eq!(order.len(), 1);
+    assert_eq!(order[0].severity, Severity::Info);
+    let whitespace = TrailingWhitespaceRule.check(&policy);
+    assert_eq!(whitespace.len(), 1);
+    assert_eq!(whitespace[0].line, Some(6));
+    assert!(whitespace[0].message.starts_with("2 line(s)"));
+}
+
+#[test]
+fn test_fixer_remediates_issues() {
+    let content = "# METADATA\n# custom:\n#   routing:\n#     required_events: [\"PreToolUse\"]\n#     required_tools: [\"Bash\"]\n# title: Test Policy   \npackage cupcake.policies.test\n\ndeny contains decision if {\n    true \n    decision := {\"reason\": \"test\", \"rule_id\": \"TEST-001\", \"severity\": \"HIGH\"}\n}\n";
+
+    let policy = create_test_policy(content);
+    let fixed = fixes::PolicyFixer::new().fix_policy(&policy).unwrap();
+
+    assert_eq!(
+        fixed.applied,
+        vec![
+            "trailing-whitespace",
+            "metadata-scope",
+            "metadata-key-order",
+            "rego-v1-import"
+        ]
+    );
+    assert_eq!(
+        fixed.content,
+        "# METADATA\n# scope: package\n# title: Test Policy\n# custom:\n#   routing:\n#     required_events: [\"PreToolUse\"]\n#     required_tools: [\"Bash\"]\npackage cupcake.policies.test\n\nimport rego.v1\n\ndeny contains decision if {\n    true\n    decision := {\"reason\": \"test\", \"rule_id\": \"TEST-001\", \"severity\": \"HIGH\"}\n}\n"
+    );
+
+    // Fixed content passes the rules the fixes target and is stable
+    let refixed = create_test_policy(&fixed.content);
+    let result = PolicyValidator::new().validate_policy(&refixed);
+    assert_eq!(result.error_count, 0);
+    assert_eq!(result.warning_count, 0);
+    assert!(result.issues.is_empty());
+    assert!(fixes::PolicyFixer::new().fix_policy(&refixed).is_none());
+}
+
+#[test]
+fn test_fixer_keeps_crlf_line_endings() {
+    // Policies checked out on Windows with autocrlf parse and fix without turning into LF
+    let content =
+        "# METADATA\r\n# title: Test   \r\npackage cupcake.policies.test\r\n\r\nimport rego.v1\r\n";
+
+    let policy = create_test_policy(content);
+    assert_eq!(
+        policy.package_name.as_deref(),
+        Some("cupcake.policies.test")
+    );
+    let fixed = fixes::PolicyFixer::new().fix_policy(&policy).unwrap();
+    assert_eq!(
+        fixed.content,
+        "# METADATA\r\n# scope: package\r\n# title: Test\r\npackage cupcake.policies.test\r\n\r\nimport rego.v1\r\n"
+    );
+}
+
+#[test]
+fn test_rego_v1_import_fix_joins_existing_imports() {
+    let content = "package cupcake.policies.test\n\nimport data.cupcake.system.paths\n\ndeny contains decision if {\n    true\n}\n";
+
+    let fixed = fixes::RegoV1ImportFix.apply(content).unwrap();
+    assert_eq!(
+        fixed,
+        "package cupcake.policies.test\n\nimport data.cupcake.system.paths\nimport rego.v1\n\ndeny contains decision if {\n    true\n}\n"
+    );
+}
+
+#[test]
+fn test_describe_rule() {
+    let validator = PolicyValidator::new();
+    assert_eq!(
+        validator.describe_rule("package-declaration"),
+        Some("Policy must have a valid package declaration")
+    );
+    assert!(validator.describe_rule("opa-check").is_some());
+    assert!(validator.describe_rule("unique-rule-id").is_some());
+    assert_eq!(validator.describe_rule("no-such-rule"), None);
+}
@@ -71,6 +71,7 @@ mod tests {
             event_type: "PreToolUse".to_string(),
             tool_name: Some("Bash".to_string()),
             event_payload: serde_json::json!({"command": "ls"}),
+            transcript: None,
         };
 
         let result = backend.evaluate(input).await.unwrap();
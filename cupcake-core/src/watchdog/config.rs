@@ -14,7 +14,9 @@
 //! .cupcake/watchdog/
 //! ├── config.json   # Backend, model, timeout settings
 //! ├── system.txt    # Custom system prompt (optional)
-//! └── user.txt      # User message template with {{event}} (optional)
+//! ├── user.txt      # User message template with {{event}} (optional)
+//! ├── stop_system.txt # System prompt for Stop/SubagentStop reviews (optional)
+//! └── stop_user.txt # Stop template with {{event}} and {{transcript}} (optional)
 //! ```
 //!
 //! ## Rulebook Syntax
@@ -130,6 +132,10 @@ pub struct WatchdogConfig {
     #[serde(default)]
     pub dry_run: bool,
 
+    /// How much of the transcript end (in bytes) Stop evaluations read
+    #[serde(default = "default_transcript_tail_bytes")]
+    pub transcript_tail_bytes: u64,
+
     /// OpenRouter-specific configuration
     #[serde(default)]
     pub openrouter: Option<OpenRouterConfig>,
@@ -143,6 +149,7 @@ impl Default for WatchdogConfig {
             timeout_seconds: default_timeout(),
             on_error: default_on_error(),
             dry_run: false,
+            transcript_tail_bytes: default_transcript_tail_bytes(),
             openrouter: None,
         }
     }
@@ -160,6 +167,10 @@ fn default_on_error() -> String {
     "allow".to_string()
 }
 
+fn default_transcript_tail_bytes() -> u64 {
+    64 * 1024
+}
+
 /// OpenRouter backend configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OpenRouterConfig {
@@ -309,6 +320,10 @@ pub struct WatchdogDirConfig {
     #[serde(default = "default_api_key_env")]
     pub api_key_env: String,
 
+    /// Bytes from the end of the transcript read for Stop evaluations
+    #[serde(default = "default_transcript_tail_bytes")]
+    pub transcript_tail_bytes: u64,
+
     /// Rules context configuration for injecting file contents into prompts
     /// Uses camelCase in JSON: `rulesContext`
     #[serde(default, rename = "rulesContext")]
@@ -323,6 +338,7 @@ impl Default for WatchdogDirConfig {
             timeout_seconds: default_timeout(),
             on_error: default_on_error(),
             api_key_env: default_api_key_env(),
+            transcript_tail_bytes: default_transcript_tail_bytes(),
             rules_context: None,
         }
     }
@@ -408,6 +424,7 @@ impl WatchdogDirConfig {
             timeout_seconds: self.timeout_seconds,
             on_error: self.on_error,
             dry_run: false, // Set from CLI flag, not file config
+            transcript_tail_bytes: self.transcript_tail_bytes,
             openrouter: Some(OpenRouterConfig {
                 model: self.model,
                 api_key_env: self.api_key_env,
@@ -25,10 +25,15 @@
 //!     decision := {"rule_id": "WATCHDOG-001", "reason": input.signals.watchdog.reasoning}
 //! }
 //! ```
+//!
+//! That implicit verdict covers pre-action events only. Any policy can request one
+//! with `required_watchdog: true` in its routing metadata and read it from
+//! `input.watchdog.verdict`.
 
 pub mod backend;
 pub mod config;
 pub mod prompts;
+pub mod transcript;
 pub mod types;
 
 #[cfg(feature = "watchdog")]
@@ -285,7 +290,33 @@ impl Watchdog {
             event_type,
             tool_name,
             event_payload: event.clone(),
+            transcript: None,
+        }
+    }
+
+    /// Create WatchdogInput from an event, attaching the transcript tail on
+    /// Stop and SubagentStop. A relative `transcript_path` resolves against
+    /// `working_dir`
+    pub fn input_with_context(
+        &self,
+        event: &serde_json::Value,
+        working_dir: &Path,
+    ) -> WatchdogInput {
+        let mut input = Self::input_from_event(event);
+        if !transcript::is_stop_event(&input.event_type) {
+            return input;
+        }
+
+        let Some(path) = event.get("transcript_path").and_then(|p| p.as_str()) else {
+            debug!("{} event has no transcript_path", input.event_type);
+            return input;
+        };
+        let path = working_dir.join(path);
+        match transcript::read_tail(&path, self.config.transcript_tail_bytes) {
+            Ok(tail) => input.transcript = Some(tail),
+            Err(e) => warn!("Failed to read transcript {}: {}", path.display(), e),
         }
+        input
     }
 }
 
@@ -300,6 +331,33 @@ mod tests {
         assert!(!watchdog.is_enabled());
     }
 
+    #[test]
+    fn test_input_with_context_reads_transcript_on_stop() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("session.jsonl"),
+            r#"{"type":"user","message":{"content":"Add a test"}}"#,
+        )
+        .unwrap();
+        let watchdog = Watchdog::new(WatchdogConfig::default()).unwrap();
+
+        let stop = serde_json::json!({
+            "hook_event_name": "SubagentStop",
+            "transcript_path": "session.jsonl"
+        });
+        let input = watchdog.input_with_context(&stop, temp.path());
+        assert_eq!(input.transcript.as_deref(), Some("USER: Add a test"));
+
+        let pre = serde_json::json!({
+            "hook_event_name": "PreToolUse",
+            "transcript_path": "session.jsonl"
+        });
+        assert!(watchdog
+            .input_with_context(&pre, temp.path())
+            .transcript
+            .is_none());
+    }
+
     #[test]
     fn test_input_from_event() {
         let event = serde_json::json!({
@@ -324,6 +382,7 @@ mod tests {
             event_type: "PreToolUse".to_string(),
             tool_name: Some("Bash".to_string()),
             event_payload: serde_json::json!({}),
+            transcript: None,
         };
 
         let output = watchdog.evaluate(input).await;
@@ -11,6 +11,7 @@ use tracing::{debug, info, warn};
 use super::backend::WatchdogBackend;
 use super::config::OpenRouterConfig;
 use super::prompts::WatchdogPrompts;
+use super::transcript::is_stop_event;
 use super::types::{WatchdogInput, WatchdogOutput};
 
 /// OpenRouter API backend
@@ -82,11 +83,19 @@ impl OpenRouterBackend {
 
     /// Build the user message using the template
     fn build_user_message(&self, input: &WatchdogInput) -> String {
+        if is_stop_event(&input.event_type) {
+            return self
+                .prompts
+                .render_stop_message(&input.event_payload, input.transcript.as_deref());
+        }
         self.prompts.render_user_message(&input.event_payload)
     }
 
     /// Get the system prompt
-    fn system_prompt(&self) -> &str {
+    fn system_prompt(&self, input: &WatchdogInput) -> &str {
+        if is_stop_event(&input.event_type) {
+            return &self.prompts.stop_system_prompt;
+        }
         // Config system_prompt takes precedence over loaded prompts
         self.config
             .system_prompt
@@ -152,7 +161,7 @@ impl WatchdogBackend for OpenRouterBackend {
             messages: vec![
                 Message {
                     role: "system".to_string(),
-                    content: self.system_prompt().to_string(),
+                    content: self.system_prompt(&input).to_string(),
                 },
                 Message {
                     role: "user".to_string(),
@@ -247,6 +256,7 @@ mod tests {
             event_type: "PreToolUse".to_string(),
             tool_name: Some("Bash".to_string()),
             event_payload: serde_json::json!({"command": "ls -la"}),
+            transcript: None,
         };
 
         let message = prompts.render_user_message(&input.event_payload);
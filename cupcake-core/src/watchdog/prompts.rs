@@ -10,6 +10,11 @@
 //!
 //! - `{{event}}` - Replaced with the pretty-printed JSON event
 //! - `{{rules_context}}` - Replaced with contents of configured rules files
+//! - `{{transcript}}` - Stop templates only: the condensed transcript tail
+//!
+//! Stop and SubagentStop reviews use their own prompts (`stop_system.txt`,
+//! `stop_user.txt`): the question is whether the work is done, not whether an
+//! action is safe.
 
 use super::config::RulesContext;
 use std::path::Path;
@@ -40,6 +45,34 @@ pub const DEFAULT_USER_TEMPLATE: &str = r#"{{event}}
 
 {{rules_context}}"#;
 
+/// Default system prompt for judging whether the agent may stop
+pub const DEFAULT_STOP_SYSTEM_PROMPT: &str = r#"You are reviewing whether an AI coding agent has finished its task before it stops. You are given the stop event and the end of the session transcript.
+
+Find the user's most recent request and decide whether the agent completed it, and did so safely. Respond with a JSON object containing:
+- "allow": boolean - true if the agent may stop, false if work is missing or was done unsafely
+- "confidence": number 0-1 - how confident you are in this assessment
+- "reasoning": string - brief explanation of your decision
+- "concerns": array of strings - each item the agent still has to do or fix (empty if none)
+- "suggestions": array of strings - concrete next steps for the agent (empty if none)
+
+Look for:
+- Parts of the request that were never addressed
+- Failing tests, build errors or tool errors that were left unresolved
+- Work the agent said it would do but did not
+- Risky actions taken without the user's consent
+
+Be pragmatic. If the request was answered or the agent is waiting on the user, allow the stop. If the transcript doesn't show enough to judge, allow the stop.
+
+Respond with ONLY the JSON object, no markdown formatting."#;
+
+/// Default user template for Stop reviews
+pub const DEFAULT_STOP_USER_TEMPLATE: &str = r#"{{event}}
+
+Recent transcript (oldest first):
+{{transcript}}
+
+{{rules_context}}"#;
+
 /// Default rules context instruction (shown when rules_context is provided)
 pub const DEFAULT_RULES_CONTEXT_PREFIX: &str =
     "Determine if the agent action breaks any of the rules provided below:";
@@ -53,6 +86,10 @@ pub struct WatchdogPrompts {
     pub user_template: String,
     /// Pre-loaded rules context content (from configured files)
     pub rules_context: String,
+    /// System prompt for Stop/SubagentStop reviews
+    pub stop_system_prompt: String,
+    /// Stop review template, adds the {{transcript}} placeholder
+    pub stop_user_template: String,
 }
 
 impl Default for WatchdogPrompts {
@@ -61,6 +98,8 @@ impl Default for WatchdogPrompts {
             system_prompt: DEFAULT_SYSTEM_PROMPT.to_string(),
             user_template: DEFAULT_USER_TEMPLATE.to_string(),
             rules_context: String::new(),
+            stop_system_prompt: DEFAULT_STOP_SYSTEM_PROMPT.to_string(),
+            stop_user_template: DEFAULT_STOP_USER_TEMPLATE.to_string(),
         }
     }
 }
@@ -95,6 +134,14 @@ impl WatchdogPrompts {
         let user_template = Self::load_file("user.txt", project_watchdog_dir, global_watchdog_dir)
             .unwrap_or_else(|| DEFAULT_USER_TEMPLATE.to_string());
 
+        let stop_system_prompt =
+            Self::load_file("stop_system.txt", project_watchdog_dir, global_watchdog_dir)
+                .unwrap_or_else(|| DEFAULT_STOP_SYSTEM_PROMPT.to_string());
+
+        let stop_user_template =
+            Self::load_file("stop_user.txt", project_watchdog_dir, global_watchdog_dir)
+                .unwrap_or_else(|| DEFAULT_STOP_USER_TEMPLATE.to_string());
+
         // Load rules context files if configured
         let rules_context = match (rules_context_config, project_watchdog_dir) {
             (Some(rc), Some(dir)) => {
@@ -112,6 +159,8 @@ impl WatchdogPrompts {
             system_prompt,
             user_template,
             rules_context,
+            stop_system_prompt,
+            stop_user_template,
         })
     }
 
@@ -169,6 +218,27 @@ impl WatchdogPrompts {
         // Clean up any trailing whitespace from empty rules_context
         result.trim_end().to_string()
     }
+
+    /// Render the Stop review message - like `render_user_message`, plus
+    /// `{{transcript}}` with the transcript tail
+    pub fn render_stop_message(
+        &self,
+        event: &serde_json::Value,
+        transcript: Option<&str>,
+    ) -> String {
+        let event_json = serde_json::to_string_pretty(event).unwrap_or_else(|_| "{}".to_string());
+        let transcript = match transcript {
+            Some(text) if !text.is_empty() => text,
+            _ => "(transcript unavailable)",
+        };
+
+        self.stop_user_template
+            .replace("{{event}}", &event_json)
+            .replace("{{transcript}}", transcript)
+            .replace("{{rules_context}}", &self.rules_context)
+            .trim_end()
+            .to_string()
+    }
 }
 
 #[cfg(test)]
@@ -234,6 +304,7 @@ mod tests {
             system_prompt: "".to_string(),
             user_template: "Evaluate this:\n{{event}}\n\nBe careful!".to_string(),
             rules_context: String::new(),
+            ..Default::default()
         };
 
         let event = serde_json::json!({
@@ -265,6 +336,7 @@ mod tests {
             system_prompt: "".to_string(),
             user_template: "{{event}}\n\n{{rules_context}}".to_string(),
             rules_context: "Determine if the agent action breaks any of the rules provided below:\n\n=== CLAUDE.md ===\nDo not delete files".to_string(),
+            ..Default::default()
         };
 
         let event = serde_json::json!({"tool_name": "Bash"});
@@ -276,6 +348,20 @@ mod tests {
         assert!(!rendered.contains("{{rules_context}}"));
     }
 
+    #[test]
+    fn test_render_stop_message() {
+        let prompts = WatchdogPrompts::default();
+        let event = serde_json::json!({"hook_event_name": "Stop"});
+
+        let rendered = prompts.render_stop_message(&event, Some("USER: Add a test"));
+        assert!(rendered.contains("\"hook_event_name\": \"Stop\""));
+        assert!(rendered.contains("USER: Add a test"));
+        assert!(!rendered.contains("{{"));
+
+        let rendered = prompts.render_stop_message(&event, None);
+        assert!(rendered.contains("(transcript unavailable)"));
+    }
+
     #[test]
     fn test_load_with_rules_context() {
         let temp = TempDir::new().unwrap();
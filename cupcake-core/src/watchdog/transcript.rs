@@ -0,0 +1,151 @@
+//! Transcript tail for Stop evaluations
+//!
+//! On Stop and SubagentStop the watchdog judges whether the agent finished what
+//! was asked. It sees the end of the session transcript (Claude Code JSONL at
+//! the event's `transcript_path`), read from the last `transcript_tail_bytes`
+//! of the file and condensed to one line per message or tool call:
+//!
+//! ```text
+//! USER: Fix the login bug and add a test
+//! ASSISTANT: I'll start by reading the handler.
+//! TOOL Edit: {"file_path":"src/login.rs",...}
+//! RESULT: The file src/login.rs has been updated.
+//! ```
+
+use serde_json::Value;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Longest text kept for a single transcript line
+const LINE_LIMIT: usize = 500;
+
+/// Events judged on the transcript instead of a pending action
+pub fn is_stop_event(event_type: &str) -> bool {
+    matches!(event_type, "Stop" | "SubagentStop")
+}
+
+/// Condensed view of the last `max_bytes` of the transcript at `path`
+pub fn read_tail(path: &Path, max_bytes: u64) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(max_bytes);
+    file.seek(SeekFrom::Start(start))?;
+
+    let mut bytes = Vec::new();
+    file.take(max_bytes).read_to_end(&mut bytes)?;
+    let content = String::from_utf8_lossy(&bytes);
+
+    // A tail that starts mid-file starts mid-entry - drop the partial line
+    let content = if start > 0 {
+        content.split_once('\n').map_or("", |(_, rest)| rest)
+    } else {
+        &content
+    };
+
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .flat_map(|entry| render_entry(&entry))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn render_entry(entry: &Value) -> Vec<String> {
+    let role = match entry.get("type").and_then(|t| t.as_str()) {
+        Some("summary") => {
+            return entry
+                .get("summary")
+                .and_then(|s| s.as_str())
+                .map(|s| vec![format!("SUMMARY: {}", clip(s))])
+                .unwrap_or_default();
+        }
+        Some("user") => "USER",
+        Some("assistant") => "ASSISTANT",
+        _ => return Vec::new(),
+    };
+
+    match entry.pointer("/message/content") {
+        Some(Value::String(text)) => vec![format!("{role}: {}", clip(text))],
+        Some(Value::Array(blocks)) => blocks
+            .iter()
+            .filter_map(|block| match block.get("type").and_then(|t| t.as_str()) {
+                Some("text") => block
+                    .get("text")
+                    .and_then(|t| t.as_str())
+                    .filter(|t| !t.trim().is_empty())
+                    .map(|t| format!("{role}: {}", clip(t))),
+                Some("tool_use") => {
+                    let name = block.get("name").and_then(|n| n.as_str()).unwrap_or("?");
+                    let input = block.get("input").map(Value::to_string).unwrap_or_default();
+                    Some(format!("TOOL {name}: {}", clip(&input)))
+                }
+                Some("tool_result") => {
+                    let text = match block.get("content") {
+                        Some(Value::String(s)) => s.clone(),
+                        Some(Value::Array(parts)) => parts
+                            .iter()
+                            .filter_map(|p| p.get("text").and_then(|t| t.as_str()))
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                        _ => String::new(),
+                    };
+                    Some(format!("RESULT: {}", clip(&text)))
+                }
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// First `LINE_LIMIT` characters, on one line
+fn clip(text: &str) -> String {
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match flat.char_indices().nth(LINE_LIMIT) {
+        Some((end, _)) => format!("{}...", &flat[..end]),
+        None => flat,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_tail_condenses_and_bounds() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("transcript.jsonl");
+        let entries = [
+            json!({"type": "user", "message": {"content": "Old request that falls outside the tail"}}),
+            json!({"type": "user", "message": {"content": "Fix the login bug\nand add a test"}}),
+            json!({"type": "assistant", "message": {"content": [
+                {"type": "text", "text": "Editing the handler."},
+                {"type": "tool_use", "name": "Edit", "input": {"file_path": "src/login.rs"}}
+            ]}}),
+            json!({"type": "user", "message": {"content": [
+                {"type": "tool_result", "content": "File updated"}
+            ]}}),
+        ];
+        let lines: Vec<String> = entries.iter().map(Value::to_string).collect();
+        std::fs::write(&path, lines.join("\n") + "\n").unwrap();
+
+        let full = read_tail(&path, 1 << 20).unwrap();
+        assert!(full.starts_with("USER: Old request"));
+
+        // Cut inside the first entry - its remainder is dropped, not misparsed
+        let bytes = (lines[1..].join("\n").len() + 10) as u64;
+        let tail = read_tail(&path, bytes).unwrap();
+        assert_eq!(
+            tail,
+            "USER: Fix the login bug and add a test\n\
+             ASSISTANT: Editing the handler.\n\
+             TOOL Edit: {\"file_path\":\"src/login.rs\"}\n\
+             RESULT: File updated"
+        );
+
+        assert!(read_tail(&temp.path().join("missing.jsonl"), 100).is_err());
+        assert!(clip(&"x".repeat(LINE_LIMIT + 1)).ends_with("..."));
+    }
+}
@@ -16,6 +16,10 @@ pub struct WatchdogInput {
 
     /// The full event payload for context
     pub event_payload: serde_json::Value,
+
+    /// Condensed tail of the session transcript (Stop and SubagentStop only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcript: Option<String>,
 }
 
 /// Output from the watchdog LLM evaluation
@@ -33,7 +33,7 @@ async fn test_cross_tool_protection_coverage() -> Result<()> {
     fs::write(shared_system_dir.join("commands.rego"), helpers_commands)?;
 
     // Use protected_paths builtin with expanded metadata
-    let protected_policy = include_str!("../../fixtures/claude/builtins/protected_paths.rego");
+    let protected_policy = include_str!("../../fixtures/builtins/protected_paths.rego");
     fs::write(builtins_dir.join("protected_paths.rego"), protected_policy)?;
 
     let rulebook_content = r#"
@@ -51,8 +51,10 @@ builtins:
     let empty_global = TempDir::new()?;
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -192,8 +194,10 @@ builtins:
     let empty_global = TempDir::new()?;
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -320,8 +324,10 @@ deny contains decision if {
     let empty_global = TempDir::new()?;
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -468,8 +474,10 @@ sensitive_access if {
     let empty_global = TempDir::new()?;
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -593,8 +601,10 @@ input_contains_sensitive if {
     let empty_global = TempDir::new()?;
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
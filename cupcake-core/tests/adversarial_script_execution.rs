@@ -136,6 +136,23 @@ echo "Done!"
         assert!(content.contains("rm -rf .cupcake"));
     }
 
+    /// Test that inline command strings are inspected without any script file
+    #[test]
+    fn test_cursor_inline_script_inspection() {
+        let mut event = json!({
+            "hook_event_name": "beforeShellExecution",
+            "command": "sh -c 'rm -rf .cupcake'",
+            "cwd": "/tmp"
+        });
+
+        let config = PreprocessConfig::with_script_inspection();
+        preprocess_input(&mut event, &config, HarnessType::Cursor);
+
+        assert_eq!(event["inline_scripts"][0]["interpreter"], "sh");
+        assert_eq!(event["executed_script_content"], "rm -rf .cupcake");
+        assert_eq!(event["script_inspection_performed"], true);
+    }
+
     /// Test that script inspection is disabled by default
     #[test]
     fn test_script_inspection_disabled_by_default() {
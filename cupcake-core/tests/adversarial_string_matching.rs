@@ -52,8 +52,10 @@ builtins:
     let empty_global = TempDir::new()?;
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -139,8 +141,10 @@ builtins:
     let empty_global = TempDir::new()?;
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -212,8 +216,8 @@ async fn test_blocks_git_no_verify_with_spacing() -> Result<()> {
     let helpers_commands = include_str!("../../fixtures/system/commands.rego");
     fs::write(shared_system_dir.join("commands.rego"), helpers_commands)?;
 
-    // Use the actual git_block_no_verify policy
-    let git_policy = include_str!("../../fixtures/claude/builtins/git_block_no_verify.rego");
+    // Use the actual git_block_no_verify template - the engine renders it on load
+    let git_policy = include_str!("../../fixtures/builtins/git_block_no_verify.rego");
     fs::write(builtins_dir.join("git_block_no_verify.rego"), git_policy)?;
 
     let rulebook_content = r#"
@@ -227,8 +231,10 @@ builtins:
     let empty_global = TempDir::new()?;
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -330,7 +336,7 @@ async fn test_protected_paths_blocks_obfuscated_writes() -> Result<()> {
     let helpers_commands = include_str!("../../fixtures/system/commands.rego");
     fs::write(shared_system_dir.join("commands.rego"), helpers_commands)?;
 
-    let protected_policy = include_str!("../../fixtures/claude/builtins/protected_paths.rego");
+    let protected_policy = include_str!("../../fixtures/builtins/protected_paths.rego");
     fs::write(builtins_dir.join("protected_paths.rego"), protected_policy)?;
 
     let rulebook_content = r#"
@@ -348,8 +354,10 @@ builtins:
     let empty_global = TempDir::new()?;
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -476,8 +484,10 @@ deny contains decision if {
     let empty_global = TempDir::new()?;
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -49,8 +49,10 @@ builtins:
     let empty_global = TempDir::new()?;
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -117,7 +119,7 @@ async fn test_protected_paths_block_symlinks() -> Result<()> {
     let helpers_commands = include_str!("../../fixtures/system/commands.rego");
     fs::write(shared_system_dir.join("commands.rego"), helpers_commands)?;
 
-    let protected_policy = include_str!("../../fixtures/claude/builtins/protected_paths.rego");
+    let protected_policy = include_str!("../../fixtures/builtins/protected_paths.rego");
     fs::write(builtins_dir.join("protected_paths.rego"), protected_policy)?;
 
     let rulebook_content = r#"
@@ -135,8 +137,10 @@ builtins:
     let empty_global = TempDir::new()?;
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -268,8 +272,10 @@ builtins:
     let empty_global = TempDir::new()?;
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -383,8 +389,10 @@ deny contains decision if {
     let empty_global = TempDir::new()?;
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -482,8 +490,10 @@ builtins:
     let empty_global = TempDir::new()?;
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -115,8 +115,10 @@ echo '{
     let empty_global = TempDir::new().unwrap();
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -0,0 +1,85 @@
+//! Integration test for binary_file_guard builtin
+//!
+//! Tests that writes of binary file types and oversized content are blocked
+//! while ordinary source writes are allowed
+
+mod common;
+use common::{create_builtin_project, engine_without_global_config};
+
+use anyhow::Result;
+use cupcake_core::engine::decision::FinalDecision;
+use cupcake_core::harness::types::HarnessType;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+fn write_event(temp_dir: &TempDir, file_path: &str, content: &str) -> Value {
+    json!({
+        "hook_event_name": "PreToolUse",
+        "session_id": "test-session",
+        "transcript_path": "/tmp/transcript.md",
+        "cwd": temp_dir.path().to_str().unwrap(),
+        "tool_name": "Write",
+        "tool_input": {
+            "file_path": file_path,
+            "content": content
+        }
+    })
+}
+
+/// Test binary_file_guard decisions through the engine
+#[tokio::test]
+async fn test_binary_file_guard_decisions() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    // Use the actual binary_file_guard template - the engine renders it on load
+    create_builtin_project(
+        temp_dir.path(),
+        HarnessType::ClaudeCode,
+        "binary_file_guard",
+        include_str!("../../fixtures/builtins/binary_file_guard.rego"),
+        r#"
+builtins:
+  binary_file_guard:
+    enabled: true
+    max_file_bytes: 64
+    respect_gitattributes: false
+"#,
+    )?;
+    let engine = engine_without_global_config(temp_dir.path(), HarnessType::ClaudeCode).await?;
+
+    // Test 1: DENY writing a binary file type
+    let decision = engine
+        .evaluate(&write_event(&temp_dir, "tools/helper.exe", "MZ"), None)
+        .await?;
+    match decision {
+        FinalDecision::Deny { reason, .. } => {
+            assert!(
+                reason.contains("tools/helper.exe is a binary file type"),
+                "Should name the file: {reason}"
+            );
+        }
+        _ => panic!("Expected Deny for binary file type, got: {decision:?}"),
+    }
+
+    // Test 2: DENY content over the size limit
+    let decision = engine
+        .evaluate(&write_event(&temp_dir, "data.json", &"x".repeat(65)), None)
+        .await?;
+    assert!(
+        matches!(decision, FinalDecision::Deny { .. }),
+        "Expected Deny for oversized write, got: {decision:?}"
+    );
+
+    // Test 3: ALLOW an ordinary source file
+    let decision = engine
+        .evaluate(
+            &write_event(&temp_dir, "src/main.rs", "fn main() {}\n"),
+            None,
+        )
+        .await?;
+    assert!(
+        matches!(decision, FinalDecision::Allow { .. }),
+        "Expected Allow for source file, got: {decision:?}"
+    );
+
+    Ok(())
+}
@@ -75,10 +75,23 @@ fn test_builtin_signal_generation() {
         }),
         protected_paths: None,
         git_block_no_verify: None,
+        dependency_guard: None,
+        container_guard: None,
+        terraform_plan_gate: None,
+        change_freeze: None,
+        rate_limit: None,
+        mass_deletion_guard: None,
+        license_header: None,
+        binary_file_guard: None,
+        prompt_injection_guard: None,
+        env_exfiltration_guard: None,
+        obfuscation_guard: None,
+        mcp_allowlist: None,
         system_protection: None,
         sensitive_data_protection: None,
         cupcake_exec_protection: None,
         claude_code_enforce_full_file_read: None,
+        claude_code_watchdog_stop_review: None,
     };
 
     // Generate signals
@@ -94,16 +107,22 @@ fn test_builtin_signal_generation() {
     // Verify git_pre_check signals
     assert!(signals.contains_key("__builtin_git_check_0"));
     assert!(signals.contains_key("__builtin_git_check_1"));
-    assert_eq!(signals["__builtin_git_check_0"].command, "cargo test");
     assert_eq!(
-        signals["__builtin_git_check_1"].command,
+        signals["__builtin_git_check_0"].command.to_string(),
+        "cargo test"
+    );
+    assert_eq!(
+        signals["__builtin_git_check_1"].command.to_string(),
         "cargo fmt --check"
     );
 
     // Verify post_edit_check signals
     assert!(signals.contains_key("__builtin_post_edit_rs"));
     assert!(signals.contains_key("__builtin_post_edit_py"));
-    assert_eq!(signals["__builtin_post_edit_rs"].command, "cargo check");
+    assert_eq!(
+        signals["__builtin_post_edit_rs"].command.to_string(),
+        "cargo check"
+    );
 
     // rulebook_security_guardrails no longer generates signals - uses builtin_config instead
     // The message and paths are injected directly via builtin_config
@@ -240,8 +259,10 @@ add_context contains context_msg if {
     let empty_global = TempDir::new()?;
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -328,8 +349,10 @@ builtins:
     let empty_global = TempDir::new()?;
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -0,0 +1,108 @@
+//! Integration test for change_freeze builtin
+//!
+//! Tests that guarded operations are blocked inside a declared freeze and
+//! allowed outside it
+
+mod common;
+use common::{create_builtin_project, engine_without_global_config};
+
+use anyhow::Result;
+use chrono::{Duration, Local};
+use cupcake_core::engine::decision::FinalDecision;
+use cupcake_core::engine::Engine;
+use cupcake_core::harness::types::HarnessType;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+fn bash_event(temp_dir: &TempDir, command: &str) -> Value {
+    json!({
+        "hook_event_name": "PreToolUse",
+        "session_id": "test-session",
+        "transcript_path": "/tmp/transcript.md",
+        "cwd": temp_dir.path().to_str().unwrap(),
+        "tool_name": "Bash",
+        "tool_input": {
+            "command": command
+        }
+    })
+}
+
+/// Engine with a single freeze from `start` to `end` (dates, inclusive)
+async fn engine_with_freeze(temp_dir: &TempDir, start: &str, end: &str) -> Result<Engine> {
+    let rulebook = format!(
+        r#"
+builtins:
+  change_freeze:
+    enabled: true
+    operations: ["git push", "terraform apply"]
+    freezes:
+      - start: "{start}"
+        end: "{end}"
+        reason: "holiday freeze"
+"#
+    );
+    // Use the actual change_freeze template - the engine renders it on load
+    create_builtin_project(
+        temp_dir.path(),
+        HarnessType::ClaudeCode,
+        "change_freeze",
+        include_str!("../../fixtures/builtins/change_freeze.rego"),
+        &rulebook,
+    )?;
+    engine_without_global_config(temp_dir.path(), HarnessType::ClaudeCode).await
+}
+
+/// Test that a freeze covering today blocks guarded operations only
+#[tokio::test]
+async fn test_change_freeze_inside_window() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let today = Local::now().date_naive();
+    let engine = engine_with_freeze(
+        &temp_dir,
+        &(today - Duration::days(1)).to_string(),
+        &(today + Duration::days(1)).to_string(),
+    )
+    .await?;
+
+    // Test 1: DENY a guarded operation during the freeze
+    let decision = engine
+        .evaluate(&bash_event(&temp_dir, "git  push origin main"), None)
+        .await?;
+    match decision {
+        FinalDecision::Deny { reason, .. } => {
+            assert!(
+                reason.contains("git push during holiday freeze"),
+                "Should name the freeze: {reason}"
+            );
+        }
+        _ => panic!("Expected Deny during freeze, got: {decision:?}"),
+    }
+
+    // Test 2: ALLOW operations that aren't guarded
+    let decision = engine
+        .evaluate(&bash_event(&temp_dir, "git status"), None)
+        .await?;
+    assert!(
+        matches!(decision, FinalDecision::Allow { .. }),
+        "Expected Allow for unguarded command, got: {decision:?}"
+    );
+
+    Ok(())
+}
+
+/// Test that a freeze that has ended no longer blocks anything
+#[tokio::test]
+async fn test_change_freeze_outside_window() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let engine = engine_with_freeze(&temp_dir, "2000-12-20", "2001-01-02").await?;
+
+    let decision = engine
+        .evaluate(&bash_event(&temp_dir, "git push origin main"), None)
+        .await?;
+    assert!(
+        matches!(decision, FinalDecision::Allow { .. }),
+        "Expected Allow outside the freeze, got: {decision:?}"
+    );
+
+    Ok(())
+}
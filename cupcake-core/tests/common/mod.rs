@@ -7,6 +7,7 @@
 #![allow(dead_code)]
 
 use anyhow::Result;
+use cupcake_core::engine::{Engine, EngineConfig};
 use cupcake_core::harness::types::HarnessType;
 use std::fs;
 use std::path::Path;
@@ -52,6 +53,9 @@ pub fn create_test_project_for_harness(project_path: &Path, harness: HarnessType
         HarnessType::Cursor => "cursor",
         HarnessType::Factory => "factory",
         HarnessType::OpenCode => "opencode",
+        HarnessType::Cline => "cline",
+        HarnessType::Aider => "aider",
+        HarnessType::Goose => "goose",
     };
     let harness_dir = policies_dir.join(harness_name);
     let system_dir = harness_dir.join("system");
@@ -99,3 +103,53 @@ pub fn create_test_global_config(global_path: &Path) -> Result<()> {
 
     Ok(())
 }
+
+/// Create a project containing one builtin policy and the rulebook enabling it
+///
+/// The builtin is written to `policies/<harness>/builtins/` alongside the system
+/// evaluation policy and the shared command helpers; template builtins are
+/// rendered by the engine on load.
+pub fn create_builtin_project(
+    project_path: &Path,
+    harness: HarnessType,
+    builtin: &str,
+    policy: &str,
+    rulebook: &str,
+) -> Result<()> {
+    let cupcake_dir = project_path.join(".cupcake");
+    let harness_dir = cupcake_dir.join("policies").join(harness.as_str());
+    let system_dir = harness_dir.join("system");
+    let builtins_dir = harness_dir.join("builtins");
+    let shared_system_dir = cupcake_dir.join("system");
+
+    fs::create_dir_all(&system_dir)?;
+    fs::create_dir_all(&builtins_dir)?;
+    fs::create_dir_all(&shared_system_dir)?;
+
+    let system_policy = include_str!("../fixtures/system_evaluate.rego");
+    fs::write(system_dir.join("evaluate.rego"), system_policy)?;
+
+    let helpers_commands = include_str!("../../../fixtures/system/commands.rego");
+    fs::write(shared_system_dir.join("commands.rego"), helpers_commands)?;
+
+    fs::write(builtins_dir.join(format!("{builtin}.rego")), policy)?;
+    fs::write(cupcake_dir.join("rulebook.yml"), rulebook)?;
+
+    Ok(())
+}
+
+/// Create an engine for `harness` that ignores any global configuration
+///
+/// Global builtins on the machine running the tests would otherwise interfere;
+/// an empty directory inside the project stands in for the global config.
+pub async fn engine_without_global_config(
+    project_path: &Path,
+    harness: HarnessType,
+) -> Result<Engine> {
+    let empty_global = project_path.join("empty-global");
+    fs::create_dir_all(&empty_global)?;
+
+    let mut config = EngineConfig::new(harness);
+    config.global_config = Some(empty_global);
+    Engine::new_with_config(project_path, config).await
+}
@@ -0,0 +1,97 @@
+//! Integration test for container_guard builtin
+//!
+//! Tests that privileged containers and destructive kubectl deletes are
+//! blocked while routine container commands are allowed
+
+mod common;
+use common::{create_builtin_project, engine_without_global_config};
+
+use anyhow::Result;
+use cupcake_core::engine::decision::FinalDecision;
+use cupcake_core::harness::types::HarnessType;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+fn bash_event(temp_dir: &TempDir, command: &str) -> Value {
+    json!({
+        "hook_event_name": "PreToolUse",
+        "session_id": "test-session",
+        "transcript_path": "/tmp/transcript.md",
+        "cwd": temp_dir.path().to_str().unwrap(),
+        "tool_name": "Bash",
+        "tool_input": {
+            "command": command
+        }
+    })
+}
+
+/// Test container_guard decisions through the engine
+#[tokio::test]
+async fn test_container_guard_decisions() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    // Use the actual container_guard template - the engine renders it on load
+    create_builtin_project(
+        temp_dir.path(),
+        HarnessType::ClaudeCode,
+        "container_guard",
+        include_str!("../../fixtures/builtins/container_guard.rego"),
+        r#"
+builtins:
+  container_guard:
+    enabled: true
+    message: "Container operation blocked"
+"#,
+    )?;
+    let engine = engine_without_global_config(temp_dir.path(), HarnessType::ClaudeCode).await?;
+
+    // Test 1: DENY a privileged container
+    let decision = engine
+        .evaluate(
+            &bash_event(&temp_dir, "docker run --privileged -it ubuntu bash"),
+            None,
+        )
+        .await?;
+    match decision {
+        FinalDecision::Deny { reason, .. } => {
+            assert!(
+                reason.contains("privileged container"),
+                "Should name the violation: {reason}"
+            );
+        }
+        _ => panic!("Expected Deny for privileged container, got: {decision:?}"),
+    }
+
+    // Test 2: DENY deleting a whole namespace
+    let decision = engine
+        .evaluate(
+            &bash_event(&temp_dir, "kubectl delete namespace production"),
+            None,
+        )
+        .await?;
+    match decision {
+        FinalDecision::Deny { reason, .. } => {
+            assert!(
+                reason.contains("kubectl delete namespace"),
+                "Should name the violation: {reason}"
+            );
+        }
+        _ => panic!("Expected Deny for kubectl delete namespace, got: {decision:?}"),
+    }
+
+    // Test 3: ALLOW routine container commands
+    for command in [
+        "docker run --rm alpine echo hello",
+        "kubectl delete pod web-7f9c -n staging",
+        "kubectl get pods",
+    ] {
+        let decision = engine
+            .evaluate(&bash_event(&temp_dir, command), None)
+            .await?;
+        assert!(
+            matches!(decision, FinalDecision::Allow { .. }),
+            "Expected Allow for '{command}', got: {decision:?}"
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+//! Integration tests for data documents (`data.{json,yaml}` alongside policies)
+
+use anyhow::Result;
+use cupcake_core::engine::decision::FinalDecision;
+use cupcake_core::engine::{Engine, EngineConfig};
+use cupcake_core::harness::types::HarnessType;
+use serial_test::serial;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+/// Denylist maintained as YAML data instead of being hardcoded into Rego
+#[tokio::test]
+#[serial]
+async fn test_policy_reads_yaml_data_document() -> Result<()> {
+    common::init_test_logging();
+
+    let project_dir = TempDir::new()?;
+    common::create_test_project_for_harness(project_dir.path(), HarnessType::ClaudeCode)?;
+    let policies_dir = project_dir.path().join(".cupcake/policies");
+
+    fs::write(
+        policies_dir.join("data.yaml"),
+        "blocked_packages:\n  - left-pad\n  - event-stream\n",
+    )?;
+    fs::write(
+        policies_dir.join("claude/blocked_packages.rego"),
+        r#"# METADATA
+# scope: package
+# custom:
+#   routing:
+#     required_events: ["PreToolUse"]
+#     required_tools: ["Bash"]
+package cupcake.policies.blocked_packages
+
+import rego.v1
+
+deny contains decision if {
+    some pkg in data.blocked_packages
+    contains(input.tool_input.command, pkg)
+    decision := {
+        "rule_id": "DATA-001",
+        "reason": concat(" ", ["Package is blocked:", pkg]),
+        "severity": "HIGH"
+    }
+}
+"#,
+    )?;
+
+    let empty_global = TempDir::new()?;
+    let config = EngineConfig {
+        harness: HarnessType::ClaudeCode,
+        wasm_max_memory: None,
+        wasm_pool_size: None,
+        opa_path: None,
+        global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
+        debug_routing: false,
+    };
+    let engine = Engine::new_with_config(project_dir.path(), config).await?;
+
+    let input = serde_json::json!({
+        "hook_event_name": "PreToolUse",
+        "tool_name": "Bash",
+        "tool_input": {"command": "npm install event-stream"},
+        "session_id": "test",
+        "transcript_path": "/tmp/transcript.jsonl",
+        "cwd": project_dir.path().to_string_lossy()
+    });
+    match engine.evaluate(&input, None).await? {
+        FinalDecision::Deny { reason, .. } => assert!(reason.contains("event-stream")),
+        other => panic!("Expected Deny from data-driven policy, got {other:?}"),
+    }
+
+    let input = serde_json::json!({
+        "hook_event_name": "PreToolUse",
+        "tool_name": "Bash",
+        "tool_input": {"command": "npm install lodash"},
+        "session_id": "test",
+        "transcript_path": "/tmp/transcript.jsonl",
+        "cwd": project_dir.path().to_string_lossy()
+    });
+    assert!(matches!(
+        engine.evaluate(&input, None).await?,
+        FinalDecision::Allow { .. }
+    ));
+
+    Ok(())
+}
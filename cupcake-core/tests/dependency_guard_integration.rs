@@ -0,0 +1,153 @@
+//! Integration test for dependency_guard builtin
+//!
+//! Tests that installs of denied packages are blocked while other installs
+//! go through, for both Claude Code and Cursor
+
+mod common;
+use common::{create_builtin_project, engine_without_global_config};
+
+use anyhow::Result;
+use cupcake_core::engine::decision::FinalDecision;
+use cupcake_core::harness::types::HarnessType;
+use serde_json::json;
+use tempfile::TempDir;
+
+const RULEBOOK: &str = r#"
+builtins:
+  dependency_guard:
+    enabled: true
+    message: "Dependency not approved"
+    deny:
+      - "left-pad"
+      - "event-stream*"
+"#;
+
+/// Test that the Claude Code policy denies a blocked install and allows others
+#[tokio::test]
+async fn test_dependency_guard_claude() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    create_builtin_project(
+        temp_dir.path(),
+        HarnessType::ClaudeCode,
+        "dependency_guard",
+        include_str!("../../fixtures/builtins/dependency_guard.rego"),
+        RULEBOOK,
+    )?;
+    let engine = engine_without_global_config(temp_dir.path(), HarnessType::ClaudeCode).await?;
+
+    // Test 1: DENY installing a package on the deny list
+    let install_event = json!({
+        "hook_event_name": "PreToolUse",
+        "session_id": "test-session",
+        "transcript_path": "/tmp/transcript.md",
+        "cwd": temp_dir.path().to_str().unwrap(),
+        "tool_name": "Bash",
+        "tool_input": {
+            "command": "npm install --save left-pad@1.3.0"
+        }
+    });
+
+    let decision = engine.evaluate(&install_event, None).await?;
+    match decision {
+        FinalDecision::Deny { reason, .. } => {
+            assert!(
+                reason.contains("Dependency not approved") && reason.contains("left-pad"),
+                "Should name the package: {reason}"
+            );
+        }
+        _ => panic!("Expected Deny for denied package, got: {decision:?}"),
+    }
+
+    // Test 2: DENY writing a denied package into a manifest
+    let manifest_event = json!({
+        "hook_event_name": "PreToolUse",
+        "session_id": "test-session",
+        "transcript_path": "/tmp/transcript.md",
+        "cwd": temp_dir.path().to_str().unwrap(),
+        "tool_name": "Edit",
+        "tool_input": {
+            "file_path": "package.json",
+            "old_string": "\"dependencies\": {",
+            "new_string": "\"dependencies\": {\n    \"left-pad\": \"^1.3.0\","
+        }
+    });
+
+    let decision = engine.evaluate(&manifest_event, None).await?;
+    assert!(
+        matches!(decision, FinalDecision::Deny { .. }),
+        "Expected Deny for manifest edit, got: {decision:?}"
+    );
+
+    // Test 3: ALLOW installing a package that isn't denied
+    let allowed_event = json!({
+        "hook_event_name": "PreToolUse",
+        "session_id": "test-session",
+        "transcript_path": "/tmp/transcript.md",
+        "cwd": temp_dir.path().to_str().unwrap(),
+        "tool_name": "Bash",
+        "tool_input": {
+            "command": "npm install lodash"
+        }
+    });
+
+    let decision = engine.evaluate(&allowed_event, None).await?;
+    assert!(
+        matches!(decision, FinalDecision::Allow { .. }),
+        "Expected Allow for unlisted package, got: {decision:?}"
+    );
+
+    Ok(())
+}
+
+/// Test that the Cursor policy denies a blocked install and allows others
+#[tokio::test]
+async fn test_dependency_guard_cursor() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    create_builtin_project(
+        temp_dir.path(),
+        HarnessType::Cursor,
+        "dependency_guard",
+        include_str!("../../fixtures/builtins/dependency_guard.rego"),
+        RULEBOOK,
+    )?;
+    let engine = engine_without_global_config(temp_dir.path(), HarnessType::Cursor).await?;
+
+    // Test 1: DENY a wildcard match on the deny list
+    let install_event = json!({
+        "hook_event_name": "beforeShellExecution",
+        "command": "yarn add event-stream-utils",
+        "cwd": temp_dir.path().to_str().unwrap(),
+        "conversation_id": "cursor-conv-123",
+        "generation_id": "cursor-gen-456",
+        "workspace_roots": [temp_dir.path().to_str().unwrap()]
+    });
+
+    let decision = engine.evaluate(&install_event, None).await?;
+    match decision {
+        FinalDecision::Deny { reason, .. } => {
+            assert!(
+                reason.contains("event-stream-utils"),
+                "Should name the package: {reason}"
+            );
+        }
+        _ => panic!("Expected Deny for denied package, got: {decision:?}"),
+    }
+
+    // Test 2: ALLOW an install of a package that isn't denied
+    let allowed_event = json!({
+        "hook_event_name": "beforeShellExecution",
+        "command": "pip install requests",
+        "cwd": temp_dir.path().to_str().unwrap(),
+        "conversation_id": "cursor-conv-123",
+        "generation_id": "cursor-gen-456",
+        "workspace_roots": [temp_dir.path().to_str().unwrap()]
+    });
+
+    let decision = engine.evaluate(&allowed_event, None).await?;
+    assert!(
+        matches!(decision, FinalDecision::Allow { .. }),
+        "Expected Allow for unlisted package, got: {decision:?}"
+    );
+
+    Ok(())
+}
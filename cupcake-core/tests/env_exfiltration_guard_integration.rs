@@ -0,0 +1,86 @@
+//! Integration test for env_exfiltration_guard builtin
+//!
+//! Tests that dumping the environment or printing secrets is blocked while
+//! printing ordinary variables is allowed
+
+mod common;
+use common::{create_builtin_project, engine_without_global_config};
+
+use anyhow::Result;
+use cupcake_core::engine::decision::FinalDecision;
+use cupcake_core::harness::types::HarnessType;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+fn bash_event(temp_dir: &TempDir, command: &str) -> Value {
+    json!({
+        "hook_event_name": "PreToolUse",
+        "session_id": "test-session",
+        "transcript_path": "/tmp/transcript.md",
+        "cwd": temp_dir.path().to_str().unwrap(),
+        "tool_name": "Bash",
+        "tool_input": {
+            "command": command
+        }
+    })
+}
+
+/// Test env_exfiltration_guard decisions through the engine
+#[tokio::test]
+async fn test_env_exfiltration_guard_decisions() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    create_builtin_project(
+        temp_dir.path(),
+        HarnessType::ClaudeCode,
+        "env_exfiltration_guard",
+        include_str!("../../fixtures/builtins/env_exfiltration_guard.rego"),
+        r#"
+builtins:
+  env_exfiltration_guard:
+    enabled: true
+    allow_vars: ["PUBLIC_API_KEY"]
+"#,
+    )?;
+    let engine = engine_without_global_config(temp_dir.path(), HarnessType::ClaudeCode).await?;
+
+    // Test 1: DENY dumping the whole environment
+    let decision = engine
+        .evaluate(&bash_event(&temp_dir, "env | grep KEY"), None)
+        .await?;
+    match decision {
+        FinalDecision::Deny { reason, .. } => {
+            assert!(
+                reason.contains("dumps the environment"),
+                "Should explain the violation: {reason}"
+            );
+        }
+        _ => panic!("Expected Deny for environment dump, got: {decision:?}"),
+    }
+
+    // Test 2: DENY printing a sensitive variable
+    let decision = engine
+        .evaluate(&bash_event(&temp_dir, "echo \"$GITHUB_TOKEN\""), None)
+        .await?;
+    match decision {
+        FinalDecision::Deny { reason, .. } => {
+            assert!(
+                reason.contains("GITHUB_TOKEN"),
+                "Should name the variable: {reason}"
+            );
+        }
+        _ => panic!("Expected Deny for printed secret, got: {decision:?}"),
+    }
+
+    // Test 3: ALLOW ordinary and explicitly allowed variables
+    for command in ["echo $HOME", "echo $PUBLIC_API_KEY"] {
+        let decision = engine
+            .evaluate(&bash_event(&temp_dir, command), None)
+            .await?;
+        assert!(
+            matches!(decision, FinalDecision::Allow { .. }),
+            "Expected Allow for '{command}', got: {decision:?}"
+        );
+    }
+
+    Ok(())
+}
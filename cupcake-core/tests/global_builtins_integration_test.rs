@@ -134,8 +134,10 @@ builtins: {}
         // Create engine with global config (pass project root, not policies directory)
         let config = cupcake_core::engine::EngineConfig {
             global_config: Some(global_temp.path().to_path_buf()),
+            team_config: None,
             harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
             wasm_max_memory: None,
+            wasm_pool_size: None,
             opa_path: None,
             debug_routing: false,
         };
@@ -189,8 +191,10 @@ builtins: {}
         // Create engine with global config (pass project root, not policies directory)
         let config = cupcake_core::engine::EngineConfig {
             global_config: Some(global_temp.path().to_path_buf()),
+            team_config: None,
             harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
             wasm_max_memory: None,
+            wasm_pool_size: None,
             opa_path: None,
             debug_routing: false,
         };
@@ -238,8 +242,10 @@ builtins: {}
         // Create engine with global config (pass project root, not policies directory)
         let config = cupcake_core::engine::EngineConfig {
             global_config: Some(global_temp.path().to_path_buf()),
+            team_config: None,
             harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
             wasm_max_memory: None,
+            wasm_pool_size: None,
             opa_path: None,
             debug_routing: false,
         };
@@ -363,8 +369,10 @@ builtins:
         // Create engine with global config (pass project root, not policies directory)
         let config = cupcake_core::engine::EngineConfig {
             global_config: Some(global_temp.path().to_path_buf()),
+            team_config: None,
             harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
             wasm_max_memory: None,
+            wasm_pool_size: None,
             opa_path: None,
             debug_routing: false,
         };
@@ -510,8 +518,10 @@ builtins:
         // Create engine with global config (pass project root, not policies directory)
         let config = cupcake_core::engine::EngineConfig {
             global_config: Some(global_temp.path().to_path_buf()),
+            team_config: None,
             harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
             wasm_max_memory: None,
+            wasm_pool_size: None,
             opa_path: None,
             debug_routing: false,
         };
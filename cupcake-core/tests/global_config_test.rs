@@ -3,7 +3,7 @@
 use anyhow::Result;
 use cupcake_core::engine::global_config::GlobalPaths;
 use serial_test::serial;
-use std::env; // Still needed for test_global_config_graceful_absence
+use std::env;
 use tempfile::TempDir;
 
 #[test]
@@ -46,6 +46,39 @@ fn test_global_config_graceful_absence() -> Result<()> {
     Ok(())
 }
 
+#[test]
+#[serial]
+fn test_global_config_search_path_env() -> Result<()> {
+    let system = TempDir::new()?;
+    let user = TempDir::new()?;
+    env::set_var(
+        "CUPCAKE_CONFIG_PATH",
+        env::join_paths([system.path(), user.path()])?,
+    );
+
+    let discovered = GlobalPaths::discover();
+    env::remove_var("CUPCAKE_CONFIG_PATH");
+
+    // Both roots are layered, the first listed takes precedence
+    let global_paths = discovered?.expect("Should discover roots from CUPCAKE_CONFIG_PATH");
+    assert_eq!(global_paths.root, system.path().canonicalize()?);
+    assert_eq!(
+        global_paths.layers,
+        vec![system.path().canonicalize()?, user.path().canonicalize()?]
+    );
+
+    // The CLI override replaces the search path entirely
+    env::set_var("CUPCAKE_CONFIG_PATH", system.path());
+    let overridden = GlobalPaths::discover_with_override(Some(user.path().to_path_buf()));
+    env::remove_var("CUPCAKE_CONFIG_PATH");
+    assert_eq!(
+        overridden?.unwrap().layers,
+        vec![user.path().canonicalize()?]
+    );
+
+    Ok(())
+}
+
 #[test]
 #[serial]
 fn test_global_config_initialization() -> Result<()> {
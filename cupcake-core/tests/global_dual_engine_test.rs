@@ -86,8 +86,10 @@ add_context contains "Global policy active"
     // Engine should initialize with both configs
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(global_root),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -154,8 +156,10 @@ test_value := "project"
     // Engine should handle both without namespace collision
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(global_root),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
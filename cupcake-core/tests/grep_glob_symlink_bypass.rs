@@ -57,8 +57,10 @@ builtins:
     let empty_global = TempDir::new()?;
     let config = EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -154,18 +156,14 @@ async fn test_glob_pattern_with_cupcake_already_blocked() -> Result<()> {
     Ok(())
 }
 
-/// Test Glob with innocent pattern searching symlink directory - STILL VULNERABLE
-///
-/// This test documents a REMAINING vulnerability that the one-liner does NOT fix.
+/// Test Glob with innocent pattern searching symlink directory - should BLOCK
 ///
 /// Attack: Create symlink named "backup" pointing to .cupcake, use Glob pattern "backup/**"
-/// Problem: Pattern "backup/**/*.rego" doesn't contain ".cupcake", so it's not blocked
-///
-/// This is a KNOWN LIMITATION because Glob patterns can't be canonicalized.
+/// Pattern "backup/**/*.rego" doesn't contain ".cupcake", but preprocessing resolves the
+/// pattern's literal prefix into `resolved_search_root`, which points into .cupcake
 #[cfg(unix)]
 #[tokio::test]
-#[ignore] // TODO: This vulnerability remains unfixed - requires complex pattern parsing
-async fn test_glob_innocent_pattern_symlink_directory_vulnerable() -> Result<()> {
+async fn test_glob_innocent_pattern_symlink_directory_blocked() -> Result<()> {
     init_test_logging();
 
     let temp_dir = TempDir::new()?;
@@ -194,13 +192,9 @@ async fn test_glob_innocent_pattern_symlink_directory_vulnerable() -> Result<()>
 
     let decision = engine.evaluate(&event, None).await?;
 
-    // SHOULD be blocked, but currently isn't because:
-    // - Pattern "backup/**/*.rego" doesn't contain ".cupcake"
-    // - We can't canonicalize glob patterns
-    // - This is a known limitation
     assert!(
         matches!(decision, FinalDecision::Halt { .. }),
-        "Glob searching symlink directory should be blocked (CURRENTLY FAILS)"
+        "Glob searching symlink directory should be blocked"
     );
 
     Ok(())
@@ -184,8 +184,10 @@ ask contains decision if {
     let empty_global = TempDir::new()?;
     let config = EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: HarnessType::Cursor,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -495,8 +497,10 @@ deny contains decision if {
     let empty_global = TempDir::new()?;
     let config = EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: HarnessType::Cursor,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
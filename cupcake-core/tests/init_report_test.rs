@@ -0,0 +1,59 @@
+//! Tests for the engine initialization report
+
+mod common;
+
+use cupcake_core::engine::namespaces::Tier;
+use cupcake_core::engine::{Engine, EngineConfig};
+use cupcake_core::harness::types::HarnessType;
+use std::fs;
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn test_init_report_records_loaded_and_skipped_policies() {
+    common::init_test_logging();
+
+    let project = TempDir::new().unwrap();
+    common::create_test_project_for_harness(project.path(), HarnessType::ClaudeCode).unwrap();
+
+    // A policy without routing metadata is skipped, with the reason recorded
+    let unrouted = project
+        .path()
+        .join(".cupcake/policies/claude/unrouted.rego");
+    fs::write(
+        &unrouted,
+        "package cupcake.policies.unrouted\n\nimport rego.v1\n\ndeny contains \"x\" if false\n",
+    )
+    .unwrap();
+
+    let empty_global = TempDir::new().unwrap();
+    let mut config = EngineConfig::new(HarnessType::ClaudeCode);
+    config.global_config = Some(empty_global.path().to_path_buf());
+    let engine = Engine::new_with_config(project.path(), config)
+        .await
+        .unwrap();
+
+    let report = engine.init_report();
+    let project_report = report.tier(Tier::Project).expect("project tier reported");
+    assert_eq!(project_report.policies_discovered, 3);
+    assert!(project_report
+        .policies_loaded
+        .contains(&"cupcake.system".to_string()));
+    assert_eq!(project_report.skipped.len(), 1);
+    assert_eq!(project_report.skipped[0].path, unrouted);
+    assert!(project_report.skipped[0]
+        .reason
+        .contains("missing routing directive"));
+    assert!(project_report.compile_ms.is_some());
+    assert_eq!(
+        project_report.wasm_bytes,
+        engine.wasm_module().map(|wasm| wasm.len())
+    );
+
+    // Global policies are compiled lazily, so the global tier has no WASM yet
+    let global_report = report.tier(Tier::Global).expect("global tier reported");
+    assert_eq!(global_report.policies_discovered, 0);
+    assert_eq!(global_report.wasm_bytes, None);
+
+    let json = serde_json::to_value(report).unwrap();
+    assert_eq!(json["tiers"][1]["tier"], "project");
+}
@@ -0,0 +1,100 @@
+//! Integration test for license_header builtin
+//!
+//! Tests that new files missing their license header are blocked while files
+//! starting with the header are allowed
+
+mod common;
+use common::{create_builtin_project, engine_without_global_config};
+
+use anyhow::Result;
+use cupcake_core::engine::decision::FinalDecision;
+use cupcake_core::harness::types::HarnessType;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+fn created_file_event(temp_dir: &TempDir, file_path: &str, content: &str) -> Value {
+    json!({
+        "hook_event_name": "PostToolUse",
+        "session_id": "test-session",
+        "transcript_path": "/tmp/transcript.md",
+        "cwd": temp_dir.path().to_str().unwrap(),
+        "tool_name": "Write",
+        "tool_input": {
+            "file_path": file_path,
+            "content": content
+        },
+        "tool_response": {
+            "type": "create",
+            "filePath": file_path
+        }
+    })
+}
+
+/// Test license_header decisions through the engine
+#[tokio::test]
+async fn test_license_header_decisions() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    // Use the actual license_header template - the engine renders it on load
+    create_builtin_project(
+        temp_dir.path(),
+        HarnessType::ClaudeCode,
+        "license_header",
+        include_str!("../../fixtures/builtins/license_header.rego"),
+        r#"
+builtins:
+  license_header:
+    enabled: true
+    action: block
+    headers:
+      rs: "// SPDX-License-Identifier: MIT"
+"#,
+    )?;
+    let engine = engine_without_global_config(temp_dir.path(), HarnessType::ClaudeCode).await?;
+
+    // Test 1: BLOCK a new file without the header
+    let decision = engine
+        .evaluate(
+            &created_file_event(&temp_dir, "src/new.rs", "fn main() {}\n"),
+            None,
+        )
+        .await?;
+    match decision {
+        FinalDecision::Block { reason, .. } => {
+            assert!(
+                reason.contains("src/new.rs") && reason.contains("// SPDX-License-Identifier: MIT"),
+                "Should show the missing header: {reason}"
+            );
+        }
+        _ => panic!("Expected Block for missing header, got: {decision:?}"),
+    }
+
+    // Test 2: ALLOW a new file that starts with the header
+    let decision = engine
+        .evaluate(
+            &created_file_event(
+                &temp_dir,
+                "src/new.rs",
+                "// SPDX-License-Identifier: MIT\n\nfn main() {}\n",
+            ),
+            None,
+        )
+        .await?;
+    assert!(
+        matches!(decision, FinalDecision::Allow { .. }),
+        "Expected Allow with the header, got: {decision:?}"
+    );
+
+    // Test 3: ALLOW files with no configured header
+    let decision = engine
+        .evaluate(
+            &created_file_event(&temp_dir, "README.md", "# Title\n"),
+            None,
+        )
+        .await?;
+    assert!(
+        matches!(decision, FinalDecision::Allow { .. }),
+        "Expected Allow for unconfigured extension, got: {decision:?}"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+//! Integration test for mass_deletion_guard builtin
+//!
+//! Tests that edits and commands removing more than the configured limits
+//! are blocked while small ones are allowed
+
+mod common;
+use common::{create_builtin_project, engine_without_global_config};
+
+use anyhow::Result;
+use cupcake_core::engine::decision::FinalDecision;
+use cupcake_core::harness::types::HarnessType;
+use serde_json::json;
+use tempfile::TempDir;
+
+/// Test mass_deletion_guard decisions through the engine
+#[tokio::test]
+async fn test_mass_deletion_guard_decisions() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    create_builtin_project(
+        temp_dir.path(),
+        HarnessType::ClaudeCode,
+        "mass_deletion_guard",
+        include_str!("../../fixtures/builtins/mass_deletion_guard.rego"),
+        r#"
+builtins:
+  mass_deletion_guard:
+    enabled: true
+    max_deleted_lines: 5
+    max_files: 3
+"#,
+    )?;
+    let engine = engine_without_global_config(temp_dir.path(), HarnessType::ClaudeCode).await?;
+
+    // Test 1: DENY removing more files than the limit
+    let rm_event = json!({
+        "hook_event_name": "PreToolUse",
+        "session_id": "test-session",
+        "transcript_path": "/tmp/transcript.md",
+        "cwd": temp_dir.path().to_str().unwrap(),
+        "tool_name": "Bash",
+        "tool_input": {
+            "command": "rm -f a.rs b.rs c.rs d.rs"
+        }
+    });
+
+    let decision = engine.evaluate(&rm_event, None).await?;
+    match decision {
+        FinalDecision::Deny { reason, .. } => {
+            assert!(
+                reason.contains("command touches 4 files, limit is 3"),
+                "Should report the file count: {reason}"
+            );
+        }
+        _ => panic!("Expected Deny for mass removal, got: {decision:?}"),
+    }
+
+    // Test 2: DENY an edit removing more lines than the limit
+    let edit_event = json!({
+        "hook_event_name": "PreToolUse",
+        "session_id": "test-session",
+        "transcript_path": "/tmp/transcript.md",
+        "cwd": temp_dir.path().to_str().unwrap(),
+        "tool_name": "Edit",
+        "tool_input": {
+            "file_path": "src/lib.rs",
+            "old_string": "1\n2\n3\n4\n5\n6\n7\n8\n9\n10",
+            "new_string": "1"
+        }
+    });
+
+    let decision = engine.evaluate(&edit_event, None).await?;
+    assert!(
+        matches!(decision, FinalDecision::Deny { .. }),
+        "Expected Deny for large deletion, got: {decision:?}"
+    );
+
+    // Test 3: ALLOW removals within the limits
+    let small_rm_event = json!({
+        "hook_event_name": "PreToolUse",
+        "session_id": "test-session",
+        "transcript_path": "/tmp/transcript.md",
+        "cwd": temp_dir.path().to_str().unwrap(),
+        "tool_name": "Bash",
+        "tool_input": {
+            "command": "rm a.rs b.rs && git status"
+        }
+    });
+
+    let decision = engine.evaluate(&small_rm_event, None).await?;
+    assert!(
+        matches!(decision, FinalDecision::Allow { .. }),
+        "Expected Allow for small removal, got: {decision:?}"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+//! Integration test for mcp_allowlist builtin
+//!
+//! Tests that `mcp__<server>__<tool>` calls are routed to the policy and
+//! decided by the server and tool allowlist
+
+mod common;
+use common::{create_builtin_project, engine_without_global_config};
+
+use anyhow::Result;
+use cupcake_core::engine::decision::FinalDecision;
+use cupcake_core::harness::types::HarnessType;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+fn mcp_event(temp_dir: &TempDir, tool_name: &str) -> Value {
+    json!({
+        "hook_event_name": "PreToolUse",
+        "session_id": "test-session",
+        "transcript_path": "/tmp/transcript.md",
+        "cwd": temp_dir.path().to_str().unwrap(),
+        "tool_name": tool_name,
+        "tool_input": {
+            "owner": "eqtylab",
+            "repo": "cupcake"
+        }
+    })
+}
+
+/// Test mcp_allowlist decisions through the engine
+#[tokio::test]
+async fn test_mcp_allowlist_decisions() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    // Use the actual mcp_allowlist template - the engine renders it on load
+    create_builtin_project(
+        temp_dir.path(),
+        HarnessType::ClaudeCode,
+        "mcp_allowlist",
+        include_str!("../../fixtures/builtins/mcp_allowlist.rego"),
+        r#"
+builtins:
+  mcp_allowlist:
+    enabled: true
+    message: "MCP call not allowed"
+    servers:
+      github: ["get_*", "list_issues"]
+      filesystem: []
+"#,
+    )?;
+    let engine = engine_without_global_config(temp_dir.path(), HarnessType::ClaudeCode).await?;
+
+    // Test 1: DENY a tool that isn't allowed on a listed server
+    let decision = engine
+        .evaluate(
+            &mcp_event(&temp_dir, "mcp__github__delete_repository"),
+            None,
+        )
+        .await?;
+    match decision {
+        FinalDecision::Deny { reason, .. } => {
+            assert!(
+                reason.contains("mcp__github__delete_repository"),
+                "Should name the tool: {reason}"
+            );
+        }
+        _ => panic!("Expected Deny for unlisted tool, got: {decision:?}"),
+    }
+
+    // Test 2: DENY any tool on a server that isn't listed
+    let decision = engine
+        .evaluate(&mcp_event(&temp_dir, "mcp__slack__post_message"), None)
+        .await?;
+    assert!(
+        matches!(decision, FinalDecision::Deny { .. }),
+        "Expected Deny for unlisted server, got: {decision:?}"
+    );
+
+    // Test 3: ALLOW listed tools, wildcards and servers with every tool allowed
+    for tool_name in [
+        "mcp__github__list_issues",
+        "mcp__github__get_pull_request",
+        "mcp__filesystem__read_file",
+    ] {
+        let decision = engine
+            .evaluate(&mcp_event(&temp_dir, tool_name), None)
+            .await?;
+        assert!(
+            matches!(decision, FinalDecision::Allow { .. }),
+            "Expected Allow for {tool_name}, got: {decision:?}"
+        );
+    }
+
+    Ok(())
+}
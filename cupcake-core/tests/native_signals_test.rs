@@ -0,0 +1,77 @@
+//! Tests for native (builtin.*) signal injection through the Executor
+
+use anyhow::Result;
+use cupcake_core::engine::executor::Executor;
+use cupcake_core::engine::rulebook::{Rulebook, SignalConfig, DEFAULT_MAX_OUTPUT_BYTES};
+use cupcake_core::engine::{PolicyUnit, RoutingDirective};
+use serde_json::json;
+use tempfile::TempDir;
+
+fn policy_requiring(signals: &[&str]) -> PolicyUnit {
+    PolicyUnit {
+        path: "policies/claude/example.rego".into(),
+        package_name: "cupcake.policies.example".to_string(),
+        routing: RoutingDirective {
+            required_events: vec!["PreToolUse".to_string()],
+            required_tools: vec!["Bash".to_string()],
+            required_signals: signals.iter().map(|s| s.to_string()).collect(),
+            required_watchdog: false,
+        },
+        metadata: None,
+        rendered: None,
+    }
+}
+
+#[tokio::test]
+async fn test_native_signals_injected_alongside_rulebook_signals() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let mut rulebook = Rulebook::default();
+    rulebook.signals.insert(
+        "custom".to_string(),
+        SignalConfig {
+            command: "echo custom".into(),
+            timeout_seconds: 5,
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            sandbox: None,
+            limits: None,
+        },
+    );
+
+    let exec = Executor {
+        rulebook: Some(&rulebook),
+        global_rulebook: None,
+        watchdog: None,
+        working_dir: temp_dir.path(),
+    };
+
+    let input = json!({"hook_event_name": "PreToolUse", "tool_name": "Bash"});
+    let policies = [policy_requiring(&["builtin.os", "builtin.git", "custom"])];
+    let enriched = exec.gather_signals(&input, &policies, None).await?;
+
+    let signals = &enriched["signals"];
+    assert_eq!(signals["custom"], json!("custom"));
+    assert_eq!(signals["builtin"]["os"]["os"], json!(std::env::consts::OS));
+    assert_eq!(signals["builtin"]["git"]["available"], json!(false));
+    assert!(signals["builtin"].get("time").is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_native_signals_work_without_rulebook() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let exec = Executor {
+        rulebook: None,
+        global_rulebook: None,
+        watchdog: None,
+        working_dir: temp_dir.path(),
+    };
+
+    let input = json!({"hook_event_name": "PreToolUse", "tool_name": "Bash"});
+    let policies = [policy_requiring(&["builtin.time"])];
+    let enriched = exec.gather_signals(&input, &policies, None).await?;
+
+    assert!(enriched["signals"]["builtin"]["time"]["unix"].is_i64());
+
+    Ok(())
+}
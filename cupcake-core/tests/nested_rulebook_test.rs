@@ -0,0 +1,124 @@
+//! Tests for per-directory rulebook overrides (monorepo support)
+//!
+//! A nested `.cupcake/rulebook.yml` in a subpackage is merged over the
+//! repository root rulebook for events that target files in that subpackage.
+
+use anyhow::Result;
+use cupcake_core::engine::rulebook::Rulebook;
+use cupcake_core::engine::ProjectPaths;
+use std::fs;
+use tempfile::TempDir;
+
+fn create_monorepo() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    let root = temp_dir.path();
+
+    fs::create_dir_all(root.join(".cupcake/signals"))?;
+    fs::write(
+        root.join(".cupcake/signals/lint.sh"),
+        "#!/bin/sh\necho root\n",
+    )?;
+    fs::write(
+        root.join(".cupcake/rulebook.yml"),
+        r#"
+signals:
+  team:
+    command: "echo root"
+builtins:
+  protected_paths:
+    message: "Root message"
+    paths:
+      - "/etc/"
+"#,
+    )?;
+
+    fs::create_dir_all(root.join("packages/api/.cupcake/signals"))?;
+    fs::create_dir_all(root.join("packages/api/src"))?;
+    fs::write(
+        root.join("packages/api/.cupcake/rulebook.yml"),
+        r#"
+builtins:
+  protected_paths:
+    paths:
+      - "migrations/"
+"#,
+    )?;
+    fs::write(
+        root.join("packages/api/.cupcake/signals/lint.sh"),
+        "#!/bin/sh\necho api\n",
+    )?;
+
+    // A subpackage .cupcake/ without a rulebook is not an override
+    fs::create_dir_all(root.join("packages/web/.cupcake"))?;
+    fs::create_dir_all(root.join("packages/web/src"))?;
+
+    Ok(temp_dir)
+}
+
+#[test]
+fn test_nearest_nested_cupcake_dir_is_found() -> Result<()> {
+    let temp_dir = create_monorepo()?;
+    let root = temp_dir.path().canonicalize()?;
+    let paths = ProjectPaths::resolve(&root)?;
+
+    // Existing and not-yet-created files both resolve to the subpackage
+    let nested = paths.nested_cupcake_dir_for(&root.join("packages/api/src/main.rs"));
+    assert_eq!(nested, Some(root.join("packages/api/.cupcake")));
+
+    let nested = paths.nested_cupcake_dir_for(std::path::Path::new("packages/api/src"));
+    assert_eq!(nested, Some(root.join("packages/api/.cupcake")));
+
+    // No rulebook in packages/web, and the root .cupcake is never an override
+    assert_eq!(
+        paths.nested_cupcake_dir_for(&root.join("packages/web/src/index.ts")),
+        None
+    );
+    assert_eq!(paths.nested_cupcake_dir_for(&root.join("README.md")), None);
+
+    // Paths outside the project are ignored
+    assert_eq!(
+        paths.nested_cupcake_dir_for(std::path::Path::new("/tmp/elsewhere.txt")),
+        None
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_nested_rulebook_merges_over_root() -> Result<()> {
+    let temp_dir = create_monorepo()?;
+    let root = temp_dir.path().canonicalize()?;
+    let nested = root.join("packages/api/.cupcake");
+
+    let rulebook = Rulebook::load_layered_with_conventions(
+        &[
+            root.join(".cupcake/rulebook.yml"),
+            nested.join("rulebook.yml"),
+        ],
+        &[root.join(".cupcake/signals"), nested.join("signals")],
+    )
+    .await?;
+
+    let protected = rulebook
+        .builtins
+        .protected_paths
+        .as_ref()
+        .expect("protected_paths");
+    assert_eq!(protected.message, "Root message");
+    assert_eq!(protected.paths, vec!["migrations/".to_string()]);
+
+    // Root signals are inherited
+    assert_eq!(
+        rulebook.get_signal("team").unwrap().command.to_string(),
+        "echo root"
+    );
+
+    // Nested signal scripts shadow root scripts with the same name
+    let lint = rulebook.get_signal("lint").unwrap();
+    assert!(lint
+        .command
+        .to_string()
+        .contains("packages/api/.cupcake/signals"));
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+//! Integration test for obfuscation_guard builtin
+//!
+//! Tests that commands assembled at runtime through eval or aliases are
+//! stopped while plain commands are allowed
+
+mod common;
+use common::{create_builtin_project, engine_without_global_config};
+
+use anyhow::Result;
+use cupcake_core::engine::decision::FinalDecision;
+use cupcake_core::harness::types::HarnessType;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+fn bash_event(temp_dir: &TempDir, command: &str) -> Value {
+    json!({
+        "hook_event_name": "PreToolUse",
+        "session_id": "test-session",
+        "transcript_path": "/tmp/transcript.md",
+        "cwd": temp_dir.path().to_str().unwrap(),
+        "tool_name": "Bash",
+        "tool_input": {
+            "command": command
+        }
+    })
+}
+
+/// Test obfuscation_guard decisions through the engine
+#[tokio::test]
+async fn test_obfuscation_guard_decisions() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    // Use the actual obfuscation_guard template - the engine renders it on load
+    create_builtin_project(
+        temp_dir.path(),
+        HarnessType::ClaudeCode,
+        "obfuscation_guard",
+        include_str!("../../fixtures/builtins/obfuscation_guard.rego"),
+        r#"
+builtins:
+  obfuscation_guard:
+    enabled: true
+    action: deny
+    message: "Obfuscated command blocked"
+    ignore_indicators: ["alias_definition"]
+"#,
+    )?;
+    let engine = engine_without_global_config(temp_dir.path(), HarnessType::ClaudeCode).await?;
+
+    // Test 1: DENY eval of a variable, showing what it expands to
+    let decision = engine
+        .evaluate(&bash_event(&temp_dir, "X='rm -rf /'; eval \"$X\""), None)
+        .await?;
+    match decision {
+        FinalDecision::Deny { reason, .. } => {
+            assert!(
+                reason.contains("eval") && reason.contains("rm -rf /"),
+                "Should show the expanded command: {reason}"
+            );
+        }
+        _ => panic!("Expected Deny for eval, got: {decision:?}"),
+    }
+
+    // Test 2: DENY running a command through an alias
+    let decision = engine
+        .evaluate(&bash_event(&temp_dir, "alias nuke='rm -rf'; nuke /"), None)
+        .await?;
+    match decision {
+        FinalDecision::Deny { reason, .. } => {
+            assert!(
+                reason.contains("alias_use"),
+                "Should flag the alias use: {reason}"
+            );
+        }
+        _ => panic!("Expected Deny for alias use, got: {decision:?}"),
+    }
+
+    // Test 3: ALLOW plain commands, including an ignored indicator kind
+    for command in ["ls -la | grep foo", "alias ll='ls -la'"] {
+        let decision = engine
+            .evaluate(&bash_event(&temp_dir, command), None)
+            .await?;
+        assert!(
+            matches!(decision, FinalDecision::Allow { .. }),
+            "Expected Allow for '{command}', got: {decision:?}"
+        );
+    }
+
+    Ok(())
+}
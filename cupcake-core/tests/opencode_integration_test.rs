@@ -110,8 +110,10 @@ deny contains decision if {
     let config = EngineConfig {
         harness: HarnessType::OpenCode,
         wasm_max_memory: Some(10 * 1024 * 1024),
+        wasm_pool_size: None,
         opa_path: None,
         global_config: Some(setup._empty_global.path().to_path_buf()),
+        team_config: None,
         debug_routing: false,
     };
     let engine = Engine::new_with_config(setup.project_dir.path(), config).await?;
@@ -171,8 +173,10 @@ deny contains decision if {
     let config = EngineConfig {
         harness: HarnessType::OpenCode,
         wasm_max_memory: Some(10 * 1024 * 1024),
+        wasm_pool_size: None,
         opa_path: None,
         global_config: Some(setup._empty_global.path().to_path_buf()),
+        team_config: None,
         debug_routing: false,
     };
     let engine = Engine::new_with_config(setup.project_dir.path(), config).await?;
@@ -231,8 +235,10 @@ add_context contains message if {
     let config = EngineConfig {
         harness: HarnessType::OpenCode,
         wasm_max_memory: Some(10 * 1024 * 1024),
+        wasm_pool_size: None,
         opa_path: None,
         global_config: Some(setup._empty_global.path().to_path_buf()),
+        team_config: None,
         debug_routing: false,
     };
     let engine = Engine::new_with_config(setup.project_dir.path(), config).await?;
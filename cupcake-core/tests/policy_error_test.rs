@@ -0,0 +1,126 @@
+//! Tests for `on_policy_error` - partial loading when a policy is broken
+
+mod common;
+
+use anyhow::Result;
+use cupcake_core::engine::namespaces::Tier;
+use cupcake_core::engine::rulebook::{OnPolicyError, Rulebook};
+use cupcake_core::engine::{Engine, EngineConfig};
+use cupcake_core::harness::types::HarnessType;
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Parses and routes fine, but OPA rejects the unsafe variable
+const BROKEN_POLICY: &str = r#"# METADATA
+# scope: package
+# custom:
+#   routing:
+#     required_events: ["PreToolUse"]
+#     required_tools: ["Bash"]
+package cupcake.policies.broken
+
+import rego.v1
+
+deny contains decision if {
+    decision := {"reason": undefined_var, "severity": "HIGH", "rule_id": "BROKEN-001"}
+}
+"#;
+
+const DENY_RM_POLICY: &str = r#"# METADATA
+# scope: package
+# custom:
+#   routing:
+#     required_events: ["PreToolUse"]
+#     required_tools: ["Bash"]
+package cupcake.policies.deny_rm
+
+import rego.v1
+
+deny contains decision if {
+    contains(input.tool_input.command, "rm -rf")
+    decision := {"reason": "No rm -rf", "severity": "HIGH", "rule_id": "RM-001"}
+}
+"#;
+
+fn project_with_broken_policy(rulebook: &str) -> Result<(TempDir, PathBuf)> {
+    let project = TempDir::new()?;
+    common::create_test_project_for_harness(project.path(), HarnessType::ClaudeCode)?;
+    let claude_dir = project.path().join(".cupcake/policies/claude");
+    fs::write(claude_dir.join("deny_rm.rego"), DENY_RM_POLICY)?;
+    let broken = claude_dir.join("broken.rego");
+    fs::write(&broken, BROKEN_POLICY)?;
+    fs::write(project.path().join(".cupcake/rulebook.yml"), rulebook)?;
+    Ok((project, broken))
+}
+
+async fn engine_for(project: &Path) -> Result<Engine> {
+    let empty_global = TempDir::new()?;
+    let mut config = EngineConfig::new(HarnessType::ClaudeCode);
+    config.global_config = Some(empty_global.path().to_path_buf());
+    Engine::new_with_config(project, config).await
+}
+
+#[tokio::test]
+async fn test_on_policy_error_defaults_to_skip() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let rulebook_path = temp_dir.path().join("rulebook.yml");
+
+    fs::write(&rulebook_path, "signals: {}\n")?;
+    let rulebook = Rulebook::load_with_conventions(&rulebook_path, temp_dir.path()).await?;
+    assert_eq!(rulebook.on_policy_error, OnPolicyError::Skip);
+
+    fs::write(&rulebook_path, "on_policy_error: fail\n")?;
+    let rulebook = Rulebook::load_with_conventions(&rulebook_path, temp_dir.path()).await?;
+    assert_eq!(rulebook.on_policy_error, OnPolicyError::Fail);
+
+    fs::write(&rulebook_path, "on_policy_error: ignore\n")?;
+    assert!(
+        Rulebook::load_with_conventions(&rulebook_path, temp_dir.path())
+            .await
+            .is_err()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_skip_excludes_broken_policy_and_keeps_the_rest() -> Result<()> {
+    common::init_test_logging();
+    let (project, broken) = project_with_broken_policy("signals: {}\n")?;
+
+    let engine = engine_for(project.path()).await?;
+
+    let report = engine.init_report().tier(Tier::Project).unwrap();
+    assert_eq!(report.skipped.len(), 1);
+    assert_eq!(report.skipped[0].path, broken);
+    assert!(report.skipped[0].reason.starts_with("failed to compile"));
+    assert!(!report
+        .policies_loaded
+        .contains(&"cupcake.policies.broken".to_string()));
+    assert_eq!(engine.policy_load_errors().len(), 1);
+
+    // The remaining policies still govern
+    let event = json!({
+        "hook_event_name": "PreToolUse",
+        "session_id": "test",
+        "cwd": "/tmp",
+        "tool_name": "Bash",
+        "tool_input": {"command": "rm -rf /"}
+    });
+    let decision = engine.evaluate(&event, None).await?;
+    assert!(decision.is_blocking(), "Expected deny, got {decision:?}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fail_refuses_to_load() -> Result<()> {
+    common::init_test_logging();
+    let (project, broken) = project_with_broken_policy("on_policy_error: fail\n")?;
+
+    let error = engine_for(project.path()).await.err().expect("should fail");
+    let message = format!("{error:#}");
+    assert!(message.contains(&broken.display().to_string()), "{message}");
+    assert!(message.contains("rego_"), "{message}");
+    Ok(())
+}
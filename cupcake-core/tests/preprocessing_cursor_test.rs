@@ -161,9 +161,16 @@ fn test_cursor_script_execution_detection() {
 
     let config = PreprocessConfig {
         normalize_whitespace: true,
+        normalize_unicode: true,
         audit_transformations: false,
         enable_script_inspection: false, // Not testing script inspection in this test
         enable_symlink_resolution: false, // Not testing symlink resolution in this test
+        enable_shell_parsing: false,     // Not testing shell parsing in this test
+        decode_payloads: false,          // Not testing payload decoding in this test
+        detect_obfuscation: false,       // Not testing obfuscation detection in this test
+        normalize_windows: false,        // Not testing Windows normalization in this test
+        inspect_archives: false,         // Not testing archive inspection in this test
+        attach_canonical_event: false,   // Not testing the canonical view in this test
     };
 
     // For now, just test that command is normalized
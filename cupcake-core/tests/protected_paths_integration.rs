@@ -36,7 +36,7 @@ async fn test_protected_paths_read_write_distinction() -> Result<()> {
     fs::write(shared_system_dir.join("commands.rego"), helpers_commands)?;
 
     // Use the actual protected_paths policy from Claude fixtures
-    let protected_policy = include_str!("../../fixtures/claude/builtins/protected_paths.rego");
+    let protected_policy = include_str!("../../fixtures/builtins/protected_paths.rego");
     fs::write(builtins_dir.join("protected_paths.rego"), protected_policy)?;
 
     // Create rulebook with protected_paths configuration
@@ -58,8 +58,10 @@ builtins:
     let empty_global = TempDir::new()?;
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -205,7 +207,7 @@ async fn test_protected_paths_bash_whitelist() -> Result<()> {
     let helpers_commands = include_str!("../../fixtures/system/commands.rego");
     fs::write(shared_system_dir.join("commands.rego"), helpers_commands)?;
 
-    let protected_policy = include_str!("../../fixtures/claude/builtins/protected_paths.rego");
+    let protected_policy = include_str!("../../fixtures/builtins/protected_paths.rego");
     fs::write(builtins_dir.join("protected_paths.rego"), protected_policy)?;
 
     let rulebook_content = r#"
@@ -222,8 +224,10 @@ builtins:
     let empty_global = TempDir::new()?;
     let config = cupcake_core::engine::EngineConfig {
         global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
         harness: cupcake_core::harness::types::HarnessType::ClaudeCode,
         wasm_max_memory: None,
+        wasm_pool_size: None,
         opa_path: None,
         debug_routing: false,
     };
@@ -266,6 +270,10 @@ builtins:
         "rm secure.txt",
         "sed -i 's/old/new/g' secure.txt",
         "vim secure.txt", // Not in whitelist
+        // Chained commands are checked one logical command at a time
+        "cat README.md && rm secure.txt",
+        "rm secure.txt; cat other.txt",
+        "true | (cd /tmp && mv secure.txt backup.txt)",
     ];
 
     for cmd in write_commands {
@@ -0,0 +1,89 @@
+//! Integration test for rate_limit builtin
+//!
+//! Tests that the engine counts invocations per session and the policy
+//! escalates once a limit is exceeded
+
+mod common;
+use common::{create_builtin_project, engine_without_global_config};
+
+use anyhow::Result;
+use cupcake_core::engine::decision::FinalDecision;
+use cupcake_core::harness::types::HarnessType;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+fn push_event(temp_dir: &TempDir, session_id: &str) -> Value {
+    json!({
+        "hook_event_name": "PreToolUse",
+        "session_id": session_id,
+        "transcript_path": "/tmp/transcript.md",
+        "cwd": temp_dir.path().to_str().unwrap(),
+        "tool_name": "Bash",
+        "tool_input": {
+            "command": "git push origin main"
+        }
+    })
+}
+
+/// Test that the call after the limit is escalated to the user
+#[tokio::test]
+async fn test_rate_limit_escalates_after_max() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    // Use the actual rate_limit template - the engine renders it on load
+    create_builtin_project(
+        temp_dir.path(),
+        HarnessType::ClaudeCode,
+        "rate_limit",
+        include_str!("../../fixtures/builtins/rate_limit.rego"),
+        r#"
+builtins:
+  rate_limit:
+    enabled: true
+    action: ask
+    message: "Too many pushes"
+    limits:
+      - name: git_push
+        tools: [Bash]
+        command: "git push"
+        max: 2
+        per: session
+"#,
+    )?;
+    let engine = engine_without_global_config(temp_dir.path(), HarnessType::ClaudeCode).await?;
+
+    // Test 1: ALLOW calls within the limit
+    for attempt in 1..=2 {
+        let decision = engine
+            .evaluate(&push_event(&temp_dir, "session-a"), None)
+            .await?;
+        assert!(
+            matches!(decision, FinalDecision::Allow { .. }),
+            "Expected Allow for push {attempt} of 2, got: {decision:?}"
+        );
+    }
+
+    // Test 2: ASK on the call past the limit
+    let decision = engine
+        .evaluate(&push_event(&temp_dir, "session-a"), None)
+        .await?;
+    match decision {
+        FinalDecision::Ask { reason, .. } => {
+            assert!(
+                reason.contains("Too many pushes") && reason.contains("git_push"),
+                "Should name the limit: {reason}"
+            );
+        }
+        _ => panic!("Expected Ask past the limit, got: {decision:?}"),
+    }
+
+    // Test 3: ALLOW in another session - counts are per session
+    let decision = engine
+        .evaluate(&push_event(&temp_dir, "session-b"), None)
+        .await?;
+    assert!(
+        matches!(decision, FinalDecision::Allow { .. }),
+        "Expected Allow in a fresh session, got: {decision:?}"
+    );
+
+    Ok(())
+}
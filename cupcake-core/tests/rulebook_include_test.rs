@@ -0,0 +1,121 @@
+//! Tests for `include:` support in rulebook.yml
+//!
+//! Included rulebooks are merged in order and the including file always wins.
+
+use anyhow::Result;
+use cupcake_core::engine::rulebook::Rulebook;
+use std::fs;
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn test_include_merges_shared_baseline() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let shared_dir = temp_dir.path().join("shared");
+    let project_dir = temp_dir.path().join("project");
+    fs::create_dir_all(&shared_dir)?;
+    fs::create_dir_all(&project_dir)?;
+
+    fs::write(
+        shared_dir.join("security.yml"),
+        r#"
+signals:
+  org_signal:
+    command: "echo org"
+builtins:
+  git_block_no_verify:
+    enabled: true
+  protected_paths:
+    message: "Org message"
+    paths:
+      - "/etc/"
+"#,
+    )?;
+
+    fs::write(
+        project_dir.join("rulebook.yml"),
+        r#"
+include:
+  - ../shared/security.yml
+signals:
+  project_signal:
+    command: "echo project"
+builtins:
+  protected_paths:
+    paths:
+      - "src/"
+"#,
+    )?;
+
+    let rulebook = Rulebook::load(project_dir.join("rulebook.yml")).await?;
+
+    // Signals from both files are present
+    assert!(rulebook.signals.contains_key("org_signal"));
+    assert!(rulebook.signals.contains_key("project_signal"));
+
+    // Builtins only configured in the include are kept
+    assert!(rulebook.builtins.git_block_no_verify.is_some());
+
+    // Nested mappings merge key by key, lists are replaced by the including file
+    let protected = rulebook.builtins.protected_paths.expect("protected_paths");
+    assert_eq!(protected.message, "Org message");
+    assert_eq!(protected.paths, vec!["src/".to_string()]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_later_includes_override_earlier() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+
+    fs::write(
+        temp_dir.path().join("a.yml"),
+        "signals:\n  shared:\n    command: \"echo a\"\n    timeout_seconds: 9\n",
+    )?;
+    fs::write(
+        temp_dir.path().join("b.yml"),
+        "signals:\n  shared:\n    command: \"echo b\"\n",
+    )?;
+    fs::write(
+        temp_dir.path().join("rulebook.yml"),
+        "include:\n  - a.yml\n  - b.yml\n",
+    )?;
+
+    let rulebook = Rulebook::load(temp_dir.path().join("rulebook.yml")).await?;
+    let signal = rulebook.get_signal("shared").expect("shared signal");
+    assert_eq!(signal.command.to_string(), "echo b");
+    assert_eq!(signal.timeout_seconds, 9);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_circular_include_is_rejected() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+
+    fs::write(temp_dir.path().join("a.yml"), "include:\n  - b.yml\n")?;
+    fs::write(temp_dir.path().join("b.yml"), "include:\n  - a.yml\n")?;
+
+    let err = Rulebook::load(temp_dir.path().join("a.yml"))
+        .await
+        .expect_err("cycle should fail");
+    assert!(format!("{err:#}").contains("Circular rulebook include"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_missing_include_is_an_error() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+
+    fs::write(
+        temp_dir.path().join("rulebook.yml"),
+        "include:\n  - does-not-exist.yml\n",
+    )?;
+
+    let err = Rulebook::load(temp_dir.path().join("rulebook.yml"))
+        .await
+        .expect_err("missing include should fail");
+    assert!(format!("{err:#}").contains("does-not-exist.yml"));
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use cupcake_core::engine::rulebook::{Rulebook, SignalConfig};
+use cupcake_core::engine::rulebook::{Rulebook, SignalConfig, DEFAULT_MAX_OUTPUT_BYTES};
 use serde_json::{json, Value};
 
 #[tokio::test]
@@ -9,8 +9,11 @@ async fn test_signal_json_parsing_string() {
     rulebook.signals.insert(
         "test_string".to_string(),
         SignalConfig {
-            command: r#"echo '"hello world"'"#.to_string(),
+            command: r#"echo '"hello world"'"#.into(),
             timeout_seconds: 5,
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            sandbox: None,
+            limits: None,
         },
     );
 
@@ -28,8 +31,11 @@ async fn test_signal_json_parsing_object() {
     rulebook.signals.insert(
         "test_object".to_string(),
         SignalConfig {
-            command: r#"echo '{"key": "value", "number": 42, "bool": true}'"#.to_string(),
+            command: r#"echo '{"key": "value", "number": 42, "bool": true}'"#.into(),
             timeout_seconds: 5,
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            sandbox: None,
+            limits: None,
         },
     );
 
@@ -52,8 +58,11 @@ async fn test_signal_json_parsing_array() {
     rulebook.signals.insert(
         "test_array".to_string(),
         SignalConfig {
-            command: r#"echo '["item1", "item2", 123]'"#.to_string(),
+            command: r#"echo '["item1", "item2", 123]'"#.into(),
             timeout_seconds: 5,
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            sandbox: None,
+            limits: None,
         },
     );
 
@@ -72,8 +81,11 @@ async fn test_signal_invalid_json_fallback() {
     rulebook.signals.insert(
         "test_invalid".to_string(),
         SignalConfig {
-            command: r#"echo 'this is not valid JSON {'"#.to_string(),
+            command: r#"echo 'this is not valid JSON {'"#.into(),
             timeout_seconds: 5,
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            sandbox: None,
+            limits: None,
         },
     );
 
@@ -94,8 +106,11 @@ async fn test_signal_empty_output() {
     rulebook.signals.insert(
         "test_empty".to_string(),
         SignalConfig {
-            command: r#"echo"#.to_string(), // Just echo with no args
+            command: r#"echo"#.into(), // Just echo with no args
             timeout_seconds: 5,
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            sandbox: None,
+            limits: None,
         },
     );
 
@@ -113,8 +128,11 @@ async fn test_signal_whitespace_trimming() {
     rulebook.signals.insert(
         "test_whitespace".to_string(),
         SignalConfig {
-            command: r#"echo '   "trimmed"   '"#.to_string(),
+            command: r#"echo '   "trimmed"   '"#.into(),
             timeout_seconds: 5,
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            sandbox: None,
+            limits: None,
         },
     );
 
@@ -132,24 +150,33 @@ async fn test_execute_signals_concurrent() {
     rulebook.signals.insert(
         "signal1".to_string(),
         SignalConfig {
-            command: r#"echo '"value1"'"#.to_string(),
+            command: r#"echo '"value1"'"#.into(),
             timeout_seconds: 5,
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            sandbox: None,
+            limits: None,
         },
     );
 
     rulebook.signals.insert(
         "signal2".to_string(),
         SignalConfig {
-            command: r#"echo '{"key": "value2"}'"#.to_string(),
+            command: r#"echo '{"key": "value2"}'"#.into(),
             timeout_seconds: 5,
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            sandbox: None,
+            limits: None,
         },
     );
 
     rulebook.signals.insert(
         "signal3".to_string(),
         SignalConfig {
-            command: r#"echo '[1, 2, 3]'"#.to_string(),
+            command: r#"echo '[1, 2, 3]'"#.into(),
             timeout_seconds: 5,
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            sandbox: None,
+            limits: None,
         },
     );
 
@@ -175,8 +202,11 @@ async fn test_execute_signals_with_failures() {
     rulebook.signals.insert(
         "good_signal".to_string(),
         SignalConfig {
-            command: r#"echo '"success"'"#.to_string(),
+            command: r#"echo '"success"'"#.into(),
             timeout_seconds: 5,
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            sandbox: None,
+            limits: None,
         },
     );
 
@@ -184,8 +214,11 @@ async fn test_execute_signals_with_failures() {
     rulebook.signals.insert(
         "bad_signal".to_string(),
         SignalConfig {
-            command: r#"exit 1"#.to_string(),
+            command: r#"exit 1"#.into(),
             timeout_seconds: 5,
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            sandbox: None,
+            limits: None,
         },
     );
 
@@ -226,8 +259,11 @@ async fn test_complex_structured_signal() {
                 "commit": "abc123"
             }
         }'"#
-            .to_string(),
+            .into(),
             timeout_seconds: 5,
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            sandbox: None,
+            limits: None,
         },
     );
 
@@ -246,3 +282,46 @@ async fn test_complex_structured_signal() {
 
     assert_eq!(result, expected);
 }
+
+#[tokio::test]
+async fn test_signal_output_truncated_at_max_output_bytes() {
+    let mut rulebook = Rulebook::default();
+
+    // Runaway signal: far more output than the configured cap
+    rulebook.signals.insert(
+        "runaway".to_string(),
+        SignalConfig {
+            command: "yes cupcake | head -c 100000".into(),
+            timeout_seconds: 5,
+            max_output_bytes: 64,
+            sandbox: None,
+            limits: None,
+        },
+    );
+
+    let result = rulebook.execute_signal("runaway").await.unwrap();
+
+    assert_eq!(result["truncated"], json!(true));
+    assert_eq!(result["original_bytes"], json!(100000));
+    assert_eq!(result["max_output_bytes"], json!(64));
+    assert!(result["output"].as_str().unwrap().len() <= 64);
+}
+
+#[tokio::test]
+async fn test_signal_output_within_limit_is_not_truncated() {
+    let mut rulebook = Rulebook::default();
+
+    rulebook.signals.insert(
+        "small".to_string(),
+        SignalConfig {
+            command: r#"echo '{"ok": true}'"#.into(),
+            timeout_seconds: 5,
+            max_output_bytes: 64,
+            sandbox: None,
+            limits: None,
+        },
+    );
+
+    let result = rulebook.execute_signal("small").await.unwrap();
+    assert_eq!(result, json!({"ok": true}));
+}
@@ -0,0 +1,281 @@
+//! Integration tests for the team configuration tier
+//!
+//! A team config uses the project `.cupcake/` layout and is evaluated between
+//! global and project policies.
+
+use anyhow::Result;
+use cupcake_core::engine::decision::FinalDecision;
+use cupcake_core::engine::{Engine, EngineConfig};
+use cupcake_core::harness::types::HarnessType;
+use serial_test::serial;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+/// Create a team directory using the stock project layout
+fn create_team_config(team_root: &std::path::Path, policy: &str) -> Result<()> {
+    let project = TempDir::new()?;
+    common::create_test_project_for_harness(project.path(), HarnessType::ClaudeCode)?;
+
+    // Copy the generated .cupcake/ layout verbatim - no namespace edits needed
+    let source = project.path().join(".cupcake");
+    for entry in walk(&source)? {
+        let relative = entry.strip_prefix(&source)?;
+        let dest = team_root.join(relative);
+        fs::create_dir_all(dest.parent().unwrap())?;
+        fs::copy(&entry, &dest)?;
+    }
+
+    fs::write(team_root.join("policies/claude/team_policy.rego"), policy)?;
+    Ok(())
+}
+
+fn walk(dir: &std::path::Path) -> Result<Vec<std::path::PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(walk(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+fn engine_config(empty_global: &TempDir, team_root: &std::path::Path) -> EngineConfig {
+    EngineConfig {
+        harness: HarnessType::ClaudeCode,
+        wasm_max_memory: None,
+        wasm_pool_size: None,
+        opa_path: None,
+        global_config: Some(empty_global.path().to_path_buf()),
+        team_config: Some(team_root.to_path_buf()),
+        debug_routing: false,
+    }
+}
+
+/// Team deny terminates evaluation before project policies run
+#[tokio::test]
+#[serial]
+async fn test_team_deny_short_circuits_project() -> Result<()> {
+    common::init_test_logging();
+
+    let empty_global = TempDir::new()?;
+    let team_dir = TempDir::new()?;
+    create_team_config(
+        team_dir.path(),
+        r#"# METADATA
+# scope: package
+# custom:
+#   routing:
+#     required_events: ["PreToolUse"]
+#     required_tools: ["Bash"]
+package cupcake.policies.team_guard
+
+import rego.v1
+
+deny contains decision if {
+    contains(input.tool_input.command, "terraform destroy")
+    decision := {
+        "rule_id": "TEAM-001",
+        "reason": "Blocked by platform team",
+        "severity": "HIGH"
+    }
+}
+"#,
+    )?;
+
+    let project_dir = TempDir::new()?;
+    common::create_test_project_for_harness(project_dir.path(), HarnessType::ClaudeCode)?;
+
+    let engine = Engine::new_with_config(
+        project_dir.path(),
+        engine_config(&empty_global, team_dir.path()),
+    )
+    .await?;
+
+    assert!(engine.team_wasm_module().await.unwrap().is_some());
+    assert!(engine
+        .team_routing_map()
+        .values()
+        .flatten()
+        .any(|p| p.package_name == "cupcake.team.policies.team_guard"));
+
+    let input = serde_json::json!({
+        "hook_event_name": "PreToolUse",
+        "tool_name": "Bash",
+        "tool_input": {"command": "terraform destroy -auto-approve"},
+        "session_id": "test",
+        "transcript_path": "/tmp/transcript.jsonl",
+        "cwd": project_dir.path().to_string_lossy()
+    });
+
+    let decision = engine.evaluate(&input, None).await?;
+    match decision {
+        FinalDecision::Deny { reason, .. } => {
+            assert!(reason.contains("Blocked by platform team"));
+        }
+        other => panic!("Expected team Deny, got {other:?}"),
+    }
+
+    let input = serde_json::json!({
+        "hook_event_name": "PreToolUse",
+        "tool_name": "Bash",
+        "tool_input": {"command": "terraform plan"},
+        "session_id": "test",
+        "transcript_path": "/tmp/transcript.jsonl",
+        "cwd": project_dir.path().to_string_lossy()
+    });
+
+    let decision = engine.evaluate(&input, None).await?;
+    assert!(matches!(decision, FinalDecision::Allow { .. }));
+
+    Ok(())
+}
+
+/// A configured team path that does not exist fails engine initialization
+#[tokio::test]
+#[serial]
+async fn test_missing_team_config_is_an_error() -> Result<()> {
+    let empty_global = TempDir::new()?;
+    let project_dir = TempDir::new()?;
+    common::create_test_project_for_harness(project_dir.path(), HarnessType::ClaudeCode)?;
+
+    let missing = std::path::PathBuf::from("/definitely/not/a/team/dir");
+    let result =
+        Engine::new_with_config(project_dir.path(), engine_config(&empty_global, &missing)).await;
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+/// A broken team policy doesn't stop the engine, but fails an eager compile
+#[tokio::test]
+#[serial]
+async fn test_broken_team_policy_fails_deferred_compile() -> Result<()> {
+    let empty_global = TempDir::new()?;
+    let team_dir = TempDir::new()?;
+    create_team_config(
+        team_dir.path(),
+        r#"# METADATA
+# scope: package
+# custom:
+#   routing:
+#     required_events: ["PreToolUse"]
+#     required_tools: ["Bash"]
+package cupcake.policies.team_broken
+
+import rego.v1
+
+deny contains decision if {
+    input.tool_input.command ==
+}
+"#,
+    )?;
+    fs::write(
+        team_dir.path().join("rulebook.yml"),
+        "on_policy_error: fail\n",
+    )?;
+
+    let project_dir = TempDir::new()?;
+    common::create_test_project_for_harness(project_dir.path(), HarnessType::ClaudeCode)?;
+
+    let engine = Engine::new_with_config(
+        project_dir.path(),
+        engine_config(&empty_global, team_dir.path()),
+    )
+    .await?;
+
+    let err = engine.compile_deferred_tiers().await.unwrap_err();
+    assert!(
+        format!("{err:#}").contains("Failed to compile team policies"),
+        "{err:#}"
+    );
+
+    Ok(())
+}
+
+/// explain_route reports the policies each tier would evaluate
+#[tokio::test]
+#[serial]
+async fn test_explain_route_across_tiers() -> Result<()> {
+    common::init_test_logging();
+
+    let empty_global = TempDir::new()?;
+    let team_dir = TempDir::new()?;
+    create_team_config(
+        team_dir.path(),
+        r#"# METADATA
+# scope: package
+# custom:
+#   routing:
+#     required_events: ["PreToolUse"]
+#     required_tools: ["Bash"]
+package cupcake.policies.team_guard
+
+import rego.v1
+
+deny contains decision if {
+    false
+    decision := {"rule_id": "TEAM-001", "reason": "never", "severity": "LOW"}
+}
+"#,
+    )?;
+
+    let project_dir = TempDir::new()?;
+    common::create_test_project_for_harness(project_dir.path(), HarnessType::ClaudeCode)?;
+    fs::write(
+        project_dir
+            .path()
+            .join(".cupcake/policies/claude/all_tools.rego"),
+        r#"# METADATA
+# scope: package
+# custom:
+#   routing:
+#     required_events: ["PreToolUse"]
+package cupcake.policies.all_tools
+
+import rego.v1
+
+deny contains decision if {
+    false
+    decision := {"rule_id": "ALL-001", "reason": "never", "severity": "LOW"}
+}
+"#,
+    )?;
+
+    let engine = Engine::new_with_config(
+        project_dir.path(),
+        engine_config(&empty_global, team_dir.path()),
+    )
+    .await?;
+
+    let routes = engine.explain_route("PreToolUse", Some("Bash"));
+    let tiers: Vec<&str> = routes.iter().map(|r| r.tier).collect();
+    assert_eq!(tiers, vec!["global", "team", "project"]);
+    assert_eq!(
+        routes[2].keys_consulted,
+        vec!["PreToolUse:Bash", "PreToolUse:*", "PreToolUse"]
+    );
+    assert!(routes[0].policies.is_empty());
+    assert!(routes[1]
+        .policies
+        .iter()
+        .any(|p| p.package_name == "cupcake.team.policies.team_guard"));
+    assert!(routes[2]
+        .policies
+        .iter()
+        .any(|p| p.package_name == "cupcake.policies.all_tools"));
+
+    // The team policy only routes Bash
+    let routes = engine.explain_route("PreToolUse", Some("Read"));
+    assert!(routes[1].policies.is_empty());
+    assert!(routes[2]
+        .policies
+        .iter()
+        .any(|p| p.package_name == "cupcake.policies.all_tools"));
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+//! Integration tests for WASM instance pooling under concurrent evaluation
+
+use anyhow::Result;
+use cupcake_core::engine::decision::FinalDecision;
+use cupcake_core::engine::{Engine, EngineConfig};
+use cupcake_core::harness::types::HarnessType;
+use serial_test::serial;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+fn bash_event(command: &str) -> serde_json::Value {
+    serde_json::json!({
+        "hook_event_name": "PreToolUse",
+        "tool_name": "Bash",
+        "tool_input": {"command": command},
+        "session_id": "test",
+        "transcript_path": "/tmp/transcript.jsonl",
+        "cwd": "/tmp"
+    })
+}
+
+/// Reused instances must not leak state between evaluations, including
+/// when more threads evaluate at once than the pool keeps idle
+#[test]
+#[serial]
+fn test_concurrent_evaluations_share_pool() -> Result<()> {
+    common::init_test_logging();
+
+    let project_dir = TempDir::new()?;
+    common::create_test_project_for_harness(project_dir.path(), HarnessType::ClaudeCode)?;
+    fs::write(
+        project_dir
+            .path()
+            .join(".cupcake/policies/claude/no_rm.rego"),
+        r#"# METADATA
+# scope: package
+# custom:
+#   routing:
+#     required_events: ["PreToolUse"]
+#     required_tools: ["Bash"]
+package cupcake.policies.no_rm
+
+import rego.v1
+
+deny contains decision if {
+    contains(input.tool_input.command, "rm -rf")
+    decision := {
+        "rule_id": "POOL-001",
+        "reason": "Recursive delete blocked",
+        "severity": "HIGH"
+    }
+}
+"#,
+    )?;
+
+    let empty_global = TempDir::new()?;
+    let config = EngineConfig {
+        harness: HarnessType::ClaudeCode,
+        wasm_max_memory: None,
+        wasm_pool_size: Some(2),
+        opa_path: None,
+        global_config: Some(empty_global.path().to_path_buf()),
+        team_config: None,
+        debug_routing: false,
+    };
+    let runtime = tokio::runtime::Runtime::new()?;
+    let engine = runtime.block_on(Engine::new_with_config(project_dir.path(), config))?;
+
+    std::thread::scope(|scope| {
+        for thread in 0..6 {
+            let engine = &engine;
+            scope.spawn(move || {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .unwrap();
+                for i in 0..20 {
+                    let blocked = (thread + i) % 2 == 0;
+                    let command = if blocked { "rm -rf build" } else { "ls -la" };
+                    let decision = runtime
+                        .block_on(engine.evaluate(&bash_event(command), None))
+                        .unwrap();
+                    match (blocked, &decision) {
+                        (true, FinalDecision::Deny { .. }) => {}
+                        (false, FinalDecision::Allow { .. }) => {}
+                        _ => panic!("Unexpected decision for {command:?}: {decision:?}"),
+                    }
+                }
+            });
+        }
+    });
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+//! Tests for policies requesting a watchdog verdict through routing metadata
+
+use anyhow::Result;
+use cupcake_core::engine::executor::Executor;
+use cupcake_core::engine::{PolicyUnit, RoutingDirective};
+use cupcake_core::watchdog::{Watchdog, WatchdogConfig};
+use serde_json::json;
+use tempfile::TempDir;
+
+fn policy(event: &str, required_watchdog: bool) -> PolicyUnit {
+    PolicyUnit {
+        path: "policies/claude/judged.rego".into(),
+        package_name: "cupcake.policies.judged".to_string(),
+        routing: RoutingDirective {
+            required_events: vec![event.to_string()],
+            required_tools: vec![],
+            required_signals: vec![],
+            required_watchdog,
+        },
+        metadata: None,
+        rendered: None,
+    }
+}
+
+#[tokio::test]
+async fn test_watchdog_verdict_routed_to_requesting_policy() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    // A watchdog without a backend fails open, which is enough to see the routing
+    let watchdog = Watchdog::new(WatchdogConfig::default())?;
+    let exec = Executor {
+        rulebook: None,
+        global_rulebook: None,
+        watchdog: Some(&watchdog),
+        working_dir: temp_dir.path(),
+    };
+
+    let input = json!({"hook_event_name": "UserPromptSubmit", "prompt": "ship it"});
+    let enriched = exec
+        .gather_signals(&input, &[policy("UserPromptSubmit", true)], None)
+        .await?;
+    assert_eq!(enriched["watchdog"]["verdict"]["allow"], json!(true));
+    assert!(enriched["signals"].get("watchdog").is_none());
+
+    let enriched = exec
+        .gather_signals(&input, &[policy("UserPromptSubmit", false)], None)
+        .await?;
+    assert!(enriched.get("watchdog").is_none());
+
+    // Pre-action events keep the implicit verdict under signals as well
+    let input = json!({"hook_event_name": "PreToolUse", "tool_name": "Bash"});
+    let enriched = exec
+        .gather_signals(&input, &[policy("PreToolUse", true)], None)
+        .await?;
+    assert_eq!(
+        enriched["watchdog"]["verdict"],
+        enriched["signals"]["watchdog"]
+    );
+
+    Ok(())
+}
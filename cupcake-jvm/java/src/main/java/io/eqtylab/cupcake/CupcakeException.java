@@ -0,0 +1,62 @@
+package io.eqtylab.cupcake;
+
+/**
+ * Error raised by the Cupcake engine.
+ *
+ * <p>{@link #getKind()} says why the operation failed, so callers can handle
+ * invalid input, policy compilation failures and runtime errors separately.
+ */
+public class CupcakeException extends RuntimeException {
+
+    /** Category of a Cupcake error. */
+    public enum Kind {
+        /** The event JSON, options or harness name was rejected. */
+        INVALID_INPUT,
+        /** Policies could not be loaded or compiled. */
+        POLICY_COMPILE,
+        /** A signal required by the routed policies could not be gathered. */
+        SIGNAL_FAILURE,
+        /** Policy evaluation failed inside the WASM runtime. */
+        WASM_RUNTIME,
+        /** An operation ran past its deadline. */
+        TIMEOUT,
+        /** Any other engine failure. */
+        INTERNAL
+    }
+
+    private final Kind kind;
+
+    /**
+     * Created by the native library with a {@code KIND:message} string.
+     */
+    CupcakeException(String encoded) {
+        this(kindOf(encoded), messageOf(encoded));
+    }
+
+    public CupcakeException(Kind kind, String message) {
+        super(message);
+        this.kind = kind;
+    }
+
+    /** Category of this error. */
+    public Kind getKind() {
+        return kind;
+    }
+
+    private static Kind kindOf(String encoded) {
+        int separator = encoded.indexOf(':');
+        if (separator > 0) {
+            try {
+                return Kind.valueOf(encoded.substring(0, separator));
+            } catch (IllegalArgumentException e) {
+                // Unknown kind from a newer native library
+            }
+        }
+        return Kind.INTERNAL;
+    }
+
+    private static String messageOf(String encoded) {
+        int separator = encoded.indexOf(':');
+        return separator > 0 ? encoded.substring(separator + 1) : encoded;
+    }
+}
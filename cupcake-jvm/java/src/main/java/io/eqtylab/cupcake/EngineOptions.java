@@ -0,0 +1,115 @@
+package io.eqtylab.cupcake;
+
+/**
+ * Options for creating a {@link PolicyEngine}.
+ *
+ * <pre>{@code
+ * EngineOptions options = new EngineOptions()
+ *     .harness("cursor")
+ *     .wasmMaxMemory(32 * 1024 * 1024)
+ *     .failClosed(true);
+ * }</pre>
+ */
+public final class EngineOptions {
+    private String harness;
+    private Long wasmMaxMemory;
+    private String opaPath;
+    private String globalConfig;
+    private boolean failClosed;
+    private boolean telemetry;
+    private boolean watch;
+    private Long watchDebounceMs;
+
+    /** Harness type: "claude" (default), "cursor", "factory" or "opencode". */
+    public EngineOptions harness(String harness) {
+        this.harness = harness;
+        return this;
+    }
+
+    /** Maximum WASM memory in bytes, between 1MB and 100MB (default 10MB). */
+    public EngineOptions wasmMaxMemory(long bytes) {
+        this.wasmMaxMemory = bytes;
+        return this;
+    }
+
+    /** Path to the OPA binary (default: bundled OPA or system PATH). */
+    public EngineOptions opaPath(String opaPath) {
+        this.opaPath = opaPath;
+        return this;
+    }
+
+    /** Global config directory (default: platform config directory). */
+    public EngineOptions globalConfig(String globalConfig) {
+        this.globalConfig = globalConfig;
+        return this;
+    }
+
+    /** Return a Deny decision instead of throwing when evaluation fails. */
+    public EngineOptions failClosed(boolean failClosed) {
+        this.failClosed = failClosed;
+        return this;
+    }
+
+    /** Write a telemetry span for each evaluation, per the rulebook's telemetry settings. */
+    public EngineOptions telemetry(boolean telemetry) {
+        this.telemetry = telemetry;
+        return this;
+    }
+
+    /** Reload automatically when files under {@code .cupcake/} change. */
+    public EngineOptions watch(boolean watch) {
+        this.watch = watch;
+        return this;
+    }
+
+    /** Quiet period after a change before reloading, in milliseconds (default 500). */
+    public EngineOptions watchDebounceMs(long millis) {
+        this.watchDebounceMs = millis;
+        return this;
+    }
+
+    /** JSON form understood by the native library. */
+    String toJson() {
+        StringBuilder json = new StringBuilder("{");
+        if (harness != null) {
+            json.append("\"harness\":").append(quote(harness)).append(',');
+        }
+        if (wasmMaxMemory != null) {
+            json.append("\"wasmMaxMemory\":").append(wasmMaxMemory).append(',');
+        }
+        if (opaPath != null) {
+            json.append("\"opaPath\":").append(quote(opaPath)).append(',');
+        }
+        if (globalConfig != null) {
+            json.append("\"globalConfig\":").append(quote(globalConfig)).append(',');
+        }
+        json.append("\"failClosed\":").append(failClosed).append(',');
+        if (watchDebounceMs != null) {
+            json.append("\"watchDebounceMs\":").append(watchDebounceMs).append(',');
+        }
+        json.append("\"telemetry\":").append(telemetry).append(',');
+        json.append("\"watch\":").append(watch);
+        return json.append('}').toString();
+    }
+
+    private static String quote(String value) {
+        StringBuilder quoted = new StringBuilder("\"");
+        for (char c : value.toCharArray()) {
+            switch (c) {
+                case '"':
+                    quoted.append("\\\"");
+                    break;
+                case '\\':
+                    quoted.append("\\\\");
+                    break;
+                default:
+                    if (c < 0x20) {
+                        quoted.append(String.format("\\u%04x", (int) c));
+                    } else {
+                        quoted.append(c);
+                    }
+            }
+        }
+        return quoted.append('"').toString();
+    }
+}
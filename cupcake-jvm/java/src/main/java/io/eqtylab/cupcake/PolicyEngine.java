@@ -0,0 +1,168 @@
+package io.eqtylab.cupcake;
+
+import java.util.concurrent.CompletableFuture;
+import java.util.concurrent.Executor;
+import java.util.concurrent.ForkJoinPool;
+import java.util.concurrent.locks.ReadWriteLock;
+import java.util.concurrent.locks.ReentrantReadWriteLock;
+
+/**
+ * Cupcake policy engine for Java and Kotlin.
+ *
+ * <p>Evaluates hook events (as JSON) against the policies in a Cupcake project
+ * and returns the decision as JSON. Instances are thread-safe; close them to
+ * release the native engine.
+ *
+ * <pre>{@code
+ * try (PolicyEngine engine = new PolicyEngine(".cupcake", "claude")) {
+ *     String decision = engine.evaluate(eventJson);
+ * }
+ * }</pre>
+ *
+ * <p>The native library is loaded from the {@code cupcake.library.path} system
+ * property if set, otherwise {@code cupcake_jvm} is looked up on
+ * {@code java.library.path}.
+ */
+public final class PolicyEngine implements AutoCloseable {
+    static {
+        String path = System.getProperty("cupcake.library.path");
+        if (path != null) {
+            System.load(path);
+        } else {
+            System.loadLibrary("cupcake_jvm");
+        }
+    }
+
+    // Evaluations hold the read lock so close() can't free the engine under them
+    private final ReadWriteLock lock = new ReentrantReadWriteLock();
+    private long handle;
+
+    /** Create an engine for the Claude Code harness. */
+    public PolicyEngine(String path) {
+        this(path, new EngineOptions());
+    }
+
+    /** Create an engine for the given harness ("claude", "cursor", "factory" or "opencode"). */
+    public PolicyEngine(String path, String harness) {
+        this(path, new EngineOptions().harness(harness));
+    }
+
+    /**
+     * Create an engine with the given options.
+     *
+     * @throws CupcakeException if the project can't be loaded or its policies don't compile
+     */
+    public PolicyEngine(String path, EngineOptions options) {
+        this.handle = nativeNew(path, options.toJson());
+    }
+
+    /**
+     * Evaluate a hook event, blocking until the decision is ready.
+     *
+     * @param inputJson the hook event as JSON
+     * @return the decision as JSON
+     * @throws CupcakeException if evaluation fails (unless fail-closed is enabled)
+     */
+    public String evaluate(String inputJson) {
+        lock.readLock().lock();
+        try {
+            return nativeEvaluate(checkOpen(), inputJson);
+        } finally {
+            lock.readLock().unlock();
+        }
+    }
+
+    /** Evaluate a hook event on the common fork-join pool. */
+    public CompletableFuture<String> evaluateAsync(String inputJson) {
+        return evaluateAsync(inputJson, ForkJoinPool.commonPool());
+    }
+
+    /** Evaluate a hook event on the given executor. */
+    public CompletableFuture<String> evaluateAsync(String inputJson, Executor executor) {
+        return CompletableFuture.supplyAsync(() -> evaluate(inputJson), executor);
+    }
+
+    /**
+     * Reload policies from disk with the original path and options.
+     *
+     * <p>If reloading fails, the previously loaded policies stay in effect.
+     *
+     * @throws CupcakeException if the reloaded policies can't be compiled
+     */
+    public void reload() {
+        lock.readLock().lock();
+        try {
+            nativeReload(checkOpen());
+        } finally {
+            lock.readLock().unlock();
+        }
+    }
+
+    /** Cupcake version string. */
+    public String version() {
+        lock.readLock().lock();
+        try {
+            return nativeVersion(checkOpen());
+        } finally {
+            lock.readLock().unlock();
+        }
+    }
+
+    /** Whether the engine is ready to evaluate policies. */
+    public boolean isReady() {
+        lock.readLock().lock();
+        try {
+            return handle != 0 && nativeIsReady(handle);
+        } finally {
+            lock.readLock().unlock();
+        }
+    }
+
+    /**
+     * Readiness, version and last reload time as JSON, for health endpoints:
+     * {@code {"ready":true,"version":"...","watching":true,"lastReload":"...","lastReloadError":null}}.
+     */
+    public String health() {
+        lock.readLock().lock();
+        try {
+            return nativeHealth(checkOpen());
+        } finally {
+            lock.readLock().unlock();
+        }
+    }
+
+    /** Release the native engine. Further calls throw. */
+    @Override
+    public void close() {
+        lock.writeLock().lock();
+        try {
+            if (handle != 0) {
+                nativeFree(handle);
+                handle = 0;
+            }
+        } finally {
+            lock.writeLock().unlock();
+        }
+    }
+
+    private long checkOpen() {
+        if (handle == 0) {
+            throw new IllegalStateException("PolicyEngine is closed");
+        }
+        return handle;
+    }
+
+    private static native long nativeNew(String path, String optionsJson);
+
+    private static native String nativeEvaluate(long handle, String inputJson);
+
+    private static native void nativeReload(long handle);
+
+    private static native String nativeVersion(long handle);
+
+    private static native boolean nativeIsReady(long handle);
+
+    private static native String nativeHealth(long handle);
+
+    private static native void nativeFree(long handle);
+}
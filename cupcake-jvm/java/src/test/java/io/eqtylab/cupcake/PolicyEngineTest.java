@@ -0,0 +1,42 @@
+package io.eqtylab.cupcake;
+
+/**
+ * Smoke tests for the native bindings that don't need OPA.
+ *
+ * <p>Run with {@code just test-java}.
+ */
+public final class PolicyEngineTest {
+    public static void main(String[] args) {
+        expectKind(
+                CupcakeException.Kind.INVALID_INPUT,
+                () -> new PolicyEngine("/nonexistent", "not-a-harness"));
+        expectKind(
+                CupcakeException.Kind.POLICY_COMPILE,
+                () -> new PolicyEngine("/nonexistent/cupcake/project"));
+
+        String json = new EngineOptions().harness("cur\"sor").wasmMaxMemory(1024).toJson();
+        check(
+                json.equals(
+                        "{\"harness\":\"cur\\\"sor\",\"wasmMaxMemory\":1024,"
+                                + "\"failClosed\":false,\"telemetry\":false,\"watch\":false}"),
+                "unexpected options JSON: " + json);
+
+        System.out.println("PolicyEngineTest passed");
+    }
+
+    private static void expectKind(CupcakeException.Kind kind, Runnable body) {
+        try {
+            body.run();
+        } catch (CupcakeException e) {
+            check(e.getKind() == kind, "expected " + kind + " but got " + e.getKind() + ": " + e.getMessage());
+            return;
+        }
+        throw new AssertionError("expected CupcakeException of kind " + kind);
+    }
+
+    private static void check(boolean condition, String message) {
+        if (!condition) {
+            throw new AssertionError(message);
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Minimal JNI declarations
+//!
+//! Only the handful of `JNIEnv` functions the bindings call are exposed. They
+//! are looked up by their index in the JNI function table, which is fixed by
+//! the JNI specification.
+
+use std::ffi::{c_char, c_void, CString};
+
+pub type JObject = *mut c_void;
+pub type JClass = JObject;
+pub type JString = JObject;
+pub type JLong = i64;
+pub type JBoolean = u8;
+pub type JInt = i32;
+pub type JChar = u16;
+pub type JSize = JInt;
+
+/// `JNIEnv*` as passed to native methods
+pub type JNIEnvPtr = *mut *const *const c_void;
+
+// Indices into the JNI function table (JNI specification, "Interface Function Table")
+const FIND_CLASS: usize = 6;
+const THROW_NEW: usize = 14;
+const EXCEPTION_CHECK: usize = 228;
+const NEW_STRING: usize = 163;
+const GET_STRING_LENGTH: usize = 164;
+const GET_STRING_CHARS: usize = 165;
+const RELEASE_STRING_CHARS: usize = 166;
+
+type FindClassFn = unsafe extern "system" fn(JNIEnvPtr, *const c_char) -> JClass;
+type ThrowNewFn = unsafe extern "system" fn(JNIEnvPtr, JClass, *const c_char) -> JInt;
+type ExceptionCheckFn = unsafe extern "system" fn(JNIEnvPtr) -> JBoolean;
+type NewStringFn = unsafe extern "system" fn(JNIEnvPtr, *const JChar, JSize) -> JString;
+type GetStringLengthFn = unsafe extern "system" fn(JNIEnvPtr, JString) -> JSize;
+type GetStringCharsFn =
+    unsafe extern "system" fn(JNIEnvPtr, JString, *mut JBoolean) -> *const JChar;
+type ReleaseStringCharsFn = unsafe extern "system" fn(JNIEnvPtr, JString, *const JChar);
+
+/// Safe-ish wrapper over a `JNIEnv*` valid for the duration of a native call
+pub struct Env {
+    ptr: JNIEnvPtr,
+}
+
+impl Env {
+    /// # Safety
+    /// `ptr` must be the `JNIEnv*` passed to the current native method.
+    pub unsafe fn from_raw(ptr: JNIEnvPtr) -> Self {
+        Self { ptr }
+    }
+
+    /// # Safety
+    /// `T` must be the function pointer type of the table entry at `index`.
+    unsafe fn function<T: Copy>(&self, index: usize) -> T {
+        let table = *self.ptr;
+        let entry = *table.add(index);
+        std::mem::transmute_copy(&entry)
+    }
+
+    /// Copy a Java string into a Rust string; None for a null reference
+    ///
+    /// Strings are exchanged as UTF-16 rather than the JVM's modified UTF-8,
+    /// which encodes NUL and supplementary characters differently from UTF-8.
+    pub fn get_string(&self, string: JString) -> Option<String> {
+        if string.is_null() {
+            return None;
+        }
+        unsafe {
+            let length: GetStringLengthFn = self.function(GET_STRING_LENGTH);
+            let get: GetStringCharsFn = self.function(GET_STRING_CHARS);
+            let release: ReleaseStringCharsFn = self.function(RELEASE_STRING_CHARS);
+            let len = usize::try_from(length(self.ptr, string)).ok()?;
+            let chars = get(self.ptr, string, std::ptr::null_mut());
+            if chars.is_null() {
+                return None;
+            }
+            // Unpaired surrogates are the only invalid UTF-16 Java can hold
+            let value = String::from_utf16_lossy(std::slice::from_raw_parts(chars, len));
+            release(self.ptr, string, chars);
+            Some(value)
+        }
+    }
+
+    /// Create a Java string; null if the string can't be created
+    pub fn new_string(&self, value: &str) -> JString {
+        let units: Vec<JChar> = value.encode_utf16().collect();
+        let Ok(len) = JSize::try_from(units.len()) else {
+            return std::ptr::null_mut();
+        };
+        unsafe {
+            let new: NewStringFn = self.function(NEW_STRING);
+            new(self.ptr, units.as_ptr(), len)
+        }
+    }
+
+    /// Throw a new exception of the given class (e.g. `java/lang/IllegalStateException`)
+    pub fn throw(&self, class_name: &str, message: &str) {
+        let (Ok(class_name), Ok(message)) = (
+            CString::new(class_name),
+            CString::new(message.replace('\0', "")),
+        ) else {
+            return;
+        };
+        unsafe {
+            // FindClass throws NoClassDefFoundError itself if the class is missing
+            let find_class: FindClassFn = self.function(FIND_CLASS);
+            let class = find_class(self.ptr, class_name.as_ptr());
+            if class.is_null() || self.exception_pending() {
+                return;
+            }
+            let throw_new: ThrowNewFn = self.function(THROW_NEW);
+            throw_new(self.ptr, class, message.as_ptr());
+        }
+    }
+
+    /// Whether a Java exception is pending on this thread
+    pub fn exception_pending(&self) -> bool {
+        unsafe {
+            let check: ExceptionCheckFn = self.function(EXCEPTION_CHECK);
+            check(self.ptr) != 0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A stand-in JVM whose strings are boxed UTF-16 buffers
+
+    unsafe extern "system" fn new_string(_: JNIEnvPtr, chars: *const JChar, len: JSize) -> JString {
+        let units = std::slice::from_raw_parts(chars, len as usize).to_vec();
+        Box::into_raw(Box::new(units)) as JString
+    }
+
+    unsafe extern "system" fn get_string_length(_: JNIEnvPtr, string: JString) -> JSize {
+        (*(string as *const Vec<JChar>)).len() as JSize
+    }
+
+    unsafe extern "system" fn get_string_chars(
+        _: JNIEnvPtr,
+        string: JString,
+        _: *mut JBoolean,
+    ) -> *const JChar {
+        (*(string as *const Vec<JChar>)).as_ptr()
+    }
+
+    unsafe extern "system" fn release_string_chars(_: JNIEnvPtr, _: JString, _: *const JChar) {}
+
+    #[test]
+    fn test_string_round_trip_keeps_supplementary_characters() {
+        let mut table = vec![std::ptr::null::<c_void>(); EXCEPTION_CHECK + 1];
+        table[NEW_STRING] = new_string as *const c_void;
+        table[GET_STRING_LENGTH] = get_string_length as *const c_void;
+        table[GET_STRING_CHARS] = get_string_chars as *const c_void;
+        table[RELEASE_STRING_CHARS] = release_string_chars as *const c_void;
+        let mut functions = table.as_ptr();
+        let env = unsafe { Env::from_raw(&mut functions) };
+
+        let value = "{\"reason\":\"\u{1F9C1} blocked\0here\"}";
+        let string = env.new_string(value);
+        assert!(!string.is_null());
+
+        // Java sees a surrogate pair, not modified UTF-8's encoded surrogates
+        let units = unsafe { &*(string as *const Vec<JChar>) };
+        assert_eq!(&units[11..13], &[0xD83E, 0xDDC1]);
+        assert_eq!(env.get_string(string).as_deref(), Some(value));
+
+        unsafe { drop(Box::from_raw(string as *mut Vec<JChar>)) };
+        assert_eq!(env.get_string(std::ptr::null_mut()), None);
+    }
+}
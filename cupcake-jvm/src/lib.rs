@@ -0,0 +1,177 @@
+//! JVM bindings for Cupcake policy engine
+//!
+//! Hand-rolled JNI entry points for `io.eqtylab.cupcake.PolicyEngine`, wrapping
+//! the core BindingEngine. The Java classes live under `java/`.
+//!
+//! The engine is handed to Java as an opaque `long` handle (a boxed
+//! BindingEngine). Errors are thrown as `io.eqtylab.cupcake.CupcakeException`
+//! with a `KIND:message` string, where KIND is [`BindingError::code`].
+
+mod jni;
+
+use cupcake_core::bindings::{BindingEngine, BindingError, BindingOptions};
+use jni::{Env, JClass, JLong, JNIEnvPtr, JString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+const EXCEPTION_CLASS: &str = "io/eqtylab/cupcake/CupcakeException";
+
+/// Throw a binding error as a CupcakeException
+fn throw_error(env: &Env, error: &BindingError) {
+    let message = error.to_string();
+    env.throw(EXCEPTION_CLASS, &format!("{}:{message}", error.code()));
+}
+
+/// Run a native method body, converting errors and panics into Java exceptions
+fn guard<T>(env: &Env, default: T, body: impl FnOnce() -> Result<T, BindingError>) -> T {
+    match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(value)) => value,
+        Ok(Err(error)) => {
+            throw_error(env, &error);
+            default
+        }
+        Err(_) => {
+            throw_error(
+                env,
+                &BindingError::Internal("Cupcake panicked during a native call".to_string()),
+            );
+            default
+        }
+    }
+}
+
+/// Borrow the engine behind a handle
+///
+/// # Safety
+/// `handle` must come from `nativeNew` and not have been passed to `nativeFree`.
+unsafe fn engine<'a>(handle: JLong) -> Result<&'a BindingEngine, BindingError> {
+    (handle as *const BindingEngine)
+        .as_ref()
+        .ok_or_else(|| BindingError::Internal("PolicyEngine is closed".to_string()))
+}
+
+fn required_string(env: &Env, string: JString, name: &str) -> Result<String, BindingError> {
+    env.get_string(string)
+        .ok_or_else(|| BindingError::InvalidInput(format!("{name} must not be null")))
+}
+
+/// `static native long nativeNew(String path, String optionsJson)`
+///
+/// # Safety
+/// Called by the JVM with a valid `JNIEnv*`.
+#[no_mangle]
+pub unsafe extern "system" fn Java_io_eqtylab_cupcake_PolicyEngine_nativeNew(
+    env: JNIEnvPtr,
+    _class: JClass,
+    path: JString,
+    options_json: JString,
+) -> JLong {
+    let env = Env::from_raw(env);
+    guard(&env, 0, || {
+        let path = required_string(&env, path, "path")?;
+        let options: BindingOptions = match env.get_string(options_json) {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| BindingError::InvalidInput(format!("Invalid options JSON: {e}")))?,
+            None => BindingOptions::default(),
+        };
+
+        let engine = BindingEngine::with_options(&path, &options)?;
+        Ok(Box::into_raw(Box::new(engine)) as JLong)
+    })
+}
+
+/// `static native String nativeEvaluate(long handle, String inputJson)`
+///
+/// # Safety
+/// Called by the JVM with a valid `JNIEnv*` and a live handle.
+#[no_mangle]
+pub unsafe extern "system" fn Java_io_eqtylab_cupcake_PolicyEngine_nativeEvaluate(
+    env: JNIEnvPtr,
+    _class: JClass,
+    handle: JLong,
+    input_json: JString,
+) -> JString {
+    let env = Env::from_raw(env);
+    guard(&env, std::ptr::null_mut(), || {
+        let engine = engine(handle)?;
+        let input = required_string(&env, input_json, "input")?;
+        let decision = engine.evaluate_sync(&input)?;
+        Ok(env.new_string(&decision))
+    })
+}
+
+/// `static native void nativeReload(long handle)`
+///
+/// # Safety
+/// Called by the JVM with a valid `JNIEnv*` and a live handle.
+#[no_mangle]
+pub unsafe extern "system" fn Java_io_eqtylab_cupcake_PolicyEngine_nativeReload(
+    env: JNIEnvPtr,
+    _class: JClass,
+    handle: JLong,
+) {
+    let env = Env::from_raw(env);
+    guard(&env, (), || engine(handle)?.reload())
+}
+
+/// `static native String nativeVersion(long handle)`
+///
+/// # Safety
+/// Called by the JVM with a valid `JNIEnv*` and a live handle.
+#[no_mangle]
+pub unsafe extern "system" fn Java_io_eqtylab_cupcake_PolicyEngine_nativeVersion(
+    env: JNIEnvPtr,
+    _class: JClass,
+    handle: JLong,
+) -> JString {
+    let env = Env::from_raw(env);
+    guard(&env, std::ptr::null_mut(), || {
+        Ok(env.new_string(&engine(handle)?.version()))
+    })
+}
+
+/// `static native String nativeHealth(long handle)`
+///
+/// # Safety
+/// Called by the JVM with a valid `JNIEnv*` and a live handle.
+#[no_mangle]
+pub unsafe extern "system" fn Java_io_eqtylab_cupcake_PolicyEngine_nativeHealth(
+    env: JNIEnvPtr,
+    _class: JClass,
+    handle: JLong,
+) -> JString {
+    let env = Env::from_raw(env);
+    guard(&env, std::ptr::null_mut(), || {
+        let health = serde_json::to_string(&engine(handle)?.health())
+            .map_err(|e| BindingError::Internal(format!("Failed to serialize health: {e}")))?;
+        Ok(env.new_string(&health))
+    })
+}
+
+/// `static native boolean nativeIsReady(long handle)`
+///
+/// # Safety
+/// Called by the JVM with a valid `JNIEnv*` and a live handle.
+#[no_mangle]
+pub unsafe extern "system" fn Java_io_eqtylab_cupcake_PolicyEngine_nativeIsReady(
+    env: JNIEnvPtr,
+    _class: JClass,
+    handle: JLong,
+) -> jni::JBoolean {
+    let env = Env::from_raw(env);
+    guard(&env, 0, || Ok(engine(handle)?.is_ready() as jni::JBoolean))
+}
+
+/// `static native void nativeFree(long handle)`
+///
+/// # Safety
+/// `handle` must come from `nativeNew` and not be used again afterwards.
+#[no_mangle]
+pub unsafe extern "system" fn Java_io_eqtylab_cupcake_PolicyEngine_nativeFree(
+    _env: JNIEnvPtr,
+    _class: JClass,
+    handle: JLong,
+) {
+    if handle != 0 {
+        drop(Box::from_raw(handle as *mut BindingEngine));
+    }
+}
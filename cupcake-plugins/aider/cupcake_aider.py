@@ -0,0 +1,298 @@
+#!/usr/bin/env python3
+"""Run Aider with Cupcake policy enforcement.
+
+Aider has no hook system, so this launcher starts Aider in-process with its
+confirmation points wrapped. Each one sends an event to
+`cupcake eval --harness aider` and acts on the decision:
+
+  shell commands       PreToolUse / PostToolUse  shell_command {command}
+  file writes          PreToolUse                write_file {path, content}
+  files added to chat  PreToolUse                add_file {path}
+  /web                 PreToolUse                fetch_url {url}
+  prompts              UserPromptSubmit          prompt
+  start / exit         SessionStart / SessionEnd
+
+Usage: python3 .cupcake/aider/cupcake_aider.py [aider arguments]
+
+Environment:
+  CUPCAKE_PATH        cupcake binary (default: cupcake)
+  CUPCAKE_TIMEOUT_MS  evaluation timeout in milliseconds (default: 5000)
+  CUPCAKE_FAIL_MODE   "closed" denies when evaluation fails, "open" allows
+                      (default: closed)
+"""
+
+import atexit
+import json
+import os
+import shutil
+import subprocess
+import sys
+import uuid
+from pathlib import Path
+
+CUPCAKE_PATH = os.environ.get("CUPCAKE_PATH", "cupcake")
+TIMEOUT_MS = int(os.environ.get("CUPCAKE_TIMEOUT_MS", "5000"))
+FAIL_OPEN = os.environ.get("CUPCAKE_FAIL_MODE", "closed") == "open"
+
+# A launcher inside a project's .cupcake/ evaluates against that directory;
+# a global one lets cupcake find the project from the working directory
+_HERE = Path(__file__).resolve().parent.parent
+POLICY_DIR = str(_HERE) if _HERE.name == ".cupcake" else None
+
+
+def _reexec_under_aider_python():
+    """Aider is usually installed in its own venv (pipx, uv tool, aider-install).
+    Re-run this launcher with the interpreter from the `aider` script."""
+    aider_script = shutil.which("aider")
+    if aider_script:
+        with open(aider_script, "rb") as f:
+            shebang = f.readline().decode(errors="replace").strip()
+        interpreter = shebang[2:].strip() if shebang.startswith("#!") else ""
+        if interpreter and os.path.exists(interpreter) and interpreter != sys.executable:
+            os.execv(interpreter, [interpreter, __file__, *sys.argv[1:]])
+    sys.exit("cupcake: could not import aider - install it or run this file with Aider's Python")
+
+
+try:
+    import aider
+    from aider import commands as aider_commands
+    from aider.coders import base_coder
+    from aider.io import InputOutput
+    from aider.main import main as aider_main
+except ImportError:
+    _reexec_under_aider_python()
+
+
+class CupcakeDenied(Exception):
+    """Raised inside Aider to abort an action a policy denied."""
+
+
+class Session:
+    id = f"aider-{uuid.uuid4().hex[:12]}"
+    coder = None
+    io = None
+    pending_context = []
+    started = False
+
+
+def _common():
+    coder = Session.coder
+    common = {
+        "session_id": Session.id,
+        "cwd": str(getattr(coder, "root", None) or os.getcwd()),
+        "aider_version": getattr(aider, "__version__", None),
+    }
+    model = getattr(getattr(coder, "main_model", None), "name", None)
+    if model:
+        common["model"] = model
+    return common
+
+
+def evaluate(event_name, **fields):
+    """Send one event to cupcake eval and return its response."""
+    event = {"hook_event_name": event_name, **_common(), **fields}
+    args = [CUPCAKE_PATH, "eval", "--harness", "aider"]
+    if POLICY_DIR:
+        args += ["--policy-dir", POLICY_DIR]
+    try:
+        proc = subprocess.run(
+            args,
+            input=json.dumps(event),
+            capture_output=True,
+            text=True,
+            timeout=TIMEOUT_MS / 1000,
+            cwd=event["cwd"],
+        )
+        if proc.returncode != 0:
+            raise RuntimeError(proc.stderr.strip() or f"exit code {proc.returncode}")
+        return json.loads(proc.stdout)
+    except Exception as err:  # noqa: BLE001 - any failure is handled by the fail mode
+        if FAIL_OPEN:
+            _warn(f"cupcake: evaluation failed, allowing: {err}")
+            return {"decision": "allow"}
+        return {"decision": "deny", "reason": f"Cupcake evaluation failed: {err}"}
+
+
+def _warn(message):
+    if Session.io:
+        Session.io.tool_warning(message)
+    else:
+        print(message, file=sys.stderr)
+
+
+def _permitted(response, question):
+    """Apply a PreToolUse decision. Returns (allowed, reason)."""
+    decision = response.get("decision", "deny")
+    reason = response.get("reason") or "Blocked by policy"
+    if decision == "allow":
+        return True, None
+    if decision == "ask" and Session.io:
+        # explicit_yes_required keeps --yes-always from answering for the user
+        if Session.io.confirm_ask(question, subject=reason, explicit_yes_required=True):
+            return True, None
+        return False, reason
+    return False, reason
+
+
+def _with_context(text, response):
+    context = response.get("context") or []
+    return "\n\n".join([text, *context]) if context else text
+
+
+# --- shell commands -------------------------------------------------------
+
+def _wrap_run_cmd(run_cmd):
+    def cupcake_run_cmd(command, *args, **kwargs):
+        tool_args = {"command": command}
+        allowed, reason = _permitted(
+            evaluate("PreToolUse", tool="shell_command", args=tool_args),
+            "Run shell command?",
+        )
+        if not allowed:
+            return 1, f"Blocked by Cupcake: {reason}"
+
+        exit_code, output = run_cmd(command, *args, **kwargs)
+        response = evaluate(
+            "PostToolUse",
+            tool="shell_command",
+            args=tool_args,
+            result={"output": output or "", "exit_code": exit_code},
+        )
+        if response.get("decision") == "deny":
+            return exit_code, f"Output withheld by Cupcake: {response.get('reason', '')}"
+        return exit_code, _with_context(output or "", response)
+
+    return cupcake_run_cmd
+
+
+# Both modules import run_cmd by name
+base_coder.run_cmd = _wrap_run_cmd(base_coder.run_cmd)
+aider_commands.run_cmd = _wrap_run_cmd(aider_commands.run_cmd)
+
+
+# --- file writes ----------------------------------------------------------
+
+_write_text = InputOutput.write_text
+
+
+def _relative(path):
+    root = getattr(Session.coder, "root", None)
+    try:
+        return os.path.relpath(path, root) if root else str(path)
+    except ValueError:
+        return str(path)
+
+
+def cupcake_write_text(self, filename, content, *args, **kwargs):
+    # Aider reports the exception to the model as a failed edit
+    allowed, reason = _permitted(
+        evaluate(
+            "PreToolUse",
+            tool="write_file",
+            args={"path": _relative(filename), "content": content},
+        ),
+        f"Allow edit to {_relative(filename)}?",
+    )
+    if not allowed:
+        raise CupcakeDenied(f"Blocked by Cupcake: {reason}")
+    return _write_text(self, filename, content, *args, **kwargs)
+
+
+InputOutput.write_text = cupcake_write_text
+
+_io_init = InputOutput.__init__
+
+
+def cupcake_io_init(self, *args, **kwargs):
+    _io_init(self, *args, **kwargs)
+    Session.io = self
+
+
+InputOutput.__init__ = cupcake_io_init
+
+
+# --- files added to the chat ----------------------------------------------
+
+_add_rel_fname = base_coder.Coder.add_rel_fname
+
+
+def cupcake_add_rel_fname(self, rel_fname):
+    allowed, reason = _permitted(
+        evaluate("PreToolUse", tool="add_file", args={"path": rel_fname}),
+        f"Add {rel_fname} to the chat?",
+    )
+    if not allowed:
+        self.io.tool_error(f"Not adding {rel_fname}: {reason}")
+        return None
+    return _add_rel_fname(self, rel_fname)
+
+
+base_coder.Coder.add_rel_fname = cupcake_add_rel_fname
+
+
+# --- /web -----------------------------------------------------------------
+
+_cmd_web = aider_commands.Commands.cmd_web
+
+
+def cupcake_cmd_web(self, args, *rest, **kwargs):
+    url = args.strip()
+    allowed, reason = _permitted(
+        evaluate("PreToolUse", tool="fetch_url", args={"url": url}),
+        f"Fetch {url}?",
+    )
+    if not allowed:
+        self.io.tool_error(f"Not fetching {url}: {reason}")
+        return None
+    return _cmd_web(self, args, *rest, **kwargs)
+
+
+aider_commands.Commands.cmd_web = cupcake_cmd_web
+
+
+# --- prompts and session lifecycle ----------------------------------------
+
+_run = base_coder.Coder.run
+_run_one = base_coder.Coder.run_one
+
+
+def _start_session(coder):
+    Session.coder = coder
+    if Session.started:
+        return
+    Session.started = True
+    files = sorted(coder.get_inchat_relative_files()) if hasattr(coder, "get_inchat_relative_files") else []
+    response = evaluate("SessionStart", files=files, edit_format=getattr(coder, "edit_format", None))
+    Session.pending_context = response.get("context") or []
+    atexit.register(lambda: evaluate("SessionEnd"))
+
+
+def cupcake_run(self, *args, **kwargs):
+    _start_session(self)
+    return _run(self, *args, **kwargs)
+
+
+def cupcake_run_one(self, user_message, preproc):
+    Session.coder = self
+    # Aider's own /commands are not prompts for the model
+    if not user_message or user_message.lstrip().startswith("/"):
+        return _run_one(self, user_message, preproc)
+
+    response = evaluate("UserPromptSubmit", prompt=user_message)
+    if response.get("decision") != "allow":
+        self.io.tool_error(f"Prompt blocked by Cupcake: {response.get('reason', '')}")
+        return None
+
+    context = Session.pending_context + (response.get("context") or [])
+    Session.pending_context = []
+    if context:
+        user_message = "\n\n".join([user_message, *context])
+    return _run_one(self, user_message, preproc)
+
+
+base_coder.Coder.run = cupcake_run
+base_coder.Coder.run_one = cupcake_run_one
+
+
+if __name__ == "__main__":
+    sys.exit(aider_main())
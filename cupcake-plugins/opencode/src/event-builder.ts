@@ -6,6 +6,8 @@ import type {
   CupcakePreToolUseEvent,
   CupcakePostToolUseEvent,
   CupcakePermissionEvent,
+  CupcakeUserPromptEvent,
+  CupcakeSessionEvent,
 } from "./types.js";
 
 /**
@@ -138,3 +140,63 @@ export function buildPermissionEvent(
 
   return event;
 }
+
+/**
+ * Build a UserPromptSubmit event for Cupcake
+ */
+export function buildUserPromptEvent(
+  sessionId: string,
+  cwd: string,
+  prompt: string,
+  agent?: string,
+  messageId?: string,
+): CupcakeUserPromptEvent {
+  const event: CupcakeUserPromptEvent = {
+    hook_event_name: "UserPromptSubmit",
+    session_id: sessionId,
+    cwd,
+    prompt,
+  };
+
+  if (agent) {
+    event.agent = agent;
+  }
+
+  if (messageId) {
+    event.message_id = messageId;
+  }
+
+  return event;
+}
+
+/**
+ * Build a session lifecycle event (SessionStart, Stop, SessionEnd) for Cupcake
+ */
+export function buildSessionEvent(
+  hookEventName: CupcakeSessionEvent["hook_event_name"],
+  sessionId: string,
+  cwd: string,
+  title?: string,
+  reason?: string,
+  source?: CupcakeSessionEvent["source"],
+): CupcakeSessionEvent {
+  const event: CupcakeSessionEvent = {
+    hook_event_name: hookEventName,
+    session_id: sessionId,
+    cwd,
+  };
+
+  if (title) {
+    event.title = title;
+  }
+
+  if (reason) {
+    event.reason = reason;
+  }
+
+  if (source) {
+    event.source = source;
+  }
+
+  return event;
+}
@@ -7,15 +7,30 @@
  * Features:
  * - PreToolUse blocking for dangerous operations
  * - Permission auto-allow/deny via permission.ask hook
+ * - PostToolUse feedback and output filtering via tool.execute.after
+ * - UserPromptSubmit blocking and context via chat.message
  * - Toast notifications for policy decisions
+ * - Session lifecycle policies (SessionStart context, also after compaction;
+ *   Stop/SessionEnd audit)
  * - Audit logging via event hook
  */
 
 import type { Plugin, PluginInput, Hooks } from "@opencode-ai/plugin";
 import type { Permission } from "@opencode-ai/sdk";
-import type { CupcakeConfig, CupcakeResponse, OpencodeClient } from "./types.js";
+import type {
+  CupcakeConfig,
+  CupcakeResponse,
+  CupcakeSessionEvent,
+  OpencodeClient,
+} from "./types.js";
 import { DEFAULT_CONFIG, getToastVariant } from "./types.js";
-import { buildPreToolUseEvent, buildPermissionEvent } from "./event-builder.js";
+import {
+  buildPreToolUseEvent,
+  buildPostToolUseEvent,
+  buildPermissionEvent,
+  buildUserPromptEvent,
+  buildSessionEvent,
+} from "./event-builder.js";
 import { executeCupcake } from "./executor.js";
 import { formatDecision, formatErrorMessage } from "./enforcer.js";
 import { existsSync, readFileSync } from "fs";
@@ -112,6 +127,78 @@ export const CupcakePlugin: Plugin = async ({ directory, client }: PluginInput):
 
   log(config, "debug", "Cupcake plugin initialized");
 
+  // tool.execute.after doesn't receive the args, so keep them per call
+  const pendingArgs = new Map<string, Record<string, any>>();
+
+  /**
+   * Evaluate session lifecycle policies
+   *
+   * session.created and session.compacted → SessionStart (context is injected
+   * into the session, again after compaction dropped it), session.idle → Stop,
+   * session.deleted → SessionEnd. These events cannot be blocked, so failures
+   * are logged and otherwise ignored.
+   */
+  async function handleSessionEvent(event: { type: string; properties: any }): Promise<void> {
+    let sessionEvent: CupcakeSessionEvent;
+    switch (event.type) {
+      case "session.created":
+        sessionEvent = buildSessionEvent(
+          "SessionStart",
+          event.properties?.info?.id,
+          directory,
+          event.properties?.info?.title,
+          undefined,
+          "startup",
+        );
+        break;
+      case "session.compacted":
+        sessionEvent = buildSessionEvent(
+          "SessionStart",
+          event.properties?.sessionID,
+          directory,
+          undefined,
+          undefined,
+          "compact",
+        );
+        break;
+      case "session.idle":
+        sessionEvent = buildSessionEvent("Stop", event.properties?.sessionID, directory);
+        break;
+      case "session.deleted":
+        sessionEvent = buildSessionEvent(
+          "SessionEnd",
+          event.properties?.info?.id,
+          directory,
+          undefined,
+          "deleted",
+        );
+        break;
+      default:
+        return;
+    }
+
+    if (!sessionEvent.session_id) {
+      return;
+    }
+
+    try {
+      const response = await executeCupcake(config, sessionEvent);
+
+      if (sessionEvent.hook_event_name === "SessionStart" && response.context?.length && client) {
+        await client.session.prompt({
+          path: { id: sessionEvent.session_id },
+          body: {
+            noReply: true,
+            parts: [{ type: "text", text: response.context.join("\n\n") }],
+          },
+        });
+        log(config, "debug", `Injected session context for ${sessionEvent.session_id}`);
+      }
+    } catch (error: any) {
+      log(config, "warn", `${sessionEvent.hook_event_name} evaluation failed: ${error.message}`);
+    }
+  }
+
   return {
     /**
      * Hook: tool.execute.before
@@ -152,6 +239,9 @@ export const CupcakePlugin: Plugin = async ({ directory, client }: PluginInput):
           throw new Error(formatErrorMessage(formatted));
         }
 
+        if (input.callID) {
+          pendingArgs.set(input.callID, output.args);
+        }
         log(config, "debug", "Allowing tool execution");
       } catch (error: any) {
         throw error;
@@ -192,6 +282,11 @@ export const CupcakePlugin: Plugin = async ({ directory, client }: PluginInput):
         // Map Cupcake decision to OpenCode permission status
         switch (response.decision) {
           case "allow":
+            if (response.passthrough) {
+              // response_passthrough: leave the decision to OpenCode
+              log(config, "debug", `Deferring permission to OpenCode: ${input.type}`);
+              break;
+            }
             output.status = "allow";
             log(config, "debug", `Auto-allowing permission: ${input.type}`);
             break;
@@ -234,8 +329,9 @@ export const CupcakePlugin: Plugin = async ({ directory, client }: PluginInput):
     /**
      * Hook: tool.execute.after
      *
-     * Fired after tool execution. Used for audit logging.
-     * Cannot prevent execution (already happened).
+     * Fired after tool execution, so nothing can be prevented. PostToolUse
+     * policies can still shape what the agent sees: a filter replaces the
+     * output, and a deny/block reason or context is appended to it.
      */
     "tool.execute.after": async (
       input: { tool: string; sessionID: string; callID: string },
@@ -244,16 +340,94 @@ export const CupcakePlugin: Plugin = async ({ directory, client }: PluginInput):
       log(config, "debug", `tool.execute.after fired for ${input.tool}`);
       log(config, "debug", "Output:", output.output?.substring(0, 200));
 
-      // Future: Could evaluate PostToolUse policies here for validation
-      // and flag suspicious outputs for review
+      const args = pendingArgs.get(input.callID) ?? {};
+      pendingArgs.delete(input.callID);
+
+      // Shell tools report an exit code; other tools only reach here on success
+      const exitCode = typeof output.metadata?.exit === "number" ? output.metadata.exit : undefined;
+      const event = buildPostToolUseEvent(
+        input.sessionID || "unknown",
+        directory,
+        input.tool,
+        args,
+        {
+          success: exitCode === undefined || exitCode === 0,
+          output: output.output,
+          exit_code: exitCode,
+        },
+        undefined,
+        undefined,
+        input.callID,
+      );
+
+      try {
+        const response = await executeCupcake(config, event);
+
+        if (response.output !== undefined) {
+          output.output = response.output;
+          log(config, "debug", `Filtered ${input.tool} output`);
+        }
+
+        const notes: string[] = [];
+        if (response.decision !== "allow" && response.reason) {
+          notes.push(`Cupcake policy: ${response.reason}`);
+          const formatted = formatDecision(response);
+          await showToast(client, config, formatted.title, formatted.message, formatted.variant);
+        }
+        notes.push(...(response.context ?? []));
+        if (notes.length > 0) {
+          output.output = [output.output, ...notes].join("\n\n");
+        }
+      } catch (error: any) {
+        // The tool already ran - there is nothing left to fail closed on
+        log(config, "warn", `PostToolUse evaluation failed: ${error.message}`);
+      }
+    },
+
+    /**
+     * Hook: chat.message
+     *
+     * Fired when the user sends a message, before the model sees it. Throwing
+     * rejects the message; context is appended to its text.
+     */
+    "chat.message": async (
+      input: { sessionID: string; agent?: string; messageID?: string },
+      output: { message: any; parts: any[] },
+    ) => {
+      const textParts = output.parts.filter((part) => part.type === "text");
+      const event = buildUserPromptEvent(
+        input.sessionID || "unknown",
+        directory,
+        textParts.map((part) => part.text).join("\n\n"),
+        input.agent,
+        input.messageID,
+      );
+
+      const response = await executeCupcake(config, event);
+      const formatted = formatDecision(response);
+
+      // Ask can't be answered for a message - it blocks, as on other harnesses
+      if (formatted.decision !== "allow") {
+        await showToast(client, config, formatted.title, formatted.message, formatted.variant);
+        throw new Error(formatErrorMessage(formatted));
+      }
+
+      const lastText = textParts[textParts.length - 1];
+      if (response.context?.length && lastText) {
+        lastText.text = [lastText.text, ...response.context].join("\n\n");
+        log(config, "debug", `Added prompt context for ${input.sessionID}`);
+      }
     },
 
     /**
      * Hook: event
      *
-     * Fired for all OpenCode events. Used for comprehensive audit logging.
+     * Fired for all OpenCode events. Session lifecycle events are evaluated as
+     * SessionStart/Stop/SessionEnd policies; the rest are used for audit logging.
      */
     event: async ({ event }) => {
+      await handleSessionEvent(event);
+
       // Only log at debug level to avoid noise
       if (config.logLevel !== "debug") {
         return;
@@ -265,6 +439,7 @@ export const CupcakePlugin: Plugin = async ({ directory, client }: PluginInput):
         "permission.replied",
         "file.edited",
         "session.created",
+        "session.error",
         "session.aborted",
       ];
 
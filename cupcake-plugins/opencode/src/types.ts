@@ -83,13 +83,41 @@ export interface CupcakePermissionEvent {
   call_id?: string;
 }
 
+/**
+ * Cupcake event for user messages (chat.message)
+ */
+export interface CupcakeUserPromptEvent {
+  hook_event_name: "UserPromptSubmit";
+  session_id: string;
+  cwd: string;
+  agent?: string;
+  message_id?: string;
+  prompt: string;
+}
+
+/**
+ * Cupcake event for session lifecycle
+ * (session.created, session.compacted, session.idle, session.deleted)
+ */
+export interface CupcakeSessionEvent {
+  hook_event_name: "SessionStart" | "Stop" | "SessionEnd";
+  session_id: string;
+  cwd: string;
+  title?: string;
+  /** SessionStart only: "startup" or "compact" */
+  source?: "startup" | "compact";
+  reason?: string;
+}
+
 /**
  * Union type for all Cupcake events
  */
 export type CupcakeEvent =
   | CupcakePreToolUseEvent
   | CupcakePostToolUseEvent
-  | CupcakePermissionEvent;
+  | CupcakePermissionEvent
+  | CupcakeUserPromptEvent
+  | CupcakeSessionEvent;
 
 /**
  * Cupcake response from policy evaluation
@@ -100,6 +128,10 @@ export interface CupcakeResponse {
   context?: string[];
   rule_id?: string;
   severity?: "LOW" | "MEDIUM" | "HIGH" | "CRITICAL";
+  /** Set on allow when OpenCode's own permission system should decide */
+  passthrough?: boolean;
+  /** PostToolUse only: replacement for the tool output the agent sees */
+  output?: string;
 }
 
 /**
@@ -0,0 +1,97 @@
+# frozen_string_literal: true
+
+require "json"
+require_relative "cupcake/version"
+require_relative "cupcake/errors"
+require_relative "cupcake/native"
+
+# Policy enforcement for AI agents and automation tools
+module Cupcake
+  # Evaluates hook events against the policies in a Cupcake project
+  #
+  #   engine = Cupcake::PolicyEngine.new(".cupcake", harness: "claude")
+  #   decision = engine.evaluate(hook_event_name: "PreToolUse", tool_name: "Bash", ...)
+  #
+  # Instances are thread-safe. Evaluation releases the GVL, so evaluations on
+  # different Ruby threads run in parallel.
+  class PolicyEngine
+    # path:: project directory or .cupcake folder
+    # harness:: "claude" (default), "cursor", "factory" or "opencode"
+    # wasm_max_memory:: maximum WASM memory in bytes (1MB-100MB, default 10MB)
+    # opa_path:: OPA binary (default: bundled OPA or system PATH)
+    # global_config:: global config directory override
+    # fail_closed:: return a Deny decision instead of raising when evaluation fails
+    # telemetry:: write a telemetry span per evaluation, per the rulebook's settings
+    # watch:: reload automatically when files under .cupcake/ change
+    # watch_debounce_ms:: quiet period after a change before reloading (default 500)
+    #
+    # Raises Cupcake::Error (e.g. PolicyCompileError) if the project can't be loaded.
+    def initialize(path = ".cupcake", harness: "claude", wasm_max_memory: nil, opa_path: nil,
+                   global_config: nil, fail_closed: false, telemetry: false, watch: false,
+                   watch_debounce_ms: nil)
+      options = {
+        harness: harness,
+        wasmMaxMemory: wasm_max_memory,
+        opaPath: opa_path,
+        globalConfig: global_config,
+        failClosed: fail_closed,
+        telemetry: telemetry,
+        watch: watch,
+        watchDebounceMs: watch_debounce_ms
+      }.compact
+      pointer = Native.call(:cupcake_engine_new, path.to_s, JSON.generate(options))
+      @engine = FFI::AutoPointer.new(pointer, Native.method(:cupcake_engine_free))
+    end
+
+    # Evaluate a hook event (Hash or JSON string) and return the decision Hash
+    def evaluate(event)
+      input = event.is_a?(String) ? event : JSON.generate(event)
+      JSON.parse(Native.take_string(Native.call(:cupcake_engine_evaluate, engine, input)))
+    end
+
+    # Evaluate on a background thread; call #value on the result for the decision
+    def evaluate_async(event)
+      Thread.new { evaluate(event) }
+    end
+
+    # Reload policies from disk with the original path and options
+    #
+    # If reloading fails, the previously loaded policies stay in effect.
+    def reload
+      Native.call(:cupcake_engine_reload, engine)
+      nil
+    end
+
+    # Cupcake version string
+    def version
+      Native.take_string(Native.cupcake_engine_version(engine))
+    end
+
+    # Readiness, version and last reload time, for health endpoints
+    #
+    #   engine.health # => {"ready" => true, "watching" => true, "lastReload" => "...", ...}
+    def health
+      JSON.parse(Native.take_string(Native.cupcake_engine_health(engine)))
+    end
+
+    # Whether the engine is ready to evaluate policies
+    def ready?
+      !@engine.nil? && Native.cupcake_engine_is_ready(@engine)
+    end
+
+    # Release the native engine; further calls raise
+    #
+    # Must not be called while evaluations are still running. Engines that are
+    # never closed are released when garbage collected.
+    def close
+      @engine&.free
+      @engine = nil
+    end
+
+    private
+
+    def engine
+      @engine or raise InternalError.new("PolicyEngine is closed", kind: "Internal")
+    end
+  end
+end
@@ -0,0 +1,52 @@
+# frozen_string_literal: true
+
+require "json"
+
+module Cupcake
+  # Base class for errors raised by the Cupcake engine
+  class Error < StandardError
+    # Error category reported by the engine, e.g. "InvalidInput"
+    attr_reader :kind
+
+    def initialize(message = nil, kind: "Internal")
+      super(message)
+      @kind = kind
+    end
+
+    # Build the error subclass for a `{"kind": ..., "message": ...}` document
+    def self.from_json(json)
+      error = JSON.parse(json)
+      klass = KINDS.fetch(error["kind"], InternalError)
+      klass.new(error["message"], kind: error["kind"])
+    rescue JSON::ParserError
+      InternalError.new(json)
+    end
+  end
+
+  # The event JSON, options or harness name was rejected
+  class InvalidInputError < Error; end
+
+  # Policies could not be loaded or compiled
+  class PolicyCompileError < Error; end
+
+  # A signal required by the routed policies could not be gathered
+  class SignalFailureError < Error; end
+
+  # Policy evaluation failed inside the WASM runtime
+  class WasmRuntimeError < Error; end
+
+  # An operation ran past its deadline
+  class TimeoutError < Error; end
+
+  # Any other engine failure
+  class InternalError < Error; end
+
+  Error::KINDS = {
+    "InvalidInput" => InvalidInputError,
+    "PolicyCompile" => PolicyCompileError,
+    "SignalFailure" => SignalFailureError,
+    "WasmRuntime" => WasmRuntimeError,
+    "Timeout" => TimeoutError,
+    "Internal" => InternalError
+  }.freeze
+end
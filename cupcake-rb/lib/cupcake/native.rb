@@ -0,0 +1,43 @@
+# frozen_string_literal: true
+
+require "ffi"
+
+module Cupcake
+  # Bindings to the cupcake-rb native library
+  #
+  # Functions that compile or evaluate policies are attached with
+  # `blocking: true`, which releases the GVL while they run.
+  module Native
+    extend FFI::Library
+
+    ffi_lib ENV.fetch("CUPCAKE_LIBRARY_PATH", "cupcake_rb")
+
+    attach_function :cupcake_engine_new, %i[string string pointer], :pointer, blocking: true
+    attach_function :cupcake_engine_evaluate, %i[pointer string pointer], :pointer, blocking: true
+    attach_function :cupcake_engine_reload, %i[pointer pointer], :bool, blocking: true
+    attach_function :cupcake_engine_version, %i[pointer], :pointer
+    attach_function :cupcake_engine_is_ready, %i[pointer], :bool
+    attach_function :cupcake_engine_health, %i[pointer], :pointer
+    attach_function :cupcake_engine_free, %i[pointer], :void
+    attach_function :cupcake_string_free, %i[pointer], :void
+
+    # Call a native function with an error out-parameter, raising on failure
+    def self.call(function, *args)
+      error = FFI::MemoryPointer.new(:pointer)
+      result = public_send(function, *args, error)
+      error_ptr = error.read_pointer
+      raise Error.from_json(take_string(error_ptr)) unless error_ptr.null?
+
+      result
+    end
+
+    # Read and release a string returned by the native library
+    def self.take_string(pointer)
+      return nil if pointer.null?
+
+      pointer.read_string.force_encoding(Encoding::UTF_8)
+    ensure
+      cupcake_string_free(pointer) unless pointer.null?
+    end
+  end
+end
@@ -0,0 +1,5 @@
+# frozen_string_literal: true
+
+module Cupcake
+  VERSION = "0.5.2"
+end
@@ -0,0 +1,211 @@
+//! Ruby bindings for Cupcake policy engine
+//!
+//! A C ABI over the core BindingEngine, loaded by the `cupcake` gem (`lib/`)
+//! through the `ffi` gem. Calls that compile or evaluate policies are attached
+//! as blocking functions, so the gem releases the GVL while they run.
+//!
+//! Strings returned to Ruby are owned by this library and must be released
+//! with `cupcake_string_free`. Errors are reported through an `error` out
+//! parameter as `BindingError` JSON (`{"kind": ..., "message": ...}`).
+
+use cupcake_core::bindings::{BindingEngine, BindingError, BindingOptions};
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+/// Hand a string to the caller; null if it contains a NUL byte
+fn into_c_string(value: String) -> *mut c_char {
+    CString::new(value).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Read a C string argument; None for null
+///
+/// # Safety
+/// `value` must be null or a valid NUL-terminated string.
+unsafe fn read_c_string(value: *const c_char) -> Option<String> {
+    (!value.is_null()).then(|| CStr::from_ptr(value).to_string_lossy().into_owned())
+}
+
+/// Run a call, reporting errors and panics through `error`
+///
+/// # Safety
+/// `error` must be null or valid for writes.
+unsafe fn guard<T>(
+    error: *mut *mut c_char,
+    default: T,
+    body: impl FnOnce() -> Result<T, BindingError>,
+) -> T {
+    let failure = match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(value)) => return value,
+        Ok(Err(e)) => e,
+        Err(_) => BindingError::Internal("Cupcake panicked during a native call".to_string()),
+    };
+    if !error.is_null() {
+        *error = into_c_string(failure.to_json());
+    }
+    default
+}
+
+/// Borrow the engine behind a pointer
+///
+/// # Safety
+/// `engine` must be null or come from `cupcake_engine_new` and not be freed.
+unsafe fn engine<'a>(engine: *const BindingEngine) -> Result<&'a BindingEngine, BindingError> {
+    engine
+        .as_ref()
+        .ok_or_else(|| BindingError::Internal("PolicyEngine is closed".to_string()))
+}
+
+/// Create an engine for a project path with JSON options (null for defaults)
+///
+/// Returns null and sets `error` on failure.
+///
+/// # Safety
+/// String arguments must be null or NUL-terminated; `error` must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn cupcake_engine_new(
+    path: *const c_char,
+    options_json: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut BindingEngine {
+    guard(error, ptr::null_mut(), || {
+        let path = read_c_string(path)
+            .ok_or_else(|| BindingError::InvalidInput("path must not be null".to_string()))?;
+        let options: BindingOptions = match read_c_string(options_json) {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| BindingError::InvalidInput(format!("Invalid options JSON: {e}")))?,
+            None => BindingOptions::default(),
+        };
+
+        let engine = BindingEngine::with_options(&path, &options)?;
+        Ok(Box::into_raw(Box::new(engine)))
+    })
+}
+
+/// Evaluate a hook event, returning the decision JSON
+///
+/// Returns null and sets `error` on failure.
+///
+/// # Safety
+/// `engine` must be live; `input_json` NUL-terminated; `error` null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn cupcake_engine_evaluate(
+    engine_ptr: *const BindingEngine,
+    input_json: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    guard(error, ptr::null_mut(), || {
+        let engine = engine(engine_ptr)?;
+        let input = read_c_string(input_json)
+            .ok_or_else(|| BindingError::InvalidInput("input must not be null".to_string()))?;
+        Ok(into_c_string(engine.evaluate_sync(&input)?))
+    })
+}
+
+/// Reload policies from disk; returns false and sets `error` on failure
+///
+/// # Safety
+/// `engine` must be live; `error` null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn cupcake_engine_reload(
+    engine_ptr: *const BindingEngine,
+    error: *mut *mut c_char,
+) -> bool {
+    guard(error, false, || {
+        engine(engine_ptr)?.reload()?;
+        Ok(true)
+    })
+}
+
+/// Cupcake version string
+///
+/// # Safety
+/// `engine` must be live.
+#[no_mangle]
+pub unsafe extern "C" fn cupcake_engine_version(engine_ptr: *const BindingEngine) -> *mut c_char {
+    guard(ptr::null_mut(), ptr::null_mut(), || {
+        Ok(into_c_string(engine(engine_ptr)?.version()))
+    })
+}
+
+/// Readiness, version and last reload time as JSON
+///
+/// # Safety
+/// `engine` must be live.
+#[no_mangle]
+pub unsafe extern "C" fn cupcake_engine_health(engine_ptr: *const BindingEngine) -> *mut c_char {
+    guard(ptr::null_mut(), ptr::null_mut(), || {
+        let health = engine(engine_ptr)?.health();
+        serde_json::to_string(&health)
+            .map(into_c_string)
+            .map_err(|e| BindingError::Internal(format!("Failed to serialize health: {e}")))
+    })
+}
+
+/// Whether the engine is ready to evaluate policies
+///
+/// # Safety
+/// `engine` must be null or live.
+#[no_mangle]
+pub unsafe extern "C" fn cupcake_engine_is_ready(engine_ptr: *const BindingEngine) -> bool {
+    engine_ptr.as_ref().is_some_and(BindingEngine::is_ready)
+}
+
+/// Release an engine
+///
+/// # Safety
+/// `engine` must be null or come from `cupcake_engine_new`, and not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn cupcake_engine_free(engine: *mut BindingEngine) {
+    if !engine.is_null() {
+        drop(Box::from_raw(engine));
+    }
+}
+
+/// Release a string returned by this library
+///
+/// # Safety
+/// `value` must be null or a string returned by this library, freed only once.
+#[no_mangle]
+pub unsafe extern "C" fn cupcake_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        drop(CString::from_raw(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn take_error(error: *mut c_char) -> serde_json::Value {
+        assert!(!error.is_null());
+        let json = unsafe { CStr::from_ptr(error) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        unsafe { cupcake_string_free(error) };
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn test_invalid_harness_reports_invalid_input() {
+        let path = CString::new("/nonexistent").unwrap();
+        let options = CString::new(r#"{"harness": "not-a-harness"}"#).unwrap();
+        let mut error = ptr::null_mut();
+
+        let engine = unsafe { cupcake_engine_new(path.as_ptr(), options.as_ptr(), &mut error) };
+        assert!(engine.is_null());
+        assert_eq!(take_error(error)["kind"], "InvalidInput");
+    }
+
+    #[test]
+    fn test_null_engine_reports_error() {
+        let input = CString::new("{}").unwrap();
+        let mut error = ptr::null_mut();
+
+        let decision = unsafe { cupcake_engine_evaluate(ptr::null(), input.as_ptr(), &mut error) };
+        assert!(decision.is_null());
+        assert_eq!(take_error(error)["kind"], "Internal");
+        assert!(!unsafe { cupcake_engine_is_ready(ptr::null()) });
+    }
+}
@@ -8,6 +8,7 @@ import { Cupcake, CupcakeError } from '../index';
 import * as path from 'path';
 
 const TEST_CUPCAKE_DIR = path.join(__dirname, '..', 'test-fixtures', '.cupcake');
+const DECISION_KINDS = ['Halt', 'Deny', 'Block', 'Ask', 'Modify', 'FilterOutput', 'Allow'];
 
 describe('Cupcake TypeScript Bindings', () => {
   describe('Initialization', () => {
@@ -59,11 +60,10 @@ describe('Cupcake TypeScript Bindings', () => {
 
       const decision = await cupcake.evaluate(event);
 
-      // Check if decision has the expected structure (OPA output format)
+      // Check if decision has the expected structure (versioned decision object)
       expect(decision).toBeTruthy();
-      // OPA returns {"Allow": {...}} or {"Deny": {...}} format
-      const hasDecision = decision.Allow || decision.Deny || decision.Halt || decision.Ask;
-      expect(hasDecision).toBeDefined();
+      expect(decision.schema_version).toBe(1);
+      expect(DECISION_KINDS).toContain(decision.decision);
     });
 
     it('should evaluate an event synchronously', () => {
@@ -76,10 +76,10 @@ describe('Cupcake TypeScript Bindings', () => {
 
       const decision = cupcake.evaluateSync(event);
 
-      // Check if decision has the expected structure (OPA output format)
+      // Check if decision has the expected structure (versioned decision object)
       expect(decision).toBeTruthy();
-      const hasDecision = decision.Allow || decision.Deny || decision.Halt || decision.Ask;
-      expect(hasDecision).toBeDefined();
+      expect(decision.schema_version).toBe(1);
+      expect(DECISION_KINDS).toContain(decision.decision);
     });
 
     it('should handle custom event structures', async () => {
@@ -91,10 +91,26 @@ describe('Cupcake TypeScript Bindings', () => {
 
       const decision = await cupcake.evaluate(event);
 
-      // Check if decision has the expected structure (OPA output format)
+      // Check if decision has the expected structure (versioned decision object)
       expect(decision).toBeTruthy();
-      const hasDecision = decision.Allow || decision.Deny || decision.Halt || decision.Ask;
-      expect(hasDecision).toBeDefined();
+      expect(decision.schema_version).toBe(1);
+      expect(DECISION_KINDS).toContain(decision.decision);
+    });
+
+    it('should answer Claude Code hook inputs in hook output format', async () => {
+      const output = await cupcake.evaluateHook({
+        hook_event_name: 'PreToolUse',
+        session_id: 'test-session',
+        transcript_path: '',
+        cwd: '/tmp',
+        tool_name: 'Bash',
+        tool_input: { command: 'ls' },
+      });
+
+      // Allow is a passthrough: no decision fields
+      expect(output).toEqual({});
+
+      await expect(cupcake.evaluateHook({ hook_event_name: 'NotAHook' })).rejects.toThrow(CupcakeError);
     });
 
     it('should throw error when evaluating before initialization', async () => {
@@ -118,8 +134,8 @@ describe('Cupcake TypeScript Bindings', () => {
       expect(decisions).toHaveLength(10);
       decisions.forEach((decision) => {
         expect(decision).toBeTruthy();
-        const hasDecision = decision.Allow || decision.Deny || decision.Halt || decision.Ask;
-        expect(hasDecision).toBeDefined();
+        expect(decision.schema_version).toBe(1);
+        expect(DECISION_KINDS).toContain(decision.decision);
       });
     });
   });
@@ -138,8 +154,8 @@ describe('Cupcake TypeScript Bindings', () => {
         command: 'test',
       });
       expect(decision).toBeTruthy();
-      const hasDecision = decision.Allow || decision.Deny || decision.Halt || decision.Ask;
-      expect(hasDecision).toBeDefined();
+      expect(decision.schema_version).toBe(1);
+      expect(DECISION_KINDS).toContain(decision.decision);
     });
   });
 
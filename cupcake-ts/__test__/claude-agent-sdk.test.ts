@@ -0,0 +1,76 @@
+/**
+ * Tests for the Claude Agent SDK hook adapter
+ *
+ * Hook outputs come from a stub evaluator, so no policies are needed.
+ */
+
+import { ClaudeHookOutput, HookEvent } from '../index';
+import { CUPCAKE_HOOK_EVENTS, cupcakeHook, cupcakeHooks, HookEvaluator } from '../claude-agent-sdk';
+
+const DENY: ClaudeHookOutput = {
+  hookSpecificOutput: {
+    hookEventName: 'PreToolUse',
+    permissionDecision: 'deny',
+    permissionDecisionReason: 'Destructive command',
+  },
+};
+
+/** Returns `output` for every input and records the inputs */
+function stubEvaluator(output: ClaudeHookOutput = {}): HookEvaluator & { inputs: HookEvent[] } {
+  const inputs: HookEvent[] = [];
+  return {
+    inputs,
+    evaluateHook: async (input: HookEvent) => {
+      inputs.push(input);
+      return output;
+    },
+  };
+}
+
+const preToolUse = {
+  hook_event_name: 'PreToolUse',
+  session_id: 'session-1',
+  transcript_path: '/tmp/transcript.jsonl',
+  cwd: '/work',
+  tool_name: 'Bash',
+  tool_input: { command: 'rm -rf /' },
+};
+
+describe('Claude Agent SDK adapter', () => {
+  it('returns the hook output for the input', async () => {
+    const cupcake = stubEvaluator(DENY);
+    const output = await cupcakeHook(cupcake)(preToolUse, 'toolu_1', { signal: new AbortController().signal });
+
+    expect(output).toEqual(DENY);
+    expect(cupcake.inputs).toEqual([{ ...preToolUse, tool_use_id: 'toolu_1' }]);
+  });
+
+  it('keeps a tool use id already in the input', async () => {
+    const cupcake = stubEvaluator();
+    await cupcakeHook(cupcake)({ ...preToolUse, tool_use_id: 'toolu_a' }, 'toolu_b', {
+      signal: new AbortController().signal,
+    });
+
+    expect(cupcake.inputs[0].tool_use_id).toBe('toolu_a');
+  });
+
+  it('registers for every supported event by default', () => {
+    const hooks = cupcakeHooks(stubEvaluator());
+
+    expect(Object.keys(hooks)).toEqual([...CUPCAKE_HOOK_EVENTS]);
+    expect(hooks.PreToolUse).toEqual([{ hooks: [expect.any(Function)] }]);
+  });
+
+  it('applies the matcher to tool events only', () => {
+    const hooks = cupcakeHooks(stubEvaluator(), {
+      events: ['PreToolUse', 'UserPromptSubmit'],
+      matcher: 'Bash|Write',
+      timeout: 10,
+    });
+
+    expect(Object.keys(hooks)).toEqual(['PreToolUse', 'UserPromptSubmit']);
+    expect(hooks.PreToolUse?.[0]).toMatchObject({ matcher: 'Bash|Write', timeout: 10 });
+    expect(hooks.UserPromptSubmit?.[0].matcher).toBeUndefined();
+    expect(hooks.UserPromptSubmit?.[0].timeout).toBe(10);
+  });
+});
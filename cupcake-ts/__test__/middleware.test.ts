@@ -0,0 +1,175 @@
+/**
+ * Tests for the agent framework tool-call middleware
+ *
+ * Decisions come from a stub evaluator, so no policies are needed.
+ */
+
+import { Decision, HookEvent } from '../index';
+import {
+  CupcakePolicyError,
+  Evaluator,
+  guardAgentTool,
+  guardFunctionCall,
+  guardToolCall,
+  withCupcake,
+} from '../middleware';
+
+/** The parts of an Agents SDK `FunctionTool` the middleware uses */
+type FunctionTool = {
+  type: 'function';
+  name: string;
+  invoke: (runContext: unknown, input: string, details?: { toolCall?: { callId?: string } }) => Promise<string>;
+};
+
+function decision(kind: Decision['decision'], extra: Partial<Decision> = {}): Decision {
+  return { schema_version: 1, decision: kind, agent_messages: [], context: [], ...extra };
+}
+
+/** Returns queued decisions in order (Allow once exhausted) and records every event */
+function stubEvaluator(...decisions: Decision[]): Evaluator & { events: HookEvent[] } {
+  const events: HookEvent[] = [];
+  return {
+    events,
+    evaluate: async (event: HookEvent) => {
+      events.push(event);
+      return decisions.shift() ?? decision('Allow');
+    },
+  };
+}
+
+describe('Tool-call middleware', () => {
+  it('sends Claude Code events before and after the call', async () => {
+    const cupcake = stubEvaluator();
+    const result = await guardToolCall(
+      cupcake,
+      { name: 'run_shell', input: { command: 'ls' }, callId: 'call_1' },
+      async (input) => `ran ${input.command}`,
+      { sessionId: 'session-1', cwd: '/work', toolNames: { run_shell: 'Bash' } },
+    );
+
+    expect(result).toBe('ran ls');
+    expect(cupcake.events).toEqual([
+      {
+        hook_event_name: 'PreToolUse',
+        session_id: 'session-1',
+        transcript_path: '',
+        cwd: '/work',
+        tool_name: 'Bash',
+        tool_input: { command: 'ls' },
+        tool_use_id: 'call_1',
+      },
+      {
+        hook_event_name: 'PostToolUse',
+        session_id: 'session-1',
+        transcript_path: '',
+        cwd: '/work',
+        tool_name: 'Bash',
+        tool_input: { command: 'ls' },
+        tool_use_id: 'call_1',
+        tool_response: 'ran ls',
+      },
+    ]);
+  });
+
+  it('refuses denied calls without running them', async () => {
+    const execute = jest.fn();
+    const call = { name: 'run_shell', input: { command: 'rm -rf /' } };
+
+    await expect(
+      guardToolCall(stubEvaluator(decision('Deny', { reason: 'Destructive' })), call, execute),
+    ).rejects.toThrow(CupcakePolicyError);
+
+    const returned = await guardToolCall(stubEvaluator(decision('Block', { reason: 'Destructive' })), call, execute, {
+      onDeny: 'return',
+    });
+    expect(returned).toBe('Policy blocked run_shell: Destructive');
+
+    await expect(
+      guardToolCall(stubEvaluator(decision('Halt', { reason: 'Stop' })), call, execute, { onDeny: 'return' }),
+    ).rejects.toThrow('Policy blocked run_shell: Stop');
+    expect(execute).not.toHaveBeenCalled();
+  });
+
+  it('runs asked calls only when approved', async () => {
+    const call = { name: 'deploy', input: {} };
+    const ask = () => stubEvaluator(decision('Ask', { reason: 'Deploy to prod?' }));
+
+    await expect(guardToolCall(ask(), call, async () => 'deployed')).rejects.toThrow(CupcakePolicyError);
+
+    const onAsk = jest.fn().mockResolvedValue(true);
+    await expect(guardToolCall(ask(), call, async () => 'deployed', { onAsk })).resolves.toBe('deployed');
+    expect(onAsk).toHaveBeenCalledWith(call, 'Deploy to prod?');
+  });
+
+  it('runs modified calls with the updated input', async () => {
+    const cupcake = stubEvaluator(decision('Modify', { updated_input: { command: 'ls -la' } }));
+    const result = await guardToolCall(cupcake, { name: 'Bash', input: { command: 'ls' } }, async (input) => input);
+
+    expect(result).toEqual({ command: 'ls -la' });
+    expect(cupcake.events[1].tool_input).toEqual({ command: 'ls -la' });
+  });
+
+  it('skips result evaluation when disabled', async () => {
+    const cupcake = stubEvaluator();
+    await guardToolCall(cupcake, { name: 'Bash', input: {} }, () => 'ok', { postToolUse: false });
+    expect(cupcake.events).toHaveLength(1);
+  });
+
+  describe('Adapters', () => {
+    it('wraps Vercel AI SDK tools', async () => {
+      const cupcake = stubEvaluator(decision('Deny', { reason: 'No weather today' }));
+      const tools = withCupcake(
+        {
+          weather: {
+            description: 'Weather',
+            execute: async (args: { city: string }, _options?: { toolCallId?: string }) => `Sunny in ${args.city}`,
+          },
+          clientSide: { description: 'Runs in the browser' },
+        },
+        cupcake,
+        { onDeny: 'return' },
+      );
+
+      expect(tools.clientSide).toEqual({ description: 'Runs in the browser' });
+      await expect(tools.weather.execute({ city: 'Paris' }, { toolCallId: 'call_1' })).resolves.toBe(
+        'Policy blocked weather: No weather today',
+      );
+      await expect(tools.weather.execute({ city: 'Paris' }, { toolCallId: 'call_2' })).resolves.toBe('Sunny in Paris');
+
+      // One session for the whole tool set
+      expect(new Set(cupcake.events.map((event) => event.session_id)).size).toBe(1);
+    });
+
+    it('wraps OpenAI Agents SDK function tools', async () => {
+      const cupcake = stubEvaluator(decision('Modify', { updated_input: { query: 'SELECT 1' } }));
+      const sql: FunctionTool = {
+        type: 'function',
+        name: 'sql',
+        invoke: async (_runContext, input) => `rows for ${JSON.parse(input).query}`,
+      };
+      const tool = guardAgentTool(sql, cupcake);
+
+      const result = await tool.invoke({}, '{"query":"DROP TABLE users"}', { toolCall: { callId: 'call_1' } });
+
+      expect(result).toBe('rows for SELECT 1');
+      expect(cupcake.events[0].tool_input).toEqual({ query: 'DROP TABLE users' });
+      expect(cupcake.events[0].tool_use_id).toBe('call_1');
+    });
+
+    it('guards raw OpenAI function calls', async () => {
+      const cupcake = stubEvaluator();
+      const result = await guardFunctionCall(
+        cupcake,
+        { id: 'call_1', function: { name: 'add', arguments: '{"a":1,"b":2}' } },
+        (args) => args.a + args.b,
+      );
+
+      expect(result).toBe(3);
+      expect(cupcake.events[0].tool_name).toBe('add');
+
+      await expect(
+        guardFunctionCall(cupcake, { call_id: 'call_2', name: 'add', arguments: '{not json' }, () => 0),
+      ).rejects.toThrow('Invalid tool arguments');
+    });
+  });
+});
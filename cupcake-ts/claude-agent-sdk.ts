@@ -0,0 +1,134 @@
+/**
+ * Claude Agent SDK hook adapter
+ *
+ * The Claude Agent SDK calls hooks in-process with the same input objects
+ * Claude Code passes to command hooks, and expects the same JSON output back.
+ * This adapter evaluates those inputs with the embedded engine and returns the
+ * response `cupcake eval` would print, so SDK apps get Cupcake's policies
+ * without shelling out to the CLI.
+ *
+ * Initialize the engine with the `claude` harness (the default).
+ *
+ * @example
+ * ```typescript
+ * import { query } from '@anthropic-ai/claude-agent-sdk';
+ * import { Cupcake } from '@eqtylab/cupcake';
+ * import { cupcakeHooks } from '@eqtylab/cupcake/claude-agent-sdk';
+ *
+ * const cupcake = new Cupcake();
+ * await cupcake.init('.cupcake');
+ *
+ * for await (const message of query({ prompt, options: { hooks: cupcakeHooks(cupcake) } })) {
+ *   // ...
+ * }
+ * ```
+ *
+ * @packageDocumentation
+ */
+
+import { ClaudeHookOutput, HookEvent } from './index';
+
+/**
+ * Hook events Cupcake evaluates
+ */
+export const CUPCAKE_HOOK_EVENTS = [
+  'PreToolUse',
+  'PostToolUse',
+  'PermissionRequest',
+  'UserPromptSubmit',
+  'SessionStart',
+  'SessionEnd',
+  'Stop',
+  'SubagentStop',
+  'PreCompact',
+  'Notification',
+] as const;
+
+export type CupcakeHookEvent = (typeof CUPCAKE_HOOK_EVENTS)[number];
+
+/**
+ * Anything that evaluates hook inputs - a `Cupcake` instance, or a stub in tests
+ */
+export interface HookEvaluator {
+  evaluateHook(input: HookEvent): Promise<ClaudeHookOutput>;
+}
+
+/**
+ * The SDK's hook callback signature
+ */
+export type HookCallback = (
+  input: HookEvent,
+  toolUseID: string | undefined,
+  options: { signal: AbortSignal },
+) => Promise<ClaudeHookOutput>;
+
+/**
+ * The SDK's hook registration for one event
+ */
+export interface HookCallbackMatcher {
+  matcher?: string;
+  hooks: HookCallback[];
+  timeout?: number;
+}
+
+/**
+ * Options for `cupcakeHooks`
+ */
+export interface ClaudeAgentSdkOptions {
+  /** Events to register for (default: all of `CUPCAKE_HOOK_EVENTS`) */
+  events?: CupcakeHookEvent[];
+
+  /** Tool name pattern for tool events, e.g. `'Bash|Edit|Write'` (default: all tools) */
+  matcher?: string;
+
+  /** Hook timeout in seconds (default: the SDK's) */
+  timeout?: number;
+}
+
+/**
+ * A hook callback that evaluates every input it receives
+ *
+ * Register it yourself to combine Cupcake with other hooks for an event.
+ *
+ * @example
+ * ```typescript
+ * const hooks = { PreToolUse: [{ matcher: 'Bash', hooks: [cupcakeHook(cupcake), auditHook] }] };
+ * ```
+ */
+export function cupcakeHook(cupcake: HookEvaluator): HookCallback {
+  return async (input, toolUseID) => {
+    // The SDK passes the tool use id alongside the input rather than in it
+    const event = toolUseID && input.tool_use_id === undefined ? { ...input, tool_use_id: toolUseID } : input;
+    return cupcake.evaluateHook(event);
+  };
+}
+
+/**
+ * Hook registrations for the SDK's `options.hooks`
+ *
+ * @param cupcake - Engine to evaluate with
+ * @param options - Events to register for, tool matcher and timeout
+ */
+export function cupcakeHooks(
+  cupcake: HookEvaluator,
+  options: ClaudeAgentSdkOptions = {},
+): Partial<Record<CupcakeHookEvent, HookCallbackMatcher[]>> {
+  const hook = cupcakeHook(cupcake);
+  const hooks: Partial<Record<CupcakeHookEvent, HookCallbackMatcher[]>> = {};
+
+  for (const event of options.events ?? CUPCAKE_HOOK_EVENTS) {
+    const registration: HookCallbackMatcher = { hooks: [hook] };
+    if (options.matcher && isToolEvent(event)) {
+      registration.matcher = options.matcher;
+    }
+    if (options.timeout !== undefined) {
+      registration.timeout = options.timeout;
+    }
+    hooks[event] = [registration];
+  }
+  return hooks;
+}
+
+function isToolEvent(event: CupcakeHookEvent): boolean {
+  return event === 'PreToolUse' || event === 'PostToolUse' || event === 'PermissionRequest';
+}
@@ -6,7 +6,7 @@
  */
 
 import express from 'express';
-import { init, evaluate, version, CupcakeError } from '../index';
+import { init, evaluate, version, health, CupcakeError } from '../index';
 
 const app = express();
 app.use(express.json());
@@ -32,12 +32,11 @@ async function policyMiddleware(req: express.Request, res: express.Response, nex
     (req as any).policyDecision = decision;
 
     // Block requests that violate policies
-    if (decision.decision === 'Halt' || decision.decision === 'Deny') {
+    if (decision.decision === 'Halt' || decision.decision === 'Deny' || decision.decision === 'Block') {
       return res.status(403).json({
         error: 'Policy violation',
         decision: decision.decision,
         reason: decision.reason,
-        rule_id: decision.rule_id,
       });
     }
 
@@ -45,7 +44,6 @@ async function policyMiddleware(req: express.Request, res: express.Response, nex
     if (decision.decision === 'Ask') {
       return res.status(202).json({
         message: 'Action requires approval',
-        question: decision.question,
         reason: decision.reason,
         approval_required: true,
       });
@@ -73,7 +71,13 @@ app.use(policyMiddleware);
 
 // Health check (bypasses policy)
 app.get('/health', (req, res) => {
-  res.json({ status: 'ok', cupcake_version: version() });
+  const { lastReload, lastReloadError } = health();
+  res.json({
+    status: lastReloadError ? 'degraded' : 'ok',
+    cupcake_version: version(),
+    last_reload: lastReload,
+    last_reload_error: lastReloadError,
+  });
 });
 
 // Execute a shell command (policy enforced)
@@ -128,9 +132,9 @@ app.post('/execute/api', (req, res) => {
 // Start server
 async function startServer() {
   try {
-    // Initialize Cupcake at server startup
+    // Initialize Cupcake at server startup; policy edits reload it automatically
     console.log('Initializing Cupcake...');
-    await init('.cupcake', 'claude');
+    await init('.cupcake', { harness: 'claude', watch: true });
     console.log(`✓ Cupcake ${version()} initialized`);
 
     const PORT = process.env.PORT || 3000;
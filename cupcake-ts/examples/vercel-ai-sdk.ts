@@ -8,6 +8,7 @@
  */
 
 import { Cupcake } from '../index';
+import { guardToolCall } from '../middleware';
 import { z } from 'zod';
 
 // Initialize Cupcake
@@ -33,6 +34,9 @@ const fileToolSchema = z.object({
 
 /**
  * Wrapper that adds Cupcake policy enforcement to any tool
+ *
+ * For a tool set that is already defined, `withCupcake(tools, cupcake)` from
+ * `@eqtylab/cupcake/middleware` wraps every tool at once.
  */
 function policyEnforcedTool<TInput, TOutput>(
   name: string,
@@ -44,31 +48,18 @@ function policyEnforcedTool<TInput, TOutput>(
     name,
     description,
     parameters: schema,
-    execute: async (input: TInput): Promise<TOutput> => {
-      // Evaluate the tool call against Cupcake policies
-      const decision = await cupcake.evaluate({
-        kind: 'tool_use',
-        tool: name,
-        input: input as any,
-        timestamp: new Date().toISOString(),
-      });
-
-      // Enforce policy decision
-      if (decision.decision === 'Halt' || decision.decision === 'Deny') {
-        throw new Error(`Policy blocked ${name}: ${decision.reason}`);
-      }
-
-      if (decision.decision === 'Ask') {
-        throw new Error(`${name} requires approval: ${decision.question || decision.reason}`);
-      }
-
-      // Policy allows - execute the tool
-      console.log(`✓ Policy allowed ${name}`);
-      if (decision.context && decision.context.length > 0) {
-        console.log('  Guidance:', decision.context.join('; '));
-      }
+    execute: async (input: TInput): Promise<TOutput | string> => {
+      // Evaluate the call, run it if allowed, and evaluate its result.
+      // Deny, Block and unapproved Ask decisions throw a CupcakePolicyError.
+      const result = await guardToolCall(
+        cupcake,
+        { name, input: input as Record<string, any> },
+        (allowed) => execute(allowed as TInput),
+        { toolNames: { shell_execute: 'Bash' } },
+      );
 
-      return execute(input);
+      console.log(`✓ Policy allowed ${name}`);
+      return result;
     },
   };
 }
@@ -28,21 +28,71 @@
 // Load the native module using platform-specific binary name
 const nativeBinding = require('./cupcake-native.darwin-arm64.node');
 const NativePolicyEngine = nativeBinding.PolicyEngine;
+const nativeApplyOutputFilter = nativeBinding.applyOutputFilter as (filter: string, toolResponse: string) => string;
 
 // Type definition for the native PolicyEngine class
 interface NativePolicyEngineClass {
-  new (path: string, harness?: string): NativePolicyEngineInstance;
+  new (path: string, options?: string | CupcakeOptions): NativePolicyEngineInstance;
 }
 
 interface NativePolicyEngineInstance {
   evaluateSync(input: string): string;
   evaluateAsync(input: string): Promise<string>;
+  evaluateHookSync(input: string): string;
+  evaluateHookAsync(input: string): Promise<string>;
+  reload(): void;
+  onDecision(callback: (event: string) => void): number;
+  offDecision(id: number): boolean;
   version(): string;
   isReady(): boolean;
+  health(): string;
 }
 
 import { ensureOpaInstalled } from './installer';
 
+/**
+ * Harness whose event format the policies expect
+ */
+export type Harness = 'claude' | 'cursor' | 'factory' | 'opencode';
+
+/**
+ * Options for initializing the engine
+ *
+ * @example
+ * ```typescript
+ * await cupcake.init('.cupcake', {
+ *   harness: 'claude',
+ *   wasmMaxMemory: 32 * 1024 * 1024,
+ *   failClosed: true,
+ * });
+ * ```
+ */
+export interface CupcakeOptions {
+  /** Harness type (default: 'claude') */
+  harness?: Harness;
+
+  /** Maximum WASM memory in bytes, between 1MB and 100MB (default: 10MB) */
+  wasmMaxMemory?: number;
+
+  /** Path to the OPA binary; skips automatic OPA installation */
+  opaPath?: string;
+
+  /** Global config directory (default: platform config directory) */
+  globalConfig?: string;
+
+  /** Return a Deny decision instead of throwing when evaluation fails */
+  failClosed?: boolean;
+
+  /** Write a telemetry span for each evaluation, per the rulebook's telemetry settings */
+  telemetry?: boolean;
+
+  /** Reload automatically when files under `.cupcake/` change */
+  watch?: boolean;
+
+  /** Quiet period after a change before reloading, in ms (default: 500) */
+  watchDebounceMs?: number;
+}
+
 /**
  * Hook event input - generic object that your application defines
  *
@@ -65,44 +115,147 @@ export interface HookEvent {
 
 /**
  * Policy decision returned from evaluation
+ *
+ * Follows the versioned decision schema (`cupcake-core/schema/decision.schema.json`).
+ * Fields may be added within a `schema_version`; removing or changing one bumps it.
  */
 export interface Decision {
-  /** Decision verb: Allow, Deny, Halt, or Ask */
-  decision: 'Allow' | 'Deny' | 'Halt' | 'Ask';
+  /** Version of the decision schema */
+  schema_version: 1;
+
+  /** Synthesized outcome, from highest to lowest priority */
+  decision: 'Halt' | 'Deny' | 'Block' | 'Ask' | 'Modify' | 'FilterOutput' | 'Allow';
 
-  /** Human-readable reason for the decision */
+  /** Human-readable reason for the decision (absent for Allow) */
   reason?: string;
 
+  /** Messages intended for the agent rather than the user */
+  agent_messages: string[];
+
   /** Additional context or guidance (for Allow decisions) */
-  context?: string[];
+  context: string[];
 
-  /** Question to ask user (for Ask decisions) */
-  question?: string;
+  /** Replacement tool input (for Modify decisions) */
+  updated_input?: Record<string, any>;
 
-  /** Severity level (for warnings) */
-  severity?: 'LOW' | 'MEDIUM' | 'HIGH' | 'CRITICAL';
+  /** Filter applied to the tool response (for FilterOutput decisions) */
+  filter?: {
+    updated_response: Record<string, any> | null;
+    redact: string[];
+    max_length: number | null;
+  };
+}
 
-  /** Rule ID that triggered this decision */
-  rule_id?: string;
+/**
+ * Claude Code hook output, as returned by `evaluateHook`
+ *
+ * The JSON command hooks print, which is also what Claude Agent SDK hook
+ * callbacks return.
+ */
+export interface ClaudeHookOutput {
+  continue?: boolean;
+  stopReason?: string;
+  suppressOutput?: boolean;
+  systemMessage?: string;
+  decision?: 'approve' | 'block';
+  reason?: string;
+  hookSpecificOutput?: {
+    hookEventName: string;
+    [key: string]: any;
+  };
+}
 
-  /** Additional fields from policy evaluation */
-  [key: string]: any;
+/**
+ * Event streamed to `onDecision` subscribers after each evaluation
+ *
+ * Each evaluation produces a `decision` or `error` event, followed by a
+ * `telemetry` event carrying its span. Events of one evaluation share a `traceId`.
+ */
+export type CupcakeEvent =
+  | {
+      type: 'decision';
+      traceId: string;
+      hookEventName?: string;
+      decision: Decision;
+      durationMs: number;
+    }
+  | {
+      type: 'error';
+      traceId: string;
+      hookEventName?: string;
+      error: { kind: CupcakeErrorKind; message: string };
+      durationMs: number;
+    }
+  | {
+      type: 'telemetry';
+      traceId: string;
+      span: Record<string, any>;
+    };
+
+/**
+ * Engine health, for readiness probes and health endpoints
+ */
+export interface CupcakeHealth {
+  ready: boolean;
+  version: string;
+
+  /** Whether policy edits reload the engine automatically */
+  watching: boolean;
+
+  /** When the policies in use were loaded (RFC 3339) */
+  lastReload: string;
+
+  /** Why the most recent reload failed, if it did; the previous policies stay in use */
+  lastReloadError: string | null;
 }
 
+/**
+ * Category of an error raised by the native engine
+ */
+export type CupcakeErrorKind =
+  | 'InvalidInput'
+  | 'PolicyCompile'
+  | 'SignalFailure'
+  | 'WasmRuntime'
+  | 'Timeout'
+  | 'Internal';
+
 /**
  * Cupcake error class for all engine-related errors
+ *
+ * `code` says which operation failed; `kind` says why, for errors raised by
+ * the native engine.
  */
 export class CupcakeError extends Error {
   constructor(
     message: string,
     public readonly code: string,
     public readonly cause?: Error,
+    public readonly kind?: CupcakeErrorKind,
   ) {
     super(message);
     this.name = 'CupcakeError';
   }
 }
 
+/**
+ * Wrap an error thrown by the native engine
+ *
+ * Native errors carry a JSON message of the form `{"kind": ..., "message": ...}`.
+ */
+function nativeError(prefix: string, code: string, error: unknown): CupcakeError {
+  const err = error as Error;
+  try {
+    const parsed = JSON.parse(err.message) as { kind: CupcakeErrorKind; message: string };
+    if (parsed && typeof parsed.kind === 'string') {
+      return new CupcakeError(`${prefix}: ${parsed.message}`, code, err, parsed.kind);
+    }
+  } catch {
+    // Not a native engine error (e.g. OPA installation failed)
+  }
+  return new CupcakeError(`${prefix}: ${err.message}`, code, err);
+}
+
 /**
  * Main Cupcake class for policy evaluation
  *
@@ -136,7 +289,7 @@ export class Cupcake {
    * 3. Initializes the WASM runtime
    *
    * @param path - Path to project directory or .cupcake folder (default: '.cupcake')
-   * @param harness - Harness type for policy namespace (default: 'claude')
+   * @param options - Harness type, or engine options (default: 'claude')
    *
    * @throws {CupcakeError} If initialization fails
    *
@@ -146,19 +299,21 @@ export class Cupcake {
    * await cupcake.init('./my-policies', 'claude');
    * ```
    */
-  async init(path: string = '.cupcake', harness: 'claude' | 'cursor' = 'claude'): Promise<void> {
+  async init(path: string = '.cupcake', options: Harness | CupcakeOptions = 'claude'): Promise<void> {
     if (this.initialized) {
       throw new CupcakeError('Cupcake already initialized', 'ALREADY_INITIALIZED');
     }
 
     try {
-      // Ensure OPA binary is available
-      await ensureOpaInstalled();
+      // Ensure OPA binary is available, unless the caller brings their own
+      if (typeof options === 'string' || !options.opaPath) {
+        await ensureOpaInstalled();
+      }
 
       // Initialize native engine (runs in background thread to avoid blocking)
       this.engine = await new Promise((resolve, reject) => {
         try {
-          const engine = new (NativePolicyEngine as unknown as NativePolicyEngineClass)(path, harness);
+          const engine = new (NativePolicyEngine as unknown as NativePolicyEngineClass)(path, options);
           resolve(engine);
         } catch (error) {
           reject(error);
@@ -167,8 +322,7 @@ export class Cupcake {
 
       this.initialized = true;
     } catch (error) {
-      const err = error as Error;
-      throw new CupcakeError(`Failed to initialize Cupcake: ${err.message}`, 'INIT_FAILED', err);
+      throw nativeError('Failed to initialize Cupcake', 'INIT_FAILED', error);
     }
   }
 
@@ -183,22 +337,21 @@ export class Cupcake {
    * For web servers, use async `init()` instead.
    *
    * @param path - Path to project directory or .cupcake folder
-   * @param harness - Harness type for policy namespace
+   * @param options - Harness type, or engine options
    *
    * @throws {CupcakeError} If initialization fails
    */
-  initSync(path: string = '.cupcake', harness: 'claude' | 'cursor' = 'claude'): void {
+  initSync(path: string = '.cupcake', options: Harness | CupcakeOptions = 'claude'): void {
     if (this.initialized) {
       throw new CupcakeError('Cupcake already initialized', 'ALREADY_INITIALIZED');
     }
 
     try {
       // Note: OPA installation is async-only, must be done beforehand
-      this.engine = new (NativePolicyEngine as unknown as NativePolicyEngineClass)(path, harness);
+      this.engine = new (NativePolicyEngine as unknown as NativePolicyEngineClass)(path, options);
       this.initialized = true;
     } catch (error) {
-      const err = error as Error;
-      throw new CupcakeError(`Failed to initialize Cupcake: ${err.message}`, 'INIT_FAILED', err);
+      throw nativeError('Failed to initialize Cupcake', 'INIT_FAILED', error);
     }
   }
 
@@ -236,8 +389,7 @@ export class Cupcake {
       const resultJson = await this.engine.evaluateAsync(inputJson);
       return JSON.parse(resultJson) as Decision;
     } catch (error) {
-      const err = error as Error;
-      throw new CupcakeError(`Policy evaluation failed: ${err.message}`, 'EVALUATION_FAILED', err);
+      throw nativeError('Policy evaluation failed', 'EVALUATION_FAILED', error);
     }
   }
 
@@ -266,11 +418,89 @@ export class Cupcake {
       const resultJson = this.engine.evaluateSync(inputJson);
       return JSON.parse(resultJson) as Decision;
     } catch (error) {
-      const err = error as Error;
-      throw new CupcakeError(`Policy evaluation failed: ${err.message}`, 'EVALUATION_FAILED', err);
+      throw nativeError('Policy evaluation failed', 'EVALUATION_FAILED', error);
+    }
+  }
+
+  /**
+   * Evaluate a Claude Code hook input and return the hook's JSON output
+   *
+   * For hosts that run hooks in-process with Claude Code's input and output
+   * objects, such as the Claude Agent SDK (see `@eqtylab/cupcake/claude-agent-sdk`).
+   * The output is what the `claude` harness would print from `cupcake eval`.
+   * Requires the engine to be initialized with the `claude` harness.
+   *
+   * @param input - Hook input (`hook_event_name`, `session_id`, `tool_name`, ...)
+   * @returns Promise resolving to the hook output (`{}` when there is nothing to say)
+   *
+   * @throws {CupcakeError} If evaluation fails, the input isn't a Claude Code
+   * hook event, or engine not initialized
+   */
+  async evaluateHook(input: HookEvent): Promise<ClaudeHookOutput> {
+    if (!this.engine) {
+      throw new CupcakeError('Cupcake not initialized. Call init() first.', 'NOT_INITIALIZED');
+    }
+
+    try {
+      const resultJson = await this.engine.evaluateHookAsync(JSON.stringify(input));
+      return JSON.parse(resultJson) as ClaudeHookOutput;
+    } catch (error) {
+      throw nativeError('Policy evaluation failed', 'EVALUATION_FAILED', error);
     }
   }
 
+  /**
+   * Reload policies from disk (BLOCKS event loop while recompiling)
+   *
+   * Uses the path and options passed to `init()`. If reloading fails, the
+   * previously loaded policies stay in effect.
+   *
+   * @throws {CupcakeError} If reloading fails or engine not initialized
+   */
+  reload(): void {
+    if (!this.engine) {
+      throw new CupcakeError('Cupcake not initialized. Call init() first.', 'NOT_INITIALIZED');
+    }
+
+    try {
+      this.engine.reload();
+    } catch (error) {
+      throw nativeError('Failed to reload Cupcake', 'RELOAD_FAILED', error);
+    }
+  }
+
+  /**
+   * Subscribe to decision, error and telemetry events
+   *
+   * Useful for live dashboards and audit streams. The callback runs on the
+   * event loop after each evaluation; subscriptions don't keep the process alive.
+   *
+   * @param callback - Called with each event
+   * @returns A function that removes the subscription
+   *
+   * @throws {CupcakeError} If engine not initialized
+   *
+   * @example
+   * ```typescript
+   * const unsubscribe = cupcake.onDecision((event) => {
+   *   if (event.type === 'decision') {
+   *     dashboard.push(event.hookEventName, event.decision);
+   *   }
+   * });
+   * ```
+   */
+  onDecision(callback: (event: CupcakeEvent) => void): () => void {
+    if (!this.engine) {
+      throw new CupcakeError('Cupcake not initialized. Call init() first.', 'NOT_INITIALIZED');
+    }
+
+    const engine = this.engine;
+    const id = engine.onDecision((event) => callback(JSON.parse(event) as CupcakeEvent));
+    return () => {
+      engine.offDecision(id);
+    };
+  }
+
   /**
    * Get the Cupcake version
    */
@@ -287,6 +517,18 @@ export class Cupcake {
   get isReady(): boolean {
     return this.engine?.isReady() ?? false;
   }
+
+  /**
+   * Readiness, version and last reload time
+   *
+   * @throws {CupcakeError} If engine not initialized
+   */
+  get health(): CupcakeHealth {
+    if (!this.engine) {
+      throw new CupcakeError('Cupcake not initialized', 'NOT_INITIALIZED');
+    }
+    return JSON.parse(this.engine.health()) as CupcakeHealth;
+  }
 }
 
 // Module-level singleton for convenience API
@@ -299,7 +541,7 @@ let defaultInstance: Cupcake | null = null;
  * use the class-based API instead.
  *
  * @param path - Path to project directory or .cupcake folder
- * @param harness - Harness type for policy namespace
+ * @param options - Harness type, or engine options
  *
  * @example
  * ```typescript
@@ -309,9 +551,9 @@ let defaultInstance: Cupcake | null = null;
  * const decision = await evaluate(event);
  * ```
  */
-export async function init(path?: string, harness?: 'claude' | 'cursor'): Promise<void> {
+export async function init(path?: string, options?: Harness | CupcakeOptions): Promise<void> {
   defaultInstance = new Cupcake();
-  await defaultInstance.init(path, harness);
+  await defaultInstance.init(path, options);
 }
 
 /**
@@ -319,9 +561,9 @@ export async function init(path?: string, harness?: 'claude' | 'cursor'): Promis
  *
  * ⚠️  Use async `init()` instead for servers.
  */
-export function initSync(path?: string, harness?: 'claude' | 'cursor'): void {
+export function initSync(path?: string, options?: Harness | CupcakeOptions): void {
   defaultInstance = new Cupcake();
-  defaultInstance.initSync(path, harness);
+  defaultInstance.initSync(path, options);
 }
 
 /**
@@ -349,6 +591,19 @@ export function evaluateSync(event: HookEvent): Decision {
   return defaultInstance.evaluateSync(event);
 }
 
+/**
+ * Subscribe to events from the default instance
+ *
+ * @param callback - Called with each decision, error and telemetry event
+ * @returns A function that removes the subscription
+ */
+export function onDecision(callback: (event: CupcakeEvent) => void): () => void {
+  if (!defaultInstance) {
+    throw new CupcakeError('Cupcake not initialized. Call init() first.', 'NOT_INITIALIZED');
+  }
+  return defaultInstance.onDecision(callback);
+}
+
 /**
  * Get the Cupcake version
  */
@@ -366,5 +621,43 @@ export function isReady(): boolean {
   return defaultInstance?.isReady ?? false;
 }
 
+/**
+ * Get the health of the default instance
+ */
+export function health(): CupcakeHealth {
+  if (!defaultInstance) {
+    throw new CupcakeError('Cupcake not initialized', 'NOT_INITIALIZED');
+  }
+  return defaultInstance.health;
+}
+
+/**
+ * Apply a FilterOutput decision's `filter` to a tool response
+ *
+ * For applications that run tools themselves: redacts and truncates the
+ * result exactly as the harness integrations do.
+ *
+ * @param filter - The decision's `filter`
+ * @param toolResponse - The tool's result (any JSON value)
+ * @returns The filtered result
+ *
+ * @throws {CupcakeError} If the filter or result can't be serialized
+ *
+ * @example
+ * ```typescript
+ * if (decision.decision === 'FilterOutput' && decision.filter) {
+ *   result = applyOutputFilter(decision.filter, result);
+ * }
+ * ```
+ */
+export function applyOutputFilter<T = unknown>(filter: NonNullable<Decision['filter']>, toolResponse: unknown): T {
+  try {
+    const filtered = nativeApplyOutputFilter(JSON.stringify(filter), JSON.stringify(toolResponse ?? null));
+    return JSON.parse(filtered) as T;
+  } catch (error) {
+    throw nativeError('Failed to apply output filter', 'FILTER_FAILED', error);
+  }
+}
+
 // Re-export the native engine class for advanced usage
 export const PolicyEngine = NativePolicyEngine as unknown as NativePolicyEngineClass;
@@ -7,6 +7,8 @@ module.exports = {
   collectCoverageFrom: [
     'index.ts',
     'installer.ts',
+    'middleware.ts',
+    'claude-agent-sdk.ts',
     '!**/*.d.ts',
     '!**/node_modules/**',
   ],
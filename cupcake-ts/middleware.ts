@@ -0,0 +1,335 @@
+/**
+ * Tool-call middleware for agent frameworks
+ *
+ * Wraps the tools of an in-process agent (OpenAI Agents SDK, Vercel AI SDK, or
+ * a hand-written function-calling loop) so every call is evaluated by Cupcake
+ * before it runs, and its result is evaluated again before the model sees it.
+ *
+ * Calls are presented to policies as Claude Code `PreToolUse` / `PostToolUse`
+ * events, so initialize the engine with the `claude` harness (the default) and
+ * use `toolNames` to map your tools onto names like `Bash` to reuse the builtins.
+ *
+ * @example
+ * ```typescript
+ * import { Cupcake } from '@eqtylab/cupcake';
+ * import { withCupcake } from '@eqtylab/cupcake/middleware';
+ *
+ * const cupcake = new Cupcake();
+ * await cupcake.init('.cupcake');
+ *
+ * const tools = withCupcake({ shell, readFile }, cupcake, { toolNames: { shell: 'Bash' } });
+ * ```
+ *
+ * @packageDocumentation
+ */
+
+import { randomUUID } from 'crypto';
+import { applyOutputFilter, CupcakeError, Decision, HookEvent } from './index';
+
+/**
+ * Anything that evaluates events - a `Cupcake` instance, or a stub in tests
+ */
+export interface Evaluator {
+  evaluate(event: HookEvent): Promise<Decision>;
+}
+
+/**
+ * A single tool call made by the model
+ */
+export interface ToolCall {
+  /** Tool name as registered with the framework */
+  name: string;
+
+  /** Parsed tool arguments */
+  input: Record<string, any>;
+
+  /** Framework's id for the call, if it has one */
+  callId?: string;
+}
+
+/**
+ * Options shared by all adapters
+ */
+export interface MiddlewareOptions {
+  /** Session id for the events (default: a random id per wrapped tool set) */
+  sessionId?: string;
+
+  /** Working directory for the events (default: `process.cwd()`) */
+  cwd?: string;
+
+  /**
+   * Name policies see for each tool, e.g. `{ run_shell: 'Bash' }`
+   *
+   * Unmapped tools keep their own name.
+   */
+  toolNames?: Record<string, string>;
+
+  /**
+   * Called for Ask decisions; resolve `true` to run the tool
+   *
+   * Without it, Ask decisions are refused like Deny.
+   */
+  onAsk?: (call: ToolCall, reason: string) => boolean | Promise<boolean>;
+
+  /**
+   * What a refused call does (default: 'throw')
+   *
+   * - `'throw'` - throw a `CupcakePolicyError`
+   * - `'return'` - return the reason as the tool result, so the model can adjust
+   *
+   * Halt decisions always throw.
+   */
+  onDeny?: 'throw' | 'return';
+
+  /** Evaluate each result as a `PostToolUse` event (default: true) */
+  postToolUse?: boolean;
+}
+
+/**
+ * Thrown when a policy refuses a tool call
+ */
+export class CupcakePolicyError extends CupcakeError {
+  constructor(
+    message: string,
+    public readonly decision: Decision,
+    public readonly toolName: string,
+  ) {
+    super(message, 'POLICY_DENIED');
+    this.name = 'CupcakePolicyError';
+  }
+}
+
+/**
+ * Evaluate a tool call, run it if allowed, and filter its result
+ *
+ * The building block of the framework adapters; use it directly in a custom
+ * function-calling loop.
+ *
+ * - Halt throws; Deny and Block are refused per `onDeny`
+ * - Ask runs the tool only if `onAsk` approves
+ * - Modify runs the tool with the policy's `updated_input`
+ * - After the call, Deny and Block replace the result per `onDeny`, and
+ *   FilterOutput redacts or truncates it
+ *
+ * @param cupcake - Engine to evaluate with
+ * @param call - The tool call
+ * @param execute - Runs the tool with the (possibly modified) input
+ * @param options - Middleware options
+ * @returns The tool's result, or the refusal reason with `onDeny: 'return'`
+ *
+ * @throws {CupcakePolicyError} If a policy refuses the call
+ */
+export async function guardToolCall<T>(
+  cupcake: Evaluator,
+  call: ToolCall,
+  execute: (input: Record<string, any>) => T | Promise<T>,
+  options: MiddlewareOptions = {},
+): Promise<T | string> {
+  const pre = await cupcake.evaluate(toolEvent('PreToolUse', call, options));
+  let input = call.input;
+
+  switch (pre.decision) {
+    case 'Halt':
+    case 'Deny':
+    case 'Block':
+      return refuse(pre, call, options);
+    case 'Ask': {
+      const approved = options.onAsk ? await options.onAsk(call, pre.reason ?? '') : false;
+      if (!approved) {
+        return refuse(pre, call, options);
+      }
+      break;
+    }
+    case 'Modify':
+      input = pre.updated_input ?? input;
+      break;
+  }
+
+  const result = await execute(input);
+  if (options.postToolUse === false) {
+    return result;
+  }
+
+  const post = await cupcake.evaluate(toolEvent('PostToolUse', { ...call, input }, options, result));
+  switch (post.decision) {
+    case 'Halt':
+    case 'Deny':
+    case 'Block':
+      return refuse(post, call, options);
+    case 'FilterOutput':
+      return post.filter ? applyOutputFilter<T>(post.filter, result) : result;
+    default:
+      return result;
+  }
+}
+
+/**
+ * Add Cupcake enforcement to a Vercel AI SDK tool set
+ *
+ * Each tool's `execute(args, { toolCallId })` is wrapped; tools without
+ * `execute` (client-side tools) are passed through unchanged.
+ *
+ * @example
+ * ```typescript
+ * const result = await generateText({
+ *   model,
+ *   tools: withCupcake({ weather, shell }, cupcake, { toolNames: { shell: 'Bash' } }),
+ *   prompt,
+ * });
+ * ```
+ */
+export function withCupcake<TOOLS extends Record<string, { execute?: (args: any, options?: any) => any }>>(
+  tools: TOOLS,
+  cupcake: Evaluator,
+  options: MiddlewareOptions = {},
+): TOOLS {
+  const resolved = withSession(options);
+  const wrapped: Record<string, unknown> = {};
+
+  for (const [name, tool] of Object.entries(tools)) {
+    const execute = tool.execute;
+    wrapped[name] = execute
+      ? {
+          ...tool,
+          execute: (args: Record<string, any>, executeOptions?: { toolCallId?: string }) =>
+            guardToolCall(
+              cupcake,
+              { name, input: args, callId: executeOptions?.toolCallId },
+              (input) => execute(input, executeOptions),
+              resolved,
+            ),
+        }
+      : tool;
+  }
+  return wrapped as TOOLS;
+}
+
+/**
+ * Add Cupcake enforcement to an OpenAI Agents SDK function tool
+ *
+ * Wraps the tool's `invoke(runContext, input, details)`, where `input` is the
+ * JSON argument string from the model. Modified input is re-serialized.
+ *
+ * @example
+ * ```typescript
+ * const agent = new Agent({
+ *   name: 'Assistant',
+ *   tools: [shell, search].map((t) => guardAgentTool(t, cupcake, { toolNames: { shell: 'Bash' } })),
+ * });
+ * ```
+ */
+export function guardAgentTool<
+  TOOL extends { name: string; invoke: (runContext: any, input: string, details?: any) => Promise<any> },
+>(tool: TOOL, cupcake: Evaluator, options: MiddlewareOptions = {}): TOOL {
+  const resolved = withSession(options);
+
+  return {
+    ...tool,
+    invoke: async (runContext: any, input: string, details?: { toolCall?: { callId?: string } }) =>
+      guardToolCall(
+        cupcake,
+        { name: tool.name, input: parseArguments(input), callId: details?.toolCall?.callId },
+        (args) => tool.invoke(runContext, JSON.stringify(args), details),
+        resolved,
+      ),
+  } as TOOL;
+}
+
+/**
+ * A function call as returned by the OpenAI API
+ *
+ * Chat Completions nests the name and arguments under `function`; the
+ * Responses API puts them on the call itself.
+ */
+export interface FunctionCall {
+  id?: string;
+  call_id?: string;
+  function?: { name: string; arguments: string };
+  name?: string;
+  arguments?: string;
+}
+
+/**
+ * Evaluate and run a raw OpenAI function call (Chat Completions or Responses API)
+ *
+ * @param cupcake - Engine to evaluate with
+ * @param toolCall - The call as returned by the API; `arguments` is a JSON string
+ * @param execute - Runs the function with the parsed (possibly modified) arguments
+ * @param options - Middleware options
+ *
+ * @example
+ * ```typescript
+ * for (const toolCall of message.tool_calls ?? []) {
+ *   const output = await guardFunctionCall(cupcake, toolCall, (args) => functions[toolCall.function.name](args));
+ *   messages.push({ role: 'tool', tool_call_id: toolCall.id, content: JSON.stringify(output) });
+ * }
+ * ```
+ */
+export async function guardFunctionCall<T>(
+  cupcake: Evaluator,
+  toolCall: FunctionCall,
+  execute: (args: Record<string, any>) => T | Promise<T>,
+  options: MiddlewareOptions = {},
+): Promise<T | string> {
+  const name = toolCall.function?.name ?? toolCall.name ?? '';
+  const args = toolCall.function?.arguments ?? toolCall.arguments ?? '{}';
+  return guardToolCall(
+    cupcake,
+    { name, input: parseArguments(args), callId: toolCall.call_id ?? toolCall.id },
+    execute,
+    withSession(options),
+  );
+}
+
+/**
+ * Build the Claude Code hook event for a tool call
+ */
+function toolEvent(
+  hookEventName: 'PreToolUse' | 'PostToolUse',
+  call: ToolCall,
+  options: MiddlewareOptions,
+  result?: unknown,
+): HookEvent {
+  const event: HookEvent = {
+    hook_event_name: hookEventName,
+    session_id: options.sessionId ?? randomUUID(),
+    transcript_path: '',
+    cwd: options.cwd ?? process.cwd(),
+    tool_name: options.toolNames?.[call.name] ?? call.name,
+    tool_input: call.input,
+  };
+  if (call.callId) {
+    event.tool_use_id = call.callId;
+  }
+  if (hookEventName === 'PostToolUse') {
+    event.tool_response = result ?? null;
+  }
+  return event;
+}
+
+/**
+ * Refuse a call: throw, or return the reason for the model
+ */
+function refuse(decision: Decision, call: ToolCall, options: MiddlewareOptions): string {
+  const message = `Policy blocked ${call.name}: ${decision.reason ?? decision.decision}`;
+  if (decision.decision === 'Halt' || options.onDeny !== 'return') {
+    throw new CupcakePolicyError(message, decision, call.name);
+  }
+  return message;
+}
+
+/**
+ * Give a wrapped tool set one session id, so policies can correlate its calls
+ */
+function withSession(options: MiddlewareOptions): MiddlewareOptions {
+  return { ...options, sessionId: options.sessionId ?? randomUUID() };
+}
+
+function parseArguments(args: string): Record<string, any> {
+  try {
+    const parsed = JSON.parse(args || '{}');
+    return parsed !== null && typeof parsed === 'object' ? parsed : { value: parsed };
+  } catch (error) {
+    throw new CupcakeError(`Invalid tool arguments: ${(error as Error).message}`, 'INVALID_ARGUMENTS', error as Error);
+  }
+}
@@ -3,10 +3,50 @@
 //! This module provides NAPI-RS bindings that wrap the core BindingEngine,
 //! exposing a JavaScript-friendly API for policy evaluation in Node.js applications.
 
-use cupcake_core::bindings::BindingEngine;
+use cupcake_core::bindings::{self, BindingEngine, BindingError, BindingOptions};
 use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{
+    ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
+};
+use napi::JsFunction;
 use napi_derive::napi;
 
+/// Options for creating a PolicyEngine
+#[napi(object)]
+pub struct EngineOptions {
+    /// Harness type ('claude', 'cursor', 'factory' or 'opencode'). Defaults to 'claude'.
+    pub harness: Option<String>,
+    /// Maximum WASM memory in bytes (1MB-100MB). Defaults to 10MB.
+    pub wasm_max_memory: Option<u32>,
+    /// Path to the OPA binary. Defaults to the bundled OPA or system PATH.
+    pub opa_path: Option<String>,
+    /// Global config directory. Defaults to the platform config directory.
+    pub global_config: Option<String>,
+    /// Return a Deny decision instead of throwing when evaluation fails
+    pub fail_closed: Option<bool>,
+    /// Write a telemetry span for each evaluation
+    pub telemetry: Option<bool>,
+    /// Reload the engine when files under `.cupcake/` change
+    pub watch: Option<bool>,
+    /// Quiet period after a change before reloading, in ms. Defaults to 500.
+    pub watch_debounce_ms: Option<u32>,
+}
+
+impl From<EngineOptions> for BindingOptions {
+    fn from(options: EngineOptions) -> Self {
+        Self {
+            harness: options.harness,
+            wasm_max_memory: options.wasm_max_memory.map(|bytes| bytes as usize),
+            opa_path: options.opa_path.map(Into::into),
+            global_config: options.global_config.map(Into::into),
+            fail_closed: options.fail_closed.unwrap_or(false),
+            telemetry: options.telemetry.unwrap_or(false),
+            watch: options.watch.unwrap_or(false),
+            watch_debounce_ms: options.watch_debounce_ms.map(u64::from),
+        }
+    }
+}
+
 /// PolicyEngine class for evaluating policies in Node.js
 ///
 /// # Thread Safety
@@ -27,27 +67,32 @@ impl PolicyEngine {
     ///
     /// # Arguments
     /// * `path` - Path to project directory or .cupcake folder
-    /// * `harness` - Optional harness type ('claude' or 'cursor'). Defaults to 'claude'.
+    /// * `options` - Optional harness type string, or an `EngineOptions` object.
+    ///   Defaults to the 'claude' harness.
     ///
     /// # Errors
-    /// Returns error if:
-    /// - Path doesn't exist or isn't a valid Cupcake project
+    /// Returns a JSON-encoded `BindingError` if:
+    /// - The harness name is invalid (`InvalidInput`)
+    /// - Path doesn't exist or isn't a valid Cupcake project (`PolicyCompile`)
     /// - OPA binary not found (install with `npx @eqtylab/cupcake install-opa`)
-    /// - Policy compilation fails
+    /// - Policy compilation fails (`PolicyCompile`)
     ///
     /// # Example
     /// ```javascript
     /// const engine = new PolicyEngine('.cupcake', 'claude');
+    /// const strict = new PolicyEngine('.cupcake', { harness: 'cursor', failClosed: true });
     /// ```
     #[napi(constructor)]
-    pub fn new(path: String, harness: Option<String>) -> Result<Self> {
-        let harness_str = harness.as_deref().unwrap_or("claude");
-        let engine = BindingEngine::new(&path, harness_str).map_err(|e| {
-            Error::new(
-                Status::GenericFailure,
-                format!("Failed to initialize engine: {}", e),
-            )
-        })?;
+    pub fn new(path: String, options: Option<Either<String, EngineOptions>>) -> Result<Self> {
+        let options = match options {
+            Some(Either::A(harness)) => BindingOptions {
+                harness: Some(harness),
+                ..Default::default()
+            },
+            Some(Either::B(options)) => options.into(),
+            None => BindingOptions::default(),
+        };
+        let engine = BindingEngine::with_options(&path, &options).map_err(to_napi_error)?;
 
         Ok(Self { inner: engine })
     }
@@ -77,13 +122,7 @@ impl PolicyEngine {
     /// ```
     #[napi(js_name = "evaluateSync")]
     pub fn evaluate_sync(&self, input: String) -> Result<String> {
-        self.inner.evaluate_sync(&input).map_err(|e| {
-            if e.contains("Invalid input JSON") {
-                Error::new(Status::InvalidArg, e)
-            } else {
-                Error::new(Status::GenericFailure, e)
-            }
-        })
+        self.inner.evaluate_sync(&input).map_err(to_napi_error)
     }
 
     /// Asynchronously evaluate a hook event (RECOMMENDED, non-blocking)
@@ -109,9 +148,96 @@ impl PolicyEngine {
         AsyncTask::new(EvaluateTask {
             engine: self.inner.clone(),
             input,
+            hook_output: false,
+        })
+    }
+
+    /// Synchronously evaluate a Claude Code hook input (BLOCKS event loop)
+    ///
+    /// Like `evaluateSync`, but answers in Claude Code's hook output format.
+    /// Requires the 'claude' harness.
+    ///
+    /// # Arguments
+    /// * `input` - JSON string of the hook input (`hook_event_name`, `session_id`, ...)
+    ///
+    /// # Returns
+    /// JSON string with the hook output (`{}` when there is nothing to say)
+    #[napi(js_name = "evaluateHookSync")]
+    pub fn evaluate_hook_sync(&self, input: String) -> Result<String> {
+        self.inner.evaluate_hook_sync(&input).map_err(to_napi_error)
+    }
+
+    /// Asynchronously evaluate a Claude Code hook input (non-blocking)
+    ///
+    /// Used by the Claude Agent SDK adapter, whose hook callbacks take and
+    /// return the same objects as Claude Code's command hooks.
+    ///
+    /// # Example
+    /// ```javascript
+    /// const output = JSON.parse(await engine.evaluateHookAsync(JSON.stringify(input)));
+    /// ```
+    #[napi(js_name = "evaluateHookAsync")]
+    pub fn evaluate_hook_async(&self, input: String) -> AsyncTask<EvaluateTask> {
+        AsyncTask::new(EvaluateTask {
+            engine: self.inner.clone(),
+            input,
+            hook_output: true,
         })
     }
 
+    /// Reload policies from disk (BLOCKS event loop while recompiling)
+    ///
+    /// Keeps the path and options the engine was created with. If the reload
+    /// fails, the engine keeps evaluating with the previously loaded policies.
+    ///
+    /// # Example
+    /// ```javascript
+    /// engine.reload();
+    /// ```
+    #[napi]
+    pub fn reload(&self) -> Result<()> {
+        self.inner.reload().map_err(to_napi_error)
+    }
+
+    /// Subscribe to decision, error and telemetry events
+    ///
+    /// The callback receives each event as a JSON string on the event loop,
+    /// after the evaluation that produced it. Subscriptions don't keep the
+    /// process alive.
+    ///
+    /// # Returns
+    /// A subscription id for `offDecision`
+    ///
+    /// # Example
+    /// ```javascript
+    /// const id = engine.onDecision((event) => console.log(JSON.parse(event).type));
+    /// engine.offDecision(id);
+    /// ```
+    #[napi(
+        js_name = "onDecision",
+        ts_args_type = "callback: (event: string) => void"
+    )]
+    pub fn on_decision(&self, env: Env, callback: JsFunction) -> Result<u32> {
+        let mut tsfn: ThreadsafeFunction<String, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<String>| {
+                ctx.env.create_string(&ctx.value).map(|event| vec![event])
+            })?;
+        tsfn.unref(&env)?;
+
+        let id = self.inner.subscribe(move |event| {
+            if let Ok(json) = serde_json::to_string(event) {
+                tsfn.call(json, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        });
+        Ok(id as u32)
+    }
+
+    /// Remove a subscription; returns false if it was already removed
+    #[napi(js_name = "offDecision")]
+    pub fn off_decision(&self, id: u32) -> bool {
+        self.inner.unsubscribe(id as u64)
+    }
+
     /// Get the Cupcake version string
     #[napi]
     pub fn version(&self) -> String {
@@ -123,6 +249,34 @@ impl PolicyEngine {
     pub fn is_ready(&self) -> bool {
         self.inner.is_ready()
     }
+
+    /// Readiness, version and last reload time as a JSON string
+    ///
+    /// # Example
+    /// ```javascript
+    /// const { lastReload, lastReloadError } = JSON.parse(engine.health());
+    /// ```
+    #[napi]
+    pub fn health(&self) -> Result<String> {
+        serde_json::to_string(&self.inner.health())
+            .map_err(|e| Error::from_reason(format!("Failed to serialize health: {e}")))
+    }
+}
+
+/// Apply a FilterOutput decision's `filter` to a tool response
+///
+/// Used by the tool-call middleware, which runs tools in-process and must
+/// redact and truncate their results the way the harnesses do.
+///
+/// # Arguments
+/// * `filter` - JSON string of the decision's `filter` object
+/// * `tool_response` - JSON string of the tool's result
+///
+/// # Returns
+/// JSON string with the filtered tool response
+#[napi(js_name = "applyOutputFilter")]
+pub fn apply_output_filter(filter: String, tool_response: String) -> Result<String> {
+    bindings::apply_output_filter(&filter, &tool_response).map_err(to_napi_error)
 }
 
 /// Background task for async evaluation
@@ -131,6 +285,8 @@ impl PolicyEngine {
 pub struct EvaluateTask {
     engine: BindingEngine,
     input: String,
+    /// Answer in Claude Code's hook output format instead of a decision
+    hook_output: bool,
 }
 
 #[napi]
@@ -140,13 +296,12 @@ impl Task for EvaluateTask {
 
     /// Compute runs on a background thread
     fn compute(&mut self) -> Result<Self::Output> {
-        self.engine.evaluate_sync(&self.input).map_err(|e| {
-            if e.contains("Invalid input JSON") {
-                Error::new(Status::InvalidArg, e)
-            } else {
-                Error::new(Status::GenericFailure, e)
-            }
-        })
+        let result = if self.hook_output {
+            self.engine.evaluate_hook_sync(&self.input)
+        } else {
+            self.engine.evaluate_sync(&self.input)
+        };
+        result.map_err(to_napi_error)
     }
 
     /// Resolve runs on the main thread to convert to JS value
@@ -155,12 +310,27 @@ impl Task for EvaluateTask {
     }
 }
 
+/// Convert a binding error into a JS error
+///
+/// The message is the error's JSON form (`{"kind": ..., "message": ...}`),
+/// which `index.ts` turns into a `CupcakeError` with a matching code.
+fn to_napi_error(error: BindingError) -> Error {
+    let status = match error {
+        BindingError::InvalidInput(_) => Status::InvalidArg,
+        _ => Status::GenericFailure,
+    };
+    Error::new(status, error.to_json())
+}
+
 /// Thread safety verification
 /// This const block ensures the BindingEngine is Send + Sync at compile time
 const _: () = {
+    #[allow(dead_code)] // Intentionally uncalled - exists only for type checking
     fn assert_send<T: Send>() {}
+    #[allow(dead_code)] // Intentionally uncalled - exists only for type checking
     fn assert_sync<T: Sync>() {}
 
+    #[allow(dead_code)] // Never executed - compiler type-checks this at compile time
     fn _assertions() {
         assert_send::<PolicyEngine>();
         assert_sync::<PolicyEngine>();
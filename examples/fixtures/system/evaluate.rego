@@ -24,6 +24,7 @@ evaluate := decision_set if {
         "blocks": collect_verbs("block"),
         "asks": collect_verbs("ask"),
         "modifications": collect_verbs("modify"),
+        "output_filters": collect_verbs("filter_output"),
         "add_context": collect_verbs("add_context")
     }
 }
@@ -0,0 +1,118 @@
+# cupcake:template claude factory opencode
+# METADATA
+# scope: package
+# title: Binary File Guard - Builtin Policy
+# authors: ["Cupcake Builtins"]
+# custom:
+#   severity: MEDIUM
+#   id: BUILTIN-BINARY-FILE-GUARD
+#   routing:
+#     required_events: ["PreToolUse"]
+#     required_tools: ["Write", "Edit", "MultiEdit", "NotebookEdit"]
+package cupcake.policies.builtins.binary_file_guard
+
+import rego.v1
+
+editing_tools := {"Write", "Edit", "MultiEdit", "NotebookEdit"}
+
+deny contains decision if {
+	get_action == "deny"
+	some violation in violations
+	decision := make_decision(violation)
+}
+
+ask contains decision if {
+	get_action == "ask"
+	some violation in violations
+	decision := make_decision(violation)
+}
+
+make_decision(violation) := {
+	"rule_id": "BUILTIN-BINARY-FILE-GUARD",
+	"reason": concat("", [get_configured_message, " (", violation, ")"]),
+	"severity": "MEDIUM",
+}
+
+violations contains concat("", [file_path, " contains binary data"]) if {
+	input.hook_event_name == "PreToolUse"
+	input.tool_name == "Write"
+	file_path := input.tool_input.file_path
+	contains(input.tool_input.content, "\u0000")
+}
+
+violations contains concat("", [file_path, " is ", format_int(size, 10), " bytes, limit is ", format_int(get_max_file_bytes, 10)]) if {
+	input.hook_event_name == "PreToolUse"
+	input.tool_name == "Write"
+	file_path := input.tool_input.file_path
+	size := count(input.tool_input.content)
+	size > get_max_file_bytes
+}
+
+violations contains concat("", [file_path, " is a binary file type"]) if {
+	some file_path in edited_paths
+	lower(file_extension(file_path)) in get_binary_extensions
+}
+
+violations contains concat("", [file_path, " is marked binary in .gitattributes (", pattern, ")"]) if {
+	some file_path in edited_paths
+	some pattern in gitattributes_binary_patterns
+	matches_gitattributes(pattern, file_path)
+}
+
+edited_paths contains input.tool_input.file_path if {
+	input.hook_event_name == "PreToolUse"
+	input.tool_name in editing_tools
+	input.tool_input.file_path
+}
+
+edited_paths contains input.tool_input.notebook_path if {
+	input.hook_event_name == "PreToolUse"
+	input.tool_name == "NotebookEdit"
+	input.tool_input.notebook_path
+}
+
+file_extension(path) := ext if {
+	basename := regex.replace(path, `.*/`, "")
+	parts := split(basename, ".")
+	count(parts) > 1
+	ext := parts[count(parts) - 1]
+} else := ""
+
+# One pattern per line, as printed by the __builtin_binary_file_guard_gitattributes signal
+gitattributes_binary_patterns contains pattern if {
+	output := input.signals.__builtin_binary_file_guard_gitattributes
+	is_string(output)
+	some line in split(output, "\n")
+	pattern := trim_space(line)
+	pattern != ""
+}
+
+# Patterns without a slash match the file name at any depth
+matches_gitattributes(pattern, file_path) if {
+	not contains(trim_suffix(pattern, "/"), "/")
+	glob.match(pattern, ["/"], regex.replace(file_path, `.*/`, ""))
+}
+
+# Patterns with a slash are anchored at the repository root
+matches_gitattributes(pattern, file_path) if {
+	contains(trim_suffix(pattern, "/"), "/")
+	glob.match(trim_prefix(pattern, "/"), ["/"], relative_path(file_path))
+}
+
+relative_path(file_path) := trim_prefix(file_path, concat("", [trim_suffix(input.cwd, "/"), "/"])) if {
+	input.cwd
+} else := file_path
+
+get_configured_message := msg if {
+	msg := input.builtin_config.binary_file_guard.message
+} else := "Binary or oversized file write blocked"
+
+get_action := action if {
+	action := input.builtin_config.binary_file_guard.action
+} else := "deny"
+
+get_max_file_bytes := n if {
+	n := input.builtin_config.binary_file_guard.max_file_bytes
+} else := 1048576
+
+get_binary_extensions := {lower(ext) | some ext in input.builtin_config.binary_file_guard.binary_extensions}
@@ -0,0 +1,120 @@
+# cupcake:template
+# METADATA
+# scope: package
+# title: Change Freeze - Builtin Policy
+# authors: ["Cupcake Builtins"]
+# custom:
+#   severity: HIGH
+#   id: BUILTIN-CHANGE-FREEZE
+#   routing:
+#     required_events: ["{{shell_event}}"]
+#     required_tools: ["Bash"] # cupcake:unless cursor
+#     required_signals: ["builtin.time"]
+package cupcake.policies.builtins.change_freeze
+
+import rego.v1
+
+import data.cupcake.system.commands
+
+deny contains decision if {
+	get_action == "deny"
+	some violation in violations
+	decision := make_decision(violation)
+}
+
+ask contains decision if {
+	get_action == "ask"
+	some violation in violations
+	decision := make_decision(violation)
+}
+
+make_decision(violation) := {
+	"rule_id": "BUILTIN-CHANGE-FREEZE",
+	"reason": concat("", [get_configured_message, " (", violation, ")"]),
+	"severity": "HIGH",
+}
+
+# Collapse repeated whitespace so "git  push" still matches "git push"
+shell_command := concat(" ", [t | some t in split(lower({{shell_command}}), " "); t != ""]) if {
+	input.hook_event_name == "{{shell_event}}"
+	input.tool_name == "Bash" # cupcake:unless cursor
+}
+
+guarded_operations contains op if {
+	some op in get_operations
+	commands.has_verb(shell_command, lower(op))
+}
+
+violations contains concat("", [op, " during ", reason]) if {
+	some op in guarded_operations
+	some reason in active_freezes
+}
+
+violations contains concat("", [op, " outside allowed change windows"]) if {
+	some op in guarded_operations
+	count(get_windows) > 0
+	not in_change_window
+}
+
+# Declared freeze periods; dates or datetimes compared at their own precision
+active_freezes contains freeze_reason(freeze) if {
+	some freeze in get_freezes
+	now := input.signals.builtin.time.local
+	substring(now, 0, count(freeze.start)) >= freeze.start
+	substring(now, 0, count(freeze.end)) <= freeze.end
+}
+
+# Freeze reported by the change-management system
+active_freezes contains status_reason(status) if {
+	status := input.signals.__builtin_change_freeze_status
+	status.frozen == true
+}
+
+freeze_reason(freeze) := freeze.reason if {
+	freeze.reason
+} else := concat("", ["change freeze ", freeze.start, " to ", freeze.end])
+
+status_reason(status) := status.reason if {
+	status.reason
+} else := "change freeze reported by change management"
+
+in_change_window if {
+	time := input.signals.builtin.time
+	now_minutes := (time.hour * 60) + time.minute
+	some window in get_windows
+	day_allowed(window, lower(substring(time.weekday, 0, 3)))
+	now_minutes >= clock_minutes(window.start)
+	now_minutes < clock_minutes(window.end)
+}
+
+day_allowed(window, _) if not window.days
+
+day_allowed(window, day) if {
+	some d in window.days
+	lower(substring(d, 0, 3)) == day
+}
+
+# "09:30" -> 570
+clock_minutes(clock) := (to_number(parts[0]) * 60) + to_number(parts[1]) if {
+	parts := split(clock, ":")
+}
+
+get_configured_message := msg if {
+	msg := input.builtin_config.change_freeze.message
+} else := "Change blocked by change freeze policy"
+
+get_action := action if {
+	action := input.builtin_config.change_freeze.action
+} else := "deny"
+
+get_operations := ops if {
+	ops := input.builtin_config.change_freeze.operations
+} else := []
+
+get_windows := windows if {
+	windows := input.builtin_config.change_freeze.windows
+} else := []
+
+get_freezes := freezes if {
+	freezes := input.builtin_config.change_freeze.freezes
+} else := []
@@ -0,0 +1,170 @@
+# cupcake:template
+# METADATA
+# scope: package
+# title: Container Guard - Builtin Policy
+# authors: ["Cupcake Builtins"]
+# custom:
+#   severity: HIGH
+#   id: BUILTIN-CONTAINER-GUARD
+#   routing:
+#     required_events: ["{{shell_event}}"]
+#     required_tools: ["Bash"] # cupcake:unless cursor
+package cupcake.policies.builtins.container_guard
+
+import rego.v1
+
+import data.cupcake.system.commands
+
+container_clis := {"docker", "podman", "nerdctl"}
+
+# Host paths that must never be mounted into a container
+default_sensitive_mounts := {
+	"/", "/etc", "/root", "/boot", "/dev", "/proc", "/sys",
+	"/var/run/docker.sock", "/run/docker.sock", "/run/containerd",
+	"~", "~/.ssh", "~/.aws", "~/.kube", "~/.docker", "~/.config/gcloud",
+	"$home", "${home}",
+}
+
+# kubectl delete targets that take out far more than a single workload
+destructive_kubectl_targets := {
+	"namespace", "namespaces", "ns", "node", "nodes", "pv", "persistentvolume",
+	"persistentvolumes", "crd", "crds", "customresourcedefinition", "customresourcedefinitions",
+}
+
+deny contains decision if {
+	get_action == "deny"
+	some violation in violations
+	decision := make_decision(violation)
+}
+
+ask contains decision if {
+	get_action == "ask"
+	some violation in violations
+	decision := make_decision(violation)
+}
+
+make_decision(violation) := {
+	"rule_id": "BUILTIN-CONTAINER-GUARD",
+	"reason": concat("", [get_configured_message, " (", violation, ")"]),
+	"severity": "HIGH",
+}
+
+shell_command := lower({{shell_command}}) if {
+	input.hook_event_name == "{{shell_event}}"
+	input.tool_name == "Bash" # cupcake:unless cursor
+}
+
+violations contains "privileged container" if {
+	starts_container(shell_command)
+	privileged(shell_command)
+}
+
+violations contains "host namespace sharing" if {
+	starts_container(shell_command)
+	shares_host_namespace(shell_command)
+}
+
+violations contains concat("", ["host mount of ", path]) if {
+	starts_container(shell_command)
+	some path in mounted_sensitive_paths(shell_command)
+}
+
+violations contains "docker system/volume prune" if {
+	commands.has_dangerous_verb(shell_command, container_clis)
+	prunes(shell_command)
+}
+
+violations contains concat("", ["kubectl delete ", target]) if {
+	commands.has_verb(shell_command, "kubectl")
+	commands.has_verb(shell_command, "delete")
+	some target in kubectl_delete_targets(shell_command)
+}
+
+starts_container(cmd) if {
+	commands.has_dangerous_verb(cmd, container_clis)
+	commands.has_dangerous_verb(cmd, {"run", "create"})
+}
+
+privileged(cmd) if contains(cmd, "--privileged")
+
+privileged(cmd) if regex.match(`--cap-add[ =](all|sys_admin|sys_ptrace|sys_module)(\s|$)`, cmd)
+
+privileged(cmd) if regex.match(`--security-opt[ =](seccomp|apparmor)[=:]unconfined`, cmd)
+
+shares_host_namespace(cmd) if regex.match(`--(pid|ipc|uts|userns)[ =]host(\s|$)`, cmd)
+
+prunes(cmd) if regex.match(`(^|\s)(system|volume)\s+prune(\s|$)`, cmd)
+
+# Sensitive host paths used as bind-mount sources (-v, --volume, --mount)
+mounted_sensitive_paths(cmd) := {path |
+	some source in mount_sources(cmd)
+	some path in sensitive_mounts
+	mount_covers(source, path)
+}
+
+mount_sources(cmd) := {source |
+	tokens := [t | some t in split(cmd, " "); t != ""]
+	some i, token in tokens
+	value := mount_value(tokens, i, token)
+	some source in value_sources(value)
+}
+
+mount_value(tokens, i, token) := tokens[i + 1] if token in {"-v", "--volume", "--mount"}
+
+mount_value(_, _, token) := substring(token, 9, -1) if startswith(token, "--volume=")
+
+mount_value(_, _, token) := substring(token, 8, -1) if startswith(token, "--mount=")
+
+mount_value(_, _, token) := substring(token, 2, -1) if {
+	startswith(token, "-v")
+	count(token) > 2
+	not startswith(token, "-v=")
+}
+
+# -v /etc:/host/etc:ro -> /etc
+value_sources(value) := {trim(split(value, ":")[0], "\"'")} if not contains(value, "=")
+
+# --mount type=bind,source=/etc,target=/host -> /etc
+value_sources(value) := {trim(substring(part, indexof(part, "=") + 1, -1), "\"'") |
+	contains(value, "=")
+	some part in split(value, ",")
+	key := split(part, "=")[0]
+	key in {"source", "src"}
+}
+
+# "/" only matches itself; other paths also cover everything beneath them
+mount_covers(source, path) if trim_right_slash(source) == path
+
+mount_covers(source, path) if {
+	path != "/"
+	startswith(source, concat("", [path, "/"]))
+}
+
+trim_right_slash(s) := s if s == "/"
+
+trim_right_slash(s) := trim_right(s, "/") if s != "/"
+
+kubectl_delete_targets(cmd) := {resource |
+	tokens := [t | some t in split(cmd, " "); t != ""]
+	some i, token in tokens
+	not tokens[i - 1] in {"-n", "--namespace"}
+	resource := split(token, "/")[0]
+	resource in destructive_kubectl_targets
+} | {flag |
+	some flag in ["--all", "--all-namespaces"]
+	regex.match(concat("", [`(^|\s)`, flag, `(\s|=true|$)`]), cmd)
+} | {"--all-namespaces" | regex.match(`(^|\s)-a(\s|$)`, cmd)}
+
+sensitive_mounts := default_sensitive_mounts | {lower(p) | some p in get_additional_mounts}
+
+get_configured_message := msg if {
+	msg := input.builtin_config.container_guard.message
+} else := "Container operation blocked by project policy"
+
+get_action := action if {
+	action := input.builtin_config.container_guard.action
+} else := "deny"
+
+get_additional_mounts := paths if {
+	paths := input.builtin_config.container_guard.additional_mounts
+} else := []
@@ -0,0 +1,199 @@
+# cupcake:template
+# METADATA
+# scope: package
+# title: Dependency Guard - Builtin Policy
+# authors: ["Cupcake Builtins"]
+# custom:
+#   severity: HIGH
+#   id: BUILTIN-DEPENDENCY-GUARD
+#   routing:
+#     required_events: ["{{shell_event}}"]
+#     required_tools: ["Bash", "Write", "Edit", "MultiEdit"] # cupcake:unless cursor
+package cupcake.policies.builtins.dependency_guard
+
+import rego.v1
+
+# Package manager invocations that add dependencies: manager -> install verbs
+install_verbs := {
+	"npm": {"install", "i", "add"},
+	"pnpm": {"install", "i", "add"},
+	"yarn": {"add"},
+	"bun": {"install", "i", "add"},
+	"pip": {"install"},
+	"pip3": {"install"},
+	"uv": {"add"},
+	"poetry": {"add"},
+	"cargo": {"add", "install"},
+	"go": {"get", "install"},
+}
+
+# Flags whose next token is a value, not a package
+value_flags := {
+	"-r", "--requirement", "-c", "--constraint", "-i", "--index-url",
+	"--extra-index-url", "-t", "--target", "--prefix", "--registry",
+	"--features", "-f", "--path", "--git", "--branch", "--tag", "--rev",
+}
+
+command_separators := {"&&", "||", ";", "|"}
+
+# cupcake:unless cursor
+# Manifests and lockfiles whose edits can introduce dependencies
+manifest_files := {
+	"package.json", "package-lock.json", "yarn.lock", "pnpm-lock.yaml", "bun.lockb",
+	"requirements.txt", "requirements-dev.txt", "pyproject.toml", "pipfile", "pipfile.lock",
+	"poetry.lock", "uv.lock", "cargo.toml", "cargo.lock", "go.mod", "go.sum",
+}
+
+# cupcake:end
+deny contains decision if {
+	get_action == "deny"
+	some violation in violations
+	decision := make_decision(violation)
+}
+
+ask contains decision if {
+	get_action == "ask"
+	some violation in violations
+	decision := make_decision(violation)
+}
+
+make_decision(violation) := {
+	"rule_id": "BUILTIN-DEPENDENCY-GUARD",
+	"reason": concat("", [get_configured_message, " (", violation, ")"]),
+	"severity": "HIGH",
+}
+
+# Packages installed from the shell
+shell_packages contains pkg if {
+	input.hook_event_name == "{{shell_event}}"
+	input.tool_name == "Bash" # cupcake:unless cursor
+	some pkg in requested_packages(lower({{shell_command}}))
+}
+
+# cupcake:unless cursor
+# Denied packages written into manifests or lockfiles
+violations contains violation if {
+	input.hook_event_name == "PreToolUse"
+	input.tool_name in {"Write", "Edit", "MultiEdit"}
+	is_manifest(input.tool_input.file_path)
+	some content in written_content
+	some pattern in get_deny_list
+	mentions_package(lower(content), lower(pattern))
+	violation := concat("", [pattern, " in ", input.tool_input.file_path])
+}
+
+written_content contains input.tool_input.content if input.tool_name == "Write"
+
+written_content contains input.tool_input.new_string if input.tool_name == "Edit"
+
+written_content contains edit.new_string if {
+	input.tool_name == "MultiEdit"
+	some edit in input.tool_input.edits
+}
+
+# cupcake:end
+violations contains concat("", [pkg, " is on the deny list"]) if {
+	some pkg in shell_packages
+	some pattern in get_deny_list
+	name_matches(pkg, lower(pattern))
+}
+
+violations contains concat("", [pkg, " is not on the allow list"]) if {
+	count(get_allow_list) > 0
+	some pkg in shell_packages
+	not allowed(pkg)
+}
+
+allowed(pkg) if {
+	some pattern in get_allow_list
+	name_matches(pkg, lower(pattern))
+}
+
+# Extract package names from every install invocation in a command
+requested_packages(cmd) := {name |
+	tokens := [t | some t in split(cmd, " "); t != ""]
+	some i, manager in tokens
+	tokens[i + 1] in install_verbs[manager]
+	some j, token in tokens
+	j > i + 1
+	not separator_between(tokens, i + 1, j)
+	arg := trim(token, "\"'")
+	is_package_arg(tokens, j, arg)
+	name := package_name(arg)
+	name != ""
+}
+
+separator_between(tokens, start, end) if {
+	some k, token in tokens
+	k > start
+	k <= end
+	token in command_separators
+}
+
+is_package_arg(tokens, j, arg) if {
+	not startswith(arg, "-")
+	not tokens[j - 1] in value_flags
+	not startswith(arg, ".")
+	not startswith(arg, "/")
+	not startswith(arg, "~")
+	not contains(arg, "://")
+	not arg in command_separators
+}
+
+# @scope/name@1.2.3 -> @scope/name
+package_name(arg) := name if {
+	startswith(arg, "@")
+	name := concat("", ["@", split(substring(arg, 1, -1), "@")[0]])
+}
+
+# name@1.2.3, name==1.0, name[extra]>=2 -> name
+package_name(arg) := name if {
+	not startswith(arg, "@")
+	name := strip_specifier(split(arg, "@")[0])
+}
+
+strip_specifier(s) := substring(s, 0, min(cuts)) if {
+	cuts := {i | some c in ["=", "<", ">", "!", "~", "[", ";"]; i := indexof(s, c); i >= 0}
+	count(cuts) > 0
+} else := s
+
+# cupcake:unless cursor
+is_manifest(path) if {
+	parts := split(lower(path), "/")
+	parts[count(parts) - 1] in manifest_files
+}
+
+# cupcake:end
+# Exact (case-insensitive) or glob (*) match on a package name
+name_matches(name, pattern) if name == pattern
+
+name_matches(name, pattern) if {
+	contains(pattern, "*")
+	regex.match(concat("", ["^", glob_to_regex(pattern), "$"]), name)
+}
+
+# cupcake:unless cursor
+# Package name appears as a whole token in manifest content
+mentions_package(content, pattern) if {
+	not contains(pattern, "*")
+	regex.match(concat("", ["(^|[^a-z0-9_.-])", glob_to_regex(pattern), "([^a-z0-9_.-]|$)"]), content)
+}
+
+# cupcake:end
+glob_to_regex(pattern) := replace(replace(replace(pattern, ".", "\\."), "+", "\\+"), "*", "[a-z0-9_./@-]*")
+
+get_configured_message := msg if {
+	msg := input.builtin_config.dependency_guard.message
+} else := "Dependency blocked by project policy"
+
+get_action := action if {
+	action := input.builtin_config.dependency_guard.action
+} else := "deny"
+
+get_deny_list := list if {
+	list := input.builtin_config.dependency_guard.deny
+} else := []
+
+get_allow_list := list if {
+	list := input.builtin_config.dependency_guard.allow
+} else := []
@@ -0,0 +1,162 @@
+# cupcake:template
+# METADATA
+# scope: package
+# title: Environment Exfiltration Guard - Builtin Policy
+# authors: ["Cupcake Builtins"]
+# custom:
+#   severity: HIGH
+#   id: BUILTIN-ENV-EXFILTRATION-GUARD
+#   routing:
+#     required_events: ["{{shell_event}}"]
+#     required_tools: ["Bash", "Write", "Edit", "MultiEdit"] # cupcake:unless cursor
+package cupcake.policies.builtins.env_exfiltration_guard
+
+import rego.v1
+
+# Commands that print every variable when run without arguments
+dump_commands := {"env", "printenv", "export", "set", "declare -x", "declare -p", "compgen -v"}
+
+# Commands whose output of a variable would land in the transcript
+print_commands := {"echo", "printf", "printenv"}
+
+deny contains decision if {
+	get_action == "deny"
+	some violation in violations
+	decision := make_decision(violation)
+}
+
+ask contains decision if {
+	get_action == "ask"
+	some violation in violations
+	decision := make_decision(violation)
+}
+
+make_decision(violation) := {
+	"rule_id": "BUILTIN-ENV-EXFILTRATION-GUARD",
+	"reason": concat("", [get_configured_message, " (", violation, ")"]),
+	"severity": "HIGH",
+}
+
+shell_command := {{shell_command}} if {
+	input.hook_event_name == "{{shell_event}}"
+	input.tool_name == "Bash" # cupcake:unless cursor
+}
+
+# Split on separators so "env | grep KEY" and "ls && env" are both seen
+segments contains trim_space(segment) if {
+	some segment in regex.split(`\s*(&&|\|\||;|\|)\s*`, shell_command)
+	trim_space(segment) != ""
+}
+
+violations contains concat("", ["dumps the environment with '", segment, "'"]) if {
+	some segment in segments
+	regex.replace(segment, `\s+`, " ") in dump_commands
+}
+
+violations contains "reads a process environment from /proc" if {
+	regex.match(`/proc/[^/\s]+/environ`, shell_command)
+}
+
+violations contains concat("", ["prints sensitive variable ", name]) if {
+	some segment in segments
+	split(segment, " ")[0] in print_commands
+	some name in referenced_variables(segment)
+	is_sensitive(name)
+}
+
+# printenv NAME prints without a $ reference
+violations contains concat("", ["prints sensitive variable ", name]) if {
+	some segment in segments
+	tokens := split(regex.replace(segment, `\s+`, " "), " ")
+	tokens[0] == "printenv"
+	some name in array.slice(tokens, 1, count(tokens))
+	is_sensitive(name)
+}
+
+# Cursor has no pre-edit hook, so only shell copies of .env files are caught # cupcake:if cursor
+violations contains concat("", ["copies ", env_file, " into another file"]) if {
+	some segment in segments
+	some env_file in copied_env_files(segment)
+}
+
+# cupcake:unless cursor
+# Hardcoded values for sensitive names written outside .env files
+violations contains concat("", ["writes a value for ", name, " into ", file_path]) if {
+	input.hook_event_name == "PreToolUse"
+	some write in written_content
+	file_path := write.file_path
+	not is_env_file(basename(file_path))
+	some match in regex.find_all_string_submatch_n(secret_assignment, write.content, -1)
+	name := match[2]
+	is_sensitive(name)
+}
+
+written_content contains {"file_path": input.tool_input.file_path, "content": input.tool_input.content} if {
+	input.tool_name == "Write"
+}
+
+written_content contains {"file_path": input.tool_input.file_path, "content": input.tool_input.new_string} if {
+	input.tool_name == "Edit"
+}
+
+written_content contains {"file_path": input.tool_input.file_path, "content": edit.new_string} if {
+	input.tool_name == "MultiEdit"
+	some edit in input.tool_input.edits
+}
+
+# NAME=value with a literal value (no $, calls or lookups), as found in .env files
+secret_assignment := `(?m)^\s*(export\s+)?([A-Za-z_][A-Za-z0-9_]*)\s*[:=]\s*["']?([^\s"'$(){}\[\]]{8,})["']?\s*$`
+
+# cupcake:end
+referenced_variables(segment) := {match[1] |
+	some match in regex.find_all_string_submatch_n(`\$\{?([A-Za-z_][A-Za-z0-9_]*)`, segment, -1)
+}
+
+# cp/mv with a .env source and a non-.env destination
+copied_env_files(segment) := {file |
+	tokens := split(regex.replace(segment, `\s+`, " "), " ")
+	tokens[0] in {"cp", "mv"}
+	not is_env_file(basename(tokens[count(tokens) - 1]))
+	some i, file in tokens
+	i > 0
+	i < count(tokens) - 1
+	is_env_file(basename(file))
+} | redirected_env_files(segment)
+
+# "cat .env > notes.txt" - the .env file appears before the redirect
+redirected_env_files(segment) := {file |
+	parts := split(segment, ">")
+	count(parts) > 1
+	not is_env_file(basename(trim_space(parts[count(parts) - 1])))
+	some file in split(regex.replace(trim_space(parts[0]), `\s+`, " "), " ")
+	is_env_file(basename(file))
+}
+
+is_env_file(name) if {
+	regex.match(`^\.env(\.[A-Za-z0-9_-]+)*$`, name)
+	not regex.match(`\.(example|sample|template|dist)$`, name)
+}
+
+basename(path) := regex.replace(path, `.*/`, "")
+
+is_sensitive(name) if {
+	not upper(name) in {upper(allowed) | some allowed in get_allow_vars}
+	some pattern in get_sensitive_patterns
+	glob.match(upper(pattern), [], upper(name))
+}
+
+get_configured_message := msg if {
+	msg := input.builtin_config.env_exfiltration_guard.message
+} else := "Environment variable exposure blocked"
+
+get_action := action if {
+	action := input.builtin_config.env_exfiltration_guard.action
+} else := "deny"
+
+get_sensitive_patterns := patterns if {
+	patterns := input.builtin_config.env_exfiltration_guard.sensitive_patterns
+} else := ["*SECRET*", "*TOKEN*", "*PASSWORD*", "*API_KEY*", "*PRIVATE_KEY*", "AWS_*"]
+
+get_allow_vars := vars if {
+	vars := input.builtin_config.env_exfiltration_guard.allow_vars
+} else := []
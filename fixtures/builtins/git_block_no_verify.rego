@@ -1,3 +1,4 @@
+# cupcake:template
 # METADATA
 # scope: package
 # title: Git Block No-Verify - Builtin Policy
@@ -6,8 +7,8 @@
 #   severity: HIGH
 #   id: BUILTIN-GIT-BLOCK-NO-VERIFY
 #   routing:
-#     required_events: ["PreToolUse"]
-#     required_tools: ["Bash"]
+#     required_events: ["{{shell_event}}"]
+#     required_tools: ["Bash"] # cupcake:unless cursor
 package cupcake.policies.builtins.git_block_no_verify
 
 import rego.v1
@@ -16,11 +17,11 @@ import data.cupcake.system.commands
 
 # Block git commands that bypass verification hooks
 deny contains decision if {
-	input.hook_event_name == "PreToolUse"
-	input.tool_name == "Bash"
+	input.hook_event_name == "{{shell_event}}"
+	input.tool_name == "Bash" # cupcake:unless cursor
 
-	# Get the command from tool input
-	command := lower(input.tool_input.command)
+	# Get the shell command
+	command := lower({{shell_command}})
 
 	# Check if it's a git command with --no-verify flag
 	contains_git_no_verify(command)
@@ -64,10 +65,10 @@ contains_git_no_verify(cmd) if {
 
 # Also block attempts to disable hooks via config
 deny contains decision if {
-	input.hook_event_name == "PreToolUse"
-	input.tool_name == "Bash"
+	input.hook_event_name == "{{shell_event}}"
+	input.tool_name == "Bash" # cupcake:unless cursor
 
-	command := lower(input.tool_input.command)
+	command := lower({{shell_command}})
 
 	# Check if trying to disable hooks via git config
 	contains_hook_disable(command)
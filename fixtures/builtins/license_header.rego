@@ -0,0 +1,76 @@
+# cupcake:template claude factory opencode
+# METADATA
+# scope: package
+# title: License Header - Builtin Policy
+# authors: ["Cupcake Builtins"]
+# custom:
+#   severity: LOW
+#   id: BUILTIN-LICENSE-HEADER
+#   routing:
+#     required_events: ["PostToolUse"]
+#     required_tools: ["Write"]
+package cupcake.policies.builtins.license_header
+
+import rego.v1
+
+# Extra lines allowed before the header (shebangs, encoding pragmas, blank lines)
+header_slack_lines := 3
+
+add_context contains instruction(missing) if {
+	get_action == "add_context"
+	some missing in missing_headers
+}
+
+block contains decision if {
+	get_action == "block"
+	some missing in missing_headers
+	decision := {
+		"rule_id": "BUILTIN-LICENSE-HEADER",
+		"reason": instruction(missing),
+		"severity": "LOW",
+	}
+}
+
+instruction(missing) := concat("", [
+	get_configured_message, ": ", missing.file_path,
+	" is missing its license header. Add this at the top of the file:\n\n", missing.header,
+])
+
+missing_headers contains {"file_path": file_path, "header": header} if {
+	input.hook_event_name == "PostToolUse"
+	input.tool_name == "Write"
+	is_new_file
+
+	file_path := input.tool_input.file_path
+	header := get_headers[file_extension(file_path)]
+	not has_header(input.tool_input.content, header)
+}
+
+# Claude Code reports whether Write created the file; without that, check every write
+is_new_file if input.tool_response.type == "create"
+
+is_new_file if not input.tool_response.type
+
+has_header(content, header) if {
+	header_lines := split(trim_space(header), "\n")
+	head_lines := array.slice(split(content, "\n"), 0, count(header_lines) + header_slack_lines)
+	contains(concat("\n", head_lines), trim_space(header))
+}
+
+file_extension(path) := ext if {
+	parts := split(path, ".")
+	count(parts) > 1
+	ext := lower(parts[count(parts) - 1])
+} else := ""
+
+get_configured_message := msg if {
+	msg := input.builtin_config.license_header.message
+} else := "License header required"
+
+get_action := action if {
+	action := input.builtin_config.license_header.action
+} else := "add_context"
+
+get_headers := headers if {
+	headers := input.builtin_config.license_header.headers
+} else := {}
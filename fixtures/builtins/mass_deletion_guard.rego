@@ -0,0 +1,136 @@
+# cupcake:template
+# METADATA
+# scope: package
+# title: Mass Deletion Guard - Builtin Policy
+# authors: ["Cupcake Builtins"]
+# custom:
+#   severity: HIGH
+#   id: BUILTIN-MASS-DELETION-GUARD
+#   routing:
+# cupcake:if cursor
+#     required_events: ["beforeShellExecution"]
+# cupcake:else
+#     required_events: ["PreToolUse", "PostToolUse"]
+#     required_tools: ["Edit", "MultiEdit", "Write", "Bash"]
+# cupcake:end
+package cupcake.policies.builtins.mass_deletion_guard
+
+import rego.v1
+
+# Shell commands whose arguments are files being removed or reverted
+file_removal_commands := [
+	["rm"],
+	["unlink"],
+	["git", "rm"],
+	["git", "restore"],
+	["git", "checkout", "--"],
+]
+
+command_separators := {"&&", "||", ";", "|"}
+
+deny contains decision if {
+	get_action == "deny"
+	some violation in violations
+	decision := make_decision(violation)
+}
+
+ask contains decision if {
+	get_action == "ask"
+	some violation in violations
+	decision := make_decision(violation)
+}
+
+make_decision(violation) := {
+	"rule_id": "BUILTIN-MASS-DELETION-GUARD",
+	"reason": concat("", [get_configured_message, " (", violation, ")"]),
+	"severity": "HIGH",
+}
+
+# cupcake:unless cursor
+# Edits that remove more lines than they add, beyond the threshold
+violations contains concat("", ["edit removes ", format_int(deleted, 10), " lines, limit is ", format_int(get_max_deleted_lines, 10)]) if {
+	input.hook_event_name == "PreToolUse"
+	input.tool_name in {"Edit", "MultiEdit"}
+	deleted := deleted_lines
+	deleted > get_max_deleted_lines
+}
+
+deleted_lines := line_count(input.tool_input.old_string) - line_count(input.tool_input.new_string) if {
+	input.tool_name == "Edit"
+}
+
+deleted_lines := sum([removed |
+	some edit in input.tool_input.edits
+	removed := line_count(edit.old_string) - line_count(edit.new_string)
+]) if {
+	input.tool_name == "MultiEdit"
+}
+
+# Write gives no old content up front, so check the patch it reports afterwards
+violations contains concat("", ["write removed ", format_int(deleted, 10), " lines, limit is ", format_int(get_max_deleted_lines, 10)]) if {
+	input.hook_event_name == "PostToolUse"
+	input.tool_name == "Write"
+	deleted := count([line |
+		some hunk in input.tool_response.structuredPatch
+		some line in hunk.lines
+		startswith(line, "-")
+	]) - count([line |
+		some hunk in input.tool_response.structuredPatch
+		some line in hunk.lines
+		startswith(line, "+")
+	])
+	deleted > get_max_deleted_lines
+}
+
+line_count(s) := 0 if s == ""
+
+line_count(s) := count(split(s, "\n")) if s != ""
+
+# cupcake:end
+# Shell commands removing or reverting more files than the threshold
+# cupcake:if cursor
+# Cursor has no pre-edit hook, so edit sizes cannot be checked here.
+# cupcake:end
+violations contains concat("", ["command touches ", format_int(touched, 10), " files, limit is ", format_int(get_max_files, 10)]) if {
+	input.hook_event_name == "{{shell_event}}"
+	input.tool_name == "Bash" # cupcake:unless cursor
+	touched := files_touched({{shell_command}})
+	touched > get_max_files
+}
+
+# Counts argument positions, so "git rm" is not counted again as "rm"
+files_touched(cmd) := count({j |
+	tokens := [t | some t in split(cmd, " "); t != ""]
+	some i, _ in tokens
+	some prefix in file_removal_commands
+	array.slice(tokens, i, i + count(prefix)) == prefix
+	some j, arg in tokens
+	j >= i + count(prefix)
+	not separator_between(tokens, i, j)
+	not startswith(arg, "-")
+})
+
+separator_between(tokens, start, end) if {
+	some k, token in tokens
+	k > start
+	k <= end
+	token in command_separators
+}
+
+get_configured_message := msg if {
+	msg := input.builtin_config.mass_deletion_guard.message
+} else := "Large deletion blocked"
+
+get_action := action if {
+	action := input.builtin_config.mass_deletion_guard.action
+} else := "deny"
+
+# cupcake:unless cursor
+get_max_deleted_lines := n if {
+	n := input.builtin_config.mass_deletion_guard.max_deleted_lines
+} else := 200
+
+# cupcake:end
+get_max_files := n if {
+	n := input.builtin_config.mass_deletion_guard.max_files
+} else := 20
@@ -0,0 +1,62 @@
+# cupcake:template claude factory
+# METADATA
+# scope: package
+# title: MCP Allowlist - Builtin Policy
+# authors: ["Cupcake Builtins"]
+# custom:
+#   severity: HIGH
+#   id: BUILTIN-MCP-ALLOWLIST
+#   routing:
+#     required_events: ["PreToolUse"]
+#     required_tools: ["mcp__*"]
+package cupcake.policies.builtins.mcp_allowlist
+
+import rego.v1
+
+ask contains decision if {
+	get_action == "ask"
+	violation
+	decision := make_decision
+}
+
+deny contains decision if {
+	get_action == "deny"
+	violation
+	decision := make_decision
+}
+
+make_decision := {
+	"rule_id": "BUILTIN-MCP-ALLOWLIST",
+	"reason": concat("", [get_configured_message, " (", input.tool_name, ")"]),
+	"severity": "HIGH",
+}
+
+# Server and tool are split out of mcp__<server>__<tool> by preprocessing
+violation if {
+	input.hook_event_name == "PreToolUse"
+	input.mcp
+	not tool_allowed
+}
+
+# An empty tool list allows every tool on the server
+tool_allowed if {
+	tools := get_servers[input.mcp.server]
+	count(tools) == 0
+}
+
+tool_allowed if {
+	some pattern in get_servers[input.mcp.server]
+	glob.match(pattern, [], input.mcp.tool)
+}
+
+get_servers := servers if {
+	servers := input.builtin_config.mcp_allowlist.servers
+} else := {}
+
+get_configured_message := msg if {
+	msg := input.builtin_config.mcp_allowlist.message
+} else := "MCP tool is not on the allowlist"
+
+get_action := action if {
+	action := input.builtin_config.mcp_allowlist.action
+} else := "deny"
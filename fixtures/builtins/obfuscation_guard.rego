@@ -0,0 +1,56 @@
+# cupcake:template
+# METADATA
+# scope: package
+# title: Obfuscation Guard - Builtin Policy
+# authors: ["Cupcake Builtins"]
+# custom:
+#   severity: MEDIUM
+#   id: BUILTIN-OBFUSCATION-GUARD
+#   routing:
+#     required_events: ["{{shell_event}}"]
+#     required_tools: ["Bash"] # cupcake:unless cursor
+package cupcake.policies.builtins.obfuscation_guard
+
+import rego.v1
+
+ask contains decision if {
+	get_action == "ask"
+	some indicator in flagged_indicators
+	decision := make_decision(indicator)
+}
+
+deny contains decision if {
+	get_action == "deny"
+	some indicator in flagged_indicators
+	decision := make_decision(indicator)
+}
+
+make_decision(indicator) := {
+	"rule_id": "BUILTIN-OBFUSCATION-GUARD",
+	"reason": concat("", [get_configured_message, " (", describe(indicator), ")"]),
+	"severity": "MEDIUM",
+}
+
+# Indicators are attached by preprocessing for commands built at runtime
+flagged_indicators contains indicator if {
+	input.hook_event_name == "{{shell_event}}"
+	input.tool_name == "Bash" # cupcake:unless cursor
+	some indicator in input.obfuscation_indicators
+	not indicator.kind in get_ignored_indicators
+}
+
+describe(indicator) := concat("", [indicator.kind, ": ", indicator.command, " → ", indicator.expanded]) if {
+	indicator.expanded
+} else := concat("", [indicator.kind, ": ", indicator.command])
+
+get_configured_message := msg if {
+	msg := input.builtin_config.obfuscation_guard.message
+} else := "Obfuscated command requires confirmation"
+
+get_action := action if {
+	action := input.builtin_config.obfuscation_guard.action
+} else := "ask"
+
+get_ignored_indicators := kinds if {
+	kinds := input.builtin_config.obfuscation_guard.ignore_indicators
+} else := []
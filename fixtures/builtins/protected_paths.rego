@@ -1,3 +1,6 @@
+# cupcake:template
+# cupcake:map edit_event cursor = afterFileEdit; default = PreToolUse
+# cupcake:map verb cursor = deny; default = halt
 # METADATA
 # scope: package
 # title: Protected Paths - Builtin Policy
@@ -6,8 +9,12 @@
 #   severity: HIGH
 #   id: BUILTIN-PROTECTED-PATHS
 #   routing:
+# cupcake:if cursor
+#     required_events: ["afterFileEdit", "beforeShellExecution"]
+# cupcake:else
 #     required_events: ["PreToolUse"]
 #     required_tools: ["Edit", "Write", "MultiEdit", "NotebookEdit", "Bash"]
+# cupcake:end
 package cupcake.policies.builtins.protected_paths
 
 import data.cupcake.system.commands
@@ -16,13 +23,16 @@ import rego.v1
 
 # Block WRITE operations on protected paths (but allow reads)
 # For regular tools (Edit, Write, NotebookEdit)
-halt contains decision if {
-	input.hook_event_name == "PreToolUse"
+# Cursor only reports edits after they're made, so they're denied there # cupcake:if cursor
+{{verb}} contains decision if {
+	input.hook_event_name == "{{edit_event}}"
 
+	# cupcake:unless cursor
 	# Check for SINGLE-file writing tools only
 	single_file_tools := {"Edit", "Write", "NotebookEdit"}
 	input.tool_name in single_file_tools
 
+	# cupcake:end
 	# Get the file path from tool input
 	# TOB-4 fix: Use canonical path (always provided by Rust preprocessing)
 	file_path := input.resolved_file_path
@@ -41,9 +51,10 @@ halt contains decision if {
 	}
 }
 
+# cupcake:unless cursor
 # Block WRITE operations on protected paths - MultiEdit special handling
 # MultiEdit has an array of edits, each with their own resolved_file_path
-halt contains decision if {
+{{verb}} contains decision if {
 	input.hook_event_name == "PreToolUse"
 	input.tool_name == "MultiEdit"
 
@@ -66,13 +77,13 @@ halt contains decision if {
 }
 
 # Block ALL Bash commands that reference protected paths UNLESS whitelisted
-halt contains decision if {
+{{verb}} contains decision if {
 	input.hook_event_name == "PreToolUse"
 	input.tool_name == "Bash"
 
-	# Get the command
-	command := input.tool_input.command
-	lower_cmd := lower(command)
+	# Check each logical command separately so a whitelisted read can't
+	# mask the rest of a chain (e.g. `cat README && rm -rf .cupcake`)
+	some lower_cmd in command_segments
 
 	# Check if any protected path is mentioned in the command
 	some protected_path in get_protected_paths
@@ -90,12 +101,13 @@ halt contains decision if {
 	}
 }
 
+# cupcake:end
 # Block destructive commands that would affect a parent directory containing protected paths
 # This catches cases like `rm -rf /home/user/*` when `/home/user/.cupcake/` is protected
 # The `affected_parent_directories` field is populated by Rust preprocessing for destructive commands
-halt contains decision if {
-	input.hook_event_name == "PreToolUse"
-	input.tool_name == "Bash"
+{{verb}} contains decision if {
+	input.hook_event_name == "{{shell_event}}"
+	input.tool_name == "Bash" # cupcake:unless cursor
 
 	# Get affected parent directories from preprocessing
 	# This is populated for commands like rm -rf, chmod -R, etc.
@@ -118,11 +130,11 @@ halt contains decision if {
 
 # Block interpreter inline scripts (-c/-e flags) that mention protected paths
 # This catches attacks like: python -c 'pathlib.Path("../my-favorite-file.txt").delete()'
-halt contains decision if {
-	input.hook_event_name == "PreToolUse"
-	input.tool_name == "Bash"
+{{verb}} contains decision if {
+	input.hook_event_name == "{{shell_event}}"
+	input.tool_name == "Bash" # cupcake:unless cursor
 
-	command := input.tool_input.command
+	command := {{shell_command}}
 	lower_cmd := lower(command)
 
 	# Detect inline script execution with interpreters
@@ -143,6 +155,12 @@ halt contains decision if {
 	}
 }
 
+# Individual commands split out of chains, pipelines, subshells and substitutions
+# by preprocessing. Falls back to the whole command when it could not be parsed.
+command_segments := [lower(cmd) | some cmd in input.commands] if {
+	count(input.commands) > 0
+} else := [lower({{shell_command}})]
+
 # Extract file path from tool input
 get_file_path_from_tool_input := path if {
 	path := input.tool_input.file_path
@@ -229,6 +247,18 @@ path_matches(path, pattern) if {
 	glob_match(lower(path), lower(pattern))
 }
 
+path_matches(path, pattern) if {
+	# Windows pattern (C:\dir\ or C:/dir) - preprocessing canonicalizes
+	# Windows paths to forward slashes, so compare patterns the same way
+	regex.match(`^[a-zA-Z]:[\\/]`, pattern)
+	windows_pattern := trim_suffix(lower(replace(pattern, "\\", "/")), "/")
+	windows_path_matches(lower(path), windows_pattern)
+}
+
+windows_path_matches(path, pattern) if path == pattern
+
+windows_path_matches(path, pattern) if startswith(path, concat("", [pattern, "/"]))
+
 # Simple glob matching (supports * wildcard)
 glob_match(path, pattern) if {
 	# Convert glob pattern to regex: * becomes .*
@@ -327,6 +357,13 @@ contains_protected_reference(cmd, protected_path) if {
 	contains(cmd, path_without_slash)
 }
 
+contains_protected_reference(cmd, protected_path) if {
+	# Backslash patterns against PowerShell/cmd.exe commands, which
+	# preprocessing canonicalizes to forward slashes
+	contains(protected_path, "\\")
+	contains(cmd, trim_suffix(lower(replace(protected_path, "\\", "/")), "/"))
+}
+
 # Get configured message from builtin config
 get_configured_message := msg if {
 	# Direct access to builtin config (no signal execution needed)
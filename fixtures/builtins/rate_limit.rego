@@ -0,0 +1,49 @@
+# cupcake:template
+# cupcake:map tool_events cursor = "beforeShellExecution", "beforeMCPExecution"; default = "PreToolUse"
+# METADATA
+# scope: package
+# title: Rate Limit - Builtin Policy
+# authors: ["Cupcake Builtins"]
+# custom:
+#   severity: MEDIUM
+#   id: BUILTIN-RATE-LIMIT
+#   routing:
+#     required_events: [{{tool_events}}]
+package cupcake.policies.builtins.rate_limit
+
+import rego.v1
+
+# Usage is recorded by the engine before evaluation:
+# input.builtin_config.rate_limit.usage.<limit> = {"count", "max", "per", "limit"}
+
+deny contains decision if {
+	get_action == "deny"
+	some violation in violations
+	decision := make_decision(violation)
+}
+
+ask contains decision if {
+	get_action == "ask"
+	some violation in violations
+	decision := make_decision(violation)
+}
+
+make_decision(violation) := {
+	"rule_id": "BUILTIN-RATE-LIMIT",
+	"reason": concat("", [get_configured_message, " (", violation, ")"]),
+	"severity": "MEDIUM",
+}
+
+violations contains violation if {
+	some name, usage in input.builtin_config.rate_limit.usage
+	usage.count > usage.max
+	violation := concat("", [name, " is limited to ", usage.limit])
+}
+
+get_configured_message := msg if {
+	msg := input.builtin_config.rate_limit.message
+} else := "Rate limit exceeded"
+
+get_action := action if {
+	action := input.builtin_config.rate_limit.action
+} else := "deny"
@@ -0,0 +1,106 @@
+# cupcake:template
+# METADATA
+# scope: package
+# title: Terraform Plan Gate - Builtin Policy
+# authors: ["Cupcake Builtins"]
+# custom:
+#   severity: HIGH
+#   id: BUILTIN-TERRAFORM-PLAN-GATE
+#   routing:
+#     required_events: ["{{shell_event}}"]
+#     required_tools: ["Bash"] # cupcake:unless cursor
+package cupcake.policies.builtins.terraform_plan_gate
+
+import rego.v1
+
+import data.cupcake.system.commands
+
+terraform_clis := {"terraform", "tofu"}
+
+# Flags whose next token is a value, not a plan file
+value_flags := {"-var", "-var-file", "-target", "-replace", "-state", "-state-out", "-backup", "-parallelism", "-lock-timeout"}
+
+command_separators := {"&&", "||", ";", "|"}
+
+deny contains decision if {
+	some violation in violations
+	decision := {
+		"rule_id": "BUILTIN-TERRAFORM-PLAN-GATE",
+		"reason": concat("", [get_configured_message, ": ", violation]),
+		"severity": "HIGH",
+	}
+}
+
+terraform_command := cmd if {
+	input.hook_event_name == "{{shell_event}}"
+	input.tool_name == "Bash" # cupcake:unless cursor
+	cmd := lower({{shell_command}})
+	commands.has_dangerous_verb(cmd, terraform_clis)
+}
+
+# Applying without reviewing a plan first
+violations contains msg if {
+	is_apply(terraform_command)
+	not applies_saved_plan(terraform_command)
+	not plan_is_fresh
+	msg := concat("", ["run `terraform plan -out=", get_plan_file, "` and review it before applying"])
+}
+
+violations contains "-auto-approve skips plan review" if {
+	not get_allow_auto_approve
+	is_apply(terraform_command)
+	regex.match(`(^|\s)--?auto-approve(=true)?(\s|$)`, terraform_command)
+}
+
+violations contains "destroying infrastructure or state is not permitted" if {
+	not get_allow_destroy
+	destroys_state(terraform_command)
+}
+
+is_apply(cmd) if commands.has_verb(cmd, "apply")
+
+# `terraform apply <planfile>` applies exactly what was planned
+applies_saved_plan(cmd) if {
+	tokens := [t | some t in split(cmd, " "); t != ""]
+	some i, token in tokens
+	token == "apply"
+	some j, arg in tokens
+	j > i
+	not separator_between(tokens, i, j)
+	not startswith(arg, "-")
+	not contains(arg, "=")
+	not tokens[j - 1] in value_flags
+}
+
+separator_between(tokens, start, end) if {
+	some k, token in tokens
+	k > start
+	k <= end
+	token in command_separators
+}
+
+destroys_state(cmd) if commands.has_verb(cmd, "destroy")
+
+destroys_state(cmd) if {
+	is_apply(cmd)
+	regex.match(`(^|\s)--?destroy(=true)?(\s|$)`, cmd)
+}
+
+destroys_state(cmd) if regex.match(`(^|\s)state\s+(rm|push)(\s|$)`, cmd)
+
+destroys_state(cmd) if regex.match(`(^|\s)workspace\s+delete(\s|$)`, cmd)
+
+# Reported by the __builtin_terraform_plan_gate_status signal
+plan_is_fresh if input.signals.__builtin_terraform_plan_gate_status.fresh == true
+
+get_configured_message := msg if {
+	msg := input.builtin_config.terraform_plan_gate.message
+} else := "Terraform change blocked"
+
+get_plan_file := file if {
+	file := input.builtin_config.terraform_plan_gate.plan_file
+} else := "tfplan"
+
+get_allow_auto_approve if input.builtin_config.terraform_plan_gate.allow_auto_approve == true
+
+get_allow_destroy if input.builtin_config.terraform_plan_gate.allow_destroy == true
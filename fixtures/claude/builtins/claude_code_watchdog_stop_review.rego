@@ -0,0 +1,51 @@
+# METADATA
+# scope: package
+# title: Watchdog Stop Review - Builtin Policy
+# authors: ["Cupcake Builtins"]
+# custom:
+#   severity: MEDIUM
+#   id: BUILTIN-WATCHDOG-STOP-REVIEW
+#   routing:
+#     required_events: ["Stop", "SubagentStop"]
+#     required_watchdog: true
+package cupcake.policies.builtins.claude_code_watchdog_stop_review
+
+import rego.v1
+
+# Keep the agent working when the watchdog judges the task unfinished.
+# The watchdog reads the end of the transcript on Stop/SubagentStop; its
+# concerns are the items still missing
+block contains decision if {
+	input.hook_event_name in {"Stop", "SubagentStop"}
+
+	# Claude Code sets this once a Stop hook has already sent the agent back -
+	# review once, never loop
+	not input.stop_hook_active
+
+	verdict := input.watchdog.verdict
+	verdict.allow == false
+	not "watchdog_error" in verdict.concerns
+	verdict.confidence >= get_min_confidence
+
+	decision := {
+		"rule_id": "BUILTIN-WATCHDOG-STOP-REVIEW",
+		"reason": concat("", [get_configured_message, " ", verdict.reasoning, missing_items(verdict), next_steps(verdict)]),
+		"severity": "MEDIUM",
+	}
+}
+
+missing_items(verdict) := concat("", ["\n\nStill missing:\n- ", concat("\n- ", verdict.concerns)]) if {
+	count(verdict.concerns) > 0
+} else := ""
+
+next_steps(verdict) := concat("", ["\n\nNext steps:\n- ", concat("\n- ", verdict.suggestions)]) if {
+	count(verdict.suggestions) > 0
+} else := ""
+
+get_min_confidence := c if {
+	c := input.builtin_config.claude_code_watchdog_stop_review.min_confidence
+} else := 0.7
+
+get_configured_message := msg if {
+	msg := input.builtin_config.claude_code_watchdog_stop_review.message
+} else := "The task doesn't look finished yet."
@@ -46,6 +46,25 @@ halt contains decision if {
 	}
 }
 
+# Block Glob/Grep searches rooted inside a protected path
+# resolved_search_root is the canonical directory being searched (the search path
+# plus the Glob pattern's literal prefix), so symlinked directories are resolved
+halt contains decision if {
+	input.hook_event_name == "PreToolUse"
+	input.tool_name in {"Glob", "Grep"}
+
+	search_root := input.resolved_search_root
+	is_protected_path(search_root)
+
+	message := get_configured_message
+
+	decision := {
+		"rule_id": "BUILTIN-RULEBOOK-SECURITY",
+		"reason": concat("", [message, " (blocked search of ", search_root, ")"]),
+		"severity": "HIGH",
+	}
+}
+
 # Block Bash commands that reference any protected path
 # Total lockdown - NO whitelist (unlike protected_paths builtin)
 halt contains decision if {
@@ -202,10 +221,12 @@ get_file_path_from_tool_input := path if {
 # - Grep's 'path' field now uses canonical paths (closes symlink bypass)
 # - Glob's 'pattern' field still uses raw patterns (can't be canonicalized)
 #
-# TODO: Known Glob limitations (complex pattern parsing required):
-# - Glob(pattern="backup/**/*.rego") where "backup" is symlink to .cupcake
+# Glob(pattern="backup/**/*.rego") where "backup" is a symlink to .cupcake is
+# caught through input.resolved_search_root (see the search rule above).
+#
+# TODO: Known Glob limitation:
 # - Glob(pattern="**/*.rego") searches symlinks without .cupcake in pattern
-# - Requires pattern parsing before file expansion to fully address
+# - Requires file expansion to fully address
 get_file_path_with_preprocessing_fallback := path if {
 	# For Glob only, use raw pattern since it can't be canonicalized (e.g., "**/*.rs")
 	# Grep's 'path' field CAN be canonicalized, so it goes through TOB-4 defense